@@ -0,0 +1,574 @@
+// Safe Rust interface to the simplifier, for use as a library rather than through the C ABI.
+//
+// A `Context` owns its arena, and expressions are referred to by `Expr` handles that remember which context created them.
+// Handles are checked on every call, so mixing up contexts or widths is reported as an `Error` instead of a panic
+// or silently corrupted result.
+//
+// The `extern "C"` entry points hand out raw arena indices into a context owned by the caller, so they cannot use the
+// handles of this facade. Instead, both layers go through the checked helpers below, which validate widths, variable
+// counts and e-graph unions on raw indices. The FFI only adds its own pointer and handle checks on top of them.
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use ahash::AHashMap;
+use egg::{Id, StopReason};
+
+use crate::{
+    ast_parser::{parse_ast, ParseError},
+    eval::Evaluator,
+    printer::PrintOptions,
+    sexpr,
+    simple_ast::{
+        self, add_to_egraph, collect_var_indices, extract_from_egraph, get_modulo_mask,
+        minimize_truth_table, order_vars_alphabetically, recursive_simplify, run_egraph, Arena,
+        AstClass, AstIdx, EEGraph, INodeUtil, MbaAnalysis, SimpleAst, MAX_WIDTH,
+    },
+    truth_table_database::{TruthTable, TruthTableDatabase},
+};
+
+#[derive(Debug)]
+pub enum Error {
+    // The handle was created by a different context or e-graph.
+    ForeignHandle,
+    Parse(ParseError),
+    InvalidWidth(u32),
+    WidthMismatch { lhs: u8, rhs: u8 },
+    // No value was given for the named variable.
+    MissingInput(String),
+    InvalidVariableCount(usize),
+    InvalidTruthTable { expected: usize, found: usize },
+    Io(std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ForeignHandle => write!(f, "handle belongs to a different context"),
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::InvalidWidth(w) => write!(f, "invalid width {}, expected 1 to {}", w, MAX_WIDTH),
+            Error::WidthMismatch { lhs, rhs } => write!(f, "width mismatch: i{} and i{}", lhs, rhs),
+            Error::MissingInput(name) => write!(f, "no value given for variable `{}`", name),
+            Error::InvalidVariableCount(n) => {
+                write!(f, "invalid variable count {}, expected 1 to {}", n, MAX_ANF_VARS)
+            }
+            Error::InvalidTruthTable { expected, found } => {
+                write!(f, "truth table has {} entries, expected {}", found, expected)
+            }
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+// The largest number of variables `minimize_anf` accepts.
+pub const MAX_ANF_VARS: usize = 16;
+
+// Every context and e-graph gets a unique id, which is stamped onto the handles it gives out.
+static NEXT_OWNER_ID: AtomicU64 = AtomicU64::new(1);
+
+fn next_owner_id() -> u64 {
+    NEXT_OWNER_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+pub(crate) fn check_width(width: u8) -> Result<u8> {
+    if width == 0 || width > MAX_WIDTH {
+        return Err(Error::InvalidWidth(width as u32));
+    }
+    Ok(width)
+}
+
+pub(crate) fn check_same_width(ctx: &simple_ast::Context, a: AstIdx, b: AstIdx) -> Result<()> {
+    let (lhs, rhs) = (ctx.arena.get_width(a), ctx.arena.get_width(b));
+    if lhs != rhs {
+        return Err(Error::WidthMismatch { lhs, rhs });
+    }
+    Ok(())
+}
+
+pub(crate) fn check_anf_variable_count(count: usize) -> Result<()> {
+    if count == 0 || count > MAX_ANF_VARS {
+        return Err(Error::InvalidVariableCount(count));
+    }
+    Ok(())
+}
+
+// The variables of a truth table must all have the same width.
+pub(crate) fn check_anf_variables(ctx: &simple_ast::Context, vars: &[AstIdx]) -> Result<()> {
+    check_anf_variable_count(vars.len())?;
+    for pair in vars.windows(2) {
+        check_same_width(ctx, pair[0], pair[1])?;
+    }
+    Ok(())
+}
+
+// Returns true if the classes were not already equivalent. Only classes of the same width can be merged.
+pub(crate) fn union_classes(egraph: &mut EEGraph, a: Id, b: Id) -> Result<bool> {
+    let (lhs, rhs) = (egraph[a].data.width, egraph[b].data.width);
+    if lhs != rhs {
+        return Err(Error::WidthMismatch { lhs, rhs });
+    }
+    Ok(egraph.union(a, b))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Expr {
+    owner: u64,
+    idx: AstIdx,
+}
+
+pub struct Context {
+    inner: simple_ast::Context,
+    id: u64,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Context {
+            inner: simple_ast::Context { arena: Arena::new() },
+            id: next_owner_id(),
+        }
+    }
+
+    fn wrap(&self, idx: AstIdx) -> Expr {
+        Expr { owner: self.id, idx }
+    }
+
+    fn unwrap(&self, e: Expr) -> Result<AstIdx> {
+        if e.owner != self.id {
+            return Err(Error::ForeignHandle);
+        }
+        Ok(e.idx)
+    }
+
+    fn unwrap_pair(&self, a: Expr, b: Expr) -> Result<(AstIdx, AstIdx)> {
+        let (a, b) = (self.unwrap(a)?, self.unwrap(b)?);
+        check_same_width(&self.inner, a, b)?;
+        Ok((a, b))
+    }
+
+    // Parse an expression in the syntax accepted by `ast_parser`. Untyped variables get `default_width`.
+    pub fn parse(&mut self, input: &str, default_width: u8) -> Result<Expr> {
+        check_width(default_width)?;
+        let idx = parse_ast(&mut self.inner, input, default_width)?;
+        Ok(self.wrap(idx))
+    }
+
+    // Parse every top level s-expression in the input, e.g. a corpus file with one expression per line.
+    pub fn parse_sexprs(&mut self, input: &str, default_width: u8) -> Result<Vec<Expr>> {
+        check_width(default_width)?;
+        let roots = sexpr::parse_sexprs(&mut self.inner, input, default_width)?;
+        Ok(roots.into_iter().map(|idx| self.wrap(idx)).collect())
+    }
+
+    pub fn symbol(&mut self, name: &str, width: u8) -> Result<Expr> {
+        check_width(width)?;
+        let idx = self.inner.arena.symbol_with_name(name.to_owned(), width);
+        Ok(self.wrap(idx))
+    }
+
+    // Bits of `c` above `width` are discarded.
+    pub fn constant(&mut self, c: u128, width: u8) -> Result<Expr> {
+        check_width(width)?;
+        let idx = self.inner.arena.constant(c & get_modulo_mask(width), width);
+        Ok(self.wrap(idx))
+    }
+
+    pub fn add(&mut self, a: Expr, b: Expr) -> Result<Expr> {
+        let (a, b) = self.unwrap_pair(a, b)?;
+        let idx = self.inner.arena.add(a, b);
+        Ok(self.wrap(idx))
+    }
+
+    pub fn mul(&mut self, a: Expr, b: Expr) -> Result<Expr> {
+        let (a, b) = self.unwrap_pair(a, b)?;
+        let idx = self.inner.arena.mul(a, b);
+        Ok(self.wrap(idx))
+    }
+
+    pub fn and(&mut self, a: Expr, b: Expr) -> Result<Expr> {
+        let (a, b) = self.unwrap_pair(a, b)?;
+        let idx = self.inner.arena.and(a, b);
+        Ok(self.wrap(idx))
+    }
+
+    pub fn or(&mut self, a: Expr, b: Expr) -> Result<Expr> {
+        let (a, b) = self.unwrap_pair(a, b)?;
+        let idx = self.inner.arena.or(a, b);
+        Ok(self.wrap(idx))
+    }
+
+    pub fn xor(&mut self, a: Expr, b: Expr) -> Result<Expr> {
+        let (a, b) = self.unwrap_pair(a, b)?;
+        let idx = self.inner.arena.xor(a, b);
+        Ok(self.wrap(idx))
+    }
+
+    // Bitwise negation.
+    pub fn neg(&mut self, a: Expr) -> Result<Expr> {
+        let a = self.unwrap(a)?;
+        let idx = self.inner.arena.neg(a);
+        Ok(self.wrap(idx))
+    }
+
+    pub fn width(&self, e: Expr) -> Result<u8> {
+        Ok(self.inner.arena.get_width(self.unwrap(e)?))
+    }
+
+    // The size of `e` as a tree, i.e. shared nodes are counted once per use.
+    pub fn cost(&self, e: Expr) -> Result<u32> {
+        Ok(self.inner.arena.get_cost(self.unwrap(e)?))
+    }
+
+    pub fn class(&self, e: Expr) -> Result<AstClass> {
+        Ok(self.inner.arena.get_class(self.unwrap(e)?))
+    }
+
+    // The variables of `e`, in alphabetical order.
+    pub fn variables(&self, e: Expr) -> Result<Vec<Expr>> {
+        let idx = self.unwrap(e)?;
+        let mut set = HashSet::new();
+        collect_var_indices(&self.inner, idx, &mut set);
+        let mut vars = Vec::new();
+        order_vars_alphabetically(&self.inner, &set, &mut vars);
+        Ok(vars.into_iter().map(|v| self.wrap(v)).collect())
+    }
+
+    pub fn variable_name(&self, e: Expr) -> Result<Option<String>> {
+        match self.inner.arena.get_node(self.unwrap(e)?) {
+            SimpleAst::Symbol { id, .. } => Ok(Some(self.inner.arena.get_symbol_name(*id))),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn to_string(&self, e: Expr) -> Result<String> {
+        self.print_with(e, &PrintOptions::default())
+    }
+
+    pub fn print_with(&self, e: Expr, options: &PrintOptions) -> Result<String> {
+        Ok(self.inner.print_with(self.unwrap(e)?, options))
+    }
+
+    // An SMT-LIB2 script which is unsatisfiable if and only if `before` and `after` are equivalent.
+    pub fn smtlib_equivalence_query(&self, before: Expr, after: Expr) -> Result<String> {
+        let (before, after) = self.unwrap_pair(before, after)?;
+        Ok(self.inner.smtlib_equivalence_query(before, after))
+    }
+
+    pub fn simplify(&mut self, e: Expr) -> Result<Expr> {
+        let idx = self.unwrap(e)?;
+        let idx = recursive_simplify(&mut self.inner, idx);
+        Ok(self.wrap(idx))
+    }
+
+    // Evaluate `e` with the given variable assignment. Every variable of `e` must be assigned, and inputs are truncated to
+    // the width of their variable.
+    pub fn eval(&self, e: Expr, inputs: &[(Expr, u128)]) -> Result<u128> {
+        let root = self.unwrap(e)?;
+        let mut vars = Vec::with_capacity(inputs.len());
+        let mut values = Vec::with_capacity(inputs.len());
+        for &(var, value) in inputs {
+            let idx = self.unwrap(var)?;
+            vars.push(idx);
+            values.push(value & get_modulo_mask(self.inner.arena.get_width(idx)));
+        }
+
+        Ok(self.evaluator(root, &vars)?.eval(&values))
+    }
+
+    // Compute the truth table of `e` in the layout expected by `minimize_anf`, using the lowest bit of the result.
+    // For a purely bitwise expression, this fully describes its behavior.
+    pub fn truth_table(&self, e: Expr, vars: &[Expr]) -> Result<Vec<bool>> {
+        if vars.len() > MAX_ANF_VARS {
+            return Err(Error::InvalidVariableCount(vars.len()));
+        }
+        let root = self.unwrap(e)?;
+        let vars = vars
+            .iter()
+            .map(|v| self.unwrap(*v))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut evaluator = self.evaluator(root, &vars)?;
+        let mut inputs = vec![0; vars.len()];
+        let table = (0..1usize << vars.len())
+            .map(|i| {
+                for (j, input) in inputs.iter_mut().enumerate() {
+                    *input = (i >> j) as u128 & 1;
+                }
+                evaluator.eval(&inputs) & 1 != 0
+            })
+            .collect();
+        Ok(table)
+    }
+
+    fn evaluator(&self, root: AstIdx, vars: &[AstIdx]) -> Result<Evaluator> {
+        let mut set = HashSet::new();
+        collect_var_indices(&self.inner, root, &mut set);
+        if let Some(missing) = set.iter().find(|v| !vars.contains(v)) {
+            let name = match self.inner.arena.get_node(*missing) {
+                SimpleAst::Symbol { id, .. } => self.inner.arena.get_symbol_name(*id),
+                _ => unreachable!(),
+            };
+            return Err(Error::MissingInput(name));
+        }
+
+        Ok(Evaluator::new(&self.inner, root, vars))
+    }
+
+    // Find a minimal boolean expression over `vars` that matches `truth_table`, where entry `i` is the result when each
+    // variable `vars[j]` is set to bit `j` of `i`.
+    pub fn minimize_anf(
+        &mut self,
+        tables: &TruthTables,
+        vars: &[Expr],
+        truth_table: &[bool],
+    ) -> Result<Expr> {
+        let vars = vars
+            .iter()
+            .map(|v| self.unwrap(*v))
+            .collect::<Result<Vec<_>>>()?;
+        check_anf_variables(&self.inner, &vars)?;
+        let expected = 1usize << vars.len();
+        if truth_table.len() != expected {
+            return Err(Error::InvalidTruthTable {
+                expected,
+                found: truth_table.len(),
+            });
+        }
+
+        let mut words = vec![0u64; expected.div_ceil(64)];
+        for (i, bit) in truth_table.iter().enumerate() {
+            words[i / 64] |= (*bit as u64) << (i % 64);
+        }
+        let table = TruthTable {
+            num_vars: vars.len() as u32,
+            arr: words.as_mut_ptr(),
+        };
+
+        let idx = minimize_truth_table(
+            &mut self.inner,
+            &tables.db,
+            &table,
+            &vars,
+            std::ptr::null_mut(),
+        );
+        Ok(self.wrap(idx))
+    }
+}
+
+// Precomputed optimal boolean functions of two, three, and four variables, used by `Context::minimize_anf`.
+pub struct TruthTables {
+    db: TruthTableDatabase,
+}
+
+impl TruthTables {
+    // Load the tables from a directory containing the `{n}variable_truthtable.bc` files.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self> {
+        Ok(TruthTables {
+            db: TruthTableDatabase::load(dir.as_ref())?,
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClassId {
+    owner: u64,
+    id: Id,
+}
+
+pub struct EGraph {
+    inner: EEGraph,
+    id: u64,
+    // The context whose expressions were added, since extraction must build the result in the same context.
+    ctx: Option<u64>,
+}
+
+impl Default for EGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EGraph {
+    pub fn new() -> Self {
+        EGraph {
+            inner: EEGraph::new(MbaAnalysis::default()),
+            id: next_owner_id(),
+            ctx: None,
+        }
+    }
+
+    fn unwrap(&self, class: ClassId) -> Result<Id> {
+        if class.owner != self.id {
+            return Err(Error::ForeignHandle);
+        }
+        Ok(class.id)
+    }
+
+    // The e-graph is tied to the first context it is used with.
+    fn check_context(&mut self, ctx: &Context) -> Result<()> {
+        match self.ctx {
+            Some(id) if id != ctx.id => Err(Error::ForeignHandle),
+            _ => {
+                self.ctx = Some(ctx.id);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn add(&mut self, ctx: &Context, e: Expr) -> Result<ClassId> {
+        let idx = ctx.unwrap(e)?;
+        self.check_context(ctx)?;
+        let id = add_to_egraph(&ctx.inner, &mut self.inner, idx, &mut AHashMap::new());
+        Ok(ClassId { owner: self.id, id })
+    }
+
+    // Returns true if the classes were not already equivalent. Call `rebuild` before searching or extracting.
+    pub fn union(&mut self, a: ClassId, b: ClassId) -> Result<bool> {
+        let (a, b) = (self.unwrap(a)?, self.unwrap(b)?);
+        union_classes(&mut self.inner, a, b)
+    }
+
+    pub fn rebuild(&mut self) {
+        self.inner.rebuild();
+    }
+
+    // Run equality saturation with the generated rules until either limit is reached.
+    pub fn run(&mut self, time_limit: Duration, iter_limit: usize) -> Option<StopReason> {
+        run_egraph(&mut self.inner, time_limit, iter_limit)
+    }
+
+    // Extract the cheapest member of `class` into `ctx`.
+    pub fn extract(&self, ctx: &mut Context, class: ClassId) -> Result<Expr> {
+        let id = self.unwrap(class)?;
+        if self.ctx.is_some_and(|owner| owner != ctx.id) {
+            return Err(Error::ForeignHandle);
+        }
+        let idx = extract_from_egraph(&mut ctx.inner, &self.inner, id);
+        Ok(ctx.wrap(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_simplify_eval() {
+        let mut ctx = Context::new();
+        let e = ctx.parse("(x & y) + (x | y)", 64).unwrap();
+        let simplified = ctx.simplify(e).unwrap();
+        assert_eq!(ctx.to_string(simplified).unwrap(), "x + y");
+
+        let vars = ctx.variables(e).unwrap();
+        assert_eq!(vars.len(), 2);
+        assert_eq!(ctx.variable_name(vars[0]).unwrap().as_deref(), Some("x"));
+        let inputs = [(vars[0], 5), (vars[1], u128::MAX)];
+        assert_eq!(ctx.eval(e, &inputs).unwrap(), 4);
+        assert_eq!(ctx.eval(simplified, &inputs).unwrap(), 4);
+    }
+
+    #[test]
+    fn errors() {
+        let mut ctx = Context::new();
+        let mut other = Context::new();
+        let x = ctx.symbol("x", 8).unwrap();
+        let y = ctx.symbol("y", 16).unwrap();
+        let z = other.symbol("z", 8).unwrap();
+
+        assert!(matches!(ctx.add(x, z), Err(Error::ForeignHandle)));
+        assert!(matches!(
+            ctx.add(x, y),
+            Err(Error::WidthMismatch { lhs: 8, rhs: 16 })
+        ));
+        assert!(matches!(ctx.symbol("w", 0), Err(Error::InvalidWidth(0))));
+        assert!(matches!(ctx.parse("x +", 8), Err(Error::Parse(_))));
+
+        let e = ctx.parse("x * 3", 8).unwrap();
+        assert!(matches!(ctx.eval(e, &[]), Err(Error::MissingInput(name)) if name == "x"));
+        assert_eq!(ctx.eval(e, &[(x, 0x1ff)]).unwrap(), 0xfd);
+
+        let roots = ctx.parse_sexprs("(+ x 1)\n(* x x)", 8).unwrap();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(ctx.cost(roots[1]).unwrap(), 3);
+
+        let c = ctx.constant(0x1ff, 8).unwrap();
+        assert_eq!(ctx.eval(c, &[]).unwrap(), 0xff);
+
+        assert!(matches!(
+            ctx.smtlib_equivalence_query(x, y),
+            Err(Error::WidthMismatch { lhs: 8, rhs: 16 })
+        ));
+        let query = ctx.smtlib_equivalence_query(x, c).unwrap();
+        assert!(query.ends_with("(check-sat)\n"));
+    }
+
+    #[test]
+    fn minimize_anf() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/Minimization/TruthTables");
+        assert!(matches!(
+            TruthTables::load("does/not/exist"),
+            Err(Error::Io(_))
+        ));
+        let tables = TruthTables::load(dir).unwrap();
+
+        let mut ctx = Context::new();
+        let x = ctx.symbol("x", 64).unwrap();
+        let y = ctx.symbol("y", 64).unwrap();
+        assert!(matches!(
+            ctx.minimize_anf(&tables, &[x, y], &[false, true]),
+            Err(Error::InvalidTruthTable {
+                expected: 4,
+                found: 2
+            })
+        ));
+
+        let e = ctx.parse("(x | y) ^ (x & y)", 64).unwrap();
+        assert_eq!(ctx.class(e).unwrap(), AstClass::Bitwise);
+        let table = ctx.truth_table(e, &[x, y]).unwrap();
+        assert_eq!(table, [false, true, true, false]);
+        let minimized = ctx.minimize_anf(&tables, &[x, y], &table).unwrap();
+        assert_eq!(ctx.to_string(minimized).unwrap(), "x ^ y");
+
+        // Check the result against the truth table for a handful of functions, including ones with a constant offset.
+        for bits in [0b0110u8, 0b1000, 0b1110, 0b1001, 0b0111, 0b1111] {
+            let table: Vec<bool> = (0..4).map(|i| bits >> i & 1 != 0).collect();
+            let e = ctx.minimize_anf(&tables, &[x, y], &table).unwrap();
+            for i in 0..4 {
+                let inputs = [(x, (i & 1) as u128), (y, (i >> 1) as u128)];
+                assert_eq!(ctx.eval(e, &inputs).unwrap() & 1, table[i] as u128);
+            }
+        }
+    }
+}
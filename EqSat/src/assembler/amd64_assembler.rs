@@ -39,8 +39,24 @@ pub trait IAmd64Assembler {
 
     fn shr_reg_cl(&mut self, reg: Register);
 
+    fn sar_reg_cl(&mut self, reg: Register);
+
+    fn rol_reg_cl(&mut self, reg: Register);
+
+    fn ror_reg_cl(&mut self, reg: Register);
+
+    fn popcnt_reg_reg(&mut self, reg1: Register, reg2: Register);
+
+    fn lzcnt_reg_reg(&mut self, reg1: Register, reg2: Register);
+
+    fn tzcnt_reg_reg(&mut self, reg1: Register, reg2: Register);
+
+    fn shl_reg_imm8(&mut self, reg: Register, imm8: u8);
+
     fn shr_reg_imm8(&mut self, reg: Register, imm8: u8);
 
+    fn sar_reg_imm8(&mut self, reg: Register, imm8: u8);
+
     fn call_reg(&mut self, reg: Register);
 
     fn ret(&mut self);
@@ -80,6 +80,9 @@ impl Amd64AssemblerDifferentialTester {
         self.diff("NotReg", |asm| asm.not_reg(reg))?;
         self.diff("ShlRegCl", |asm| asm.shl_reg_cl(reg))?;
         self.diff("ShrRegCl", |asm| asm.shr_reg_cl(reg))?;
+        self.diff("SarRegCl", |asm| asm.sar_reg_cl(reg))?;
+        self.diff("RolRegCl", |asm| asm.rol_reg_cl(reg))?;
+        self.diff("RorRegCl", |asm| asm.ror_reg_cl(reg))?;
         self.diff("CallReg", |asm| asm.call_reg(reg))?;
 
         // Test reg, constant instructions
@@ -90,7 +93,9 @@ impl Amd64AssemblerDifferentialTester {
             self.diff("AddRegImm32", |asm| asm.add_reg_imm32(reg, c as u32))?;
             self.diff("SubRegImm32", |asm| asm.sub_reg_imm32(reg, c as u32))?;
             self.diff("AndRegImm32", |asm| asm.and_reg_imm32(reg, c as u32))?;
+            self.diff("ShlRegImm8", |asm| asm.shl_reg_imm8(reg, c as u8))?;
             self.diff("ShrRegImm8", |asm| asm.shr_reg_imm8(reg, c as u8))?;
+            self.diff("SarRegImm8", |asm| asm.sar_reg_imm8(reg, c as u8))?;
 
             if reg != Register::RSP {
                 self.diff("PushMem64", |asm| asm.push_mem64(reg, c as i32))?;
@@ -118,6 +123,12 @@ impl Amd64AssemblerDifferentialTester {
         self.diff("XorRegReg", |asm| asm.xor_reg_reg(reg2, reg1))?;
         self.diff("ImulRegReg", |asm| asm.imul_reg_reg(reg1, reg2))?;
         self.diff("ImulRegReg", |asm| asm.imul_reg_reg(reg2, reg1))?;
+        self.diff("PopcntRegReg", |asm| asm.popcnt_reg_reg(reg1, reg2))?;
+        self.diff("PopcntRegReg", |asm| asm.popcnt_reg_reg(reg2, reg1))?;
+        self.diff("LzcntRegReg", |asm| asm.lzcnt_reg_reg(reg1, reg2))?;
+        self.diff("LzcntRegReg", |asm| asm.lzcnt_reg_reg(reg2, reg1))?;
+        self.diff("TzcntRegReg", |asm| asm.tzcnt_reg_reg(reg1, reg2))?;
+        self.diff("TzcntRegReg", |asm| asm.tzcnt_reg_reg(reg2, reg1))?;
 
         // Test reg, reg, constant instructions
         for _ in 0..100 {
@@ -136,19 +136,48 @@ impl FastAmd64Assembler {
         self.emit_buffer(&arr);
     }
 
-    pub fn shift_reg_cl(&mut self, shl: bool, reg: Register) {
+    // `ext` is the opcode extension stored in modrm.reg: 0 for rol, 1 for ror, 4 for shl, 5 for shr, 7 for sar.
+    pub fn shift_reg_cl(&mut self, ext: u8, reg: Register) {
         let mut rex = 0x48;
         if self.is_extended(reg) {
             rex |= 0x01;
         }
 
         let opcode = 0xD3;
-        let m1 = if shl { 0x04 } else { 0x05 };
-        let modrm = 0xC0 | (m1 << 3) | (self.get_register_code(reg) & 0x07);
+        let modrm = 0xC0 | (ext << 3) | (self.get_register_code(reg) & 0x07);
 
         self.emit_bytes(&[rex, opcode, modrm]);
     }
 
+    // `opcode` is the second byte of the F3 0F xx encoding: 0xB8 for popcnt, 0xBC for tzcnt, 0xBD for lzcnt.
+    pub fn bit_count_reg_reg(&mut self, opcode: u8, reg1: Register, reg2: Register) {
+        let mut rex = 0x48;
+        if self.is_extended(reg1) {
+            rex |= 0x04;
+        }
+        if self.is_extended(reg2) {
+            rex |= 0x01;
+        }
+
+        let modrm = 0xC0
+            | ((self.get_register_code(reg1) & 0x07) << 3)
+            | (self.get_register_code(reg2) & 0x07);
+
+        self.emit_bytes(&[0xF3, rex, 0x0F, opcode, modrm]);
+    }
+
+    pub fn shift_reg_imm8(&mut self, ext: u8, reg: Register, imm8: u8) {
+        let mut rex = 0x48;
+        if self.is_extended(reg) {
+            rex |= 0x01;
+        }
+
+        let opcode = 0xC1;
+        let modrm = 0xC0 | (ext << 3) | (self.get_register_code(reg) & 0x07);
+
+        self.emit_bytes(&[rex, opcode, modrm, imm8]);
+    }
+
     #[inline(always)]
     fn mov_reg_mem64_template(&mut self, dst_reg: Register, base_reg: Register, offset: i32) {
         let p = &mut [0u8; 8];
@@ -401,23 +430,47 @@ impl IAmd64Assembler for FastAmd64Assembler {
     }
 
     fn shl_reg_cl(&mut self, reg: Register) {
-        self.shift_reg_cl(true, reg);
+        self.shift_reg_cl(0x04, reg);
     }
 
     fn shr_reg_cl(&mut self, reg: Register) {
-        self.shift_reg_cl(false, reg);
+        self.shift_reg_cl(0x05, reg);
     }
 
-    fn shr_reg_imm8(&mut self, reg: Register, imm8: u8) {
-        let mut rex = 0x48;
-        if self.is_extended(reg) {
-            rex |= 0x01;
-        }
+    fn sar_reg_cl(&mut self, reg: Register) {
+        self.shift_reg_cl(0x07, reg);
+    }
 
-        let opcode = 0xC1;
-        let modrm = 0xC0 | (0x05 << 3) | (self.get_register_code(reg) & 0x07);
+    fn rol_reg_cl(&mut self, reg: Register) {
+        self.shift_reg_cl(0x00, reg);
+    }
 
-        self.emit_bytes(&[rex, opcode, modrm, imm8]);
+    fn ror_reg_cl(&mut self, reg: Register) {
+        self.shift_reg_cl(0x01, reg);
+    }
+
+    fn popcnt_reg_reg(&mut self, reg1: Register, reg2: Register) {
+        self.bit_count_reg_reg(0xB8, reg1, reg2);
+    }
+
+    fn lzcnt_reg_reg(&mut self, reg1: Register, reg2: Register) {
+        self.bit_count_reg_reg(0xBD, reg1, reg2);
+    }
+
+    fn tzcnt_reg_reg(&mut self, reg1: Register, reg2: Register) {
+        self.bit_count_reg_reg(0xBC, reg1, reg2);
+    }
+
+    fn shl_reg_imm8(&mut self, reg: Register, imm8: u8) {
+        self.shift_reg_imm8(0x04, reg, imm8);
+    }
+
+    fn shr_reg_imm8(&mut self, reg: Register, imm8: u8) {
+        self.shift_reg_imm8(0x05, reg, imm8);
+    }
+
+    fn sar_reg_imm8(&mut self, reg: Register, imm8: u8) {
+        self.shift_reg_imm8(0x07, reg, imm8);
     }
 
     fn call_reg(&mut self, reg: Register) {
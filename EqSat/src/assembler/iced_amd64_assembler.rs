@@ -144,10 +144,48 @@ impl IAmd64Assembler for IcedAmd64Assembler {
         self.assembler.shr(Self::conv(reg), cl).unwrap();
     }
 
+    fn sar_reg_cl(&mut self, reg: Register) {
+        self.assembler.sar(Self::conv(reg), cl).unwrap();
+    }
+
+    fn rol_reg_cl(&mut self, reg: Register) {
+        self.assembler.rol(Self::conv(reg), cl).unwrap();
+    }
+
+    fn ror_reg_cl(&mut self, reg: Register) {
+        self.assembler.ror(Self::conv(reg), cl).unwrap();
+    }
+
+    fn popcnt_reg_reg(&mut self, reg1: Register, reg2: Register) {
+        self.assembler
+            .popcnt(Self::conv(reg1), Self::conv(reg2))
+            .unwrap();
+    }
+
+    fn lzcnt_reg_reg(&mut self, reg1: Register, reg2: Register) {
+        self.assembler
+            .lzcnt(Self::conv(reg1), Self::conv(reg2))
+            .unwrap();
+    }
+
+    fn tzcnt_reg_reg(&mut self, reg1: Register, reg2: Register) {
+        self.assembler
+            .tzcnt(Self::conv(reg1), Self::conv(reg2))
+            .unwrap();
+    }
+
+    fn shl_reg_imm8(&mut self, reg: Register, imm8: u8) {
+        self.assembler.shl(Self::conv(reg), imm8 as u32).unwrap();
+    }
+
     fn shr_reg_imm8(&mut self, reg: Register, imm8: u8) {
         self.assembler.shr(Self::conv(reg), imm8 as u32).unwrap();
     }
 
+    fn sar_reg_imm8(&mut self, reg: Register, imm8: u8) {
+        self.assembler.sar(Self::conv(reg), imm8 as u32).unwrap();
+    }
+
     fn call_reg(&mut self, reg: Register) {
         self.assembler.call(Self::conv(reg)).unwrap();
     }
@@ -0,0 +1,1510 @@
+// Parser for the infix MBA syntax used throughout the datasets and emitted by `AstPrinter`.
+//
+// Operator precedence, from loosest to tightest binding:
+//   c ? a : b                      (right associative)
+//   == != < <= > >= <s <=s >s >=s  (non associative)
+//   ++
+//   |
+//   ^
+//   &
+//   << >> >>s <<< >>>
+//   + -
+//   * / % /s %s
+//   ~ -                            (unary)
+//   **                             (right associative)
+//   x[h:l], x zx iN, x sx iN, x tr iN  (postfix)
+//
+// Constants may be written in decimal or hex(0x..), and both constants and symbols may carry
+// a width annotation(e.g. `x:i8`, `255:i8`). Unannotated symbols default to the width
+// passed to `parse_ast`, while unannotated constants adopt the width of the expression they appear in.
+// The carry function and bit counts are written as calls: `maj(a, b, c)`, `popcount(x)`, `ctlz(x)` and `cttz(x)`.
+use std::fmt;
+
+use ahash::AHashMap;
+
+use crate::simple_ast::{AstIdx, Context, INodeUtil, Predicate, MAX_WIDTH};
+
+// Maximum nesting of parentheses, calls and prefix operators.
+// The parser is recursive, so this keeps deeply nested input from overflowing the stack.
+const MAX_DEPTH: usize = 1000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub(crate) fn join(&self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnexpectedCharacter(char),
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    UnexpectedEndOfInput {
+        expected: &'static str,
+    },
+    ConstantOutOfRange,
+    InvalidWidth(u64),
+    WidthMismatch {
+        lhs: u8,
+        rhs: u8,
+    },
+    InvalidCast {
+        from: u8,
+        to: u8,
+    },
+    InvalidExtract {
+        high: u64,
+        low: u64,
+        width: u8,
+    },
+    NestingTooDeep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    // Render the error along with the offending line of input, e.g.
+    //  error: expected operand, found `)`
+    //    (x+)
+    //       ^
+    pub fn render(&self, input: &str) -> String {
+        let line_start = input[..self.span.start.min(input.len())]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let line_end = input[line_start..]
+            .find('\n')
+            .map(|i| line_start + i)
+            .unwrap_or(input.len());
+        let line = &input[line_start..line_end];
+
+        let offset = input[line_start..self.span.start.min(line_end)]
+            .chars()
+            .count();
+        let len = input[self.span.start.min(line_end)..self.span.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+
+        return format!(
+            "error: {}\n  {}\n  {}{}",
+            self,
+            line,
+            " ".repeat(offset),
+            "^".repeat(len)
+        );
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character `{}`", c),
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {}, found `{}`", expected, found)
+            }
+            ParseErrorKind::UnexpectedEndOfInput { expected } => {
+                write!(f, "expected {}, found end of input", expected)
+            }
+            ParseErrorKind::ConstantOutOfRange => {
+                write!(f, "constant does not fit in {} bits", MAX_WIDTH)
+            }
+            ParseErrorKind::InvalidWidth(w) => {
+                write!(f, "invalid width i{}, widths must be between 1 and {}", w, MAX_WIDTH)
+            }
+            ParseErrorKind::WidthMismatch { lhs, rhs } => {
+                write!(f, "width mismatch between i{} and i{}", lhs, rhs)
+            }
+            ParseErrorKind::InvalidCast { from, to } => {
+                write!(f, "cannot cast from i{} to i{}", from, to)
+            }
+            ParseErrorKind::InvalidExtract { high, low, width } => {
+                write!(
+                    f,
+                    "invalid extract [{}:{}] of an i{} value",
+                    high, low, width
+                )
+            }
+            ParseErrorKind::NestingTooDeep => {
+                write!(f, "expression nests deeper than {} levels", MAX_DEPTH)
+            }
+        }?;
+
+        write!(f, " at {}..{}", self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Number(u128),
+    Ident(String),
+    // A `:iN` width annotation
+    Width(u64),
+    Plus,
+    Minus,
+    Star,
+    StarStar,
+    Slash,
+    Percent,
+    SlashS,
+    PercentS,
+    Amp,
+    Pipe,
+    Caret,
+    Tilde,
+    Shl,
+    Lshr,
+    Ashr,
+    Rotl,
+    Rotr,
+    Concat,
+    Cmp(Predicate),
+    Question,
+    Colon,
+    Comma,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Number(c) => write!(f, "{}", c),
+            TokenKind::Ident(name) => write!(f, "{}", name),
+            TokenKind::Width(w) => write!(f, ":i{}", w),
+            TokenKind::Plus => write!(f, "+"),
+            TokenKind::Minus => write!(f, "-"),
+            TokenKind::Star => write!(f, "*"),
+            TokenKind::StarStar => write!(f, "**"),
+            TokenKind::Slash => write!(f, "/"),
+            TokenKind::Percent => write!(f, "%"),
+            TokenKind::SlashS => write!(f, "/s"),
+            TokenKind::PercentS => write!(f, "%s"),
+            TokenKind::Amp => write!(f, "&"),
+            TokenKind::Pipe => write!(f, "|"),
+            TokenKind::Caret => write!(f, "^"),
+            TokenKind::Tilde => write!(f, "~"),
+            TokenKind::Shl => write!(f, "<<"),
+            TokenKind::Lshr => write!(f, ">>"),
+            TokenKind::Ashr => write!(f, ">>s"),
+            TokenKind::Rotl => write!(f, "<<<"),
+            TokenKind::Rotr => write!(f, ">>>"),
+            TokenKind::Concat => write!(f, "++"),
+            TokenKind::Cmp(pred) => write!(f, "{}", pred),
+            TokenKind::Question => write!(f, "?"),
+            TokenKind::Colon => write!(f, ":"),
+            TokenKind::Comma => write!(f, ","),
+            TokenKind::LParen => write!(f, "("),
+            TokenKind::RParen => write!(f, ")"),
+            TokenKind::LBracket => write!(f, "["),
+            TokenKind::RBracket => write!(f, "]"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_'
+}
+
+fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let peek = |n: usize| bytes.get(i + n).copied().unwrap_or(0);
+        let kind = if c.is_ascii_digit() {
+            let (radix, digits_start) = if c == b'0' && (peek(1) == b'x' || peek(1) == b'X') {
+                (16, i + 2)
+            } else {
+                (10, i)
+            };
+
+            i = digits_start;
+            while i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+                i += 1;
+            }
+
+            let span = Span::new(start, i);
+            let digits = &input[digits_start..i];
+            if digits.is_empty() || (radix == 10 && !digits.bytes().all(|b| b.is_ascii_digit())) {
+                return Err(ParseError::new(
+                    ParseErrorKind::UnexpectedToken {
+                        expected: "constant",
+                        found: input[start..i].to_string(),
+                    },
+                    span,
+                ));
+            }
+
+            let value = u128::from_str_radix(digits, radix)
+                .map_err(|_| ParseError::new(ParseErrorKind::ConstantOutOfRange, span))?;
+            TokenKind::Number(value)
+        } else if is_ident_start(c) {
+            while i < bytes.len() && is_ident_char(bytes[i]) {
+                i += 1;
+            }
+            TokenKind::Ident(input[start..i].to_string())
+        } else if c == b':' && peek(1) == b'i' && peek(2).is_ascii_digit() {
+            i += 2;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+
+            let width = input[start + 2..i].parse::<u64>().unwrap_or(u64::MAX);
+            TokenKind::Width(width)
+        } else {
+            // Punctuation. Longer operators must be matched first.
+            let (kind, len) = match (c, peek(1), peek(2)) {
+                (b'*', b'*', _) => (TokenKind::StarStar, 2),
+                (b'+', b'+', _) => (TokenKind::Concat, 2),
+                (b'<', b'<', b'<') => (TokenKind::Rotl, 3),
+                (b'>', b'>', b'>') => (TokenKind::Rotr, 3),
+                (b'<', b'<', _) => (TokenKind::Shl, 2),
+                (b'>', b'>', b's') => (TokenKind::Ashr, 3),
+                (b'>', b'>', _) => (TokenKind::Lshr, 2),
+                (b'/', b's', _) => (TokenKind::SlashS, 2),
+                (b'%', b's', _) => (TokenKind::PercentS, 2),
+                (b'=', b'=', _) => (TokenKind::Cmp(Predicate::Eq), 2),
+                (b'!', b'=', _) => (TokenKind::Cmp(Predicate::Ne), 2),
+                (b'>', b'=', b's') => (TokenKind::Cmp(Predicate::Sge), 3),
+                (b'<', b'=', b's') => (TokenKind::Cmp(Predicate::Sle), 3),
+                (b'>', b'=', _) => (TokenKind::Cmp(Predicate::Uge), 2),
+                (b'<', b'=', _) => (TokenKind::Cmp(Predicate::Ule), 2),
+                (b'>', b's', _) => (TokenKind::Cmp(Predicate::Sgt), 2),
+                (b'<', b's', _) => (TokenKind::Cmp(Predicate::Slt), 2),
+                (b'>', _, _) => (TokenKind::Cmp(Predicate::Ugt), 1),
+                (b'<', _, _) => (TokenKind::Cmp(Predicate::Ult), 1),
+                (b'+', _, _) => (TokenKind::Plus, 1),
+                (b'-', _, _) => (TokenKind::Minus, 1),
+                (b'*', _, _) => (TokenKind::Star, 1),
+                (b'/', _, _) => (TokenKind::Slash, 1),
+                (b'%', _, _) => (TokenKind::Percent, 1),
+                (b'&', _, _) => (TokenKind::Amp, 1),
+                (b'|', _, _) => (TokenKind::Pipe, 1),
+                (b'^', _, _) => (TokenKind::Caret, 1),
+                (b'~', _, _) => (TokenKind::Tilde, 1),
+                (b'?', _, _) => (TokenKind::Question, 1),
+                (b':', _, _) => (TokenKind::Colon, 1),
+                (b',', _, _) => (TokenKind::Comma, 1),
+                (b'(', _, _) => (TokenKind::LParen, 1),
+                (b')', _, _) => (TokenKind::RParen, 1),
+                (b'[', _, _) => (TokenKind::LBracket, 1),
+                (b']', _, _) => (TokenKind::RBracket, 1),
+                _ => {
+                    let ch = input[i..].chars().next().unwrap();
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnexpectedCharacter(ch),
+                        Span::new(i, i + ch.len_utf8()),
+                    ));
+                }
+            };
+
+            // `x <s` is a signed comparison, but `x <s1` compares against the symbol `s1`,
+            // and `x/s:i8` divides by the symbol `s`.
+            let is_signed = matches!(
+                kind,
+                TokenKind::Ashr
+                    | TokenKind::SlashS
+                    | TokenKind::PercentS
+                    | TokenKind::Cmp(
+                        Predicate::Sgt | Predicate::Sge | Predicate::Slt | Predicate::Sle
+                    )
+            );
+            let is_symbol = is_ident_char(peek(len))
+                || (peek(len) == b':' && peek(len + 1) == b'i' && peek(len + 2).is_ascii_digit());
+            if is_signed && is_symbol {
+                let (kind, len) = match (c, peek(1)) {
+                    (b'>', b'>') => (TokenKind::Lshr, 2),
+                    (b'/', _) => (TokenKind::Slash, 1),
+                    (b'%', _) => (TokenKind::Percent, 1),
+                    (b'>', b'=') => (TokenKind::Cmp(Predicate::Uge), 2),
+                    (b'<', b'=') => (TokenKind::Cmp(Predicate::Ule), 2),
+                    (b'>', _) => (TokenKind::Cmp(Predicate::Ugt), 1),
+                    _ => (TokenKind::Cmp(Predicate::Ult), 1),
+                };
+                i += len;
+                kind
+            } else {
+                i += len;
+                kind
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, i),
+        });
+    }
+
+    return Ok(tokens);
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Udiv,
+    Urem,
+    Sdiv,
+    Srem,
+    Pow,
+    And,
+    Or,
+    Xor,
+    Shl,
+    Lshr,
+    Ashr,
+    Rotl,
+    Rotr,
+    Concat,
+}
+
+// Untyped syntax tree shared by the infix and s-expression readers.
+#[derive(Debug, Clone)]
+pub(crate) enum ExprKind {
+    Constant { c: u128, width: Option<u8> },
+    Symbol { name: String, width: Option<u8> },
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    // Bitwise negation
+    Not(Box<Expr>),
+    // Arithmetic negation
+    Minus(Box<Expr>),
+    Zext(Box<Expr>, u8),
+    Sext(Box<Expr>, u8),
+    Trunc(Box<Expr>, u8),
+    Popcount(Box<Expr>),
+    Ctlz(Box<Expr>),
+    Cttz(Box<Expr>),
+    Extract(Box<Expr>, u64, u64),
+    ICmp(Predicate, Box<Expr>, Box<Expr>),
+    Select(Box<Expr>, Box<Expr>, Box<Expr>),
+    Carry(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Expr {
+    pub(crate) kind: ExprKind,
+    pub(crate) span: Span,
+}
+
+impl Expr {
+    pub(crate) fn operands(&self) -> Vec<&Expr> {
+        match &self.kind {
+            ExprKind::Constant { .. } | ExprKind::Symbol { .. } => vec![],
+            ExprKind::Binary(_, a, b) | ExprKind::ICmp(_, a, b) => vec![a, b],
+            ExprKind::Not(a)
+            | ExprKind::Minus(a)
+            | ExprKind::Zext(a, _)
+            | ExprKind::Sext(a, _)
+            | ExprKind::Trunc(a, _)
+            | ExprKind::Popcount(a)
+            | ExprKind::Ctlz(a)
+            | ExprKind::Cttz(a)
+            | ExprKind::Extract(a, ..) => vec![a],
+            ExprKind::Select(a, b, c) | ExprKind::Carry(a, b, c) => vec![a, b, c],
+        }
+    }
+}
+
+// The default destructor would recurse once per level of the tree, so operands are detached and dropped from a worklist.
+impl Drop for Expr {
+    fn drop(&mut self) {
+        let leaf = ExprKind::Constant { c: 0, width: None };
+        let mut stack = vec![std::mem::replace(&mut self.kind, leaf.clone())];
+        while let Some(kind) = stack.pop() {
+            let mut operands = match kind {
+                ExprKind::Constant { .. } | ExprKind::Symbol { .. } => continue,
+                ExprKind::Binary(_, a, b) | ExprKind::ICmp(_, a, b) => vec![a, b],
+                ExprKind::Not(a)
+                | ExprKind::Minus(a)
+                | ExprKind::Zext(a, _)
+                | ExprKind::Sext(a, _)
+                | ExprKind::Trunc(a, _)
+                | ExprKind::Popcount(a)
+                | ExprKind::Ctlz(a)
+                | ExprKind::Cttz(a)
+                | ExprKind::Extract(a, ..) => vec![a],
+                ExprKind::Select(a, b, c) | ExprKind::Carry(a, b, c) => vec![a, b, c],
+            };
+            for operand in operands.iter_mut() {
+                stack.push(std::mem::replace(&mut operand.kind, leaf.clone()));
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    // Nesting depth at the current position
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek() == Some(kind) {
+            self.pos += 1;
+            return true;
+        }
+
+        return false;
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        match self.tokens.get(self.pos) {
+            Some(token) => ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    expected,
+                    found: token.kind.to_string(),
+                },
+                token.span,
+            ),
+            None => ParseError::new(
+                ParseErrorKind::UnexpectedEndOfInput { expected },
+                Span::new(self.input.len(), self.input.len()),
+            ),
+        }
+    }
+
+    // Descend one level into the tree, failing if the nesting limit is exceeded.
+    // Each call must be paired with a `self.depth -= 1` once the subtree is built.
+    fn enter(&mut self) -> Result<(), ParseError> {
+        if self.depth >= MAX_DEPTH {
+            let span = match self.tokens.get(self.pos) {
+                Some(token) => token.span,
+                None => Span::new(self.input.len(), self.input.len()),
+            };
+            return Err(ParseError::new(ParseErrorKind::NestingTooDeep, span));
+        }
+
+        self.depth += 1;
+        return Ok(());
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &'static str) -> Result<Span, ParseError> {
+        if self.peek() == Some(&kind) {
+            return Ok(self.next().unwrap().span);
+        }
+
+        return Err(self.unexpected(expected));
+    }
+
+    fn parse_width(&mut self) -> Result<Option<u8>, ParseError> {
+        if let Some(TokenKind::Width(w)) = self.peek() {
+            let w = *w;
+            let span = self.next().unwrap().span;
+            if w == 0 || w > MAX_WIDTH as u64 {
+                return Err(ParseError::new(ParseErrorKind::InvalidWidth(w), span));
+            }
+
+            return Ok(Some(w as u8));
+        }
+
+        return Ok(None);
+    }
+
+    // Parse a bare integer, as used by extract bounds
+    fn parse_index(&mut self) -> Result<u64, ParseError> {
+        if let Some(TokenKind::Number(c)) = self.peek() {
+            // Out of range indices are reported when the extract is lowered.
+            let c = u64::try_from(*c).unwrap_or(u64::MAX);
+            self.pos += 1;
+            return Ok(c);
+        }
+
+        return Err(self.unexpected("integer"));
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        let cond = self.parse_cmp()?;
+        if !self.eat(&TokenKind::Question) {
+            return Ok(cond);
+        }
+
+        self.enter()?;
+        let a = self.parse_expr()?;
+        self.expect(TokenKind::Colon, "`:`")?;
+        let b = self.parse_expr()?;
+        self.depth -= 1;
+        let span = cond.span.join(b.span);
+        return Ok(Expr {
+            kind: ExprKind::Select(Box::new(cond), Box::new(a), Box::new(b)),
+            span,
+        });
+    }
+
+    fn parse_cmp(&mut self) -> Result<Expr, ParseError> {
+        let lhs = self.parse_binary(0)?;
+        if let Some(TokenKind::Cmp(pred)) = self.peek() {
+            let pred = *pred;
+            self.pos += 1;
+            let rhs = self.parse_binary(0)?;
+            let span = lhs.span.join(rhs.span);
+            return Ok(Expr {
+                kind: ExprKind::ICmp(pred, Box::new(lhs), Box::new(rhs)),
+                span,
+            });
+        }
+
+        return Ok(lhs);
+    }
+
+    // Binary operators with their precedence level, from loosest to tightest.
+    // All of these are left associative.
+    fn get_binop(kind: &TokenKind) -> Option<(BinOp, u8)> {
+        let op = match kind {
+            TokenKind::Concat => (BinOp::Concat, 0),
+            TokenKind::Pipe => (BinOp::Or, 1),
+            TokenKind::Caret => (BinOp::Xor, 2),
+            TokenKind::Amp => (BinOp::And, 3),
+            TokenKind::Shl => (BinOp::Shl, 4),
+            TokenKind::Lshr => (BinOp::Lshr, 4),
+            TokenKind::Ashr => (BinOp::Ashr, 4),
+            TokenKind::Rotl => (BinOp::Rotl, 4),
+            TokenKind::Rotr => (BinOp::Rotr, 4),
+            TokenKind::Plus => (BinOp::Add, 5),
+            TokenKind::Minus => (BinOp::Sub, 5),
+            TokenKind::Star => (BinOp::Mul, 6),
+            TokenKind::Slash => (BinOp::Udiv, 6),
+            TokenKind::Percent => (BinOp::Urem, 6),
+            TokenKind::SlashS => (BinOp::Sdiv, 6),
+            TokenKind::PercentS => (BinOp::Srem, 6),
+            _ => return None,
+        };
+
+        return Some(op);
+    }
+
+    fn parse_binary(&mut self, min_prec: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let Some((op, prec)) = self.peek().and_then(Self::get_binop) else {
+                break;
+            };
+            if prec < min_prec {
+                break;
+            }
+
+            self.pos += 1;
+            let rhs = self.parse_binary(prec + 1)?;
+            let span = lhs.span.join(rhs.span);
+            lhs = Expr {
+                kind: ExprKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+                span,
+            };
+        }
+
+        return Ok(lhs);
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        let start = match self.tokens.get(self.pos) {
+            Some(token) => token.span,
+            None => return Err(self.unexpected("operand")),
+        };
+
+        let make: fn(Box<Expr>) -> ExprKind = match self.peek() {
+            Some(TokenKind::Tilde) => ExprKind::Not,
+            Some(TokenKind::Minus) => ExprKind::Minus,
+            _ => return self.parse_pow(),
+        };
+
+        self.enter()?;
+        self.pos += 1;
+        let operand = self.parse_unary()?;
+        self.depth -= 1;
+        let span = start.join(operand.span);
+        return Ok(Expr {
+            kind: make(Box::new(operand)),
+            span,
+        });
+    }
+
+    fn parse_pow(&mut self) -> Result<Expr, ParseError> {
+        let base = self.parse_postfix()?;
+        if !self.eat(&TokenKind::StarStar) {
+            return Ok(base);
+        }
+
+        // Note that the exponent may itself be negated, e.g. `x**-1`.
+        self.enter()?;
+        let exponent = self.parse_unary()?;
+        self.depth -= 1;
+        let span = base.span.join(exponent.span);
+        return Ok(Expr {
+            kind: ExprKind::Binary(BinOp::Pow, Box::new(base), Box::new(exponent)),
+            span,
+        });
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            match self.peek() {
+                Some(TokenKind::LBracket) => {
+                    self.pos += 1;
+                    let high = self.parse_index()?;
+                    self.expect(TokenKind::Colon, "`:`")?;
+                    let low = self.parse_index()?;
+                    let end = self.expect(TokenKind::RBracket, "`]`")?;
+                    let span = expr.span.join(end);
+                    expr = Expr {
+                        kind: ExprKind::Extract(Box::new(expr), high, low),
+                        span,
+                    };
+                }
+                Some(TokenKind::Ident(name)) if name == "zx" || name == "sx" || name == "tr" => {
+                    let name = name.clone();
+                    self.pos += 1;
+                    let Some(TokenKind::Ident(ty)) = self.peek() else {
+                        return Err(self.unexpected("integer type"));
+                    };
+                    let token = self.tokens[self.pos].clone();
+                    let width = ty
+                        .strip_prefix('i')
+                        .and_then(|w| w.parse::<u64>().ok())
+                        .ok_or_else(|| self.unexpected("integer type"))?;
+                    if width == 0 || width > MAX_WIDTH as u64 {
+                        return Err(ParseError::new(
+                            ParseErrorKind::InvalidWidth(width),
+                            token.span,
+                        ));
+                    }
+
+                    self.pos += 1;
+                    let span = expr.span.join(token.span);
+                    let inner = Box::new(expr);
+                    expr = Expr {
+                        kind: match name.as_str() {
+                            "zx" => ExprKind::Zext(inner, width as u8),
+                            "sx" => ExprKind::Sext(inner, width as u8),
+                            _ => ExprKind::Trunc(inner, width as u8),
+                        },
+                        span,
+                    };
+                }
+                _ => break,
+            }
+        }
+
+        return Ok(expr);
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(self.unexpected("operand"));
+        };
+
+        match token.kind {
+            TokenKind::Number(c) => {
+                self.pos += 1;
+                let width = self.parse_width()?;
+                return Ok(Expr {
+                    kind: ExprKind::Constant { c, width },
+                    span: Span::new(token.span.start, self.tokens[self.pos - 1].span.end),
+                });
+            }
+            // `maj(a, b, c)` denotes the carry(majority) function.
+            TokenKind::Ident(name)
+                if name == "maj"
+                    && matches!(
+                        self.tokens.get(self.pos + 1).map(|t| &t.kind),
+                        Some(TokenKind::LParen)
+                    ) =>
+            {
+                self.enter()?;
+                self.pos += 2;
+                let a = self.parse_expr()?;
+                self.expect(TokenKind::Comma, "`,`")?;
+                let b = self.parse_expr()?;
+                self.expect(TokenKind::Comma, "`,`")?;
+                let c = self.parse_expr()?;
+                let end = self.expect(TokenKind::RParen, "`)`")?;
+                self.depth -= 1;
+                return Ok(Expr {
+                    kind: ExprKind::Carry(Box::new(a), Box::new(b), Box::new(c)),
+                    span: token.span.join(end),
+                });
+            }
+            TokenKind::Ident(name)
+                if matches!(name.as_str(), "popcount" | "ctlz" | "cttz")
+                    && matches!(
+                        self.tokens.get(self.pos + 1).map(|t| &t.kind),
+                        Some(TokenKind::LParen)
+                    ) =>
+            {
+                self.enter()?;
+                self.pos += 2;
+                let a = Box::new(self.parse_expr()?);
+                let end = self.expect(TokenKind::RParen, "`)`")?;
+                self.depth -= 1;
+                return Ok(Expr {
+                    kind: match name.as_str() {
+                        "popcount" => ExprKind::Popcount(a),
+                        "ctlz" => ExprKind::Ctlz(a),
+                        _ => ExprKind::Cttz(a),
+                    },
+                    span: token.span.join(end),
+                });
+            }
+            TokenKind::Ident(name) => {
+                self.pos += 1;
+                let width = self.parse_width()?;
+                return Ok(Expr {
+                    kind: ExprKind::Symbol { name, width },
+                    span: Span::new(token.span.start, self.tokens[self.pos - 1].span.end),
+                });
+            }
+            TokenKind::LParen => {
+                self.enter()?;
+                self.pos += 1;
+                let mut inner = self.parse_expr()?;
+                let end = self.expect(TokenKind::RParen, "`)`")?;
+                self.depth -= 1;
+                inner.span = token.span.join(end);
+                return Ok(inner);
+            }
+            _ => return Err(self.unexpected("operand")),
+        }
+    }
+}
+
+// Lowers the parsed tree into the arena, inferring widths and verifying that they agree.
+// Long operator chains such as `x+x+...+x` produce deep trees without deep nesting, so this is iterative.
+struct Lowering<'a> {
+    ctx: &'a mut Context,
+    default_width: u8,
+}
+
+enum LowerStep<'e> {
+    Enter(&'e Expr, Option<u8>),
+    // Verify the width of the most recently lowered operand
+    Check(u8, Span),
+    Exit(&'e Expr),
+}
+
+impl<'a> Lowering<'a> {
+    // Compute the width of every subexpression if it is determined by it's contents.
+    // The width is None for expressions composed solely of unannotated constants.
+    fn infer_widths(&self, root: &Expr) -> AHashMap<*const Expr, Option<u8>> {
+        let mut widths: AHashMap<*const Expr, Option<u8>> = AHashMap::new();
+        let mut stack = vec![(root, false)];
+        while let Some((expr, visited)) = stack.pop() {
+            if !visited {
+                stack.push((expr, true));
+                stack.extend(expr.operands().into_iter().map(|e| (e, false)));
+                continue;
+            }
+
+            let get = |e: &Expr| widths[&(e as *const Expr)];
+            let width = match &expr.kind {
+                ExprKind::Constant { width, .. } => *width,
+                ExprKind::Symbol { width, .. } => Some(width.unwrap_or(self.default_width)),
+                ExprKind::Binary(BinOp::Concat, a, b) => {
+                    let a = get(a).unwrap_or(self.default_width);
+                    let b = get(b).unwrap_or(self.default_width);
+                    Some(a.saturating_add(b))
+                }
+                ExprKind::Binary(_, a, b) => get(a).or_else(|| get(b)),
+                ExprKind::Not(a)
+                | ExprKind::Minus(a)
+                | ExprKind::Popcount(a)
+                | ExprKind::Ctlz(a)
+                | ExprKind::Cttz(a) => get(a),
+                ExprKind::Zext(_, to) | ExprKind::Sext(_, to) | ExprKind::Trunc(_, to) => {
+                    Some(*to)
+                }
+                ExprKind::Extract(_, high, low) => Some(
+                    high.saturating_sub(*low)
+                        .saturating_add(1)
+                        .min(u8::MAX as u64) as u8,
+                ),
+                ExprKind::ICmp(..) => Some(1),
+                ExprKind::Select(_, a, b) => get(a).or_else(|| get(b)),
+                ExprKind::Carry(a, b, c) => get(a).or_else(|| get(b)).or_else(|| get(c)),
+            };
+            widths.insert(expr, width);
+        }
+
+        return widths;
+    }
+
+    fn check_width(&self, idx: AstIdx, expected: u8, span: Span) -> Result<(), ParseError> {
+        let width = self.ctx.arena.get_width(idx);
+        if width != expected {
+            return Err(ParseError::new(
+                ParseErrorKind::WidthMismatch {
+                    lhs: expected,
+                    rhs: width,
+                },
+                span,
+            ));
+        }
+
+        return Ok(());
+    }
+
+    fn lower(&mut self, root: &Expr) -> Result<AstIdx, ParseError> {
+        let widths = self.infer_widths(root);
+        let mut stack = vec![LowerStep::Enter(root, None)];
+        let mut lowered: Vec<AstIdx> = Vec::new();
+        while let Some(step) = stack.pop() {
+            let expr = match step {
+                LowerStep::Enter(expr, expected) => {
+                    self.enter(expr, expected, &widths, &mut stack, &mut lowered);
+                    continue;
+                }
+                LowerStep::Check(width, span) => {
+                    self.check_width(*lowered.last().unwrap(), width, span)?;
+                    continue;
+                }
+                LowerStep::Exit(expr) => expr,
+            };
+
+            let count = expr.operands().len();
+            let operands = lowered.split_off(lowered.len() - count);
+            let idx = self.build(expr, &operands)?;
+            lowered.push(idx);
+        }
+
+        return Ok(lowered.pop().unwrap());
+    }
+
+    // Lower a leaf, or schedule the operands of an inner node.
+    // Operands listed together must all share the same width.
+    fn enter<'e>(
+        &mut self,
+        expr: &'e Expr,
+        expected: Option<u8>,
+        widths: &AHashMap<*const Expr, Option<u8>>,
+        stack: &mut Vec<LowerStep<'e>>,
+        lowered: &mut Vec<AstIdx>,
+    ) {
+        let idx = match &expr.kind {
+            ExprKind::Constant { c, width } => {
+                let width = width.or(expected).unwrap_or(self.default_width);
+                self.ctx.arena.constant(*c, width)
+            }
+            ExprKind::Symbol { name, width } => {
+                let width = width.unwrap_or(self.default_width);
+                self.ctx.arena.symbol_with_name(name.clone(), width)
+            }
+            _ => {
+                let default_width = self.default_width;
+                let uniform = |steps: &mut Vec<LowerStep<'e>>,
+                               operands: &[&'e Expr],
+                               expected: Option<u8>| {
+                    let width = operands
+                        .iter()
+                        .find_map(|e| widths[&(*e as *const Expr)])
+                        .or(expected)
+                        .unwrap_or(default_width);
+                    for operand in operands {
+                        steps.push(LowerStep::Enter(operand, Some(width)));
+                        steps.push(LowerStep::Check(width, operand.span));
+                    }
+                };
+
+                let mut steps = Vec::new();
+                match &expr.kind {
+                    ExprKind::Binary(BinOp::Concat, a, b) => {
+                        steps.push(LowerStep::Enter(a, None));
+                        steps.push(LowerStep::Enter(b, None));
+                    }
+                    ExprKind::Binary(_, a, b) => uniform(&mut steps, &[a, b], expected),
+                    ExprKind::Not(a)
+                    | ExprKind::Minus(a)
+                    | ExprKind::Popcount(a)
+                    | ExprKind::Ctlz(a)
+                    | ExprKind::Cttz(a) => steps.push(LowerStep::Enter(a, expected)),
+                    ExprKind::Zext(a, _)
+                    | ExprKind::Sext(a, _)
+                    | ExprKind::Trunc(a, _)
+                    | ExprKind::Extract(a, ..) => steps.push(LowerStep::Enter(a, None)),
+                    ExprKind::ICmp(_, a, b) => uniform(&mut steps, &[a, b], None),
+                    ExprKind::Select(cond, a, b) => {
+                        steps.push(LowerStep::Enter(cond, Some(1)));
+                        steps.push(LowerStep::Check(1, cond.span));
+                        uniform(&mut steps, &[a, b], expected);
+                    }
+                    ExprKind::Carry(a, b, c) => uniform(&mut steps, &[a, b, c], expected),
+                    ExprKind::Constant { .. } | ExprKind::Symbol { .. } => unreachable!(),
+                }
+
+                stack.push(LowerStep::Exit(expr));
+                stack.extend(steps.into_iter().rev());
+                return;
+            }
+        };
+
+        lowered.push(idx);
+    }
+
+    // Create the node for an inner expression once it's operands have been lowered.
+    fn build(&mut self, expr: &Expr, operands: &[AstIdx]) -> Result<AstIdx, ParseError> {
+        let arena = &mut self.ctx.arena;
+        let idx = match &expr.kind {
+            ExprKind::Binary(BinOp::Concat, ..) => {
+                let (a, b) = (operands[0], operands[1]);
+                let width = arena.get_width(a) as u64 + arena.get_width(b) as u64;
+                if width > MAX_WIDTH as u64 {
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidWidth(width),
+                        expr.span,
+                    ));
+                }
+
+                arena.concat(a, b)
+            }
+            ExprKind::Binary(op, ..) => {
+                let (a, b) = (operands[0], operands[1]);
+                match op {
+                    BinOp::Add => arena.add(a, b),
+                    BinOp::Sub => {
+                        // a - b => a + (-1 * b)
+                        let width = arena.get_width(b);
+                        let minus_one = arena.constant(u128::MAX, width);
+                        let neg_b = arena.mul(minus_one, b);
+                        arena.add(a, neg_b)
+                    }
+                    BinOp::Mul => arena.mul(a, b),
+                    BinOp::Udiv => arena.udiv(a, b),
+                    BinOp::Urem => arena.urem(a, b),
+                    BinOp::Sdiv => arena.sdiv(a, b),
+                    BinOp::Srem => arena.srem(a, b),
+                    BinOp::Pow => arena.pow(a, b),
+                    BinOp::And => arena.and(a, b),
+                    BinOp::Or => arena.or(a, b),
+                    BinOp::Xor => arena.xor(a, b),
+                    BinOp::Shl => arena.shl(a, b),
+                    BinOp::Lshr => arena.lshr(a, b),
+                    BinOp::Ashr => arena.ashr(a, b),
+                    BinOp::Rotl => arena.rotl(a, b),
+                    BinOp::Rotr => arena.rotr(a, b),
+                    BinOp::Concat => unreachable!(),
+                }
+            }
+            ExprKind::Not(_) => arena.neg(operands[0]),
+            ExprKind::Minus(_) => {
+                let a = operands[0];
+                let width = arena.get_width(a);
+                let minus_one = arena.constant(u128::MAX, width);
+                arena.mul(minus_one, a)
+            }
+            ExprKind::Zext(_, to) | ExprKind::Sext(_, to) | ExprKind::Trunc(_, to) => {
+                let is_trunc = matches!(expr.kind, ExprKind::Trunc(..));
+                let a = operands[0];
+                let from = arena.get_width(a);
+                if (!is_trunc && *to < from) || (is_trunc && *to > from) {
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidCast { from, to: *to },
+                        expr.span,
+                    ));
+                }
+
+                match expr.kind {
+                    ExprKind::Zext(..) => arena.zext(a, *to),
+                    ExprKind::Sext(..) => arena.sext(a, *to),
+                    _ => arena.trunc(a, *to),
+                }
+            }
+            ExprKind::Popcount(_) => arena.popcount(operands[0]),
+            ExprKind::Ctlz(_) => arena.ctlz(operands[0]),
+            ExprKind::Cttz(_) => arena.cttz(operands[0]),
+            ExprKind::Extract(_, high, low) => {
+                let a = operands[0];
+                let width = arena.get_width(a);
+                if low > high || *high >= width as u64 {
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidExtract {
+                            high: *high,
+                            low: *low,
+                            width,
+                        },
+                        expr.span,
+                    ));
+                }
+
+                arena.extract(a, *high as u8, *low as u8)
+            }
+            ExprKind::ICmp(pred, ..) => arena.icmp(*pred, operands[0], operands[1]),
+            ExprKind::Select(..) => arena.select(operands[0], operands[1], operands[2]),
+            ExprKind::Carry(..) => arena.carry(operands[0], operands[1], operands[2]),
+            ExprKind::Constant { .. } | ExprKind::Symbol { .. } => unreachable!(),
+        };
+
+        return Ok(idx);
+    }
+}
+
+// Parse an infix expression into the given context.
+// Unannotated symbols are assigned `default_width`.
+pub fn parse_ast(ctx: &mut Context, input: &str, default_width: u8) -> Result<AstIdx, ParseError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        input,
+        tokens,
+        pos: 0,
+        depth: 0,
+    };
+
+    let expr = parser.parse_expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parser.unexpected("operator or end of input"));
+    }
+
+    return lower_expr(ctx, &expr, default_width);
+}
+
+// Intern a syntax tree into the given context.
+pub(crate) fn lower_expr(
+    ctx: &mut Context,
+    expr: &Expr,
+    default_width: u8,
+) -> Result<AstIdx, ParseError> {
+    if default_width == 0 || default_width > MAX_WIDTH {
+        return Err(ParseError::new(
+            ParseErrorKind::InvalidWidth(default_width as u64),
+            Span::new(0, 0),
+        ));
+    }
+
+    let mut lowering = Lowering { ctx, default_width };
+    return lowering.lower(expr);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        simple_ast::{recursive_simplify, AstPrinter, SimpleAst},
+        test_util::new_ctx,
+    };
+
+    use super::*;
+
+    // Parse an expression which is expected to fold to a constant.
+    fn fold(ctx: &mut Context, input: &str, default_width: u8) -> u128 {
+        let idx = parse_ast(ctx, input, default_width).unwrap();
+        ctx.arena.get_constant(idx)
+    }
+
+    fn print(ctx: &Context, idx: AstIdx) -> String {
+        AstPrinter::print(ctx, ctx.arena.get_node(idx))
+    }
+
+    fn assert_round_trip(ctx: &mut Context, idx: AstIdx) {
+        let printed = print(ctx, idx);
+        let parsed =
+            parse_ast(ctx, &printed, 64).unwrap_or_else(|e| panic!("{}", e.render(&printed)));
+        assert_eq!(
+            idx,
+            parsed,
+            "{} was parsed as {}",
+            printed,
+            print(ctx, parsed)
+        );
+    }
+
+    #[test]
+    fn round_trip_printer() {
+        let mut ctx = new_ctx();
+        let a = &mut ctx.arena;
+        let x = a.symbol_with_name("x".to_string(), 64);
+        let y = a.symbol_with_name("y".to_string(), 64);
+        let c1 = a.constant(1111, 64);
+        let c2 = a.constant(-32i64 as u128, 64);
+
+        let and = a.and(c1, x);
+        let mul = a.mul(c2, and);
+        let xor = a.xor(x, y);
+        let not = a.neg(xor);
+        let or = a.or(not, y);
+        let add = a.add(mul, or);
+        let two = a.constant(2, 64);
+        let pow = a.pow(x, two);
+        let three = a.constant(3, 64);
+        let shr = a.lshr(add, three);
+        let shl = a.shl(x, y);
+        let sar = a.ashr(shl, three);
+        let root = a.add(pow, shr);
+        let udiv = a.udiv(x, y);
+        let urem = a.urem(add, y);
+        let sdiv = a.sdiv(x, three);
+        let srem = a.srem(sdiv, y);
+        let rotl = a.rotl(x, y);
+        let rotr = a.rotr(rotl, y);
+        let popcount = a.popcount(add);
+        let ctlz = a.ctlz(x);
+        let cttz = a.cttz(popcount);
+
+        let mut cases = vec![and, mul, xor, not, or, add, pow, shr, shl, sar, root];
+        cases.extend([udiv, urem, sdiv, srem, rotl, rotr, popcount, ctlz, cttz]);
+
+        // Width changing and boolean operations
+        let b = a.symbol_with_name("b".to_string(), 8);
+        let b_ext = a.zext(b, 64);
+        let b_sext = a.sext(b, 32);
+        let x_tr = a.trunc(x, 8);
+        let cmp = a.icmp(Predicate::Slt, x_tr, b);
+        let sel = a.select(cmp, b_ext, y);
+        let ext = a.extract(x, 15, 8);
+        let concat = a.concat(ext, b);
+        let x8 = a.symbol_with_name("x".to_string(), 8);
+        let carry = a.carry(x8, b, x_tr);
+        cases.extend([b_ext, b_sext, x_tr, cmp, sel, ext, concat, carry]);
+
+        for case in cases {
+            assert_round_trip(&mut ctx, case);
+        }
+    }
+
+    #[test]
+    fn parse_dataset_syntax() {
+        let mut ctx = new_ctx();
+        let input = "4712409062275004791 + 2*(x^y) + 3*(x*y) + 13734335011434546825*x + -1*(x|~y)";
+        let idx = parse_ast(&mut ctx, input, 64).unwrap();
+        assert_round_trip(&mut ctx, idx);
+
+        // Negative and hex constants should agree
+        let a = parse_ast(&mut ctx, "-8221290598995256160+x", 64).unwrap();
+        let b = parse_ast(&mut ctx, "0x8DE81BC22BA11CA0 + x", 64).unwrap();
+        assert_eq!(a, b);
+
+        // Subtraction is canonicalized as addition by a negated operand
+        let a = parse_ast(&mut ctx, "x - y", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x + -1*y", 64).unwrap();
+        assert_eq!(a, b);
+
+        // `**` binds tighter than unary negation, which binds tighter than `*`.
+        let a = parse_ast(&mut ctx, "-x**2*y", 64).unwrap();
+        let b = parse_ast(&mut ctx, "(-1*(x**2))*y", 64).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn parse_shifts() {
+        let mut ctx = new_ctx();
+
+        // Shifts by a constant are canonicalized into multiplications.
+        let a = parse_ast(&mut ctx, "x << 3", 64).unwrap();
+        let b = parse_ast(&mut ctx, "8*x", 64).unwrap();
+        assert_eq!(a, b);
+
+        // `>>s` is an arithmetic shift, unless the `s` begins a symbol name.
+        let a = parse_ast(&mut ctx, "x >>s y", 64).unwrap();
+        assert!(matches!(ctx.arena.get_node(a), SimpleAst::Ashr(_)));
+        let a = parse_ast(&mut ctx, "x >>sy", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x >> sy", 64).unwrap();
+        assert_eq!(a, b);
+
+        // Constant folding follows SMT-LIB semantics for out of range shift amounts.
+        assert_eq!(fold(&mut ctx, "0x81 >>s 1", 8), 0xC0);
+        assert_eq!(fold(&mut ctx, "0x81 >>s 200", 8), 0xFF);
+        assert_eq!(fold(&mut ctx, "0x41 >>s 9", 8), 0);
+        assert_eq!(fold(&mut ctx, "0x81 << 1", 8), 0x02);
+        assert_eq!(fold(&mut ctx, "0x81 << 8", 8), 0);
+        assert_eq!(fold(&mut ctx, "0xFF << 7", 8), 0x80);
+    }
+
+    #[test]
+    fn parse_division() {
+        let mut ctx = new_ctx();
+
+        // Division binds as tightly as multiplication.
+        let a = parse_ast(&mut ctx, "x + y / z * w", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x + ((y / z) * w)", 64).unwrap();
+        assert_eq!(a, b);
+
+        // `/s` is a signed division, unless the `s` begins a symbol name.
+        let a = parse_ast(&mut ctx, "x /s y", 64).unwrap();
+        assert!(matches!(ctx.arena.get_node(a), SimpleAst::Sdiv(_)));
+        let a = parse_ast(&mut ctx, "x %sy", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x % sy", 64).unwrap();
+        assert_eq!(a, b);
+        let a = parse_ast(&mut ctx, "x/s:i64", 64).unwrap();
+        assert!(matches!(ctx.arena.get_node(a), SimpleAst::Udiv(_)));
+
+        // Constant folding follows SMT-LIB semantics for division by zero.
+        assert_eq!(fold(&mut ctx, "200 / 7", 8), 28);
+        assert_eq!(fold(&mut ctx, "200 % 7", 8), 4);
+        assert_eq!(fold(&mut ctx, "200 / 0", 8), 0xFF);
+        assert_eq!(fold(&mut ctx, "200 % 0", 8), 200);
+        assert_eq!(fold(&mut ctx, "-7 /s 2", 8), -3i8 as u8 as u128);
+        assert_eq!(fold(&mut ctx, "-7 %s 2", 8), -1i8 as u8 as u128);
+        assert_eq!(fold(&mut ctx, "-7 /s 0", 8), 1);
+        assert_eq!(fold(&mut ctx, "7 /s 0", 8), 0xFF);
+        assert_eq!(fold(&mut ctx, "-7 %s 0", 8), -7i8 as u8 as u128);
+        assert_eq!(fold(&mut ctx, "0x80 /s -1", 8), 0x80);
+        assert_eq!(fold(&mut ctx, "0x80 %s -1", 8), 0);
+
+        // Unsigned division by a power of two is a shift, and the remainder a mask.
+        let a = parse_ast(&mut ctx, "x % 8", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x & 7", 64).unwrap();
+        assert_eq!(recursive_simplify(&mut ctx, a), b);
+        let a = parse_ast(&mut ctx, "x / 8", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x >> 3", 64).unwrap();
+        assert_eq!(recursive_simplify(&mut ctx, a), b);
+    }
+
+    #[test]
+    fn parse_rotates() {
+        let mut ctx = new_ctx();
+
+        // Rotations by a constant are canonicalized to the left.
+        let a = parse_ast(&mut ctx, "x:i32 >>> 8", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x:i32 <<< 24", 64).unwrap();
+        assert_eq!(a, b);
+        assert!(matches!(ctx.arena.get_node(a), SimpleAst::Rotl(_)));
+        let a = parse_ast(&mut ctx, "x <<< 64", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x", 64).unwrap();
+        assert_eq!(a, b);
+
+        assert_eq!(fold(&mut ctx, "0x81 <<< 1", 8), 0x03);
+        assert_eq!(fold(&mut ctx, "0x81 >>> 1", 8), 0xC0);
+        assert_eq!(fold(&mut ctx, "0x81 <<< 9", 8), 0x03);
+
+        // Rotate idioms are folded back into rotates, regardless of how the halves are combined.
+        for input in [
+            "(x:i32 << 7) | (x:i32 >> 25)",
+            "(x:i32 >> 25) + (x:i32 << 7)",
+            "(x:i32 << 7) ^ ((x:i32 >> 25) & 127)",
+            "((x:i32 << 7) & 0xFFFFFF80) | (x:i32 >> 25)",
+        ] {
+            let a = parse_ast(&mut ctx, input, 64).unwrap();
+            let b = parse_ast(&mut ctx, "x:i32 <<< 7", 64).unwrap();
+            assert_eq!(recursive_simplify(&mut ctx, a), b, "{}", input);
+        }
+
+        // But not when the mask clears bits of the rotated value.
+        let a = parse_ast(&mut ctx, "(x:i32 << 7) | ((x:i32 >> 25) & 63)", 64).unwrap();
+        let a = recursive_simplify(&mut ctx, a);
+        assert!(!matches!(ctx.arena.get_node(a), SimpleAst::Rotl(_)));
+    }
+
+    #[test]
+    fn parse_sign_extension() {
+        let mut ctx = new_ctx();
+        assert_eq!(fold(&mut ctx, "0x80:i8 sx i16", 8), 0xFF80);
+        assert_eq!(fold(&mut ctx, "0x7F:i8 sx i16", 8), 0x7F);
+
+        // Chains of sign extensions and truncations are folded.
+        let simplify = |ctx: &mut Context, s: &str| {
+            let idx = parse_ast(ctx, s, 64).unwrap();
+            recursive_simplify(ctx, idx)
+        };
+        let cases = [
+            ("x:i8 sx i16 sx i64", "x:i8 sx i64"),
+            ("x:i8 sx i64 tr i8", "x:i8"),
+            ("x:i16 sx i64 tr i8", "x:i16 tr i8"),
+            ("x:i8 sx i64 tr i32", "x:i8 sx i32"),
+            ("x:i8 zx i16 sx i64", "x:i8 zx i16 zx i64"),
+        ];
+        for (input, expected) in cases {
+            let a = simplify(&mut ctx, input);
+            let b = parse_ast(&mut ctx, expected, 64).unwrap();
+            assert_eq!(a, b, "{}", input);
+        }
+
+        let e = parse_ast(&mut ctx, "x:i16 sx i8", 64).unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::InvalidCast { from: 16, to: 8 });
+    }
+
+    #[test]
+    fn parse_bit_counts() {
+        let mut ctx = new_ctx();
+        assert_eq!(fold(&mut ctx, "popcount(0xF1)", 8), 5);
+        assert_eq!(fold(&mut ctx, "ctlz(0x10)", 8), 3);
+        assert_eq!(fold(&mut ctx, "cttz(0x10)", 8), 4);
+        assert_eq!(fold(&mut ctx, "ctlz(0)", 8), 8);
+        assert_eq!(fold(&mut ctx, "cttz(0)", 8), 8);
+
+        // Counts fit in log2(width)+1 bits, and are bounded further by the known bits of the input.
+        let idx = parse_ast(&mut ctx, "popcount(x)", 64).unwrap();
+        assert_eq!(ctx.arena.get_data(idx).known_bits.zeroes, !0x7Fu64 as u128);
+        let idx = parse_ast(&mut ctx, "popcount(x & 0xF0)", 64).unwrap();
+        assert_eq!(ctx.arena.get_data(idx).known_bits.zeroes, !0x7u64 as u128);
+        let idx = parse_ast(&mut ctx, "cttz(x | 4)", 64).unwrap();
+        assert_eq!(ctx.arena.get_data(idx).known_bits.zeroes, !0x3u64 as u128);
+
+        // Sums of extracted bits fold into a popcount.
+        let all_bits = (0..8)
+            .map(|i| format!("((x:i8 >> {}) & 1)", i))
+            .collect::<Vec<_>>()
+            .join(" + ");
+        let cases = [
+            (all_bits.as_str(), "popcount(x:i8)"),
+            ("(x & 1) + ((x >> 3) & 1)", "popcount(x & 9)"),
+            ("((x >> 3) & 1) + (x[5:5] zx i64)", "popcount(x & 40)"),
+            ("popcount(x & 6) + (x & 1)", "popcount(x & 7)"),
+            ("popcount(~x & (x - 1))", "cttz(x)"),
+        ];
+        for (input, expected) in cases {
+            let a = parse_ast(&mut ctx, input, 64).unwrap();
+            let b = parse_ast(&mut ctx, expected, 64).unwrap();
+            assert_eq!(recursive_simplify(&mut ctx, a), b, "{}", input);
+        }
+
+        // Overlapping bits are counted twice, so they are left alone.
+        let a = parse_ast(&mut ctx, "popcount(x & 3) + (x & 1)", 64).unwrap();
+        let a = recursive_simplify(&mut ctx, a);
+        assert!(matches!(ctx.arena.get_node(a), SimpleAst::Add(_)));
+    }
+
+    #[test]
+    fn parse_wide_widths() {
+        let mut ctx = new_ctx();
+
+        // Concatenating two 64-bit values keeps the known bits of both halves.
+        let idx = parse_ast(&mut ctx, "(x & 0xFF) ++ (y | 1)", 64).unwrap();
+        assert_eq!(ctx.arena.get_width(idx), 128);
+        let kb = ctx.arena.get_data(idx).known_bits;
+        assert_eq!(kb.zeroes, (!0xFFu64 as u128) << 64);
+        assert_eq!(kb.ones, 1);
+        assert_round_trip(&mut ctx, idx);
+
+        // Constants fold across the 64-bit boundary.
+        assert_eq!(fold(&mut ctx, "0xFFFFFFFFFFFFFFFF + 1", 128), 1 << 64);
+        assert_eq!(fold(&mut ctx, "0xFFFFFFFFFFFFFFFF * 0xFFFFFFFFFFFFFFFF", 128), (u64::MAX as u128).pow(2));
+        assert_eq!(fold(&mut ctx, "(0x8000000000000000:i64 sx i128) >>s 64", 128), u128::MAX);
+        assert_eq!(fold(&mut ctx, "popcount(-1)", 128), 128);
+
+        // Shifts by a constant are not folded, but their known bits are exact.
+        let idx = parse_ast(&mut ctx, "-1 >> 100", 128).unwrap();
+        assert_eq!(ctx.arena.get_data(idx).known_bits.as_constant(), Some(0xFFF_FFFF));
+
+        for s in ["x:i128 + -2:i128", "(x:i96 zx i128) * 0x123456789ABCDEF0123:i128", "x:i128[100:37]"] {
+            let idx = parse_ast(&mut ctx, s, 64).unwrap();
+            assert_round_trip(&mut ctx, idx);
+        }
+    }
+
+    #[test]
+    fn infer_constant_widths() {
+        let mut ctx = new_ctx();
+        let idx = parse_ast(&mut ctx, "(x:i8 + 255) & 3", 64).unwrap();
+        assert_eq!(ctx.arena.get_width(idx), 8);
+
+        let idx = parse_ast(&mut ctx, "x ? 1 : 2:i16", 1).unwrap();
+        assert_eq!(ctx.arena.get_width(idx), 16);
+    }
+
+    #[test]
+    fn report_errors() {
+        let mut ctx = new_ctx();
+        let err = |ctx: &mut Context, s: &str| parse_ast(ctx, s, 64).unwrap_err();
+
+        let e = err(&mut ctx, "(x + )");
+        assert_eq!(e.span, Span::new(5, 6));
+        assert!(matches!(e.kind, ParseErrorKind::UnexpectedToken { .. }));
+
+        let e = err(&mut ctx, "x + y $ z");
+        assert_eq!(e.kind, ParseErrorKind::UnexpectedCharacter('$'));
+        assert_eq!(e.span, Span::new(6, 7));
+
+        let e = err(&mut ctx, "(x & y");
+        assert!(matches!(
+            e.kind,
+            ParseErrorKind::UnexpectedEndOfInput { .. }
+        ));
+
+        let e = err(&mut ctx, "x:i8 + y:i16");
+        assert_eq!(e.kind, ParseErrorKind::WidthMismatch { lhs: 8, rhs: 16 });
+        assert_eq!(e.span, Span::new(7, 12));
+
+        let e = err(&mut ctx, "x:i128 + 340282366920938463463374607431768211456");
+        assert_eq!(e.kind, ParseErrorKind::ConstantOutOfRange);
+
+        let e = err(&mut ctx, "x:i129");
+        assert_eq!(e.kind, ParseErrorKind::InvalidWidth(129));
+
+        let e = err(&mut ctx, "x:i128 ++ y:i8");
+        assert_eq!(e.kind, ParseErrorKind::InvalidWidth(136));
+
+        let e = err(&mut ctx, "x[64:0]");
+        assert!(matches!(e.kind, ParseErrorKind::InvalidExtract { .. }));
+
+        assert!(e.render("x[64:0]").ends_with("  x[64:0]\n  ^^^^^^^"));
+    }
+
+    #[test]
+    fn limit_nesting() {
+        let mut ctx = new_ctx();
+        let nested = |open: &str, close: &str, n: usize| {
+            format!("{}x{}", open.repeat(n), close.repeat(n))
+        };
+
+        let x = parse_ast(&mut ctx, "x", 64).unwrap();
+        let input = nested("(", ")", MAX_DEPTH);
+        assert_eq!(parse_ast(&mut ctx, &input, 64).unwrap(), x);
+        let input = nested("~", "", MAX_DEPTH);
+        assert!(parse_ast(&mut ctx, &input, 64).is_ok());
+
+        // Nesting beyond the limit is an error rather than a stack overflow.
+        for (open, close) in [("(", ")"), ("~", ""), ("-", ""), ("popcount(", ")"), ("x**", "")] {
+            let input = nested(open, close, 100_000);
+            let e = parse_ast(&mut ctx, &input, 64).unwrap_err();
+            assert_eq!(e.kind, ParseErrorKind::NestingTooDeep);
+        }
+
+        // Long chains of binary operators do not nest, but still produce a deep tree.
+        let input = format!("x{}", " >> x".repeat(100_000));
+        let chain = parse_ast(&mut ctx, &input, 64).unwrap();
+        assert_eq!(ctx.arena.get_width(chain), 64);
+        let input = format!("x:i8{}", " >> x".repeat(100_000));
+        let e = parse_ast(&mut ctx, &input, 64).unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::WidthMismatch { lhs: 8, rhs: 64 });
+    }
+}
@@ -0,0 +1,327 @@
+// Command line frontend for the native simplification pipeline, mirroring the options of `Simplifier.exe`.
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+use std::time::{Duration, Instant};
+
+use eq_sat::{AstClass, Context, EGraph, Expr, PrintOptions, TruthTables, MAX_ANF_VARS};
+use rand::Rng;
+
+const USAGE: &str = "Usage: simplifier [options] <expr>
+Command line input not preceded by the option indicators below is considered to be an input expression. Only one input expression is accepted.
+Command line options:
+    -h:        print usage
+    -b:        specify the bit number of variables (default is 64)
+    -f:        read expressions from a file, one per line, instead of the command line
+    -s:        parse the input as s-expressions
+    -z:        check the simplified result against the input on random values, then prove it with z3 if it is on the PATH
+    -e:        enable the equality saturation based simplifier
+    -t:        time limit of the equality saturation based simplifier in milliseconds (default is 5000)
+    -m:        directory containing the truth table databases (default is Minimization/TruthTables next to the executable)";
+
+struct Options {
+    bit_width: u8,
+    file: Option<PathBuf>,
+    sexpr: bool,
+    verify: bool,
+    use_eqsat: bool,
+    eqsat_time_limit: Duration,
+    truth_tables: PathBuf,
+    input: Option<String>,
+}
+
+// Resolved relative to the executable rather than the source tree, so the tool keeps working after it is moved.
+fn default_truth_tables() -> PathBuf {
+    let exe = std::env::current_exe().unwrap_or_default();
+    exe.parent()
+        .unwrap_or(Path::new("."))
+        .join("Minimization/TruthTables")
+}
+
+fn parse_args(args: &[String]) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        bit_width: 64,
+        file: None,
+        sexpr: false,
+        verify: false,
+        use_eqsat: false,
+        eqsat_time_limit: Duration::from_millis(5000),
+        truth_tables: default_truth_tables(),
+        input: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("option {} expects a value", name))
+        };
+        match arg.as_str() {
+            "-h" => return Ok(None),
+            "-b" => {
+                let width = value("-b")?;
+                options.bit_width = width
+                    .parse()
+                    .map_err(|_| format!("invalid bit width `{}`", width))?;
+            }
+            "-f" => options.file = Some(PathBuf::from(value("-f")?)),
+            "-s" => options.sexpr = true,
+            "-z" => options.verify = true,
+            "-e" => options.use_eqsat = true,
+            "-t" => {
+                let ms = value("-t")?;
+                let ms = ms
+                    .parse()
+                    .map_err(|_| format!("invalid time limit `{}`", ms))?;
+                options.eqsat_time_limit = Duration::from_millis(ms);
+            }
+            "-m" => options.truth_tables = PathBuf::from(value("-m")?),
+            _ => {
+                if let Some(input) = &options.input {
+                    return Err(format!(
+                        "found more than one expression argument, received both `{}` and `{}`",
+                        input, arg
+                    ));
+                }
+                options.input = Some(arg.clone());
+            }
+        }
+    }
+
+    if options.input.is_none() && options.file.is_none() {
+        return Ok(None);
+    }
+    if options.input.is_some() && options.file.is_some() {
+        return Err("an expression cannot be combined with -f".to_owned());
+    }
+    Ok(Some(options))
+}
+
+// Replace `e` with its minimized algebraic normal form if it is a purely bitwise function that gets cheaper.
+fn minimize_bitwise(ctx: &mut Context, tables: &TruthTables, e: Expr) -> eq_sat::Result<Expr> {
+    let vars = ctx.variables(e)?;
+    if ctx.class(e)? != AstClass::Bitwise || vars.is_empty() || vars.len() > MAX_ANF_VARS {
+        return Ok(e);
+    }
+
+    let table = ctx.truth_table(e, &vars)?;
+    let minimized = ctx.minimize_anf(tables, &vars, &table)?;
+    if ctx.cost(minimized)? < ctx.cost(e)? {
+        return Ok(minimized);
+    }
+    Ok(e)
+}
+
+fn simplify(
+    ctx: &mut Context,
+    tables: Option<&TruthTables>,
+    options: &Options,
+    e: Expr,
+) -> eq_sat::Result<Expr> {
+    let mut best = ctx.simplify(e)?;
+    if let Some(tables) = tables {
+        best = minimize_bitwise(ctx, tables, best)?;
+    }
+
+    if options.use_eqsat {
+        let mut egraph = EGraph::new();
+        let class = egraph.add(ctx, best)?;
+        let stop_reason = egraph.run(options.eqsat_time_limit, usize::MAX);
+        println!("Equality saturation stopped: {:?}", stop_reason);
+
+        let extracted = egraph.extract(ctx, class)?;
+        let extracted = ctx.simplify(extracted)?;
+        if ctx.cost(extracted)? < ctx.cost(best)? {
+            best = extracted;
+        }
+    }
+
+    Ok(best)
+}
+
+// Values are printed as signed integers of the variable's width.
+fn signed(value: u128, width: u8) -> i128 {
+    let shift = 128 - width as u32;
+    ((value << shift) as i128) >> shift
+}
+
+// Evaluate both expressions on the same inputs, starting with a few edge cases. Returns the first counterexample.
+fn find_counterexample(
+    ctx: &Context,
+    before: Expr,
+    after: Expr,
+    iterations: usize,
+) -> eq_sat::Result<Option<Vec<(Expr, u128)>>> {
+    let vars = ctx.variables(before)?;
+    let mut rng = rand::thread_rng();
+    for i in 0..iterations {
+        let mut inputs = Vec::with_capacity(vars.len());
+        for var in vars.iter() {
+            let value = match i {
+                0 => 0,
+                1 => 1,
+                2 => u128::MAX,
+                _ => rng.gen(),
+            };
+            inputs.push((*var, value));
+        }
+
+        if ctx.eval(before, &inputs)? != ctx.eval(after, &inputs)? {
+            return Ok(Some(inputs));
+        }
+    }
+
+    Ok(None)
+}
+
+const SOLVER: &str = "z3";
+
+// Check the query with an external solver. Returns whether the expressions are equivalent, or why it could not be decided.
+fn prove(query: &str) -> Result<bool, String> {
+    let mut child = Command::new(SOLVER)
+        .arg("-in")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", SOLVER, e))?;
+
+    // Dropping the handle closes the solver's input.
+    let mut stdin = child.stdin.take().unwrap();
+    stdin
+        .write_all(query.as_bytes())
+        .map_err(|e| format!("failed to write to {}: {}", SOLVER, e))?;
+    drop(stdin);
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run {}: {}", SOLVER, e))?;
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "unsat" => Ok(true),
+        "sat" => Ok(false),
+        answer => Err(format!("{} answered `{}`", SOLVER, answer)),
+    }
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let tables = match TruthTables::load(&options.truth_tables) {
+        Ok(tables) => Some(tables),
+        Err(e) => {
+            eprintln!(
+                "warning: ANF minimization is disabled, failed to load truth tables from {}: {} (use -m to set their location)",
+                options.truth_tables.display(),
+                e
+            );
+            None
+        }
+    };
+
+    let text = match &options.file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?,
+        None => options.input.clone().unwrap(),
+    };
+
+    let mut ctx = Context::new();
+    let exprs = if options.sexpr {
+        ctx.parse_sexprs(&text, options.bit_width)
+            .map_err(|e| e.to_string())?
+    } else if options.file.is_some() {
+        let mut exprs = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let e = ctx
+                .parse(line, options.bit_width)
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+            exprs.push(e);
+        }
+        exprs
+    } else {
+        match ctx.parse(&text, options.bit_width) {
+            Err(eq_sat::Error::Parse(e)) => return Err(e.render(&text)),
+            e => vec![e.map_err(|e| e.to_string())?],
+        }
+    };
+
+    // Only leaves whose width differs from the one given with `-b` are annotated.
+    let print_options = PrintOptions {
+        default_width: options.bit_width,
+        ..Default::default()
+    };
+    let print = |ctx: &Context, e: Expr| ctx.print_with(e, &print_options).map_err(|e| e.to_string());
+    for input in exprs {
+        println!("\nExpression: {}\n", print(&ctx, input)?);
+
+        let start = Instant::now();
+        let result = simplify(&mut ctx, tables.as_ref(), options, input).map_err(|e| e.to_string())?;
+        let elapsed = start.elapsed();
+
+        println!(
+            "Simplified to: {}\n\nwith cost: {}\ntook: {:.3} ms",
+            print(&ctx, result)?,
+            ctx.cost(result).map_err(|e| e.to_string())?,
+            elapsed.as_secs_f64() * 1000.0
+        );
+
+        if !options.verify {
+            continue;
+        }
+
+        const ITERATIONS: usize = 10000;
+        match find_counterexample(&ctx, input, result, ITERATIONS).map_err(|e| e.to_string())? {
+            None => {
+                println!("No counterexample found in {} inputs.", ITERATIONS);
+                let query = ctx
+                    .smtlib_equivalence_query(input, result)
+                    .map_err(|e| e.to_string())?;
+                match prove(&query) {
+                    Ok(true) => println!("Proven equivalent with {}.", SOLVER),
+                    Ok(false) => println!("Expressions are not equivalent according to {}.", SOLVER),
+                    Err(e) => println!("Equivalence not proven: {}.", e),
+                }
+            }
+            Some(inputs) => {
+                let values: Vec<String> = inputs
+                    .iter()
+                    .map(|(var, value)| {
+                        let name = ctx.variable_name(*var).unwrap().unwrap();
+                        let width = ctx.width(*var).unwrap();
+                        format!("{} = {}", name, signed(*value, width))
+                    })
+                    .collect();
+                println!(
+                    "Expressions are not equivalent. Counterexample:\n[{}]",
+                    values.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,274 @@
+// C99 backend. Every value is a `uint64_t`, and signed operations are built on top of unsigned arithmetic, so the
+// emitted code never relies on undefined or implementation-defined behavior.
+use std::fmt::Write;
+
+use crate::{
+    ffi::FfiResult,
+    simple_ast::{get_modulo_mask, AstIdx, Context, INodeUtil, SimpleAst},
+};
+
+use super::{check_fits_u64, comparison_operator, is_signed, trivial_comparison, value_operands, Schedule};
+
+const SIGN_BIT: &str = "0x8000000000000000ULL";
+
+// Helper functions in the order they must be defined, since some call `sext`.
+const HELPERS: [&str; 12] = [
+    "pow", "sext", "ashr", "udiv", "urem", "sdiv", "srem", "rotl", "rotr", "popcount", "ctlz", "cttz",
+];
+
+pub fn emit(ctx: &Context, root: AstIdx, vars: &[AstIdx], name: &str) -> FfiResult<String> {
+    check_fits_u64(ctx, root)?;
+    let schedule = Schedule::new(ctx, root, vars)?;
+    let mut used = Vec::new();
+    let mut body = String::new();
+    for (param, var) in schedule.params.iter().zip(vars.iter()) {
+        let width = ctx.arena.get_width(*var);
+        if width != 64 {
+            writeln!(body, "    {} &= {};", param, mask(width)).unwrap();
+        }
+    }
+
+    for idx in schedule.nodes.iter() {
+        let expr = expr(ctx, &schedule, *idx, name, &mut used);
+        writeln!(body, "    uint64_t {} = {};", schedule.name_of(*idx).unwrap(), expr).unwrap();
+    }
+    writeln!(body, "    return {};", value(ctx, &schedule, root)).unwrap();
+
+    if used.iter().any(|h| matches!(*h, "ashr" | "sdiv" | "srem")) {
+        used.push("sext");
+    }
+
+    let mut out = String::from("#include <stdint.h>\n\n");
+    for helper in HELPERS.iter().filter(|h| used.contains(h)) {
+        out.push_str(&helper_source(helper, name));
+        out.push('\n');
+    }
+
+    let params: Vec<String> = schedule.params.iter().map(|p| format!("uint64_t {}", p)).collect();
+    let params = if params.is_empty() { "void".to_string() } else { params.join(", ") };
+    write!(out, "uint64_t {}({}) {{\n{}}}\n", name, params, body).unwrap();
+    return Ok(out);
+}
+
+fn expr(ctx: &Context, schedule: &Schedule, idx: AstIdx, name: &str, used: &mut Vec<&'static str>) -> String {
+    let ast = ctx.arena.get_node(idx);
+    let operands = value_operands(ast);
+    let v = |i: usize| value(ctx, schedule, operands[i]);
+    let width = ctx.arena.get_width(idx);
+    let ow = ctx.arena.get_width(operands[0]);
+    let mut call = |helper: &'static str, args: String| {
+        if !used.contains(&helper) {
+            used.push(helper);
+        }
+        format!("{}_{}({})", name, helper, args)
+    };
+
+    match ast {
+        SimpleAst::Add(_) => masked(format!("{} + {}", v(0), v(1)), width),
+        SimpleAst::Mul(_) => masked(format!("{} * {}", v(0), v(1)), width),
+        SimpleAst::Pow(_) => masked(call("pow", format!("{}, {}", v(0), v(1))), width),
+        SimpleAst::And(_) => format!("{} & {}", v(0), v(1)),
+        SimpleAst::Or(_) => format!("{} | {}", v(0), v(1)),
+        SimpleAst::Xor(_) => format!("{} ^ {}", v(0), v(1)),
+        SimpleAst::Neg(_) => masked(format!("~{}", v(0)), width),
+        SimpleAst::Shl(_) => shift(ctx, operands[1], ow, masked(format!("{} << {}", v(0), v(1)), width), v(1)),
+        SimpleAst::Lshr(_) => shift(ctx, operands[1], ow, format!("{} >> {}", v(0), v(1)), v(1)),
+        SimpleAst::Ashr(_) => masked(call("ashr", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Udiv(_) => masked(call("udiv", format!("{}, {}", v(0), v(1))), width),
+        SimpleAst::Urem(_) => call("urem", format!("{}, {}", v(0), v(1))),
+        SimpleAst::Sdiv(_) => masked(call("sdiv", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Srem(_) => masked(call("srem", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Rotl(_) => masked(call("rotl", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Rotr(_) => masked(call("rotr", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Popcount(_) => call("popcount", v(0)),
+        SimpleAst::Ctlz(_) => call("ctlz", format!("{}, {}", v(0), ow)),
+        SimpleAst::Cttz(_) => call("cttz", format!("{}, {}", v(0), ow)),
+        SimpleAst::Zext(_) => v(0),
+        SimpleAst::Trunc(_) => masked(v(0), width),
+        SimpleAst::Sext(_) => masked(call("sext", format!("{}, {}", v(0), ow)), width),
+        SimpleAst::ICmp { predicate, .. } => {
+            let constant = |i: usize| ctx.arena.is_constant(operands[i]).then(|| ctx.arena.get_constant(operands[i]));
+            if let Some(result) = trivial_comparison(*predicate, constant(0), constant(1), ow) {
+                return (result as u64).to_string();
+            }
+
+            let op = comparison_operator(*predicate);
+            if !is_signed(*predicate) {
+                return format!("{} {} {}", v(0), op, v(1));
+            }
+
+            // Flipping the sign bit maps the signed order onto the unsigned order.
+            let a = call("sext", format!("{}, {}", v(0), ow));
+            let b = call("sext", format!("{}, {}", v(1), ow));
+            format!("({} ^ {}) {} ({} ^ {})", a, SIGN_BIT, op, b, SIGN_BIT)
+        }
+        SimpleAst::Select { .. } => format!("{} ? {} : {}", v(0), v(1), v(2)),
+        SimpleAst::Extract([_, _, low]) => match ctx.arena.get_constant(*low) {
+            0 => masked(v(0), width),
+            low => masked(format!("{} >> {}", v(0), low), width),
+        },
+        SimpleAst::Concat(_) => format!("({} << {}) | {}", v(0), ctx.arena.get_width(operands[1]), v(1)),
+        SimpleAst::Carry(_) => format!(
+            "({} & {}) | ({} & {}) | ({} & {})",
+            v(0),
+            v(1),
+            v(0),
+            v(2),
+            v(1),
+            v(2)
+        ),
+        SimpleAst::Constant { .. } | SimpleAst::Symbol { .. } => unreachable!(),
+    }
+}
+
+// Shifting by the width or more yields zero. Constant amounts are resolved here, so the compiler never sees an
+// oversized shift.
+fn shift(ctx: &Context, amount: AstIdx, width: u8, expr: String, amount_str: String) -> String {
+    if ctx.arena.is_constant(amount) {
+        return match ctx.arena.get_constant(amount) >= width as u128 {
+            true => "0".to_string(),
+            false => expr,
+        };
+    }
+
+    return format!("{} >= {} ? 0 : {}", amount_str, width, expr);
+}
+
+fn value(ctx: &Context, schedule: &Schedule, idx: AstIdx) -> String {
+    match schedule.name_of(idx) {
+        Some(name) => name,
+        None => literal(ctx.arena.get_constant(idx) as u64),
+    }
+}
+
+fn literal(c: u64) -> String {
+    match c < 256 {
+        true => format!("{}ULL", c),
+        false => format!("0x{:X}ULL", c),
+    }
+}
+
+fn mask(width: u8) -> String {
+    literal(get_modulo_mask(width) as u64)
+}
+
+fn masked(expr: String, width: u8) -> String {
+    match width {
+        64 => expr,
+        _ => format!("({}) & {}", expr, mask(width)),
+    }
+}
+
+fn helper_source(helper: &str, prefix: &str) -> String {
+    let source = match helper {
+        "pow" => {
+            "static uint64_t NAME_pow(uint64_t base, uint64_t exp) {
+    uint64_t result = 1;
+    while (exp != 0) {
+        if (exp & 1)
+            result *= base;
+        base *= base;
+        exp >>= 1;
+    }
+    return result;
+}
+"
+        }
+        "sext" => {
+            "static uint64_t NAME_sext(uint64_t a, unsigned w) {
+    uint64_t sign = (uint64_t)1 << (w - 1);
+    a &= sign | (sign - 1);
+    return (a ^ sign) - sign;
+}
+"
+        }
+        "ashr" => {
+            "static uint64_t NAME_ashr(uint64_t a, uint64_t b, unsigned w) {
+    uint64_t s = NAME_sext(a, w);
+    unsigned n = b > 63 ? 63 : (unsigned)b;
+    return (s >> 63) ? ~(~s >> n) : s >> n;
+}
+"
+        }
+        "udiv" => {
+            "static uint64_t NAME_udiv(uint64_t a, uint64_t b) {
+    return b == 0 ? UINT64_MAX : a / b;
+}
+"
+        }
+        "urem" => {
+            "static uint64_t NAME_urem(uint64_t a, uint64_t b) {
+    return b == 0 ? a : a % b;
+}
+"
+        }
+        "sdiv" => {
+            "static uint64_t NAME_sdiv(uint64_t a, uint64_t b, unsigned w) {
+    uint64_t sa = NAME_sext(a, w), sb = NAME_sext(b, w);
+    uint64_t na = sa >> 63, nb = sb >> 63;
+    if (sb == 0)
+        return na ? 1 : UINT64_MAX;
+    uint64_t q = (na ? -sa : sa) / (nb ? -sb : sb);
+    return na != nb ? -q : q;
+}
+"
+        }
+        "srem" => {
+            "static uint64_t NAME_srem(uint64_t a, uint64_t b, unsigned w) {
+    uint64_t sa = NAME_sext(a, w), sb = NAME_sext(b, w);
+    uint64_t na = sa >> 63, nb = sb >> 63;
+    if (sb == 0)
+        return a;
+    uint64_t r = (na ? -sa : sa) % (nb ? -sb : sb);
+    return na ? -r : r;
+}
+"
+        }
+        "rotl" => {
+            "static uint64_t NAME_rotl(uint64_t a, uint64_t b, unsigned w) {
+    uint64_t n = b % w;
+    return n == 0 ? a : (a << n) | (a >> (w - n));
+}
+"
+        }
+        "rotr" => {
+            "static uint64_t NAME_rotr(uint64_t a, uint64_t b, unsigned w) {
+    uint64_t n = b % w;
+    return n == 0 ? a : (a >> n) | (a << (w - n));
+}
+"
+        }
+        "popcount" => {
+            "static uint64_t NAME_popcount(uint64_t a) {
+    uint64_t n = 0;
+    for (; a != 0; a &= a - 1)
+        n++;
+    return n;
+}
+"
+        }
+        "ctlz" => {
+            "static uint64_t NAME_ctlz(uint64_t a, unsigned w) {
+    uint64_t n = w;
+    for (; a != 0; a >>= 1)
+        n--;
+    return n;
+}
+"
+        }
+        "cttz" => {
+            "static uint64_t NAME_cttz(uint64_t a, unsigned w) {
+    if (a == 0)
+        return w;
+    uint64_t n = 0;
+    for (; (a & 1) == 0; a >>= 1)
+        n++;
+    return n;
+}
+"
+        }
+        _ => unreachable!(),
+    };
+
+    return source.replace("NAME", prefix);
+}
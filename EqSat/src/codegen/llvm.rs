@@ -0,0 +1,295 @@
+// LLVM IR backend. Values use integer types of their exact width, so no masking is needed. Operations whose result is
+// poison or undefined in LLVM, such as oversized shifts and division by zero, are guarded with selects so that they
+// yield the same result as `eval_ast`.
+use std::fmt::Write;
+
+use crate::{
+    ffi::FfiResult,
+    simple_ast::{get_modulo_mask, sign_extend, AstIdx, Context, INodeUtil, Predicate, SimpleAst},
+};
+
+use super::{value_operands, Schedule};
+
+// Returns the literal for a constant of the given width, which LLVM expects to be signed.
+pub fn literal(c: u128, width: u8) -> String {
+    match width {
+        1 => (c & 1 != 0).to_string(),
+        _ => sign_extend(c, width).to_string(),
+    }
+}
+
+pub fn emit(ctx: &Context, root: AstIdx, vars: &[AstIdx], name: &str) -> FfiResult<String> {
+    let schedule = Schedule::new(ctx, root, vars)?;
+    let mut emitter = Emitter {
+        ctx,
+        schedule: &schedule,
+        name,
+        body: String::new(),
+        declarations: Vec::new(),
+        pow_widths: Vec::new(),
+    };
+    for idx in schedule.nodes.iter() {
+        emitter.emit_node(*idx);
+    }
+
+    let width = ctx.arena.get_width(root);
+    let params: Vec<String> = schedule
+        .params
+        .iter()
+        .zip(vars.iter())
+        .map(|(p, var)| format!("i{} %{}", ctx.arena.get_width(*var), p))
+        .collect();
+
+    let mut out = String::new();
+    for declaration in emitter.declarations.iter() {
+        writeln!(out, "{}", declaration).unwrap();
+    }
+    if !emitter.declarations.is_empty() {
+        out.push('\n');
+    }
+
+    writeln!(out, "define i{} @{}({}) {{", width, name, params.join(", ")).unwrap();
+    writeln!(out, "entry:").unwrap();
+    out.push_str(&emitter.body);
+    writeln!(out, "  ret i{} {}", width, emitter.value(root)).unwrap();
+    writeln!(out, "}}").unwrap();
+
+    for width in emitter.pow_widths.iter() {
+        out.push('\n');
+        out.push_str(&pow_source(name, *width));
+    }
+
+    return Ok(out);
+}
+
+struct Emitter<'a> {
+    ctx: &'a Context,
+    schedule: &'a Schedule,
+    name: &'a str,
+    body: String,
+    // Intrinsic declarations, in the order they were first used.
+    declarations: Vec<String>,
+    // Widths for which a `pow` helper function must be defined.
+    pow_widths: Vec<u8>,
+}
+
+impl Emitter<'_> {
+    fn value(&self, idx: AstIdx) -> String {
+        match self.schedule.name_of(idx) {
+            Some(name) => format!("%{}", name),
+            None => literal(self.ctx.arena.get_constant(idx), self.ctx.arena.get_width(idx)),
+        }
+    }
+
+    fn inst(&mut self, dest: &str, inst: String) {
+        writeln!(self.body, "  %{} = {}", dest, inst).unwrap();
+    }
+
+    // Returns the name of an intrinsic, declaring it on first use.
+    fn intrinsic(&mut self, name: &str, width: u8, params: &str) -> String {
+        let declaration = format!("declare i{} @llvm.{}.i{}({})", width, name, width, params);
+        if !self.declarations.contains(&declaration) {
+            self.declarations.push(declaration);
+        }
+
+        return format!("@llvm.{}.i{}", name, width);
+    }
+
+    // Converts a value between widths, using a no-op bitcast if they are equal.
+    fn resize(&mut self, dest: &str, op: &str, value: &str, from: u8, to: u8) {
+        let op = if from == to { "bitcast" } else { op };
+        self.inst(dest, format!("{} i{} {} to i{}", op, from, value, to));
+    }
+
+    fn emit_node(&mut self, idx: AstIdx) {
+        let ctx = self.ctx;
+        let ast = ctx.arena.get_node(idx);
+        let operands = value_operands(ast);
+        let t = self.schedule.name_of(idx).unwrap();
+        let v: Vec<String> = operands.iter().map(|o| self.value(*o)).collect();
+        let width = ctx.arena.get_width(idx);
+        let ow = ctx.arena.get_width(operands[0]);
+        let ty = format!("i{}", ow);
+        let lit = |c: u128| literal(c & get_modulo_mask(ow), ow);
+
+        let binop = |op: &str| format!("{} {} {}, {}", op, ty, v[0], v[1]);
+        match ast {
+            SimpleAst::Add(_) => self.inst(&t, binop("add")),
+            SimpleAst::Mul(_) => self.inst(&t, binop("mul")),
+            SimpleAst::And(_) => self.inst(&t, binop("and")),
+            SimpleAst::Or(_) => self.inst(&t, binop("or")),
+            SimpleAst::Xor(_) => self.inst(&t, binop("xor")),
+            SimpleAst::Neg(_) => self.inst(&t, format!("xor {} {}, {}", ty, v[0], lit(u128::MAX))),
+            SimpleAst::Pow(_) => {
+                if !self.pow_widths.contains(&ow) {
+                    self.pow_widths.push(ow);
+                }
+                let call = format!("call {} @{}.pow.{}({} {}, {} {})", ty, self.name, ty, ty, v[0], ty, v[1]);
+                self.inst(&t, call);
+            }
+            // Shifting by the width or more is poison, but yields zero in `eval_ast`.
+            SimpleAst::Shl(_) | SimpleAst::Lshr(_) => {
+                let op = if matches!(ast, SimpleAst::Shl(_)) { "shl" } else { "lshr" };
+                self.inst(&format!("{}.ok", t), format!("icmp ult {} {}, {}", ty, v[1], lit(ow as u128)));
+                self.inst(&format!("{}.s", t), binop(op));
+                self.inst(&t, format!("select i1 %{}.ok, {} %{}.s, {} {}", t, ty, t, ty, lit(0)));
+            }
+            // Oversized arithmetic shifts fill the result with the sign bit.
+            SimpleAst::Ashr(_) => {
+                let max = lit(ow as u128 - 1);
+                self.inst(&format!("{}.big", t), format!("icmp ugt {} {}, {}", ty, v[1], max));
+                self.inst(&format!("{}.n", t), format!("select i1 %{}.big, {} {}, {} {}", t, ty, max, ty, v[1]));
+                self.inst(&t, format!("ashr {} {}, %{}.n", ty, v[0], t));
+            }
+            // Division by zero is undefined, so a dummy divisor is used and the result replaced afterwards.
+            SimpleAst::Udiv(_) | SimpleAst::Urem(_) => {
+                let (op, by_zero) = match ast {
+                    SimpleAst::Udiv(_) => ("udiv", lit(u128::MAX)),
+                    _ => ("urem", v[0].clone()),
+                };
+                self.inst(&format!("{}.z", t), format!("icmp eq {} {}, {}", ty, v[1], lit(0)));
+                self.inst(&format!("{}.d", t), format!("select i1 %{}.z, {} {}, {} {}", t, ty, lit(1), ty, v[1]));
+                self.inst(&format!("{}.q", t), format!("{} {} {}, %{}.d", op, ty, v[0], t));
+                self.inst(&t, format!("select i1 %{}.z, {} {}, {} %{}.q", t, ty, by_zero, ty, t));
+            }
+            SimpleAst::Sdiv(_) | SimpleAst::Srem(_) => self.emit_signed_division(&t, ast, &v, ow),
+            SimpleAst::Rotl(_) | SimpleAst::Rotr(_) => {
+                let name = if matches!(ast, SimpleAst::Rotl(_)) { "fshl" } else { "fshr" };
+                let f = self.intrinsic(name, ow, &format!("{}, {}, {}", ty, ty, ty));
+                self.inst(&t, format!("call {} {}({} {}, {} {}, {} {})", ty, f, ty, v[0], ty, v[0], ty, v[1]));
+            }
+            SimpleAst::Popcount(_) => {
+                let f = self.intrinsic("ctpop", ow, &ty);
+                self.inst(&t, format!("call {} {}({} {})", ty, f, ty, v[0]));
+            }
+            SimpleAst::Ctlz(_) | SimpleAst::Cttz(_) => {
+                let name = if matches!(ast, SimpleAst::Ctlz(_)) { "ctlz" } else { "cttz" };
+                let f = self.intrinsic(name, ow, &format!("{}, i1", ty));
+                self.inst(&t, format!("call {} {}({} {}, i1 false)", ty, f, ty, v[0]));
+            }
+            SimpleAst::Zext(_) => self.resize(&t, "zext", &v[0], ow, width),
+            SimpleAst::Sext(_) => self.resize(&t, "sext", &v[0], ow, width),
+            SimpleAst::Trunc(_) => self.resize(&t, "trunc", &v[0], ow, width),
+            SimpleAst::ICmp { predicate, .. } => {
+                self.inst(&t, format!("icmp {} {} {}, {}", predicate_name(*predicate), ty, v[0], v[1]));
+            }
+            SimpleAst::Select { .. } => {
+                // Conditions wider than one bit are true if they are nonzero.
+                let cond = match ow {
+                    1 => v[0].clone(),
+                    _ => {
+                        self.inst(&format!("{}.c", t), format!("icmp ne {} {}, {}", ty, v[0], lit(0)));
+                        format!("%{}.c", t)
+                    }
+                };
+                let ty = format!("i{}", width);
+                self.inst(&t, format!("select i1 {}, {} {}, {} {}", cond, ty, v[1], ty, v[2]));
+            }
+            SimpleAst::Extract([_, _, low]) => match ctx.arena.get_constant(*low) {
+                0 => self.resize(&t, "trunc", &v[0], ow, width),
+                low => {
+                    self.inst(&format!("{}.s", t), format!("lshr {} {}, {}", ty, v[0], lit(low)));
+                    self.resize(&t, "trunc", &format!("%{}.s", t), ow, width);
+                }
+            },
+            SimpleAst::Concat(_) => {
+                let low_width = ctx.arena.get_width(operands[1]);
+                let wide = format!("i{}", width);
+                self.resize(&format!("{}.hi", t), "zext", &v[0], ow, width);
+                self.resize(&format!("{}.lo", t), "zext", &v[1], low_width, width);
+                self.inst(&format!("{}.sh", t), format!("shl {} %{}.hi, {}", wide, t, low_width));
+                self.inst(&t, format!("or {} %{}.sh, %{}.lo", wide, t, t));
+            }
+            SimpleAst::Carry(_) => {
+                self.inst(&format!("{}.ab", t), binop("and"));
+                self.inst(&format!("{}.ac", t), format!("and {} {}, {}", ty, v[0], v[2]));
+                self.inst(&format!("{}.bc", t), format!("and {} {}, {}", ty, v[1], v[2]));
+                self.inst(&format!("{}.o", t), format!("or {} %{}.ab, %{}.ac", ty, t, t));
+                self.inst(&t, format!("or {} %{}.o, %{}.bc", ty, t, t));
+            }
+            SimpleAst::Constant { .. } | SimpleAst::Symbol { .. } => unreachable!(),
+        }
+    }
+
+    // Signed division by zero and `MIN / -1` are undefined in LLVM. Both are computed with a dummy divisor of one,
+    // which already gives the wrapped result for the overflowing case.
+    fn emit_signed_division(&mut self, t: &str, ast: &SimpleAst, v: &[String], width: u8) {
+        let ty = format!("i{}", width);
+        let is_div = matches!(ast, SimpleAst::Sdiv(_));
+
+        // In a single bit, one is the same as minus one, so the only divisor that is not zero always overflows.
+        if width == 1 {
+            let inst = match is_div {
+                true => format!("select i1 {}, i1 {}, i1 true", v[1], v[0]),
+                false => format!("select i1 {}, i1 false, i1 {}", v[1], v[0]),
+            };
+            self.inst(t, inst);
+            return;
+        }
+
+        let min = literal(1 << (width - 1), width);
+        self.inst(&format!("{}.z", t), format!("icmp eq {} {}, 0", ty, v[1]));
+        self.inst(&format!("{}.m1", t), format!("icmp eq {} {}, -1", ty, v[1]));
+        self.inst(&format!("{}.min", t), format!("icmp eq {} {}, {}", ty, v[0], min));
+        self.inst(&format!("{}.ov", t), format!("and i1 %{}.m1, %{}.min", t, t));
+        self.inst(&format!("{}.bad", t), format!("or i1 %{}.z, %{}.ov", t, t));
+        self.inst(&format!("{}.d", t), format!("select i1 %{}.bad, {} 1, {} {}", t, ty, ty, v[1]));
+        if is_div {
+            self.inst(&format!("{}.q", t), format!("sdiv {} {}, %{}.d", ty, v[0], t));
+            // `x / 0` is one for negative dividends and all ones otherwise.
+            self.inst(&format!("{}.neg", t), format!("icmp slt {} {}, 0", ty, v[0]));
+            self.inst(&format!("{}.dz", t), format!("select i1 %{}.neg, {} 1, {} -1", t, ty, ty));
+            self.inst(t, format!("select i1 %{}.z, {} %{}.dz, {} %{}.q", t, ty, t, ty, t));
+        } else {
+            self.inst(&format!("{}.r", t), format!("srem {} {}, %{}.d", ty, v[0], t));
+            self.inst(t, format!("select i1 %{}.z, {} {}, {} %{}.r", t, ty, v[0], ty, t));
+        }
+    }
+}
+
+fn predicate_name(pred: Predicate) -> &'static str {
+    match pred {
+        Predicate::Eq => "eq",
+        Predicate::Ne => "ne",
+        Predicate::Ugt => "ugt",
+        Predicate::Uge => "uge",
+        Predicate::Ult => "ult",
+        Predicate::Ule => "ule",
+        Predicate::Sgt => "sgt",
+        Predicate::Sge => "sge",
+        Predicate::Slt => "slt",
+        Predicate::Sle => "sle",
+    }
+}
+
+// Square and multiply, with the helper named after the function so that several emitted functions can be linked
+// together.
+fn pow_source(name: &str, width: u8) -> String {
+    return format!(
+        "define internal i{w} @{name}.pow.i{w}(i{w} %base, i{w} %exp) {{
+entry:
+  br label %loop
+loop:
+  %result = phi i{w} [ {one}, %entry ], [ %result.next, %body ]
+  %b = phi i{w} [ %base, %entry ], [ %b.next, %body ]
+  %e = phi i{w} [ %exp, %entry ], [ %e.next, %body ]
+  %done = icmp eq i{w} %e, {zero}
+  br i1 %done, label %exit, label %body
+body:
+  %bit = and i{w} %e, {one}
+  %odd = icmp ne i{w} %bit, {zero}
+  %mul = mul i{w} %result, %b
+  %result.next = select i1 %odd, i{w} %mul, i{w} %result
+  %b.next = mul i{w} %b, %b
+  %e.next = lshr i{w} %e, {one}
+  br label %loop
+exit:
+  ret i{w} %result
+}}
+",
+        w = width,
+        name = name,
+        one = literal(1, width),
+        zero = literal(0, width)
+    );
+}
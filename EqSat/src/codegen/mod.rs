@@ -0,0 +1,490 @@
+// Backends for translating a DAG into source code for other tools.
+//
+// Every node reachable from the root, other than constants and the function parameters, is assigned to its own
+// temporary in post order, so shared nodes are computed once. The C and Rust backends store every value in a 64-bit
+// integer that is kept reduced modulo the width of its node, while the LLVM backend uses integer types of the exact
+// width.
+use ahash::AHashMap;
+use libc::c_char;
+
+use crate::{
+    eval::post_order,
+    ffi::{self, FfiError, FfiResult},
+    rewrite::num_value_operands,
+    simple_ast::{get_modulo_mask, AstIdx, Context, INodeUtil, Predicate, SimpleAst},
+};
+
+pub mod c;
+pub mod llvm;
+pub mod rust;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Backend {
+    C = 0,
+    Rust = 1,
+    LlvmIr = 2,
+}
+
+// The nodes of a function in the order they must be computed.
+pub(crate) struct Schedule {
+    // Nodes that are assigned to a temporary, in post order.
+    pub nodes: Vec<AstIdx>,
+    // Identifier of each parameter, parallel to the variables the function was requested with.
+    pub params: Vec<String>,
+    // Whether each parameter is referenced by the DAG.
+    pub used: Vec<bool>,
+    temps: AHashMap<AstIdx, usize>,
+    param_of: AHashMap<AstIdx, usize>,
+}
+
+impl Schedule {
+    // Fails if a parameter is not a symbol, or if the AST contains a symbol that is not present in `vars`.
+    pub fn new(ctx: &Context, root: AstIdx, vars: &[AstIdx]) -> FfiResult<Self> {
+        let mut params: Vec<String> = Vec::new();
+        for var in vars {
+            let SimpleAst::Symbol { id, .. } = ctx.arena.get_node(*var) else {
+                return Err(FfiError::invalid("function parameters must be symbols"));
+            };
+            let mut name = sanitize(&ctx.arena.get_symbol_name(*id));
+            // Symbols with the same name but different widths are distinct parameters, and names of the form `t0`
+            // are reserved for temporaries.
+            while params.contains(&name) || is_temp_name(&name) {
+                name.push('_');
+            }
+            params.push(name);
+        }
+
+        let param_of: AHashMap<AstIdx, usize> = vars.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+        // Constants are emitted inline, and the width operands of extensions and extracts are never referenced.
+        let mut nodes = Vec::new();
+        let mut temps = AHashMap::new();
+        let mut used = vec![false; vars.len()];
+        for idx in post_order(ctx, root) {
+            match ctx.arena.get_node(idx) {
+                SimpleAst::Constant { .. } => continue,
+                SimpleAst::Symbol { id, .. } => match param_of.get(&idx) {
+                    Some(p) => used[*p] = true,
+                    None => {
+                        return Err(FfiError::invalid(format!(
+                            "no parameter was provided for symbol {}",
+                            ctx.arena.get_symbol_name(*id)
+                        )))
+                    }
+                },
+                _ => {
+                    temps.insert(idx, nodes.len());
+                    nodes.push(idx);
+                }
+            }
+        }
+
+        return Ok(Schedule {
+            nodes,
+            params,
+            used,
+            temps,
+            param_of,
+        });
+    }
+
+    // Returns the parameter or temporary holding the value of a node, or `None` if the node is a constant.
+    pub fn name_of(&self, idx: AstIdx) -> Option<String> {
+        if let Some(p) = self.param_of.get(&idx) {
+            return Some(self.params[*p].clone());
+        }
+
+        return self.temps.get(&idx).map(|t| format!("t{}", t));
+    }
+}
+
+// Returns the value operands of a node, skipping constant widths and bit indices.
+pub(crate) fn value_operands(ast: &SimpleAst) -> &[AstIdx] {
+    use egg::Language;
+    &ast.children()[..num_value_operands(ast)]
+}
+
+// Returns the C and Rust operator for a comparison. Signed predicates must be applied to sign extended operands.
+pub(crate) fn comparison_operator(pred: Predicate) -> &'static str {
+    match pred {
+        Predicate::Eq => "==",
+        Predicate::Ne => "!=",
+        Predicate::Ugt | Predicate::Sgt => ">",
+        Predicate::Uge | Predicate::Sge => ">=",
+        Predicate::Ult | Predicate::Slt => "<",
+        Predicate::Ule | Predicate::Sle => "<=",
+    }
+}
+
+pub(crate) fn is_signed(pred: Predicate) -> bool {
+    matches!(pred, Predicate::Sgt | Predicate::Sge | Predicate::Slt | Predicate::Sle)
+}
+
+// Returns the result of an unsigned comparison against the smallest or largest value of a width, which is known
+// without looking at the other operand. Compilers tend to warn about these comparisons.
+pub(crate) fn trivial_comparison(pred: Predicate, a: Option<u128>, b: Option<u128>, width: u8) -> Option<bool> {
+    let max = get_modulo_mask(width);
+    match (pred, a, b) {
+        (Predicate::Uge, _, Some(0)) | (Predicate::Ule, Some(0), _) => Some(true),
+        (Predicate::Ult, _, Some(0)) | (Predicate::Ugt, Some(0), _) => Some(false),
+        (Predicate::Ule, _, Some(c)) | (Predicate::Uge, Some(c), _) if c == max => Some(true),
+        (Predicate::Ugt, _, Some(c)) | (Predicate::Ult, Some(c), _) if c == max => Some(false),
+        _ => None,
+    }
+}
+
+// Fails if a node is wider than 64 bits, which the C and Rust backends cannot represent.
+pub(crate) fn check_fits_u64(ctx: &Context, root: AstIdx) -> FfiResult<()> {
+    for idx in post_order(ctx, root) {
+        if ctx.arena.get_width(idx) > 64 {
+            return Err(FfiError::invalid("the C and Rust backends only support values of up to 64 bits"));
+        }
+    }
+    Ok(())
+}
+
+fn is_temp_name(name: &str) -> bool {
+    name.len() > 1 && name.starts_with('t') && name[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+fn sanitize(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+
+    return out;
+}
+
+// Translate the DAG rooted at `root` into a function named `name`, whose parameters are the variables in `vars`.
+pub fn emit(ctx: &Context, root: AstIdx, vars: &[AstIdx], backend: Backend, name: &str) -> FfiResult<String> {
+    let name = sanitize(name);
+    match backend {
+        Backend::C => c::emit(ctx, root, vars, &name),
+        Backend::Rust => rust::emit(ctx, root, vars, &name),
+        Backend::LlvmIr => llvm::emit(ctx, root, vars, &name),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextEmitCode(
+    ctx: *const Context,
+    id: AstIdx,
+    variables: *const AstIdx,
+    var_count: u64,
+    backend: u8,
+    name: *const c_char,
+) -> *mut c_char {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let backend = match backend {
+            0 => Backend::C,
+            1 => Backend::Rust,
+            2 => Backend::LlvmIr,
+            _ => return Err(FfiError::invalid(format!("invalid backend {}", backend))),
+        };
+        // A function without parameters may be requested with a null pointer.
+        let vars = ffi::slice(variables, var_count as usize, "variables")?;
+        ffi::nodes(ctx, vars)?;
+        let str = emit(ctx, ffi::node(ctx, id)?, vars, backend, &ffi::string(name)?)?;
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::PathBuf,
+        process::Command,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use crate::{
+        ast_parser::parse_ast,
+        simple_ast::{
+            collect_var_indices, eval_ast, get_modulo_mask, order_vars_alphabetically, Arena, INodeUtil,
+        },
+    };
+
+    use super::*;
+
+    const EXPRS: [&str; 14] = [
+        "(x & y) + (x | y) * 3 - (x ^ ~y)",
+        "(x ** 3) + (y ** x)",
+        "((x:i8 << y:i8) ^ (x:i8 >> y:i8)) + (x:i8 >>s y:i8)",
+        "(x:i16 / y:i16) + (x:i16 % y:i16) * 5",
+        "(x:i8 /s y:i8) ++ (x:i8 %s y:i8)",
+        "(x /s y) + (x %s y)",
+        "(x:i8 <<< y:i8) ++ (x:i8 >>> y:i8) ++ (x:i8 <<< 3)",
+        "popcount(x:i16) + ctlz(x:i16) * cttz(y:i16)",
+        "(x:i8 sx i32) + (y:i16 zx i32) + ((x tr i32) & 0xFFFF)",
+        "x:i16[11:4] ++ y:i16[15:15] ++ x:i16[3:0]",
+        "maj(x:i8, y:i8, z:i8) ++ (x:i8 <s y:i8 ? z:i8 : x:i8)",
+        "(x:i8 <=s y:i8 ? 1:i8 : 0:i8) + (x:i8 > y:i8 ? 2:i8 : 0:i8) + (x:i8 >=s 0 ? 4:i8 : 0:i8)",
+        "((x & 0xFF) * (y | 1)) ** 2",
+        "x:i1 ^ (y:i1 & z:i1)",
+    ];
+
+    const INPUTS: [u64; 12] = [
+        0,
+        1,
+        2,
+        3,
+        7,
+        0x7F,
+        0x80,
+        0xFF,
+        0x8000,
+        0xDEADBEEF,
+        0x8000000000000000,
+        u64::MAX,
+    ];
+
+    static NEXT_DIR: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let id = NEXT_DIR.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("eqsat-codegen-{}-{}", std::process::id(), id));
+        std::fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    fn run(cmd: &mut Command) -> String {
+        let output = cmd.output().unwrap();
+        assert!(
+            output.status.success(),
+            "{:?} failed:\n{}",
+            cmd,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return String::from_utf8(output.stdout).unwrap();
+    }
+
+    // A test case is an expression, its variables and one set of inputs per evaluation.
+    struct Case {
+        root: AstIdx,
+        vars: Vec<AstIdx>,
+        inputs: Vec<Vec<u64>>,
+        expected: Vec<u64>,
+    }
+
+    fn cases(ctx: &mut Context) -> Vec<Case> {
+        let mut cases = Vec::new();
+        for (n, s) in EXPRS.iter().enumerate() {
+            let root = parse_ast(ctx, s, 64).unwrap();
+            let mut set = std::collections::HashSet::new();
+            collect_var_indices(ctx, root, &mut set);
+            let mut vars = Vec::new();
+            order_vars_alphabetically(ctx, &set, &mut vars);
+
+            let mut inputs = Vec::new();
+            for i in 0..INPUTS.len() {
+                for j in 0..INPUTS.len() {
+                    let input: Vec<u64> = (0..vars.len())
+                        .map(|v| INPUTS[[i, j, (i + j + n) % INPUTS.len()][v]])
+                        .collect();
+                    inputs.push(input);
+                }
+            }
+
+            let expected = inputs
+                .iter()
+                .map(|input| {
+                    let mapping = vars.iter().copied().zip(input.iter().map(|v| *v as u128)).collect();
+                    eval_ast(ctx, root, &mapping) as u64
+                })
+                .collect();
+            cases.push(Case {
+                root,
+                vars,
+                inputs,
+                expected,
+            });
+        }
+
+        return cases;
+    }
+
+    fn check_output(output: &str, cases: &[Case]) {
+        let mut lines = output.lines();
+        for (n, case) in cases.iter().enumerate() {
+            for (input, expected) in case.inputs.iter().zip(case.expected.iter()) {
+                let actual: u64 = lines.next().unwrap().parse().unwrap();
+                assert_eq!(actual, *expected, "{} with inputs {:?}", EXPRS[n], input);
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "needs a C compiler, run with --ignored"]
+    fn c_round_trip() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let cases = cases(&mut ctx);
+        let mut src = String::from("#include <stdio.h>\n");
+        let mut main = String::from("int main(void) {\n");
+        for (n, case) in cases.iter().enumerate() {
+            src.push_str(&emit(&ctx, case.root, &case.vars, Backend::C, &format!("f{}", n)).unwrap());
+            for input in case.inputs.iter() {
+                let args: Vec<String> = input.iter().map(|v| format!("{}ULL", v)).collect();
+                main.push_str(&format!("    printf(\"%llu\\n\", (unsigned long long)f{}({}));\n", n, args.join(", ")));
+            }
+        }
+        main.push_str("    return 0;\n}\n");
+        src.push_str(&main);
+
+        let dir = temp_dir();
+        std::fs::write(dir.join("main.c"), src).unwrap();
+        run(Command::new("cc")
+            .args(["-std=c99", "-O1", "-Wall", "-Werror", "-Wno-unused-function", "-fsanitize=undefined"])
+            .arg("-fno-sanitize-recover=all")
+            .arg(dir.join("main.c"))
+            .arg("-o")
+            .arg(dir.join("main")));
+        check_output(&run(&mut Command::new(dir.join("main"))), &cases);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs rustc, run with --ignored"]
+    fn rust_round_trip() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let cases = cases(&mut ctx);
+        let mut src = String::new();
+        let mut main = String::from("fn main() {\n");
+        for (n, case) in cases.iter().enumerate() {
+            src.push_str(&emit(&ctx, case.root, &case.vars, Backend::Rust, &format!("f{}", n)).unwrap());
+            for input in case.inputs.iter() {
+                let args: Vec<String> = input.iter().map(|v| format!("{}", v)).collect();
+                main.push_str(&format!("    println!(\"{{}}\", f{}({}));\n", n, args.join(", ")));
+            }
+        }
+        main.push_str("}\n");
+        src.push_str(&main);
+
+        // Debug assertions catch any arithmetic overflow or oversized shift in the emitted code.
+        let dir = temp_dir();
+        std::fs::write(dir.join("main.rs"), src).unwrap();
+        run(Command::new("rustc")
+            .args(["--edition=2021", "-C", "debug-assertions=on", "-D", "warnings"])
+            .arg(dir.join("main.rs"))
+            .arg("-o")
+            .arg(dir.join("main")));
+        check_output(&run(&mut Command::new(dir.join("main"))), &cases);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs the LLVM interpreter lli, run with --ignored"]
+    fn llvm_round_trip() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let cases = cases(&mut ctx);
+        // Results are printed in decimal with `putchar`, which avoids depending on the pointer syntax of a specific
+        // LLVM version.
+        let mut src = String::from(
+            "declare i32 @putchar(i32)
+
+define void @print(i64 %v) {
+entry:
+  %q = udiv i64 %v, 10
+  %r = urem i64 %v, 10
+  %more = icmp ne i64 %q, 0
+  br i1 %more, label %rec, label %digit
+rec:
+  call void @print(i64 %q)
+  br label %digit
+digit:
+  %d = trunc i64 %r to i32
+  %c = add i32 %d, 48
+  call i32 @putchar(i32 %c)
+  ret void
+}
+
+",
+        );
+        let mut declarations = std::collections::BTreeSet::new();
+        let mut main = String::from("define i32 @main() {\n");
+        let mut counter = 0;
+        for (n, case) in cases.iter().enumerate() {
+            // Each function declares the intrinsics it uses, which must only be declared once per module.
+            let ir = emit(&ctx, case.root, &case.vars, Backend::LlvmIr, &format!("f{}", n)).unwrap();
+            for line in ir.lines() {
+                if line.starts_with("declare ") {
+                    declarations.insert(line.to_string());
+                } else {
+                    src.push_str(line);
+                    src.push('\n');
+                }
+            }
+            let width = ctx.arena.get_width(case.root);
+            let widen = if width == 64 { "bitcast" } else { "zext" };
+            for input in case.inputs.iter() {
+                let args: Vec<String> = input
+                    .iter()
+                    .zip(case.vars.iter())
+                    .map(|(v, var)| {
+                        let w = ctx.arena.get_width(*var);
+                        format!("i{} {}", w, llvm::literal(*v as u128 & get_modulo_mask(w), w))
+                    })
+                    .collect();
+                main.push_str(&format!("  %r{} = call i{} @f{}({})\n", counter, width, n, args.join(", ")));
+                main.push_str(&format!("  %z{} = {} i{} %r{} to i64\n", counter, widen, width, counter));
+                main.push_str(&format!("  call void @print(i64 %z{})\n", counter));
+                main.push_str("  call i32 @putchar(i32 10)\n");
+                counter += 1;
+            }
+        }
+        main.push_str("  ret i32 0\n}\n");
+        src.push_str(&main);
+        for declaration in declarations {
+            src.push_str(&declaration);
+            src.push('\n');
+        }
+
+        let dir = temp_dir();
+        std::fs::write(dir.join("main.ll"), src).unwrap();
+        check_output(&run(Command::new("lli").arg(dir.join("main.ll"))), &cases);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn emit_code_errors() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let root = parse_ast(&mut ctx, "x + y", 64).unwrap();
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let y = ctx.arena.symbol_with_name("y".to_string(), 64);
+        let wide = parse_ast(&mut ctx, "a:i128 + 1:i128", 64).unwrap();
+        let a = ctx.arena.symbol_with_name("a".to_string(), 128);
+        let name = c"f";
+
+        let emit_code = |root: AstIdx, vars: &[AstIdx], backend: u8| unsafe {
+            let str = ContextEmitCode(&ctx, root, vars.as_ptr(), vars.len() as u64, backend, name.as_ptr());
+            let status = ffi::GetLastStatus();
+            if !str.is_null() {
+                ffi::FreeString(str);
+            }
+            status
+        };
+
+        assert_eq!(emit_code(root, &[x, y], 2), ffi::FfiStatus::Ok);
+        assert_eq!(emit_code(root, &[x, y], 3), ffi::FfiStatus::InvalidArgument);
+        // Every symbol needs a parameter, and every parameter must be a symbol.
+        assert_eq!(emit_code(root, &[x], 0), ffi::FfiStatus::InvalidArgument);
+        assert_eq!(emit_code(root, &[x, y, root], 1), ffi::FfiStatus::InvalidArgument);
+        // Only the LLVM backend supports values wider than 64 bits.
+        assert_eq!(emit_code(wide, &[a], 0), ffi::FfiStatus::InvalidArgument);
+        assert_eq!(emit_code(wide, &[a], 1), ffi::FfiStatus::InvalidArgument);
+        assert_eq!(emit_code(wide, &[a], 2), ffi::FfiStatus::Ok);
+    }
+}
@@ -0,0 +1,247 @@
+// Rust backend. Every value is a `u64`, and the emitted function uses wrapping arithmetic and guarded shifts, so it
+// cannot panic when built with overflow checks. Helpers are nested inside the function to keep it self-contained.
+use std::fmt::Write;
+
+use crate::{
+    ffi::FfiResult,
+    simple_ast::{get_modulo_mask, AstIdx, Context, INodeUtil, SimpleAst},
+};
+
+use super::{check_fits_u64, comparison_operator, is_signed, trivial_comparison, value_operands, Schedule};
+
+const HELPERS: [&str; 9] = [
+    "pow", "sext", "ashr", "udiv", "urem", "sdiv", "srem", "rotl", "rotr",
+];
+
+pub fn emit(ctx: &Context, root: AstIdx, vars: &[AstIdx], name: &str) -> FfiResult<String> {
+    check_fits_u64(ctx, root)?;
+    let schedule = Schedule::new(ctx, root, vars)?;
+    let mut used = Vec::new();
+    let mut body = String::new();
+    for (i, var) in vars.iter().enumerate() {
+        let width = ctx.arena.get_width(*var);
+        if schedule.used[i] && width != 64 {
+            let param = &schedule.params[i];
+            writeln!(body, "    let {} = {} & {};", param, param, mask(width)).unwrap();
+        }
+    }
+
+    for idx in schedule.nodes.iter() {
+        let expr = expr(ctx, &schedule, *idx, &mut used);
+        writeln!(body, "    let {} = {};", schedule.name_of(*idx).unwrap(), expr).unwrap();
+    }
+    writeln!(body, "    {}", value(ctx, &schedule, root)).unwrap();
+
+    if used.iter().any(|h| matches!(*h, "ashr" | "sdiv" | "srem")) {
+        used.push("sext");
+    }
+
+    let mut helpers = String::new();
+    for helper in HELPERS.iter().filter(|h| used.contains(h)) {
+        helpers.push_str(helper_source(helper));
+    }
+
+    // Unused parameters are prefixed with an underscore to silence warnings.
+    let params: Vec<String> = schedule
+        .params
+        .iter()
+        .zip(schedule.used.iter())
+        .map(|(p, used)| if *used { format!("{}: u64", p) } else { format!("_{}: u64", p) })
+        .collect();
+    return Ok(format!("pub fn {}({}) -> u64 {{\n{}{}}}\n", name, params.join(", "), helpers, body));
+}
+
+fn expr(ctx: &Context, schedule: &Schedule, idx: AstIdx, used: &mut Vec<&'static str>) -> String {
+    let ast = ctx.arena.get_node(idx);
+    let operands = value_operands(ast);
+    let v = |i: usize| value(ctx, schedule, operands[i]);
+    let width = ctx.arena.get_width(idx);
+    let ow = ctx.arena.get_width(operands[0]);
+    let mut call = |helper: &'static str, args: String| {
+        if !used.contains(&helper) {
+            used.push(helper);
+        }
+        format!("{}({})", helper, args)
+    };
+
+    match ast {
+        SimpleAst::Add(_) => masked(format!("{}.wrapping_add({})", v(0), v(1)), width),
+        SimpleAst::Mul(_) => masked(format!("{}.wrapping_mul({})", v(0), v(1)), width),
+        SimpleAst::Pow(_) => masked(call("pow", format!("{}, {}", v(0), v(1))), width),
+        SimpleAst::And(_) => format!("{} & {}", v(0), v(1)),
+        SimpleAst::Or(_) => format!("{} | {}", v(0), v(1)),
+        SimpleAst::Xor(_) => format!("{} ^ {}", v(0), v(1)),
+        SimpleAst::Neg(_) => masked(format!("!{}", v(0)), width),
+        SimpleAst::Shl(_) => {
+            let shl = match width {
+                64 => format!("{} << {}", v(0), v(1)),
+                _ => format!("({} << {}) & {}", v(0), v(1), mask(width)),
+            };
+            shift(ctx, operands[1], ow, shl, v(1))
+        }
+        SimpleAst::Lshr(_) => shift(ctx, operands[1], ow, format!("{} >> {}", v(0), v(1)), v(1)),
+        SimpleAst::Ashr(_) => masked(call("ashr", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Udiv(_) => masked(call("udiv", format!("{}, {}", v(0), v(1))), width),
+        SimpleAst::Urem(_) => call("urem", format!("{}, {}", v(0), v(1))),
+        SimpleAst::Sdiv(_) => masked(call("sdiv", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Srem(_) => masked(call("srem", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Rotl(_) => masked(call("rotl", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Rotr(_) => masked(call("rotr", format!("{}, {}, {}", v(0), v(1), ow)), width),
+        SimpleAst::Popcount(_) => format!("{}.count_ones() as u64", v(0)),
+        SimpleAst::Ctlz(_) => match ow {
+            64 => format!("{}.leading_zeros() as u64", v(0)),
+            _ => format!("({}.leading_zeros() - {}) as u64", v(0), 64 - ow as u32),
+        },
+        SimpleAst::Cttz(_) => format!("if {} == 0 {{ {} }} else {{ {}.trailing_zeros() as u64 }}", v(0), ow, v(0)),
+        SimpleAst::Zext(_) => v(0),
+        SimpleAst::Trunc(_) => masked(v(0), width),
+        SimpleAst::Sext(_) => masked(call("sext", format!("{}, {}", v(0), ow)), width),
+        SimpleAst::ICmp { predicate, .. } => {
+            let constant = |i: usize| ctx.arena.is_constant(operands[i]).then(|| ctx.arena.get_constant(operands[i]));
+            if let Some(result) = trivial_comparison(*predicate, constant(0), constant(1), ow) {
+                return format!("{}u64", result as u64);
+            }
+
+            let op = comparison_operator(*predicate);
+            if !is_signed(*predicate) {
+                return format!("({} {} {}) as u64", v(0), op, v(1));
+            }
+
+            let a = call("sext", format!("{}, {}", v(0), ow));
+            let b = call("sext", format!("{}, {}", v(1), ow));
+            format!("(({} as i64) {} ({} as i64)) as u64", a, op, b)
+        }
+        SimpleAst::Select { .. } => format!("if {} != 0 {{ {} }} else {{ {} }}", v(0), v(1), v(2)),
+        SimpleAst::Extract([_, _, low]) => match ctx.arena.get_constant(*low) {
+            0 => masked(v(0), width),
+            low => masked(format!("({} >> {})", v(0), low), width),
+        },
+        SimpleAst::Concat(_) => format!("({} << {}) | {}", v(0), ctx.arena.get_width(operands[1]), v(1)),
+        SimpleAst::Carry(_) => format!(
+            "({} & {}) | ({} & {}) | ({} & {})",
+            v(0),
+            v(1),
+            v(0),
+            v(2),
+            v(1),
+            v(2)
+        ),
+        SimpleAst::Constant { .. } | SimpleAst::Symbol { .. } => unreachable!(),
+    }
+}
+
+// Shifting by the width or more yields zero. Constant amounts are resolved here, since a shift by 64 or more panics.
+fn shift(ctx: &Context, amount: AstIdx, width: u8, expr: String, amount_str: String) -> String {
+    if ctx.arena.is_constant(amount) {
+        return match ctx.arena.get_constant(amount) >= width as u128 {
+            true => "0u64".to_string(),
+            false => expr,
+        };
+    }
+
+    return format!("if {} >= {} {{ 0 }} else {{ {} }}", amount_str, width, expr);
+}
+
+fn value(ctx: &Context, schedule: &Schedule, idx: AstIdx) -> String {
+    match schedule.name_of(idx) {
+        Some(name) => name,
+        None => literal(ctx.arena.get_constant(idx) as u64),
+    }
+}
+
+fn literal(c: u64) -> String {
+    match c < 256 {
+        true => format!("{}u64", c),
+        false => format!("0x{:X}u64", c),
+    }
+}
+
+fn mask(width: u8) -> String {
+    literal(get_modulo_mask(width) as u64)
+}
+
+// The operand must not contain an operator with lower precedence than `&`.
+fn masked(expr: String, width: u8) -> String {
+    match width {
+        64 => expr,
+        _ => format!("{} & {}", expr, mask(width)),
+    }
+}
+
+fn helper_source(helper: &str) -> &'static str {
+    match helper {
+        "pow" => {
+            "    fn pow(mut base: u64, mut exp: u64) -> u64 {
+        let mut result = 1u64;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = result.wrapping_mul(base);
+            }
+            base = base.wrapping_mul(base);
+            exp >>= 1;
+        }
+        result
+    }
+"
+        }
+        "sext" => {
+            "    fn sext(a: u64, w: u32) -> u64 {
+        (((a << (64 - w)) as i64) >> (64 - w)) as u64
+    }
+"
+        }
+        "ashr" => {
+            "    fn ashr(a: u64, b: u64, w: u32) -> u64 {
+        ((sext(a, w) as i64) >> b.min(63)) as u64
+    }
+"
+        }
+        "udiv" => {
+            "    fn udiv(a: u64, b: u64) -> u64 {
+        if b == 0 { u64::MAX } else { a / b }
+    }
+"
+        }
+        "urem" => {
+            "    fn urem(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { a % b }
+    }
+"
+        }
+        "sdiv" => {
+            "    fn sdiv(a: u64, b: u64, w: u32) -> u64 {
+        let (sa, sb) = (sext(a, w) as i64, sext(b, w) as i64);
+        if sb == 0 {
+            return if sa < 0 { 1 } else { u64::MAX };
+        }
+        sa.wrapping_div(sb) as u64
+    }
+"
+        }
+        "srem" => {
+            "    fn srem(a: u64, b: u64, w: u32) -> u64 {
+        let (sa, sb) = (sext(a, w) as i64, sext(b, w) as i64);
+        if sb == 0 {
+            return a;
+        }
+        sa.wrapping_rem(sb) as u64
+    }
+"
+        }
+        "rotl" => {
+            "    fn rotl(a: u64, b: u64, w: u32) -> u64 {
+        let n = b % w as u64;
+        if n == 0 { a } else { (a << n) | (a >> (w as u64 - n)) }
+    }
+"
+        }
+        "rotr" => {
+            "    fn rotr(a: u64, b: u64, w: u32) -> u64 {
+        let n = b % w as u64;
+        if n == 0 { a } else { (a >> n) | (a << (w as u64 - n)) }
+    }
+"
+        }
+        _ => unreachable!(),
+    }
+}
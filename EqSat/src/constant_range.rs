@@ -0,0 +1,899 @@
+// Constant range analysis, following LLVM's `ConstantRange`.
+//
+// A range is the half-open interval [lower, upper) of `width` bit values, which may wrap around from the largest
+// unsigned value to zero. Since lower == upper cannot describe a non-trivial set, it encodes the full set if both are
+// all ones and the empty set if both are zero. A range can express facts like "x < 17" that known bits cannot, while
+// known bits can express e.g. "x is even", so the two analyses are computed side by side and used to tighten each other.
+use crate::known_bits::KnownBits;
+use crate::simple_ast::{get_modulo_mask, lshr_const, sign_extend, Predicate};
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Default)]
+pub struct ConstantRange {
+    pub width: u32,
+
+    // Inclusive lower bound.
+    pub lower: u128,
+
+    // Exclusive upper bound.
+    pub upper: u128,
+}
+
+impl ConstantRange {
+    // The range [lower, upper), which must be neither full nor empty.
+    pub fn new(width: u8, lower: u128, upper: u128) -> Self {
+        let mask = get_modulo_mask(width);
+        let (lower, upper) = (lower & mask, upper & mask);
+        assert!(lower != upper, "full and empty ranges must be created with `full` or `empty`");
+        Self {
+            width: width as u32,
+            lower,
+            upper,
+        }
+    }
+
+    pub fn full(width: u8) -> Self {
+        let mask = get_modulo_mask(width);
+        Self {
+            width: width as u32,
+            lower: mask,
+            upper: mask,
+        }
+    }
+
+    pub fn empty(width: u8) -> Self {
+        Self {
+            width: width as u32,
+            lower: 0,
+            upper: 0,
+        }
+    }
+
+    pub fn constant(c: u128, width: u8) -> Self {
+        return Self::new(width, c, c.wrapping_add(1));
+    }
+
+    // The values from `min` up to and including `max`, wrapping around if `max` is smaller than `min`.
+    pub fn from_bounds(width: u8, min: u128, max: u128) -> Self {
+        let mask = get_modulo_mask(width);
+        let (lower, upper) = (min & mask, max.wrapping_add(1) & mask);
+        if lower == upper {
+            return Self::full(width);
+        }
+
+        return Self::new(width, lower, upper);
+    }
+
+    // The values from zero up to and including `max`.
+    pub fn at_most(width: u8, max: u128) -> Self {
+        return Self::from_bounds(width, 0, max.min(get_modulo_mask(width)));
+    }
+
+    // The smallest range containing every value with the given known bits.
+    pub fn from_known_bits(kb: &KnownBits) -> Self {
+        let width = kb.width as u8;
+        let max = !kb.zeroes & get_modulo_mask(width);
+        let unsigned = Self::from_bounds(width, kb.ones, max);
+
+        // If the sign bit is unknown, the values are also contiguous when viewed as signed integers.
+        let sign_bit = 1u128 << (width - 1);
+        if (kb.zeroes | kb.ones) & sign_bit != 0 {
+            return unsigned;
+        }
+
+        let signed = Self::from_bounds(width, kb.ones | sign_bit, max & !sign_bit);
+        return unsigned.intersect(&signed);
+    }
+
+    // The bits shared by the smallest and largest value, viewed as both unsigned and signed integers.
+    pub fn to_known_bits(&self) -> KnownBits {
+        let width = self.width as u8;
+        if self.is_full() || self.is_empty() {
+            return KnownBits::empty(width);
+        }
+
+        let mask = get_modulo_mask(width);
+        let unsigned = Self::common_prefix(width, self.umin(), self.umax());
+        let signed = Self::common_prefix(width, self.smin() as u128 & mask, self.smax() as u128 & mask);
+        return unsigned.union(&signed);
+    }
+
+    fn common_prefix(width: u8, a: u128, b: u128) -> KnownBits {
+        let known = u128::MAX.checked_shl(128 - (a ^ b).leading_zeros()).unwrap_or(0) & get_modulo_mask(width);
+        return KnownBits::new(width, known & !a, known & a);
+    }
+
+    pub fn is_full(&self) -> bool {
+        return self.lower == self.upper && self.lower != 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.lower == self.upper && self.lower == 0;
+    }
+
+    // Whether the range wraps around from the largest unsigned value to zero, not counting ranges ending at zero.
+    fn is_wrapped(&self) -> bool {
+        return self.lower > self.upper && self.upper != 0;
+    }
+
+    fn is_upper_wrapped(&self) -> bool {
+        return self.lower > self.upper;
+    }
+
+    pub fn as_constant(&self) -> Option<u128> {
+        if self.is_full() || self.is_empty() || self.size() != 1 {
+            return None;
+        }
+
+        return Some(self.lower);
+    }
+
+    pub fn contains(&self, value: u128) -> bool {
+        if self.is_full() {
+            return true;
+        }
+        if self.lower <= self.upper {
+            return self.lower <= value && value < self.upper;
+        }
+
+        return self.lower <= value || value < self.upper;
+    }
+
+    // The number of values, which is meaningless for the full range.
+    fn size(&self) -> u128 {
+        return self.upper.wrapping_sub(self.lower) & get_modulo_mask(self.width as u8);
+    }
+
+    fn is_smaller_than(&self, other: &ConstantRange) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        if other.is_full() {
+            return true;
+        }
+
+        return self.size() < other.size();
+    }
+
+    fn smaller(a: ConstantRange, b: ConstantRange) -> ConstantRange {
+        return if b.is_smaller_than(&a) { b } else { a };
+    }
+
+    pub fn umin(&self) -> u128 {
+        if self.is_full() || self.is_wrapped() {
+            return 0;
+        }
+
+        return self.lower;
+    }
+
+    pub fn umax(&self) -> u128 {
+        let mask = get_modulo_mask(self.width as u8);
+        if self.is_full() || self.is_upper_wrapped() {
+            return mask;
+        }
+
+        return self.upper.wrapping_sub(1) & mask;
+    }
+
+    pub fn smin(&self) -> i128 {
+        let sign_bit = self.sign_bit();
+        return sign_extend(self.flip_sign().umin() ^ sign_bit, self.width as u8);
+    }
+
+    pub fn smax(&self) -> i128 {
+        let sign_bit = self.sign_bit();
+        return sign_extend(self.flip_sign().umax() ^ sign_bit, self.width as u8);
+    }
+
+    fn sign_bit(&self) -> u128 {
+        return 1u128 << (self.width - 1);
+    }
+
+    // Offset the range by the sign bit, which maps the signed order onto the unsigned order.
+    fn flip_sign(&self) -> ConstantRange {
+        if self.is_full() || self.is_empty() {
+            return *self;
+        }
+
+        let sign_bit = self.sign_bit();
+        return Self::new(self.width as u8, self.lower.wrapping_add(sign_bit), self.upper.wrapping_add(sign_bit));
+    }
+
+    // The smallest range containing both ranges.
+    pub fn union(&self, other: &ConstantRange) -> ConstantRange {
+        let width = self.width as u8;
+        if self.is_full() || other.is_empty() {
+            return *self;
+        }
+        if other.is_full() || self.is_empty() {
+            return *other;
+        }
+        if !self.is_upper_wrapped() && other.is_upper_wrapped() {
+            return other.union(self);
+        }
+
+        let (lower, upper) = (self.lower, self.upper);
+        if !self.is_upper_wrapped() {
+            // Both ranges are disjoint, so the gap on either side can be closed.
+            if other.upper < lower || upper < other.lower {
+                return Self::smaller(Self::new(width, lower, other.upper), Self::new(width, other.lower, upper));
+            }
+
+            let l = lower.min(other.lower);
+            let u = if other.upper.wrapping_sub(1) > upper.wrapping_sub(1) { other.upper } else { upper };
+            if l == 0 && u & get_modulo_mask(width) == 0 {
+                return Self::full(width);
+            }
+
+            return Self::new(width, l, u);
+        }
+
+        if !other.is_upper_wrapped() {
+            if other.upper <= upper || other.lower >= lower {
+                return *self;
+            }
+            if other.lower <= upper && lower <= other.upper {
+                return Self::full(width);
+            }
+            if upper < other.lower && other.upper < lower {
+                return Self::smaller(Self::new(width, lower, other.upper), Self::new(width, other.lower, upper));
+            }
+            if upper < other.lower && lower <= other.upper {
+                return Self::new(width, other.lower, upper);
+            }
+
+            return Self::new(width, lower, other.upper);
+        }
+
+        // Both ranges wrap around, so they overlap at the largest value.
+        if other.lower <= upper || lower <= other.upper {
+            return Self::full(width);
+        }
+
+        return Self::new(width, lower.min(other.lower), upper.max(other.upper));
+    }
+
+    // The smallest range containing the values of both ranges. If these are two disjoint pieces, the smaller of the two
+    // ranges is returned.
+    pub fn intersect(&self, other: &ConstantRange) -> ConstantRange {
+        let width = self.width as u8;
+        if self.is_empty() || other.is_full() {
+            return *self;
+        }
+        if other.is_empty() || self.is_full() {
+            return *other;
+        }
+        if !self.is_upper_wrapped() && other.is_upper_wrapped() {
+            return other.intersect(self);
+        }
+
+        let (lower, upper) = (self.lower, self.upper);
+        if !self.is_upper_wrapped() {
+            if lower < other.lower {
+                if upper <= other.lower {
+                    return Self::empty(width);
+                }
+                if upper < other.upper {
+                    return Self::new(width, other.lower, upper);
+                }
+
+                return *other;
+            }
+            if upper < other.upper {
+                return *self;
+            }
+            if lower < other.upper {
+                return Self::new(width, lower, other.upper);
+            }
+
+            return Self::empty(width);
+        }
+
+        if !other.is_upper_wrapped() {
+            if other.lower < upper {
+                if other.upper < upper {
+                    return *other;
+                }
+                if other.upper <= lower {
+                    return Self::new(width, other.lower, upper);
+                }
+
+                return Self::smaller(*self, *other);
+            }
+            if other.lower < lower {
+                if other.upper <= lower {
+                    return Self::empty(width);
+                }
+
+                return Self::new(width, lower, other.upper);
+            }
+
+            return *other;
+        }
+
+        if other.upper < upper {
+            if other.lower < upper {
+                return Self::smaller(*self, *other);
+            }
+            if other.lower < lower {
+                return Self::new(width, lower, other.upper);
+            }
+
+            return *other;
+        }
+        if other.upper <= lower {
+            if other.lower < lower {
+                return *self;
+            }
+
+            return Self::new(width, other.lower, upper);
+        }
+
+        return Self::smaller(*self, *other);
+    }
+
+    pub fn add(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+        if lhs.is_full() || rhs.is_full() {
+            return Self::full(width);
+        }
+
+        let mask = get_modulo_mask(width);
+        let lower = lhs.lower.wrapping_add(rhs.lower) & mask;
+        let upper = lhs.upper.wrapping_add(rhs.upper).wrapping_sub(1) & mask;
+        if lower == upper {
+            return Self::full(width);
+        }
+
+        // If the sum is smaller than either operand, it wrapped around more than once.
+        let result = Self::new(width, lower, upper);
+        if result.is_smaller_than(lhs) || result.is_smaller_than(rhs) {
+            return Self::full(width);
+        }
+
+        return result;
+    }
+
+    // The products of the unsigned and of the signed bounds, if they do not overflow.
+    pub fn mul(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        let unsigned = match lhs.umax().checked_mul(rhs.umax()) {
+            Some(max) if max <= get_modulo_mask(width) => Self::from_bounds(width, lhs.umin() * rhs.umin(), max),
+            _ => Self::full(width),
+        };
+
+        let products = [
+            lhs.smin().checked_mul(rhs.smin()),
+            lhs.smin().checked_mul(rhs.smax()),
+            lhs.smax().checked_mul(rhs.smin()),
+            lhs.smax().checked_mul(rhs.smax()),
+        ];
+        let fits = |p: &Option<i128>| p.map_or(false, |p| sign_extend(p as u128, width) == p);
+        let signed = match products.iter().all(fits) {
+            true => {
+                let min = products.iter().map(|p| p.unwrap()).min().unwrap();
+                let max = products.iter().map(|p| p.unwrap()).max().unwrap();
+                Self::from_bounds(width, min as u128, max as u128)
+            }
+            false => Self::full(width),
+        };
+
+        return unsigned.intersect(&signed);
+    }
+
+    // The result is at most the smaller operand.
+    pub fn and(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        return Self::at_most(width, lhs.umax().min(rhs.umax()));
+    }
+
+    // The result is at least the larger operand.
+    pub fn or(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        return Self::from_bounds(width, lhs.umin().max(rhs.umin()), get_modulo_mask(width));
+    }
+
+    // ~x is -1 - x, which maps [lower, upper) onto [-upper, -lower).
+    pub fn neg(lhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || lhs.is_full() {
+            return *lhs;
+        }
+
+        return Self::new(width, lhs.upper.wrapping_neg(), lhs.lower.wrapping_neg());
+    }
+
+    pub fn shl(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        // Only shifts that cannot move a set bit out of the value preserve the order.
+        let max_shift = rhs.umax();
+        let leading_zeroes = lhs.umax().leading_zeros() - (128 - width as u32);
+        if max_shift >= width as u128 || max_shift > leading_zeroes as u128 {
+            return Self::full(width);
+        }
+
+        return Self::from_bounds(width, lhs.umin() << rhs.umin(), lhs.umax() << max_shift);
+    }
+
+    pub fn lshr(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        let min = lshr_const(lhs.umin(), rhs.umax(), width);
+        let max = lshr_const(lhs.umax(), rhs.umin(), width);
+        return Self::from_bounds(width, min, max);
+    }
+
+    // Negative values grow towards -1 and positive values shrink towards 0 as the shift amount increases.
+    pub fn ashr(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        let (min_shift, max_shift) = (rhs.umin().min(127) as u32, rhs.umax().min(127) as u32);
+        let (smin, smax) = (lhs.smin(), lhs.smax());
+        let min = if smin < 0 { smin >> min_shift } else { smin >> max_shift };
+        let max = if smax < 0 { smax >> max_shift } else { smax >> min_shift };
+        return Self::from_bounds(width, min as u128, max as u128);
+    }
+
+    // Division by zero yields all ones.
+    pub fn udiv(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        let mask = get_modulo_mask(width);
+        if rhs.umax() == 0 {
+            return Self::constant(mask, width);
+        }
+
+        let min = lhs.umin() / rhs.umax();
+        let max = if rhs.umin() == 0 { mask } else { lhs.umax() / rhs.umin() };
+        return Self::from_bounds(width, min, max);
+    }
+
+    // The remainder is smaller than the divisor, unless the divisor is zero, in which case it is the dividend.
+    pub fn urem(lhs: &ConstantRange, rhs: &ConstantRange) -> ConstantRange {
+        let width = lhs.width as u8;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Self::empty(width);
+        }
+        if lhs.umax() < rhs.umin() {
+            return *lhs;
+        }
+
+        let max = match rhs.umin() {
+            0 => lhs.umax(),
+            _ => lhs.umax().min(rhs.umax() - 1),
+        };
+        return Self::at_most(width, max);
+    }
+
+    pub fn zext(lhs: &ConstantRange, width: u32) -> ConstantRange {
+        let width = width as u8;
+        if lhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        return Self::from_bounds(width, lhs.umin(), lhs.umax());
+    }
+
+    pub fn sext(lhs: &ConstantRange, width: u32) -> ConstantRange {
+        let width = width as u8;
+        if lhs.is_empty() {
+            return Self::empty(width);
+        }
+
+        return Self::from_bounds(width, lhs.smin() as u128, lhs.smax() as u128);
+    }
+
+    // A contiguous set of fewer than 2**width values stays contiguous when truncated.
+    pub fn trunc(lhs: &ConstantRange, width: u32) -> ConstantRange {
+        let width = width as u8;
+        if lhs.is_empty() {
+            return Self::empty(width);
+        }
+        if lhs.is_full() || lhs.size() > get_modulo_mask(width) {
+            return Self::full(width);
+        }
+
+        return Self::new(width, lhs.lower, lhs.upper);
+    }
+
+    pub fn extract(lhs: &ConstantRange, high: u8, low: u8) -> ConstantRange {
+        let shifted = Self::lshr(lhs, &Self::constant(low as u128, lhs.width as u8));
+        return Self::trunc(&shifted, (high - low + 1) as u32);
+    }
+
+    pub fn concat(high: &ConstantRange, low: &ConstantRange) -> ConstantRange {
+        let width = (high.width + low.width) as u8;
+        if high.is_empty() || low.is_empty() {
+            return Self::empty(width);
+        }
+
+        let min = (high.umin() << low.width) | low.umin();
+        let max = (high.umax() << low.width) | low.umax();
+        return Self::from_bounds(width, min, max);
+    }
+
+    pub fn select(a: &ConstantRange, b: &ConstantRange, c: &ConstantRange) -> ConstantRange {
+        return match a.as_constant() {
+            Some(0) => *c,
+            Some(_) => *b,
+            None => b.union(c),
+        };
+    }
+
+    // The result of the comparison, if it is the same for all values in both ranges.
+    pub fn icmp(pred: Predicate, lhs: &ConstantRange, rhs: &ConstantRange) -> Option<bool> {
+        if lhs.is_empty() || rhs.is_empty() {
+            return None;
+        }
+
+        return match pred {
+            Predicate::Eq => Self::known_eq(lhs, rhs),
+            Predicate::Ne => Self::known_eq(lhs, rhs).map(|eq| !eq),
+            Predicate::Ult => Self::known_ult(lhs, rhs, false),
+            Predicate::Ugt => Self::known_ult(rhs, lhs, false),
+            Predicate::Uge => Self::known_ult(lhs, rhs, false).map(|lt| !lt),
+            Predicate::Ule => Self::known_ult(rhs, lhs, false).map(|gt| !gt),
+            Predicate::Slt => Self::known_ult(lhs, rhs, true),
+            Predicate::Sgt => Self::known_ult(rhs, lhs, true),
+            Predicate::Sge => Self::known_ult(lhs, rhs, true).map(|lt| !lt),
+            Predicate::Sle => Self::known_ult(rhs, lhs, true).map(|gt| !gt),
+        };
+    }
+
+    fn known_eq(lhs: &ConstantRange, rhs: &ConstantRange) -> Option<bool> {
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return Some(a == b);
+        }
+        if lhs.intersect(rhs).is_empty() {
+            return Some(false);
+        }
+
+        return None;
+    }
+
+    fn known_ult(lhs: &ConstantRange, rhs: &ConstantRange, signed: bool) -> Option<bool> {
+        let (lhs, rhs) = match signed {
+            true => (lhs.flip_sign(), rhs.flip_sign()),
+            false => (*lhs, *rhs),
+        };
+
+        if lhs.umax() < rhs.umin() {
+            return Some(true);
+        }
+        if lhs.umin() >= rhs.umax() {
+            return Some(false);
+        }
+
+        return None;
+    }
+}
+
+// Known bits and ranges describe different facts about the same values, so each can be used to tighten the other.
+// A tighter range may reveal more known bits in turn, so this repeats until neither changes. Known bits only grow and
+// ranges only shrink, so a fixed point is reached after a few rounds.
+pub fn refine(mut known_bits: KnownBits, mut range: ConstantRange) -> (KnownBits, ConstantRange) {
+    loop {
+        let refined_bits = known_bits.union(&range.to_known_bits());
+        let refined_range = range.intersect(&ConstantRange::from_known_bits(&refined_bits));
+        if refined_bits == known_bits && refined_range == range {
+            return (known_bits, range);
+        }
+        known_bits = refined_bits;
+        range = refined_range;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::cmp;
+    use crate::simple_ast::{ashr_const, shl_const, udiv_const, urem_const};
+
+    // Every range at the given width, including the full and empty ranges.
+    fn all_ranges(width: u8) -> Vec<ConstantRange> {
+        let mask = get_modulo_mask(width);
+        let mut result = vec![ConstantRange::full(width), ConstantRange::empty(width)];
+        for lower in 0..=mask {
+            for upper in (0..=mask).filter(|u| *u != lower) {
+                result.push(ConstantRange::new(width, lower, upper));
+            }
+        }
+
+        return result;
+    }
+
+    fn values(r: &ConstantRange) -> Vec<u128> {
+        let mask = get_modulo_mask(r.width as u8);
+        return (0..=mask).filter(|v| r.contains(*v)).collect();
+    }
+
+    fn check_unop(
+        width: u8,
+        out_width: u8,
+        transfer: impl Fn(&ConstantRange) -> ConstantRange,
+        concrete: impl Fn(u128) -> u128,
+    ) {
+        let mask = get_modulo_mask(out_width);
+        for a in all_ranges(width) {
+            let result = transfer(&a);
+            assert_eq!(result.width, out_width as u32);
+            for x in values(&a) {
+                let v = concrete(x) & mask;
+                assert!(result.contains(v), "{:?} does not contain {} for {:?}", result, v, a);
+            }
+        }
+    }
+
+    fn check_binop(
+        width: u8,
+        out_width: u8,
+        transfer: impl Fn(&ConstantRange, &ConstantRange) -> ConstantRange,
+        concrete: impl Fn(u128, u128) -> u128,
+    ) {
+        let mask = get_modulo_mask(out_width);
+        let all = all_ranges(width);
+        for a in all.iter() {
+            let a_values = values(a);
+            for b in all.iter() {
+                let result = transfer(a, b);
+                assert_eq!(result.width, out_width as u32);
+                for x in a_values.iter() {
+                    for y in values(b) {
+                        let v = concrete(*x, y) & mask;
+                        assert!(result.contains(v), "{:?} does not contain {} for {:?}, {:?}", result, v, a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    // Binary operators are checked at small widths only, since every pair of ranges is enumerated.
+    const WIDTHS: [u8; 3] = [1, 2, 3];
+
+    #[test]
+    fn union_and_intersect_are_optimal() {
+        for w in [1, 2, 3] {
+            let all = all_ranges(w);
+            for a in all.iter() {
+                for b in all.iter() {
+                    let union = a.union(b);
+                    let intersection = a.intersect(b);
+                    for v in 0..=get_modulo_mask(w) {
+                        if a.contains(v) || b.contains(v) {
+                            assert!(union.contains(v), "{:?} | {:?} = {:?}", a, b, union);
+                        }
+                        if a.contains(v) && b.contains(v) {
+                            assert!(intersection.contains(v), "{:?} & {:?} = {:?}", a, b, intersection);
+                        }
+                    }
+
+                    // The results are as small as possible, and exact if the intersection is a single range.
+                    let union_values = values(&union);
+                    let exact: Vec<u128> = values(a).into_iter().filter(|v| b.contains(*v)).collect();
+                    for r in all.iter() {
+                        if values(a).iter().chain(values(b).iter()).all(|v| r.contains(*v)) {
+                            assert!(values(r).len() >= union_values.len(), "{:?} | {:?} = {:?}", a, b, union);
+                        }
+                        if values(r) == exact {
+                            assert_eq!(values(&intersection), exact, "{:?} & {:?}", a, b);
+                        }
+                    }
+
+                    // E-graph merges rely on the intersection never growing.
+                    assert!(!a.is_smaller_than(&intersection) && !b.is_smaller_than(&intersection));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounds_are_exact() {
+        for w in [1, 2, 3, 4] {
+            for r in all_ranges(w).iter().filter(|r| !r.is_empty()) {
+                let values = values(r);
+                let signed: Vec<i128> = values.iter().map(|v| sign_extend(*v, w)).collect();
+                assert_eq!(r.umin(), *values.iter().min().unwrap());
+                assert_eq!(r.umax(), *values.iter().max().unwrap());
+                assert_eq!(r.smin(), *signed.iter().min().unwrap());
+                assert_eq!(r.smax(), *signed.iter().max().unwrap());
+                assert_eq!(r.as_constant().is_some(), values.len() == 1);
+            }
+        }
+    }
+
+    #[test]
+    fn known_bits_conversions_are_sound() {
+        for w in [1, 2, 3, 4] {
+            let mask = get_modulo_mask(w);
+            for r in all_ranges(w) {
+                let kb = r.to_known_bits();
+                assert!(values(&r).iter().all(|v| kb.contains(*v)), "{:?} -> {:?}", r, kb);
+
+                if let Some(c) = r.as_constant() {
+                    assert_eq!(kb, KnownBits::constant(c, w));
+                }
+            }
+
+            for zeroes in 0..=mask {
+                for ones in (0..=mask).filter(|o| o & zeroes == 0) {
+                    let kb = KnownBits::new(w, zeroes, ones);
+                    let r = ConstantRange::from_known_bits(&kb);
+                    assert!((0..=mask).filter(|v| kb.contains(*v)).all(|v| r.contains(v)), "{:?} -> {:?}", kb, r);
+                }
+            }
+        }
+
+        // Values in 0..16 have their upper bits cleared.
+        let kb = ConstantRange::new(8, 0, 16).to_known_bits();
+        assert_eq!(kb, KnownBits::new(8, 0xF0, 0));
+
+        // Values with an unknown sign bit and their other bits known to be zero lie in -128..=0.
+        let r = ConstantRange::from_known_bits(&KnownBits::new(8, 0x7F, 0));
+        assert_eq!((r.smin(), r.smax()), (-128, 0));
+    }
+
+    #[test]
+    fn refine_reaches_a_fixed_point() {
+        // Bit 1 being set excludes 0 and 1 from the range, after which the remaining value 2 is fully known.
+        let (kb, r) = refine(KnownBits::new(2, 0, 2), ConstantRange::new(2, 0, 3));
+        assert_eq!(kb, KnownBits::constant(2, 2));
+        assert_eq!(r, ConstantRange::constant(2, 2));
+
+        for w in [1, 2, 3, 4] {
+            let mask = get_modulo_mask(w);
+            for r in all_ranges(w) {
+                for zeroes in 0..=mask {
+                    for ones in (0..=mask).filter(|o| o & zeroes == 0) {
+                        let kb = KnownBits::new(w, zeroes, ones);
+                        let (refined_kb, refined_r) = refine(kb, r);
+                        assert_eq!(refine(refined_kb, refined_r), (refined_kb, refined_r), "{:?} {:?}", kb, r);
+
+                        // Refining never loses a value allowed by both.
+                        for v in values(&r).into_iter().filter(|v| kb.contains(*v)) {
+                            assert!(refined_kb.contains(v) && refined_r.contains(v), "{:?} {:?} {}", kb, r, v);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic_is_sound() {
+        for w in WIDTHS {
+            check_binop(w, w, ConstantRange::add, |a, b| a.wrapping_add(b));
+            check_binop(w, w, ConstantRange::mul, |a, b| a.wrapping_mul(b));
+            check_binop(w, w, ConstantRange::udiv, |a, b| udiv_const(a, b, w));
+            check_binop(w, w, ConstantRange::urem, |a, b| urem_const(a, b, w));
+        }
+
+        // Neither operand can be large enough to overflow.
+        let small = ConstantRange::new(8, 3, 10);
+        assert_eq!(ConstantRange::add(&small, &small), ConstantRange::new(8, 6, 19));
+        assert_eq!(ConstantRange::mul(&small, &small), ConstantRange::new(8, 9, 82));
+    }
+
+    #[test]
+    fn bitwise_is_sound() {
+        for w in WIDTHS {
+            check_binop(w, w, ConstantRange::and, |a, b| a & b);
+            check_binop(w, w, ConstantRange::or, |a, b| a | b);
+        }
+        for w in [1, 2, 3, 4] {
+            check_unop(w, w, ConstantRange::neg, |a| !a);
+        }
+    }
+
+    #[test]
+    fn shifts_are_sound() {
+        for w in WIDTHS {
+            check_binop(w, w, ConstantRange::shl, |a, b| shl_const(a, b, w));
+            check_binop(w, w, ConstantRange::lshr, |a, b| lshr_const(a, b, w));
+            check_binop(w, w, ConstantRange::ashr, |a, b| ashr_const(a, b, w));
+        }
+    }
+
+    #[test]
+    fn casts_are_sound() {
+        for w in [1, 2, 3, 4] {
+            check_unop(w, w + 2, |a| ConstantRange::zext(a, w as u32 + 2), |a| a);
+            check_unop(w, w + 2, |a| ConstantRange::sext(a, w as u32 + 2), |a| sign_extend(a, w) as u128);
+            for to in 1..=w {
+                check_unop(w, to, |a| ConstantRange::trunc(a, to as u32), |a| a);
+                for low in 0..=(w - to) {
+                    let high = low + to - 1;
+                    check_unop(w, to, |a| ConstantRange::extract(a, high, low), |a| a >> low);
+                }
+            }
+        }
+        for w in WIDTHS {
+            check_binop(w, w * 2, ConstantRange::concat, |a, b| (a << w) | b);
+        }
+    }
+
+    #[test]
+    fn select_is_sound() {
+        for w in [1, 2, 3] {
+            let all = all_ranges(w);
+            for c in all_ranges(1) {
+                for a in all.iter() {
+                    for b in all.iter() {
+                        let result = ConstantRange::select(&c, a, b);
+                        for v in values(&c) {
+                            let chosen = if v != 0 { a } else { b };
+                            assert!(values(chosen).iter().all(|x| result.contains(*x)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn comparisons_are_sound() {
+        let predicates = [
+            Predicate::Eq,
+            Predicate::Ne,
+            Predicate::Ugt,
+            Predicate::Uge,
+            Predicate::Ult,
+            Predicate::Ule,
+            Predicate::Sgt,
+            Predicate::Sge,
+            Predicate::Slt,
+            Predicate::Sle,
+        ];
+        for w in WIDTHS {
+            let all = all_ranges(w);
+            for pred in predicates {
+                for a in all.iter() {
+                    for b in all.iter() {
+                        let Some(known) = ConstantRange::icmp(pred, a, b) else {
+                            continue;
+                        };
+                        for x in values(a) {
+                            for y in values(b) {
+                                assert_eq!(cmp(pred, x, y, w), known, "{:?} {:?} {:?}", pred, a, b);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Masking with 15 yields a value below 16, which known bits alone cannot compare against 17.
+        let masked = ConstantRange::and(&ConstantRange::full(8), &ConstantRange::constant(15, 8));
+        let c = ConstantRange::constant(17, 8);
+        assert_eq!(ConstantRange::icmp(Predicate::Ult, &masked, &c), Some(true));
+        assert_eq!(ConstantRange::icmp(Predicate::Eq, &masked, &c), Some(false));
+        assert_eq!(ConstantRange::icmp(Predicate::Slt, &masked, &ConstantRange::constant(8, 8)), None);
+    }
+}
@@ -0,0 +1,313 @@
+// Graphviz export of expression DAGs and e-graphs.
+//
+// Constant widths and bit indices are folded into the label of the node that uses them, e.g. `zx i64` or `[7:0]`,
+// so that only value operands are drawn as edges.
+use std::fmt::Write;
+
+use ahash::AHashSet;
+use egg::{Extractor, Id, Language};
+use libc::c_char;
+
+use crate::{
+    eval::post_order,
+    ffi,
+    known_bits::KnownBits,
+    rewrite::num_value_operands,
+    simple_ast::{AstIdx, Context, EEGraph, EGraphCostFn, INodeUtil, SimpleAst},
+};
+
+impl Context {
+    // Render the DAG rooted at `root`, with every shared node drawn once and annotated with its width, class and known
+    // bits.
+    pub fn to_dot(&self, root: AstIdx) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph ast {{").unwrap();
+        writeln!(out, "  node [shape=box, fontname=\"monospace\"];").unwrap();
+
+        let order = post_order(self, root);
+        let mut value_operands = AHashSet::new();
+        for idx in order.iter() {
+            let ast = self.arena.get_node(*idx);
+            value_operands.extend(ast.children()[..num_value_operands(ast)].iter().copied());
+        }
+
+        for idx in order {
+            // Skip nodes that are only used as widths or bit indices, since those are drawn inline.
+            if idx != root && !value_operands.contains(&idx) {
+                continue;
+            }
+
+            let ast = self.arena.get_node(idx);
+            let data = self.arena.get_data(idx);
+            let label = format!(
+                "{}\\n{:?}, i{}\\n{}",
+                escape(&node_label(ast, |c| self.arena.get_constant(c), |id| self.arena.get_symbol_name(id))),
+                data.class,
+                data.width,
+                format_known_bits(&data.known_bits, data.width)
+            );
+            let style = if idx == root { ", penwidth=2" } else { "" };
+            writeln!(out, "  n{} [label=\"{}\"{}];", idx.0, label, style).unwrap();
+
+            let operands = &ast.children()[..num_value_operands(ast)];
+            for (i, child) in operands.iter().enumerate() {
+                let label = if operands.len() > 1 { format!(" [label=\"{}\"]", i) } else { String::new() };
+                writeln!(out, "  n{} -> n{}{};", idx.0, child.0, label).unwrap();
+            }
+        }
+
+        writeln!(out, "}}").unwrap();
+        return out;
+    }
+}
+
+// Render every e-class as a cluster of its e-nodes. The e-nodes chosen by the extractor for the term rooted at `root`
+// are filled, and the edges between them are highlighted. `ctx` is only used to look up symbol names.
+pub fn egraph_to_dot(egraph: &EEGraph, ctx: &Context, root: Id) -> String {
+    let extractor = Extractor::new(egraph, EGraphCostFn { egraph });
+    let root = egraph.find(root);
+
+    // Walk the extracted term from the root.
+    let mut extracted = AHashSet::new();
+    let mut stack = vec![root];
+    while let Some(class) = stack.pop() {
+        if !extracted.insert(class) {
+            continue;
+        }
+
+        let node = extractor.find_best_node(class);
+        for child in node.children()[..num_value_operands(node)].iter() {
+            stack.push(egraph.find(*child));
+        }
+    }
+
+    let constant_of = |id: Id| egraph[id].data.known_bits.as_constant().unwrap();
+    let mut out = String::new();
+    writeln!(out, "digraph egraph {{").unwrap();
+    writeln!(out, "  compound=true;").unwrap();
+    writeln!(out, "  node [shape=box, fontname=\"monospace\"];").unwrap();
+
+    let mut classes: Vec<_> = egraph.classes().collect();
+    classes.sort_by_key(|c| c.id);
+    for class in classes.iter() {
+        let best = extractor.find_best_node(class.id);
+        writeln!(out, "  subgraph cluster_{} {{", class.id).unwrap();
+        writeln!(
+            out,
+            "    label=\"e{}: {:?}, i{}\\n{}\";",
+            class.id,
+            class.data.class,
+            class.data.width,
+            format_known_bits(&class.data.known_bits, class.data.width)
+        )
+        .unwrap();
+        writeln!(out, "    style=dashed;").unwrap();
+        for (i, node) in class.nodes.iter().enumerate() {
+            let label = escape(&node_label(node, constant_of, |id| ctx.arena.get_symbol_name(id)));
+            let style = if extracted.contains(&class.id) && node == best {
+                ", style=filled, fillcolor=lightblue"
+            } else {
+                ""
+            };
+            writeln!(out, "    e{}_{} [label=\"{}\"{}];", class.id, i, label, style).unwrap();
+        }
+        writeln!(out, "  }}").unwrap();
+    }
+
+    // Edges point at the first e-node of the child e-class, but are clipped to the cluster border.
+    for class in classes.iter() {
+        let best = extractor.find_best_node(class.id);
+        for (i, node) in class.nodes.iter().enumerate() {
+            let chosen = extracted.contains(&class.id) && node == best;
+            let operands = &node.children()[..num_value_operands(node)];
+            for (j, child) in operands.iter().enumerate() {
+                let child = egraph.find(*child);
+                let mut attrs = vec![format!("lhead=cluster_{}", child)];
+                if operands.len() > 1 {
+                    attrs.push(format!("label=\"{}\"", j));
+                }
+                if chosen {
+                    attrs.push("color=blue, penwidth=2".to_string());
+                }
+                writeln!(out, "  e{}_{} -> e{}_0 [{}];", class.id, i, child, attrs.join(", ")).unwrap();
+            }
+        }
+    }
+
+    writeln!(out, "}}").unwrap();
+    return out;
+}
+
+// Label a node by its operator, with constant operands such as widths inlined.
+fn node_label(
+    ast: &SimpleAst,
+    constant_of: impl Fn(AstIdx) -> u128,
+    symbol_name: impl Fn(u32) -> String,
+) -> String {
+    match ast {
+        SimpleAst::Add(_) => "+".to_string(),
+        SimpleAst::Mul(_) => "*".to_string(),
+        SimpleAst::Pow(_) => "**".to_string(),
+        SimpleAst::And(_) => "&".to_string(),
+        SimpleAst::Or(_) => "|".to_string(),
+        SimpleAst::Xor(_) => "^".to_string(),
+        SimpleAst::Neg(_) => "~".to_string(),
+        SimpleAst::Lshr(_) => ">>".to_string(),
+        SimpleAst::Shl(_) => "<<".to_string(),
+        SimpleAst::Ashr(_) => ">>s".to_string(),
+        SimpleAst::Udiv(_) => "/".to_string(),
+        SimpleAst::Urem(_) => "%".to_string(),
+        SimpleAst::Sdiv(_) => "/s".to_string(),
+        SimpleAst::Srem(_) => "%s".to_string(),
+        SimpleAst::Rotl(_) => "<<<".to_string(),
+        SimpleAst::Rotr(_) => ">>>".to_string(),
+        SimpleAst::Popcount(_) => "popcount".to_string(),
+        SimpleAst::Ctlz(_) => "ctlz".to_string(),
+        SimpleAst::Cttz(_) => "cttz".to_string(),
+        SimpleAst::Constant { c, width } if *width <= 64 => format!("{}:i{}", *c as i64, width),
+        SimpleAst::Constant { c, width } => format!("{}:i{}", *c as i128, width),
+        SimpleAst::Symbol { id, width } => format!("{}:i{}", symbol_name(*id), width),
+        SimpleAst::Zext([_, to]) => format!("zx i{}", constant_of(*to)),
+        SimpleAst::Sext([_, to]) => format!("sx i{}", constant_of(*to)),
+        SimpleAst::Trunc([_, to]) => format!("tr i{}", constant_of(*to)),
+        SimpleAst::ICmp { predicate, .. } => predicate.to_string(),
+        SimpleAst::Select { .. } => "select".to_string(),
+        SimpleAst::Extract([_, high, low]) => format!("[{}:{}]", constant_of(*high), constant_of(*low)),
+        SimpleAst::Concat(_) => "++".to_string(),
+        SimpleAst::Carry(_) => "carry".to_string(),
+    }
+}
+
+// Print the known bits from the most significant bit down, using `?` for unknown bits.
+fn format_known_bits(kb: &KnownBits, width: u8) -> String {
+    (0..width)
+        .rev()
+        .map(|i| {
+            if (kb.zeroes >> i) & 1 != 0 {
+                '0'
+            } else if (kb.ones >> i) & 1 != 0 {
+                '1'
+            } else {
+                '?'
+            }
+        })
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextGetDotString(ctx: *const Context, id: AstIdx) -> *mut c_char {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let str = ctx.to_dot(ffi::node(ctx, id)?);
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn EGraphGetDotString(
+    egraph: *const EEGraph,
+    ctx: *const Context,
+    root: Id,
+) -> *mut c_char {
+    ffi::guard(|| {
+        let egraph = ffi::non_null_ref(egraph, "egraph")?;
+        let str = egraph_to_dot(egraph, ffi::context_ref(ctx)?, ffi::eclass(egraph, root)?);
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ahash::AHashMap;
+
+    use crate::{
+        ast_parser::parse_ast,
+        ffi::FreeString,
+        simple_ast::{add_to_egraph, Arena, MbaAnalysis},
+    };
+
+    use super::*;
+
+    #[test]
+    fn dot_shows_sharing() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let root = parse_ast(&mut ctx, "((x:i8 & y:i8) + (x:i8 & y:i8) * 3) ++ (z:i8 zx i16)", 64).unwrap();
+        let dot = ctx.to_dot(root);
+        assert!(dot.starts_with("digraph ast {\n"));
+        assert!(dot.ends_with("}\n"));
+
+        // The shared conjunction is emitted once, with one edge from each user.
+        let x = ctx.arena.symbol_with_name("x".to_string(), 8);
+        let y = ctx.arena.symbol_with_name("y".to_string(), 8);
+        let and = ctx.arena.and(x, y);
+        assert!(dot.contains(&format!("  n{} [label=\"&\\nBitwise, i8\\n????????\"];\n", and.0)));
+        assert_eq!(dot.matches(&format!(" -> n{}", and.0)).count(), 2);
+        assert_eq!(dot.matches(&format!("  n{} -> n{} [label=\"0\"];\n", and.0, x.0)).count(), 1);
+
+        // The width of the extension is drawn inline rather than as an operand, and the known bits of the result are
+        // printed from the most significant bit down.
+        let z = ctx.arena.symbol_with_name("z".to_string(), 8);
+        let zext = ctx.arena.zext(z, 16);
+        let known = format!("{}{}", "0".repeat(8), "?".repeat(8));
+        assert!(dot.contains(&format!("  n{} [label=\"zx i16\\nBitwiseWithConstants, i16\\n{}\"];\n", zext.0, known)));
+        assert!(dot.contains(&format!("  n{} -> n{};\n", zext.0, z.0)));
+        let sixteen = ctx.arena.constant(16, 8);
+        assert!(!dot.contains(&format!("n{} ", sixteen.0)));
+
+        // The root is emphasized.
+        assert!(dot.contains(&format!("  n{} [label=\"++\\n", root.0)));
+        assert!(dot.contains("\", penwidth=2];\n"));
+    }
+    #[test]
+    fn dot_highlights_extracted_nodes() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let a = parse_ast(&mut ctx, "(x:i8 & y:i8) + (x:i8 | y:i8)", 64).unwrap();
+        let b = parse_ast(&mut ctx, "x:i8 + y:i8", 64).unwrap();
+        let mut egraph = EEGraph::new(MbaAnalysis::default());
+        let mut cache = AHashMap::new();
+        let root = add_to_egraph(&ctx, &mut egraph, a, &mut cache);
+        add_to_egraph(&ctx, &mut egraph, b, &mut cache);
+        egraph.union(root, cache[&b]);
+        egraph.rebuild();
+
+        let dot = egraph_to_dot(&egraph, &ctx, root);
+        assert!(dot.starts_with("digraph egraph {\n  compound=true;\n"));
+        assert!(dot.ends_with("}\n"));
+
+        // Every e-class is drawn as one cluster, and both additions end up in the cluster of the root.
+        assert_eq!(dot.matches("  subgraph cluster_").count(), egraph.number_of_classes());
+        let root = egraph.find(root);
+        let cluster = &dot[dot.find(&format!("  subgraph cluster_{} {{\n", root)).unwrap()..];
+        let cluster = &cluster[..cluster.find("  }\n").unwrap()];
+        assert!(cluster.contains(&format!("    label=\"e{}: ", root)));
+        assert_eq!(cluster.matches(" [label=\"+\"").count(), 2);
+
+        // Only the cheaper `x + y` and its operands are extracted, and only the edges of the chosen addition are
+        // highlighted.
+        let filled: Vec<_> = dot.lines().filter(|l| l.ends_with(", style=filled, fillcolor=lightblue];")).collect();
+        assert_eq!(filled.len(), 3);
+        for label in ["+", "x:i8", "y:i8"] {
+            assert!(filled.iter().any(|l| l.contains(&format!("[label=\"{}\"", label))));
+        }
+        assert!(!dot.lines().any(|l| l.contains("[label=\"&\"") && l.contains("filled")));
+        assert_eq!(dot.matches(", color=blue, penwidth=2];").count(), 2);
+        let x = egraph.find(cache[&ctx.arena.symbol_with_name("x".to_string(), 8)]);
+        assert!(dot.contains(&format!(" -> e{}_0 [lhead=cluster_{}, label=\"0\", color=blue, penwidth=2];\n", x, x)));
+
+        unsafe {
+            let str = EGraphGetDotString(&egraph, &ctx, root);
+            assert_eq!(std::ffi::CStr::from_ptr(str).to_str().unwrap(), dot);
+            assert_eq!(FreeString(str), ffi::FfiStatus::Ok);
+            assert!(EGraphGetDotString(std::ptr::null(), &ctx, root).is_null());
+            assert_eq!(ffi::GetLastStatus(), ffi::FfiStatus::NullPointer);
+        }
+    }
+}
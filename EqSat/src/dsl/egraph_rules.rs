@@ -2681,7 +2681,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_neg_expand {
         let y_id = subst[self.y];
         let t2 = egraph.add(SimpleAst::And([x_id, y_id]));
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t4 = egraph.add(SimpleAst::Mul([t2, literal_18446744073709551615_id]));
@@ -2715,7 +2715,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_or_expand {
         let t2 = egraph.add(SimpleAst::Add([a_id, b_id]));
         let t3 = egraph.add(SimpleAst::And([a_id, b_id]));
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t5 = egraph.add(SimpleAst::Mul([t3, literal_18446744073709551615_id]));
@@ -2754,7 +2754,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_xor_expand {
         let t4 = egraph.add(SimpleAst::And([a_id, b_id]));
         let t5 = egraph.add(SimpleAst::Mul([literal_2_id, t4]));
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t7 = egraph.add(SimpleAst::Mul([t5, literal_18446744073709551615_id]));
@@ -2990,7 +2990,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_eq_to_sub {
         let bounded_width = egraph[a_id].data.width;
         let b_id = subst[self.b];
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t3 = egraph.add(SimpleAst::Mul([b_id, literal_18446744073709551615_id]));
@@ -3065,7 +3065,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_neq_to_sub {
         let bounded_width = egraph[a_id].data.width;
         let b_id = subst[self.b];
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t3 = egraph.add(SimpleAst::Mul([b_id, literal_18446744073709551615_id]));
@@ -4155,7 +4155,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_add_negate_to_invert_sign
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -4258,7 +4258,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_qsynth_1 {
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let d_id = subst[self.d];
@@ -4424,7 +4424,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_or_maxint {
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
 
@@ -4476,7 +4476,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_or_negated_itself {
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
 
@@ -5256,7 +5256,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule___merge_inverse_bitwise_te
         let y_id = subst[self.y];
         let bounded_width = egraph[y_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Add([literal_18446744073709551615_id, y_id]));
@@ -5285,7 +5285,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule___merge_inverse_bitwise_te
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
 
@@ -5371,7 +5371,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule___merge_inverse_bitwise_te
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let y_id = subst[self.y];
@@ -5417,11 +5417,11 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule___merge_inverse_bitwise_te
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551614_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551614,
+            c: 340282366920938463463374607431768211454,
             width: bounded_width,
         });
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t3 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, x_id]));
@@ -5566,7 +5566,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_invert_add_bitwise_not_sel
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
 
@@ -5594,7 +5594,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_invert_mul_bitwise_not_sel
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -5670,7 +5670,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_xor_same_mult_by_minus_one
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -5719,7 +5719,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_xor_same_mult_by_minus_one
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -5861,7 +5861,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_conj_neg_xor_minus_one_rul
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
 
@@ -6050,7 +6050,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_conj_neg_conj_identity_rul
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6098,7 +6098,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_conj_neg_conj_identity_rul
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6145,7 +6145,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_conj_neg_conj_identity_rul
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6193,7 +6193,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_conj_neg_conj_identity_rul
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6318,7 +6318,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_disj_conj_identity_rule_1
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6366,7 +6366,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_disj_conj_identity_rule_2
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6413,7 +6413,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_disj_conj_identity_rule_3
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -6461,7 +6461,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_disj_conj_identity_rule_4
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -7137,7 +7137,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_add_negated_itself_by_two
         let a_id = subst[self.a];
         let bounded_width = egraph[a_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, a_id]));
@@ -7684,7 +7684,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_merge_and_multipliers {
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let y_id = subst[self.y];
@@ -7949,7 +7949,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_new_2 {
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let y_id = subst[self.y];
@@ -8067,7 +8067,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_new_23 {
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let y_id = subst[self.y];
@@ -8117,7 +8117,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_new_24 {
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let y_id = subst[self.y];
@@ -8198,7 +8198,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_opaque_constant_1_again {
         let x_id = subst[self.x];
         let bounded_width = egraph[x_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
 
@@ -8434,7 +8434,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_combine_and_add_xor_or {
         let z_id = subst[self.z];
         let bounded_width = egraph[z_id].data.width;
         let literal_18446744073709551614_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551614,
+            c: 340282366920938463463374607431768211454,
             width: bounded_width,
         });
         let x_id = subst[self.x];
@@ -8492,7 +8492,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_combine_and_add_xor_or_2 {
             width: bounded_width,
         });
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let x_id = subst[self.x];
@@ -8814,7 +8814,7 @@ impl Applier<SimpleAst, MbaAnalysis> for applier_rule_linear_mba_2 {
         let c1_id = subst[self.c1];
         let bounded_width = egraph[c1_id].data.width;
         let literal_18446744073709551615_id = egraph.add(SimpleAst::Constant {
-            c: 18446744073709551615,
+            c: 340282366920938463463374607431768211455,
             width: bounded_width,
         });
         let t2 = egraph.add(SimpleAst::Mul([literal_18446744073709551615_id, c1_id]));
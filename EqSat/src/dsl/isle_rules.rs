@@ -22,12 +22,24 @@ pub trait Context {
     fn xor(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
     fn neg(&mut self, arg0: AstIdx) -> SimpleAst;
     fn lshr(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn shl(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn ashr(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn udiv(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn urem(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn sdiv(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn srem(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn rotl(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn rotr(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn popcount(&mut self, arg0: AstIdx) -> SimpleAst;
+    fn ctlz(&mut self, arg0: AstIdx) -> SimpleAst;
+    fn cttz(&mut self, arg0: AstIdx) -> SimpleAst;
     fn any(&mut self, arg0: AstIdx) -> SimpleAst;
     fn lookup_value(&mut self, arg0: AstIdx) -> Option<SimpleAst>;
     fn lookup_id(&mut self, arg0: &SimpleAst) -> AstIdx;
-    fn constant(&mut self, arg0: u64, arg1: u8) -> SimpleAst;
+    fn constant(&mut self, arg0: u128, arg1: u8) -> SimpleAst;
     fn symbol(&mut self, arg0: u32, arg1: u8) -> SimpleAst;
     fn zext(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
+    fn sext(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
     fn trunc(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
     fn icmp(&mut self, arg0: Predicate, arg1: AstIdx, arg2: AstIdx) -> SimpleAst;
     fn select(&mut self, arg0: AstIdx, arg1: AstIdx, arg2: AstIdx) -> SimpleAst;
@@ -36,7 +48,7 @@ pub trait Context {
     fn carry(&mut self, arg0: AstIdx, arg1: AstIdx, arg2: AstIdx) -> SimpleAst;
     fn fold_add(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst;
     fn get_width(&mut self, arg0: AstIdx) -> u8;
-    fn is_constant_modulo(&mut self, arg0: u64, arg1: u64, arg2: u8) -> Option<Empty>;
+    fn is_constant_modulo(&mut self, arg0: u128, arg1: u128, arg2: u8) -> Option<Empty>;
     fn is_icmp_eq(&mut self, arg0: Predicate) -> Option<Empty>;
     fn is_icmp_ne(&mut self, arg0: Predicate) -> Option<Empty>;
     fn is_icmp_ugt(&mut self, arg0: Predicate) -> Option<Empty>;
@@ -57,6 +69,15 @@ pub trait Context {
     fn pred_sge(&mut self, ) -> Predicate;
     fn pred_slt(&mut self, ) -> Predicate;
     fn pred_sle(&mut self, ) -> Predicate;
+    fn shl_to_mul_factor(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<u128>;
+    fn pow2_log2(&mut self, arg0: AstIdx) -> Option<u128>;
+    fn pow2_low_mask(&mut self, arg0: AstIdx) -> Option<u128>;
+    fn rotate_source(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<AstIdx>;
+    fn rotate_amount(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<u128>;
+    fn popcount_operand(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<AstIdx>;
+    fn is_sign_bit_zero(&mut self, arg0: AstIdx) -> Option<Empty>;
+    fn cast_is_noop(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<Empty>;
+    fn cast_is_narrowing(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<Empty>;
     fn rule_fold_neg_xor_precondition(&mut self, arg0: AstIdx) -> Option<Empty>;
     fn rule_fold_nested_neg_xor_precondition(&mut self, arg0: AstIdx) -> Option<Empty>;
     fn rule_fold_neg_xor_xor_precondition(&mut self, arg0: AstIdx) -> Option<Empty>;
@@ -231,44 +252,44 @@ pub fn constructor_lower<C: Context>(
     arg0: &SimpleAst,
 ) -> Option<SimpleAst> {
     match arg0 {
-        &SimpleAst::Add([v228, v229]) => {
-            let v249 = &C::lookup_value(ctx, v228);
-            if let Some(v250) = v249 {
-                match v250 {
-                    &SimpleAst::Add([v912, v913]) => {
-                        let v230 = &C::lookup_value(ctx, v229);
-                        if let Some(v231) = v230 {
-                            if let &SimpleAst::Mul([v232, v233]) = v231 {
-                                let v268 = &C::lookup_value(ctx, v233);
-                                if let Some(v269) = v268 {
-                                    match v269 {
-                                        &SimpleAst::And([v270, v271]) => {
-                                            let v272 = &C::lookup_value(ctx, v270);
-                                            if let Some(v273) = v272 {
-                                                if let &SimpleAst::Neg([v274]) = v273 {
-                                                    if v274 == v912 {
-                                                        let v918 = &C::lookup_value(ctx, v913);
-                                                        if let Some(v919) = v918 {
-                                                            if let &SimpleAst::Mul([v920, v921]) = v919 {
-                                                                let v1057 = &C::lookup_value(ctx, v271);
-                                                                if let Some(v1058) = v1057 {
-                                                                    if let &SimpleAst::Mul([v1065, v1066]) = v1058 {
-                                                                        if v232 == v1065 {
-                                                                            if v921 == v1066 {
-                                                                                let v1067 = C::rule_mba_9_precondition(ctx, v920, v232);
-                                                                                if let Some(v1068) = v1067 {
-                                                                                    let v972 = &C::any(ctx, v912);
-                                                                                    let v973 = C::lookup_id(ctx, v972);
-                                                                                    let v1069 = C::get_width(ctx, v912);
-                                                                                    let v1070 = &C::constant(ctx, 0x2, v1069);
-                                                                                    let v1071 = C::lookup_id(ctx, v1070);
-                                                                                    let v1072 = &C::any(ctx, v921);
-                                                                                    let v1073 = C::lookup_id(ctx, v1072);
-                                                                                    let v1074 = &C::mul(ctx, v1071, v1073);
-                                                                                    let v1075 = C::lookup_id(ctx, v1074);
-                                                                                    let v1076 = &C::xor(ctx, v973, v1075);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1078.
-                                                                                    return Some(v1076.clone());
+        &SimpleAst::Add([v35, v36]) => {
+            let v342 = &C::lookup_value(ctx, v35);
+            if let Some(v343) = v342 {
+                match v343 {
+                    &SimpleAst::Add([v1005, v1006]) => {
+                        let v324 = &C::lookup_value(ctx, v36);
+                        if let Some(v325) = v324 {
+                            if let &SimpleAst::Mul([v326, v327]) = v325 {
+                                let v361 = &C::lookup_value(ctx, v327);
+                                if let Some(v362) = v361 {
+                                    match v362 {
+                                        &SimpleAst::And([v363, v364]) => {
+                                            let v365 = &C::lookup_value(ctx, v363);
+                                            if let Some(v366) = v365 {
+                                                if let &SimpleAst::Neg([v367]) = v366 {
+                                                    if v367 == v1005 {
+                                                        let v1011 = &C::lookup_value(ctx, v1006);
+                                                        if let Some(v1012) = v1011 {
+                                                            if let &SimpleAst::Mul([v1013, v1014]) = v1012 {
+                                                                let v1150 = &C::lookup_value(ctx, v364);
+                                                                if let Some(v1151) = v1150 {
+                                                                    if let &SimpleAst::Mul([v1158, v1159]) = v1151 {
+                                                                        if v326 == v1158 {
+                                                                            if v1014 == v1159 {
+                                                                                let v1160 = C::rule_mba_9_precondition(ctx, v1013, v326);
+                                                                                if let Some(v1161) = v1160 {
+                                                                                    let v1065 = &C::any(ctx, v1005);
+                                                                                    let v1066 = C::lookup_id(ctx, v1065);
+                                                                                    let v1162 = C::get_width(ctx, v1005);
+                                                                                    let v1163 = &C::constant(ctx, 0x2, v1162);
+                                                                                    let v1164 = C::lookup_id(ctx, v1163);
+                                                                                    let v1165 = &C::any(ctx, v1014);
+                                                                                    let v1166 = C::lookup_id(ctx, v1165);
+                                                                                    let v1167 = &C::mul(ctx, v1164, v1166);
+                                                                                    let v1168 = C::lookup_id(ctx, v1167);
+                                                                                    let v1169 = &C::xor(ctx, v1066, v1168);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1212.
+                                                                                    return Some(v1169.clone());
                                                                                 }
                                                                             }
                                                                         }
@@ -280,45 +301,45 @@ pub fn constructor_lower<C: Context>(
                                                 }
                                             }
                                         }
-                                        &SimpleAst::Or([v488, v489]) => {
-                                            let v914 = &C::lookup_value(ctx, v912);
-                                            if let Some(v915) = v914 {
-                                                if let &SimpleAst::And([v1191, v1192]) = v915 {
-                                                    let v918 = &C::lookup_value(ctx, v913);
-                                                    if let Some(v919) = v918 {
-                                                        if let &SimpleAst::Xor([v1193, v1194]) = v919 {
-                                                            if v488 == v1193 {
-                                                                let v1195 = &C::lookup_value(ctx, v1194);
-                                                                if let Some(v1196) = v1195 {
-                                                                    if let &SimpleAst::Or([v1197, v1198]) = v1196 {
-                                                                        if v1191 == v1197 {
-                                                                            if v1192 == v1198 {
-                                                                                let v1199 = &C::lookup_value(ctx, v489);
-                                                                                if let Some(v1200) = v1199 {
-                                                                                    if let &SimpleAst::Neg([v1201]) = v1200 {
-                                                                                        let v1202 = &C::lookup_value(ctx, v1201);
-                                                                                        if let Some(v1203) = v1202 {
-                                                                                            if let &SimpleAst::And([v1204, v1205]) = v1203 {
-                                                                                                if v1191 == v1204 {
-                                                                                                    if v1192 == v1205 {
-                                                                                                        let v1206 = C::rule_combine_and_add_xor_or_precondition(ctx, v232);
-                                                                                                        if let Some(v1207) = v1206 {
-                                                                                                            let v1208 = C::get_width(ctx, v1191);
-                                                                                                            let v1209 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFE, v1208);
-                                                                                                            let v1210 = C::lookup_id(ctx, v1209);
-                                                                                                            let v1211 = &C::any(ctx, v1193);
-                                                                                                            let v1212 = C::lookup_id(ctx, v1211);
-                                                                                                            let v1213 = &C::any(ctx, v1191);
-                                                                                                            let v1214 = C::lookup_id(ctx, v1213);
-                                                                                                            let v1215 = &C::xor(ctx, v1212, v1214);
-                                                                                                            let v1216 = C::lookup_id(ctx, v1215);
-                                                                                                            let v1217 = &C::any(ctx, v1192);
-                                                                                                            let v1218 = C::lookup_id(ctx, v1217);
-                                                                                                            let v1219 = &C::xor(ctx, v1216, v1218);
-                                                                                                            let v1220 = C::lookup_id(ctx, v1219);
-                                                                                                            let v1221 = &C::add(ctx, v1210, v1220);
-                                                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1169.
-                                                                                                            return Some(v1221.clone());
+                                        &SimpleAst::Or([v581, v582]) => {
+                                            let v1007 = &C::lookup_value(ctx, v1005);
+                                            if let Some(v1008) = v1007 {
+                                                if let &SimpleAst::And([v1284, v1285]) = v1008 {
+                                                    let v1011 = &C::lookup_value(ctx, v1006);
+                                                    if let Some(v1012) = v1011 {
+                                                        if let &SimpleAst::Xor([v1286, v1287]) = v1012 {
+                                                            if v581 == v1286 {
+                                                                let v1288 = &C::lookup_value(ctx, v1287);
+                                                                if let Some(v1289) = v1288 {
+                                                                    if let &SimpleAst::Or([v1290, v1291]) = v1289 {
+                                                                        if v1284 == v1290 {
+                                                                            if v1285 == v1291 {
+                                                                                let v1292 = &C::lookup_value(ctx, v582);
+                                                                                if let Some(v1293) = v1292 {
+                                                                                    if let &SimpleAst::Neg([v1294]) = v1293 {
+                                                                                        let v1295 = &C::lookup_value(ctx, v1294);
+                                                                                        if let Some(v1296) = v1295 {
+                                                                                            if let &SimpleAst::And([v1297, v1298]) = v1296 {
+                                                                                                if v1284 == v1297 {
+                                                                                                    if v1285 == v1298 {
+                                                                                                        let v1299 = C::rule_combine_and_add_xor_or_precondition(ctx, v326);
+                                                                                                        if let Some(v1300) = v1299 {
+                                                                                                            let v1301 = C::get_width(ctx, v1284);
+                                                                                                            let v1302 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFE, v1301);
+                                                                                                            let v1303 = C::lookup_id(ctx, v1302);
+                                                                                                            let v1304 = &C::any(ctx, v1286);
+                                                                                                            let v1305 = C::lookup_id(ctx, v1304);
+                                                                                                            let v1306 = &C::any(ctx, v1284);
+                                                                                                            let v1307 = C::lookup_id(ctx, v1306);
+                                                                                                            let v1308 = &C::xor(ctx, v1305, v1307);
+                                                                                                            let v1309 = C::lookup_id(ctx, v1308);
+                                                                                                            let v1310 = &C::any(ctx, v1285);
+                                                                                                            let v1311 = C::lookup_id(ctx, v1310);
+                                                                                                            let v1312 = &C::xor(ctx, v1309, v1311);
+                                                                                                            let v1313 = C::lookup_id(ctx, v1312);
+                                                                                                            let v1314 = &C::add(ctx, v1303, v1313);
+                                                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1303.
+                                                                                                            return Some(v1314.clone());
                                                                                                         }
                                                                                                     }
                                                                                                 }
@@ -342,60 +363,60 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Mul([v251, v252]) => {
-                        let v257 = &C::lookup_value(ctx, v252);
-                        if let Some(v258) = v257 {
-                            match v258 {
-                                &SimpleAst::Add([v1222, v1223]) => {
-                                    let v230 = &C::lookup_value(ctx, v229);
-                                    if let Some(v231) = v230 {
-                                        if let &SimpleAst::Mul([v232, v233]) = v231 {
-                                            let v268 = &C::lookup_value(ctx, v233);
-                                            if let Some(v269) = v268 {
-                                                if let &SimpleAst::Or([v488, v489]) = v269 {
-                                                    let v1199 = &C::lookup_value(ctx, v489);
-                                                    if let Some(v1200) = v1199 {
-                                                        if let &SimpleAst::Neg([v1201]) = v1200 {
-                                                            let v1202 = &C::lookup_value(ctx, v1201);
-                                                            if let Some(v1203) = v1202 {
-                                                                if let &SimpleAst::And([v1204, v1205]) = v1203 {
-                                                                    let v1224 = &C::lookup_value(ctx, v1222);
-                                                                    if let Some(v1225) = v1224 {
-                                                                        if let &SimpleAst::And([v1226, v1227]) = v1225 {
-                                                                            if v1204 == v1226 {
-                                                                                if v1205 == v1227 {
-                                                                                    let v1228 = &C::lookup_value(ctx, v1223);
-                                                                                    if let Some(v1229) = v1228 {
-                                                                                        if let &SimpleAst::Xor([v1230, v1231]) = v1229 {
-                                                                                            if v488 == v1230 {
-                                                                                                let v1232 = &C::lookup_value(ctx, v1231);
-                                                                                                if let Some(v1233) = v1232 {
-                                                                                                    if let &SimpleAst::Or([v1234, v1235]) = v1233 {
-                                                                                                        if v1204 == v1234 {
-                                                                                                            if v1205 == v1235 {
-                                                                                                                let v1236 = C::rule_combine_and_add_xor_or_2_precondition(ctx, v251, v232);
-                                                                                                                if let Some(v1237) = v1236 {
-                                                                                                                    let v544 = C::get_width(ctx, v251);
-                                                                                                                    let v1238 = &C::constant(ctx, 0x2, v544);
-                                                                                                                    let v1239 = C::lookup_id(ctx, v1238);
-                                                                                                                    let v1240 = C::get_width(ctx, v251);
-                                                                                                                    let v1241 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v1240);
-                                                                                                                    let v1242 = C::lookup_id(ctx, v1241);
-                                                                                                                    let v1243 = &C::any(ctx, v1230);
-                                                                                                                    let v1244 = C::lookup_id(ctx, v1243);
-                                                                                                                    let v1245 = &C::any(ctx, v1226);
-                                                                                                                    let v1246 = C::lookup_id(ctx, v1245);
-                                                                                                                    let v1247 = &C::xor(ctx, v1244, v1246);
-                                                                                                                    let v1248 = C::lookup_id(ctx, v1247);
-                                                                                                                    let v1249 = &C::any(ctx, v1227);
-                                                                                                                    let v1250 = C::lookup_id(ctx, v1249);
-                                                                                                                    let v1251 = &C::xor(ctx, v1248, v1250);
-                                                                                                                    let v1252 = C::lookup_id(ctx, v1251);
-                                                                                                                    let v1253 = &C::mul(ctx, v1242, v1252);
-                                                                                                                    let v1254 = C::lookup_id(ctx, v1253);
-                                                                                                                    let v1255 = &C::add(ctx, v1239, v1254);
-                                                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1176.
-                                                                                                                    return Some(v1255.clone());
+                    &SimpleAst::Mul([v344, v345]) => {
+                        let v350 = &C::lookup_value(ctx, v345);
+                        if let Some(v351) = v350 {
+                            match v351 {
+                                &SimpleAst::Add([v1315, v1316]) => {
+                                    let v324 = &C::lookup_value(ctx, v36);
+                                    if let Some(v325) = v324 {
+                                        if let &SimpleAst::Mul([v326, v327]) = v325 {
+                                            let v361 = &C::lookup_value(ctx, v327);
+                                            if let Some(v362) = v361 {
+                                                if let &SimpleAst::Or([v581, v582]) = v362 {
+                                                    let v1292 = &C::lookup_value(ctx, v582);
+                                                    if let Some(v1293) = v1292 {
+                                                        if let &SimpleAst::Neg([v1294]) = v1293 {
+                                                            let v1295 = &C::lookup_value(ctx, v1294);
+                                                            if let Some(v1296) = v1295 {
+                                                                if let &SimpleAst::And([v1297, v1298]) = v1296 {
+                                                                    let v1317 = &C::lookup_value(ctx, v1315);
+                                                                    if let Some(v1318) = v1317 {
+                                                                        if let &SimpleAst::And([v1319, v1320]) = v1318 {
+                                                                            if v1297 == v1319 {
+                                                                                if v1298 == v1320 {
+                                                                                    let v1321 = &C::lookup_value(ctx, v1316);
+                                                                                    if let Some(v1322) = v1321 {
+                                                                                        if let &SimpleAst::Xor([v1323, v1324]) = v1322 {
+                                                                                            if v581 == v1323 {
+                                                                                                let v1325 = &C::lookup_value(ctx, v1324);
+                                                                                                if let Some(v1326) = v1325 {
+                                                                                                    if let &SimpleAst::Or([v1327, v1328]) = v1326 {
+                                                                                                        if v1297 == v1327 {
+                                                                                                            if v1298 == v1328 {
+                                                                                                                let v1329 = C::rule_combine_and_add_xor_or_2_precondition(ctx, v344, v326);
+                                                                                                                if let Some(v1330) = v1329 {
+                                                                                                                    let v637 = C::get_width(ctx, v344);
+                                                                                                                    let v1331 = &C::constant(ctx, 0x2, v637);
+                                                                                                                    let v1332 = C::lookup_id(ctx, v1331);
+                                                                                                                    let v1333 = C::get_width(ctx, v344);
+                                                                                                                    let v1334 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v1333);
+                                                                                                                    let v1335 = C::lookup_id(ctx, v1334);
+                                                                                                                    let v1336 = &C::any(ctx, v1323);
+                                                                                                                    let v1337 = C::lookup_id(ctx, v1336);
+                                                                                                                    let v1338 = &C::any(ctx, v1319);
+                                                                                                                    let v1339 = C::lookup_id(ctx, v1338);
+                                                                                                                    let v1340 = &C::xor(ctx, v1337, v1339);
+                                                                                                                    let v1341 = C::lookup_id(ctx, v1340);
+                                                                                                                    let v1342 = &C::any(ctx, v1320);
+                                                                                                                    let v1343 = C::lookup_id(ctx, v1342);
+                                                                                                                    let v1344 = &C::xor(ctx, v1341, v1343);
+                                                                                                                    let v1345 = C::lookup_id(ctx, v1344);
+                                                                                                                    let v1346 = &C::mul(ctx, v1335, v1345);
+                                                                                                                    let v1347 = C::lookup_id(ctx, v1346);
+                                                                                                                    let v1348 = &C::add(ctx, v1332, v1347);
+                                                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1310.
+                                                                                                                    return Some(v1348.clone());
                                                                                                                 }
                                                                                                             }
                                                                                                         }
@@ -417,20 +438,20 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::And([v289, v290]) => {
-                                    let v230 = &C::lookup_value(ctx, v229);
-                                    if let Some(v231) = v230 {
-                                        if let &SimpleAst::Or([v451, v452]) = v231 {
-                                            if v289 == v452 {
-                                                let v291 = &C::lookup_value(ctx, v290);
-                                                if let Some(v292) = v291 {
-                                                    if let &SimpleAst::Neg([v293]) = v292 {
-                                                        if v293 == v451 {
-                                                            let v1062 = C::rule_mba_5_precondition(ctx, v251);
-                                                            if let Some(v1063) = v1062 {
-                                                                let v1064 = &C::any(ctx, v293);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1071.
-                                                                return Some(v1064.clone());
+                                &SimpleAst::And([v382, v383]) => {
+                                    let v324 = &C::lookup_value(ctx, v36);
+                                    if let Some(v325) = v324 {
+                                        if let &SimpleAst::Or([v544, v545]) = v325 {
+                                            if v382 == v545 {
+                                                let v384 = &C::lookup_value(ctx, v383);
+                                                if let Some(v385) = v384 {
+                                                    if let &SimpleAst::Neg([v386]) = v385 {
+                                                        if v386 == v544 {
+                                                            let v1155 = C::rule_mba_5_precondition(ctx, v344);
+                                                            if let Some(v1156) = v1155 {
+                                                                let v1157 = &C::any(ctx, v386);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1205.
+                                                                return Some(v1157.clone());
                                                             }
                                                         }
                                                     }
@@ -438,73 +459,73 @@ pub fn constructor_lower<C: Context>(
                                             }
                                         }
                                     }
-                                    if v229 == v289 {
-                                        let v1048 = C::rule_mba_2_precondition(ctx, v251);
-                                        if let Some(v1049) = v1048 {
-                                            let v1050 = &C::any(ctx, v290);
-                                            let v1051 = C::lookup_id(ctx, v1050);
-                                            let v1052 = &C::neg(ctx, v1051);
-                                            let v1053 = C::lookup_id(ctx, v1052);
-                                            let v1054 = &C::any(ctx, v289);
-                                            let v1055 = C::lookup_id(ctx, v1054);
-                                            let v1056 = &C::and(ctx, v1053, v1055);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1057.
-                                            return Some(v1056.clone());
-                                        }
-                                    }
-                                }
-                                &SimpleAst::Xor([v1394, v1395]) => {
-                                    let v230 = &C::lookup_value(ctx, v229);
-                                    if let Some(v231) = v230 {
-                                        if let &SimpleAst::Mul([v232, v233]) = v231 {
-                                            let v261 = &C::lookup_value(ctx, v232);
-                                            if let Some(v262) = v261 {
-                                                if let &SimpleAst::Mul([v1407, v1408]) = v262 {
-                                                    if v251 == v1408 {
-                                                        let v268 = &C::lookup_value(ctx, v233);
-                                                        if let Some(v269) = v268 {
-                                                            if let &SimpleAst::Add([v1409, v1410]) = v269 {
-                                                                let v1396 = &C::lookup_value(ctx, v1395);
-                                                                if let Some(v1397) = v1396 {
-                                                                    if let &SimpleAst::Neg([v1398]) = v1397 {
-                                                                        let v1399 = &C::lookup_value(ctx, v1398);
-                                                                        if let Some(v1400) = v1399 {
-                                                                            if let &SimpleAst::Or([v1401, v1402]) = v1400 {
-                                                                                let v1403 = &C::lookup_value(ctx, v1402);
-                                                                                if let Some(v1404) = v1403 {
-                                                                                    if let &SimpleAst::And([v1405, v1406]) = v1404 {
-                                                                                        if v1394 == v1405 {
-                                                                                            let v1411 = &C::lookup_value(ctx, v1409);
-                                                                                            if let Some(v1412) = v1411 {
-                                                                                                if let &SimpleAst::And([v1413, v1414]) = v1412 {
-                                                                                                    if v1394 == v1413 {
-                                                                                                        if v1401 == v1414 {
-                                                                                                            let v1415 = &C::lookup_value(ctx, v1410);
-                                                                                                            if let Some(v1416) = v1415 {
-                                                                                                                if let &SimpleAst::Or([v1417, v1418]) = v1416 {
-                                                                                                                    if v1406 == v1417 {
-                                                                                                                        let v1419 = &C::lookup_value(ctx, v1418);
-                                                                                                                        if let Some(v1420) = v1419 {
-                                                                                                                            if let &SimpleAst::Or([v1421, v1422]) = v1420 {
-                                                                                                                                if v1401 == v1421 {
-                                                                                                                                    let v1423 = &C::lookup_value(ctx, v1422);
-                                                                                                                                    if let Some(v1424) = v1423 {
-                                                                                                                                        if let &SimpleAst::Neg([v1425]) = v1424 {
-                                                                                                                                            if v1394 == v1425 {
-                                                                                                                                                let v1426 = C::rule_linear_mba_2_precondition(ctx, v1407);
-                                                                                                                                                if let Some(v1427) = v1426 {
-                                                                                                                                                    let v544 = C::get_width(ctx, v251);
-                                                                                                                                                    let v545 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v544);
-                                                                                                                                                    let v546 = C::lookup_id(ctx, v545);
-                                                                                                                                                    let v547 = &C::any(ctx, v251);
-                                                                                                                                                    let v548 = C::lookup_id(ctx, v547);
-                                                                                                                                                    let v549 = &C::mul(ctx, v546, v548);
-                                                                                                                                                    let v1428 = C::lookup_id(ctx, v549);
-                                                                                                                                                    let v1429 = &C::any(ctx, v1401);
-                                                                                                                                                    let v1430 = C::lookup_id(ctx, v1429);
-                                                                                                                                                    let v1431 = &C::mul(ctx, v1428, v1430);
-                                                                                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1237.
-                                                                                                                                                    return Some(v1431.clone());
+                                    if v36 == v382 {
+                                        let v1141 = C::rule_mba_2_precondition(ctx, v344);
+                                        if let Some(v1142) = v1141 {
+                                            let v1143 = &C::any(ctx, v383);
+                                            let v1144 = C::lookup_id(ctx, v1143);
+                                            let v1145 = &C::neg(ctx, v1144);
+                                            let v1146 = C::lookup_id(ctx, v1145);
+                                            let v1147 = &C::any(ctx, v382);
+                                            let v1148 = C::lookup_id(ctx, v1147);
+                                            let v1149 = &C::and(ctx, v1146, v1148);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1191.
+                                            return Some(v1149.clone());
+                                        }
+                                    }
+                                }
+                                &SimpleAst::Xor([v1487, v1488]) => {
+                                    let v324 = &C::lookup_value(ctx, v36);
+                                    if let Some(v325) = v324 {
+                                        if let &SimpleAst::Mul([v326, v327]) = v325 {
+                                            let v354 = &C::lookup_value(ctx, v326);
+                                            if let Some(v355) = v354 {
+                                                if let &SimpleAst::Mul([v1500, v1501]) = v355 {
+                                                    if v344 == v1501 {
+                                                        let v361 = &C::lookup_value(ctx, v327);
+                                                        if let Some(v362) = v361 {
+                                                            if let &SimpleAst::Add([v1502, v1503]) = v362 {
+                                                                let v1489 = &C::lookup_value(ctx, v1488);
+                                                                if let Some(v1490) = v1489 {
+                                                                    if let &SimpleAst::Neg([v1491]) = v1490 {
+                                                                        let v1492 = &C::lookup_value(ctx, v1491);
+                                                                        if let Some(v1493) = v1492 {
+                                                                            if let &SimpleAst::Or([v1494, v1495]) = v1493 {
+                                                                                let v1496 = &C::lookup_value(ctx, v1495);
+                                                                                if let Some(v1497) = v1496 {
+                                                                                    if let &SimpleAst::And([v1498, v1499]) = v1497 {
+                                                                                        if v1487 == v1498 {
+                                                                                            let v1504 = &C::lookup_value(ctx, v1502);
+                                                                                            if let Some(v1505) = v1504 {
+                                                                                                if let &SimpleAst::And([v1506, v1507]) = v1505 {
+                                                                                                    if v1487 == v1506 {
+                                                                                                        if v1494 == v1507 {
+                                                                                                            let v1508 = &C::lookup_value(ctx, v1503);
+                                                                                                            if let Some(v1509) = v1508 {
+                                                                                                                if let &SimpleAst::Or([v1510, v1511]) = v1509 {
+                                                                                                                    if v1499 == v1510 {
+                                                                                                                        let v1512 = &C::lookup_value(ctx, v1511);
+                                                                                                                        if let Some(v1513) = v1512 {
+                                                                                                                            if let &SimpleAst::Or([v1514, v1515]) = v1513 {
+                                                                                                                                if v1494 == v1514 {
+                                                                                                                                    let v1516 = &C::lookup_value(ctx, v1515);
+                                                                                                                                    if let Some(v1517) = v1516 {
+                                                                                                                                        if let &SimpleAst::Neg([v1518]) = v1517 {
+                                                                                                                                            if v1487 == v1518 {
+                                                                                                                                                let v1519 = C::rule_linear_mba_2_precondition(ctx, v1500);
+                                                                                                                                                if let Some(v1520) = v1519 {
+                                                                                                                                                    let v637 = C::get_width(ctx, v344);
+                                                                                                                                                    let v638 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v637);
+                                                                                                                                                    let v639 = C::lookup_id(ctx, v638);
+                                                                                                                                                    let v640 = &C::any(ctx, v344);
+                                                                                                                                                    let v641 = C::lookup_id(ctx, v640);
+                                                                                                                                                    let v642 = &C::mul(ctx, v639, v641);
+                                                                                                                                                    let v1521 = C::lookup_id(ctx, v642);
+                                                                                                                                                    let v1522 = &C::any(ctx, v1494);
+                                                                                                                                                    let v1523 = C::lookup_id(ctx, v1522);
+                                                                                                                                                    let v1524 = &C::mul(ctx, v1521, v1523);
+                                                                                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1371.
+                                                                                                                                                    return Some(v1524.clone());
                                                                                                                                                 }
                                                                                                                                             }
                                                                                                                                         }
@@ -538,86 +559,86 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::And([v445, v446]) => {
-                        let v230 = &C::lookup_value(ctx, v229);
-                        if let Some(v231) = v230 {
-                            match v231 {
-                                &SimpleAst::Mul([v232, v233]) => {
-                                    let v1101 = &C::lookup_value(ctx, v445);
-                                    if let Some(v1102) = v1101 {
-                                        if let &SimpleAst::Neg([v1103]) = v1102 {
-                                            if v232 == v446 {
-                                                let v268 = &C::lookup_value(ctx, v233);
-                                                if let Some(v269) = v268 {
-                                                    if let &SimpleAst::Mul([v469, v470]) = v269 {
-                                                        let v1116 = C::rule_new_24_precondition(ctx, v469, v470);
-                                                        if let Some(v1117) = v1116 {
-                                                            let v1106 = C::get_width(ctx, v1103);
-                                                            let v1107 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v1106);
-                                                            let v1108 = C::lookup_id(ctx, v1107);
-                                                            let v1109 = &C::any(ctx, v1103);
-                                                            let v1110 = C::lookup_id(ctx, v1109);
-                                                            let v1111 = &C::any(ctx, v446);
-                                                            let v1112 = C::lookup_id(ctx, v1111);
-                                                            let v1113 = &C::and(ctx, v1110, v1112);
-                                                            let v1114 = C::lookup_id(ctx, v1113);
-                                                            let v1115 = &C::mul(ctx, v1108, v1114);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1112.
-                                                            return Some(v1115.clone());
+                    &SimpleAst::And([v538, v539]) => {
+                        let v324 = &C::lookup_value(ctx, v36);
+                        if let Some(v325) = v324 {
+                            match v325 {
+                                &SimpleAst::Mul([v326, v327]) => {
+                                    let v1194 = &C::lookup_value(ctx, v538);
+                                    if let Some(v1195) = v1194 {
+                                        if let &SimpleAst::Neg([v1196]) = v1195 {
+                                            if v326 == v539 {
+                                                let v361 = &C::lookup_value(ctx, v327);
+                                                if let Some(v362) = v361 {
+                                                    if let &SimpleAst::Mul([v562, v563]) = v362 {
+                                                        let v1209 = C::rule_new_24_precondition(ctx, v562, v563);
+                                                        if let Some(v1210) = v1209 {
+                                                            let v1199 = C::get_width(ctx, v1196);
+                                                            let v1200 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v1199);
+                                                            let v1201 = C::lookup_id(ctx, v1200);
+                                                            let v1202 = &C::any(ctx, v1196);
+                                                            let v1203 = C::lookup_id(ctx, v1202);
+                                                            let v1204 = &C::any(ctx, v539);
+                                                            let v1205 = C::lookup_id(ctx, v1204);
+                                                            let v1206 = &C::and(ctx, v1203, v1205);
+                                                            let v1207 = C::lookup_id(ctx, v1206);
+                                                            let v1208 = &C::mul(ctx, v1201, v1207);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1246.
+                                                            return Some(v1208.clone());
                                                         }
                                                     }
                                                 }
                                             }
-                                            if v233 == v446 {
-                                                let v1104 = C::rule_new_23_precondition(ctx, v232);
-                                                if let Some(v1105) = v1104 {
-                                                    let v1106 = C::get_width(ctx, v1103);
-                                                    let v1107 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v1106);
-                                                    let v1108 = C::lookup_id(ctx, v1107);
-                                                    let v1109 = &C::any(ctx, v1103);
-                                                    let v1110 = C::lookup_id(ctx, v1109);
-                                                    let v1111 = &C::any(ctx, v446);
-                                                    let v1112 = C::lookup_id(ctx, v1111);
-                                                    let v1113 = &C::and(ctx, v1110, v1112);
-                                                    let v1114 = C::lookup_id(ctx, v1113);
-                                                    let v1115 = &C::mul(ctx, v1108, v1114);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1105.
-                                                    return Some(v1115.clone());
+                                            if v327 == v539 {
+                                                let v1197 = C::rule_new_23_precondition(ctx, v326);
+                                                if let Some(v1198) = v1197 {
+                                                    let v1199 = C::get_width(ctx, v1196);
+                                                    let v1200 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v1199);
+                                                    let v1201 = C::lookup_id(ctx, v1200);
+                                                    let v1202 = &C::any(ctx, v1196);
+                                                    let v1203 = C::lookup_id(ctx, v1202);
+                                                    let v1204 = &C::any(ctx, v539);
+                                                    let v1205 = C::lookup_id(ctx, v1204);
+                                                    let v1206 = &C::and(ctx, v1203, v1205);
+                                                    let v1207 = C::lookup_id(ctx, v1206);
+                                                    let v1208 = &C::mul(ctx, v1201, v1207);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1239.
+                                                    return Some(v1208.clone());
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                &SimpleAst::And([v282, v283]) => {
-                                    let v1101 = &C::lookup_value(ctx, v445);
-                                    if let Some(v1102) = v1101 {
-                                        match v1102 {
-                                            &SimpleAst::And([v1286, v1287]) => {
-                                                let v447 = &C::lookup_value(ctx, v282);
-                                                if let Some(v448) = v447 {
-                                                    if let &SimpleAst::And([v1291, v1292]) = v448 {
-                                                        if v1286 == v1291 {
-                                                            let v1142 = &C::lookup_value(ctx, v446);
-                                                            if let Some(v1143) = v1142 {
-                                                                if let &SimpleAst::Neg([v1144]) = v1143 {
-                                                                    if v283 == v1144 {
-                                                                        let v1288 = &C::lookup_value(ctx, v1287);
-                                                                        if let Some(v1289) = v1288 {
-                                                                            if let &SimpleAst::Neg([v1290]) = v1289 {
-                                                                                if v1290 == v1292 {
-                                                                                    let v1293 = &C::any(ctx, v1286);
-                                                                                    let v1294 = C::lookup_id(ctx, v1293);
-                                                                                    let v1295 = &C::any(ctx, v1290);
-                                                                                    let v1296 = C::lookup_id(ctx, v1295);
-                                                                                    let v1297 = &C::any(ctx, v1144);
-                                                                                    let v1298 = C::lookup_id(ctx, v1297);
-                                                                                    let v1299 = &C::xor(ctx, v1296, v1298);
-                                                                                    let v1300 = C::lookup_id(ctx, v1299);
-                                                                                    let v1301 = &C::neg(ctx, v1300);
-                                                                                    let v1302 = C::lookup_id(ctx, v1301);
-                                                                                    let v1303 = &C::and(ctx, v1294, v1302);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1201.
-                                                                                    return Some(v1303.clone());
+                                &SimpleAst::And([v375, v376]) => {
+                                    let v1194 = &C::lookup_value(ctx, v538);
+                                    if let Some(v1195) = v1194 {
+                                        match v1195 {
+                                            &SimpleAst::And([v1379, v1380]) => {
+                                                let v540 = &C::lookup_value(ctx, v375);
+                                                if let Some(v541) = v540 {
+                                                    if let &SimpleAst::And([v1384, v1385]) = v541 {
+                                                        if v1379 == v1384 {
+                                                            let v1235 = &C::lookup_value(ctx, v539);
+                                                            if let Some(v1236) = v1235 {
+                                                                if let &SimpleAst::Neg([v1237]) = v1236 {
+                                                                    if v376 == v1237 {
+                                                                        let v1381 = &C::lookup_value(ctx, v1380);
+                                                                        if let Some(v1382) = v1381 {
+                                                                            if let &SimpleAst::Neg([v1383]) = v1382 {
+                                                                                if v1383 == v1385 {
+                                                                                    let v1386 = &C::any(ctx, v1379);
+                                                                                    let v1387 = C::lookup_id(ctx, v1386);
+                                                                                    let v1388 = &C::any(ctx, v1383);
+                                                                                    let v1389 = C::lookup_id(ctx, v1388);
+                                                                                    let v1390 = &C::any(ctx, v1237);
+                                                                                    let v1391 = C::lookup_id(ctx, v1390);
+                                                                                    let v1392 = &C::xor(ctx, v1389, v1391);
+                                                                                    let v1393 = C::lookup_id(ctx, v1392);
+                                                                                    let v1394 = &C::neg(ctx, v1393);
+                                                                                    let v1395 = C::lookup_id(ctx, v1394);
+                                                                                    let v1396 = &C::and(ctx, v1387, v1395);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1335.
+                                                                                    return Some(v1396.clone());
                                                                                 }
                                                                             }
                                                                         }
@@ -628,41 +649,41 @@ pub fn constructor_lower<C: Context>(
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Neg([v1103]) => {
-                                                if v282 == v446 {
-                                                    if v283 == v1103 {
-                                                        let v450 = &C::any(ctx, v446);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1195.
-                                                        return Some(v450.clone());
+                                            &SimpleAst::Neg([v1196]) => {
+                                                if v375 == v539 {
+                                                    if v376 == v1196 {
+                                                        let v543 = &C::any(ctx, v539);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1329.
+                                                        return Some(v543.clone());
                                                     }
                                                 }
                                             }
                                             _ => {}
                                         }
                                     }
-                                    if v283 == v445 {
-                                        let v1142 = &C::lookup_value(ctx, v446);
-                                        if let Some(v1143) = v1142 {
-                                            if let &SimpleAst::Neg([v1144]) = v1143 {
-                                                if v282 == v1144 {
-                                                    let v933 = &C::any(ctx, v445);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1145.
-                                                    return Some(v933.clone());
+                                    if v376 == v538 {
+                                        let v1235 = &C::lookup_value(ctx, v539);
+                                        if let Some(v1236) = v1235 {
+                                            if let &SimpleAst::Neg([v1237]) = v1236 {
+                                                if v375 == v1237 {
+                                                    let v1026 = &C::any(ctx, v538);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1279.
+                                                    return Some(v1026.clone());
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                &SimpleAst::Neg([v239]) => {
-                                    let v993 = &C::lookup_value(ctx, v239);
-                                    if let Some(v994) = v993 {
-                                        if let &SimpleAst::And([v1126, v1127]) = v994 {
-                                            if v445 == v1126 {
-                                                if v446 == v1127 {
-                                                    let v1128 = C::get_width(ctx, v445);
-                                                    let v1129 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v1128);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1126.
-                                                    return Some(v1129.clone());
+                                &SimpleAst::Neg([v333]) => {
+                                    let v1086 = &C::lookup_value(ctx, v333);
+                                    if let Some(v1087) = v1086 {
+                                        if let &SimpleAst::And([v1219, v1220]) = v1087 {
+                                            if v538 == v1219 {
+                                                if v539 == v1220 {
+                                                    let v1221 = C::get_width(ctx, v538);
+                                                    let v1222 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v1221);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1260.
+                                                    return Some(v1222.clone());
                                                 }
                                             }
                                         }
@@ -672,37 +693,37 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Or([v280, v281]) => {
-                        let v230 = &C::lookup_value(ctx, v229);
-                        if let Some(v231) = v230 {
-                            if let &SimpleAst::Mul([v232, v233]) = v231 {
-                                if v233 == v280 {
-                                    let v1085 = C::rule_new_3_precondition(ctx, v232);
-                                    if let Some(v1086) = v1085 {
-                                        let v284 = &C::any(ctx, v280);
-                                        let v285 = C::lookup_id(ctx, v284);
-                                        let v1087 = &C::neg(ctx, v285);
-                                        let v1088 = C::lookup_id(ctx, v1087);
-                                        let v1089 = &C::any(ctx, v281);
-                                        let v1090 = C::lookup_id(ctx, v1089);
-                                        let v1091 = &C::and(ctx, v1088, v1090);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1092.
-                                        return Some(v1091.clone());
-                                    }
-                                }
-                                let v268 = &C::lookup_value(ctx, v233);
-                                if let Some(v269) = v268 {
-                                    if let &SimpleAst::And([v270, v271]) = v269 {
-                                        if v270 == v281 {
-                                            let v1057 = &C::lookup_value(ctx, v271);
-                                            if let Some(v1058) = v1057 {
-                                                if let &SimpleAst::Neg([v1059]) = v1058 {
-                                                    if v280 == v1059 {
-                                                        let v1060 = C::rule_mba_4_precondition(ctx, v232);
-                                                        if let Some(v1061) = v1060 {
-                                                            let v284 = &C::any(ctx, v280);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1064.
-                                                            return Some(v284.clone());
+                    &SimpleAst::Or([v373, v374]) => {
+                        let v324 = &C::lookup_value(ctx, v36);
+                        if let Some(v325) = v324 {
+                            if let &SimpleAst::Mul([v326, v327]) = v325 {
+                                if v327 == v373 {
+                                    let v1178 = C::rule_new_3_precondition(ctx, v326);
+                                    if let Some(v1179) = v1178 {
+                                        let v377 = &C::any(ctx, v373);
+                                        let v378 = C::lookup_id(ctx, v377);
+                                        let v1180 = &C::neg(ctx, v378);
+                                        let v1181 = C::lookup_id(ctx, v1180);
+                                        let v1182 = &C::any(ctx, v374);
+                                        let v1183 = C::lookup_id(ctx, v1182);
+                                        let v1184 = &C::and(ctx, v1181, v1183);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1226.
+                                        return Some(v1184.clone());
+                                    }
+                                }
+                                let v361 = &C::lookup_value(ctx, v327);
+                                if let Some(v362) = v361 {
+                                    if let &SimpleAst::And([v363, v364]) = v362 {
+                                        if v363 == v374 {
+                                            let v1150 = &C::lookup_value(ctx, v364);
+                                            if let Some(v1151) = v1150 {
+                                                if let &SimpleAst::Neg([v1152]) = v1151 {
+                                                    if v373 == v1152 {
+                                                        let v1153 = C::rule_mba_4_precondition(ctx, v326);
+                                                        if let Some(v1154) = v1153 {
+                                                            let v377 = &C::any(ctx, v373);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1198.
+                                                            return Some(v377.clone());
                                                         }
                                                     }
                                                 }
@@ -713,76 +734,76 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Xor([v315, v316]) => {
-                        let v230 = &C::lookup_value(ctx, v229);
-                        if let Some(v231) = v230 {
-                            match v231 {
-                                &SimpleAst::Mul([v232, v233]) => {
-                                    let v268 = &C::lookup_value(ctx, v233);
-                                    if let Some(v269) = v268 {
-                                        if let &SimpleAst::Or([v488, v489]) = v269 {
-                                            if v315 == v488 {
-                                                if v316 == v489 {
-                                                    let v1077 = C::rule_new_2_precondition(ctx, v232);
-                                                    if let Some(v1078) = v1077 {
-                                                        let v465 = C::get_width(ctx, v315);
-                                                        let v466 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v465);
-                                                        let v1079 = C::lookup_id(ctx, v466);
-                                                        let v1080 = &C::any(ctx, v315);
-                                                        let v1081 = C::lookup_id(ctx, v1080);
-                                                        let v483 = &C::any(ctx, v316);
-                                                        let v484 = C::lookup_id(ctx, v483);
-                                                        let v1082 = &C::and(ctx, v1081, v484);
-                                                        let v1083 = C::lookup_id(ctx, v1082);
-                                                        let v1084 = &C::mul(ctx, v1079, v1083);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1085.
-                                                        return Some(v1084.clone());
+                    &SimpleAst::Xor([v408, v409]) => {
+                        let v324 = &C::lookup_value(ctx, v36);
+                        if let Some(v325) = v324 {
+                            match v325 {
+                                &SimpleAst::Mul([v326, v327]) => {
+                                    let v361 = &C::lookup_value(ctx, v327);
+                                    if let Some(v362) = v361 {
+                                        if let &SimpleAst::Or([v581, v582]) = v362 {
+                                            if v408 == v581 {
+                                                if v409 == v582 {
+                                                    let v1170 = C::rule_new_2_precondition(ctx, v326);
+                                                    if let Some(v1171) = v1170 {
+                                                        let v558 = C::get_width(ctx, v408);
+                                                        let v559 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v558);
+                                                        let v1172 = C::lookup_id(ctx, v559);
+                                                        let v1173 = &C::any(ctx, v408);
+                                                        let v1174 = C::lookup_id(ctx, v1173);
+                                                        let v576 = &C::any(ctx, v409);
+                                                        let v577 = C::lookup_id(ctx, v576);
+                                                        let v1175 = &C::and(ctx, v1174, v577);
+                                                        let v1176 = C::lookup_id(ctx, v1175);
+                                                        let v1177 = &C::mul(ctx, v1172, v1176);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1219.
+                                                        return Some(v1177.clone());
                                                     }
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                &SimpleAst::Or([v451, v452]) => {
-                                    let v453 = &C::lookup_value(ctx, v451);
-                                    if let Some(v454) = v453 {
-                                        if let &SimpleAst::Xor([v1371, v1372]) = v454 {
-                                            let v1256 = &C::lookup_value(ctx, v316);
-                                            if let Some(v1257) = v1256 {
-                                                if let &SimpleAst::Or([v1362, v1363]) = v1257 {
-                                                    let v1364 = &C::lookup_value(ctx, v1363);
-                                                    if let Some(v1365) = v1364 {
-                                                        if let &SimpleAst::And([v1366, v1367]) = v1365 {
-                                                            if v315 == v1366 {
-                                                                let v1368 = &C::lookup_value(ctx, v1367);
-                                                                if let Some(v1369) = v1368 {
-                                                                    if let &SimpleAst::Neg([v1370]) = v1369 {
-                                                                        if v1370 == v1371 {
-                                                                            let v1373 = &C::lookup_value(ctx, v1372);
-                                                                            if let Some(v1374) = v1373 {
-                                                                                if let &SimpleAst::Xor([v1375, v1376]) = v1374 {
-                                                                                    if v315 == v1376 {
-                                                                                        if v1362 == v1375 {
-                                                                                            let v1377 = &C::lookup_value(ctx, v452);
-                                                                                            if let Some(v1378) = v1377 {
-                                                                                                if let &SimpleAst::And([v1379, v1380]) = v1378 {
-                                                                                                    if v315 == v1380 {
-                                                                                                        if v1362 == v1379 {
-                                                                                                            let v1381 = &C::any(ctx, v1362);
-                                                                                                            let v1382 = C::lookup_id(ctx, v1381);
-                                                                                                            let v1383 = &C::any(ctx, v1362);
-                                                                                                            let v1384 = C::lookup_id(ctx, v1383);
-                                                                                                            let v1385 = &C::any(ctx, v315);
-                                                                                                            let v1386 = C::lookup_id(ctx, v1385);
-                                                                                                            let v1387 = &C::any(ctx, v1370);
-                                                                                                            let v1388 = C::lookup_id(ctx, v1387);
-                                                                                                            let v1389 = &C::or(ctx, v1386, v1388);
-                                                                                                            let v1390 = C::lookup_id(ctx, v1389);
-                                                                                                            let v1391 = &C::xor(ctx, v1384, v1390);
-                                                                                                            let v1392 = C::lookup_id(ctx, v1391);
-                                                                                                            let v1393 = &C::add(ctx, v1382, v1392);
-                                                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1231.
-                                                                                                            return Some(v1393.clone());
+                                &SimpleAst::Or([v544, v545]) => {
+                                    let v546 = &C::lookup_value(ctx, v544);
+                                    if let Some(v547) = v546 {
+                                        if let &SimpleAst::Xor([v1464, v1465]) = v547 {
+                                            let v1349 = &C::lookup_value(ctx, v409);
+                                            if let Some(v1350) = v1349 {
+                                                if let &SimpleAst::Or([v1455, v1456]) = v1350 {
+                                                    let v1457 = &C::lookup_value(ctx, v1456);
+                                                    if let Some(v1458) = v1457 {
+                                                        if let &SimpleAst::And([v1459, v1460]) = v1458 {
+                                                            if v408 == v1459 {
+                                                                let v1461 = &C::lookup_value(ctx, v1460);
+                                                                if let Some(v1462) = v1461 {
+                                                                    if let &SimpleAst::Neg([v1463]) = v1462 {
+                                                                        if v1463 == v1464 {
+                                                                            let v1466 = &C::lookup_value(ctx, v1465);
+                                                                            if let Some(v1467) = v1466 {
+                                                                                if let &SimpleAst::Xor([v1468, v1469]) = v1467 {
+                                                                                    if v408 == v1469 {
+                                                                                        if v1455 == v1468 {
+                                                                                            let v1470 = &C::lookup_value(ctx, v545);
+                                                                                            if let Some(v1471) = v1470 {
+                                                                                                if let &SimpleAst::And([v1472, v1473]) = v1471 {
+                                                                                                    if v408 == v1473 {
+                                                                                                        if v1455 == v1472 {
+                                                                                                            let v1474 = &C::any(ctx, v1455);
+                                                                                                            let v1475 = C::lookup_id(ctx, v1474);
+                                                                                                            let v1476 = &C::any(ctx, v1455);
+                                                                                                            let v1477 = C::lookup_id(ctx, v1476);
+                                                                                                            let v1478 = &C::any(ctx, v408);
+                                                                                                            let v1479 = C::lookup_id(ctx, v1478);
+                                                                                                            let v1480 = &C::any(ctx, v1463);
+                                                                                                            let v1481 = C::lookup_id(ctx, v1480);
+                                                                                                            let v1482 = &C::or(ctx, v1479, v1481);
+                                                                                                            let v1483 = C::lookup_id(ctx, v1482);
+                                                                                                            let v1484 = &C::xor(ctx, v1477, v1483);
+                                                                                                            let v1485 = C::lookup_id(ctx, v1484);
+                                                                                                            let v1486 = &C::add(ctx, v1475, v1485);
+                                                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1365.
+                                                                                                            return Some(v1486.clone());
                                                                                                         }
                                                                                                     }
                                                                                                 }
@@ -802,31 +823,31 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Xor([v308, v309]) => {
-                                    let v1256 = &C::lookup_value(ctx, v316);
-                                    if let Some(v1257) = v1256 {
-                                        if let &SimpleAst::Xor([v1258, v1259]) = v1257 {
-                                            if v308 == v1259 {
-                                                let v1260 = &C::lookup_value(ctx, v309);
-                                                if let Some(v1261) = v1260 {
-                                                    if let &SimpleAst::And([v1262, v1263]) = v1261 {
-                                                        if v315 == v1262 {
-                                                            if v1258 == v1263 {
-                                                                let v1264 = &C::any(ctx, v1259);
-                                                                let v1265 = C::lookup_id(ctx, v1264);
-                                                                let v1266 = &C::any(ctx, v1259);
-                                                                let v1267 = C::lookup_id(ctx, v1266);
-                                                                let v1268 = &C::any(ctx, v1258);
-                                                                let v1269 = C::lookup_id(ctx, v1268);
-                                                                let v501 = &C::any(ctx, v315);
-                                                                let v502 = C::lookup_id(ctx, v501);
-                                                                let v1270 = &C::or(ctx, v1269, v502);
-                                                                let v1271 = C::lookup_id(ctx, v1270);
-                                                                let v1272 = &C::xor(ctx, v1267, v1271);
-                                                                let v1273 = C::lookup_id(ctx, v1272);
-                                                                let v1274 = &C::add(ctx, v1265, v1273);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1183.
-                                                                return Some(v1274.clone());
+                                &SimpleAst::Xor([v401, v402]) => {
+                                    let v1349 = &C::lookup_value(ctx, v409);
+                                    if let Some(v1350) = v1349 {
+                                        if let &SimpleAst::Xor([v1351, v1352]) = v1350 {
+                                            if v401 == v1352 {
+                                                let v1353 = &C::lookup_value(ctx, v402);
+                                                if let Some(v1354) = v1353 {
+                                                    if let &SimpleAst::And([v1355, v1356]) = v1354 {
+                                                        if v408 == v1355 {
+                                                            if v1351 == v1356 {
+                                                                let v1357 = &C::any(ctx, v1352);
+                                                                let v1358 = C::lookup_id(ctx, v1357);
+                                                                let v1359 = &C::any(ctx, v1352);
+                                                                let v1360 = C::lookup_id(ctx, v1359);
+                                                                let v1361 = &C::any(ctx, v1351);
+                                                                let v1362 = C::lookup_id(ctx, v1361);
+                                                                let v594 = &C::any(ctx, v408);
+                                                                let v595 = C::lookup_id(ctx, v594);
+                                                                let v1363 = &C::or(ctx, v1362, v595);
+                                                                let v1364 = C::lookup_id(ctx, v1363);
+                                                                let v1365 = &C::xor(ctx, v1360, v1364);
+                                                                let v1366 = C::lookup_id(ctx, v1365);
+                                                                let v1367 = &C::add(ctx, v1358, v1366);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1317.
+                                                                return Some(v1367.clone());
                                                             }
                                                         }
                                                     }
@@ -842,65 +863,65 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            let v230 = &C::lookup_value(ctx, v229);
-            if let Some(v231) = v230 {
-                match v231 {
-                    &SimpleAst::Mul([v232, v233]) => {
-                        let v268 = &C::lookup_value(ctx, v233);
-                        if let Some(v269) = v268 {
-                            match v269 {
-                                &SimpleAst::Mul([v469, v470]) => {
-                                    if let Some(v250) = v249 {
-                                        match v250 {
-                                            &SimpleAst::Add([v912, v913]) => {
-                                                let v471 = &C::lookup_value(ctx, v470);
-                                                if let Some(v472) = v471 {
-                                                    match v472 {
-                                                        &SimpleAst::And([v473, v474]) => {
-                                                            let v914 = &C::lookup_value(ctx, v912);
-                                                            if let Some(v915) = v914 {
-                                                                if let &SimpleAst::Mul([v916, v917]) = v915 {
-                                                                    if v473 == v917 {
-                                                                        let v918 = &C::lookup_value(ctx, v913);
-                                                                        if let Some(v919) = v918 {
-                                                                            if let &SimpleAst::Mul([v920, v921]) = v919 {
-                                                                                if v474 == v921 {
-                                                                                    if v916 == v920 {
-                                                                                        let v961 = &C::lookup_value(ctx, v469);
-                                                                                        if let Some(v962) = v961 {
-                                                                                            if let &SimpleAst::Mul([v963, v964]) = v962 {
-                                                                                                if v916 == v964 {
-                                                                                                    let v965 = C::rule_xor_mul_shrink_precondition(ctx, v232, v963);
-                                                                                                    if let Some(v966) = v965 {
-                                                                                                        let v924 = &C::any(ctx, v916);
-                                                                                                        let v925 = C::lookup_id(ctx, v924);
-                                                                                                        let v926 = &C::any(ctx, v917);
-                                                                                                        let v927 = C::lookup_id(ctx, v926);
-                                                                                                        let v928 = &C::any(ctx, v921);
-                                                                                                        let v929 = C::lookup_id(ctx, v928);
-                                                                                                        let v967 = &C::xor(ctx, v927, v929);
-                                                                                                        let v968 = C::lookup_id(ctx, v967);
-                                                                                                        let v969 = &C::mul(ctx, v925, v968);
-                                                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 983.
-                                                                                                        return Some(v969.clone());
+            let v324 = &C::lookup_value(ctx, v36);
+            if let Some(v325) = v324 {
+                match v325 {
+                    &SimpleAst::Mul([v326, v327]) => {
+                        let v361 = &C::lookup_value(ctx, v327);
+                        if let Some(v362) = v361 {
+                            match v362 {
+                                &SimpleAst::Mul([v562, v563]) => {
+                                    if let Some(v343) = v342 {
+                                        match v343 {
+                                            &SimpleAst::Add([v1005, v1006]) => {
+                                                let v564 = &C::lookup_value(ctx, v563);
+                                                if let Some(v565) = v564 {
+                                                    match v565 {
+                                                        &SimpleAst::And([v566, v567]) => {
+                                                            let v1007 = &C::lookup_value(ctx, v1005);
+                                                            if let Some(v1008) = v1007 {
+                                                                if let &SimpleAst::Mul([v1009, v1010]) = v1008 {
+                                                                    if v566 == v1010 {
+                                                                        let v1011 = &C::lookup_value(ctx, v1006);
+                                                                        if let Some(v1012) = v1011 {
+                                                                            if let &SimpleAst::Mul([v1013, v1014]) = v1012 {
+                                                                                if v567 == v1014 {
+                                                                                    if v1009 == v1013 {
+                                                                                        let v1054 = &C::lookup_value(ctx, v562);
+                                                                                        if let Some(v1055) = v1054 {
+                                                                                            if let &SimpleAst::Mul([v1056, v1057]) = v1055 {
+                                                                                                if v1009 == v1057 {
+                                                                                                    let v1058 = C::rule_xor_mul_shrink_precondition(ctx, v326, v1056);
+                                                                                                    if let Some(v1059) = v1058 {
+                                                                                                        let v1017 = &C::any(ctx, v1009);
+                                                                                                        let v1018 = C::lookup_id(ctx, v1017);
+                                                                                                        let v1019 = &C::any(ctx, v1010);
+                                                                                                        let v1020 = C::lookup_id(ctx, v1019);
+                                                                                                        let v1021 = &C::any(ctx, v1014);
+                                                                                                        let v1022 = C::lookup_id(ctx, v1021);
+                                                                                                        let v1060 = &C::xor(ctx, v1020, v1022);
+                                                                                                        let v1061 = C::lookup_id(ctx, v1060);
+                                                                                                        let v1062 = &C::mul(ctx, v1018, v1061);
+                                                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1117.
+                                                                                                        return Some(v1062.clone());
                                                                                                     }
                                                                                                 }
                                                                                             }
                                                                                         }
-                                                                                        if v469 == v916 {
-                                                                                            let v922 = C::rule_or_mul_shrink_precondition(ctx, v232);
-                                                                                            if let Some(v923) = v922 {
-                                                                                                let v924 = &C::any(ctx, v916);
-                                                                                                let v925 = C::lookup_id(ctx, v924);
-                                                                                                let v926 = &C::any(ctx, v917);
-                                                                                                let v927 = C::lookup_id(ctx, v926);
-                                                                                                let v928 = &C::any(ctx, v921);
-                                                                                                let v929 = C::lookup_id(ctx, v928);
-                                                                                                let v930 = &C::or(ctx, v927, v929);
-                                                                                                let v931 = C::lookup_id(ctx, v930);
-                                                                                                let v932 = &C::mul(ctx, v925, v931);
-                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 952.
-                                                                                                return Some(v932.clone());
+                                                                                        if v562 == v1009 {
+                                                                                            let v1015 = C::rule_or_mul_shrink_precondition(ctx, v326);
+                                                                                            if let Some(v1016) = v1015 {
+                                                                                                let v1017 = &C::any(ctx, v1009);
+                                                                                                let v1018 = C::lookup_id(ctx, v1017);
+                                                                                                let v1019 = &C::any(ctx, v1010);
+                                                                                                let v1020 = C::lookup_id(ctx, v1019);
+                                                                                                let v1021 = &C::any(ctx, v1014);
+                                                                                                let v1022 = C::lookup_id(ctx, v1021);
+                                                                                                let v1023 = &C::or(ctx, v1020, v1022);
+                                                                                                let v1024 = C::lookup_id(ctx, v1023);
+                                                                                                let v1025 = &C::mul(ctx, v1018, v1024);
+                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1086.
+                                                                                                return Some(v1025.clone());
                                                                                             }
                                                                                         }
                                                                                     }
@@ -911,30 +932,30 @@ pub fn constructor_lower<C: Context>(
                                                                 }
                                                             }
                                                         }
-                                                        &SimpleAst::Or([v1000, v1001]) => {
-                                                            let v914 = &C::lookup_value(ctx, v912);
-                                                            if let Some(v915) = v914 {
-                                                                if let &SimpleAst::Mul([v916, v917]) = v915 {
-                                                                    if v469 == v916 {
-                                                                        if v917 == v1000 {
-                                                                            let v918 = &C::lookup_value(ctx, v913);
-                                                                            if let Some(v919) = v918 {
-                                                                                if let &SimpleAst::Mul([v920, v921]) = v919 {
-                                                                                    if v469 == v920 {
-                                                                                        if v921 == v1001 {
-                                                                                            let v1002 = C::rule_and_mul_shrink_precondition(ctx, v232);
-                                                                                            if let Some(v1003) = v1002 {
-                                                                                                let v924 = &C::any(ctx, v916);
-                                                                                                let v925 = C::lookup_id(ctx, v924);
-                                                                                                let v926 = &C::any(ctx, v917);
-                                                                                                let v927 = C::lookup_id(ctx, v926);
-                                                                                                let v928 = &C::any(ctx, v921);
-                                                                                                let v929 = C::lookup_id(ctx, v928);
-                                                                                                let v1004 = &C::and(ctx, v927, v929);
-                                                                                                let v1005 = C::lookup_id(ctx, v1004);
-                                                                                                let v1006 = &C::mul(ctx, v925, v1005);
-                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1016.
-                                                                                                return Some(v1006.clone());
+                                                        &SimpleAst::Or([v1093, v1094]) => {
+                                                            let v1007 = &C::lookup_value(ctx, v1005);
+                                                            if let Some(v1008) = v1007 {
+                                                                if let &SimpleAst::Mul([v1009, v1010]) = v1008 {
+                                                                    if v562 == v1009 {
+                                                                        if v1010 == v1093 {
+                                                                            let v1011 = &C::lookup_value(ctx, v1006);
+                                                                            if let Some(v1012) = v1011 {
+                                                                                if let &SimpleAst::Mul([v1013, v1014]) = v1012 {
+                                                                                    if v562 == v1013 {
+                                                                                        if v1014 == v1094 {
+                                                                                            let v1095 = C::rule_and_mul_shrink_precondition(ctx, v326);
+                                                                                            if let Some(v1096) = v1095 {
+                                                                                                let v1017 = &C::any(ctx, v1009);
+                                                                                                let v1018 = C::lookup_id(ctx, v1017);
+                                                                                                let v1019 = &C::any(ctx, v1010);
+                                                                                                let v1020 = C::lookup_id(ctx, v1019);
+                                                                                                let v1021 = &C::any(ctx, v1014);
+                                                                                                let v1022 = C::lookup_id(ctx, v1021);
+                                                                                                let v1097 = &C::and(ctx, v1020, v1022);
+                                                                                                let v1098 = C::lookup_id(ctx, v1097);
+                                                                                                let v1099 = &C::mul(ctx, v1018, v1098);
+                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1150.
+                                                                                                return Some(v1099.clone());
                                                                                             }
                                                                                         }
                                                                                     }
@@ -949,59 +970,59 @@ pub fn constructor_lower<C: Context>(
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Mul([v251, v252]) => {
-                                                let v471 = &C::lookup_value(ctx, v470);
-                                                if let Some(v472) = v471 {
-                                                    if let &SimpleAst::And([v473, v474]) = v472 {
-                                                        let v257 = &C::lookup_value(ctx, v252);
-                                                        if let Some(v258) = v257 {
-                                                            if let &SimpleAst::And([v289, v290]) = v258 {
-                                                                if v289 == v473 {
-                                                                    if v290 == v474 {
-                                                                        let v1024 = C::rule_merge_and_multipliers_precondition(ctx, v232);
-                                                                        if let Some(v1025) = v1024 {
-                                                                            let v1013 = &C::any(ctx, v251);
-                                                                            let v1014 = C::lookup_id(ctx, v1013);
-                                                                            let v300 = C::get_width(ctx, v251);
-                                                                            let v301 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v300);
-                                                                            let v302 = C::lookup_id(ctx, v301);
-                                                                            let v1026 = &C::any(ctx, v469);
-                                                                            let v1027 = C::lookup_id(ctx, v1026);
-                                                                            let v1028 = &C::mul(ctx, v302, v1027);
-                                                                            let v1029 = C::lookup_id(ctx, v1028);
-                                                                            let v1030 = &C::add(ctx, v1014, v1029);
-                                                                            let v1031 = C::lookup_id(ctx, v1030);
-                                                                            let v1032 = &C::any(ctx, v289);
-                                                                            let v1033 = C::lookup_id(ctx, v1032);
-                                                                            let v1034 = &C::any(ctx, v290);
-                                                                            let v1035 = C::lookup_id(ctx, v1034);
-                                                                            let v1036 = &C::and(ctx, v1033, v1035);
-                                                                            let v1037 = C::lookup_id(ctx, v1036);
-                                                                            let v1038 = &C::mul(ctx, v1031, v1037);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1043.
-                                                                            return Some(v1038.clone());
+                                            &SimpleAst::Mul([v344, v345]) => {
+                                                let v564 = &C::lookup_value(ctx, v563);
+                                                if let Some(v565) = v564 {
+                                                    if let &SimpleAst::And([v566, v567]) = v565 {
+                                                        let v350 = &C::lookup_value(ctx, v345);
+                                                        if let Some(v351) = v350 {
+                                                            if let &SimpleAst::And([v382, v383]) = v351 {
+                                                                if v382 == v566 {
+                                                                    if v383 == v567 {
+                                                                        let v1117 = C::rule_merge_and_multipliers_precondition(ctx, v326);
+                                                                        if let Some(v1118) = v1117 {
+                                                                            let v1106 = &C::any(ctx, v344);
+                                                                            let v1107 = C::lookup_id(ctx, v1106);
+                                                                            let v393 = C::get_width(ctx, v344);
+                                                                            let v394 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v393);
+                                                                            let v395 = C::lookup_id(ctx, v394);
+                                                                            let v1119 = &C::any(ctx, v562);
+                                                                            let v1120 = C::lookup_id(ctx, v1119);
+                                                                            let v1121 = &C::mul(ctx, v395, v1120);
+                                                                            let v1122 = C::lookup_id(ctx, v1121);
+                                                                            let v1123 = &C::add(ctx, v1107, v1122);
+                                                                            let v1124 = C::lookup_id(ctx, v1123);
+                                                                            let v1125 = &C::any(ctx, v382);
+                                                                            let v1126 = C::lookup_id(ctx, v1125);
+                                                                            let v1127 = &C::any(ctx, v383);
+                                                                            let v1128 = C::lookup_id(ctx, v1127);
+                                                                            let v1129 = &C::and(ctx, v1126, v1128);
+                                                                            let v1130 = C::lookup_id(ctx, v1129);
+                                                                            let v1131 = &C::mul(ctx, v1124, v1130);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1177.
+                                                                            return Some(v1131.clone());
                                                                         }
                                                                     }
                                                                 }
                                                             }
                                                         }
-                                                        if v251 == v469 {
-                                                            if v252 == v473 {
-                                                                let v1011 = C::rule_cancel_and_reduce_bitwise_subtraction_precondition(ctx, v232);
-                                                                if let Some(v1012) = v1011 {
-                                                                    let v1013 = &C::any(ctx, v251);
-                                                                    let v1014 = C::lookup_id(ctx, v1013);
-                                                                    let v1015 = &C::any(ctx, v252);
-                                                                    let v1016 = C::lookup_id(ctx, v1015);
-                                                                    let v1017 = &C::any(ctx, v474);
-                                                                    let v1018 = C::lookup_id(ctx, v1017);
-                                                                    let v1019 = &C::neg(ctx, v1018);
-                                                                    let v1020 = C::lookup_id(ctx, v1019);
-                                                                    let v1021 = &C::and(ctx, v1016, v1020);
-                                                                    let v1022 = C::lookup_id(ctx, v1021);
-                                                                    let v1023 = &C::mul(ctx, v1014, v1022);
-                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1036.
-                                                                    return Some(v1023.clone());
+                                                        if v344 == v562 {
+                                                            if v345 == v566 {
+                                                                let v1104 = C::rule_cancel_and_reduce_bitwise_subtraction_precondition(ctx, v326);
+                                                                if let Some(v1105) = v1104 {
+                                                                    let v1106 = &C::any(ctx, v344);
+                                                                    let v1107 = C::lookup_id(ctx, v1106);
+                                                                    let v1108 = &C::any(ctx, v345);
+                                                                    let v1109 = C::lookup_id(ctx, v1108);
+                                                                    let v1110 = &C::any(ctx, v567);
+                                                                    let v1111 = C::lookup_id(ctx, v1110);
+                                                                    let v1112 = &C::neg(ctx, v1111);
+                                                                    let v1113 = C::lookup_id(ctx, v1112);
+                                                                    let v1114 = &C::and(ctx, v1109, v1113);
+                                                                    let v1115 = C::lookup_id(ctx, v1114);
+                                                                    let v1116 = &C::mul(ctx, v1107, v1115);
+                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1170.
+                                                                    return Some(v1116.clone());
                                                                 }
                                                             }
                                                         }
@@ -1012,51 +1033,51 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::And([v270, v271]) => {
-                                    if v228 == v270 {
-                                        let v1039 = C::rule_mba_1_precondition(ctx, v232);
-                                        if let Some(v1040) = v1039 {
-                                            let v1041 = &C::any(ctx, v271);
-                                            let v1042 = C::lookup_id(ctx, v1041);
-                                            let v1043 = &C::neg(ctx, v1042);
-                                            let v1044 = C::lookup_id(ctx, v1043);
-                                            let v1045 = &C::any(ctx, v228);
-                                            let v1046 = C::lookup_id(ctx, v1045);
-                                            let v1047 = &C::and(ctx, v1044, v1046);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1050.
-                                            return Some(v1047.clone());
-                                        }
-                                    }
-                                    if let Some(v250) = v249 {
-                                        match v250 {
-                                            &SimpleAst::Add([v912, v913]) => {
-                                                if v270 == v912 {
-                                                    if v271 == v913 {
-                                                        let v970 = C::rule_xor_shrink_precondition(ctx, v232);
-                                                        if let Some(v971) = v970 {
-                                                            let v972 = &C::any(ctx, v912);
-                                                            let v973 = C::lookup_id(ctx, v972);
-                                                            let v974 = &C::any(ctx, v913);
-                                                            let v975 = C::lookup_id(ctx, v974);
-                                                            let v976 = &C::xor(ctx, v973, v975);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 990.
-                                                            return Some(v976.clone());
+                                &SimpleAst::And([v363, v364]) => {
+                                    if v35 == v363 {
+                                        let v1132 = C::rule_mba_1_precondition(ctx, v326);
+                                        if let Some(v1133) = v1132 {
+                                            let v1134 = &C::any(ctx, v364);
+                                            let v1135 = C::lookup_id(ctx, v1134);
+                                            let v1136 = &C::neg(ctx, v1135);
+                                            let v1137 = C::lookup_id(ctx, v1136);
+                                            let v1138 = &C::any(ctx, v35);
+                                            let v1139 = C::lookup_id(ctx, v1138);
+                                            let v1140 = &C::and(ctx, v1137, v1139);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1184.
+                                            return Some(v1140.clone());
+                                        }
+                                    }
+                                    if let Some(v343) = v342 {
+                                        match v343 {
+                                            &SimpleAst::Add([v1005, v1006]) => {
+                                                if v363 == v1005 {
+                                                    if v364 == v1006 {
+                                                        let v1063 = C::rule_xor_shrink_precondition(ctx, v326);
+                                                        if let Some(v1064) = v1063 {
+                                                            let v1065 = &C::any(ctx, v1005);
+                                                            let v1066 = C::lookup_id(ctx, v1065);
+                                                            let v1067 = &C::any(ctx, v1006);
+                                                            let v1068 = C::lookup_id(ctx, v1067);
+                                                            let v1069 = &C::xor(ctx, v1066, v1068);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1124.
+                                                            return Some(v1069.clone());
                                                         }
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Or([v280, v281]) => {
-                                                if v270 == v280 {
-                                                    if v271 == v281 {
-                                                        let v977 = C::rule_xor_shrink2_precondition(ctx, v232);
-                                                        if let Some(v978) = v977 {
-                                                            let v284 = &C::any(ctx, v280);
-                                                            let v285 = C::lookup_id(ctx, v284);
-                                                            let v286 = &C::any(ctx, v281);
-                                                            let v287 = C::lookup_id(ctx, v286);
-                                                            let v979 = &C::xor(ctx, v285, v287);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 997.
-                                                            return Some(v979.clone());
+                                            &SimpleAst::Or([v373, v374]) => {
+                                                if v363 == v373 {
+                                                    if v364 == v374 {
+                                                        let v1070 = C::rule_xor_shrink2_precondition(ctx, v326);
+                                                        if let Some(v1071) = v1070 {
+                                                            let v377 = &C::any(ctx, v373);
+                                                            let v378 = C::lookup_id(ctx, v377);
+                                                            let v379 = &C::any(ctx, v374);
+                                                            let v380 = C::lookup_id(ctx, v379);
+                                                            let v1072 = &C::xor(ctx, v378, v380);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1131.
+                                                            return Some(v1072.clone());
                                                         }
                                                     }
                                                 }
@@ -1065,40 +1086,40 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Xor([v294, v295]) => {
-                                    if let Some(v250) = v249 {
-                                        if let &SimpleAst::Or([v280, v281]) = v250 {
-                                            if v280 == v294 {
-                                                if v281 == v295 {
-                                                    let v1007 = C::rule_and_shrink_precondition(ctx, v232);
-                                                    if let Some(v1008) = v1007 {
-                                                        let v284 = &C::any(ctx, v280);
-                                                        let v285 = C::lookup_id(ctx, v284);
-                                                        let v286 = &C::any(ctx, v281);
-                                                        let v287 = C::lookup_id(ctx, v286);
-                                                        let v1009 = &C::and(ctx, v285, v287);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1023.
-                                                        return Some(v1009.clone());
+                                &SimpleAst::Xor([v387, v388]) => {
+                                    if let Some(v343) = v342 {
+                                        if let &SimpleAst::Or([v373, v374]) = v343 {
+                                            if v373 == v387 {
+                                                if v374 == v388 {
+                                                    let v1100 = C::rule_and_shrink_precondition(ctx, v326);
+                                                    if let Some(v1101) = v1100 {
+                                                        let v377 = &C::any(ctx, v373);
+                                                        let v378 = C::lookup_id(ctx, v377);
+                                                        let v379 = &C::any(ctx, v374);
+                                                        let v380 = C::lookup_id(ctx, v379);
+                                                        let v1102 = &C::and(ctx, v378, v380);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1157.
+                                                        return Some(v1102.clone());
                                                     }
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                &SimpleAst::Neg([v911]) => {
-                                    if let Some(v250) = v249 {
-                                        if let &SimpleAst::Mul([v251, v252]) = v250 {
-                                            if v232 == v251 {
-                                                if v232 == v252 {
-                                                    if v232 == v911 {
-                                                        let v544 = C::get_width(ctx, v251);
-                                                        let v545 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v544);
-                                                        let v546 = C::lookup_id(ctx, v545);
-                                                        let v547 = &C::any(ctx, v251);
-                                                        let v548 = C::lookup_id(ctx, v547);
-                                                        let v549 = &C::mul(ctx, v546, v548);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 946.
-                                                        return Some(v549.clone());
+                                &SimpleAst::Neg([v1004]) => {
+                                    if let Some(v343) = v342 {
+                                        if let &SimpleAst::Mul([v344, v345]) = v343 {
+                                            if v326 == v344 {
+                                                if v326 == v345 {
+                                                    if v326 == v1004 {
+                                                        let v637 = C::get_width(ctx, v344);
+                                                        let v638 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v637);
+                                                        let v639 = C::lookup_id(ctx, v638);
+                                                        let v640 = &C::any(ctx, v344);
+                                                        let v641 = C::lookup_id(ctx, v640);
+                                                        let v642 = &C::mul(ctx, v639, v641);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1080.
+                                                        return Some(v642.clone());
                                                     }
                                                 }
                                             }
@@ -1108,41 +1129,41 @@ pub fn constructor_lower<C: Context>(
                                 _ => {}
                             }
                         }
-                        if let Some(v250) = v249 {
-                            match v250 {
-                                &SimpleAst::Mul([v251, v252]) => {
-                                    let v257 = &C::lookup_value(ctx, v252);
-                                    if let Some(v258) = v257 {
-                                        match v258 {
-                                            &SimpleAst::Or([v259, v260]) => {
-                                                if v233 == v259 {
-                                                    let v867 = C::rule___check_bitwise_in_sums_cancel_terms_4_precondition(ctx, v251, v232);
-                                                    if let Some(v868) = v867 {
-                                                        let v869 = &C::any(ctx, v260);
-                                                        let v870 = C::lookup_id(ctx, v869);
-                                                        let v871 = &C::any(ctx, v259);
-                                                        let v872 = C::lookup_id(ctx, v871);
-                                                        let v873 = &C::any(ctx, v260);
-                                                        let v874 = C::lookup_id(ctx, v873);
-                                                        let v875 = &C::xor(ctx, v872, v874);
-                                                        let v876 = C::lookup_id(ctx, v875);
-                                                        let v877 = &C::add(ctx, v870, v876);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 921.
-                                                        return Some(v877.clone());
+                        if let Some(v343) = v342 {
+                            match v343 {
+                                &SimpleAst::Mul([v344, v345]) => {
+                                    let v350 = &C::lookup_value(ctx, v345);
+                                    if let Some(v351) = v350 {
+                                        match v351 {
+                                            &SimpleAst::Or([v352, v353]) => {
+                                                if v327 == v352 {
+                                                    let v960 = C::rule___check_bitwise_in_sums_cancel_terms_4_precondition(ctx, v344, v326);
+                                                    if let Some(v961) = v960 {
+                                                        let v962 = &C::any(ctx, v353);
+                                                        let v963 = C::lookup_id(ctx, v962);
+                                                        let v964 = &C::any(ctx, v352);
+                                                        let v965 = C::lookup_id(ctx, v964);
+                                                        let v966 = &C::any(ctx, v353);
+                                                        let v967 = C::lookup_id(ctx, v966);
+                                                        let v968 = &C::xor(ctx, v965, v967);
+                                                        let v969 = C::lookup_id(ctx, v968);
+                                                        let v970 = &C::add(ctx, v963, v969);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1055.
+                                                        return Some(v970.clone());
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Neg([v543]) => {
-                                                if v232 == v251 {
-                                                    if v233 == v543 {
-                                                        let v544 = C::get_width(ctx, v251);
-                                                        let v545 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v544);
-                                                        let v546 = C::lookup_id(ctx, v545);
-                                                        let v547 = &C::any(ctx, v251);
-                                                        let v548 = C::lookup_id(ctx, v547);
-                                                        let v549 = &C::mul(ctx, v546, v548);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 693.
-                                                        return Some(v549.clone());
+                                            &SimpleAst::Neg([v636]) => {
+                                                if v326 == v344 {
+                                                    if v327 == v636 {
+                                                        let v637 = C::get_width(ctx, v344);
+                                                        let v638 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v637);
+                                                        let v639 = C::lookup_id(ctx, v638);
+                                                        let v640 = &C::any(ctx, v344);
+                                                        let v641 = C::lookup_id(ctx, v640);
+                                                        let v642 = &C::mul(ctx, v639, v641);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 827.
+                                                        return Some(v642.clone());
                                                     }
                                                 }
                                             }
@@ -1150,19 +1171,19 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Or([v280, v281]) => {
-                                    if let Some(v269) = v268 {
-                                        if let &SimpleAst::And([v270, v271]) = v269 {
-                                            if v271 == v281 {
-                                                let v272 = &C::lookup_value(ctx, v270);
-                                                if let Some(v273) = v272 {
-                                                    if let &SimpleAst::Neg([v274]) = v273 {
-                                                        if v274 == v280 {
-                                                            let v467 = C::rule___merge_inverse_bitwise_terms_22_precondition(ctx, v232);
-                                                            if let Some(v468) = v467 {
-                                                                let v284 = &C::any(ctx, v280);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 648.
-                                                                return Some(v284.clone());
+                                &SimpleAst::Or([v373, v374]) => {
+                                    if let Some(v362) = v361 {
+                                        if let &SimpleAst::And([v363, v364]) = v362 {
+                                            if v364 == v374 {
+                                                let v365 = &C::lookup_value(ctx, v363);
+                                                if let Some(v366) = v365 {
+                                                    if let &SimpleAst::Neg([v367]) = v366 {
+                                                        if v367 == v373 {
+                                                            let v560 = C::rule___merge_inverse_bitwise_terms_22_precondition(ctx, v326);
+                                                            if let Some(v561) = v560 {
+                                                                let v377 = &C::any(ctx, v373);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 782.
+                                                                return Some(v377.clone());
                                                             }
                                                         }
                                                     }
@@ -1171,32 +1192,32 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Xor([v315, v316]) => {
-                                    if let Some(v269) = v268 {
-                                        match v269 {
-                                            &SimpleAst::Mul([v469, v470]) => {
-                                                let v471 = &C::lookup_value(ctx, v470);
-                                                if let Some(v472) = v471 {
-                                                    if let &SimpleAst::And([v473, v474]) = v472 {
-                                                        if v316 == v474 {
-                                                            let v475 = &C::lookup_value(ctx, v473);
-                                                            if let Some(v476) = v475 {
-                                                                if let &SimpleAst::Neg([v477]) = v476 {
-                                                                    if v315 == v477 {
-                                                                        let v478 = C::rule___merge_inverse_bitwise_terms_23_precondition(ctx, v232, v469);
-                                                                        if let Some(v479) = v478 {
-                                                                            let v319 = &C::any(ctx, v315);
-                                                                            let v320 = C::lookup_id(ctx, v319);
-                                                                            let v480 = C::get_width(ctx, v315);
-                                                                            let v481 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v480);
-                                                                            let v482 = C::lookup_id(ctx, v481);
-                                                                            let v483 = &C::any(ctx, v316);
-                                                                            let v484 = C::lookup_id(ctx, v483);
-                                                                            let v485 = &C::mul(ctx, v482, v484);
-                                                                            let v486 = C::lookup_id(ctx, v485);
-                                                                            let v487 = &C::add(ctx, v320, v486);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 655.
-                                                                            return Some(v487.clone());
+                                &SimpleAst::Xor([v408, v409]) => {
+                                    if let Some(v362) = v361 {
+                                        match v362 {
+                                            &SimpleAst::Mul([v562, v563]) => {
+                                                let v564 = &C::lookup_value(ctx, v563);
+                                                if let Some(v565) = v564 {
+                                                    if let &SimpleAst::And([v566, v567]) = v565 {
+                                                        if v409 == v567 {
+                                                            let v568 = &C::lookup_value(ctx, v566);
+                                                            if let Some(v569) = v568 {
+                                                                if let &SimpleAst::Neg([v570]) = v569 {
+                                                                    if v408 == v570 {
+                                                                        let v571 = C::rule___merge_inverse_bitwise_terms_23_precondition(ctx, v326, v562);
+                                                                        if let Some(v572) = v571 {
+                                                                            let v412 = &C::any(ctx, v408);
+                                                                            let v413 = C::lookup_id(ctx, v412);
+                                                                            let v573 = C::get_width(ctx, v408);
+                                                                            let v574 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v573);
+                                                                            let v575 = C::lookup_id(ctx, v574);
+                                                                            let v576 = &C::any(ctx, v409);
+                                                                            let v577 = C::lookup_id(ctx, v576);
+                                                                            let v578 = &C::mul(ctx, v575, v577);
+                                                                            let v579 = C::lookup_id(ctx, v578);
+                                                                            let v580 = &C::add(ctx, v413, v579);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 789.
+                                                                            return Some(v580.clone());
                                                                         }
                                                                     }
                                                                 }
@@ -1205,31 +1226,31 @@ pub fn constructor_lower<C: Context>(
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Or([v488, v489]) => {
-                                                if v316 == v489 {
-                                                    let v490 = &C::lookup_value(ctx, v488);
-                                                    if let Some(v491) = v490 {
-                                                        if let &SimpleAst::Neg([v492]) = v491 {
-                                                            if v315 == v492 {
-                                                                let v493 = C::rule___merge_inverse_bitwise_terms_24_precondition(ctx, v232);
-                                                                if let Some(v494) = v493 {
-                                                                    let v465 = C::get_width(ctx, v315);
-                                                                    let v496 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFE, v465);
-                                                                    let v497 = C::lookup_id(ctx, v496);
-                                                                    let v498 = C::get_width(ctx, v315);
-                                                                    let v499 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v498);
-                                                                    let v500 = C::lookup_id(ctx, v499);
-                                                                    let v501 = &C::any(ctx, v315);
-                                                                    let v502 = C::lookup_id(ctx, v501);
-                                                                    let v503 = &C::mul(ctx, v500, v502);
-                                                                    let v504 = C::lookup_id(ctx, v503);
-                                                                    let v505 = &C::add(ctx, v497, v504);
-                                                                    let v506 = C::lookup_id(ctx, v505);
-                                                                    let v507 = &C::any(ctx, v316);
-                                                                    let v508 = C::lookup_id(ctx, v507);
-                                                                    let v509 = &C::add(ctx, v506, v508);
-                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 662.
-                                                                    return Some(v509.clone());
+                                            &SimpleAst::Or([v581, v582]) => {
+                                                if v409 == v582 {
+                                                    let v583 = &C::lookup_value(ctx, v581);
+                                                    if let Some(v584) = v583 {
+                                                        if let &SimpleAst::Neg([v585]) = v584 {
+                                                            if v408 == v585 {
+                                                                let v586 = C::rule___merge_inverse_bitwise_terms_24_precondition(ctx, v326);
+                                                                if let Some(v587) = v586 {
+                                                                    let v558 = C::get_width(ctx, v408);
+                                                                    let v589 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFE, v558);
+                                                                    let v590 = C::lookup_id(ctx, v589);
+                                                                    let v591 = C::get_width(ctx, v408);
+                                                                    let v592 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v591);
+                                                                    let v593 = C::lookup_id(ctx, v592);
+                                                                    let v594 = &C::any(ctx, v408);
+                                                                    let v595 = C::lookup_id(ctx, v594);
+                                                                    let v596 = &C::mul(ctx, v593, v595);
+                                                                    let v597 = C::lookup_id(ctx, v596);
+                                                                    let v598 = &C::add(ctx, v590, v597);
+                                                                    let v599 = C::lookup_id(ctx, v598);
+                                                                    let v600 = &C::any(ctx, v409);
+                                                                    let v601 = C::lookup_id(ctx, v600);
+                                                                    let v602 = &C::add(ctx, v599, v601);
+                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 796.
+                                                                    return Some(v602.clone());
                                                                 }
                                                             }
                                                         }
@@ -1243,41 +1264,41 @@ pub fn constructor_lower<C: Context>(
                                 _ => {}
                             }
                         }
-                        if v228 == v233 {
-                            let v369 = C::rule_add_cancellation_precondition(ctx, v232);
-                            if let Some(v370) = v369 {
-                                let v243 = C::get_width(ctx, v228);
-                                let v371 = &C::constant(ctx, 0x0, v243);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 561.
-                                return Some(v371.clone());
+                        if v35 == v327 {
+                            let v462 = C::rule_add_cancellation_precondition(ctx, v326);
+                            if let Some(v463) = v462 {
+                                let v336 = C::get_width(ctx, v35);
+                                let v464 = &C::constant(ctx, 0x0, v336);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 695.
+                                return Some(v464.clone());
                             }
                         }
                     }
-                    &SimpleAst::And([v282, v283]) => {
-                        let v946 = &C::lookup_value(ctx, v283);
-                        if let Some(v947) = v946 {
-                            if let &SimpleAst::Neg([v948]) = v947 {
-                                if v228 == v948 {
-                                    let v949 = &C::any(ctx, v228);
-                                    let v950 = C::lookup_id(ctx, v949);
-                                    let v951 = &C::any(ctx, v282);
-                                    let v952 = C::lookup_id(ctx, v951);
-                                    let v953 = &C::or(ctx, v950, v952);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 971.
-                                    return Some(v953.clone());
+                    &SimpleAst::And([v375, v376]) => {
+                        let v1039 = &C::lookup_value(ctx, v376);
+                        if let Some(v1040) = v1039 {
+                            if let &SimpleAst::Neg([v1041]) = v1040 {
+                                if v35 == v1041 {
+                                    let v1042 = &C::any(ctx, v35);
+                                    let v1043 = C::lookup_id(ctx, v1042);
+                                    let v1044 = &C::any(ctx, v375);
+                                    let v1045 = C::lookup_id(ctx, v1044);
+                                    let v1046 = &C::or(ctx, v1043, v1045);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1105.
+                                    return Some(v1046.clone());
                                 }
                             }
                         }
-                        if let Some(v250) = v249 {
-                            if let &SimpleAst::And([v445, v446]) = v250 {
-                                if v283 == v446 {
-                                    let v447 = &C::lookup_value(ctx, v282);
-                                    if let Some(v448) = v447 {
-                                        if let &SimpleAst::Neg([v449]) = v448 {
-                                            if v445 == v449 {
-                                                let v450 = &C::any(ctx, v446);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 630.
-                                                return Some(v450.clone());
+                        if let Some(v343) = v342 {
+                            if let &SimpleAst::And([v538, v539]) = v343 {
+                                if v376 == v539 {
+                                    let v540 = &C::lookup_value(ctx, v375);
+                                    if let Some(v541) = v540 {
+                                        if let &SimpleAst::Neg([v542]) = v541 {
+                                            if v538 == v542 {
+                                                let v543 = &C::any(ctx, v539);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 764.
+                                                return Some(v543.clone());
                                             }
                                         }
                                     }
@@ -1285,36 +1306,36 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Or([v451, v452]) => {
-                        if let Some(v250) = v249 {
-                            match v250 {
-                                &SimpleAst::And([v445, v446]) => {
-                                    if v445 == v451 {
-                                        if v446 == v452 {
-                                            let v933 = &C::any(ctx, v445);
-                                            let v934 = C::lookup_id(ctx, v933);
-                                            let v935 = &C::any(ctx, v446);
-                                            let v936 = C::lookup_id(ctx, v935);
-                                            let v1010 = &C::add(ctx, v934, v936);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1030.
-                                            return Some(v1010.clone());
-                                        }
-                                    }
-                                }
-                                &SimpleAst::Or([v280, v281]) => {
-                                    if v281 == v452 {
-                                        let v453 = &C::lookup_value(ctx, v451);
-                                        if let Some(v454) = v453 {
-                                            if let &SimpleAst::Neg([v455]) = v454 {
-                                                if v280 == v455 {
-                                                    let v456 = C::get_width(ctx, v280);
-                                                    let v457 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v456);
-                                                    let v458 = C::lookup_id(ctx, v457);
-                                                    let v459 = &C::any(ctx, v281);
-                                                    let v460 = C::lookup_id(ctx, v459);
-                                                    let v461 = &C::add(ctx, v458, v460);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 636.
-                                                    return Some(v461.clone());
+                    &SimpleAst::Or([v544, v545]) => {
+                        if let Some(v343) = v342 {
+                            match v343 {
+                                &SimpleAst::And([v538, v539]) => {
+                                    if v538 == v544 {
+                                        if v539 == v545 {
+                                            let v1026 = &C::any(ctx, v538);
+                                            let v1027 = C::lookup_id(ctx, v1026);
+                                            let v1028 = &C::any(ctx, v539);
+                                            let v1029 = C::lookup_id(ctx, v1028);
+                                            let v1103 = &C::add(ctx, v1027, v1029);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1164.
+                                            return Some(v1103.clone());
+                                        }
+                                    }
+                                }
+                                &SimpleAst::Or([v373, v374]) => {
+                                    if v374 == v545 {
+                                        let v546 = &C::lookup_value(ctx, v544);
+                                        if let Some(v547) = v546 {
+                                            if let &SimpleAst::Neg([v548]) = v547 {
+                                                if v373 == v548 {
+                                                    let v549 = C::get_width(ctx, v373);
+                                                    let v550 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v549);
+                                                    let v551 = C::lookup_id(ctx, v550);
+                                                    let v552 = &C::any(ctx, v374);
+                                                    let v553 = C::lookup_id(ctx, v552);
+                                                    let v554 = &C::add(ctx, v551, v553);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 770.
+                                                    return Some(v554.clone());
                                                 }
                                             }
                                         }
@@ -1324,32 +1345,32 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Xor([v308, v309]) => {
-                        if let Some(v250) = v249 {
-                            match v250 {
-                                &SimpleAst::And([v445, v446]) => {
-                                    if v308 == v445 {
-                                        if v309 == v446 {
-                                            let v933 = &C::any(ctx, v445);
-                                            let v934 = C::lookup_id(ctx, v933);
-                                            let v935 = &C::any(ctx, v446);
-                                            let v936 = C::lookup_id(ctx, v935);
-                                            let v937 = &C::or(ctx, v934, v936);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 959.
-                                            return Some(v937.clone());
-                                        }
-                                    }
-                                }
-                                &SimpleAst::Xor([v315, v316]) => {
-                                    if v309 == v316 {
-                                        let v462 = &C::lookup_value(ctx, v308);
-                                        if let Some(v463) = v462 {
-                                            if let &SimpleAst::Neg([v464]) = v463 {
-                                                if v315 == v464 {
-                                                    let v465 = C::get_width(ctx, v315);
-                                                    let v466 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v465);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 642.
-                                                    return Some(v466.clone());
+                    &SimpleAst::Xor([v401, v402]) => {
+                        if let Some(v343) = v342 {
+                            match v343 {
+                                &SimpleAst::And([v538, v539]) => {
+                                    if v401 == v538 {
+                                        if v402 == v539 {
+                                            let v1026 = &C::any(ctx, v538);
+                                            let v1027 = C::lookup_id(ctx, v1026);
+                                            let v1028 = &C::any(ctx, v539);
+                                            let v1029 = C::lookup_id(ctx, v1028);
+                                            let v1030 = &C::or(ctx, v1027, v1029);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1093.
+                                            return Some(v1030.clone());
+                                        }
+                                    }
+                                }
+                                &SimpleAst::Xor([v408, v409]) => {
+                                    if v402 == v409 {
+                                        let v555 = &C::lookup_value(ctx, v401);
+                                        if let Some(v556) = v555 {
+                                            if let &SimpleAst::Neg([v557]) = v556 {
+                                                if v408 == v557 {
+                                                    let v558 = C::get_width(ctx, v408);
+                                                    let v559 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v558);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 776.
+                                                    return Some(v559.clone());
                                                 }
                                             }
                                         }
@@ -1359,87 +1380,87 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Neg([v239]) => {
-                        if let Some(v250) = v249 {
-                            if let &SimpleAst::And([v445, v446]) = v250 {
-                                let v993 = &C::lookup_value(ctx, v239);
-                                if let Some(v994) = v993 {
-                                    if let &SimpleAst::Or([v995, v996]) = v994 {
-                                        if v445 == v995 {
-                                            if v446 == v996 {
-                                                let v933 = &C::any(ctx, v445);
-                                                let v934 = C::lookup_id(ctx, v933);
-                                                let v935 = &C::any(ctx, v446);
-                                                let v936 = C::lookup_id(ctx, v935);
-                                                let v997 = &C::xor(ctx, v934, v936);
-                                                let v998 = C::lookup_id(ctx, v997);
-                                                let v999 = &C::neg(ctx, v998);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1010.
-                                                return Some(v999.clone());
+                    &SimpleAst::Neg([v333]) => {
+                        if let Some(v343) = v342 {
+                            if let &SimpleAst::And([v538, v539]) = v343 {
+                                let v1086 = &C::lookup_value(ctx, v333);
+                                if let Some(v1087) = v1086 {
+                                    if let &SimpleAst::Or([v1088, v1089]) = v1087 {
+                                        if v538 == v1088 {
+                                            if v539 == v1089 {
+                                                let v1026 = &C::any(ctx, v538);
+                                                let v1027 = C::lookup_id(ctx, v1026);
+                                                let v1028 = &C::any(ctx, v539);
+                                                let v1029 = C::lookup_id(ctx, v1028);
+                                                let v1090 = &C::xor(ctx, v1027, v1029);
+                                                let v1091 = C::lookup_id(ctx, v1090);
+                                                let v1092 = &C::neg(ctx, v1091);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1144.
+                                                return Some(v1092.clone());
                                             }
                                         }
                                     }
                                 }
                             }
                         }
-                        if v228 == v239 {
-                            let v243 = C::get_width(ctx, v228);
-                            let v244 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v243);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 687.
-                            return Some(v244.clone());
+                        if v35 == v333 {
+                            let v336 = C::get_width(ctx, v35);
+                            let v337 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v336);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 821.
+                            return Some(v337.clone());
                         }
                     }
                     _ => {}
                 }
             }
-            let v366 = C::rule_add_zero_precondition(ctx, v228);
-            if let Some(v367) = v366 {
-                let v368 = &C::any(ctx, v229);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 554.
-                return Some(v368.clone());
+            let v459 = C::rule_add_zero_precondition(ctx, v35);
+            if let Some(v460) = v459 {
+                let v461 = &C::any(ctx, v36);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 688.
+                return Some(v461.clone());
             }
-            if v228 == v229 {
-                let v243 = C::get_width(ctx, v228);
-                let v361 = &C::constant(ctx, 0x2, v243);
-                let v362 = C::lookup_id(ctx, v361);
-                let v363 = &C::any(ctx, v228);
-                let v364 = C::lookup_id(ctx, v363);
-                let v365 = &C::mul(ctx, v362, v364);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 548.
-                return Some(v365.clone());
+            if v35 == v36 {
+                let v336 = C::get_width(ctx, v35);
+                let v454 = &C::constant(ctx, 0x2, v336);
+                let v455 = C::lookup_id(ctx, v454);
+                let v456 = &C::any(ctx, v35);
+                let v457 = C::lookup_id(ctx, v456);
+                let v458 = &C::mul(ctx, v455, v457);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 682.
+                return Some(v458.clone());
             }
-            if let Some(v231) = v230 {
-                match v231 {
-                    &SimpleAst::Mul([v232, v233]) => {
-                        if let Some(v250) = v249 {
-                            match v250 {
-                                &SimpleAst::Mul([v251, v252]) => {
-                                    let v257 = &C::lookup_value(ctx, v252);
-                                    if let Some(v258) = v257 {
-                                        match v258 {
-                                            &SimpleAst::And([v289, v290]) => {
-                                                let v268 = &C::lookup_value(ctx, v233);
-                                                if let Some(v269) = v268 {
-                                                    if let &SimpleAst::Xor([v294, v295]) = v269 {
-                                                        if v289 == v294 {
-                                                            let v291 = &C::lookup_value(ctx, v290);
-                                                            if let Some(v292) = v291 {
-                                                                if let &SimpleAst::Neg([v293]) = v292 {
-                                                                    if v293 == v295 {
-                                                                        let v296 = C::rule_qsynth_1_precondition(ctx, v251, v232);
-                                                                        if let Some(v297) = v296 {
-                                                                            let v298 = &C::any(ctx, v289);
-                                                                            let v299 = C::lookup_id(ctx, v298);
-                                                                            let v300 = C::get_width(ctx, v251);
-                                                                            let v301 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v300);
-                                                                            let v302 = C::lookup_id(ctx, v301);
-                                                                            let v303 = &C::any(ctx, v293);
-                                                                            let v304 = C::lookup_id(ctx, v303);
-                                                                            let v305 = &C::mul(ctx, v302, v304);
-                                                                            let v306 = C::lookup_id(ctx, v305);
-                                                                            let v307 = &C::add(ctx, v299, v306);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 449.
-                                                                            return Some(v307.clone());
+            if let Some(v325) = v324 {
+                match v325 {
+                    &SimpleAst::Mul([v326, v327]) => {
+                        if let Some(v343) = v342 {
+                            match v343 {
+                                &SimpleAst::Mul([v344, v345]) => {
+                                    let v350 = &C::lookup_value(ctx, v345);
+                                    if let Some(v351) = v350 {
+                                        match v351 {
+                                            &SimpleAst::And([v382, v383]) => {
+                                                let v361 = &C::lookup_value(ctx, v327);
+                                                if let Some(v362) = v361 {
+                                                    if let &SimpleAst::Xor([v387, v388]) = v362 {
+                                                        if v382 == v387 {
+                                                            let v384 = &C::lookup_value(ctx, v383);
+                                                            if let Some(v385) = v384 {
+                                                                if let &SimpleAst::Neg([v386]) = v385 {
+                                                                    if v386 == v388 {
+                                                                        let v389 = C::rule_qsynth_1_precondition(ctx, v344, v326);
+                                                                        if let Some(v390) = v389 {
+                                                                            let v391 = &C::any(ctx, v382);
+                                                                            let v392 = C::lookup_id(ctx, v391);
+                                                                            let v393 = C::get_width(ctx, v344);
+                                                                            let v394 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v393);
+                                                                            let v395 = C::lookup_id(ctx, v394);
+                                                                            let v396 = &C::any(ctx, v386);
+                                                                            let v397 = C::lookup_id(ctx, v396);
+                                                                            let v398 = &C::mul(ctx, v395, v397);
+                                                                            let v399 = C::lookup_id(ctx, v398);
+                                                                            let v400 = &C::add(ctx, v392, v399);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 583.
+                                                                            return Some(v400.clone());
                                                                         }
                                                                     }
                                                                 }
@@ -1448,35 +1469,35 @@ pub fn constructor_lower<C: Context>(
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Or([v259, v260]) => {
-                                                let v253 = &C::lookup_value(ctx, v251);
-                                                if let Some(v254) = v253 {
-                                                    if let &SimpleAst::And([v255, v256]) = v254 {
-                                                        if v255 == v259 {
-                                                            if v256 == v260 {
-                                                                let v261 = &C::lookup_value(ctx, v232);
-                                                                if let Some(v262) = v261 {
-                                                                    if let &SimpleAst::And([v263, v264]) = v262 {
-                                                                        if v255 == v263 {
-                                                                            let v265 = &C::lookup_value(ctx, v264);
-                                                                            if let Some(v266) = v265 {
-                                                                                if let &SimpleAst::Neg([v267]) = v266 {
-                                                                                    if v256 == v267 {
-                                                                                        let v268 = &C::lookup_value(ctx, v233);
-                                                                                        if let Some(v269) = v268 {
-                                                                                            if let &SimpleAst::And([v270, v271]) = v269 {
-                                                                                                if v256 == v271 {
-                                                                                                    let v272 = &C::lookup_value(ctx, v270);
-                                                                                                    if let Some(v273) = v272 {
-                                                                                                        if let &SimpleAst::Neg([v274]) = v273 {
-                                                                                                            if v255 == v274 {
-                                                                                                                let v275 = &C::any(ctx, v255);
-                                                                                                                let v276 = C::lookup_id(ctx, v275);
-                                                                                                                let v277 = &C::any(ctx, v256);
-                                                                                                                let v278 = C::lookup_id(ctx, v277);
-                                                                                                                let v279 = &C::mul(ctx, v276, v278);
-                                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 437.
-                                                                                                                return Some(v279.clone());
+                                            &SimpleAst::Or([v352, v353]) => {
+                                                let v346 = &C::lookup_value(ctx, v344);
+                                                if let Some(v347) = v346 {
+                                                    if let &SimpleAst::And([v348, v349]) = v347 {
+                                                        if v348 == v352 {
+                                                            if v349 == v353 {
+                                                                let v354 = &C::lookup_value(ctx, v326);
+                                                                if let Some(v355) = v354 {
+                                                                    if let &SimpleAst::And([v356, v357]) = v355 {
+                                                                        if v348 == v356 {
+                                                                            let v358 = &C::lookup_value(ctx, v357);
+                                                                            if let Some(v359) = v358 {
+                                                                                if let &SimpleAst::Neg([v360]) = v359 {
+                                                                                    if v349 == v360 {
+                                                                                        let v361 = &C::lookup_value(ctx, v327);
+                                                                                        if let Some(v362) = v361 {
+                                                                                            if let &SimpleAst::And([v363, v364]) = v362 {
+                                                                                                if v349 == v364 {
+                                                                                                    let v365 = &C::lookup_value(ctx, v363);
+                                                                                                    if let Some(v366) = v365 {
+                                                                                                        if let &SimpleAst::Neg([v367]) = v366 {
+                                                                                                            if v348 == v367 {
+                                                                                                                let v368 = &C::any(ctx, v348);
+                                                                                                                let v369 = C::lookup_id(ctx, v368);
+                                                                                                                let v370 = &C::any(ctx, v349);
+                                                                                                                let v371 = C::lookup_id(ctx, v370);
+                                                                                                                let v372 = &C::mul(ctx, v369, v371);
+                                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 571.
+                                                                                                                return Some(v372.clone());
                                                                                                             }
                                                                                                         }
                                                                                                     }
@@ -1498,21 +1519,21 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Xor([v315, v316]) => {
-                                    let v268 = &C::lookup_value(ctx, v233);
-                                    if let Some(v269) = v268 {
-                                        if let &SimpleAst::And([v270, v271]) = v269 {
-                                            if v270 == v315 {
-                                                if v271 == v316 {
-                                                    let v317 = C::rule_qsynth_2_commutative_precondition(ctx, v232);
-                                                    if let Some(v318) = v317 {
-                                                        let v319 = &C::any(ctx, v315);
-                                                        let v320 = C::lookup_id(ctx, v319);
-                                                        let v321 = &C::any(ctx, v316);
-                                                        let v322 = C::lookup_id(ctx, v321);
-                                                        let v323 = &C::add(ctx, v320, v322);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 463.
-                                                        return Some(v323.clone());
+                                &SimpleAst::Xor([v408, v409]) => {
+                                    let v361 = &C::lookup_value(ctx, v327);
+                                    if let Some(v362) = v361 {
+                                        if let &SimpleAst::And([v363, v364]) = v362 {
+                                            if v363 == v408 {
+                                                if v364 == v409 {
+                                                    let v410 = C::rule_qsynth_2_commutative_precondition(ctx, v326);
+                                                    if let Some(v411) = v410 {
+                                                        let v412 = &C::any(ctx, v408);
+                                                        let v413 = C::lookup_id(ctx, v412);
+                                                        let v414 = &C::any(ctx, v409);
+                                                        let v415 = C::lookup_id(ctx, v414);
+                                                        let v416 = &C::add(ctx, v413, v415);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 597.
+                                                        return Some(v416.clone());
                                                     }
                                                 }
                                             }
@@ -1522,51 +1543,51 @@ pub fn constructor_lower<C: Context>(
                                 _ => {}
                             }
                         }
-                        if v228 == v232 {
-                            let v234 = C::rule_arith_to_negation_precondition(ctx, v228);
-                            if let Some(v235) = v234 {
-                                let v236 = &C::any(ctx, v233);
-                                let v237 = C::lookup_id(ctx, v236);
-                                let v238 = &C::neg(ctx, v237);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 423.
-                                return Some(v238.clone());
+                        if v35 == v326 {
+                            let v328 = C::rule_arith_to_negation_precondition(ctx, v35);
+                            if let Some(v329) = v328 {
+                                let v330 = &C::any(ctx, v327);
+                                let v331 = C::lookup_id(ctx, v330);
+                                let v332 = &C::neg(ctx, v331);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 557.
+                                return Some(v332.clone());
                             }
                         }
                     }
-                    &SimpleAst::And([v282, v283]) => {
-                        if let Some(v250) = v249 {
-                            if let &SimpleAst::Or([v280, v281]) = v250 {
-                                if v280 == v282 {
-                                    if v281 == v283 {
-                                        let v284 = &C::any(ctx, v280);
-                                        let v285 = C::lookup_id(ctx, v284);
-                                        let v286 = &C::any(ctx, v281);
-                                        let v287 = C::lookup_id(ctx, v286);
-                                        let v288 = &C::add(ctx, v285, v287);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 443.
-                                        return Some(v288.clone());
+                    &SimpleAst::And([v375, v376]) => {
+                        if let Some(v343) = v342 {
+                            if let &SimpleAst::Or([v373, v374]) = v343 {
+                                if v373 == v375 {
+                                    if v374 == v376 {
+                                        let v377 = &C::any(ctx, v373);
+                                        let v378 = C::lookup_id(ctx, v377);
+                                        let v379 = &C::any(ctx, v374);
+                                        let v380 = C::lookup_id(ctx, v379);
+                                        let v381 = &C::add(ctx, v378, v380);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 577.
+                                        return Some(v381.clone());
                                     }
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Xor([v308, v309]) => {
-                        if let Some(v250) = v249 {
-                            if let &SimpleAst::Mul([v251, v252]) = v250 {
-                                let v257 = &C::lookup_value(ctx, v252);
-                                if let Some(v258) = v257 {
-                                    if let &SimpleAst::And([v289, v290]) = v258 {
-                                        if v289 == v308 {
-                                            if v290 == v309 {
-                                                let v310 = C::rule_qsynth_2_precondition(ctx, v251);
-                                                if let Some(v311) = v310 {
-                                                    let v298 = &C::any(ctx, v289);
-                                                    let v299 = C::lookup_id(ctx, v298);
-                                                    let v312 = &C::any(ctx, v290);
-                                                    let v313 = C::lookup_id(ctx, v312);
-                                                    let v314 = &C::add(ctx, v299, v313);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 456.
-                                                    return Some(v314.clone());
+                    &SimpleAst::Xor([v401, v402]) => {
+                        if let Some(v343) = v342 {
+                            if let &SimpleAst::Mul([v344, v345]) = v343 {
+                                let v350 = &C::lookup_value(ctx, v345);
+                                if let Some(v351) = v350 {
+                                    if let &SimpleAst::And([v382, v383]) = v351 {
+                                        if v382 == v401 {
+                                            if v383 == v402 {
+                                                let v403 = C::rule_qsynth_2_precondition(ctx, v344);
+                                                if let Some(v404) = v403 {
+                                                    let v391 = &C::any(ctx, v382);
+                                                    let v392 = C::lookup_id(ctx, v391);
+                                                    let v405 = &C::any(ctx, v383);
+                                                    let v406 = C::lookup_id(ctx, v405);
+                                                    let v407 = &C::add(ctx, v392, v406);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 590.
+                                                    return Some(v407.clone());
                                                 }
                                             }
                                         }
@@ -1575,100 +1596,118 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Neg([v239]) => {
-                        let v240 = C::rule_add_negate_to_invert_sign_precondition(ctx, v228);
-                        if let Some(v241) = v240 {
-                            let v243 = C::get_width(ctx, v228);
-                            let v244 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v243);
-                            let v245 = C::lookup_id(ctx, v244);
-                            let v246 = &C::any(ctx, v239);
-                            let v247 = C::lookup_id(ctx, v246);
-                            let v248 = &C::mul(ctx, v245, v247);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 430.
-                            return Some(v248.clone());
+                    &SimpleAst::Neg([v333]) => {
+                        let v334 = C::rule_add_negate_to_invert_sign_precondition(ctx, v35);
+                        if let Some(v335) = v334 {
+                            let v336 = C::get_width(ctx, v35);
+                            let v337 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v336);
+                            let v338 = C::lookup_id(ctx, v337);
+                            let v339 = &C::any(ctx, v333);
+                            let v340 = C::lookup_id(ctx, v339);
+                            let v341 = &C::mul(ctx, v338, v340);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 564.
+                            return Some(v341.clone());
                         }
                     }
                     _ => {}
                 }
             }
+            let v37 = C::rotate_source(ctx, v35, v36);
+            if let Some(v38) = v37 {
+                let v39 = C::rotate_amount(ctx, v35, v36);
+                if let Some(v40) = v39 {
+                    let v41 = C::get_width(ctx, v38);
+                    let v42 = &C::constant(ctx, v40, v41);
+                    let v43 = C::lookup_id(ctx, v42);
+                    let v44 = &C::rotl(ctx, v38, v43);
+                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 225.
+                    return Some(v44.clone());
+                }
+            }
+            let v55 = C::popcount_operand(ctx, v35, v36);
+            if let Some(v56) = v55 {
+                let v57 = &C::popcount(ctx, v56);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 241.
+                return Some(v57.clone());
+            }
         }
-        &SimpleAst::Mul([v372, v373]) => {
-            let v391 = &C::lookup_value(ctx, v373);
-            if let Some(v392) = v391 {
-                match v392 {
-                    &SimpleAst::Mul([v393, v394]) => {
-                        if v372 == v393 {
-                            let v395 = C::rule_minus_twice_precondition(ctx, v372);
-                            if let Some(v396) = v395 {
-                                let v397 = &C::any(ctx, v394);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 596.
-                                return Some(v397.clone());
+        &SimpleAst::Mul([v465, v466]) => {
+            let v484 = &C::lookup_value(ctx, v466);
+            if let Some(v485) = v484 {
+                match v485 {
+                    &SimpleAst::Mul([v486, v487]) => {
+                        if v465 == v486 {
+                            let v488 = C::rule_minus_twice_precondition(ctx, v465);
+                            if let Some(v489) = v488 {
+                                let v490 = &C::any(ctx, v487);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 730.
+                                return Some(v490.clone());
                             }
                         }
                     }
-                    &SimpleAst::Pow([v532, v533]) => {
-                        let v528 = &C::lookup_value(ctx, v372);
-                        if let Some(v529) = v528 {
-                            if let &SimpleAst::Pow([v530, v531]) = v529 {
-                                if v530 == v532 {
-                                    let v534 = &C::any(ctx, v530);
-                                    let v535 = C::lookup_id(ctx, v534);
-                                    let v536 = &C::any(ctx, v531);
-                                    let v537 = C::lookup_id(ctx, v536);
-                                    let v538 = &C::any(ctx, v533);
-                                    let v539 = C::lookup_id(ctx, v538);
-                                    let v540 = &C::add(ctx, v537, v539);
-                                    let v541 = C::lookup_id(ctx, v540);
-                                    let v542 = &C::pow(ctx, v535, v541);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 681.
-                                    return Some(v542.clone());
+                    &SimpleAst::Pow([v625, v626]) => {
+                        let v621 = &C::lookup_value(ctx, v465);
+                        if let Some(v622) = v621 {
+                            if let &SimpleAst::Pow([v623, v624]) = v622 {
+                                if v623 == v625 {
+                                    let v627 = &C::any(ctx, v623);
+                                    let v628 = C::lookup_id(ctx, v627);
+                                    let v629 = &C::any(ctx, v624);
+                                    let v630 = C::lookup_id(ctx, v629);
+                                    let v631 = &C::any(ctx, v626);
+                                    let v632 = C::lookup_id(ctx, v631);
+                                    let v633 = &C::add(ctx, v630, v632);
+                                    let v634 = C::lookup_id(ctx, v633);
+                                    let v635 = &C::pow(ctx, v628, v634);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 815.
+                                    return Some(v635.clone());
                                 }
                             }
                         }
                     }
-                    &SimpleAst::And([v572, v573]) => {
-                        let v574 = &C::lookup_value(ctx, v573);
-                        if let Some(v575) = v574 {
-                            if let &SimpleAst::Mul([v576, v577]) = v575 {
-                                if v572 == v577 {
-                                    let v578 = C::rule_xor_same_mult_by_minus_one_2_precondition(ctx, v372, v576);
-                                    if let Some(v579) = v578 {
-                                        let v580 = &C::any(ctx, v572);
-                                        let v581 = C::lookup_id(ctx, v580);
-                                        let v564 = C::get_width(ctx, v372);
-                                        let v565 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v564);
-                                        let v566 = C::lookup_id(ctx, v565);
-                                        let v582 = &C::any(ctx, v572);
-                                        let v583 = C::lookup_id(ctx, v582);
-                                        let v584 = &C::mul(ctx, v566, v583);
-                                        let v585 = C::lookup_id(ctx, v584);
-                                        let v586 = &C::xor(ctx, v581, v585);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 712.
-                                        return Some(v586.clone());
+                    &SimpleAst::And([v665, v666]) => {
+                        let v667 = &C::lookup_value(ctx, v666);
+                        if let Some(v668) = v667 {
+                            if let &SimpleAst::Mul([v669, v670]) = v668 {
+                                if v665 == v670 {
+                                    let v671 = C::rule_xor_same_mult_by_minus_one_2_precondition(ctx, v465, v669);
+                                    if let Some(v672) = v671 {
+                                        let v673 = &C::any(ctx, v665);
+                                        let v674 = C::lookup_id(ctx, v673);
+                                        let v657 = C::get_width(ctx, v465);
+                                        let v658 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v657);
+                                        let v659 = C::lookup_id(ctx, v658);
+                                        let v675 = &C::any(ctx, v665);
+                                        let v676 = C::lookup_id(ctx, v675);
+                                        let v677 = &C::mul(ctx, v659, v676);
+                                        let v678 = C::lookup_id(ctx, v677);
+                                        let v679 = &C::xor(ctx, v674, v678);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 846.
+                                        return Some(v679.clone());
                                     }
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Or([v554, v555]) => {
-                        let v556 = &C::lookup_value(ctx, v555);
-                        if let Some(v557) = v556 {
-                            if let &SimpleAst::Mul([v558, v559]) = v557 {
-                                if v554 == v559 {
-                                    let v560 = C::rule_xor_same_mult_by_minus_one_1_precondition(ctx, v372, v558);
-                                    if let Some(v561) = v560 {
-                                        let v562 = &C::any(ctx, v554);
-                                        let v563 = C::lookup_id(ctx, v562);
-                                        let v564 = C::get_width(ctx, v372);
-                                        let v565 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v564);
-                                        let v566 = C::lookup_id(ctx, v565);
-                                        let v567 = &C::any(ctx, v554);
-                                        let v568 = C::lookup_id(ctx, v567);
-                                        let v569 = &C::mul(ctx, v566, v568);
-                                        let v570 = C::lookup_id(ctx, v569);
-                                        let v571 = &C::xor(ctx, v563, v570);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 705.
-                                        return Some(v571.clone());
+                    &SimpleAst::Or([v647, v648]) => {
+                        let v649 = &C::lookup_value(ctx, v648);
+                        if let Some(v650) = v649 {
+                            if let &SimpleAst::Mul([v651, v652]) = v650 {
+                                if v647 == v652 {
+                                    let v653 = C::rule_xor_same_mult_by_minus_one_1_precondition(ctx, v465, v651);
+                                    if let Some(v654) = v653 {
+                                        let v655 = &C::any(ctx, v647);
+                                        let v656 = C::lookup_id(ctx, v655);
+                                        let v657 = C::get_width(ctx, v465);
+                                        let v658 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v657);
+                                        let v659 = C::lookup_id(ctx, v658);
+                                        let v660 = &C::any(ctx, v647);
+                                        let v661 = C::lookup_id(ctx, v660);
+                                        let v662 = &C::mul(ctx, v659, v661);
+                                        let v663 = C::lookup_id(ctx, v662);
+                                        let v664 = &C::xor(ctx, v656, v663);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 839.
+                                        return Some(v664.clone());
                                     }
                                 }
                             }
@@ -1677,102 +1716,102 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            let v378 = C::rule_mul_one_precondition(ctx, v372);
-            if let Some(v379) = v378 {
-                let v380 = &C::any(ctx, v373);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 575.
-                return Some(v380.clone());
+            let v471 = C::rule_mul_one_precondition(ctx, v465);
+            if let Some(v472) = v471 {
+                let v473 = &C::any(ctx, v466);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 709.
+                return Some(v473.clone());
             }
-            let v374 = C::rule_mul_zero_precondition(ctx, v372);
-            if let Some(v375) = v374 {
-                let v376 = C::get_width(ctx, v372);
-                let v377 = &C::constant(ctx, 0x0, v376);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 568.
-                return Some(v377.clone());
+            let v467 = C::rule_mul_zero_precondition(ctx, v465);
+            if let Some(v468) = v467 {
+                let v469 = C::get_width(ctx, v465);
+                let v470 = &C::constant(ctx, 0x0, v469);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 702.
+                return Some(v470.clone());
             }
         }
-        &SimpleAst::Pow([v381, v382]) => {
-            let v388 = C::rule_power_one_precondition(ctx, v382);
-            if let Some(v389) = v388 {
-                let v390 = &C::any(ctx, v381);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 589.
-                return Some(v390.clone());
+        &SimpleAst::Pow([v474, v475]) => {
+            let v481 = C::rule_power_one_precondition(ctx, v475);
+            if let Some(v482) = v481 {
+                let v483 = &C::any(ctx, v474);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 723.
+                return Some(v483.clone());
             }
-            let v383 = C::rule_power_zero_precondition(ctx, v382);
-            if let Some(v384) = v383 {
-                let v386 = C::get_width(ctx, v381);
-                let v387 = &C::constant(ctx, 0x1, v386);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 582.
-                return Some(v387.clone());
+            let v476 = C::rule_power_zero_precondition(ctx, v475);
+            if let Some(v477) = v476 {
+                let v479 = C::get_width(ctx, v474);
+                let v480 = &C::constant(ctx, 0x1, v479);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 716.
+                return Some(v480.clone());
             }
         }
-        &SimpleAst::And([v56, v57]) => {
-            let v61 = &C::lookup_value(ctx, v57);
-            if let Some(v62) = v61 {
-                match v62 {
-                    &SimpleAst::Or([v133, v134]) => {
-                        let v691 = &C::lookup_value(ctx, v134);
-                        if let Some(v692) = v691 {
-                            if let &SimpleAst::Xor([v1275, v1276]) = v692 {
-                                if v56 == v1275 {
-                                    let v359 = &C::any(ctx, v56);
-                                    let v844 = C::lookup_id(ctx, v359);
-                                    let v1277 = &C::any(ctx, v1276);
-                                    let v1278 = C::lookup_id(ctx, v1277);
-                                    let v1279 = &C::neg(ctx, v1278);
-                                    let v1280 = C::lookup_id(ctx, v1279);
-                                    let v1281 = &C::any(ctx, v133);
-                                    let v1282 = C::lookup_id(ctx, v1281);
-                                    let v1283 = &C::or(ctx, v1280, v1282);
-                                    let v1284 = C::lookup_id(ctx, v1283);
-                                    let v1285 = &C::and(ctx, v844, v1284);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1189.
-                                    return Some(v1285.clone());
+        &SimpleAst::And([v154, v155]) => {
+            let v159 = &C::lookup_value(ctx, v155);
+            if let Some(v160) = v159 {
+                match v160 {
+                    &SimpleAst::Or([v229, v230]) => {
+                        let v784 = &C::lookup_value(ctx, v230);
+                        if let Some(v785) = v784 {
+                            if let &SimpleAst::Xor([v1368, v1369]) = v785 {
+                                if v154 == v1368 {
+                                    let v452 = &C::any(ctx, v154);
+                                    let v937 = C::lookup_id(ctx, v452);
+                                    let v1370 = &C::any(ctx, v1369);
+                                    let v1371 = C::lookup_id(ctx, v1370);
+                                    let v1372 = &C::neg(ctx, v1371);
+                                    let v1373 = C::lookup_id(ctx, v1372);
+                                    let v1374 = &C::any(ctx, v229);
+                                    let v1375 = C::lookup_id(ctx, v1374);
+                                    let v1376 = &C::or(ctx, v1373, v1375);
+                                    let v1377 = C::lookup_id(ctx, v1376);
+                                    let v1378 = &C::and(ctx, v937, v1377);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1323.
+                                    return Some(v1378.clone());
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Xor([v150, v151]) => {
-                        let v58 = &C::lookup_value(ctx, v56);
-                        if let Some(v59) = v58 {
-                            match v59 {
-                                &SimpleAst::And([v1330, v1331]) => {
-                                    if v150 == v1331 {
-                                        let v1332 = &C::any(ctx, v1331);
-                                        let v1333 = C::lookup_id(ctx, v1332);
-                                        let v1334 = &C::any(ctx, v1330);
-                                        let v1335 = C::lookup_id(ctx, v1334);
-                                        let v1336 = &C::and(ctx, v1333, v1335);
-                                        let v1337 = C::lookup_id(ctx, v1336);
-                                        let v1338 = &C::any(ctx, v151);
-                                        let v1339 = C::lookup_id(ctx, v1338);
-                                        let v1340 = &C::neg(ctx, v1339);
-                                        let v1341 = C::lookup_id(ctx, v1340);
-                                        let v1342 = &C::and(ctx, v1337, v1341);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1219.
-                                        return Some(v1342.clone());
-                                    }
-                                }
-                                &SimpleAst::Xor([v1092, v1093]) => {
-                                    let v647 = &C::lookup_value(ctx, v151);
-                                    if let Some(v648) = v647 {
-                                        if let &SimpleAst::Xor([v1145, v1146]) = v648 {
-                                            if v1092 == v1145 {
-                                                let v1147 = &C::lookup_value(ctx, v1146);
-                                                if let Some(v1148) = v1147 {
-                                                    if let &SimpleAst::Neg([v1149]) = v1148 {
-                                                        if v1093 == v1149 {
-                                                            let v1150 = &C::any(ctx, v150);
-                                                            let v1151 = C::lookup_id(ctx, v1150);
-                                                            let v1152 = &C::any(ctx, v1092);
-                                                            let v1153 = C::lookup_id(ctx, v1152);
-                                                            let v1154 = &C::any(ctx, v1093);
-                                                            let v1155 = C::lookup_id(ctx, v1154);
-                                                            let v1156 = &C::xor(ctx, v1153, v1155);
-                                                            let v1157 = C::lookup_id(ctx, v1156);
-                                                            let v1158 = &C::and(ctx, v1151, v1157);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1151.
-                                                            return Some(v1158.clone());
+                    &SimpleAst::Xor([v246, v247]) => {
+                        let v156 = &C::lookup_value(ctx, v154);
+                        if let Some(v157) = v156 {
+                            match v157 {
+                                &SimpleAst::And([v1423, v1424]) => {
+                                    if v246 == v1424 {
+                                        let v1425 = &C::any(ctx, v1424);
+                                        let v1426 = C::lookup_id(ctx, v1425);
+                                        let v1427 = &C::any(ctx, v1423);
+                                        let v1428 = C::lookup_id(ctx, v1427);
+                                        let v1429 = &C::and(ctx, v1426, v1428);
+                                        let v1430 = C::lookup_id(ctx, v1429);
+                                        let v1431 = &C::any(ctx, v247);
+                                        let v1432 = C::lookup_id(ctx, v1431);
+                                        let v1433 = &C::neg(ctx, v1432);
+                                        let v1434 = C::lookup_id(ctx, v1433);
+                                        let v1435 = &C::and(ctx, v1430, v1434);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1353.
+                                        return Some(v1435.clone());
+                                    }
+                                }
+                                &SimpleAst::Xor([v1185, v1186]) => {
+                                    let v740 = &C::lookup_value(ctx, v247);
+                                    if let Some(v741) = v740 {
+                                        if let &SimpleAst::Xor([v1238, v1239]) = v741 {
+                                            if v1185 == v1238 {
+                                                let v1240 = &C::lookup_value(ctx, v1239);
+                                                if let Some(v1241) = v1240 {
+                                                    if let &SimpleAst::Neg([v1242]) = v1241 {
+                                                        if v1186 == v1242 {
+                                                            let v1243 = &C::any(ctx, v246);
+                                                            let v1244 = C::lookup_id(ctx, v1243);
+                                                            let v1245 = &C::any(ctx, v1185);
+                                                            let v1246 = C::lookup_id(ctx, v1245);
+                                                            let v1247 = &C::any(ctx, v1186);
+                                                            let v1248 = C::lookup_id(ctx, v1247);
+                                                            let v1249 = &C::xor(ctx, v1246, v1248);
+                                                            let v1250 = C::lookup_id(ctx, v1249);
+                                                            let v1251 = &C::and(ctx, v1244, v1250);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1285.
+                                                            return Some(v1251.clone());
                                                         }
                                                     }
                                                 }
@@ -1780,39 +1819,39 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Neg([v60]) => {
-                                    let v599 = &C::lookup_value(ctx, v60);
-                                    if let Some(v600) = v599 {
-                                        if let &SimpleAst::And([v980, v981]) = v600 {
-                                            if v150 == v981 {
-                                                let v647 = &C::lookup_value(ctx, v151);
-                                                if let Some(v648) = v647 {
-                                                    if let &SimpleAst::Xor([v1145, v1146]) = v648 {
-                                                        let v1147 = &C::lookup_value(ctx, v1146);
-                                                        if let Some(v1148) = v1147 {
-                                                            if let &SimpleAst::Neg([v1149]) = v1148 {
-                                                                if v980 == v1149 {
-                                                                    let v1343 = &C::any(ctx, v981);
-                                                                    let v1344 = C::lookup_id(ctx, v1343);
-                                                                    let v1345 = &C::any(ctx, v980);
-                                                                    let v1346 = C::lookup_id(ctx, v1345);
-                                                                    let v1347 = &C::and(ctx, v1344, v1346);
-                                                                    let v1348 = C::lookup_id(ctx, v1347);
-                                                                    let v1349 = &C::any(ctx, v981);
-                                                                    let v1350 = C::lookup_id(ctx, v1349);
-                                                                    let v1351 = &C::any(ctx, v980);
-                                                                    let v1352 = C::lookup_id(ctx, v1351);
-                                                                    let v1353 = &C::xor(ctx, v1350, v1352);
-                                                                    let v1354 = C::lookup_id(ctx, v1353);
-                                                                    let v1355 = &C::any(ctx, v1145);
-                                                                    let v1356 = C::lookup_id(ctx, v1355);
-                                                                    let v1357 = &C::xor(ctx, v1354, v1356);
-                                                                    let v1358 = C::lookup_id(ctx, v1357);
-                                                                    let v1359 = &C::or(ctx, v1348, v1358);
-                                                                    let v1360 = C::lookup_id(ctx, v1359);
-                                                                    let v1361 = &C::neg(ctx, v1360);
-                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1225.
-                                                                    return Some(v1361.clone());
+                                &SimpleAst::Neg([v158]) => {
+                                    let v692 = &C::lookup_value(ctx, v158);
+                                    if let Some(v693) = v692 {
+                                        if let &SimpleAst::And([v1073, v1074]) = v693 {
+                                            if v246 == v1074 {
+                                                let v740 = &C::lookup_value(ctx, v247);
+                                                if let Some(v741) = v740 {
+                                                    if let &SimpleAst::Xor([v1238, v1239]) = v741 {
+                                                        let v1240 = &C::lookup_value(ctx, v1239);
+                                                        if let Some(v1241) = v1240 {
+                                                            if let &SimpleAst::Neg([v1242]) = v1241 {
+                                                                if v1073 == v1242 {
+                                                                    let v1436 = &C::any(ctx, v1074);
+                                                                    let v1437 = C::lookup_id(ctx, v1436);
+                                                                    let v1438 = &C::any(ctx, v1073);
+                                                                    let v1439 = C::lookup_id(ctx, v1438);
+                                                                    let v1440 = &C::and(ctx, v1437, v1439);
+                                                                    let v1441 = C::lookup_id(ctx, v1440);
+                                                                    let v1442 = &C::any(ctx, v1074);
+                                                                    let v1443 = C::lookup_id(ctx, v1442);
+                                                                    let v1444 = &C::any(ctx, v1073);
+                                                                    let v1445 = C::lookup_id(ctx, v1444);
+                                                                    let v1446 = &C::xor(ctx, v1443, v1445);
+                                                                    let v1447 = C::lookup_id(ctx, v1446);
+                                                                    let v1448 = &C::any(ctx, v1238);
+                                                                    let v1449 = C::lookup_id(ctx, v1448);
+                                                                    let v1450 = &C::xor(ctx, v1447, v1449);
+                                                                    let v1451 = C::lookup_id(ctx, v1450);
+                                                                    let v1452 = &C::or(ctx, v1441, v1451);
+                                                                    let v1453 = C::lookup_id(ctx, v1452);
+                                                                    let v1454 = &C::neg(ctx, v1453);
+                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1359.
+                                                                    return Some(v1454.clone());
                                                                 }
                                                             }
                                                         }
@@ -1825,19 +1864,19 @@ pub fn constructor_lower<C: Context>(
                                 _ => {}
                             }
                         }
-                        let v647 = &C::lookup_value(ctx, v151);
-                        if let Some(v648) = v647 {
-                            if let &SimpleAst::Add([v1118, v1119]) = v648 {
-                                if v150 == v1119 {
-                                    let v1120 = &C::lookup_value(ctx, v1118);
-                                    if let Some(v1121) = v1120 {
-                                        if let &SimpleAst::Mul([v1122, v1123]) = v1121 {
-                                            let v1124 = C::rule_opaque_constant_1_precondition(ctx, v56, v1122, v1123);
-                                            if let Some(v1125) = v1124 {
-                                                let v354 = C::get_width(ctx, v56);
-                                                let v355 = &C::constant(ctx, 0x0, v354);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1119.
-                                                return Some(v355.clone());
+                        let v740 = &C::lookup_value(ctx, v247);
+                        if let Some(v741) = v740 {
+                            if let &SimpleAst::Add([v1211, v1212]) = v741 {
+                                if v246 == v1212 {
+                                    let v1213 = &C::lookup_value(ctx, v1211);
+                                    if let Some(v1214) = v1213 {
+                                        if let &SimpleAst::Mul([v1215, v1216]) = v1214 {
+                                            let v1217 = C::rule_opaque_constant_1_precondition(ctx, v154, v1215, v1216);
+                                            if let Some(v1218) = v1217 {
+                                                let v447 = C::get_width(ctx, v154);
+                                                let v448 = &C::constant(ctx, 0x0, v447);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1253.
+                                                return Some(v448.clone());
                                             }
                                         }
                                     }
@@ -1848,46 +1887,46 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            let v58 = &C::lookup_value(ctx, v56);
-            if let Some(v59) = v58 {
-                match v59 {
-                    &SimpleAst::Xor([v1092, v1093]) => {
-                        if v57 == v1092 {
-                            let v1094 = &C::any(ctx, v1092);
-                            let v1095 = C::lookup_id(ctx, v1094);
-                            let v1096 = &C::any(ctx, v1093);
-                            let v1097 = C::lookup_id(ctx, v1096);
-                            let v1098 = &C::neg(ctx, v1097);
-                            let v1099 = C::lookup_id(ctx, v1098);
-                            let v1100 = &C::and(ctx, v1095, v1099);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1099.
-                            return Some(v1100.clone());
+            let v156 = &C::lookup_value(ctx, v154);
+            if let Some(v157) = v156 {
+                match v157 {
+                    &SimpleAst::Xor([v1185, v1186]) => {
+                        if v155 == v1185 {
+                            let v1187 = &C::any(ctx, v1185);
+                            let v1188 = C::lookup_id(ctx, v1187);
+                            let v1189 = &C::any(ctx, v1186);
+                            let v1190 = C::lookup_id(ctx, v1189);
+                            let v1191 = &C::neg(ctx, v1190);
+                            let v1192 = C::lookup_id(ctx, v1191);
+                            let v1193 = &C::and(ctx, v1188, v1192);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1233.
+                            return Some(v1193.clone());
                         }
                     }
-                    &SimpleAst::Neg([v60]) => {
-                        if let Some(v62) = v61 {
-                            if let &SimpleAst::Neg([v63]) = v62 {
-                                let v550 = &C::lookup_value(ctx, v63);
-                                if let Some(v551) = v550 {
-                                    if let &SimpleAst::And([v675, v676]) = v551 {
-                                        let v599 = &C::lookup_value(ctx, v60);
-                                        if let Some(v600) = v599 {
-                                            if let &SimpleAst::And([v980, v981]) = v600 {
-                                                let v982 = &C::lookup_value(ctx, v980);
-                                                if let Some(v983) = v982 {
-                                                    if let &SimpleAst::Neg([v984]) = v983 {
-                                                        if v675 == v984 {
-                                                            let v985 = &C::lookup_value(ctx, v981);
-                                                            if let Some(v986) = v985 {
-                                                                if let &SimpleAst::Neg([v987]) = v986 {
-                                                                    if v676 == v987 {
-                                                                        let v988 = &C::any(ctx, v987);
-                                                                        let v989 = C::lookup_id(ctx, v988);
-                                                                        let v990 = &C::any(ctx, v984);
-                                                                        let v991 = C::lookup_id(ctx, v990);
-                                                                        let v992 = &C::xor(ctx, v989, v991);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1004.
-                                                                        return Some(v992.clone());
+                    &SimpleAst::Neg([v158]) => {
+                        if let Some(v160) = v159 {
+                            if let &SimpleAst::Neg([v161]) = v160 {
+                                let v643 = &C::lookup_value(ctx, v161);
+                                if let Some(v644) = v643 {
+                                    if let &SimpleAst::And([v768, v769]) = v644 {
+                                        let v692 = &C::lookup_value(ctx, v158);
+                                        if let Some(v693) = v692 {
+                                            if let &SimpleAst::And([v1073, v1074]) = v693 {
+                                                let v1075 = &C::lookup_value(ctx, v1073);
+                                                if let Some(v1076) = v1075 {
+                                                    if let &SimpleAst::Neg([v1077]) = v1076 {
+                                                        if v768 == v1077 {
+                                                            let v1078 = &C::lookup_value(ctx, v1074);
+                                                            if let Some(v1079) = v1078 {
+                                                                if let &SimpleAst::Neg([v1080]) = v1079 {
+                                                                    if v769 == v1080 {
+                                                                        let v1081 = &C::any(ctx, v1080);
+                                                                        let v1082 = C::lookup_id(ctx, v1081);
+                                                                        let v1083 = &C::any(ctx, v1077);
+                                                                        let v1084 = C::lookup_id(ctx, v1083);
+                                                                        let v1085 = &C::xor(ctx, v1082, v1084);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1138.
+                                                                        return Some(v1085.clone());
                                                                     }
                                                                 }
                                                             }
@@ -1904,20 +1943,20 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            if let Some(v62) = v61 {
-                match v62 {
-                    &SimpleAst::Add([v819, v820]) => {
-                        if v56 == v819 {
-                            let v821 = &C::lookup_value(ctx, v820);
-                            if let Some(v822) = v821 {
-                                if let &SimpleAst::And([v823, v824]) = v822 {
-                                    let v825 = &C::lookup_value(ctx, v823);
-                                    if let Some(v826) = v825 {
-                                        if let &SimpleAst::Neg([v827]) = v826 {
-                                            if v56 == v827 {
-                                                let v359 = &C::any(ctx, v56);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 894.
-                                                return Some(v359.clone());
+            if let Some(v160) = v159 {
+                match v160 {
+                    &SimpleAst::Add([v912, v913]) => {
+                        if v154 == v912 {
+                            let v914 = &C::lookup_value(ctx, v913);
+                            if let Some(v915) = v914 {
+                                if let &SimpleAst::And([v916, v917]) = v915 {
+                                    let v918 = &C::lookup_value(ctx, v916);
+                                    if let Some(v919) = v918 {
+                                        if let &SimpleAst::Neg([v920]) = v919 {
+                                            if v154 == v920 {
+                                                let v452 = &C::any(ctx, v154);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1028.
+                                                return Some(v452.clone());
                                             }
                                         }
                                     }
@@ -1925,44 +1964,44 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Mul([v603, v604]) => {
-                        let v605 = &C::lookup_value(ctx, v604);
-                        if let Some(v606) = v605 {
-                            match v606 {
-                                &SimpleAst::And([v710, v711]) => {
-                                    let v712 = &C::lookup_value(ctx, v711);
-                                    if let Some(v713) = v712 {
-                                        match v713 {
-                                            &SimpleAst::Mul([v714, v715]) => {
-                                                if v56 == v710 {
-                                                    if v56 == v715 {
-                                                        if v603 == v714 {
-                                                            let v716 = C::rule_conj_conj_identity_rule_precondition(ctx, v603);
-                                                            if let Some(v717) = v716 {
-                                                                let v359 = &C::any(ctx, v56);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 796.
-                                                                return Some(v359.clone());
+                    &SimpleAst::Mul([v696, v697]) => {
+                        let v698 = &C::lookup_value(ctx, v697);
+                        if let Some(v699) = v698 {
+                            match v699 {
+                                &SimpleAst::And([v803, v804]) => {
+                                    let v805 = &C::lookup_value(ctx, v804);
+                                    if let Some(v806) = v805 {
+                                        match v806 {
+                                            &SimpleAst::Mul([v807, v808]) => {
+                                                if v154 == v803 {
+                                                    if v154 == v808 {
+                                                        if v696 == v807 {
+                                                            let v809 = C::rule_conj_conj_identity_rule_precondition(ctx, v696);
+                                                            if let Some(v810) = v809 {
+                                                                let v452 = &C::any(ctx, v154);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 930.
+                                                                return Some(v452.clone());
                                                             }
                                                         }
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Or([v840, v841]) => {
-                                                if v56 == v840 {
-                                                    let v836 = &C::lookup_value(ctx, v710);
-                                                    if let Some(v837) = v836 {
-                                                        if let &SimpleAst::Mul([v838, v839]) = v837 {
-                                                            if v603 == v838 {
-                                                                if v839 == v841 {
-                                                                    let v842 = C::rule_conj_conj_disj_rule_precondition(ctx, v603);
-                                                                    if let Some(v843) = v842 {
-                                                                        let v359 = &C::any(ctx, v56);
-                                                                        let v844 = C::lookup_id(ctx, v359);
-                                                                        let v845 = &C::any(ctx, v839);
-                                                                        let v846 = C::lookup_id(ctx, v845);
-                                                                        let v847 = &C::and(ctx, v844, v846);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 907.
-                                                                        return Some(v847.clone());
+                                            &SimpleAst::Or([v933, v934]) => {
+                                                if v154 == v933 {
+                                                    let v929 = &C::lookup_value(ctx, v803);
+                                                    if let Some(v930) = v929 {
+                                                        if let &SimpleAst::Mul([v931, v932]) = v930 {
+                                                            if v696 == v931 {
+                                                                if v932 == v934 {
+                                                                    let v935 = C::rule_conj_conj_disj_rule_precondition(ctx, v696);
+                                                                    if let Some(v936) = v935 {
+                                                                        let v452 = &C::any(ctx, v154);
+                                                                        let v937 = C::lookup_id(ctx, v452);
+                                                                        let v938 = &C::any(ctx, v932);
+                                                                        let v939 = C::lookup_id(ctx, v938);
+                                                                        let v940 = &C::and(ctx, v937, v939);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1041.
+                                                                        return Some(v940.clone());
                                                                     }
                                                                 }
                                                             }
@@ -1974,24 +2013,24 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Xor([v607, v608]) => {
-                                    if let Some(v59) = v58 {
-                                        if let &SimpleAst::Neg([v60]) = v59 {
-                                            let v599 = &C::lookup_value(ctx, v60);
-                                            if let Some(v600) = v599 {
-                                                if let &SimpleAst::Mul([v601, v602]) = v600 {
-                                                    if v602 == v607 {
-                                                        let v609 = &C::lookup_value(ctx, v608);
-                                                        if let Some(v610) = v609 {
-                                                            if let &SimpleAst::Mul([v611, v612]) = v610 {
-                                                                if v602 == v612 {
-                                                                    if v603 == v611 {
-                                                                        let v613 = C::rule_conj_neg_xor_zero_rule_precondition(ctx, v601, v603);
-                                                                        if let Some(v614) = v613 {
-                                                                            let v615 = C::get_width(ctx, v601);
-                                                                            let v616 = &C::constant(ctx, 0x0, v615);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 726.
-                                                                            return Some(v616.clone());
+                                &SimpleAst::Xor([v700, v701]) => {
+                                    if let Some(v157) = v156 {
+                                        if let &SimpleAst::Neg([v158]) = v157 {
+                                            let v692 = &C::lookup_value(ctx, v158);
+                                            if let Some(v693) = v692 {
+                                                if let &SimpleAst::Mul([v694, v695]) = v693 {
+                                                    if v695 == v700 {
+                                                        let v702 = &C::lookup_value(ctx, v701);
+                                                        if let Some(v703) = v702 {
+                                                            if let &SimpleAst::Mul([v704, v705]) = v703 {
+                                                                if v695 == v705 {
+                                                                    if v696 == v704 {
+                                                                        let v706 = C::rule_conj_neg_xor_zero_rule_precondition(ctx, v694, v696);
+                                                                        if let Some(v707) = v706 {
+                                                                            let v708 = C::get_width(ctx, v694);
+                                                                            let v709 = &C::constant(ctx, 0x0, v708);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 860.
+                                                                            return Some(v709.clone());
                                                                         }
                                                                     }
                                                                 }
@@ -2007,21 +2046,21 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::And([v587, v588]) => {
-                        let v589 = &C::lookup_value(ctx, v587);
-                        if let Some(v590) = v589 {
-                            if let &SimpleAst::Mul([v591, v592]) = v590 {
-                                if v56 == v592 {
-                                    let v593 = &C::lookup_value(ctx, v588);
-                                    if let Some(v594) = v593 {
-                                        if let &SimpleAst::Mul([v595, v596]) = v594 {
-                                            if v56 == v596 {
-                                                let v597 = C::rule_conj_zero_rule_precondition(ctx, v591, v595);
-                                                if let Some(v598) = v597 {
-                                                    let v354 = C::get_width(ctx, v56);
-                                                    let v355 = &C::constant(ctx, 0x0, v354);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 719.
-                                                    return Some(v355.clone());
+                    &SimpleAst::And([v680, v681]) => {
+                        let v682 = &C::lookup_value(ctx, v680);
+                        if let Some(v683) = v682 {
+                            if let &SimpleAst::Mul([v684, v685]) = v683 {
+                                if v154 == v685 {
+                                    let v686 = &C::lookup_value(ctx, v681);
+                                    if let Some(v687) = v686 {
+                                        if let &SimpleAst::Mul([v688, v689]) = v687 {
+                                            if v154 == v689 {
+                                                let v690 = C::rule_conj_zero_rule_precondition(ctx, v684, v688);
+                                                if let Some(v691) = v690 {
+                                                    let v447 = C::get_width(ctx, v154);
+                                                    let v448 = &C::constant(ctx, 0x0, v447);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 853.
+                                                    return Some(v448.clone());
                                                 }
                                             }
                                         }
@@ -2030,31 +2069,31 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Or([v133, v134]) => {
-                        let v688 = &C::lookup_value(ctx, v133);
-                        if let Some(v689) = v688 {
-                            match v689 {
-                                &SimpleAst::Mul([v758, v759]) => {
-                                    let v691 = &C::lookup_value(ctx, v134);
-                                    if let Some(v692) = v691 {
-                                        if let &SimpleAst::Mul([v767, v768]) = v692 {
-                                            if v758 == v767 {
-                                                let v760 = &C::lookup_value(ctx, v759);
-                                                if let Some(v761) = v760 {
-                                                    if let &SimpleAst::Neg([v762]) = v761 {
-                                                        let v763 = &C::lookup_value(ctx, v762);
-                                                        if let Some(v764) = v763 {
-                                                            if let &SimpleAst::Mul([v765, v766]) = v764 {
-                                                                if v56 == v766 {
-                                                                    let v769 = &C::lookup_value(ctx, v768);
-                                                                    if let Some(v770) = v769 {
-                                                                        if let &SimpleAst::Neg([v771]) = v770 {
-                                                                            if v56 == v771 {
-                                                                                let v772 = C::rule_conj_disj_identity_rule_1_precondition(ctx, v758, v765);
-                                                                                if let Some(v773) = v772 {
-                                                                                    let v359 = &C::any(ctx, v56);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 845.
-                                                                                    return Some(v359.clone());
+                    &SimpleAst::Or([v229, v230]) => {
+                        let v781 = &C::lookup_value(ctx, v229);
+                        if let Some(v782) = v781 {
+                            match v782 {
+                                &SimpleAst::Mul([v851, v852]) => {
+                                    let v784 = &C::lookup_value(ctx, v230);
+                                    if let Some(v785) = v784 {
+                                        if let &SimpleAst::Mul([v860, v861]) = v785 {
+                                            if v851 == v860 {
+                                                let v853 = &C::lookup_value(ctx, v852);
+                                                if let Some(v854) = v853 {
+                                                    if let &SimpleAst::Neg([v855]) = v854 {
+                                                        let v856 = &C::lookup_value(ctx, v855);
+                                                        if let Some(v857) = v856 {
+                                                            if let &SimpleAst::Mul([v858, v859]) = v857 {
+                                                                if v154 == v859 {
+                                                                    let v862 = &C::lookup_value(ctx, v861);
+                                                                    if let Some(v863) = v862 {
+                                                                        if let &SimpleAst::Neg([v864]) = v863 {
+                                                                            if v154 == v864 {
+                                                                                let v865 = C::rule_conj_disj_identity_rule_1_precondition(ctx, v851, v858);
+                                                                                if let Some(v866) = v865 {
+                                                                                    let v452 = &C::any(ctx, v154);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 979.
+                                                                                    return Some(v452.clone());
                                                                                 }
                                                                             }
                                                                         }
@@ -2068,40 +2107,40 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Neg([v690]) => {
-                                    let v691 = &C::lookup_value(ctx, v134);
-                                    if let Some(v692) = v691 {
-                                        match v692 {
-                                            &SimpleAst::Mul([v767, v768]) => {
-                                                let v769 = &C::lookup_value(ctx, v768);
-                                                if let Some(v770) = v769 {
-                                                    if let &SimpleAst::Neg([v771]) = v770 {
-                                                        if v56 == v771 {
-                                                            let v774 = &C::lookup_value(ctx, v690);
-                                                            if let Some(v775) = v774 {
-                                                                if let &SimpleAst::Mul([v776, v777]) = v775 {
-                                                                    let v778 = &C::lookup_value(ctx, v777);
-                                                                    if let Some(v779) = v778 {
-                                                                        if let &SimpleAst::Neg([v780]) = v779 {
-                                                                            if v56 == v780 {
-                                                                                let v783 = &C::lookup_value(ctx, v776);
-                                                                                if let Some(v784) = v783 {
-                                                                                    if let &SimpleAst::Mul([v785, v786]) = v784 {
-                                                                                        if v767 == v785 {
-                                                                                            let v787 = C::rule_conj_disj_identity_rule_3_precondition(ctx, v785);
-                                                                                            if let Some(v788) = v787 {
-                                                                                                let v359 = &C::any(ctx, v56);
-                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 859.
-                                                                                                return Some(v359.clone());
+                                &SimpleAst::Neg([v783]) => {
+                                    let v784 = &C::lookup_value(ctx, v230);
+                                    if let Some(v785) = v784 {
+                                        match v785 {
+                                            &SimpleAst::Mul([v860, v861]) => {
+                                                let v862 = &C::lookup_value(ctx, v861);
+                                                if let Some(v863) = v862 {
+                                                    if let &SimpleAst::Neg([v864]) = v863 {
+                                                        if v154 == v864 {
+                                                            let v867 = &C::lookup_value(ctx, v783);
+                                                            if let Some(v868) = v867 {
+                                                                if let &SimpleAst::Mul([v869, v870]) = v868 {
+                                                                    let v871 = &C::lookup_value(ctx, v870);
+                                                                    if let Some(v872) = v871 {
+                                                                        if let &SimpleAst::Neg([v873]) = v872 {
+                                                                            if v154 == v873 {
+                                                                                let v876 = &C::lookup_value(ctx, v869);
+                                                                                if let Some(v877) = v876 {
+                                                                                    if let &SimpleAst::Mul([v878, v879]) = v877 {
+                                                                                        if v860 == v878 {
+                                                                                            let v880 = C::rule_conj_disj_identity_rule_3_precondition(ctx, v878);
+                                                                                            if let Some(v881) = v880 {
+                                                                                                let v452 = &C::any(ctx, v154);
+                                                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 993.
+                                                                                                return Some(v452.clone());
                                                                                             }
                                                                                         }
                                                                                     }
                                                                                 }
-                                                                                let v781 = C::rule_conj_disj_identity_rule_2_precondition(ctx, v776, v767);
-                                                                                if let Some(v782) = v781 {
-                                                                                    let v359 = &C::any(ctx, v56);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 852.
-                                                                                    return Some(v359.clone());
+                                                                                let v874 = C::rule_conj_disj_identity_rule_2_precondition(ctx, v869, v860);
+                                                                                if let Some(v875) = v874 {
+                                                                                    let v452 = &C::any(ctx, v154);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 986.
+                                                                                    return Some(v452.clone());
                                                                                 }
                                                                             }
                                                                         }
@@ -2112,35 +2151,35 @@ pub fn constructor_lower<C: Context>(
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::Neg([v693]) => {
-                                                if let Some(v59) = v58 {
-                                                    if let &SimpleAst::Mul([v635, v636]) = v59 {
-                                                        if v636 == v690 {
-                                                            let v694 = &C::lookup_value(ctx, v693);
-                                                            if let Some(v695) = v694 {
-                                                                if let &SimpleAst::Mul([v696, v697]) = v695 {
-                                                                    if v636 == v697 {
-                                                                        let v700 = C::rule_conj_neg_conj_identity_rule_4_precondition(ctx, v635, v696);
-                                                                        if let Some(v701) = v700 {
-                                                                            let v645 = C::get_width(ctx, v635);
-                                                                            let v683 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v645);
-                                                                            let v684 = C::lookup_id(ctx, v683);
-                                                                            let v655 = &C::any(ctx, v636);
-                                                                            let v656 = C::lookup_id(ctx, v655);
-                                                                            let v685 = &C::mul(ctx, v684, v656);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 782.
-                                                                            return Some(v685.clone());
+                                            &SimpleAst::Neg([v786]) => {
+                                                if let Some(v157) = v156 {
+                                                    if let &SimpleAst::Mul([v728, v729]) = v157 {
+                                                        if v729 == v783 {
+                                                            let v787 = &C::lookup_value(ctx, v786);
+                                                            if let Some(v788) = v787 {
+                                                                if let &SimpleAst::Mul([v789, v790]) = v788 {
+                                                                    if v729 == v790 {
+                                                                        let v793 = C::rule_conj_neg_conj_identity_rule_4_precondition(ctx, v728, v789);
+                                                                        if let Some(v794) = v793 {
+                                                                            let v738 = C::get_width(ctx, v728);
+                                                                            let v776 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v738);
+                                                                            let v777 = C::lookup_id(ctx, v776);
+                                                                            let v748 = &C::any(ctx, v729);
+                                                                            let v749 = C::lookup_id(ctx, v748);
+                                                                            let v778 = &C::mul(ctx, v777, v749);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 916.
+                                                                            return Some(v778.clone());
                                                                         }
-                                                                        let v698 = C::rule_conj_neg_conj_identity_rule_3_precondition(ctx, v635, v696);
-                                                                        if let Some(v699) = v698 {
-                                                                            let v645 = C::get_width(ctx, v635);
-                                                                            let v683 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v645);
-                                                                            let v684 = C::lookup_id(ctx, v683);
-                                                                            let v655 = &C::any(ctx, v636);
-                                                                            let v656 = C::lookup_id(ctx, v655);
-                                                                            let v685 = &C::mul(ctx, v684, v656);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 775.
-                                                                            return Some(v685.clone());
+                                                                        let v791 = C::rule_conj_neg_conj_identity_rule_3_precondition(ctx, v728, v789);
+                                                                        if let Some(v792) = v791 {
+                                                                            let v738 = C::get_width(ctx, v728);
+                                                                            let v776 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v738);
+                                                                            let v777 = C::lookup_id(ctx, v776);
+                                                                            let v748 = &C::any(ctx, v729);
+                                                                            let v749 = C::lookup_id(ctx, v748);
+                                                                            let v778 = &C::mul(ctx, v777, v749);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 909.
+                                                                            return Some(v778.clone());
                                                                         }
                                                                     }
                                                                 }
@@ -2157,24 +2196,24 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Xor([v150, v151]) => {
-                        if let Some(v59) = v58 {
-                            if let &SimpleAst::Mul([v635, v636]) = v59 {
-                                if v150 == v636 {
-                                    let v647 = &C::lookup_value(ctx, v151);
-                                    if let Some(v648) = v647 {
-                                        if let &SimpleAst::Mul([v649, v650]) = v648 {
-                                            if v150 == v650 {
-                                                let v651 = C::rule_conj_xor_identity_rule_precondition(ctx, v635, v649);
-                                                if let Some(v652) = v651 {
-                                                    let v645 = C::get_width(ctx, v635);
-                                                    let v653 = &C::constant(ctx, 0x2, v645);
-                                                    let v654 = C::lookup_id(ctx, v653);
-                                                    let v655 = &C::any(ctx, v636);
-                                                    let v656 = C::lookup_id(ctx, v655);
-                                                    let v657 = &C::mul(ctx, v654, v656);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 747.
-                                                    return Some(v657.clone());
+                    &SimpleAst::Xor([v246, v247]) => {
+                        if let Some(v157) = v156 {
+                            if let &SimpleAst::Mul([v728, v729]) = v157 {
+                                if v246 == v729 {
+                                    let v740 = &C::lookup_value(ctx, v247);
+                                    if let Some(v741) = v740 {
+                                        if let &SimpleAst::Mul([v742, v743]) = v741 {
+                                            if v246 == v743 {
+                                                let v744 = C::rule_conj_xor_identity_rule_precondition(ctx, v728, v742);
+                                                if let Some(v745) = v744 {
+                                                    let v738 = C::get_width(ctx, v728);
+                                                    let v746 = &C::constant(ctx, 0x2, v738);
+                                                    let v747 = C::lookup_id(ctx, v746);
+                                                    let v748 = &C::any(ctx, v729);
+                                                    let v749 = C::lookup_id(ctx, v748);
+                                                    let v750 = &C::mul(ctx, v747, v749);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 881.
+                                                    return Some(v750.clone());
                                                 }
                                             }
                                         }
@@ -2183,39 +2222,39 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Neg([v63]) => {
-                        let v550 = &C::lookup_value(ctx, v63);
-                        if let Some(v551) = v550 {
-                            match v551 {
-                                &SimpleAst::And([v675, v676]) => {
-                                    if let Some(v59) = v58 {
-                                        if let &SimpleAst::Mul([v635, v636]) = v59 {
-                                            if v636 == v675 {
-                                                let v677 = &C::lookup_value(ctx, v676);
-                                                if let Some(v678) = v677 {
-                                                    if let &SimpleAst::Mul([v679, v680]) = v678 {
-                                                        if v636 == v680 {
-                                                            let v686 = C::rule_conj_neg_conj_identity_rule_2_precondition(ctx, v635, v679);
-                                                            if let Some(v687) = v686 {
-                                                                let v645 = C::get_width(ctx, v635);
-                                                                let v683 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v645);
-                                                                let v684 = C::lookup_id(ctx, v683);
-                                                                let v655 = &C::any(ctx, v636);
-                                                                let v656 = C::lookup_id(ctx, v655);
-                                                                let v685 = &C::mul(ctx, v684, v656);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 768.
-                                                                return Some(v685.clone());
+                    &SimpleAst::Neg([v161]) => {
+                        let v643 = &C::lookup_value(ctx, v161);
+                        if let Some(v644) = v643 {
+                            match v644 {
+                                &SimpleAst::And([v768, v769]) => {
+                                    if let Some(v157) = v156 {
+                                        if let &SimpleAst::Mul([v728, v729]) = v157 {
+                                            if v729 == v768 {
+                                                let v770 = &C::lookup_value(ctx, v769);
+                                                if let Some(v771) = v770 {
+                                                    if let &SimpleAst::Mul([v772, v773]) = v771 {
+                                                        if v729 == v773 {
+                                                            let v779 = C::rule_conj_neg_conj_identity_rule_2_precondition(ctx, v728, v772);
+                                                            if let Some(v780) = v779 {
+                                                                let v738 = C::get_width(ctx, v728);
+                                                                let v776 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v738);
+                                                                let v777 = C::lookup_id(ctx, v776);
+                                                                let v748 = &C::any(ctx, v729);
+                                                                let v749 = C::lookup_id(ctx, v748);
+                                                                let v778 = &C::mul(ctx, v777, v749);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 902.
+                                                                return Some(v778.clone());
                                                             }
-                                                            let v681 = C::rule_conj_neg_conj_identity_rule_1_precondition(ctx, v635, v679);
-                                                            if let Some(v682) = v681 {
-                                                                let v645 = C::get_width(ctx, v635);
-                                                                let v683 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v645);
-                                                                let v684 = C::lookup_id(ctx, v683);
-                                                                let v655 = &C::any(ctx, v636);
-                                                                let v656 = C::lookup_id(ctx, v655);
-                                                                let v685 = &C::mul(ctx, v684, v656);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 761.
-                                                                return Some(v685.clone());
+                                                            let v774 = C::rule_conj_neg_conj_identity_rule_1_precondition(ctx, v728, v772);
+                                                            if let Some(v775) = v774 {
+                                                                let v738 = C::get_width(ctx, v728);
+                                                                let v776 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v738);
+                                                                let v777 = C::lookup_id(ctx, v776);
+                                                                let v748 = &C::any(ctx, v729);
+                                                                let v749 = C::lookup_id(ctx, v748);
+                                                                let v778 = &C::mul(ctx, v777, v749);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 895.
+                                                                return Some(v778.clone());
                                                             }
                                                         }
                                                     }
@@ -2224,28 +2263,28 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Or([v552, v553]) => {
-                                    if v56 == v552 {
-                                        let v354 = C::get_width(ctx, v56);
-                                        let v355 = &C::constant(ctx, 0x0, v354);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 699.
-                                        return Some(v355.clone());
-                                    }
-                                }
-                                &SimpleAst::Xor([v637, v638]) => {
-                                    if let Some(v59) = v58 {
-                                        if let &SimpleAst::Mul([v635, v636]) = v59 {
-                                            if v636 == v637 {
-                                                let v639 = &C::lookup_value(ctx, v638);
-                                                if let Some(v640) = v639 {
-                                                    if let &SimpleAst::Mul([v641, v642]) = v640 {
-                                                        if v636 == v642 {
-                                                            let v643 = C::rule_conj_negated_xor_zero_rule_precondition(ctx, v635, v641);
-                                                            if let Some(v644) = v643 {
-                                                                let v645 = C::get_width(ctx, v635);
-                                                                let v646 = &C::constant(ctx, 0x0, v645);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 740.
-                                                                return Some(v646.clone());
+                                &SimpleAst::Or([v645, v646]) => {
+                                    if v154 == v645 {
+                                        let v447 = C::get_width(ctx, v154);
+                                        let v448 = &C::constant(ctx, 0x0, v447);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 833.
+                                        return Some(v448.clone());
+                                    }
+                                }
+                                &SimpleAst::Xor([v730, v731]) => {
+                                    if let Some(v157) = v156 {
+                                        if let &SimpleAst::Mul([v728, v729]) = v157 {
+                                            if v729 == v730 {
+                                                let v732 = &C::lookup_value(ctx, v731);
+                                                if let Some(v733) = v732 {
+                                                    if let &SimpleAst::Mul([v734, v735]) = v733 {
+                                                        if v729 == v735 {
+                                                            let v736 = C::rule_conj_negated_xor_zero_rule_precondition(ctx, v728, v734);
+                                                            if let Some(v737) = v736 {
+                                                                let v738 = C::get_width(ctx, v728);
+                                                                let v739 = &C::constant(ctx, 0x0, v738);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 874.
+                                                                return Some(v739.clone());
                                                             }
                                                         }
                                                     }
@@ -2261,111 +2300,111 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            if let Some(v59) = v58 {
-                if let &SimpleAst::Add([v400, v401]) = v59 {
-                    let v402 = &C::lookup_value(ctx, v401);
-                    if let Some(v403) = v402 {
-                        if let &SimpleAst::Mul([v404, v405]) = v403 {
-                            if v400 == v404 {
-                                let v406 = C::rule_and_bitwise_negation_precondition(ctx, v400);
-                                if let Some(v407) = v406 {
-                                    let v408 = &C::any(ctx, v405);
-                                    let v409 = C::lookup_id(ctx, v408);
-                                    let v410 = &C::neg(ctx, v409);
-                                    let v411 = C::lookup_id(ctx, v410);
-                                    let v412 = &C::any(ctx, v57);
-                                    let v413 = C::lookup_id(ctx, v412);
-                                    let v414 = &C::and(ctx, v411, v413);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 609.
-                                    return Some(v414.clone());
+            if let Some(v157) = v156 {
+                if let &SimpleAst::Add([v493, v494]) = v157 {
+                    let v495 = &C::lookup_value(ctx, v494);
+                    if let Some(v496) = v495 {
+                        if let &SimpleAst::Mul([v497, v498]) = v496 {
+                            if v493 == v497 {
+                                let v499 = C::rule_and_bitwise_negation_precondition(ctx, v493);
+                                if let Some(v500) = v499 {
+                                    let v501 = &C::any(ctx, v498);
+                                    let v502 = C::lookup_id(ctx, v501);
+                                    let v503 = &C::neg(ctx, v502);
+                                    let v504 = C::lookup_id(ctx, v503);
+                                    let v505 = &C::any(ctx, v155);
+                                    let v506 = C::lookup_id(ctx, v505);
+                                    let v507 = &C::and(ctx, v504, v506);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 743.
+                                    return Some(v507.clone());
                                 }
                             }
                         }
                     }
                 }
             }
-            if let Some(v62) = v61 {
-                if let &SimpleAst::Neg([v63]) = v62 {
-                    if v56 == v63 {
-                        let v354 = C::get_width(ctx, v56);
-                        let v355 = &C::constant(ctx, 0x0, v354);
-                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 542.
-                        return Some(v355.clone());
+            if let Some(v160) = v159 {
+                if let &SimpleAst::Neg([v161]) = v160 {
+                    if v154 == v161 {
+                        let v447 = C::get_width(ctx, v154);
+                        let v448 = &C::constant(ctx, 0x0, v447);
+                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 676.
+                        return Some(v448.clone());
                     }
                 }
             }
-            if v56 == v57 {
-                let v359 = &C::any(ctx, v56);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 536.
-                return Some(v359.clone());
+            if v154 == v155 {
+                let v452 = &C::any(ctx, v154);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 670.
+                return Some(v452.clone());
             }
-            let v356 = C::rule_and_maxint_precondition(ctx, v56);
-            if let Some(v357) = v356 {
-                let v358 = &C::any(ctx, v57);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 529.
-                return Some(v358.clone());
+            let v449 = C::rule_and_maxint_precondition(ctx, v154);
+            if let Some(v450) = v449 {
+                let v451 = &C::any(ctx, v155);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 663.
+                return Some(v451.clone());
             }
-            let v352 = C::rule_and_zero_precondition(ctx, v56);
-            if let Some(v353) = v352 {
-                let v354 = C::get_width(ctx, v56);
-                let v355 = &C::constant(ctx, 0x0, v354);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 522.
-                return Some(v355.clone());
+            let v445 = C::rule_and_zero_precondition(ctx, v154);
+            if let Some(v446) = v445 {
+                let v447 = C::get_width(ctx, v154);
+                let v448 = &C::constant(ctx, 0x0, v447);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 656.
+                return Some(v448.clone());
             }
-            if let Some(v59) = v58 {
-                match v59 {
-                    &SimpleAst::Or([v131, v132]) => {
-                        if let Some(v62) = v61 {
-                            if let &SimpleAst::Or([v133, v134]) = v62 {
-                                if v131 == v133 {
-                                    let v135 = &C::any(ctx, v131);
-                                    let v136 = C::lookup_id(ctx, v135);
-                                    let v137 = &C::any(ctx, v132);
-                                    let v138 = C::lookup_id(ctx, v137);
-                                    let v139 = &C::any(ctx, v134);
-                                    let v140 = C::lookup_id(ctx, v139);
-                                    let v141 = &C::and(ctx, v138, v140);
-                                    let v142 = C::lookup_id(ctx, v141);
-                                    let v143 = &C::or(ctx, v136, v142);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 387.
-                                    return Some(v143.clone());
+            if let Some(v157) = v156 {
+                match v157 {
+                    &SimpleAst::Or([v227, v228]) => {
+                        if let Some(v160) = v159 {
+                            if let &SimpleAst::Or([v229, v230]) = v160 {
+                                if v227 == v229 {
+                                    let v231 = &C::any(ctx, v227);
+                                    let v232 = C::lookup_id(ctx, v231);
+                                    let v233 = &C::any(ctx, v228);
+                                    let v234 = C::lookup_id(ctx, v233);
+                                    let v235 = &C::any(ctx, v230);
+                                    let v236 = C::lookup_id(ctx, v235);
+                                    let v237 = &C::and(ctx, v234, v236);
+                                    let v238 = C::lookup_id(ctx, v237);
+                                    let v239 = &C::or(ctx, v232, v238);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 521.
+                                    return Some(v239.clone());
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Neg([v60]) => {
-                        if let Some(v62) = v61 {
-                            match v62 {
-                                &SimpleAst::Xor([v150, v151]) => {
-                                    let v152 = &C::lookup_value(ctx, v150);
-                                    if let Some(v153) = v152 {
-                                        if let &SimpleAst::Neg([v154]) = v153 {
-                                            let v64 = &C::any(ctx, v60);
-                                            let v65 = C::lookup_id(ctx, v64);
-                                            let v155 = &C::any(ctx, v154);
-                                            let v156 = C::lookup_id(ctx, v155);
-                                            let v157 = &C::any(ctx, v151);
-                                            let v158 = C::lookup_id(ctx, v157);
-                                            let v159 = &C::xor(ctx, v156, v158);
-                                            let v160 = C::lookup_id(ctx, v159);
-                                            let v161 = &C::or(ctx, v65, v160);
-                                            let v162 = C::lookup_id(ctx, v161);
-                                            let v163 = &C::neg(ctx, v162);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 399.
-                                            return Some(v163.clone());
-                                        }
-                                    }
-                                }
-                                &SimpleAst::Neg([v63]) => {
-                                    let v64 = &C::any(ctx, v60);
-                                    let v65 = C::lookup_id(ctx, v64);
-                                    let v66 = &C::any(ctx, v63);
-                                    let v67 = C::lookup_id(ctx, v66);
-                                    let v68 = &C::or(ctx, v65, v67);
-                                    let v69 = C::lookup_id(ctx, v68);
-                                    let v70 = &C::neg(ctx, v69);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 357.
-                                    return Some(v70.clone());
+                    &SimpleAst::Neg([v158]) => {
+                        if let Some(v160) = v159 {
+                            match v160 {
+                                &SimpleAst::Xor([v246, v247]) => {
+                                    let v248 = &C::lookup_value(ctx, v246);
+                                    if let Some(v249) = v248 {
+                                        if let &SimpleAst::Neg([v250]) = v249 {
+                                            let v162 = &C::any(ctx, v158);
+                                            let v163 = C::lookup_id(ctx, v162);
+                                            let v251 = &C::any(ctx, v250);
+                                            let v252 = C::lookup_id(ctx, v251);
+                                            let v253 = &C::any(ctx, v247);
+                                            let v254 = C::lookup_id(ctx, v253);
+                                            let v255 = &C::xor(ctx, v252, v254);
+                                            let v256 = C::lookup_id(ctx, v255);
+                                            let v257 = &C::or(ctx, v163, v256);
+                                            let v258 = C::lookup_id(ctx, v257);
+                                            let v259 = &C::neg(ctx, v258);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 533.
+                                            return Some(v259.clone());
+                                        }
+                                    }
+                                }
+                                &SimpleAst::Neg([v161]) => {
+                                    let v162 = &C::any(ctx, v158);
+                                    let v163 = C::lookup_id(ctx, v162);
+                                    let v164 = &C::any(ctx, v161);
+                                    let v165 = C::lookup_id(ctx, v164);
+                                    let v166 = &C::or(ctx, v163, v165);
+                                    let v167 = C::lookup_id(ctx, v166);
+                                    let v168 = &C::neg(ctx, v167);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 491.
+                                    return Some(v168.clone());
                                 }
                                 _ => {}
                             }
@@ -2375,35 +2414,35 @@ pub fn constructor_lower<C: Context>(
                 }
             }
         }
-        &SimpleAst::Or([v112, v113]) => {
-            let v114 = &C::lookup_value(ctx, v112);
-            if let Some(v115) = v114 {
-                match v115 {
-                    &SimpleAst::Mul([v617, v618]) => {
-                        let v118 = &C::lookup_value(ctx, v113);
-                        if let Some(v119) = v118 {
-                            if let &SimpleAst::And([v120, v121]) = v119 {
-                                let v848 = &C::lookup_value(ctx, v618);
-                                if let Some(v849) = v848 {
-                                    if let &SimpleAst::Or([v850, v851]) = v849 {
-                                        let v852 = &C::lookup_value(ctx, v850);
-                                        if let Some(v853) = v852 {
-                                            if let &SimpleAst::Mul([v854, v855]) = v853 {
-                                                if v120 == v855 {
-                                                    if v617 == v854 {
-                                                        let v856 = &C::lookup_value(ctx, v851);
-                                                        if let Some(v857) = v856 {
-                                                            if let &SimpleAst::And([v858, v859]) = v857 {
-                                                                let v860 = &C::lookup_value(ctx, v858);
-                                                                if let Some(v861) = v860 {
-                                                                    if let &SimpleAst::And([v862, v863]) = v861 {
-                                                                        if v120 == v862 {
-                                                                            if v121 == v863 {
-                                                                                let v864 = C::rule_disj_disj_conj_rule_2_precondition(ctx, v617);
-                                                                                if let Some(v865) = v864 {
-                                                                                    let v866 = &C::any(ctx, v855);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 914.
-                                                                                    return Some(v866.clone());
+        &SimpleAst::Or([v25, v26]) => {
+            let v210 = &C::lookup_value(ctx, v25);
+            if let Some(v211) = v210 {
+                match v211 {
+                    &SimpleAst::Mul([v710, v711]) => {
+                        let v214 = &C::lookup_value(ctx, v26);
+                        if let Some(v215) = v214 {
+                            if let &SimpleAst::And([v216, v217]) = v215 {
+                                let v941 = &C::lookup_value(ctx, v711);
+                                if let Some(v942) = v941 {
+                                    if let &SimpleAst::Or([v943, v944]) = v942 {
+                                        let v945 = &C::lookup_value(ctx, v943);
+                                        if let Some(v946) = v945 {
+                                            if let &SimpleAst::Mul([v947, v948]) = v946 {
+                                                if v216 == v948 {
+                                                    if v710 == v947 {
+                                                        let v949 = &C::lookup_value(ctx, v944);
+                                                        if let Some(v950) = v949 {
+                                                            if let &SimpleAst::And([v951, v952]) = v950 {
+                                                                let v953 = &C::lookup_value(ctx, v951);
+                                                                if let Some(v954) = v953 {
+                                                                    if let &SimpleAst::And([v955, v956]) = v954 {
+                                                                        if v216 == v955 {
+                                                                            if v217 == v956 {
+                                                                                let v957 = C::rule_disj_disj_conj_rule_2_precondition(ctx, v710);
+                                                                                if let Some(v958) = v957 {
+                                                                                    let v959 = &C::any(ctx, v948);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1048.
+                                                                                    return Some(v959.clone());
                                                                                 }
                                                                             }
                                                                         }
@@ -2420,92 +2459,92 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::And([v116, v117]) => {
-                        if v113 == v116 {
-                            let v122 = &C::any(ctx, v116);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1139.
-                            return Some(v122.clone());
-                        }
-                        let v118 = &C::lookup_value(ctx, v113);
-                        if let Some(v119) = v118 {
-                            if let &SimpleAst::Xor([v878, v879]) = v119 {
-                                if v117 == v879 {
-                                    let v343 = &C::lookup_value(ctx, v116);
-                                    if let Some(v344) = v343 {
-                                        if let &SimpleAst::And([v889, v890]) = v344 {
-                                            if v878 == v890 {
-                                                let v891 = &C::any(ctx, v889);
-                                                let v892 = C::lookup_id(ctx, v891);
-                                                let v893 = &C::any(ctx, v890);
-                                                let v894 = C::lookup_id(ctx, v893);
-                                                let v895 = &C::and(ctx, v892, v894);
-                                                let v896 = C::lookup_id(ctx, v895);
-                                                let v897 = &C::any(ctx, v117);
-                                                let v898 = C::lookup_id(ctx, v897);
-                                                let v899 = &C::and(ctx, v896, v898);
-                                                let v900 = C::lookup_id(ctx, v899);
-                                                let v901 = &C::any(ctx, v890);
-                                                let v902 = C::lookup_id(ctx, v901);
-                                                let v903 = &C::any(ctx, v117);
-                                                let v904 = C::lookup_id(ctx, v903);
-                                                let v905 = &C::xor(ctx, v902, v904);
-                                                let v906 = C::lookup_id(ctx, v905);
-                                                let v907 = &C::add(ctx, v900, v906);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 934.
-                                                return Some(v907.clone());
-                                            }
-                                        }
-                                    }
-                                    if v116 == v878 {
-                                        let v122 = &C::any(ctx, v116);
-                                        let v123 = C::lookup_id(ctx, v122);
-                                        let v124 = &C::any(ctx, v117);
-                                        let v125 = C::lookup_id(ctx, v124);
-                                        let v880 = &C::and(ctx, v123, v125);
-                                        let v881 = C::lookup_id(ctx, v880);
-                                        let v882 = &C::any(ctx, v116);
-                                        let v883 = C::lookup_id(ctx, v882);
-                                        let v884 = &C::any(ctx, v117);
-                                        let v885 = C::lookup_id(ctx, v884);
-                                        let v886 = &C::xor(ctx, v883, v885);
-                                        let v887 = C::lookup_id(ctx, v886);
-                                        let v888 = &C::add(ctx, v881, v887);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 928.
-                                        return Some(v888.clone());
+                    &SimpleAst::And([v212, v213]) => {
+                        if v26 == v212 {
+                            let v218 = &C::any(ctx, v212);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1273.
+                            return Some(v218.clone());
+                        }
+                        let v214 = &C::lookup_value(ctx, v26);
+                        if let Some(v215) = v214 {
+                            if let &SimpleAst::Xor([v971, v972]) = v215 {
+                                if v213 == v972 {
+                                    let v436 = &C::lookup_value(ctx, v212);
+                                    if let Some(v437) = v436 {
+                                        if let &SimpleAst::And([v982, v983]) = v437 {
+                                            if v971 == v983 {
+                                                let v984 = &C::any(ctx, v982);
+                                                let v985 = C::lookup_id(ctx, v984);
+                                                let v986 = &C::any(ctx, v983);
+                                                let v987 = C::lookup_id(ctx, v986);
+                                                let v988 = &C::and(ctx, v985, v987);
+                                                let v989 = C::lookup_id(ctx, v988);
+                                                let v990 = &C::any(ctx, v213);
+                                                let v991 = C::lookup_id(ctx, v990);
+                                                let v992 = &C::and(ctx, v989, v991);
+                                                let v993 = C::lookup_id(ctx, v992);
+                                                let v994 = &C::any(ctx, v983);
+                                                let v995 = C::lookup_id(ctx, v994);
+                                                let v996 = &C::any(ctx, v213);
+                                                let v997 = C::lookup_id(ctx, v996);
+                                                let v998 = &C::xor(ctx, v995, v997);
+                                                let v999 = C::lookup_id(ctx, v998);
+                                                let v1000 = &C::add(ctx, v993, v999);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1068.
+                                                return Some(v1000.clone());
+                                            }
+                                        }
+                                    }
+                                    if v212 == v971 {
+                                        let v218 = &C::any(ctx, v212);
+                                        let v219 = C::lookup_id(ctx, v218);
+                                        let v220 = &C::any(ctx, v213);
+                                        let v221 = C::lookup_id(ctx, v220);
+                                        let v973 = &C::and(ctx, v219, v221);
+                                        let v974 = C::lookup_id(ctx, v973);
+                                        let v975 = &C::any(ctx, v212);
+                                        let v976 = C::lookup_id(ctx, v975);
+                                        let v977 = &C::any(ctx, v213);
+                                        let v978 = C::lookup_id(ctx, v977);
+                                        let v979 = &C::xor(ctx, v976, v978);
+                                        let v980 = C::lookup_id(ctx, v979);
+                                        let v981 = &C::add(ctx, v974, v980);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1062.
+                                        return Some(v981.clone());
                                     }
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Xor([v954, v955]) => {
-                        if v113 == v954 {
-                            let v956 = &C::any(ctx, v954);
-                            let v957 = C::lookup_id(ctx, v956);
-                            let v958 = &C::any(ctx, v955);
-                            let v959 = C::lookup_id(ctx, v958);
-                            let v960 = &C::or(ctx, v957, v959);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 977.
-                            return Some(v960.clone());
+                    &SimpleAst::Xor([v1047, v1048]) => {
+                        if v26 == v1047 {
+                            let v1049 = &C::any(ctx, v1047);
+                            let v1050 = C::lookup_id(ctx, v1049);
+                            let v1051 = &C::any(ctx, v1048);
+                            let v1052 = C::lookup_id(ctx, v1051);
+                            let v1053 = &C::or(ctx, v1050, v1052);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1111.
+                            return Some(v1053.clone());
                         }
                     }
-                    &SimpleAst::Neg([v1304]) => {
-                        let v118 = &C::lookup_value(ctx, v113);
-                        if let Some(v119) = v118 {
-                            if let &SimpleAst::And([v120, v121]) = v119 {
-                                let v1305 = &C::lookup_value(ctx, v1304);
-                                if let Some(v1306) = v1305 {
-                                    if let &SimpleAst::Or([v1307, v1308]) = v1306 {
-                                        if v120 == v1307 {
-                                            if v121 == v1308 {
-                                                let v1309 = &C::any(ctx, v1307);
-                                                let v1310 = C::lookup_id(ctx, v1309);
-                                                let v1311 = &C::any(ctx, v1308);
-                                                let v1312 = C::lookup_id(ctx, v1311);
-                                                let v1313 = &C::xor(ctx, v1310, v1312);
-                                                let v1314 = C::lookup_id(ctx, v1313);
-                                                let v1315 = &C::neg(ctx, v1314);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1207.
-                                                return Some(v1315.clone());
+                    &SimpleAst::Neg([v1397]) => {
+                        let v214 = &C::lookup_value(ctx, v26);
+                        if let Some(v215) = v214 {
+                            if let &SimpleAst::And([v216, v217]) = v215 {
+                                let v1398 = &C::lookup_value(ctx, v1397);
+                                if let Some(v1399) = v1398 {
+                                    if let &SimpleAst::Or([v1400, v1401]) = v1399 {
+                                        if v216 == v1400 {
+                                            if v217 == v1401 {
+                                                let v1402 = &C::any(ctx, v1400);
+                                                let v1403 = C::lookup_id(ctx, v1402);
+                                                let v1404 = &C::any(ctx, v1401);
+                                                let v1405 = C::lookup_id(ctx, v1404);
+                                                let v1406 = &C::xor(ctx, v1403, v1405);
+                                                let v1407 = C::lookup_id(ctx, v1406);
+                                                let v1408 = &C::neg(ctx, v1407);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1341.
+                                                return Some(v1408.clone());
                                             }
                                         }
                                     }
@@ -2516,38 +2555,38 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            let v118 = &C::lookup_value(ctx, v113);
-            if let Some(v119) = v118 {
-                match v119 {
-                    &SimpleAst::Add([v801, v802]) => {
-                        let v807 = &C::lookup_value(ctx, v802);
-                        if let Some(v808) = v807 {
-                            if let &SimpleAst::Mul([v809, v810]) = v808 {
-                                if v112 == v801 {
-                                    let v813 = &C::lookup_value(ctx, v810);
-                                    if let Some(v814) = v813 {
-                                        if let &SimpleAst::And([v815, v816]) = v814 {
-                                            if v112 == v815 {
-                                                let v817 = C::rule_disj_sub_disj_identity_rule_2_precondition(ctx, v809);
-                                                if let Some(v818) = v817 {
-                                                    let v331 = &C::any(ctx, v112);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 887.
-                                                    return Some(v331.clone());
+            let v214 = &C::lookup_value(ctx, v26);
+            if let Some(v215) = v214 {
+                match v215 {
+                    &SimpleAst::Add([v894, v895]) => {
+                        let v900 = &C::lookup_value(ctx, v895);
+                        if let Some(v901) = v900 {
+                            if let &SimpleAst::Mul([v902, v903]) = v901 {
+                                if v25 == v894 {
+                                    let v906 = &C::lookup_value(ctx, v903);
+                                    if let Some(v907) = v906 {
+                                        if let &SimpleAst::And([v908, v909]) = v907 {
+                                            if v25 == v908 {
+                                                let v910 = C::rule_disj_sub_disj_identity_rule_2_precondition(ctx, v902);
+                                                if let Some(v911) = v910 {
+                                                    let v424 = &C::any(ctx, v25);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1021.
+                                                    return Some(v424.clone());
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                let v803 = &C::lookup_value(ctx, v801);
-                                if let Some(v804) = v803 {
-                                    if let &SimpleAst::Or([v805, v806]) = v804 {
-                                        if v112 == v805 {
-                                            if v806 == v810 {
-                                                let v811 = C::rule_disj_sub_disj_identity_rule_1_precondition(ctx, v809);
-                                                if let Some(v812) = v811 {
-                                                    let v331 = &C::any(ctx, v112);
-                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 880.
-                                                    return Some(v331.clone());
+                                let v896 = &C::lookup_value(ctx, v894);
+                                if let Some(v897) = v896 {
+                                    if let &SimpleAst::Or([v898, v899]) = v897 {
+                                        if v25 == v898 {
+                                            if v899 == v903 {
+                                                let v904 = C::rule_disj_sub_disj_identity_rule_1_precondition(ctx, v902);
+                                                if let Some(v905) = v904 {
+                                                    let v424 = &C::any(ctx, v25);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1014.
+                                                    return Some(v424.clone());
                                                 }
                                             }
                                         }
@@ -2556,72 +2595,72 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Mul([v658, v659]) => {
-                        let v660 = &C::lookup_value(ctx, v659);
-                        if let Some(v661) = v660 {
-                            match v661 {
-                                &SimpleAst::Or([v702, v703]) => {
-                                    let v704 = &C::lookup_value(ctx, v703);
-                                    if let Some(v705) = v704 {
-                                        match v705 {
-                                            &SimpleAst::Mul([v706, v707]) => {
-                                                if v112 == v707 {
-                                                    let v789 = &C::lookup_value(ctx, v702);
-                                                    if let Some(v790) = v789 {
-                                                        if let &SimpleAst::Mul([v791, v792]) = v790 {
-                                                            if v112 == v792 {
-                                                                if v658 == v791 {
-                                                                    let v795 = &C::lookup_value(ctx, v706);
-                                                                    if let Some(v796) = v795 {
-                                                                        if let &SimpleAst::Mul([v797, v798]) = v796 {
-                                                                            if v658 == v797 {
-                                                                                let v799 = C::rule_disj_neg_disj_identity_rule_2_precondition(ctx, v658);
-                                                                                if let Some(v800) = v799 {
-                                                                                    let v331 = &C::any(ctx, v112);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 873.
-                                                                                    return Some(v331.clone());
+                    &SimpleAst::Mul([v751, v752]) => {
+                        let v753 = &C::lookup_value(ctx, v752);
+                        if let Some(v754) = v753 {
+                            match v754 {
+                                &SimpleAst::Or([v795, v796]) => {
+                                    let v797 = &C::lookup_value(ctx, v796);
+                                    if let Some(v798) = v797 {
+                                        match v798 {
+                                            &SimpleAst::Mul([v799, v800]) => {
+                                                if v25 == v800 {
+                                                    let v882 = &C::lookup_value(ctx, v795);
+                                                    if let Some(v883) = v882 {
+                                                        if let &SimpleAst::Mul([v884, v885]) = v883 {
+                                                            if v25 == v885 {
+                                                                if v751 == v884 {
+                                                                    let v888 = &C::lookup_value(ctx, v799);
+                                                                    if let Some(v889) = v888 {
+                                                                        if let &SimpleAst::Mul([v890, v891]) = v889 {
+                                                                            if v751 == v890 {
+                                                                                let v892 = C::rule_disj_neg_disj_identity_rule_2_precondition(ctx, v751);
+                                                                                if let Some(v893) = v892 {
+                                                                                    let v424 = &C::any(ctx, v25);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1007.
+                                                                                    return Some(v424.clone());
                                                                                 }
                                                                             }
                                                                         }
                                                                     }
-                                                                    let v793 = C::rule_disj_neg_disj_identity_rule_1_precondition(ctx, v658);
-                                                                    if let Some(v794) = v793 {
-                                                                        let v331 = &C::any(ctx, v112);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 866.
-                                                                        return Some(v331.clone());
+                                                                    let v886 = C::rule_disj_neg_disj_identity_rule_1_precondition(ctx, v751);
+                                                                    if let Some(v887) = v886 {
+                                                                        let v424 = &C::any(ctx, v25);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1000.
+                                                                        return Some(v424.clone());
                                                                     }
                                                                 }
                                                             }
                                                         }
                                                     }
-                                                    if v112 == v702 {
-                                                        if v658 == v706 {
-                                                            let v708 = C::rule_disj_disj_identity_rule_precondition(ctx, v658);
-                                                            if let Some(v709) = v708 {
-                                                                let v331 = &C::any(ctx, v112);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 789.
-                                                                return Some(v331.clone());
+                                                    if v25 == v795 {
+                                                        if v751 == v799 {
+                                                            let v801 = C::rule_disj_disj_identity_rule_precondition(ctx, v751);
+                                                            if let Some(v802) = v801 {
+                                                                let v424 = &C::any(ctx, v25);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 923.
+                                                                return Some(v424.clone());
                                                             }
                                                         }
                                                     }
                                                 }
                                             }
-                                            &SimpleAst::And([v828, v829]) => {
-                                                if v112 == v828 {
-                                                    let v789 = &C::lookup_value(ctx, v702);
-                                                    if let Some(v790) = v789 {
-                                                        if let &SimpleAst::Mul([v791, v792]) = v790 {
-                                                            if v658 == v791 {
-                                                                if v792 == v829 {
-                                                                    let v830 = C::rule_disj_disj_conj_rule_precondition(ctx, v658);
-                                                                    if let Some(v831) = v830 {
-                                                                        let v331 = &C::any(ctx, v112);
-                                                                        let v832 = C::lookup_id(ctx, v331);
-                                                                        let v833 = &C::any(ctx, v792);
-                                                                        let v834 = C::lookup_id(ctx, v833);
-                                                                        let v835 = &C::or(ctx, v832, v834);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 900.
-                                                                        return Some(v835.clone());
+                                            &SimpleAst::And([v921, v922]) => {
+                                                if v25 == v921 {
+                                                    let v882 = &C::lookup_value(ctx, v795);
+                                                    if let Some(v883) = v882 {
+                                                        if let &SimpleAst::Mul([v884, v885]) = v883 {
+                                                            if v751 == v884 {
+                                                                if v885 == v922 {
+                                                                    let v923 = C::rule_disj_disj_conj_rule_precondition(ctx, v751);
+                                                                    if let Some(v924) = v923 {
+                                                                        let v424 = &C::any(ctx, v25);
+                                                                        let v925 = C::lookup_id(ctx, v424);
+                                                                        let v926 = &C::any(ctx, v885);
+                                                                        let v927 = C::lookup_id(ctx, v926);
+                                                                        let v928 = &C::or(ctx, v925, v927);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1034.
+                                                                        return Some(v928.clone());
                                                                     }
                                                                 }
                                                             }
@@ -2633,25 +2672,25 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Xor([v662, v663]) => {
-                                    if let Some(v115) = v114 {
-                                        if let &SimpleAst::Mul([v617, v618]) = v115 {
-                                            if v618 == v662 {
-                                                let v664 = &C::lookup_value(ctx, v663);
-                                                if let Some(v665) = v664 {
-                                                    if let &SimpleAst::Mul([v666, v667]) = v665 {
-                                                        if v618 == v667 {
-                                                            if v658 == v666 {
-                                                                let v668 = C::rule_disj_xor_identity_rule_precondition(ctx, v617, v658);
-                                                                if let Some(v669) = v668 {
-                                                                    let v633 = C::get_width(ctx, v617);
-                                                                    let v670 = &C::constant(ctx, 0x2, v633);
-                                                                    let v671 = C::lookup_id(ctx, v670);
-                                                                    let v672 = &C::any(ctx, v618);
-                                                                    let v673 = C::lookup_id(ctx, v672);
-                                                                    let v674 = &C::mul(ctx, v671, v673);
-                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 754.
-                                                                    return Some(v674.clone());
+                                &SimpleAst::Xor([v755, v756]) => {
+                                    if let Some(v211) = v210 {
+                                        if let &SimpleAst::Mul([v710, v711]) = v211 {
+                                            if v711 == v755 {
+                                                let v757 = &C::lookup_value(ctx, v756);
+                                                if let Some(v758) = v757 {
+                                                    if let &SimpleAst::Mul([v759, v760]) = v758 {
+                                                        if v711 == v760 {
+                                                            if v751 == v759 {
+                                                                let v761 = C::rule_disj_xor_identity_rule_precondition(ctx, v710, v751);
+                                                                if let Some(v762) = v761 {
+                                                                    let v726 = C::get_width(ctx, v710);
+                                                                    let v763 = &C::constant(ctx, 0x2, v726);
+                                                                    let v764 = C::lookup_id(ctx, v763);
+                                                                    let v765 = &C::any(ctx, v711);
+                                                                    let v766 = C::lookup_id(ctx, v765);
+                                                                    let v767 = &C::mul(ctx, v764, v766);
+                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 888.
+                                                                    return Some(v767.clone());
                                                                 }
                                                             }
                                                         }
@@ -2665,40 +2704,40 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::And([v120, v121]) => {
-                        let v346 = &C::lookup_value(ctx, v121);
-                        if let Some(v347) = v346 {
-                            if let &SimpleAst::Mul([v721, v722]) = v347 {
-                                let v718 = &C::lookup_value(ctx, v120);
-                                if let Some(v719) = v718 {
-                                    match v719 {
-                                        &SimpleAst::Mul([v742, v743]) => {
-                                            let v744 = &C::lookup_value(ctx, v743);
-                                            if let Some(v745) = v744 {
-                                                if let &SimpleAst::Neg([v746]) = v745 {
-                                                    if v112 == v746 {
-                                                        let v747 = &C::lookup_value(ctx, v722);
-                                                        if let Some(v748) = v747 {
-                                                            if let &SimpleAst::Neg([v749]) = v748 {
-                                                                if v112 == v749 {
-                                                                    let v752 = &C::lookup_value(ctx, v721);
-                                                                    if let Some(v753) = v752 {
-                                                                        if let &SimpleAst::Mul([v754, v755]) = v753 {
-                                                                            if v742 == v754 {
-                                                                                let v756 = C::rule_disj_conj_identity_rule_2_2_precondition(ctx, v742, v755);
-                                                                                if let Some(v757) = v756 {
-                                                                                    let v331 = &C::any(ctx, v112);
-                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 838.
-                                                                                    return Some(v331.clone());
+                    &SimpleAst::And([v216, v217]) => {
+                        let v439 = &C::lookup_value(ctx, v217);
+                        if let Some(v440) = v439 {
+                            if let &SimpleAst::Mul([v814, v815]) = v440 {
+                                let v811 = &C::lookup_value(ctx, v216);
+                                if let Some(v812) = v811 {
+                                    match v812 {
+                                        &SimpleAst::Mul([v835, v836]) => {
+                                            let v837 = &C::lookup_value(ctx, v836);
+                                            if let Some(v838) = v837 {
+                                                if let &SimpleAst::Neg([v839]) = v838 {
+                                                    if v25 == v839 {
+                                                        let v840 = &C::lookup_value(ctx, v815);
+                                                        if let Some(v841) = v840 {
+                                                            if let &SimpleAst::Neg([v842]) = v841 {
+                                                                if v25 == v842 {
+                                                                    let v845 = &C::lookup_value(ctx, v814);
+                                                                    if let Some(v846) = v845 {
+                                                                        if let &SimpleAst::Mul([v847, v848]) = v846 {
+                                                                            if v835 == v847 {
+                                                                                let v849 = C::rule_disj_conj_identity_rule_2_2_precondition(ctx, v835, v848);
+                                                                                if let Some(v850) = v849 {
+                                                                                    let v424 = &C::any(ctx, v25);
+                                                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 972.
+                                                                                    return Some(v424.clone());
                                                                                 }
                                                                             }
                                                                         }
                                                                     }
-                                                                    let v750 = C::rule_disj_conj_identity_rule_2_1_precondition(ctx, v742, v721);
-                                                                    if let Some(v751) = v750 {
-                                                                        let v331 = &C::any(ctx, v112);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 831.
-                                                                        return Some(v331.clone());
+                                                                    let v843 = C::rule_disj_conj_identity_rule_2_1_precondition(ctx, v835, v814);
+                                                                    if let Some(v844) = v843 {
+                                                                        let v424 = &C::any(ctx, v25);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 965.
+                                                                        return Some(v424.clone());
                                                                     }
                                                                 }
                                                             }
@@ -2707,32 +2746,32 @@ pub fn constructor_lower<C: Context>(
                                                 }
                                             }
                                         }
-                                        &SimpleAst::Neg([v720]) => {
-                                            if let Some(v115) = v114 {
-                                                if let &SimpleAst::Mul([v617, v618]) = v115 {
-                                                    if v618 == v720 {
-                                                        if v618 == v722 {
-                                                            let v727 = C::rule_disj_conj_identity_rule_2_precondition(ctx, v617, v721);
-                                                            if let Some(v728) = v727 {
-                                                                let v633 = C::get_width(ctx, v617);
-                                                                let v634 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v633);
-                                                                let v725 = C::lookup_id(ctx, v634);
-                                                                let v672 = &C::any(ctx, v618);
-                                                                let v673 = C::lookup_id(ctx, v672);
-                                                                let v726 = &C::mul(ctx, v725, v673);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 810.
-                                                                return Some(v726.clone());
+                                        &SimpleAst::Neg([v813]) => {
+                                            if let Some(v211) = v210 {
+                                                if let &SimpleAst::Mul([v710, v711]) = v211 {
+                                                    if v711 == v813 {
+                                                        if v711 == v815 {
+                                                            let v820 = C::rule_disj_conj_identity_rule_2_precondition(ctx, v710, v814);
+                                                            if let Some(v821) = v820 {
+                                                                let v726 = C::get_width(ctx, v710);
+                                                                let v727 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v726);
+                                                                let v818 = C::lookup_id(ctx, v727);
+                                                                let v765 = &C::any(ctx, v711);
+                                                                let v766 = C::lookup_id(ctx, v765);
+                                                                let v819 = &C::mul(ctx, v818, v766);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 944.
+                                                                return Some(v819.clone());
                                                             }
-                                                            let v723 = C::rule_disj_conj_identity_rule_1_precondition(ctx, v617, v721);
-                                                            if let Some(v724) = v723 {
-                                                                let v633 = C::get_width(ctx, v617);
-                                                                let v634 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v633);
-                                                                let v725 = C::lookup_id(ctx, v634);
-                                                                let v672 = &C::any(ctx, v618);
-                                                                let v673 = C::lookup_id(ctx, v672);
-                                                                let v726 = &C::mul(ctx, v725, v673);
-                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 803.
-                                                                return Some(v726.clone());
+                                                            let v816 = C::rule_disj_conj_identity_rule_1_precondition(ctx, v710, v814);
+                                                            if let Some(v817) = v816 {
+                                                                let v726 = C::get_width(ctx, v710);
+                                                                let v727 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v726);
+                                                                let v818 = C::lookup_id(ctx, v727);
+                                                                let v765 = &C::any(ctx, v711);
+                                                                let v766 = C::lookup_id(ctx, v765);
+                                                                let v819 = &C::mul(ctx, v818, v766);
+                                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 937.
+                                                                return Some(v819.clone());
                                                             }
                                                         }
                                                     }
@@ -2745,28 +2784,28 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Neg([v332]) => {
-                        if let Some(v115) = v114 {
-                            if let &SimpleAst::Mul([v617, v618]) = v115 {
-                                let v619 = &C::lookup_value(ctx, v332);
-                                if let Some(v620) = v619 {
-                                    match v620 {
-                                        &SimpleAst::Mul([v621, v622]) => {
-                                            let v623 = &C::lookup_value(ctx, v622);
-                                            if let Some(v624) = v623 {
-                                                if let &SimpleAst::Xor([v625, v626]) = v624 {
-                                                    if v618 == v625 {
-                                                        let v627 = &C::lookup_value(ctx, v626);
-                                                        if let Some(v628) = v627 {
-                                                            if let &SimpleAst::Mul([v629, v630]) = v628 {
-                                                                if v618 == v630 {
-                                                                    if v621 == v629 {
-                                                                        let v631 = C::rule_conj_neg_xor_minus_one_rule_precondition(ctx, v617, v621);
-                                                                        if let Some(v632) = v631 {
-                                                                            let v633 = C::get_width(ctx, v617);
-                                                                            let v634 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v633);
-                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 733.
-                                                                            return Some(v634.clone());
+                    &SimpleAst::Neg([v425]) => {
+                        if let Some(v211) = v210 {
+                            if let &SimpleAst::Mul([v710, v711]) = v211 {
+                                let v712 = &C::lookup_value(ctx, v425);
+                                if let Some(v713) = v712 {
+                                    match v713 {
+                                        &SimpleAst::Mul([v714, v715]) => {
+                                            let v716 = &C::lookup_value(ctx, v715);
+                                            if let Some(v717) = v716 {
+                                                if let &SimpleAst::Xor([v718, v719]) = v717 {
+                                                    if v711 == v718 {
+                                                        let v720 = &C::lookup_value(ctx, v719);
+                                                        if let Some(v721) = v720 {
+                                                            if let &SimpleAst::Mul([v722, v723]) = v721 {
+                                                                if v711 == v723 {
+                                                                    if v714 == v722 {
+                                                                        let v724 = C::rule_conj_neg_xor_minus_one_rule_precondition(ctx, v710, v714);
+                                                                        if let Some(v725) = v724 {
+                                                                            let v726 = C::get_width(ctx, v710);
+                                                                            let v727 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v726);
+                                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 867.
+                                                                            return Some(v727.clone());
                                                                         }
                                                                     }
                                                                 }
@@ -2776,36 +2815,36 @@ pub fn constructor_lower<C: Context>(
                                                 }
                                             }
                                         }
-                                        &SimpleAst::Or([v729, v730]) => {
-                                            if v618 == v729 {
-                                                let v731 = &C::lookup_value(ctx, v730);
-                                                if let Some(v732) = v731 {
-                                                    if let &SimpleAst::Neg([v733]) = v732 {
-                                                        let v734 = &C::lookup_value(ctx, v733);
-                                                        if let Some(v735) = v734 {
-                                                            if let &SimpleAst::Mul([v736, v737]) = v735 {
-                                                                if v618 == v737 {
-                                                                    let v740 = C::rule_disj_conj_identity_rule_4_precondition(ctx, v617, v736);
-                                                                    if let Some(v741) = v740 {
-                                                                        let v633 = C::get_width(ctx, v617);
-                                                                        let v634 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v633);
-                                                                        let v725 = C::lookup_id(ctx, v634);
-                                                                        let v672 = &C::any(ctx, v618);
-                                                                        let v673 = C::lookup_id(ctx, v672);
-                                                                        let v726 = &C::mul(ctx, v725, v673);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 824.
-                                                                        return Some(v726.clone());
+                                        &SimpleAst::Or([v822, v823]) => {
+                                            if v711 == v822 {
+                                                let v824 = &C::lookup_value(ctx, v823);
+                                                if let Some(v825) = v824 {
+                                                    if let &SimpleAst::Neg([v826]) = v825 {
+                                                        let v827 = &C::lookup_value(ctx, v826);
+                                                        if let Some(v828) = v827 {
+                                                            if let &SimpleAst::Mul([v829, v830]) = v828 {
+                                                                if v711 == v830 {
+                                                                    let v833 = C::rule_disj_conj_identity_rule_4_precondition(ctx, v710, v829);
+                                                                    if let Some(v834) = v833 {
+                                                                        let v726 = C::get_width(ctx, v710);
+                                                                        let v727 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v726);
+                                                                        let v818 = C::lookup_id(ctx, v727);
+                                                                        let v765 = &C::any(ctx, v711);
+                                                                        let v766 = C::lookup_id(ctx, v765);
+                                                                        let v819 = &C::mul(ctx, v818, v766);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 958.
+                                                                        return Some(v819.clone());
                                                                     }
-                                                                    let v738 = C::rule_disj_conj_identity_rule_3_precondition(ctx, v617, v736);
-                                                                    if let Some(v739) = v738 {
-                                                                        let v633 = C::get_width(ctx, v617);
-                                                                        let v634 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v633);
-                                                                        let v725 = C::lookup_id(ctx, v634);
-                                                                        let v672 = &C::any(ctx, v618);
-                                                                        let v673 = C::lookup_id(ctx, v672);
-                                                                        let v726 = &C::mul(ctx, v725, v673);
-                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 817.
-                                                                        return Some(v726.clone());
+                                                                    let v831 = C::rule_disj_conj_identity_rule_3_precondition(ctx, v710, v829);
+                                                                    if let Some(v832) = v831 {
+                                                                        let v726 = C::get_width(ctx, v710);
+                                                                        let v727 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v726);
+                                                                        let v818 = C::lookup_id(ctx, v727);
+                                                                        let v765 = &C::any(ctx, v711);
+                                                                        let v766 = C::lookup_id(ctx, v765);
+                                                                        let v819 = &C::mul(ctx, v818, v766);
+                                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 951.
+                                                                        return Some(v819.clone());
                                                                     }
                                                                 }
                                                             }
@@ -2823,47 +2862,47 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            if let Some(v115) = v114 {
-                match v115 {
-                    &SimpleAst::Add([v415, v416]) => {
-                        let v417 = &C::lookup_value(ctx, v416);
-                        if let Some(v418) = v417 {
-                            if let &SimpleAst::Mul([v419, v420]) = v418 {
-                                if v415 == v419 {
-                                    let v421 = C::rule_or_bitwise_negation_precondition(ctx, v415);
-                                    if let Some(v422) = v421 {
-                                        let v423 = &C::any(ctx, v420);
-                                        let v424 = C::lookup_id(ctx, v423);
-                                        let v425 = &C::neg(ctx, v424);
-                                        let v426 = C::lookup_id(ctx, v425);
-                                        let v427 = &C::any(ctx, v113);
-                                        let v428 = C::lookup_id(ctx, v427);
-                                        let v429 = &C::or(ctx, v426, v428);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 616.
-                                        return Some(v429.clone());
+            if let Some(v211) = v210 {
+                match v211 {
+                    &SimpleAst::Add([v508, v509]) => {
+                        let v510 = &C::lookup_value(ctx, v509);
+                        if let Some(v511) = v510 {
+                            if let &SimpleAst::Mul([v512, v513]) = v511 {
+                                if v508 == v512 {
+                                    let v514 = C::rule_or_bitwise_negation_precondition(ctx, v508);
+                                    if let Some(v515) = v514 {
+                                        let v516 = &C::any(ctx, v513);
+                                        let v517 = C::lookup_id(ctx, v516);
+                                        let v518 = &C::neg(ctx, v517);
+                                        let v519 = C::lookup_id(ctx, v518);
+                                        let v520 = &C::any(ctx, v26);
+                                        let v521 = C::lookup_id(ctx, v520);
+                                        let v522 = &C::or(ctx, v519, v521);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 750.
+                                        return Some(v522.clone());
                                     }
                                 }
                             }
                         }
                     }
-                    &SimpleAst::And([v116, v117]) => {
-                        if let Some(v119) = v118 {
-                            if let &SimpleAst::And([v120, v121]) = v119 {
-                                let v343 = &C::lookup_value(ctx, v116);
-                                if let Some(v344) = v343 {
-                                    if let &SimpleAst::Neg([v345]) = v344 {
-                                        if v120 == v345 {
-                                            let v346 = &C::lookup_value(ctx, v121);
-                                            if let Some(v347) = v346 {
-                                                if let &SimpleAst::Neg([v348]) = v347 {
-                                                    if v117 == v348 {
-                                                        let v349 = &C::any(ctx, v345);
-                                                        let v350 = C::lookup_id(ctx, v349);
-                                                        let v124 = &C::any(ctx, v117);
-                                                        let v125 = C::lookup_id(ctx, v124);
-                                                        let v351 = &C::xor(ctx, v350, v125);
-                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 516.
-                                                        return Some(v351.clone());
+                    &SimpleAst::And([v212, v213]) => {
+                        if let Some(v215) = v214 {
+                            if let &SimpleAst::And([v216, v217]) = v215 {
+                                let v436 = &C::lookup_value(ctx, v212);
+                                if let Some(v437) = v436 {
+                                    if let &SimpleAst::Neg([v438]) = v437 {
+                                        if v216 == v438 {
+                                            let v439 = &C::lookup_value(ctx, v217);
+                                            if let Some(v440) = v439 {
+                                                if let &SimpleAst::Neg([v441]) = v440 {
+                                                    if v213 == v441 {
+                                                        let v442 = &C::any(ctx, v438);
+                                                        let v443 = C::lookup_id(ctx, v442);
+                                                        let v220 = &C::any(ctx, v213);
+                                                        let v221 = C::lookup_id(ctx, v220);
+                                                        let v444 = &C::xor(ctx, v443, v221);
+                                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 650.
+                                                        return Some(v444.clone());
                                                     }
                                                 }
                                             }
@@ -2876,82 +2915,94 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            if let Some(v119) = v118 {
-                if let &SimpleAst::Neg([v332]) = v119 {
-                    if v112 == v332 {
-                        let v329 = C::get_width(ctx, v112);
-                        let v330 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v329);
-                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 490.
-                        return Some(v330.clone());
+            if let Some(v215) = v214 {
+                if let &SimpleAst::Neg([v425]) = v215 {
+                    if v25 == v425 {
+                        let v422 = C::get_width(ctx, v25);
+                        let v423 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v422);
+                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 624.
+                        return Some(v423.clone());
                     }
                 }
             }
-            if v112 == v113 {
-                let v331 = &C::any(ctx, v112);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 484.
-                return Some(v331.clone());
+            if v25 == v26 {
+                let v424 = &C::any(ctx, v25);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 618.
+                return Some(v424.clone());
             }
-            let v327 = C::rule_or_maxint_precondition(ctx, v112);
-            if let Some(v328) = v327 {
-                let v329 = C::get_width(ctx, v112);
-                let v330 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFF, v329);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 477.
-                return Some(v330.clone());
+            let v420 = C::rule_or_maxint_precondition(ctx, v25);
+            if let Some(v421) = v420 {
+                let v422 = C::get_width(ctx, v25);
+                let v423 = &C::constant(ctx, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v422);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 611.
+                return Some(v423.clone());
             }
-            let v324 = C::rule_or_zero_precondition(ctx, v112);
-            if let Some(v325) = v324 {
-                let v326 = &C::any(ctx, v113);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 470.
-                return Some(v326.clone());
+            let v417 = C::rule_or_zero_precondition(ctx, v25);
+            if let Some(v418) = v417 {
+                let v419 = &C::any(ctx, v26);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 604.
+                return Some(v419.clone());
             }
-            if let Some(v115) = v114 {
-                if let &SimpleAst::And([v116, v117]) = v115 {
-                    if let Some(v119) = v118 {
-                        if let &SimpleAst::And([v120, v121]) = v119 {
-                            if v116 == v120 {
-                                let v122 = &C::any(ctx, v116);
-                                let v123 = C::lookup_id(ctx, v122);
-                                let v124 = &C::any(ctx, v117);
-                                let v125 = C::lookup_id(ctx, v124);
-                                let v126 = &C::any(ctx, v121);
-                                let v127 = C::lookup_id(ctx, v126);
-                                let v128 = &C::or(ctx, v125, v127);
-                                let v129 = C::lookup_id(ctx, v128);
-                                let v130 = &C::and(ctx, v123, v129);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 381.
-                                return Some(v130.clone());
+            if let Some(v211) = v210 {
+                if let &SimpleAst::And([v212, v213]) = v211 {
+                    if let Some(v215) = v214 {
+                        if let &SimpleAst::And([v216, v217]) = v215 {
+                            if v212 == v216 {
+                                let v218 = &C::any(ctx, v212);
+                                let v219 = C::lookup_id(ctx, v218);
+                                let v220 = &C::any(ctx, v213);
+                                let v221 = C::lookup_id(ctx, v220);
+                                let v222 = &C::any(ctx, v217);
+                                let v223 = C::lookup_id(ctx, v222);
+                                let v224 = &C::or(ctx, v221, v223);
+                                let v225 = C::lookup_id(ctx, v224);
+                                let v226 = &C::and(ctx, v219, v225);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 515.
+                                return Some(v226.clone());
                             }
                         }
                     }
                 }
             }
+            let v27 = C::rotate_source(ctx, v25, v26);
+            if let Some(v28) = v27 {
+                let v29 = C::rotate_amount(ctx, v25, v26);
+                if let Some(v30) = v29 {
+                    let v31 = C::get_width(ctx, v28);
+                    let v32 = &C::constant(ctx, v30, v31);
+                    let v33 = C::lookup_id(ctx, v32);
+                    let v34 = &C::rotl(ctx, v28, v33);
+                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 220.
+                    return Some(v34.clone());
+                }
+            }
         }
-        &SimpleAst::Xor([v1, v2]) => {
-            let v3 = &C::lookup_value(ctx, v2);
-            if let Some(v4) = v3 {
-                if let &SimpleAst::Or([v87, v88]) = v4 {
-                    let v186 = &C::lookup_value(ctx, v88);
-                    if let Some(v187) = v186 {
-                        if let &SimpleAst::Neg([v1316]) = v187 {
-                            let v1317 = &C::lookup_value(ctx, v1316);
-                            if let Some(v1318) = v1317 {
-                                if let &SimpleAst::And([v1319, v1320]) = v1318 {
-                                    if v1 == v1320 {
-                                        let v8 = &C::any(ctx, v1);
-                                        let v9 = C::lookup_id(ctx, v8);
-                                        let v192 = &C::any(ctx, v87);
-                                        let v193 = C::lookup_id(ctx, v192);
-                                        let v1321 = &C::any(ctx, v1319);
-                                        let v1322 = C::lookup_id(ctx, v1321);
-                                        let v1323 = &C::neg(ctx, v1322);
-                                        let v1324 = C::lookup_id(ctx, v1323);
-                                        let v1325 = &C::or(ctx, v193, v1324);
-                                        let v1326 = C::lookup_id(ctx, v1325);
-                                        let v1327 = &C::and(ctx, v9, v1326);
-                                        let v1328 = C::lookup_id(ctx, v1327);
-                                        let v1329 = &C::neg(ctx, v1328);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1213.
-                                        return Some(v1329.clone());
+        &SimpleAst::Xor([v45, v46]) => {
+            let v101 = &C::lookup_value(ctx, v46);
+            if let Some(v102) = v101 {
+                if let &SimpleAst::Or([v185, v186]) = v102 {
+                    let v282 = &C::lookup_value(ctx, v186);
+                    if let Some(v283) = v282 {
+                        if let &SimpleAst::Neg([v1409]) = v283 {
+                            let v1410 = &C::lookup_value(ctx, v1409);
+                            if let Some(v1411) = v1410 {
+                                if let &SimpleAst::And([v1412, v1413]) = v1411 {
+                                    if v45 == v1413 {
+                                        let v106 = &C::any(ctx, v45);
+                                        let v107 = C::lookup_id(ctx, v106);
+                                        let v288 = &C::any(ctx, v185);
+                                        let v289 = C::lookup_id(ctx, v288);
+                                        let v1414 = &C::any(ctx, v1412);
+                                        let v1415 = C::lookup_id(ctx, v1414);
+                                        let v1416 = &C::neg(ctx, v1415);
+                                        let v1417 = C::lookup_id(ctx, v1416);
+                                        let v1418 = &C::or(ctx, v289, v1417);
+                                        let v1419 = C::lookup_id(ctx, v1418);
+                                        let v1420 = &C::and(ctx, v107, v1419);
+                                        let v1421 = C::lookup_id(ctx, v1420);
+                                        let v1422 = &C::neg(ctx, v1421);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1347.
+                                        return Some(v1422.clone());
                                     }
                                 }
                             }
@@ -2959,46 +3010,46 @@ pub fn constructor_lower<C: Context>(
                     }
                 }
             }
-            let v83 = &C::lookup_value(ctx, v1);
-            if let Some(v84) = v83 {
-                match v84 {
-                    &SimpleAst::And([v164, v165]) => {
-                        if let Some(v4) = v3 {
-                            if let &SimpleAst::And([v43, v44]) = v4 {
-                                if v43 == v164 {
-                                    let v166 = &C::lookup_value(ctx, v165);
-                                    if let Some(v167) = v166 {
-                                        if let &SimpleAst::Xor([v1159, v1160]) = v167 {
-                                            let v1161 = &C::any(ctx, v164);
-                                            let v1162 = C::lookup_id(ctx, v1161);
-                                            let v48 = &C::any(ctx, v44);
-                                            let v49 = C::lookup_id(ctx, v48);
-                                            let v1163 = &C::any(ctx, v1159);
-                                            let v1164 = C::lookup_id(ctx, v1163);
-                                            let v1165 = &C::any(ctx, v1160);
-                                            let v1166 = C::lookup_id(ctx, v1165);
-                                            let v1167 = &C::xor(ctx, v1164, v1166);
-                                            let v1168 = C::lookup_id(ctx, v1167);
-                                            let v1169 = &C::xor(ctx, v49, v1168);
-                                            let v1170 = C::lookup_id(ctx, v1169);
-                                            let v1171 = &C::and(ctx, v1162, v1170);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1157.
-                                            return Some(v1171.clone());
-                                        }
-                                    }
-                                    let v1130 = &C::lookup_value(ctx, v44);
-                                    if let Some(v1131) = v1130 {
-                                        if let &SimpleAst::Add([v1132, v1133]) = v1131 {
-                                            if v165 == v1132 {
-                                                let v1134 = &C::lookup_value(ctx, v1133);
-                                                if let Some(v1135) = v1134 {
-                                                    if let &SimpleAst::Mul([v1136, v1137]) = v1135 {
-                                                        let v1138 = C::rule_opaque_constant_two_precondition(ctx, v164, v1136, v1137);
-                                                        if let Some(v1139) = v1138 {
-                                                            let v1140 = C::get_width(ctx, v164);
-                                                            let v1141 = &C::constant(ctx, 0x0, v1140);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1132.
-                                                            return Some(v1141.clone());
+            let v181 = &C::lookup_value(ctx, v45);
+            if let Some(v182) = v181 {
+                match v182 {
+                    &SimpleAst::And([v260, v261]) => {
+                        if let Some(v102) = v101 {
+                            if let &SimpleAst::And([v141, v142]) = v102 {
+                                if v141 == v260 {
+                                    let v262 = &C::lookup_value(ctx, v261);
+                                    if let Some(v263) = v262 {
+                                        if let &SimpleAst::Xor([v1252, v1253]) = v263 {
+                                            let v1254 = &C::any(ctx, v260);
+                                            let v1255 = C::lookup_id(ctx, v1254);
+                                            let v146 = &C::any(ctx, v142);
+                                            let v147 = C::lookup_id(ctx, v146);
+                                            let v1256 = &C::any(ctx, v1252);
+                                            let v1257 = C::lookup_id(ctx, v1256);
+                                            let v1258 = &C::any(ctx, v1253);
+                                            let v1259 = C::lookup_id(ctx, v1258);
+                                            let v1260 = &C::xor(ctx, v1257, v1259);
+                                            let v1261 = C::lookup_id(ctx, v1260);
+                                            let v1262 = &C::xor(ctx, v147, v1261);
+                                            let v1263 = C::lookup_id(ctx, v1262);
+                                            let v1264 = &C::and(ctx, v1255, v1263);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1291.
+                                            return Some(v1264.clone());
+                                        }
+                                    }
+                                    let v1223 = &C::lookup_value(ctx, v142);
+                                    if let Some(v1224) = v1223 {
+                                        if let &SimpleAst::Add([v1225, v1226]) = v1224 {
+                                            if v261 == v1225 {
+                                                let v1227 = &C::lookup_value(ctx, v1226);
+                                                if let Some(v1228) = v1227 {
+                                                    if let &SimpleAst::Mul([v1229, v1230]) = v1228 {
+                                                        let v1231 = C::rule_opaque_constant_two_precondition(ctx, v260, v1229, v1230);
+                                                        if let Some(v1232) = v1231 {
+                                                            let v1233 = C::get_width(ctx, v260);
+                                                            let v1234 = &C::constant(ctx, 0x0, v1233);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1266.
+                                                            return Some(v1234.clone());
                                                         }
                                                     }
                                                 }
@@ -3009,28 +3060,28 @@ pub fn constructor_lower<C: Context>(
                             }
                         }
                     }
-                    &SimpleAst::Or([v85, v86]) => {
-                        let v1172 = &C::lookup_value(ctx, v85);
-                        if let Some(v1173) = v1172 {
-                            if let &SimpleAst::Xor([v1174, v1175]) = v1173 {
-                                if v2 == v1174 {
-                                    let v1176 = &C::lookup_value(ctx, v86);
-                                    if let Some(v1177) = v1176 {
-                                        if let &SimpleAst::And([v1178, v1179]) = v1177 {
-                                            if v2 == v1178 {
-                                                let v1180 = &C::any(ctx, v1175);
-                                                let v1181 = C::lookup_id(ctx, v1180);
-                                                let v1182 = &C::any(ctx, v1179);
-                                                let v1183 = C::lookup_id(ctx, v1182);
-                                                let v1184 = &C::any(ctx, v1174);
-                                                let v1185 = C::lookup_id(ctx, v1184);
-                                                let v1186 = &C::and(ctx, v1183, v1185);
-                                                let v1187 = C::lookup_id(ctx, v1186);
-                                                let v1188 = &C::neg(ctx, v1187);
-                                                let v1189 = C::lookup_id(ctx, v1188);
-                                                let v1190 = &C::and(ctx, v1181, v1189);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1163.
-                                                return Some(v1190.clone());
+                    &SimpleAst::Or([v183, v184]) => {
+                        let v1265 = &C::lookup_value(ctx, v183);
+                        if let Some(v1266) = v1265 {
+                            if let &SimpleAst::Xor([v1267, v1268]) = v1266 {
+                                if v46 == v1267 {
+                                    let v1269 = &C::lookup_value(ctx, v184);
+                                    if let Some(v1270) = v1269 {
+                                        if let &SimpleAst::And([v1271, v1272]) = v1270 {
+                                            if v46 == v1271 {
+                                                let v1273 = &C::any(ctx, v1268);
+                                                let v1274 = C::lookup_id(ctx, v1273);
+                                                let v1275 = &C::any(ctx, v1272);
+                                                let v1276 = C::lookup_id(ctx, v1275);
+                                                let v1277 = &C::any(ctx, v1267);
+                                                let v1278 = C::lookup_id(ctx, v1277);
+                                                let v1279 = &C::and(ctx, v1276, v1278);
+                                                let v1280 = C::lookup_id(ctx, v1279);
+                                                let v1281 = &C::neg(ctx, v1280);
+                                                let v1282 = C::lookup_id(ctx, v1281);
+                                                let v1283 = &C::and(ctx, v1274, v1282);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1297.
+                                                return Some(v1283.clone());
                                             }
                                         }
                                     }
@@ -3041,171 +3092,171 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            if let Some(v4) = v3 {
-                if let &SimpleAst::Or([v87, v88]) = v4 {
-                    if v1 == v87 {
-                        let v8 = &C::any(ctx, v1);
-                        let v9 = C::lookup_id(ctx, v8);
-                        let v10 = &C::neg(ctx, v9);
-                        let v11 = C::lookup_id(ctx, v10);
-                        let v908 = &C::any(ctx, v88);
-                        let v909 = C::lookup_id(ctx, v908);
-                        let v910 = &C::and(ctx, v11, v909);
-                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 940.
-                        return Some(v910.clone());
+            if let Some(v102) = v101 {
+                if let &SimpleAst::Or([v185, v186]) = v102 {
+                    if v45 == v185 {
+                        let v106 = &C::any(ctx, v45);
+                        let v107 = C::lookup_id(ctx, v106);
+                        let v108 = &C::neg(ctx, v107);
+                        let v109 = C::lookup_id(ctx, v108);
+                        let v1001 = &C::any(ctx, v186);
+                        let v1002 = C::lookup_id(ctx, v1001);
+                        let v1003 = &C::and(ctx, v109, v1002);
+                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1074.
+                        return Some(v1003.clone());
                     }
                 }
             }
-            if let Some(v84) = v83 {
-                if let &SimpleAst::Add([v430, v431]) = v84 {
-                    let v432 = &C::lookup_value(ctx, v431);
-                    if let Some(v433) = v432 {
-                        if let &SimpleAst::Mul([v434, v435]) = v433 {
-                            if v430 == v434 {
-                                let v436 = C::rule_xor_bitwise_negation_precondition(ctx, v430);
-                                if let Some(v437) = v436 {
-                                    let v438 = &C::any(ctx, v435);
-                                    let v439 = C::lookup_id(ctx, v438);
-                                    let v440 = &C::neg(ctx, v439);
-                                    let v441 = C::lookup_id(ctx, v440);
-                                    let v442 = &C::any(ctx, v2);
-                                    let v443 = C::lookup_id(ctx, v442);
-                                    let v444 = &C::xor(ctx, v441, v443);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 623.
-                                    return Some(v444.clone());
+            if let Some(v182) = v181 {
+                if let &SimpleAst::Add([v523, v524]) = v182 {
+                    let v525 = &C::lookup_value(ctx, v524);
+                    if let Some(v526) = v525 {
+                        if let &SimpleAst::Mul([v527, v528]) = v526 {
+                            if v523 == v527 {
+                                let v529 = C::rule_xor_bitwise_negation_precondition(ctx, v523);
+                                if let Some(v530) = v529 {
+                                    let v531 = &C::any(ctx, v528);
+                                    let v532 = C::lookup_id(ctx, v531);
+                                    let v533 = &C::neg(ctx, v532);
+                                    let v534 = C::lookup_id(ctx, v533);
+                                    let v535 = &C::any(ctx, v46);
+                                    let v536 = C::lookup_id(ctx, v535);
+                                    let v537 = &C::xor(ctx, v534, v536);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 757.
+                                    return Some(v537.clone());
                                 }
                             }
                         }
                     }
                 }
             }
-            if v1 == v2 {
-                let v341 = C::get_width(ctx, v1);
-                let v342 = &C::constant(ctx, 0x0, v341);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 510.
-                return Some(v342.clone());
+            if v45 == v46 {
+                let v434 = C::get_width(ctx, v45);
+                let v435 = &C::constant(ctx, 0x0, v434);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 644.
+                return Some(v435.clone());
             }
-            let v336 = C::rule_xor_maxint_precondition(ctx, v1);
-            if let Some(v337) = v336 {
-                let v335 = &C::any(ctx, v2);
-                let v338 = C::lookup_id(ctx, v335);
-                let v339 = &C::neg(ctx, v338);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 503.
-                return Some(v339.clone());
+            let v429 = C::rule_xor_maxint_precondition(ctx, v45);
+            if let Some(v430) = v429 {
+                let v428 = &C::any(ctx, v46);
+                let v431 = C::lookup_id(ctx, v428);
+                let v432 = &C::neg(ctx, v431);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 637.
+                return Some(v432.clone());
             }
-            let v333 = C::rule_xor_zero_precondition(ctx, v1);
-            if let Some(v334) = v333 {
-                let v335 = &C::any(ctx, v2);
-                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 496.
-                return Some(v335.clone());
+            let v426 = C::rule_xor_zero_precondition(ctx, v45);
+            if let Some(v427) = v426 {
+                let v428 = &C::any(ctx, v46);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 630.
+                return Some(v428.clone());
             }
-            if let Some(v84) = v83 {
-                match v84 {
-                    &SimpleAst::And([v164, v165]) => {
-                        let v166 = &C::lookup_value(ctx, v165);
-                        if let Some(v167) = v166 {
-                            if let &SimpleAst::Or([v168, v169]) = v167 {
-                                if v2 == v168 {
-                                    let v170 = &C::lookup_value(ctx, v169);
-                                    if let Some(v171) = v170 {
-                                        if let &SimpleAst::Neg([v172]) = v171 {
-                                            let v173 = &C::any(ctx, v168);
-                                            let v174 = C::lookup_id(ctx, v173);
-                                            let v175 = &C::any(ctx, v164);
-                                            let v176 = C::lookup_id(ctx, v175);
-                                            let v177 = &C::any(ctx, v172);
-                                            let v178 = C::lookup_id(ctx, v177);
-                                            let v179 = &C::and(ctx, v176, v178);
-                                            let v180 = C::lookup_id(ctx, v179);
-                                            let v181 = &C::or(ctx, v174, v180);
-                                            let v182 = C::lookup_id(ctx, v181);
-                                            let v183 = &C::any(ctx, v164);
-                                            let v184 = C::lookup_id(ctx, v183);
-                                            let v185 = &C::xor(ctx, v182, v184);
-                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 405.
-                                            return Some(v185.clone());
+            if let Some(v182) = v181 {
+                match v182 {
+                    &SimpleAst::And([v260, v261]) => {
+                        let v262 = &C::lookup_value(ctx, v261);
+                        if let Some(v263) = v262 {
+                            if let &SimpleAst::Or([v264, v265]) = v263 {
+                                if v46 == v264 {
+                                    let v266 = &C::lookup_value(ctx, v265);
+                                    if let Some(v267) = v266 {
+                                        if let &SimpleAst::Neg([v268]) = v267 {
+                                            let v269 = &C::any(ctx, v264);
+                                            let v270 = C::lookup_id(ctx, v269);
+                                            let v271 = &C::any(ctx, v260);
+                                            let v272 = C::lookup_id(ctx, v271);
+                                            let v273 = &C::any(ctx, v268);
+                                            let v274 = C::lookup_id(ctx, v273);
+                                            let v275 = &C::and(ctx, v272, v274);
+                                            let v276 = C::lookup_id(ctx, v275);
+                                            let v277 = &C::or(ctx, v270, v276);
+                                            let v278 = C::lookup_id(ctx, v277);
+                                            let v279 = &C::any(ctx, v260);
+                                            let v280 = C::lookup_id(ctx, v279);
+                                            let v281 = &C::xor(ctx, v278, v280);
+                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 539.
+                                            return Some(v281.clone());
                                         }
                                     }
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Or([v85, v86]) => {
-                        if let Some(v4) = v3 {
-                            if let &SimpleAst::Or([v87, v88]) = v4 {
-                                let v186 = &C::lookup_value(ctx, v88);
-                                if let Some(v187) = v186 {
-                                    if let &SimpleAst::Xor([v188, v189]) = v187 {
-                                        if v85 == v188 {
-                                            if v86 == v189 {
-                                                let v190 = &C::any(ctx, v85);
-                                                let v191 = C::lookup_id(ctx, v190);
-                                                let v192 = &C::any(ctx, v87);
-                                                let v193 = C::lookup_id(ctx, v192);
-                                                let v194 = &C::xor(ctx, v191, v193);
-                                                let v195 = C::lookup_id(ctx, v194);
-                                                let v196 = &C::any(ctx, v86);
-                                                let v197 = C::lookup_id(ctx, v196);
-                                                let v198 = &C::any(ctx, v87);
-                                                let v199 = C::lookup_id(ctx, v198);
-                                                let v200 = &C::xor(ctx, v197, v199);
-                                                let v201 = C::lookup_id(ctx, v200);
-                                                let v202 = &C::and(ctx, v195, v201);
-                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 411.
-                                                return Some(v202.clone());
-                                            }
-                                        }
-                                    }
-                                }
-                                if v86 == v88 {
-                                    let v89 = &C::any(ctx, v86);
-                                    let v90 = C::lookup_id(ctx, v89);
-                                    let v91 = &C::neg(ctx, v90);
-                                    let v92 = C::lookup_id(ctx, v91);
-                                    let v93 = &C::any(ctx, v85);
-                                    let v94 = C::lookup_id(ctx, v93);
-                                    let v95 = &C::any(ctx, v87);
-                                    let v96 = C::lookup_id(ctx, v95);
-                                    let v97 = &C::xor(ctx, v94, v96);
-                                    let v98 = C::lookup_id(ctx, v97);
-                                    let v99 = &C::and(ctx, v92, v98);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 369.
-                                    return Some(v99.clone());
+                    &SimpleAst::Or([v183, v184]) => {
+                        if let Some(v102) = v101 {
+                            if let &SimpleAst::Or([v185, v186]) = v102 {
+                                let v282 = &C::lookup_value(ctx, v186);
+                                if let Some(v283) = v282 {
+                                    if let &SimpleAst::Xor([v284, v285]) = v283 {
+                                        if v183 == v284 {
+                                            if v184 == v285 {
+                                                let v286 = &C::any(ctx, v183);
+                                                let v287 = C::lookup_id(ctx, v286);
+                                                let v288 = &C::any(ctx, v185);
+                                                let v289 = C::lookup_id(ctx, v288);
+                                                let v290 = &C::xor(ctx, v287, v289);
+                                                let v291 = C::lookup_id(ctx, v290);
+                                                let v292 = &C::any(ctx, v184);
+                                                let v293 = C::lookup_id(ctx, v292);
+                                                let v294 = &C::any(ctx, v185);
+                                                let v295 = C::lookup_id(ctx, v294);
+                                                let v296 = &C::xor(ctx, v293, v295);
+                                                let v297 = C::lookup_id(ctx, v296);
+                                                let v298 = &C::and(ctx, v291, v297);
+                                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 545.
+                                                return Some(v298.clone());
+                                            }
+                                        }
+                                    }
+                                }
+                                if v184 == v186 {
+                                    let v187 = &C::any(ctx, v184);
+                                    let v188 = C::lookup_id(ctx, v187);
+                                    let v189 = &C::neg(ctx, v188);
+                                    let v190 = C::lookup_id(ctx, v189);
+                                    let v191 = &C::any(ctx, v183);
+                                    let v192 = C::lookup_id(ctx, v191);
+                                    let v193 = &C::any(ctx, v185);
+                                    let v194 = C::lookup_id(ctx, v193);
+                                    let v195 = &C::xor(ctx, v192, v194);
+                                    let v196 = C::lookup_id(ctx, v195);
+                                    let v197 = &C::and(ctx, v190, v196);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 503.
+                                    return Some(v197.clone());
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Neg([v144]) => {
-                        if let Some(v4) = v3 {
-                            match v4 {
-                                &SimpleAst::Xor([v15, v16]) => {
-                                    let v203 = &C::lookup_value(ctx, v144);
-                                    if let Some(v204) = v203 {
-                                        if let &SimpleAst::Or([v205, v206]) = v204 {
-                                            let v207 = &C::lookup_value(ctx, v206);
-                                            if let Some(v208) = v207 {
-                                                if let &SimpleAst::Or([v209, v210]) = v208 {
-                                                    if v15 == v209 {
-                                                        if v16 == v210 {
-                                                            let v211 = &C::any(ctx, v209);
-                                                            let v212 = C::lookup_id(ctx, v211);
-                                                            let v213 = &C::any(ctx, v205);
-                                                            let v214 = C::lookup_id(ctx, v213);
-                                                            let v215 = &C::or(ctx, v212, v214);
-                                                            let v216 = C::lookup_id(ctx, v215);
-                                                            let v217 = &C::any(ctx, v210);
-                                                            let v218 = C::lookup_id(ctx, v217);
-                                                            let v219 = &C::neg(ctx, v218);
-                                                            let v220 = C::lookup_id(ctx, v219);
-                                                            let v221 = &C::and(ctx, v216, v220);
-                                                            let v222 = C::lookup_id(ctx, v221);
-                                                            let v223 = &C::any(ctx, v209);
-                                                            let v224 = C::lookup_id(ctx, v223);
-                                                            let v225 = &C::xor(ctx, v222, v224);
-                                                            let v226 = C::lookup_id(ctx, v225);
-                                                            let v227 = &C::neg(ctx, v226);
-                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 417.
-                                                            return Some(v227.clone());
+                    &SimpleAst::Neg([v240]) => {
+                        if let Some(v102) = v101 {
+                            match v102 {
+                                &SimpleAst::Xor([v113, v114]) => {
+                                    let v299 = &C::lookup_value(ctx, v240);
+                                    if let Some(v300) = v299 {
+                                        if let &SimpleAst::Or([v301, v302]) = v300 {
+                                            let v303 = &C::lookup_value(ctx, v302);
+                                            if let Some(v304) = v303 {
+                                                if let &SimpleAst::Or([v305, v306]) = v304 {
+                                                    if v113 == v305 {
+                                                        if v114 == v306 {
+                                                            let v307 = &C::any(ctx, v305);
+                                                            let v308 = C::lookup_id(ctx, v307);
+                                                            let v309 = &C::any(ctx, v301);
+                                                            let v310 = C::lookup_id(ctx, v309);
+                                                            let v311 = &C::or(ctx, v308, v310);
+                                                            let v312 = C::lookup_id(ctx, v311);
+                                                            let v313 = &C::any(ctx, v306);
+                                                            let v314 = C::lookup_id(ctx, v313);
+                                                            let v315 = &C::neg(ctx, v314);
+                                                            let v316 = C::lookup_id(ctx, v315);
+                                                            let v317 = &C::and(ctx, v312, v316);
+                                                            let v318 = C::lookup_id(ctx, v317);
+                                                            let v319 = &C::any(ctx, v305);
+                                                            let v320 = C::lookup_id(ctx, v319);
+                                                            let v321 = &C::xor(ctx, v318, v320);
+                                                            let v322 = C::lookup_id(ctx, v321);
+                                                            let v323 = &C::neg(ctx, v322);
+                                                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 551.
+                                                            return Some(v323.clone());
                                                         }
                                                     }
                                                 }
@@ -3213,14 +3264,14 @@ pub fn constructor_lower<C: Context>(
                                         }
                                     }
                                 }
-                                &SimpleAst::Neg([v5]) => {
-                                    let v145 = &C::any(ctx, v144);
-                                    let v146 = C::lookup_id(ctx, v145);
-                                    let v147 = &C::any(ctx, v5);
-                                    let v148 = C::lookup_id(ctx, v147);
-                                    let v149 = &C::xor(ctx, v146, v148);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 393.
-                                    return Some(v149.clone());
+                                &SimpleAst::Neg([v103]) => {
+                                    let v241 = &C::any(ctx, v240);
+                                    let v242 = C::lookup_id(ctx, v241);
+                                    let v243 = &C::any(ctx, v103);
+                                    let v244 = C::lookup_id(ctx, v243);
+                                    let v245 = &C::xor(ctx, v242, v244);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 527.
+                                    return Some(v245.clone());
                                 }
                                 _ => {}
                             }
@@ -3229,183 +3280,299 @@ pub fn constructor_lower<C: Context>(
                     _ => {}
                 }
             }
-            if let Some(v4) = v3 {
-                match v4 {
-                    &SimpleAst::And([v43, v44]) => {
-                        let v54 = C::rule_fold_const_or_precondition(ctx, v1, v43);
-                        if let Some(v55) = v54 {
-                            let v8 = &C::any(ctx, v1);
-                            let v9 = C::lookup_id(ctx, v8);
-                            let v48 = &C::any(ctx, v44);
-                            let v49 = C::lookup_id(ctx, v48);
-                            let v50 = &C::or(ctx, v9, v49);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 350.
-                            return Some(v50.clone());
-                        }
-                        if v1 == v43 {
-                            let v8 = &C::any(ctx, v1);
-                            let v9 = C::lookup_id(ctx, v8);
-                            let v48 = &C::any(ctx, v44);
-                            let v49 = C::lookup_id(ctx, v48);
-                            let v51 = &C::neg(ctx, v49);
-                            let v52 = C::lookup_id(ctx, v51);
-                            let v53 = &C::and(ctx, v9, v52);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 344.
-                            return Some(v53.clone());
-                        }
-                        let v45 = &C::lookup_value(ctx, v43);
-                        if let Some(v46) = v45 {
-                            if let &SimpleAst::Neg([v47]) = v46 {
-                                if v1 == v47 {
-                                    let v8 = &C::any(ctx, v1);
-                                    let v9 = C::lookup_id(ctx, v8);
-                                    let v48 = &C::any(ctx, v44);
-                                    let v49 = C::lookup_id(ctx, v48);
-                                    let v50 = &C::or(ctx, v9, v49);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 338.
-                                    return Some(v50.clone());
+            if let Some(v102) = v101 {
+                match v102 {
+                    &SimpleAst::And([v141, v142]) => {
+                        let v152 = C::rule_fold_const_or_precondition(ctx, v45, v141);
+                        if let Some(v153) = v152 {
+                            let v106 = &C::any(ctx, v45);
+                            let v107 = C::lookup_id(ctx, v106);
+                            let v146 = &C::any(ctx, v142);
+                            let v147 = C::lookup_id(ctx, v146);
+                            let v148 = &C::or(ctx, v107, v147);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 484.
+                            return Some(v148.clone());
+                        }
+                        if v45 == v141 {
+                            let v106 = &C::any(ctx, v45);
+                            let v107 = C::lookup_id(ctx, v106);
+                            let v146 = &C::any(ctx, v142);
+                            let v147 = C::lookup_id(ctx, v146);
+                            let v149 = &C::neg(ctx, v147);
+                            let v150 = C::lookup_id(ctx, v149);
+                            let v151 = &C::and(ctx, v107, v150);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 478.
+                            return Some(v151.clone());
+                        }
+                        let v143 = &C::lookup_value(ctx, v141);
+                        if let Some(v144) = v143 {
+                            if let &SimpleAst::Neg([v145]) = v144 {
+                                if v45 == v145 {
+                                    let v106 = &C::any(ctx, v45);
+                                    let v107 = C::lookup_id(ctx, v106);
+                                    let v146 = &C::any(ctx, v142);
+                                    let v147 = C::lookup_id(ctx, v146);
+                                    let v148 = &C::or(ctx, v107, v147);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 472.
+                                    return Some(v148.clone());
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Xor([v15, v16]) => {
-                        let v17 = &C::lookup_value(ctx, v15);
-                        if let Some(v18) = v17 {
-                            if let &SimpleAst::Neg([v19]) = v18 {
-                                let v20 = C::rule_fold_nested_neg_xor_precondition(ctx, v1);
-                                if let Some(v21) = v20 {
-                                    let v8 = &C::any(ctx, v1);
-                                    let v9 = C::lookup_id(ctx, v8);
-                                    let v10 = &C::neg(ctx, v9);
-                                    let v11 = C::lookup_id(ctx, v10);
-                                    let v22 = &C::any(ctx, v19);
-                                    let v23 = C::lookup_id(ctx, v22);
-                                    let v24 = &C::any(ctx, v16);
-                                    let v25 = C::lookup_id(ctx, v24);
-                                    let v26 = &C::xor(ctx, v23, v25);
-                                    let v27 = C::lookup_id(ctx, v26);
-                                    let v28 = &C::xor(ctx, v11, v27);
-                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 324.
-                                    return Some(v28.clone());
+                    &SimpleAst::Xor([v113, v114]) => {
+                        let v115 = &C::lookup_value(ctx, v113);
+                        if let Some(v116) = v115 {
+                            if let &SimpleAst::Neg([v117]) = v116 {
+                                let v118 = C::rule_fold_nested_neg_xor_precondition(ctx, v45);
+                                if let Some(v119) = v118 {
+                                    let v106 = &C::any(ctx, v45);
+                                    let v107 = C::lookup_id(ctx, v106);
+                                    let v108 = &C::neg(ctx, v107);
+                                    let v109 = C::lookup_id(ctx, v108);
+                                    let v120 = &C::any(ctx, v117);
+                                    let v121 = C::lookup_id(ctx, v120);
+                                    let v122 = &C::any(ctx, v114);
+                                    let v123 = C::lookup_id(ctx, v122);
+                                    let v124 = &C::xor(ctx, v121, v123);
+                                    let v125 = C::lookup_id(ctx, v124);
+                                    let v126 = &C::xor(ctx, v109, v125);
+                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 458.
+                                    return Some(v126.clone());
                                 }
                             }
                         }
                     }
-                    &SimpleAst::Neg([v5]) => {
-                        let v6 = C::rule_fold_neg_xor_precondition(ctx, v1);
-                        if let Some(v7) = v6 {
-                            let v8 = &C::any(ctx, v1);
-                            let v9 = C::lookup_id(ctx, v8);
-                            let v10 = &C::neg(ctx, v9);
-                            let v11 = C::lookup_id(ctx, v10);
-                            let v12 = &C::any(ctx, v5);
-                            let v13 = C::lookup_id(ctx, v12);
-                            let v14 = &C::xor(ctx, v11, v13);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 317.
-                            return Some(v14.clone());
+                    &SimpleAst::Neg([v103]) => {
+                        let v104 = C::rule_fold_neg_xor_precondition(ctx, v45);
+                        if let Some(v105) = v104 {
+                            let v106 = &C::any(ctx, v45);
+                            let v107 = C::lookup_id(ctx, v106);
+                            let v108 = &C::neg(ctx, v107);
+                            let v109 = C::lookup_id(ctx, v108);
+                            let v110 = &C::any(ctx, v103);
+                            let v111 = C::lookup_id(ctx, v110);
+                            let v112 = &C::xor(ctx, v109, v111);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 451.
+                            return Some(v112.clone());
                         }
                     }
                     _ => {}
                 }
             }
+            let v47 = C::rotate_source(ctx, v45, v46);
+            if let Some(v48) = v47 {
+                let v49 = C::rotate_amount(ctx, v45, v46);
+                if let Some(v50) = v49 {
+                    let v51 = C::get_width(ctx, v48);
+                    let v52 = &C::constant(ctx, v50, v51);
+                    let v53 = C::lookup_id(ctx, v52);
+                    let v54 = &C::rotl(ctx, v48, v53);
+                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 230.
+                    return Some(v54.clone());
+                }
+            }
         }
-        &SimpleAst::Neg([v29]) => {
-            let v30 = &C::lookup_value(ctx, v29);
-            if let Some(v31) = v30 {
-                match v31 {
-                    &SimpleAst::And([v100, v101]) => {
-                        let v102 = &C::lookup_value(ctx, v100);
-                        if let Some(v103) = v102 {
-                            if let &SimpleAst::Neg([v104]) = v103 {
-                                let v938 = &C::lookup_value(ctx, v101);
-                                if let Some(v939) = v938 {
-                                    if let &SimpleAst::Neg([v940]) = v939 {
-                                        let v941 = &C::any(ctx, v940);
-                                        let v942 = C::lookup_id(ctx, v941);
-                                        let v943 = &C::any(ctx, v104);
-                                        let v944 = C::lookup_id(ctx, v943);
-                                        let v945 = &C::or(ctx, v942, v944);
-                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 965.
-                                        return Some(v945.clone());
-                                    }
-                                }
-                                let v105 = &C::any(ctx, v104);
-                                let v106 = C::lookup_id(ctx, v105);
-                                let v107 = &C::any(ctx, v101);
-                                let v108 = C::lookup_id(ctx, v107);
-                                let v109 = &C::neg(ctx, v108);
-                                let v110 = C::lookup_id(ctx, v109);
-                                let v111 = &C::or(ctx, v106, v110);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 375.
-                                return Some(v111.clone());
+        &SimpleAst::Neg([v127]) => {
+            let v128 = &C::lookup_value(ctx, v127);
+            if let Some(v129) = v128 {
+                match v129 {
+                    &SimpleAst::And([v198, v199]) => {
+                        let v200 = &C::lookup_value(ctx, v198);
+                        if let Some(v201) = v200 {
+                            if let &SimpleAst::Neg([v202]) = v201 {
+                                let v1031 = &C::lookup_value(ctx, v199);
+                                if let Some(v1032) = v1031 {
+                                    if let &SimpleAst::Neg([v1033]) = v1032 {
+                                        let v1034 = &C::any(ctx, v1033);
+                                        let v1035 = C::lookup_id(ctx, v1034);
+                                        let v1036 = &C::any(ctx, v202);
+                                        let v1037 = C::lookup_id(ctx, v1036);
+                                        let v1038 = &C::or(ctx, v1035, v1037);
+                                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 1099.
+                                        return Some(v1038.clone());
+                                    }
+                                }
+                                let v203 = &C::any(ctx, v202);
+                                let v204 = C::lookup_id(ctx, v203);
+                                let v205 = &C::any(ctx, v199);
+                                let v206 = C::lookup_id(ctx, v205);
+                                let v207 = &C::neg(ctx, v206);
+                                let v208 = C::lookup_id(ctx, v207);
+                                let v209 = &C::or(ctx, v204, v208);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 509.
+                                return Some(v209.clone());
                             }
                         }
                     }
-                    &SimpleAst::Or([v71, v72]) => {
-                        let v510 = &C::lookup_value(ctx, v71);
-                        if let Some(v511) = v510 {
-                            if let &SimpleAst::Neg([v512]) = v511 {
-                                let v513 = &C::any(ctx, v512);
-                                let v514 = C::lookup_id(ctx, v513);
-                                let v515 = &C::any(ctx, v72);
-                                let v516 = C::lookup_id(ctx, v515);
-                                let v517 = &C::neg(ctx, v516);
-                                let v518 = C::lookup_id(ctx, v517);
-                                let v519 = &C::and(ctx, v514, v518);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 669.
-                                return Some(v519.clone());
+                    &SimpleAst::Or([v169, v170]) => {
+                        let v603 = &C::lookup_value(ctx, v169);
+                        if let Some(v604) = v603 {
+                            if let &SimpleAst::Neg([v605]) = v604 {
+                                let v606 = &C::any(ctx, v605);
+                                let v607 = C::lookup_id(ctx, v606);
+                                let v608 = &C::any(ctx, v170);
+                                let v609 = C::lookup_id(ctx, v608);
+                                let v610 = &C::neg(ctx, v609);
+                                let v611 = C::lookup_id(ctx, v610);
+                                let v612 = &C::and(ctx, v607, v611);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 803.
+                                return Some(v612.clone());
                             }
                         }
-                        let v73 = &C::lookup_value(ctx, v72);
-                        if let Some(v74) = v73 {
-                            if let &SimpleAst::Neg([v75]) = v74 {
-                                let v76 = &C::any(ctx, v71);
-                                let v77 = C::lookup_id(ctx, v76);
-                                let v78 = &C::neg(ctx, v77);
-                                let v79 = C::lookup_id(ctx, v78);
-                                let v80 = &C::any(ctx, v75);
-                                let v81 = C::lookup_id(ctx, v80);
-                                let v82 = &C::and(ctx, v79, v81);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 363.
-                                return Some(v82.clone());
+                        let v171 = &C::lookup_value(ctx, v170);
+                        if let Some(v172) = v171 {
+                            if let &SimpleAst::Neg([v173]) = v172 {
+                                let v174 = &C::any(ctx, v169);
+                                let v175 = C::lookup_id(ctx, v174);
+                                let v176 = &C::neg(ctx, v175);
+                                let v177 = C::lookup_id(ctx, v176);
+                                let v178 = &C::any(ctx, v173);
+                                let v179 = C::lookup_id(ctx, v178);
+                                let v180 = &C::and(ctx, v177, v179);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 497.
+                                return Some(v180.clone());
                             }
                         }
                     }
-                    &SimpleAst::Xor([v32, v33]) => {
-                        let v520 = &C::lookup_value(ctx, v32);
-                        if let Some(v521) = v520 {
-                            if let &SimpleAst::Neg([v522]) = v521 {
-                                let v523 = &C::any(ctx, v522);
-                                let v524 = C::lookup_id(ctx, v523);
-                                let v525 = &C::any(ctx, v33);
-                                let v526 = C::lookup_id(ctx, v525);
-                                let v527 = &C::xor(ctx, v524, v526);
-                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 675.
-                                return Some(v527.clone());
+                    &SimpleAst::Xor([v130, v131]) => {
+                        let v613 = &C::lookup_value(ctx, v130);
+                        if let Some(v614) = v613 {
+                            if let &SimpleAst::Neg([v615]) = v614 {
+                                let v616 = &C::any(ctx, v615);
+                                let v617 = C::lookup_id(ctx, v616);
+                                let v618 = &C::any(ctx, v131);
+                                let v619 = C::lookup_id(ctx, v618);
+                                let v620 = &C::xor(ctx, v617, v619);
+                                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 809.
+                                return Some(v620.clone());
                             }
                         }
-                        let v34 = C::rule_fold_neg_xor_xor_precondition(ctx, v32);
-                        if let Some(v35) = v34 {
-                            let v36 = &C::any(ctx, v32);
-                            let v37 = C::lookup_id(ctx, v36);
-                            let v38 = &C::neg(ctx, v37);
-                            let v39 = C::lookup_id(ctx, v38);
-                            let v40 = &C::any(ctx, v33);
-                            let v41 = C::lookup_id(ctx, v40);
-                            let v42 = &C::xor(ctx, v39, v41);
-                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 331.
-                            return Some(v42.clone());
+                        let v132 = C::rule_fold_neg_xor_xor_precondition(ctx, v130);
+                        if let Some(v133) = v132 {
+                            let v134 = &C::any(ctx, v130);
+                            let v135 = C::lookup_id(ctx, v134);
+                            let v136 = &C::neg(ctx, v135);
+                            let v137 = C::lookup_id(ctx, v136);
+                            let v138 = &C::any(ctx, v131);
+                            let v139 = C::lookup_id(ctx, v138);
+                            let v140 = &C::xor(ctx, v137, v139);
+                            // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 465.
+                            return Some(v140.clone());
                         }
                     }
-                    &SimpleAst::Neg([v398]) => {
-                        let v399 = &C::any(ctx, v398);
-                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 603.
-                        return Some(v399.clone());
+                    &SimpleAst::Neg([v491]) => {
+                        let v492 = &C::any(ctx, v491);
+                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 737.
+                        return Some(v492.clone());
                     }
                     _ => {}
                 }
             }
         }
+        &SimpleAst::Shl([v1, v2]) => {
+            let v3 = C::shl_to_mul_factor(ctx, v1, v2);
+            if let Some(v4) = v3 {
+                let v5 = C::get_width(ctx, v1);
+                let v6 = &C::constant(ctx, v4, v5);
+                let v7 = C::lookup_id(ctx, v6);
+                let v8 = &C::mul(ctx, v7, v1);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 192.
+                return Some(v8.clone());
+            }
+        }
+        &SimpleAst::Udiv([v9, v10]) => {
+            let v11 = C::pow2_log2(ctx, v10);
+            if let Some(v12) = v11 {
+                let v13 = C::get_width(ctx, v9);
+                let v14 = &C::constant(ctx, v12, v13);
+                let v15 = C::lookup_id(ctx, v14);
+                let v16 = &C::lshr(ctx, v9, v15);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 204.
+                return Some(v16.clone());
+            }
+        }
+        &SimpleAst::Urem([v17, v18]) => {
+            let v19 = C::pow2_low_mask(ctx, v18);
+            if let Some(v20) = v19 {
+                let v21 = C::get_width(ctx, v17);
+                let v22 = &C::constant(ctx, v20, v21);
+                let v23 = C::lookup_id(ctx, v22);
+                let v24 = &C::and(ctx, v23, v17);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 208.
+                return Some(v24.clone());
+            }
+        }
+        &SimpleAst::Popcount([v58]) => {
+            let v59 = &C::lookup_value(ctx, v58);
+            if let Some(v60) = v59 {
+                if let &SimpleAst::And([v61, v62]) = v60 {
+                    let v63 = &C::lookup_value(ctx, v61);
+                    if let Some(v64) = v63 {
+                        if let &SimpleAst::Neg([v65]) = v64 {
+                            let v66 = &C::lookup_value(ctx, v62);
+                            if let Some(v67) = v66 {
+                                if let &SimpleAst::Add([v68, v69]) = v67 {
+                                    if v65 == v69 {
+                                        let v70 = &C::lookup_value(ctx, v68);
+                                        if let Some(v71) = v70 {
+                                            if let &SimpleAst::Constant {
+                                                c: v72,
+                                                width: v73,
+                                            } = v71 {
+                                                let v75 = C::is_constant_modulo(ctx, v72, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, v73);
+                                                if let Some(v76) = v75 {
+                                                    let v77 = &C::cttz(ctx, v65);
+                                                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 247.
+                                                    return Some(v77.clone());
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        &SimpleAst::Sext([v78, v79]) => {
+            let v80 = &C::lookup_value(ctx, v78);
+            if let Some(v81) = v80 {
+                if let &SimpleAst::Sext([v82, v83]) = v81 {
+                    let v84 = &C::sext(ctx, v82, v79);
+                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 254.
+                    return Some(v84.clone());
+                }
+            }
+            let v85 = C::is_sign_bit_zero(ctx, v78);
+            if let Some(v86) = v85 {
+                let v87 = &C::zext(ctx, v78, v79);
+                // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 261.
+                return Some(v87.clone());
+            }
+        }
+        &SimpleAst::Trunc([v88, v89]) => {
+            let v90 = &C::lookup_value(ctx, v88);
+            if let Some(v91) = v90 {
+                if let &SimpleAst::Sext([v92, v93]) = v91 {
+                    let v94 = C::cast_is_noop(ctx, v92, v89);
+                    if let Some(v95) = v94 {
+                        let v96 = &C::any(ctx, v92);
+                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 271.
+                        return Some(v96.clone());
+                    }
+                    let v97 = C::cast_is_narrowing(ctx, v92, v89);
+                    if let Some(v98) = v97 {
+                        let v99 = &C::trunc(ctx, v92, v89);
+                        // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 275.
+                        return Some(v99.clone());
+                    }
+                    let v100 = &C::sext(ctx, v92, v89);
+                    // Rule at C:\Users\colton\source\repos\Simplifier\EqSat\src\dsl\rules.isle line 279.
+                    return Some(v100.clone());
+                }
+            }
+        }
         _ => {}
     }
     None
@@ -0,0 +1,246 @@
+// Memoised evaluator for the DAGs stored in an `Arena`.
+//
+// The nodes reachable from a root are flattened into post order once, using an explicit stack rather than recursion,
+// so that deep expressions cannot overflow the stack and shared nodes are evaluated exactly once per set of inputs.
+// Inputs are passed as a dense slice, where `inputs[i]` holds the value of the `i`th variable given to `Evaluator::new`.
+use ahash::{AHashMap, AHashSet};
+use egg::Language;
+
+use crate::simple_ast::{
+    ashr_const, ctlz_const, cttz_const, get_modulo_mask, lshr_const, popcount_const, rotl_const,
+    rotr_const, sdiv_const, shl_const, sign_extend, srem_const, udiv_const, urem_const, AstIdx,
+    Context, INodeUtil, Predicate, SimpleAst, pow_const,
+};
+
+struct EvalNode {
+    ast: SimpleAst,
+    width: u8,
+    // Width of the first operand, which determines the semantics of shifts, divisions, comparisons, etc.
+    operand_width: u8,
+    // Indices of the operands within the post order, or the input index for symbols.
+    operands: [u32; 3],
+}
+
+pub struct Evaluator {
+    // Nodes reachable from the root in post order, so the root comes last.
+    nodes: Vec<EvalNode>,
+    values: Vec<u128>,
+    num_vars: usize,
+}
+
+impl Evaluator {
+    // Panics if the AST contains a symbol that is not present in `vars`.
+    pub fn new(ctx: &Context, root: AstIdx, vars: &[AstIdx]) -> Self {
+        let var_to_input: AHashMap<AstIdx, u32> = vars
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (*v, i as u32))
+            .collect();
+
+        let mut nodes = Vec::new();
+        let mut slots: AHashMap<AstIdx, u32> = AHashMap::new();
+        for idx in post_order(ctx, root) {
+            let ast = ctx.arena.get_node(idx);
+            let mut operands = [0; 3];
+            if let SimpleAst::Symbol { id, .. } = ast {
+                operands[0] = *var_to_input.get(&idx).unwrap_or_else(|| {
+                    panic!("No input was provided for symbol {}", ctx.arena.get_symbol_name(*id))
+                });
+            } else {
+                for (i, child) in ast.children().iter().enumerate() {
+                    operands[i] = slots[child];
+                }
+            }
+
+            let operand_width = match ast.children().first() {
+                Some(child) => ctx.arena.get_width(*child),
+                None => ctx.arena.get_width(idx),
+            };
+
+            slots.insert(idx, nodes.len() as u32);
+            nodes.push(EvalNode {
+                ast: ast.clone(),
+                width: ctx.arena.get_width(idx),
+                operand_width,
+                operands,
+            });
+        }
+
+        let values = vec![0; nodes.len()];
+        return Evaluator {
+            nodes,
+            values,
+            num_vars: vars.len(),
+        };
+    }
+
+    pub fn eval(&mut self, inputs: &[u128]) -> u128 {
+        assert_eq!(inputs.len(), self.num_vars);
+
+        for i in 0..self.nodes.len() {
+            let node = &self.nodes[i];
+            let v = |j: usize| self.values[node.operands[j] as usize];
+            let w = node.operand_width;
+            let r = match &node.ast {
+                SimpleAst::Add(_) => v(0).wrapping_add(v(1)),
+                SimpleAst::Mul(_) => v(0).wrapping_mul(v(1)),
+                SimpleAst::Pow(_) => pow_const(v(0), v(1)),
+                SimpleAst::And(_) => v(0) & v(1),
+                SimpleAst::Or(_) => v(0) | v(1),
+                SimpleAst::Xor(_) => v(0) ^ v(1),
+                SimpleAst::Lshr(_) => lshr_const(v(0), v(1), w),
+                SimpleAst::Shl(_) => shl_const(v(0), v(1), w),
+                SimpleAst::Ashr(_) => ashr_const(v(0), v(1), w),
+                SimpleAst::Udiv(_) => udiv_const(v(0), v(1), w),
+                SimpleAst::Urem(_) => urem_const(v(0), v(1), w),
+                SimpleAst::Sdiv(_) => sdiv_const(v(0), v(1), w),
+                SimpleAst::Srem(_) => srem_const(v(0), v(1), w),
+                SimpleAst::Rotl(_) => rotl_const(v(0), v(1), w),
+                SimpleAst::Rotr(_) => rotr_const(v(0), v(1), w),
+                SimpleAst::Neg(_) => !v(0),
+                SimpleAst::Popcount(_) => popcount_const(v(0), w),
+                SimpleAst::Ctlz(_) => ctlz_const(v(0), w),
+                SimpleAst::Cttz(_) => cttz_const(v(0), w),
+                SimpleAst::Constant { c, .. } => *c,
+                SimpleAst::Symbol { .. } => inputs[node.operands[0] as usize],
+                // Operands are always reduced modulo their width, so truncation is handled by the final mask.
+                SimpleAst::Zext(_) | SimpleAst::Trunc(_) => v(0),
+                SimpleAst::Sext(_) => sign_extend(v(0), w) as u128,
+                SimpleAst::ICmp { predicate, .. } => cmp(*predicate, v(0), v(1), w) as u128,
+                SimpleAst::Select { .. } => {
+                    if v(0) != 0 {
+                        v(1)
+                    } else {
+                        v(2)
+                    }
+                }
+                SimpleAst::Extract(_) => v(0) >> v(2),
+                SimpleAst::Concat(_) => {
+                    let low_width = node.width - w;
+                    (v(0) << low_width) | v(1)
+                }
+                SimpleAst::Carry(_) => (v(0) & v(1)) | (v(0) & v(2)) | (v(1) & v(2)),
+            };
+
+            self.values[i] = r & get_modulo_mask(node.width);
+        }
+
+        return self.values[self.nodes.len() - 1];
+    }
+}
+
+// Returns the nodes reachable from `root` in post order, visiting each shared node only once.
+pub(crate) fn post_order(ctx: &Context, root: AstIdx) -> Vec<AstIdx> {
+    let mut order = Vec::new();
+    let mut visited: AHashSet<AstIdx> = AHashSet::new();
+    let mut stack = vec![(root, false)];
+    while let Some((idx, operands_visited)) = stack.pop() {
+        if operands_visited {
+            order.push(idx);
+            continue;
+        }
+
+        if !visited.insert(idx) {
+            continue;
+        }
+
+        stack.push((idx, true));
+        for child in ctx.arena.get_node(idx).children().iter().rev() {
+            if !visited.contains(child) {
+                stack.push((*child, false));
+            }
+        }
+    }
+
+    return order;
+}
+
+pub(crate) fn cmp(pred: Predicate, a: u128, b: u128, width: u8) -> bool {
+    let sa = sign_extend(a, width);
+    let sb = sign_extend(b, width);
+    match pred {
+        Predicate::Eq => a == b,
+        Predicate::Ne => a != b,
+        Predicate::Ugt => a > b,
+        Predicate::Uge => a >= b,
+        Predicate::Ult => a < b,
+        Predicate::Ule => a <= b,
+        Predicate::Sgt => sa > sb,
+        Predicate::Sge => sa >= sb,
+        Predicate::Slt => sa < sb,
+        Predicate::Sle => sa <= sb,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast_parser::parse_ast, simple_ast::Arena};
+
+    use super::*;
+
+    fn eval(s: &str, vars: &[(&str, u8, u128)]) -> u128 {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let idx = parse_ast(&mut ctx, s, 64).unwrap();
+        let (vars, inputs): (Vec<_>, Vec<_>) = vars
+            .iter()
+            .map(|(name, width, value)| (ctx.arena.symbol_with_name(name.to_string(), *width), *value))
+            .unzip();
+        return Evaluator::new(&ctx, idx, &vars).eval(&inputs);
+    }
+
+    #[test]
+    fn eval_operators() {
+        assert_eq!(eval("x ** 3", &[("x", 64, 5)]), 125);
+        assert_eq!(eval("x:i8 ** 2", &[("x", 8, 20)]), 144);
+        assert_eq!(eval("x >> 70", &[("x", 64, u64::MAX as u128)]), 0);
+        assert_eq!(eval("x:i8 >>s y:i8", &[("x", 8, 0x80), ("y", 8, 9)]), 0xFF);
+        assert_eq!(eval("x:i16[11:4]", &[("x", 16, 0xABCD)]), 0xBC);
+        assert_eq!(eval("x:i8 ++ y:i4", &[("x", 8, 0xAB), ("y", 4, 0xC)]), 0xABC);
+        assert_eq!(eval("maj(x:i4, y:i4, z:i4)", &[("x", 4, 0b1100), ("y", 4, 0b1010), ("z", 4, 0b0110)]), 0b1110);
+        assert_eq!(eval("x:i8 <s 1 ? 1:i8 : 2", &[("x", 8, 0x80)]), 1);
+        assert_eq!(eval("x:i8 sx i16", &[("x", 8, 0x80)]), 0xFF80);
+
+        // Inputs are reduced modulo the width of their symbol.
+        assert_eq!(eval("x:i8 zx i64", &[("x", 8, 0x1FF)]), 0xFF);
+    }
+
+    #[test]
+    fn eval_wide_operators() {
+        let max = u64::MAX as u128;
+        let full = eval("x zx i128 * y zx i128", &[("x", 64, max), ("y", 64, max)]);
+        assert_eq!(full, max * max);
+        assert_eq!(eval("x ++ y", &[("x", 64, 0x1234), ("y", 64, 0x5678)]), 0x1234 << 64 | 0x5678);
+        assert_eq!(eval("(x ++ y)[127:64]", &[("x", 64, 0x1234), ("y", 64, 0x5678)]), 0x1234);
+        assert_eq!(eval("x:i128 >>s 127", &[("x", 128, 1 << 127)]), u128::MAX);
+        assert_eq!(eval("x sx i128", &[("x", 64, max)]), u128::MAX);
+        assert_eq!(eval("x:i128 <s 0:i128 ? 1 : 2", &[("x", 128, u128::MAX)]), 1);
+        assert_eq!(eval("ctlz(x:i128)", &[("x", 128, 1)]), 127);
+        assert_eq!(eval("x:i96 + 1:i96", &[("x", 96, u128::MAX)]), 0);
+    }
+
+    #[test]
+    fn eval_deep_dag() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        // Each level references the previous one twice, so a naive tree walk would take 2**n steps.
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let y = ctx.arena.symbol_with_name("y".to_string(), 64);
+        let not_y = ctx.arena.neg(y);
+        let one = ctx.arena.constant(1, 64);
+        let mut idx = x;
+        for _ in 0..100000 {
+            let sum = ctx.arena.add(idx, one);
+            let a = ctx.arena.and(sum, y);
+            let b = ctx.arena.and(sum, not_y);
+            idx = ctx.arena.add(a, b);
+        }
+
+        let mut evaluator = Evaluator::new(&ctx, idx, &[x, y]);
+        assert_eq!(evaluator.eval(&[7, 0x1234]), 100007);
+        assert_eq!(evaluator.eval(&[u64::MAX as u128, 3]), 99999);
+    }
+}
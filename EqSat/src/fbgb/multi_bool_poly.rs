@@ -4,6 +4,7 @@ use crate::fbgb::bool_poly::BoolPoly;
 use crate::fbgb::transforms::{anf_eliminate_var, anf_filter_by_var, anf_multiply_by_var, anf_negate_many, fast_anf_transform, anf_create_variable_table};
 use crate::fbgb::npn::*;
 use core::ffi::c_void;
+use crate::ffi::{self, FfiError, FfiResult, FfiStatus};
 use std::cmp::Ordering;
 
 
@@ -288,167 +289,225 @@ macro_rules! dispatch_n {
             }};
         }
         match $n {
-            1  => __arm!(1),
-            2  => __arm!(2),
-            3  => __arm!(3),
-            4  => __arm!(4),
-            5  => __arm!(5),
-            6  => __arm!(6),
-            7  => __arm!(7),
-            8  => __arm!(8),
-            9  => __arm!(9),
-            10 => __arm!(10),
-            11 => __arm!(11),
-            12 => __arm!(12),
-            13 => __arm!(13),
-            14 => __arm!(14),
-            15 => __arm!(15),
-            16 => __arm!(16),
-            other => panic!(
+            1  => Ok(__arm!(1)),
+            2  => Ok(__arm!(2)),
+            3  => Ok(__arm!(3)),
+            4  => Ok(__arm!(4)),
+            5  => Ok(__arm!(5)),
+            6  => Ok(__arm!(6)),
+            7  => Ok(__arm!(7)),
+            8  => Ok(__arm!(8)),
+            9  => Ok(__arm!(9)),
+            10 => Ok(__arm!(10)),
+            11 => Ok(__arm!(11)),
+            12 => Ok(__arm!(12)),
+            13 => Ok(__arm!(13)),
+            14 => Ok(__arm!(14)),
+            15 => Ok(__arm!(15)),
+            16 => Ok(__arm!(16)),
+            other => Err(FfiError::invalid(format!(
                 "Got truth table with {} variables, expected integer between 1 and 16",
                 other
-            ),
+            ))),
         }
     }};
 }
 
 #[inline]
-unsafe fn as_mut<'a, const N: usize>(ptr: *mut c_void) -> &'a mut MultiBoolPoly<N>
+unsafe fn as_mut<'a, const N: usize>(ptr: *mut c_void) -> FfiResult<&'a mut MultiBoolPoly<N>>
 where
     [(); (1 << N) / 64 + ((1 << N) % 64 != 0) as usize]: Sized,
 {
-    &mut *(ptr as *mut MultiBoolPoly<N>)
+    ffi::non_null(ptr as *mut MultiBoolPoly<N>, "polynomial")
 }
 
 #[inline]
-unsafe fn as_ref<'a, const N: usize>(ptr: *const c_void) -> &'a MultiBoolPoly<N>
+unsafe fn as_ref<'a, const N: usize>(ptr: *const c_void) -> FfiResult<&'a MultiBoolPoly<N>>
 where
     [(); (1 << N) / 64 + ((1 << N) % 64 != 0) as usize]: Sized,
 {
-    &*(ptr as *const MultiBoolPoly<N>)
+    ffi::non_null_ref(ptr as *const MultiBoolPoly<N>, "polynomial")
+}
+
+fn check_var_index(var_index: u32, num_vars: usize) -> FfiResult<()> {
+    if var_index as usize >= num_vars {
+        return Err(FfiError::invalid(format!("variable {} is out of range for {} variables", var_index, num_vars)));
+    }
+    Ok(())
+}
+
+// The coefficients are stored as 64-bit integers.
+fn check_bit_width(bit_width: u8) -> FfiResult<u8> {
+    if bit_width == 0 || bit_width > 64 {
+        return Err(FfiError::invalid(format!("invalid bit width {}, expected 1 to 64", bit_width)));
+    }
+    Ok(bit_width)
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn CreateBoolPoly(num_vars: u8, bit_width: u8) -> *mut c_void {
-    dispatch_n!(num_vars, |N| {
-        Box::into_raw(Box::new(MultiBoolPoly::<N>::new(bit_width))) as *mut c_void
+    ffi::guard(|| {
+        dispatch_n!(num_vars, |N| {
+            Box::into_raw(Box::new(MultiBoolPoly::<N>::new(check_bit_width(bit_width)?))) as *mut c_void
+        })
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn CreateBoolPolyFromVec(num_vars: u8, bit_width: u8, vec_ptr: *const u64) -> *mut c_void {
-    dispatch_n!(num_vars, |N| {
-        let mut mpb = MultiBoolPoly::<N>::new(bit_width);
-        for i in 0..(bit_width as usize) {
-            let mut bp = BoolPoly::<N>::new();
-            for j in 0..BoolPoly::<N>::WORD_COUNT {
-                bp.value[j] = *vec_ptr.add(i * BoolPoly::<N>::WORD_COUNT + j);
-            }
-            mpb.value[i] = bp;
+    ffi::guard(|| {
+        check_bit_width(bit_width)?;
+        dispatch_n!(num_vars, |N| {
+            let vec = ffi::slice(vec_ptr, bit_width as usize * BoolPoly::<N>::WORD_COUNT, "vec_ptr")?;
+            let mut mpb = MultiBoolPoly::<N>::new(bit_width);
+            for i in 0..(bit_width as usize) {
+                let mut bp = BoolPoly::<N>::new();
+                for j in 0..BoolPoly::<N>::WORD_COUNT {
+                    bp.value[j] = vec[i * BoolPoly::<N>::WORD_COUNT + j];
+                }
+                mpb.value[i] = bp;
 
-        }
-        Box::into_raw(Box::new(mpb)) as *mut c_void
+            }
+            Box::into_raw(Box::new(mpb)) as *mut c_void
+        })
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn CreateBoolPolyFromVariable(num_vars: u8, bit_width: u8, var_index: u32) -> *mut c_void {
-    dispatch_n!(num_vars, |N| {
-        let mut mpb = MultiBoolPoly::<N>::new(bit_width);
-        for i in 0..(bit_width as usize) {
-            mpb.value[i] = anf_create_variable_table(var_index as usize);
+    ffi::guard(|| {
+        dispatch_n!(num_vars, |N| {
+            check_var_index(var_index, N)?;
+            let mut mpb = MultiBoolPoly::<N>::new(check_bit_width(bit_width)?);
+            for i in 0..(bit_width as usize) {
+                mpb.value[i] = anf_create_variable_table(var_index as usize);
 
-        }
-        Box::into_raw(Box::new(mpb)) as *mut c_void
+            }
+            Box::into_raw(Box::new(mpb)) as *mut c_void
+        })
     })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn FreeBoolPoly(n: u8, ptr: *mut c_void) {
-    dispatch_n!(n, |N| {
-        drop(Box::from_raw(ptr as *mut MultiBoolPoly<N>));
+pub unsafe extern "C" fn FreeBoolPoly(n: u8, ptr: *mut c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+            drop(Box::from_raw(as_mut::<N>(ptr)? as *mut MultiBoolPoly<N>));
+        })
     })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyAnd(n: u8, lhs: *mut c_void, rhs: *const c_void) {
-    dispatch_n!(n, |N| { as_mut::<N>(lhs).and(as_ref::<N>(rhs)); })
+pub unsafe extern "C" fn BoolPolyAnd(n: u8, lhs: *mut c_void, rhs: *const c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| { as_mut::<N>(lhs)?.and(as_ref::<N>(rhs)?); })
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyOr(n: u8, lhs: *mut c_void, rhs: *const c_void) {
-    dispatch_n!(n, |N| { as_mut::<N>(lhs).or(as_ref::<N>(rhs)); })
+pub unsafe extern "C" fn BoolPolyOr(n: u8, lhs: *mut c_void, rhs: *const c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| { as_mut::<N>(lhs)?.or(as_ref::<N>(rhs)?); })
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyXor(n: u8, lhs: *mut c_void, rhs: *const c_void) {
-    dispatch_n!(n, |N| { as_mut::<N>(lhs).xor(as_ref::<N>(rhs)); })
+pub unsafe extern "C" fn BoolPolyXor(n: u8, lhs: *mut c_void, rhs: *const c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| { as_mut::<N>(lhs)?.xor(as_ref::<N>(rhs)?); })
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyMul(n: u8, lhs: *mut c_void, rhs: *const c_void) {
-    dispatch_n!(n, |N| { as_mut::<N>(lhs).mul(as_ref::<N>(rhs)); })
+pub unsafe extern "C" fn BoolPolyMul(n: u8, lhs: *mut c_void, rhs: *const c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| { as_mut::<N>(lhs)?.mul(as_ref::<N>(rhs)?); })
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyNot(n: u8, target: *mut c_void) {
-    dispatch_n!(n, |N| { as_mut::<N>(target).not(); })
+pub unsafe extern "C" fn BoolPolyNot(n: u8, target: *mut c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| { as_mut::<N>(target)?.not(); })
+    })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyGetConstantOffset(n: u8, ptr: *mut c_void) -> u64 {
-    dispatch_n!(n, |N| {
-        as_ref::<N>(ptr).get_constant_offset()
+    ffi::guard(|| {
+        dispatch_n!(n, |N| {
+            as_ref::<N>(ptr)?.get_constant_offset()
+        })
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyGetRow(n: u8, ptr: *mut c_void, index: u32) -> *mut u64 {
-    dispatch_n!(n, |N| {
-        as_mut::<N>(ptr).value[index as usize].value.as_mut_ptr()
+    ffi::guard(|| {
+        dispatch_n!(n, |N| {
+            let poly = as_mut::<N>(ptr)?;
+            if index >= poly.bit_width as u32 {
+                return Err(FfiError::invalid(format!("row {} is out of range for a {} bit polynomial", index, poly.bit_width)));
+            }
+            poly.value[index as usize].value.as_mut_ptr()
+        })
     })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyChangeBasis(n: u8, target: *mut c_void) {
-    dispatch_n!(n, |N| {
-        for i in 0..(as_mut::<N>(target).bit_width as usize) {
-            as_mut::<N>(target).value[i] = fast_anf_transform(&as_mut::<N>(target).value[i]);
-        }
+pub unsafe extern "C" fn BoolPolyChangeBasis(n: u8, target: *mut c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+            let target = as_mut::<N>(target)?;
+            for i in 0..(target.bit_width as usize) {
+                target.value[i] = fast_anf_transform(&target.value[i]);
+            }
 
-     })
+         })
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyGetVariableCounts(n: u8, ptr: *mut c_void, arr: *mut u32) {
-    dispatch_n!(n, |N| {
-        let counts = as_ref::<N>(ptr).get_variable_counts();
-        for i in 0..N {
-            *arr.add(i) = counts[i] as u32;
-        }
+pub unsafe extern "C" fn BoolPolyGetVariableCounts(n: u8, ptr: *mut c_void, arr: *mut u32) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+            let counts = as_ref::<N>(ptr)?.get_variable_counts();
+            ffi::non_null(arr, "arr")?;
+            for i in 0..N {
+                *arr.add(i) = counts[i] as u32;
+            }
+        })
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyFilterByVar(n: u8, ptr: *mut c_void, var_index: u32, contains: u8) -> *mut c_void  {
-    dispatch_n!(n, |N| {
-        let r = as_ref::<N>(ptr).filter_by_variable(var_index as usize, contains != 0);
-        Box::into_raw(Box::new(r)) as *mut c_void
+    ffi::guard(|| {
+        dispatch_n!(n, |N| {
+            check_var_index(var_index, N)?;
+            let r = as_ref::<N>(ptr)?.filter_by_variable(var_index as usize, contains != 0);
+            Box::into_raw(Box::new(r)) as *mut c_void
+        })
     })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyEliminateVar(n: u8, ptr: *mut c_void, var_index: u32) -> *mut c_void  {
-    dispatch_n!(n, |N| {
-        let r = as_mut::<N>(ptr).eliminate_variable(var_index as usize);
-        Box::into_raw(Box::new(r)) as *mut c_void
+pub unsafe extern "C" fn BoolPolyEliminateVar(n: u8, ptr: *mut c_void, var_index: u32) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+            check_var_index(var_index, N)?;
+            as_mut::<N>(ptr)?.eliminate_variable(var_index as usize);
+        })
     })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyMultiplyByVar(n: u8, ptr: *mut c_void, var_index: u32) {
-    dispatch_n!(n, |N| {
-        as_mut::<N>(ptr).multiply_by_variable(var_index as usize);
+pub unsafe extern "C" fn BoolPolyMultiplyByVar(n: u8, ptr: *mut c_void, var_index: u32) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+            check_var_index(var_index, N)?;
+            as_mut::<N>(ptr)?.multiply_by_variable(var_index as usize);
+        })
     })
 }
 
@@ -458,12 +517,18 @@ pub unsafe extern "C" fn BoolPolyRemoveVars(
     ptr: *const c_void,
     var_mask: u32,
 ) -> *mut c_void {
-    dispatch_n!(num_vars_in, |N_IN| {
-        let num_vars_out = num_vars_in - var_mask.count_ones() as u8;
-        dispatch_n!(num_vars_out, |N_OUT| {
-            let reduced = as_ref::<N_IN>(ptr).remove_variables::<N_OUT>(var_mask);
-            Box::into_raw(Box::new(reduced)) as *mut c_void
-        })
+    ffi::guard(|| {
+        dispatch_n!(num_vars_in, |N_IN| {
+            if N_IN < 32 && var_mask >> N_IN != 0 {
+                return Err(FfiError::invalid(format!("variable mask {:#x} is out of range for {} variables", var_mask, N_IN)));
+            }
+            let num_vars_out = num_vars_in - var_mask.count_ones() as u8;
+            let input = as_ref::<N_IN>(ptr)?;
+            dispatch_n!(num_vars_out, |N_OUT| {
+                let reduced = input.remove_variables::<N_OUT>(var_mask);
+                Box::into_raw(Box::new(reduced)) as *mut c_void
+            })
+        })?
     })
 }
 // Attempt to change an N-bit boolean polynomial to a 1-bit polynomial by expressing it as c1 + c2*(a + b + ab + ...),
@@ -475,84 +540,103 @@ pub unsafe extern "C" fn BoolPolyTryReduceTo1Bit(
     out_constant_offset: *mut u64,
     out_coefficient: *mut u64,
 ) -> u8 {
-    dispatch_n!(num_vars, |N| {
-        match as_ref::<N>(ptr).try_downgrade_to_1bit() {
-            Some((table, constant_offset, coefficient)) => {
-                let mut wrapped = MultiBoolPoly::<N>::new(1);
-                wrapped.value[0] = table;
-
-                *out_table = Box::into_raw(Box::new(wrapped)) as *mut c_void;
-                *out_constant_offset = constant_offset;
-                *out_coefficient = coefficient;
-                1u8
+    ffi::guard(|| {
+        dispatch_n!(num_vars, |N| {
+            ffi::non_null(out_table, "out_table")?;
+            ffi::non_null(out_constant_offset, "out_constant_offset")?;
+            ffi::non_null(out_coefficient, "out_coefficient")?;
+            match as_ref::<N>(ptr)?.try_downgrade_to_1bit() {
+                Some((table, constant_offset, coefficient)) => {
+                    let mut wrapped = MultiBoolPoly::<N>::new(1);
+                    wrapped.value[0] = table;
+
+                    *out_table = Box::into_raw(Box::new(wrapped)) as *mut c_void;
+                    *out_constant_offset = constant_offset;
+                    *out_coefficient = coefficient;
+                    1u8
+                }
+                None => 0u8,
             }
-            None => 0u8,
-        }
+        })
     })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolySift(n: u8, target: *mut c_void, negated_mask: *mut u32, negated_constant: *mut u64) {
-    dispatch_n!(n, |N| {
-         let (mask, constant) = as_mut::<N>(target).sift(); 
-         *negated_mask = mask;
-         *negated_constant = constant;
-        })
+pub unsafe extern "C" fn BoolPolySift(n: u8, target: *mut c_void, negated_mask: *mut u32, negated_constant: *mut u64) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+             let negated_mask = ffi::non_null(negated_mask, "negated_mask")?;
+             let negated_constant = ffi::non_null(negated_constant, "negated_constant")?;
+             let (mask, constant) = as_mut::<N>(target)?.sift(); 
+             *negated_mask = mask;
+             *negated_constant = constant;
+            })
+    })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyClone(n: u8, ptr: *const c_void) -> *mut c_void {
-    dispatch_n!(n, |N| {
-        let original = as_ref::<N>(ptr);
-        let cloned = Box::new(original.clone());
-        Box::into_raw(cloned) as *mut c_void
+    ffi::guard(|| {
+        dispatch_n!(n, |N| {
+            let original = as_ref::<N>(ptr)?;
+            let cloned = Box::new(original.clone());
+            Box::into_raw(cloned) as *mut c_void
+        })
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyEquals(n: u8, lhs: *const c_void, rhs: *const c_void) -> u8 {
-    dispatch_n!(n, |N| {
-        if as_ref::<N>(lhs) == as_ref::<N>(rhs) {
-            1
-        } else {
-            0
-        }
+    ffi::guard(|| {
+        dispatch_n!(n, |N| {
+            if as_ref::<N>(lhs)? == as_ref::<N>(rhs)? {
+                1
+            } else {
+                0
+            }
+        })
     })
 }
 
 // Checks whether `(a^b) == (b|c)` == 0 without heap allocating a new polynomial for the result.
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyIsOr(n: u8, a: *const c_void, b: *const c_void, c: *const c_void) -> u8 {
-    dispatch_n!(n, |N| {
+    ffi::guard(|| {
+        dispatch_n!(n, |N| {
 
-        let mut ground_truth = as_ref::<N>(a).clone();
-        ground_truth.xor(as_ref::<N>(b)); 
+            let mut ground_truth = as_ref::<N>(a)?.clone();
+            ground_truth.xor(as_ref::<N>(b)?); 
 
-        let mut combined = as_ref::<N>(b).clone();
-        combined.anf_or(as_ref::<N>(c)); 
+            let mut combined = as_ref::<N>(b)?.clone();
+            combined.anf_or(as_ref::<N>(c)?); 
 
-        if ground_truth == combined {
-            1
-        } else {
-            0
-        }
+            if ground_truth == combined {
+                1
+            } else {
+                0
+            }
+        })
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn BoolPolyGetHash(n: u8, ptr: *const c_void) -> u64 {
-    dispatch_n!(n, |N| { as_ref::<N>(ptr).hash() })
+    ffi::guard(|| {
+        dispatch_n!(n, |N| { as_ref::<N>(ptr)?.hash() })
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn BoolPolyDivRem(n: u8, a: *const c_void, b: *const c_void, div: *mut c_void, rem: *mut c_void) {
-    dispatch_n!(n, |N| {
-        let a_ref = as_ref::<N>(a);
-        let b_ref = as_ref::<N>(b);
+pub unsafe extern "C" fn BoolPolyDivRem(n: u8, a: *const c_void, b: *const c_void, div: *mut c_void, rem: *mut c_void) -> FfiStatus {
+    ffi::guard_status(|| {
+        dispatch_n!(n, |N| {
+            let a_ref = as_ref::<N>(a)?;
+            let b_ref = as_ref::<N>(b)?;
 
-        let div_ref = as_mut::<N>(div);
-        let rem_ref = as_mut::<N>(rem);
+            let div_ref = as_mut::<N>(div)?;
+            let rem_ref = as_mut::<N>(rem)?;
 
-        a_ref.div_rem(b_ref, div_ref, rem_ref);
+            a_ref.div_rem(b_ref, div_ref, rem_ref);
+        })
     })
 }
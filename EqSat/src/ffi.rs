@@ -0,0 +1,581 @@
+// Error reporting for the `extern "C"` entry points.
+//
+// Each entry point runs its body through `guard`, which catches panics and records the outcome of the call in a
+// thread-local status. Arguments are validated up front where possible, so that a bad handle or width is reported rather
+// than read out of bounds. A failed call returns a sentinel: null for pointers, `invalid_idx()` for nodes, and zero or
+// false otherwise, while entry points without a result return their `FfiStatus`. The reason for the most recent failure
+// on the calling thread is available through `GetLastStatus` and `GetLastErrorMessage`.
+//
+// The arithmetic helpers called from JIT compiled code are exempt, since they cannot fail and sit on the hot path.
+//
+// Arrays and strings returned to the caller are owned by the caller, and must be released with `FreeAstIdxArray`,
+// `FreeU64Array` or `FreeString`. Handles are released with the `Free*` function of their type.
+use std::cell::{Cell, RefCell};
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use libc::c_char;
+
+use crate::{
+    ast_parser::ParseError,
+    known_bits::FfiKnownBits,
+    api,
+    simple_ast::{AstIdx, Context, EEGraph, LocalNodes},
+    snapshot::SnapshotError,
+};
+
+// Returned in place of a node when a call fails. This is also how `ContextCollectGarbage` marks freed nodes.
+pub fn invalid_idx() -> AstIdx {
+    AstIdx::from(u32::MAX as usize)
+}
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FfiStatus {
+    Ok = 0,
+    NullPointer = 1,
+    // A node index that does not belong to the context.
+    InvalidHandle = 2,
+    InvalidArgument = 3,
+    ParseError = 4,
+    Io = 5,
+    // An internal error, e.g. a violated invariant or a limit being exceeded.
+    Panic = 6,
+}
+
+#[derive(Debug)]
+pub struct FfiError {
+    pub status: FfiStatus,
+    pub message: String,
+}
+
+pub type FfiResult<T> = Result<T, FfiError>;
+
+impl FfiError {
+    pub fn new(status: FfiStatus, message: impl Into<String>) -> Self {
+        FfiError {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(FfiStatus::InvalidArgument, message)
+    }
+}
+
+impl fmt::Display for FfiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<ParseError> for FfiError {
+    fn from(e: ParseError) -> Self {
+        FfiError::new(FfiStatus::ParseError, e.to_string())
+    }
+}
+
+impl From<std::io::Error> for FfiError {
+    fn from(e: std::io::Error) -> Self {
+        FfiError::new(FfiStatus::Io, e.to_string())
+    }
+}
+
+impl From<api::Error> for FfiError {
+    fn from(e: api::Error) -> Self {
+        match e {
+            api::Error::ForeignHandle => FfiError::new(FfiStatus::InvalidHandle, e.to_string()),
+            api::Error::Parse(e) => e.into(),
+            api::Error::Io(e) => e.into(),
+            e => FfiError::invalid(e.to_string()),
+        }
+    }
+}
+
+impl From<SnapshotError> for FfiError {
+    fn from(e: SnapshotError) -> Self {
+        match e {
+            SnapshotError::Io(e) => e.into(),
+            e => FfiError::invalid(e.to_string()),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_STATUS: Cell<FfiStatus> = const { Cell::new(FfiStatus::Ok) };
+    static LAST_MESSAGE: RefCell<CString> = RefCell::new(CString::default());
+}
+
+// The value returned by an entry point when it fails.
+pub trait FfiDefault {
+    fn failed() -> Self;
+}
+
+impl FfiDefault for () {
+    fn failed() -> Self {}
+}
+
+impl FfiDefault for AstIdx {
+    fn failed() -> Self {
+        invalid_idx()
+    }
+}
+
+impl<T> FfiDefault for *mut T {
+    fn failed() -> Self {
+        std::ptr::null_mut()
+    }
+}
+
+impl<T> FfiDefault for *const T {
+    fn failed() -> Self {
+        std::ptr::null()
+    }
+}
+
+macro_rules! impl_ffi_default_zero {
+    ($($ty:ty),*) => {
+        $(impl FfiDefault for $ty {
+            fn failed() -> Self {
+                Default::default()
+            }
+        })*
+    };
+}
+
+impl_ffi_default_zero!(bool, u8, u32, u64, usize, FfiKnownBits);
+
+fn set_last_error(status: FfiStatus, message: String) {
+    LAST_STATUS.with(|s| s.set(status));
+    // Interior nul bytes would truncate the message, so they are replaced.
+    let message = CString::new(message.replace('\0', "\\0")).unwrap();
+    LAST_MESSAGE.with(|m| *m.borrow_mut() = message);
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        return s.to_string();
+    }
+    if let Some(s) = payload.downcast_ref::<String>() {
+        return s.clone();
+    }
+    return "unknown panic".to_string();
+}
+
+// Run the body of an entry point, recording its status and converting errors and panics into `T::failed()`.
+pub fn guard<T: FfiDefault>(f: impl FnOnce() -> FfiResult<T>) -> T {
+    LAST_STATUS.with(|s| s.set(FfiStatus::Ok));
+    match catch_unwind(AssertUnwindSafe(f)) {
+        // Nested entry points called by the body, e.g. from a callback, may have failed in the meantime.
+        Ok(Ok(value)) => {
+            LAST_STATUS.with(|s| s.set(FfiStatus::Ok));
+            value
+        }
+        Ok(Err(e)) => {
+            set_last_error(e.status, e.message);
+            T::failed()
+        }
+        Err(payload) => {
+            set_last_error(FfiStatus::Panic, panic_message(&*payload));
+            T::failed()
+        }
+    }
+}
+
+// Like `guard`, for entry points that have no result of their own.
+pub fn guard_status(f: impl FnOnce() -> FfiResult<()>) -> FfiStatus {
+    guard(f);
+    LAST_STATUS.with(|s| s.get())
+}
+
+pub fn non_null<'a, T>(ptr: *mut T, name: &str) -> FfiResult<&'a mut T> {
+    unsafe { ptr.as_mut() }.ok_or_else(|| FfiError::new(FfiStatus::NullPointer, format!("{} is null", name)))
+}
+
+pub fn non_null_ref<'a, T>(ptr: *const T, name: &str) -> FfiResult<&'a T> {
+    unsafe { ptr.as_ref() }.ok_or_else(|| FfiError::new(FfiStatus::NullPointer, format!("{} is null", name)))
+}
+
+pub fn context<'a>(ctx: *mut Context) -> FfiResult<&'a mut Context> {
+    non_null(ctx, "context")
+}
+
+pub fn context_ref<'a>(ctx: *const Context) -> FfiResult<&'a Context> {
+    non_null_ref(ctx, "context")
+}
+
+// The nodes of a context, for operations that mutate or free them and hence do not support shared arenas.
+pub fn local(ctx: &mut Context) -> FfiResult<&mut LocalNodes> {
+    ctx.arena
+        .local_mut()
+        .ok_or_else(|| FfiError::invalid("operation is not supported on a context with a shared arena"))
+}
+
+// A slice of `len` elements, where null is accepted for an empty slice.
+pub fn slice<'a, T>(ptr: *const T, len: usize, name: &str) -> FfiResult<&'a [T]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    non_null_ref(ptr, name)?;
+    Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+}
+
+pub fn string(s: *const c_char) -> FfiResult<String> {
+    non_null_ref(s, "string")?;
+    let s = unsafe { CStr::from_ptr(s) };
+    match s.to_str() {
+        Ok(s) => Ok(s.to_owned()),
+        Err(e) => Err(FfiError::invalid(format!("string is not valid utf-8: {}", e))),
+    }
+}
+
+pub fn node(ctx: &Context, idx: AstIdx) -> FfiResult<AstIdx> {
+    if usize::from(idx) >= ctx.arena.len() {
+        return Err(FfiError::new(
+            FfiStatus::InvalidHandle,
+            format!("node {} does not exist, the context has {} nodes", usize::from(idx), ctx.arena.len()),
+        ));
+    }
+    Ok(idx)
+}
+
+pub fn eclass(egraph: &EEGraph, id: egg::Id) -> FfiResult<egg::Id> {
+    if usize::from(id) >= egraph.analysis.ids {
+        return Err(FfiError::new(
+            FfiStatus::InvalidHandle,
+            format!("e-class {} does not exist, the e-graph has {} ids", usize::from(id), egraph.analysis.ids),
+        ));
+    }
+    Ok(id)
+}
+
+pub fn nodes(ctx: &Context, idxs: &[AstIdx]) -> FfiResult<()> {
+    for idx in idxs {
+        node(ctx, *idx)?;
+    }
+    Ok(())
+}
+
+// Validate both nodes and check that they have the same width.
+pub fn same_width(ctx: &Context, a: AstIdx, b: AstIdx) -> FfiResult<()> {
+    Ok(api::check_same_width(ctx, node(ctx, a)?, node(ctx, b)?)?)
+}
+
+pub fn width(width: u8) -> FfiResult<u8> {
+    Ok(api::check_width(width)?)
+}
+
+// Hand a vector to the caller, storing its length in `out_len`. The length is needed again to free it.
+pub fn into_raw_array<T>(v: Vec<T>, out_len: &mut u64) -> *mut T {
+    *out_len = v.len() as u64;
+    Box::into_raw(v.into_boxed_slice()) as *mut T
+}
+
+pub fn into_raw_string(s: &str) -> *mut c_char {
+    CString::new(s.replace('\0', "\\0")).unwrap().into_raw()
+}
+
+unsafe fn free_array<T>(ptr: *mut T, len: u64) {
+    if !ptr.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len as usize)));
+    }
+}
+
+// Free an array of nodes or e-classes, e.g. the result of `ContextCollectVariables` or `EGraphGetClasses`.
+// `len` must be the length reported when the array was returned. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn FreeAstIdxArray(ptr: *mut AstIdx, len: u64) -> FfiStatus {
+    guard_status(|| Ok(free_array(ptr, len)))
+}
+
+// Free an array of integers, e.g. the result of `ContextEvaluateForAllZeroesAndOnes`.
+#[no_mangle]
+pub unsafe extern "C" fn FreeU64Array(ptr: *mut u64, len: u64) -> FfiStatus {
+    guard_status(|| Ok(free_array(ptr, len)))
+}
+
+// Free a string returned by the library, e.g. the result of `ContextGetAstString`. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn FreeString(s: *mut c_char) -> FfiStatus {
+    guard_status(|| {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+        Ok(())
+    })
+}
+
+// The status of the most recent call on this thread.
+#[no_mangle]
+pub extern "C" fn GetLastStatus() -> FfiStatus {
+    LAST_STATUS.with(|s| s.get())
+}
+
+// The message of the most recent failure on this thread, or an empty string if the last call succeeded.
+// The string is owned by the library and stays valid until the next failing call on this thread.
+#[no_mangle]
+pub extern "C" fn GetLastErrorMessage() -> *const c_char {
+    if GetLastStatus() == FfiStatus::Ok {
+        return c"".as_ptr();
+    }
+    LAST_MESSAGE.with(|m| m.borrow().as_ptr())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::*;
+    use crate::{
+        codegen::ContextEmitCode,
+        dot::{ContextGetDotString, EGraphGetDotString},
+        fbgb::multi_bool_poly::{BoolPolyClone, CreateBoolPoly, FreeBoolPoly},
+        printer::{ContextPrintWithOptions, FfiPrintOptions},
+        simple_ast::{
+            ContextAdd, ContextCollectGarbage, ContextCollectVariables, ContextConstant,
+            ContextEvaluateForAllZeroesAndOnes, ContextGetAstString, ContextGetSExprString, ContextGetSymbolName,
+            ContextJit, ContextParseAstString, ContextSymbol, CreateContext, CreateEGraph, EGraphAddFromContext,
+            EGraphExtract, EGraphExtractAll, EGraphGetClassNodes, EGraphGetClasses, EGraphRebuild, EGraphUnion, FreeContext,
+            FreeEGraph,
+        },
+        smtlib::{ContextGetSmtLibEquivalenceQuery, ContextGetSmtLibString},
+        tape::{ContextCompileTape, FreeTape},
+    };
+
+    // Counts the bytes currently allocated by each thread, so that tests running in parallel do not interfere.
+    struct CountingAllocator;
+
+    thread_local! {
+        static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+    }
+
+    fn track(delta: isize) {
+        // The counter is unavailable while the thread is being torn down.
+        let _ = LIVE_BYTES.try_with(|live| live.set(live.get() + delta));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                track(layout.size() as isize);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            track(-(layout.size() as isize));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                track(new_size as isize - layout.size() as isize);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn live_bytes() -> isize {
+        LIVE_BYTES.with(|live| live.get())
+    }
+
+    fn last_message() -> String {
+        unsafe { CStr::from_ptr(GetLastErrorMessage()) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn reports_errors() {
+        let ctx = CreateContext();
+        let x = ContextSymbol(ctx, c"x".as_ptr(), 8);
+        assert_eq!(GetLastStatus(), FfiStatus::Ok);
+        assert_eq!(last_message(), "");
+
+        let y = ContextSymbol(ctx, c"y".as_ptr(), 16);
+        assert_eq!(ContextAdd(ctx, x, y), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::InvalidArgument);
+        assert_eq!(last_message(), "width mismatch: i8 and i16");
+
+        assert_eq!(ContextAdd(ctx, x, AstIdx::from(1000usize)), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::InvalidHandle);
+
+        assert_eq!(ContextConstant(ctx, 1, 0), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::InvalidArgument);
+
+        assert_eq!(ContextAdd(std::ptr::null_mut(), x, x), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::NullPointer);
+
+        assert_eq!(ContextParseAstString(ctx, c"x +".as_ptr(), 8), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::ParseError);
+        assert_eq!(last_message(), "expected operand, found end of input at 3..3");
+
+        // Operators the JIT cannot lower are rejected before any code is written to the page.
+        let shr = ContextParseAstString(ctx, c"x >> 1".as_ptr(), 8);
+        let mut page = [0u8; 16];
+        let mut output = [0u64; 2];
+        let status = unsafe {
+            ContextJit(ctx, shr, 1, 0, 8, &x, 1, 2, page.as_mut_ptr(), output.as_mut_ptr())
+        };
+        assert_eq!(status, FfiStatus::InvalidArgument);
+        assert_eq!(page, [0u8; 16]);
+
+        // E-graph unions are checked like those of the safe API.
+        let egraph = CreateEGraph();
+        let a = EGraphAddFromContext(egraph, ctx, x);
+        let b = EGraphAddFromContext(egraph, ctx, y);
+        assert_eq!(EGraphUnion(egraph, a, b), FfiStatus::InvalidArgument);
+        assert_eq!(last_message(), "width mismatch: i8 and i16");
+
+        // So are e-class ids.
+        let missing = egg::Id::from(1000usize);
+        assert_eq!(EGraphUnion(egraph, a, missing), FfiStatus::InvalidHandle);
+        assert_eq!(last_message(), "e-class 1000 does not exist, the e-graph has 2 ids");
+        let mut len = 0;
+        assert!(EGraphGetClassNodes(egraph, missing, ctx, &mut len).is_null());
+        assert_eq!(GetLastStatus(), FfiStatus::InvalidHandle);
+        assert_eq!(EGraphExtract(egraph, ctx, missing), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::InvalidHandle);
+        assert!(unsafe { EGraphGetDotString(egraph, ctx, missing) }.is_null());
+        assert_eq!(GetLastStatus(), FfiStatus::InvalidHandle);
+        assert_eq!(unsafe { FreeEGraph(egraph) }, FfiStatus::Ok);
+
+        // A successful call resets the status.
+        assert_ne!(ContextAdd(ctx, x, x), invalid_idx());
+        assert_eq!(GetLastStatus(), FfiStatus::Ok);
+
+        // Even if an entry point it called itself failed, as a callback may do.
+        let status = guard_status(|| {
+            assert_eq!(ContextAdd(ctx, x, y), invalid_idx());
+            Ok(())
+        });
+        assert_eq!(status, FfiStatus::Ok);
+        assert_eq!(GetLastStatus(), FfiStatus::Ok);
+    }
+
+    // Fetch every kind of buffer and string the library returns, releasing each with its free function.
+    unsafe fn fetch_and_free(ctx: *mut Context) {
+        let x = ContextSymbol(ctx, c"x".as_ptr(), 8);
+        let e = ContextParseAstString(ctx, c"(x & 3) + y * 5".as_ptr(), 8);
+        assert_ne!(e, invalid_idx());
+
+        let mut len = 0;
+        let vars = ContextCollectVariables(ctx, e, &mut len);
+        assert_eq!(len, 2);
+        assert_eq!(FreeAstIdxArray(vars, len), FfiStatus::Ok);
+
+        let values = ContextEvaluateForAllZeroesAndOnes(ctx, e, 255, &mut len);
+        assert_eq!(len, 4);
+        assert_eq!(FreeU64Array(values, len), FfiStatus::Ok);
+
+        let f = ContextParseAstString(ctx, c"(x & 3) + ((y << 2) + y)".as_ptr(), 8);
+        let vars = ContextCollectVariables(ctx, e, &mut len);
+        let options = FfiPrintOptions {
+            constant_style: 2,
+            width_annotations: 2,
+            default_width: 8,
+            let_bindings: 1,
+        };
+        for s in [
+            ContextGetSymbolName(ctx, x),
+            ContextGetAstString(ctx, e),
+            ContextGetSExprString(ctx, e),
+            ContextGetDotString(ctx, e),
+            ContextGetSmtLibString(ctx, e),
+            ContextGetSmtLibEquivalenceQuery(ctx, e, f),
+            ContextPrintWithOptions(ctx, e, &options),
+            ContextEmitCode(ctx, e, vars, len, 0, c"f".as_ptr()),
+        ] {
+            assert!(!s.is_null());
+            assert_eq!(FreeString(s), FfiStatus::Ok);
+        }
+
+        let tape = ContextCompileTape(ctx, e, vars, len);
+        assert_eq!(FreeTape(tape), FfiStatus::Ok);
+        assert_eq!(FreeAstIdxArray(vars, len), FfiStatus::Ok);
+
+        let egraph = CreateEGraph();
+        let a = EGraphAddFromContext(egraph, ctx, e);
+        let b = EGraphAddFromContext(egraph, ctx, f);
+        assert_eq!(EGraphUnion(egraph, a, b), FfiStatus::Ok);
+        assert_eq!(EGraphRebuild(egraph), FfiStatus::Ok);
+        let classes = EGraphGetClasses(egraph, &mut len);
+        assert!(len > 0);
+        assert_eq!(FreeAstIdxArray(classes, len), FfiStatus::Ok);
+        let nodes = EGraphGetClassNodes(egraph, a, ctx, &mut len);
+        assert_eq!(len, 2);
+        assert_eq!(FreeAstIdxArray(nodes, len), FfiStatus::Ok);
+        let extracted = EGraphExtractAll(egraph, ctx, &mut len);
+        assert!(len > 0);
+        assert_eq!(FreeAstIdxArray(extracted, len), FfiStatus::Ok);
+        let s = EGraphGetDotString(egraph, ctx, a);
+        assert!(!s.is_null());
+        assert_eq!(FreeString(s), FfiStatus::Ok);
+        assert_eq!(FreeEGraph(egraph), FfiStatus::Ok);
+
+        let poly = CreateBoolPoly(3, 8);
+        assert_eq!(FreeBoolPoly(3, BoolPolyClone(3, poly)), FfiStatus::Ok);
+        assert_eq!(FreeBoolPoly(3, poly), FfiStatus::Ok);
+
+        // Nothing is rooted, so this frees every node created above, and the next call starts from an empty arena.
+        let remap = ContextCollectGarbage(ctx, &mut len);
+        assert!(len > 0);
+        assert_eq!(FreeAstIdxArray(remap, len), FfiStatus::Ok);
+    }
+
+    #[test]
+    fn returned_buffers_are_freed() {
+        unsafe {
+            // Warm up any lazily initialized state before counting.
+            let ctx = CreateContext();
+            fetch_and_free(ctx);
+
+            let before = live_bytes();
+            for _ in 0..10 {
+                fetch_and_free(ctx);
+            }
+            assert_eq!(live_bytes(), before);
+
+            FreeContext(ctx);
+        }
+    }
+
+    #[test]
+    fn contexts_are_freed() {
+        unsafe {
+            let ctx = CreateContext();
+            fetch_and_free(ctx);
+            FreeContext(ctx);
+
+            let before = live_bytes();
+            let ctx = CreateContext();
+            fetch_and_free(ctx);
+            let mut len = 0;
+            let remap = ContextCollectGarbage(ctx, &mut len);
+            assert_eq!(FreeAstIdxArray(remap, len), FfiStatus::Ok);
+            assert_eq!(FreeContext(ctx), FfiStatus::Ok);
+            assert_eq!(live_bytes(), before);
+        }
+    }
+
+    #[test]
+    fn catches_panics() {
+        let ctx = CreateContext();
+        let x = ContextSymbol(ctx, c"x".as_ptr(), 8);
+        assert_eq!(
+            guard(|| -> FfiResult<AstIdx> { panic!("invariant violated") }),
+            invalid_idx()
+        );
+        assert_eq!(GetLastStatus(), FfiStatus::Panic);
+        assert_eq!(last_message(), "invariant violated");
+        assert_eq!(guard_status(|| Ok(())), FfiStatus::Ok);
+    }
+}
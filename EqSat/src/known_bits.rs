@@ -1,20 +1,56 @@
-use libc::{c_char, c_void};
-use std::{ffi::CStr, ptr};
-
-use crate::simple_ast::Predicate;
+// Known bits analysis, following the transfer functions of LLVM's `KnownBits`.
+//
+// A bit may be known to be zero, known to be one, or unknown. The transfer functions below are sound for every width
+// up to 128, and most are optimal, i.e. they know every bit that is the same for all values the operands may hold.
+// The exceptions are multiplication, division, remainders, rotates by unknown amounts and bit counts.
+use crate::eval::cmp;
+use crate::simple_ast::{
+    ashr_const, ctlz_const, cttz_const, lshr_const, popcount_const, rotl_const, rotr_const, sdiv_const,
+    shl_const, sign_extend, srem_const, udiv_const, urem_const, Predicate,
+};
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Default)]
-#[repr(C)]
 pub struct KnownBits {
     pub width: u32,
 
+    pub zeroes: u128,
+
+    pub ones: u128,
+}
+
+// Layout of the known bits used by the C# bindings, which only support widths of up to 64.
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct FfiKnownBits {
+    pub width: u32,
+
     pub zeroes: u64,
 
     pub ones: u64,
 }
 
+impl From<&KnownBits> for FfiKnownBits {
+    fn from(kb: &KnownBits) -> Self {
+        Self {
+            width: kb.width,
+            zeroes: kb.zeroes as u64,
+            ones: kb.ones as u64,
+        }
+    }
+}
+
+impl From<FfiKnownBits> for KnownBits {
+    fn from(kb: FfiKnownBits) -> Self {
+        Self {
+            width: kb.width,
+            zeroes: kb.zeroes as u128,
+            ones: kb.ones as u128,
+        }
+    }
+}
+
 impl KnownBits {
-    pub fn new(width: u8, zeroes: u64, ones: u64) -> Self {
+    pub fn new(width: u8, zeroes: u128, ones: u128) -> Self {
         Self {
             width: width as u32,
             zeroes,
@@ -30,8 +66,8 @@ impl KnownBits {
         }
     }
 
-    pub fn constant(c: u64, width: u8) -> Self {
-        let mask = u64::MAX >> (64 - width);
+    pub fn constant(c: u128, width: u8) -> Self {
+        let mask = Self::get_modulo_mask(width);
         Self {
             width: width as u32,
             zeroes: !c & mask,
@@ -40,110 +76,364 @@ impl KnownBits {
     }
 
     pub fn add(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetAddKnownBits(lhs, rhs, &mut out);
-        }
-        out
+        Self::add_carry(lhs, rhs, &KnownBits::constant(0, 1))
     }
 
     pub fn sub(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetSubKnownBits(lhs, rhs, &mut out);
-        }
-        out
+        // a - b == a + ~b + 1
+        Self::add_carry(lhs, &Self::neg(rhs), &KnownBits::constant(1, 1))
+    }
+
+    // Addition with a one bit carry in, i.e. LLVM's `computeForAddCarry`. The sum of the smallest and largest
+    // possible operands bounds the carries into each bit, and a bit is known if its operands and carry are.
+    pub fn add_carry(lhs: &KnownBits, rhs: &KnownBits, carry: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        let mask = Self::get_modulo_mask(width);
+        let max_sum = lhs.max_value().wrapping_add(rhs.max_value()).wrapping_add(carry.max_value());
+        let min_sum = lhs.ones.wrapping_add(rhs.ones).wrapping_add(carry.ones);
+
+        let carry_zeroes = !(max_sum ^ lhs.zeroes ^ rhs.zeroes);
+        let carry_ones = min_sum ^ lhs.ones ^ rhs.ones;
+        let known = (lhs.zeroes | lhs.ones) & (rhs.zeroes | rhs.ones) & (carry_zeroes | carry_ones) & mask;
+        return KnownBits::new(width, !max_sum & known, min_sum & known);
     }
 
     pub fn mul(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetMulKnownBits(lhs, rhs, &mut out);
+        let width = lhs.width as u8;
+        let mask = Self::get_modulo_mask(width);
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return KnownBits::constant(a.wrapping_mul(b), width);
+        }
+
+        // Writing the operands as a*2**i and b*2**j, the product is a*b*2**(i+j). The low bits of a*b only depend on
+        // the low bits of a and b, so they are known as far as the low bits of both a and b are.
+        let (lhs_trailing, rhs_trailing) = (lhs.min_trailing_zeroes(), rhs.min_trailing_zeroes());
+        let lhs_known = lhs.known_low_bits() - lhs_trailing;
+        let rhs_known = rhs.known_low_bits() - rhs_trailing;
+        let low_bits = (lhs_trailing + rhs_trailing + lhs_known.min(rhs_known)).min(width as u32);
+        let low = Self::low_mask(low_bits);
+        let product = lhs.ones.wrapping_mul(rhs.ones);
+
+        // Without overflow, the product is bounded by the product of the largest possible operands.
+        let mut zeroes = !product & low;
+        if let Some(max) = lhs.max_value().checked_mul(rhs.max_value()).filter(|max| *max <= mask) {
+            zeroes |= Self::with_max_value(width, max).zeroes;
         }
-        out
+
+        return KnownBits::new(width, zeroes, product & low);
     }
 
     pub fn and(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetAndKnownBits(lhs, rhs, &mut out);
-        }
-        out
+        KnownBits::new(lhs.width as u8, lhs.zeroes | rhs.zeroes, lhs.ones & rhs.ones)
     }
 
     pub fn or(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetOrKnownBits(lhs, rhs, &mut out);
-        }
-        out
+        KnownBits::new(lhs.width as u8, lhs.zeroes & rhs.zeroes, lhs.ones | rhs.ones)
     }
 
     pub fn xor(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetXorKnownBits(lhs, rhs, &mut out);
-        }
-        out
+        let zeroes = (lhs.zeroes & rhs.zeroes) | (lhs.ones & rhs.ones);
+        let ones = (lhs.zeroes & rhs.ones) | (lhs.ones & rhs.zeroes);
+        KnownBits::new(lhs.width as u8, zeroes, ones)
     }
 
+    // Bitwise negation, matching `SimpleAst::Neg`.
     pub fn neg(lhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetNegKnownBits(lhs, &mut out);
-        }
-        out
+        KnownBits::new(lhs.width as u8, lhs.ones, lhs.zeroes)
+    }
+
+    // The majority function computed by `SimpleAst::Carry`. A bit is known if two of its operands agree on it.
+    pub fn carry(a: &KnownBits, b: &KnownBits, c: &KnownBits) -> KnownBits {
+        let zeroes = (a.zeroes & b.zeroes) | (a.zeroes & c.zeroes) | (b.zeroes & c.zeroes);
+        let ones = (a.ones & b.ones) | (a.ones & c.ones) | (b.ones & c.ones);
+        KnownBits::new(a.width as u8, zeroes, ones)
     }
 
     pub fn shl(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetShlKnownBits(lhs, rhs, &mut out);
-        }
-        out
+        Self::shift(lhs, rhs, shl_const)
     }
 
     pub fn lshr(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetLshrKnownBits(lhs, rhs, &mut out);
+        Self::shift(lhs, rhs, lshr_const)
+    }
+
+    pub fn ashr(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        Self::shift(lhs, rhs, ashr_const)
+    }
+
+    // Each shift moves every bit to a fixed position, filling the rest with zeroes or copies of the sign bit. So
+    // shifting the known ones and the possible ones by a known amount yields the known ones and possible ones of the
+    // result. For an unknown amount, this is the intersection over every amount the rhs may hold. Amounts of at least
+    // the width all behave the same, so they are only considered once.
+    fn shift(lhs: &KnownBits, rhs: &KnownBits, shift_const: fn(u128, u128, u8) -> u128) -> KnownBits {
+        let width = lhs.width as u8;
+        let mask = Self::get_modulo_mask(width);
+        let shift_by = |amount: u128| {
+            let zeroes = !shift_const(lhs.max_value(), amount, width) & mask;
+            KnownBits::new(width, zeroes, shift_const(lhs.ones, amount, width))
+        };
+
+        if let Some(amount) = rhs.as_constant() {
+            return shift_by(amount);
         }
-        out
+
+        let mut result = KnownBits::new(width, mask, mask);
+        for amount in 0..width as u128 {
+            if rhs.contains(amount) {
+                result = result.intersect(&shift_by(amount));
+            }
+        }
+        if rhs.max_value() >= width as u128 {
+            result = result.intersect(&shift_by(width as u128));
+        }
+
+        return result;
+    }
+
+    // Division only tracks leading zeroes, which is enough to bound the result's width, and folds fully known
+    // operands.
+    pub fn udiv(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return KnownBits::constant(udiv_const(a, b, width), width);
+        }
+
+        // A zero divisor yields all ones, so nothing is known unless the rhs is nonzero.
+        let rhs_min = rhs.ones;
+        if rhs_min == 0 {
+            return KnownBits::empty(width);
+        }
+
+        return Self::with_max_value(width, lhs.max_value() / rhs_min);
+    }
+
+    pub fn urem(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return KnownBits::constant(urem_const(a, b, width), width);
+        }
+
+        // Modulo a power of two is a mask of the low bits.
+        if let Some(b) = rhs.as_constant() {
+            if b.is_power_of_two() {
+                let mask = b - 1;
+                let zeroes = lhs.zeroes | (!mask & Self::get_modulo_mask(width));
+                return KnownBits::new(width, zeroes, lhs.ones & mask);
+            }
+        }
+
+        // The remainder never exceeds the lhs, and is below the rhs when the rhs is nonzero.
+        let mut max = lhs.max_value();
+        if rhs.ones != 0 {
+            max = max.min(rhs.max_value() - 1);
+        }
+
+        return Self::with_max_value(width, max);
+    }
+
+    pub fn sdiv(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return KnownBits::constant(sdiv_const(a, b, width), width);
+        }
+
+        return KnownBits::empty(width);
+    }
+
+    pub fn srem(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return KnownBits::constant(srem_const(a, b, width), width);
+        }
+
+        // The remainder takes the sign of the dividend and is never larger in magnitude,
+        // so a non-negative lhs bounds the result.
+        let sign_bit = 1u128 << (width - 1);
+        if (lhs.zeroes & sign_bit) != 0 {
+            return Self::with_max_value(width, lhs.max_value());
+        }
+
+        return KnownBits::empty(width);
+    }
+
+    pub fn rotl(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let Some(amount) = rhs.as_constant() {
+            let zeroes = rotl_const(lhs.zeroes, amount, width);
+            return KnownBits::new(width, zeroes, rotl_const(lhs.ones, amount, width));
+        }
+
+        return Self::rotate_unknown(lhs);
+    }
+
+    pub fn rotr(lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let Some(amount) = rhs.as_constant() {
+            let zeroes = rotr_const(lhs.zeroes, amount, width);
+            return KnownBits::new(width, zeroes, rotr_const(lhs.ones, amount, width));
+        }
+
+        return Self::rotate_unknown(lhs);
+    }
+
+    // Rotating by an unknown amount only preserves bits that are the same everywhere.
+    fn rotate_unknown(lhs: &KnownBits) -> KnownBits {
+        let mask = Self::get_modulo_mask(lhs.width as u8);
+        if lhs.zeroes == mask || lhs.ones == mask {
+            return *lhs;
+        }
+
+        return KnownBits::empty(lhs.width as u8);
+    }
+
+    // Bit counts never exceed the width, so at most the low log2(width)+1 bits can be set.
+    // The bounds below are tighter: a count can only reach as far as the bits that are not known to end it.
+    pub fn popcount(lhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let Some(c) = lhs.as_constant() {
+            return KnownBits::constant(popcount_const(c, width), width);
+        }
+
+        return Self::with_max_value(width, popcount_const(lhs.max_value(), width));
+    }
+
+    pub fn ctlz(lhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let Some(c) = lhs.as_constant() {
+            return KnownBits::constant(ctlz_const(c, width), width);
+        }
+
+        // The count stops at the highest known one.
+        return Self::with_max_value(width, ctlz_const(lhs.ones, width));
+    }
+
+    pub fn cttz(lhs: &KnownBits) -> KnownBits {
+        let width = lhs.width as u8;
+        if let Some(c) = lhs.as_constant() {
+            return KnownBits::constant(cttz_const(c, width), width);
+        }
+
+        // The count stops at the lowest known one.
+        return Self::with_max_value(width, cttz_const(lhs.ones, width));
     }
 
     pub fn zext(lhs: &KnownBits, width: u32) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetZextKnownBits(lhs, width, &mut out);
+        let high = Self::get_modulo_mask(width as u8) & !Self::get_modulo_mask(lhs.width as u8);
+        return KnownBits::new(width as u8, lhs.zeroes | high, lhs.ones);
+    }
+
+    // Sign extension copies whatever we know about the sign bit into the new high bits.
+    pub fn sext(lhs: &KnownBits, width: u32) -> KnownBits {
+        let from = lhs.width as u8;
+        let sign_bit = 1u128 << (from - 1);
+        let high = Self::get_modulo_mask(width as u8) & !Self::get_modulo_mask(from);
+
+        let mut zeroes = lhs.zeroes;
+        let mut ones = lhs.ones;
+        if (zeroes & sign_bit) != 0 {
+            zeroes |= high;
+        }
+        if (ones & sign_bit) != 0 {
+            ones |= high;
         }
-        out
+
+        return KnownBits::new(width as u8, zeroes, ones);
     }
 
     pub fn trunc(lhs: &KnownBits, width: u32) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetTruncKnownBits(lhs, width, &mut out);
-        }
-        out
+        let mask = Self::get_modulo_mask(width as u8);
+        return KnownBits::new(width as u8, lhs.zeroes & mask, lhs.ones & mask);
+    }
+
+    pub fn extract(lhs: &KnownBits, high: u8, low: u8) -> KnownBits {
+        let mask = Self::get_modulo_mask(1 + high - low);
+        return KnownBits::new(1 + high - low, (lhs.zeroes >> low) & mask, (lhs.ones >> low) & mask);
+    }
+
+    // The high operand occupies the upper bits of the result.
+    pub fn concat(high: &KnownBits, low: &KnownBits) -> KnownBits {
+        let shift = low.width;
+        return KnownBits::new(
+            (high.width + low.width) as u8,
+            (high.zeroes << shift) | low.zeroes,
+            (high.ones << shift) | low.ones,
+        );
     }
 
     pub fn icmp(pred: Predicate, lhs: &KnownBits, rhs: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetIcmpKnownBits(pred, lhs, rhs, &mut out);
+        let width = lhs.width as u8;
+        if let (Some(a), Some(b)) = (lhs.as_constant(), rhs.as_constant()) {
+            return KnownBits::constant(cmp(pred, a, b, width) as u128, 1);
         }
-        out
+
+        // The operands are independent, so the comparison is only known if it holds for the extreme values of both.
+        let result = match pred {
+            Predicate::Eq => Self::known_eq(lhs, rhs),
+            Predicate::Ne => Self::known_eq(lhs, rhs).map(|eq| !eq),
+            Predicate::Ult => Self::known_ult(lhs, rhs, false),
+            Predicate::Ugt => Self::known_ult(rhs, lhs, false),
+            Predicate::Uge => Self::known_ult(lhs, rhs, false).map(|lt| !lt),
+            Predicate::Ule => Self::known_ult(rhs, lhs, false).map(|gt| !gt),
+            Predicate::Slt => Self::known_ult(lhs, rhs, true),
+            Predicate::Sgt => Self::known_ult(rhs, lhs, true),
+            Predicate::Sge => Self::known_ult(lhs, rhs, true).map(|lt| !lt),
+            Predicate::Sle => Self::known_ult(rhs, lhs, true).map(|gt| !gt),
+        };
+
+        return match result {
+            Some(known) => KnownBits::constant(known as u128, 1),
+            None => KnownBits::empty(1),
+        };
     }
 
-    pub fn select(a: &KnownBits, b: &KnownBits, c: &KnownBits) -> KnownBits {
-        let mut out = KnownBits::default();
-        unsafe {
-            GetSelectKnownBits(a, b, c, &mut out);
+    // A bit known to differ decides equality, and otherwise both operands can be made equal or different.
+    fn known_eq(lhs: &KnownBits, rhs: &KnownBits) -> Option<bool> {
+        if (lhs.zeroes & rhs.ones) | (lhs.ones & rhs.zeroes) != 0 {
+            return Some(false);
+        }
+
+        return None;
+    }
+
+    fn known_ult(lhs: &KnownBits, rhs: &KnownBits, signed: bool) -> Option<bool> {
+        let (lhs_min, lhs_max) = lhs.range(signed);
+        let (rhs_min, rhs_max) = rhs.range(signed);
+        if lhs_max < rhs_min {
+            return Some(true);
+        }
+        if lhs_min >= rhs_max {
+            return Some(false);
+        }
+
+        return None;
+    }
+
+    // The smallest and largest possible value, sign extended for signed comparisons.
+    fn range(&self, signed: bool) -> (i128, i128) {
+        let width = self.width as u8;
+        if !signed {
+            // Unsigned values of up to 128 bits do not fit into an i128, so they are offset by the sign bit.
+            let flip = 1u128 << 127;
+            let (min, max) = (self.ones << (128 - width as u32), self.max_value() << (128 - width as u32));
+            return ((min ^ flip) as i128, (max ^ flip) as i128);
         }
-        out
+
+        // The sign bit is set in the minimum and clear in the maximum, unless it is known.
+        let sign_bit = 1u128 << (width - 1);
+        let min = self.ones | (self.max_value() & sign_bit);
+        let max = self.max_value() & !(sign_bit & !self.ones);
+        return (sign_extend(min, width), sign_extend(max, width));
+    }
+
+    pub fn select(a: &KnownBits, b: &KnownBits, c: &KnownBits) -> KnownBits {
+        return match a.as_constant() {
+            Some(0) => *c,
+            Some(_) => *b,
+            None => b.intersect(c),
+        };
     }
 
-    pub fn get_unknown_bits(&self) -> u64 {
+    pub fn get_unknown_bits(&self) -> u128 {
         let unknown_bits = (!(self.zeroes | self.ones)) & Self::get_modulo_mask(self.width as u8);
         return unknown_bits;
     }
@@ -152,7 +442,7 @@ impl KnownBits {
         return self.get_unknown_bits() == 0;
     }
 
-    pub fn as_constant(&self) -> Option<u64> {
+    pub fn as_constant(&self) -> Option<u128> {
         if self.is_constant() {
             return Some(self.ones);
         } else {
@@ -170,78 +460,323 @@ impl KnownBits {
         }
     }
 
-    fn get_modulo_mask(width: u8) -> u64 {
-        return u64::MAX >> (64 - width);
+    // The bits known in both.
+    pub fn intersect(&self, other: &KnownBits) -> KnownBits {
+        KnownBits {
+            width: self.width,
+            zeroes: self.zeroes & other.zeroes,
+            ones: self.ones & other.ones,
+        }
+    }
+
+    // Whether `value` is one of the values described by the known bits.
+    pub fn contains(&self, value: u128) -> bool {
+        return (value & self.zeroes) == 0 && (value & self.ones) == self.ones;
+    }
+
+    fn max_value(&self) -> u128 {
+        return !self.zeroes & Self::get_modulo_mask(self.width as u8);
+    }
+
+    fn min_trailing_zeroes(&self) -> u32 {
+        return self.zeroes.trailing_ones().min(self.width);
+    }
+
+    fn known_low_bits(&self) -> u32 {
+        return (self.zeroes | self.ones).trailing_ones().min(self.width);
+    }
+
+    // Get the known bits of a value that is known to be at most `max`.
+    fn with_max_value(width: u8, max: u128) -> KnownBits {
+        let mask = Self::get_modulo_mask(width);
+        let zeroes = match max {
+            0 => mask,
+            _ => !(u128::MAX >> max.leading_zeros()) & mask,
+        };
+
+        return KnownBits::new(width, zeroes, 0);
+    }
+
+    fn low_mask(bits: u32) -> u128 {
+        return u128::MAX.checked_shr(128 - bits).unwrap_or(0);
+    }
+
+    fn get_modulo_mask(width: u8) -> u128 {
+        return u128::MAX >> (128 - width as u32);
     }
 }
 
-#[link(name = "Mba.FFI", kind = "raw-dylib")]
-extern "C" {
-    fn GetAddKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetSubKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetMulKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetAndKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetOrKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetXorKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetNegKnownBits(lhs: *const KnownBits, out: *mut KnownBits) -> *const c_void;
-
-    fn GetShlKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetLshrKnownBits(
-        lhs: *const KnownBits,
-        rhs: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetZextKnownBits(lhs: *const KnownBits, width: u32, out: *mut KnownBits) -> *const c_void;
-
-    fn GetTruncKnownBits(lhs: *const KnownBits, width: u32, out: *mut KnownBits) -> *const c_void;
-
-    fn GetIcmpKnownBits(
-        pred: Predicate,
-        a: *const KnownBits,
-        b: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
-
-    fn GetSelectKnownBits(
-        a: *const KnownBits,
-        b: *const KnownBits,
-        c: *const KnownBits,
-        out: *mut KnownBits,
-    ) -> *const c_void;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every consistent assignment of known zeroes and ones at the given width.
+    fn all_known_bits(width: u8) -> Vec<KnownBits> {
+        let mut result = vec![KnownBits::empty(width)];
+        for bit in 0..width {
+            let mut next = Vec::new();
+            for kb in result {
+                next.push(kb);
+                next.push(KnownBits::new(width, kb.zeroes | (1 << bit), kb.ones));
+                next.push(KnownBits::new(width, kb.zeroes, kb.ones | (1 << bit)));
+            }
+            result = next;
+        }
+
+        return result;
+    }
+
+    fn values(kb: &KnownBits) -> Vec<u128> {
+        let mask = KnownBits::get_modulo_mask(kb.width as u8);
+        return (0..=mask).filter(|v| kb.contains(*v)).collect();
+    }
+
+    // The most precise known bits describing all of the given values.
+    fn abstraction(width: u8, values: impl Iterator<Item = u128>) -> KnownBits {
+        let mask = KnownBits::get_modulo_mask(width);
+        let mut result = KnownBits::new(width, mask, mask);
+        for v in values {
+            result = result.intersect(&KnownBits::constant(v, width));
+        }
+
+        return result;
+    }
+
+    // Check that `actual` knows no more than `expected`, and exactly as much if the transfer function is optimal.
+    fn check(actual: KnownBits, expected: KnownBits, optimal: bool, operands: &[&KnownBits]) {
+        assert_eq!(actual.width, expected.width);
+        let sound = (actual.zeroes & !expected.zeroes) == 0 && (actual.ones & !expected.ones) == 0;
+        assert!(sound, "unsound result {:?}, expected {:?} for {:?}", actual, expected, operands);
+        if optimal {
+            assert_eq!(actual, expected, "imprecise result for {:?}", operands);
+        }
+    }
+
+    fn check_unop(
+        width: u8,
+        out_width: u8,
+        transfer: impl Fn(&KnownBits) -> KnownBits,
+        concrete: impl Fn(u128) -> u128,
+        optimal: bool,
+    ) {
+        let mask = KnownBits::get_modulo_mask(out_width);
+        for a in all_known_bits(width) {
+            let expected = abstraction(out_width, values(&a).into_iter().map(|x| concrete(x) & mask));
+            check(transfer(&a), expected, optimal, &[&a]);
+        }
+    }
+
+    fn check_binop(
+        width: u8,
+        out_width: u8,
+        transfer: impl Fn(&KnownBits, &KnownBits) -> KnownBits,
+        concrete: impl Fn(u128, u128) -> u128,
+        optimal: bool,
+    ) {
+        let mask = KnownBits::get_modulo_mask(out_width);
+        let all = all_known_bits(width);
+        for a in all.iter() {
+            let a_values = values(a);
+            for b in all.iter() {
+                let b_values = values(b);
+                let results = a_values
+                    .iter()
+                    .flat_map(|x| b_values.iter().map(|y| concrete(*x, *y) & mask));
+                check(transfer(a, b), abstraction(out_width, results), optimal, &[a, b]);
+            }
+        }
+    }
+
+    const WIDTHS: [u8; 4] = [1, 2, 3, 4];
+
+    #[test]
+    fn bitwise_is_optimal() {
+        for w in WIDTHS {
+            check_binop(w, w, KnownBits::and, |a, b| a & b, true);
+            check_binop(w, w, KnownBits::or, |a, b| a | b, true);
+            check_binop(w, w, KnownBits::xor, |a, b| a ^ b, true);
+            check_unop(w, w, KnownBits::neg, |a| !a, true);
+        }
+
+        for w in [1, 2, 3] {
+            let all = all_known_bits(w);
+            for a in all.iter() {
+                for b in all.iter() {
+                    for c in all.iter() {
+                        let results = values(a).into_iter().flat_map(|x| {
+                            let (b, c) = (values(b), values(c));
+                            b.into_iter()
+                                .flat_map(move |y| c.clone().into_iter().map(move |z| (x & y) | (x & z) | (y & z)))
+                        });
+                        check(KnownBits::carry(a, b, c), abstraction(w, results), true, &[a, b, c]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn multiplication_is_sound() {
+        // Only the low bits and an upper bound of the product are tracked, so this is not optimal.
+        for w in WIDTHS {
+            check_binop(w, w, KnownBits::mul, |a, b| a.wrapping_mul(b), false);
+        }
+
+        let x = KnownBits::new(4, 0b0001, 0b0010);
+        assert_eq!(KnownBits::mul(&x, &x), KnownBits::new(4, 0b0011, 0b0100));
+    }
+
+    #[test]
+    fn division_is_sound() {
+        // Only constants and an upper bound of the result are tracked, so these are not optimal.
+        for w in WIDTHS {
+            check_binop(w, w, KnownBits::udiv, |a, b| udiv_const(a, b, w), false);
+            check_binop(w, w, KnownBits::urem, |a, b| urem_const(a, b, w), false);
+            check_binop(w, w, KnownBits::sdiv, |a, b| sdiv_const(a, b, w), false);
+            check_binop(w, w, KnownBits::srem, |a, b| srem_const(a, b, w), false);
+        }
+    }
+
+    #[test]
+    fn rotates_are_sound() {
+        // Rotating by an unknown amount loses every bit unless the value is uniform.
+        for w in WIDTHS {
+            check_binop(w, w, KnownBits::rotl, |a, b| rotl_const(a, b, w), false);
+            check_binop(w, w, KnownBits::rotr, |a, b| rotr_const(a, b, w), false);
+        }
+
+        // Rotating by a known amount is exact.
+        for w in WIDTHS {
+            for amount in 0..w as u128 {
+                let b = KnownBits::constant(amount, w);
+                check_unop(w, w, |a| KnownBits::rotl(a, &b), |a| rotl_const(a, amount, w), true);
+                check_unop(w, w, |a| KnownBits::rotr(a, &b), |a| rotr_const(a, amount, w), true);
+            }
+        }
+    }
+
+    #[test]
+    fn bit_counts_are_sound() {
+        // Only an upper bound of the count is tracked, so these are not optimal.
+        for w in WIDTHS {
+            check_unop(w, w, KnownBits::popcount, |a| popcount_const(a, w), false);
+            check_unop(w, w, KnownBits::ctlz, |a| ctlz_const(a, w), false);
+            check_unop(w, w, KnownBits::cttz, |a| cttz_const(a, w), false);
+        }
+    }
+
+    #[test]
+    fn addition_is_optimal() {
+        for w in WIDTHS {
+            let mask = KnownBits::get_modulo_mask(w);
+            check_binop(w, w, KnownBits::add, |a, b| a.wrapping_add(b), true);
+            check_binop(w, w, KnownBits::sub, |a, b| a.wrapping_sub(b), true);
+
+            let carry_in = [KnownBits::constant(0, 1), KnownBits::constant(1, 1), KnownBits::empty(1)];
+            for carry in carry_in.iter() {
+                let concrete = |a: u128, b: u128| {
+                    let c = values(carry);
+                    c.into_iter().map(move |c| a.wrapping_add(b).wrapping_add(c) & mask)
+                };
+                for a in all_known_bits(w).iter() {
+                    for b in all_known_bits(w).iter() {
+                        let results = values(a).into_iter().flat_map(|x| {
+                            values(b).into_iter().flat_map(move |y| concrete(x, y))
+                        });
+                        check(KnownBits::add_carry(a, b, carry), abstraction(w, results), true, &[a, b, carry]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shifts_are_optimal() {
+        for w in WIDTHS {
+            check_binop(w, w, KnownBits::shl, |a, b| shl_const(a, b, w), true);
+            check_binop(w, w, KnownBits::lshr, |a, b| lshr_const(a, b, w), true);
+            check_binop(w, w, KnownBits::ashr, |a, b| ashr_const(a, b, w), true);
+        }
+    }
+
+    #[test]
+    fn comparisons_are_optimal() {
+        let predicates = [
+            Predicate::Eq,
+            Predicate::Ne,
+            Predicate::Ugt,
+            Predicate::Uge,
+            Predicate::Ult,
+            Predicate::Ule,
+            Predicate::Sgt,
+            Predicate::Sge,
+            Predicate::Slt,
+            Predicate::Sle,
+        ];
+        for w in WIDTHS {
+            for pred in predicates {
+                let transfer = |a: &KnownBits, b: &KnownBits| KnownBits::icmp(pred, a, b);
+                check_binop(w, 1, transfer, |a, b| cmp(pred, a, b, w) as u128, true);
+            }
+        }
+    }
+
+    #[test]
+    fn casts_are_optimal() {
+        for w in WIDTHS {
+            check_unop(w, w + 2, |a| KnownBits::zext(a, w as u32 + 2), |a| a, true);
+            check_unop(w, w + 2, |a| KnownBits::sext(a, w as u32 + 2), |a| sign_extend(a, w) as u128, true);
+            for to in 1..=w {
+                check_unop(w, to, |a| KnownBits::trunc(a, to as u32), |a| a, true);
+            }
+            for low in 0..w {
+                for high in low..w {
+                    let transfer = |a: &KnownBits| KnownBits::extract(a, high, low);
+                    check_unop(w, 1 + high - low, transfer, |a| a >> low, true);
+                }
+            }
+        }
+
+        for (high, low) in [(1, 3), (3, 2), (2, 2)] {
+            let (highs, lows) = (all_known_bits(high), all_known_bits(low));
+            for a in highs.iter() {
+                for b in lows.iter() {
+                    let results = values(a)
+                        .into_iter()
+                        .flat_map(|x| values(b).into_iter().map(move |y| (x << low) | y));
+                    check(KnownBits::concat(a, b), abstraction(high + low, results), true, &[a, b]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn select_is_optimal() {
+        for w in [1, 2, 3] {
+            let all = all_known_bits(w);
+            for cond in all_known_bits(1).iter() {
+                for b in all.iter() {
+                    for c in all.iter() {
+                        let results = values(cond)
+                            .into_iter()
+                            .flat_map(|x| if x != 0 { values(b) } else { values(c) });
+                        check(KnownBits::select(cond, b, c), abstraction(w, results), true, &[cond, b, c]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_values() {
+        let x = KnownBits::new(128, 0xF, 1 << 127);
+        let one = KnownBits::constant(1, 128);
+        assert_eq!(KnownBits::add(&x, &one), KnownBits::new(128, 0xE, (1 << 127) | 1));
+        assert_eq!(KnownBits::sub(&one, &one), KnownBits::constant(0, 128));
+        assert_eq!(KnownBits::shl(&one, &KnownBits::constant(127, 128)), KnownBits::constant(1 << 127, 128));
+        assert_eq!(KnownBits::ashr(&x, &KnownBits::constant(120, 128)).ones, u128::MAX << 7);
+        assert_eq!(KnownBits::mul(&x, &x).zeroes, 0xFF);
+        assert_eq!(KnownBits::icmp(Predicate::Slt, &x, &one), KnownBits::constant(1, 1));
+        assert_eq!(KnownBits::icmp(Predicate::Ugt, &x, &one), KnownBits::constant(1, 1));
+    }
 }
@@ -5,7 +5,7 @@
 #![allow(unused)]
 
 use ahash::AHashMap;
-use egg::{BackoffScheduler, CostFunction, EGraph, Extractor, Id, Language, RecExpr, Runner};
+use egg::{BackoffScheduler, CostFunction, Extractor, Id, Language, RecExpr, Runner};
 use rand::Rng;
 
 // use egraph::simplify_via_eqsat;
@@ -30,21 +30,45 @@ use crate::{
 };
 
 // use egg::*;
-use simple_ast::{marshal_string, AstData, AstIdx, Context, Empty, SimpleAst};
+use simple_ast::{marshal_string, AstData, AstIdx, Empty, SimpleAst};
 
-use mimalloc::MiMalloc;
+pub use api::{ClassId, Context, EGraph, Error, Expr, Result, TruthTables, MAX_ANF_VARS};
+pub use ast_parser::{ParseError, ParseErrorKind, Span};
+pub use egg::StopReason;
+pub use printer::PrintOptions;
+pub use simple_ast::AstClass;
 
+// Only the shared library and command line builds enable the `mimalloc` feature.
+// Unit tests install a counting allocator instead, see `ffi::tests`.
+#[cfg(all(feature = "mimalloc", not(test)))]
 #[global_allocator]
-static GLOBAL: MiMalloc = MiMalloc;
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+pub mod api;
 mod assembler;
+mod ast_parser;
+mod codegen;
+mod constant_range;
+mod dot;
+mod eval;
 mod fbgb;
+mod ffi;
 
 
 mod known_bits;
 
 mod linalg;
+mod printer;
+mod rewrite;
+mod sexpr;
+mod shared_arena;
 mod simple_ast;
+mod smtlib;
+mod snapshot;
+mod stats;
+mod tape;
+#[cfg(test)]
+mod test_util;
 mod truth_table_database;
 
 #[path = "dsl/isle_rules.rs"]
@@ -53,7 +77,3 @@ mod isle_rules;
 mod egraph_rules;
 #[path = "dsl/isle_macros.rs"]
 mod isle_macros;
-
-
-
-fn main() {}
\ No newline at end of file
@@ -0,0 +1,481 @@
+// Configurable printer for the infix syntax accepted by `parse_ast`.
+//
+// Unlike `AstPrinter`, operands are only parenthesized where the precedence of the parser requires it, constants can be
+// printed in several styles, and width annotations can be limited to the leaves whose width differs from the default.
+// In the `let` binding mode every shared node is printed once, as a named definition preceding the result.
+use std::fmt::Write;
+
+use ahash::{AHashMap, AHashSet};
+use egg::Language;
+use libc::c_char;
+
+use crate::{
+    eval::post_order,
+    ffi::{self, FfiError},
+    rewrite::num_value_operands,
+    simple_ast::{get_modulo_mask, sign_extend, AstIdx, Context, INodeUtil, SimpleAst},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConstantStyle {
+    // Two's complement decimal, e.g. `-1`.
+    Signed,
+    // Unsigned decimal, e.g. `255` for `-1:i8`.
+    Unsigned,
+    // Unsigned hexadecimal, e.g. `0xFF`.
+    Hex,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WidthAnnotations {
+    Never,
+    // Annotate the constants and symbols whose width differs from the default width, which is enough for the output to
+    // be parsed back with that default width.
+    NonDefault,
+    Always,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PrintOptions {
+    pub constant_style: ConstantStyle,
+    pub width_annotations: WidthAnnotations,
+    pub default_width: u8,
+    // Name the nodes that are used more than once rather than printing them at every use.
+    pub let_bindings: bool,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            constant_style: ConstantStyle::Signed,
+            width_annotations: WidthAnnotations::NonDefault,
+            default_width: 64,
+            let_bindings: false,
+        }
+    }
+}
+
+// Precedence levels of the parser, from loosest to tightest binding.
+const SELECT: u8 = 0;
+const CMP: u8 = 1;
+const CONCAT: u8 = 2;
+const OR: u8 = 3;
+const XOR: u8 = 4;
+const AND: u8 = 5;
+const SHIFT: u8 = 6;
+const ADD: u8 = 7;
+const MUL: u8 = 8;
+const UNARY: u8 = 9;
+const POW: u8 = 10;
+const POSTFIX: u8 = 11;
+const ATOM: u8 = 12;
+
+impl Context {
+    pub fn print_with(&self, root: AstIdx, options: &PrintOptions) -> String {
+        let printer = Printer { ctx: self, options };
+
+        let order = post_order(self, root);
+        let mut uses: AHashMap<AstIdx, usize> = AHashMap::new();
+        let mut symbols = AHashSet::new();
+        for idx in order.iter() {
+            let ast = self.arena.get_node(*idx);
+            if let SimpleAst::Symbol { id, .. } = ast {
+                symbols.insert(self.arena.get_symbol_name(*id));
+            }
+            for child in ast.children()[..num_value_operands(ast)].iter() {
+                *uses.entry(*child).or_default() += 1;
+            }
+        }
+
+        // Pick a prefix that no symbol name starts with.
+        let mut prefix = "t".to_string();
+        while symbols.iter().any(|s| s.starts_with(&prefix)) {
+            prefix.push('_');
+        }
+
+        // Print every node after its operands rather than recursing from the root, so that deeply nested DAGs cannot
+        // overflow the stack. The text of an operand is dropped once its last user has been printed.
+        // With let bindings, every shared node is named in post order so that definitions precede their uses.
+        let mut out = String::new();
+        let mut texts: AHashMap<AstIdx, (String, u8)> = AHashMap::new();
+        let mut num_names = 0;
+        for idx in order.iter() {
+            let ast = self.arena.get_node(*idx);
+            let text = printer.print(*idx, &texts);
+            for child in ast.children()[..num_value_operands(ast)].iter() {
+                let remaining = uses.get_mut(child).unwrap();
+                *remaining -= 1;
+                if *remaining == 0 {
+                    texts.remove(child);
+                }
+            }
+
+            let is_leaf = matches!(ast, SimpleAst::Constant { .. } | SimpleAst::Symbol { .. });
+            if !options.let_bindings || is_leaf || *idx == root || uses.get(idx).copied().unwrap_or(0) < 2 {
+                texts.insert(*idx, text);
+                continue;
+            }
+
+            let name = format!("{}{}", prefix, num_names);
+            num_names += 1;
+            writeln!(out, "let {} = {}", name, text.0).unwrap();
+            texts.insert(*idx, (name, ATOM));
+        }
+
+        out.push_str(&texts[&root].0);
+        return out;
+    }
+}
+
+struct Printer<'a> {
+    ctx: &'a Context,
+    options: &'a PrintOptions,
+}
+
+impl Printer<'_> {
+    // Returns the text of a node along with the precedence of its outermost operator, given the text of its operands.
+    fn print(&self, idx: AstIdx, texts: &AHashMap<AstIdx, (String, u8)>) -> (String, u8) {
+        let ctx = self.ctx;
+        let ast = ctx.arena.get_node(idx);
+        let children = ast.children();
+        let constant = |i: usize| ctx.arena.get_constant(children[i]);
+        // Print an operand, parenthesizing it if it binds looser than `min`.
+        let operand = |i: usize, min: u8| {
+            let (s, prec) = &texts[&children[i]];
+            match *prec < min {
+                true => format!("({})", s),
+                false => s.clone(),
+            }
+        };
+        // All binary operators are left associative.
+        let binary = |op: &str, prec: u8| {
+            let s = format!("{} {} {}", operand(0, prec), op, operand(1, prec + 1));
+            (s, prec)
+        };
+
+        match ast {
+            SimpleAst::Constant { c, width } => self.constant(*c, *width),
+            SimpleAst::Symbol { id, width } => {
+                let name = ctx.arena.get_symbol_name(*id);
+                (format!("{}{}", name, self.annotation(*width)), ATOM)
+            }
+            SimpleAst::Add(_) => binary("+", ADD),
+            SimpleAst::Mul(_) => binary("*", MUL),
+            SimpleAst::Udiv(_) => binary("/", MUL),
+            SimpleAst::Urem(_) => binary("%", MUL),
+            SimpleAst::Sdiv(_) => binary("/s", MUL),
+            SimpleAst::Srem(_) => binary("%s", MUL),
+            SimpleAst::And(_) => binary("&", AND),
+            SimpleAst::Or(_) => binary("|", OR),
+            SimpleAst::Xor(_) => binary("^", XOR),
+            SimpleAst::Shl(_) => binary("<<", SHIFT),
+            SimpleAst::Lshr(_) => binary(">>", SHIFT),
+            SimpleAst::Ashr(_) => binary(">>s", SHIFT),
+            SimpleAst::Rotl(_) => binary("<<<", SHIFT),
+            SimpleAst::Rotr(_) => binary(">>>", SHIFT),
+            SimpleAst::Concat(_) => binary("++", CONCAT),
+            // The base of a power is a postfix expression, while the exponent may be negated.
+            SimpleAst::Pow(_) => (
+                format!("{} ** {}", operand(0, POSTFIX), operand(1, UNARY)),
+                POW,
+            ),
+            SimpleAst::Neg(_) => (format!("~{}", operand(0, UNARY)), UNARY),
+            SimpleAst::Zext(_) => (
+                format!("{} zx i{}", operand(0, POSTFIX), constant(1)),
+                POSTFIX,
+            ),
+            SimpleAst::Sext(_) => (
+                format!("{} sx i{}", operand(0, POSTFIX), constant(1)),
+                POSTFIX,
+            ),
+            SimpleAst::Trunc(_) => (
+                format!("{} tr i{}", operand(0, POSTFIX), constant(1)),
+                POSTFIX,
+            ),
+            SimpleAst::Extract(_) => (
+                format!("{}[{}:{}]", operand(0, POSTFIX), constant(1), constant(2)),
+                POSTFIX,
+            ),
+            // Comparisons do not chain, so both operands must bind tighter.
+            SimpleAst::ICmp { predicate, .. } => (
+                format!(
+                    "{} {} {}",
+                    operand(0, CONCAT),
+                    predicate,
+                    operand(1, CONCAT)
+                ),
+                CMP,
+            ),
+            // Both branches extend as far right as possible, so only the condition is restricted.
+            SimpleAst::Select { .. } => (
+                format!(
+                    "{} ? {} : {}",
+                    operand(0, CMP),
+                    operand(1, SELECT),
+                    operand(2, SELECT)
+                ),
+                SELECT,
+            ),
+            SimpleAst::Popcount(_) => (format!("popcount({})", operand(0, SELECT)), ATOM),
+            SimpleAst::Ctlz(_) => (format!("ctlz({})", operand(0, SELECT)), ATOM),
+            SimpleAst::Cttz(_) => (format!("cttz({})", operand(0, SELECT)), ATOM),
+            SimpleAst::Carry(_) => (
+                format!(
+                    "maj({}, {}, {})",
+                    operand(0, SELECT),
+                    operand(1, SELECT),
+                    operand(2, SELECT)
+                ),
+                ATOM,
+            ),
+        }
+    }
+
+    fn constant(&self, c: u128, width: u8) -> (String, u8) {
+        let c = c & get_modulo_mask(width);
+        let annotation = self.annotation(width);
+        match self.options.constant_style {
+            // A negative constant is parsed as a negation, so it binds like one.
+            ConstantStyle::Signed => match sign_extend(c, width) {
+                s if s < 0 => (format!("-{}{}", s.unsigned_abs(), annotation), UNARY),
+                s => (format!("{}{}", s, annotation), ATOM),
+            },
+            ConstantStyle::Unsigned => (format!("{}{}", c, annotation), ATOM),
+            ConstantStyle::Hex => (format!("0x{:X}{}", c, annotation), ATOM),
+        }
+    }
+
+    fn annotation(&self, width: u8) -> String {
+        let annotate = match self.options.width_annotations {
+            WidthAnnotations::Never => false,
+            WidthAnnotations::NonDefault => width != self.options.default_width,
+            WidthAnnotations::Always => true,
+        };
+
+        match annotate {
+            true => format!(":i{}", width),
+            false => String::new(),
+        }
+    }
+}
+
+// Fixed layout version of `PrintOptions` for FFI, with the enums stored as their index.
+#[repr(C)]
+pub struct FfiPrintOptions {
+    pub constant_style: u8,
+    pub width_annotations: u8,
+    pub default_width: u8,
+    pub let_bindings: u8,
+}
+
+impl TryFrom<&FfiPrintOptions> for PrintOptions {
+    type Error = FfiError;
+
+    fn try_from(options: &FfiPrintOptions) -> Result<Self, FfiError> {
+        Ok(PrintOptions {
+            constant_style: match options.constant_style {
+                0 => ConstantStyle::Signed,
+                1 => ConstantStyle::Unsigned,
+                2 => ConstantStyle::Hex,
+                style => return Err(FfiError::invalid(format!("unknown constant style {}", style))),
+            },
+            width_annotations: match options.width_annotations {
+                0 => WidthAnnotations::Never,
+                1 => WidthAnnotations::NonDefault,
+                2 => WidthAnnotations::Always,
+                mode => return Err(FfiError::invalid(format!("unknown width annotation mode {}", mode))),
+            },
+            default_width: ffi::width(options.default_width)?,
+            let_bindings: options.let_bindings != 0,
+        })
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextPrintWithOptions(
+    ctx: *const Context,
+    id: AstIdx,
+    options: *const FfiPrintOptions,
+) -> *mut c_char {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let options = PrintOptions::try_from(ffi::non_null_ref(options, "options")?)?;
+        let str = ctx.print_with(ffi::node(ctx, id)?, &options);
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast_parser::parse_ast, eval::Evaluator, test_util::new_ctx};
+
+    use super::*;
+
+    fn print(ctx: &mut Context, s: &str, options: &PrintOptions) -> String {
+        let idx = parse_ast(ctx, s, 64).unwrap();
+        return ctx.print_with(idx, options);
+    }
+
+    #[test]
+    fn minimal_parentheses() {
+        let mut ctx = new_ctx();
+        let options = PrintOptions::default();
+        let cases = [
+            ("(x & y) + (x | y) * 3", "(x & y) + 3 * (x | y)"),
+            ("(x + y) * z", "z * (x + y)"),
+            ("x - (y - z)", "x + -1 * (y + -1 * z)"),
+            ("(x ^ y) & z", "z & (x ^ y)"),
+            ("x ^ (y & z)", "x ^ y & z"),
+            ("(x ** 2) ** 3", "(x ** 2) ** 3"),
+            ("~(x + 1)", "~(1 + x)"),
+            ("(-2:i8) ** x:i8", "(-2:i8) ** x:i8"),
+            ("(x:i8 + y:i8) zx i64", "(x:i8 + y:i8) zx i64"),
+            (
+                "(x:i8 ++ y:i8) == z:i16 ? 1:i8 : 2:i8",
+                "x:i8 ++ y:i8 == z:i16 ? 1:i8 : 2:i8",
+            ),
+            (
+                "x:i1 ? (y:i1 ? 1:i8 : 2:i8) : 3:i8",
+                "x:i1 ? y:i1 ? 1:i8 : 2:i8 : 3:i8",
+            ),
+            ("(x:i1 ? y : z) + 1", "1 + (x:i1 ? y : z)"),
+            (
+                "popcount(x & y) << (z >>s 3)",
+                "popcount(x & y) << (z >>s 3)",
+            ),
+            (
+                "x[15:8] ++ maj(x:i8, y:i8, z:i8)",
+                "x[15:8] ++ maj(x:i8, y:i8, z:i8)",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let root = parse_ast(&mut ctx, input, 64).unwrap();
+            let printed = ctx.print_with(root, &options);
+            assert_eq!(printed, expected, "{}", input);
+            // The output is parsed back into the same node.
+            assert_eq!(
+                parse_ast(&mut ctx, &printed, 64).unwrap(),
+                root,
+                "{}",
+                printed
+            );
+        }
+    }
+
+    #[test]
+    fn constant_styles_and_annotations() {
+        let mut ctx = new_ctx();
+        let s = "(x:i8 + 0xF0:i8) zx i64 ^ 255";
+        let mut options = PrintOptions::default();
+        assert_eq!(print(&mut ctx, s, &options), "255 ^ (-16:i8 + x:i8) zx i64");
+
+        options.constant_style = ConstantStyle::Unsigned;
+        assert_eq!(print(&mut ctx, s, &options), "255 ^ (240:i8 + x:i8) zx i64");
+
+        options.constant_style = ConstantStyle::Hex;
+        assert_eq!(
+            print(&mut ctx, s, &options),
+            "0xFF ^ (0xF0:i8 + x:i8) zx i64"
+        );
+
+        options.width_annotations = WidthAnnotations::Always;
+        assert_eq!(
+            print(&mut ctx, s, &options),
+            "0xFF:i64 ^ (0xF0:i8 + x:i8) zx i64"
+        );
+
+        options.width_annotations = WidthAnnotations::Never;
+        assert_eq!(print(&mut ctx, s, &options), "0xFF ^ (0xF0 + x) zx i64");
+
+        // With a default width of 8, only the 64-bit leaves are annotated.
+        options.width_annotations = WidthAnnotations::NonDefault;
+        options.default_width = 8;
+        assert_eq!(print(&mut ctx, s, &options), "0xFF:i64 ^ (0xF0 + x) zx i64");
+    }
+
+    #[test]
+    fn let_bindings() {
+        let mut ctx = new_ctx();
+        let options = PrintOptions {
+            let_bindings: true,
+            ..Default::default()
+        };
+
+        let root = parse_ast(
+            &mut ctx,
+            "((x & y) + (x & y) * t0) ** ((x & y) + (x & y) * t0)",
+            64,
+        )
+        .unwrap();
+        let printed = ctx.print_with(root, &options);
+        assert_eq!(
+            printed,
+            "let t_0 = x & y\nlet t_1 = t_0 + t0 * t_0\nt_1 ** t_1"
+        );
+
+        // Substituting the definitions gives back an equivalent expression.
+        let mut lines: Vec<&str> = printed.lines().collect();
+        let mut expr = lines.pop().unwrap().to_string();
+        for line in lines.iter().rev() {
+            let (name, value) = line
+                .strip_prefix("let ")
+                .unwrap()
+                .split_once(" = ")
+                .unwrap();
+            expr = expr.replace(name, &format!("({})", value));
+        }
+        let reparsed = parse_ast(&mut ctx, &expr, 64).unwrap();
+        let vars: Vec<AstIdx> = ["t0", "x", "y"]
+            .iter()
+            .map(|s| ctx.arena.symbol_with_name(s.to_string(), 64))
+            .collect();
+        let mut a = Evaluator::new(&ctx, root, &vars);
+        let mut b = Evaluator::new(&ctx, reparsed, &vars);
+        assert_eq!(a.eval(&[3, 0x1234, 0xFF0]), b.eval(&[3, 0x1234, 0xFF0]));
+
+        // Nothing is named if there is no sharing.
+        assert_eq!(print(&mut ctx, "x + y", &options), "x + y");
+    }
+
+    #[test]
+    fn print_deep_dags() {
+        let mut ctx = new_ctx();
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let mut root = x;
+        for _ in 0..20000 {
+            root = ctx.arena.lshr(root, x);
+        }
+
+        let printed = ctx.print_with(root, &PrintOptions::default());
+        assert_eq!(printed, format!("x{}", " >> x".repeat(20000)));
+        assert_eq!(parse_ast(&mut ctx, &printed, 64).unwrap(), root);
+    }
+
+    #[test]
+    fn ffi_options() {
+        let mut ctx = new_ctx();
+        let root = parse_ast(&mut ctx, "x:i8 + 255:i8", 64).unwrap();
+        let print = |constant_style, width_annotations, default_width| unsafe {
+            let options = FfiPrintOptions {
+                constant_style,
+                width_annotations,
+                default_width,
+                let_bindings: 0,
+            };
+            let str = ContextPrintWithOptions(&ctx, root, &options);
+            if str.is_null() {
+                return Err(ffi::GetLastStatus());
+            }
+            let printed = std::ffi::CStr::from_ptr(str).to_str().unwrap().to_string();
+            ffi::FreeString(str);
+            Ok(printed)
+        };
+
+        assert_eq!(print(2, 1, 8).as_deref(), Ok("0xFF + x"));
+        assert_eq!(print(3, 1, 8), Err(ffi::FfiStatus::InvalidArgument));
+        assert_eq!(print(0, 3, 8), Err(ffi::FfiStatus::InvalidArgument));
+        assert_eq!(print(0, 1, 0), Err(ffi::FfiStatus::InvalidArgument));
+        assert_eq!(print(0, 1, 129), Err(ffi::FfiStatus::InvalidArgument));
+    }
+}
@@ -0,0 +1,310 @@
+// Bottom-up rewriting of the DAGs stored in an `Arena`.
+//
+// Each node reachable from the root is visited exactly once, after its operands, so sharing is preserved and the cost is
+// linear in the size of the DAG. Nodes whose operands changed are re-interned through the arena constructors, which
+// recomputes their `AstData` and applies the usual constant folding.
+use ahash::AHashMap;
+use egg::Language;
+use libc::c_void;
+
+use crate::{
+    eval::post_order,
+    ffi,
+    simple_ast::{AstIdx, Context, INodeUtil, SimpleAst},
+};
+
+impl Context {
+    // Rewrite the DAG rooted at `root` from the bottom up. For each node, `f` is given the original node and the node
+    // rebuilt on top of the rewritten operands, and may return a replacement for it of the same width.
+    pub fn rewrite(
+        &mut self,
+        root: AstIdx,
+        mut f: impl FnMut(&mut Context, AstIdx, AstIdx) -> Option<AstIdx>,
+    ) -> AstIdx {
+        return rewrite_dag(self, root, |_| None, |ctx, original, rebuilt| {
+            let replacement = f(ctx, original, rebuilt)?;
+            assert_eq!(
+                ctx.arena.get_width(original),
+                ctx.arena.get_width(replacement),
+                "rewrite must preserve the width of a node"
+            );
+            Some(replacement)
+        });
+    }
+
+    // Replace every occurrence of the keys in `mapping` with their values. Replacements are not rewritten themselves,
+    // so e.g. `x -> x + 1` is applied once rather than recursively.
+    pub fn substitute(&mut self, root: AstIdx, mapping: &AHashMap<AstIdx, AstIdx>) -> AstIdx {
+        for (from, to) in mapping.iter() {
+            assert_eq!(
+                self.arena.get_width(*from),
+                self.arena.get_width(*to),
+                "substitution must preserve the width of a node"
+            );
+        }
+
+        return rewrite_dag(self, root, |idx| mapping.get(&idx).copied(), |_, _, _| None);
+    }
+}
+
+// `replace` is queried before visiting the operands of a node, and stops the traversal if it returns a value.
+fn rewrite_dag(
+    ctx: &mut Context,
+    root: AstIdx,
+    mut replace: impl FnMut(AstIdx) -> Option<AstIdx>,
+    mut f: impl FnMut(&mut Context, AstIdx, AstIdx) -> Option<AstIdx>,
+) -> AstIdx {
+    let mut rewritten: AHashMap<AstIdx, AstIdx> = AHashMap::new();
+    let mut stack = vec![(root, false)];
+    while let Some((idx, operands_visited)) = stack.pop() {
+        if !operands_visited {
+            if rewritten.contains_key(&idx) {
+                continue;
+            }
+
+            if let Some(replacement) = replace(idx) {
+                rewritten.insert(idx, replacement);
+                continue;
+            }
+
+            stack.push((idx, true));
+            let node = ctx.arena.get_node(idx);
+            for child in node.children()[..num_value_operands(node)].iter().rev() {
+                if !rewritten.contains_key(child) {
+                    stack.push((*child, false));
+                }
+            }
+
+            continue;
+        }
+
+        // A node may be pushed more than once before it is visited, e.g. when it is used twice by the same parent.
+        if rewritten.contains_key(&idx) {
+            continue;
+        }
+
+        let mut node = ctx.arena.get_node(idx).clone();
+        let n = num_value_operands(&node);
+        let mut changed = false;
+        for child in node.children_mut()[..n].iter_mut() {
+            let new = rewritten[child];
+            changed |= new != *child;
+            *child = new;
+        }
+
+        let rebuilt = if changed {
+            ctx.arena.insert_node(node)
+        } else {
+            idx
+        };
+        let result = f(ctx, idx, rebuilt).unwrap_or(rebuilt);
+        rewritten.insert(idx, result);
+    }
+
+    return rewritten[&root];
+}
+
+// The number of leading operands that are expressions, as opposed to constant widths or bit indices.
+pub(crate) fn num_value_operands(node: &SimpleAst) -> usize {
+    match node {
+        SimpleAst::Zext(_) | SimpleAst::Sext(_) | SimpleAst::Trunc(_) | SimpleAst::Extract(_) => 1,
+        _ => node.children().len(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextSubstitute(
+    ctx: *mut Context,
+    root: AstIdx,
+    from: *const AstIdx,
+    to: *const AstIdx,
+    count: u64,
+) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let from = ffi::slice(from, count as usize, "from")?;
+        let to = ffi::slice(to, count as usize, "to")?;
+        ffi::nodes(ctx, from)?;
+        ffi::nodes(ctx, to)?;
+        for (a, b) in from.iter().zip(to.iter()) {
+            ffi::same_width(ctx, *a, *b)?;
+        }
+        let mapping: AHashMap<AstIdx, AstIdx> = from.iter().copied().zip(to.iter().copied()).collect();
+        Ok(ctx.substitute(ffi::node(ctx, root)?, &mapping))
+    })
+}
+
+// The callback receives the original node and the node rebuilt on top of the rewritten operands, and returns the node
+// to replace it with. Returning the rebuilt node leaves it unchanged. If the callback returns an invalid node or one of
+// a different width, it is not invoked again and the rewrite fails.
+#[no_mangle]
+pub unsafe extern "C" fn ContextRewrite(
+    ctx: *mut Context,
+    root: AstIdx,
+    callback: unsafe extern "C" fn(*mut Context, AstIdx, AstIdx, *mut c_void) -> AstIdx,
+    user_data: *mut c_void,
+) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let root = ffi::node(ctx, root)?;
+        let mut error = None;
+        let result = ctx.rewrite(root, |ctx, original, rebuilt| {
+            if error.is_some() {
+                return None;
+            }
+
+            let replacement = callback(ctx, original, rebuilt, user_data);
+            match ffi::same_width(ctx, original, replacement) {
+                Ok(()) => Some(replacement),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            }
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(result),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast_parser::parse_ast, eval::Evaluator, simple_ast::AstPrinter, test_util::new_ctx};
+
+    use super::*;
+
+    #[test]
+    fn substitute_symbols() {
+        let mut ctx = new_ctx();
+        let root = parse_ast(&mut ctx, "(x & y) + (x | y) * z", 64).unwrap();
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let y = ctx.arena.symbol_with_name("y".to_string(), 64);
+        let z = ctx.arena.symbol_with_name("z".to_string(), 64);
+
+        // Substituting every variable with a constant evaluates the expression.
+        let mapping = AHashMap::from([
+            (x, ctx.arena.constant(12, 64)),
+            (y, ctx.arena.constant(10, 64)),
+            (z, ctx.arena.constant(3, 64)),
+        ]);
+        let result = ctx.substitute(root, &mapping);
+        assert_eq!(ctx.arena.get_constant(result), 8 + 14 * 3);
+
+        // Replacements are not substituted again.
+        let x_plus_y = ctx.arena.add(x, y);
+        let result = ctx.substitute(root, &AHashMap::from([(x, x_plus_y), (y, x)]));
+        let expected = parse_ast(&mut ctx, "((x + y) & x) + ((x + y) | x) * z", 64).unwrap();
+        let mut a = Evaluator::new(&ctx, result, &[x, y, z]);
+        let mut b = Evaluator::new(&ctx, expected, &[x, y, z]);
+        for inputs in [[1, 2, 3], [u64::MAX as u128, 7, 9], [0x1234, 0xFFFF, 2]] {
+            assert_eq!(a.eval(&inputs), b.eval(&inputs));
+        }
+
+        // Substituting a subexpression replaces it wherever it is shared.
+        let x_and_y = ctx.arena.and(x, y);
+        let result = ctx.substitute(root, &AHashMap::from([(x_and_y, z)]));
+        assert_eq!(result, parse_ast(&mut ctx, "z + (x | y) * z", 64).unwrap());
+
+        // An empty mapping leaves the DAG untouched.
+        assert_eq!(ctx.substitute(root, &AHashMap::new()), root);
+    }
+
+    #[test]
+    fn substitute_preserves_sharing() {
+        let mut ctx = new_ctx();
+
+        // Each level references the previous one twice, so a naive tree walk would take 2**n steps.
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let y = ctx.arena.symbol_with_name("y".to_string(), 64);
+        let one = ctx.arena.constant(1, 64);
+        let mut idx = x;
+        for _ in 0..100000 {
+            let sum = ctx.arena.add(idx, one);
+            let a = ctx.arena.and(sum, y);
+            let b = ctx.arena.xor(sum, y);
+            idx = ctx.arena.or(a, b);
+        }
+
+        let len = ctx.arena.len();
+        let z = ctx.arena.symbol_with_name("z".to_string(), 64);
+        let result = ctx.substitute(idx, &AHashMap::from([(y, z)]));
+        // Only `z` and the nodes that depend on `y` are created, i.e. everything but the first `x + 1`.
+        assert_eq!(ctx.arena.len(), len + 1 + 4 * 100000 - 1);
+
+        let mut a = Evaluator::new(&ctx, idx, &[x, y]);
+        let mut b = Evaluator::new(&ctx, result, &[x, z]);
+        assert_eq!(a.eval(&[7, 0x1234]), b.eval(&[7, 0x1234]));
+    }
+
+    #[test]
+    fn rewrite_bottom_up() {
+        let mut ctx = new_ctx();
+        let root = parse_ast(&mut ctx, "(x:i16 * 2 + y:i16 * 2) ++ (x:i8 zx i16)", 64).unwrap();
+
+        // Strength reduce multiplications by two, observing that operands are rewritten first.
+        let mut visited = Vec::new();
+        let result = ctx.rewrite(root, |ctx, original, rebuilt| {
+            visited.push(original);
+            if let SimpleAst::Mul([a, b]) = ctx.arena.get_node(rebuilt).clone() {
+                if ctx.arena.is_constant(a) && ctx.arena.get_constant(a) == 2 {
+                    let one = ctx.arena.constant(1, ctx.arena.get_width(b));
+                    return Some(ctx.arena.shl(b, one));
+                }
+            }
+
+            None
+        });
+
+        let expected = parse_ast(&mut ctx, "((x:i16 << 1) + (y:i16 << 1)) ++ (x:i8 zx i16)", 64).unwrap();
+        assert_eq!(
+            AstPrinter::print(&ctx, ctx.arena.get_node(result)),
+            AstPrinter::print(&ctx, ctx.arena.get_node(expected))
+        );
+
+        // Every node is visited once, after its operands, and the width operand of the extension is not visited.
+        assert_eq!(*visited.last().unwrap(), root);
+        let mut unique = visited.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), visited.len());
+        assert!(visited.len() < post_order(&ctx, root).len());
+    }
+
+    unsafe extern "C" fn widen_symbols(ctx: *mut Context, _: AstIdx, rebuilt: AstIdx, _: *mut c_void) -> AstIdx {
+        let ctx = &mut *ctx;
+        match ctx.arena.get_node(rebuilt) {
+            SimpleAst::Symbol { .. } => ctx.arena.zext(rebuilt, 64),
+            _ => rebuilt,
+        }
+    }
+
+    unsafe extern "C" fn invalid_node(_: *mut Context, _: AstIdx, _: AstIdx, _: *mut c_void) -> AstIdx {
+        ffi::invalid_idx()
+    }
+
+    #[test]
+    fn rewrite_rejects_invalid_replacements() {
+        let mut ctx = new_ctx();
+        let root = parse_ast(&mut ctx, "x:i8 + y:i8", 64).unwrap();
+
+        unsafe {
+            let result = ContextRewrite(&mut ctx, root, widen_symbols, std::ptr::null_mut());
+            assert_eq!(result, ffi::invalid_idx());
+            assert_eq!(ffi::GetLastStatus(), ffi::FfiStatus::InvalidArgument);
+
+            let result = ContextRewrite(&mut ctx, root, invalid_node, std::ptr::null_mut());
+            assert_eq!(result, ffi::invalid_idx());
+            assert_eq!(ffi::GetLastStatus(), ffi::FfiStatus::InvalidHandle);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "rewrite must preserve the width of a node")]
+    fn rewrite_preserves_width() {
+        let mut ctx = new_ctx();
+        let root = parse_ast(&mut ctx, "x:i8 + y:i8", 64).unwrap();
+        ctx.rewrite(root, |ctx, _, rebuilt| Some(ctx.arena.zext(rebuilt, 16)));
+    }
+}
@@ -0,0 +1,632 @@
+// Reader and writer for the s-expression syntax understood by `impl egg::FromOp for SimpleAst`, e.g.
+//  (+ 1168 (* -32 (& 18 a3)))
+//
+// The vocabulary is the same as egg's:
+//  `+ * / % /s %s ** & | ^ ~ << >> >>s <<< >>> popcount ctlz cttz zx sx tr select ++ extract carry`,
+// plus `(icmp <pred> a b)` for comparisons since egg's "icmp <pred>" operator cannot be written as a single atom.
+// Constants and symbols may carry a `:w` width suffix(`5:64`, `x:8`). Unannotated symbols are assigned the default width,
+// and unannotated constants adopt the width of the expression they appear in.
+//
+// The writer emits the exact e-node structure used by egg, so the width operands of `zx`, `sx`, `tr` and `extract`
+// are printed as constants(e.g. `(zx x:8 64:8)`). Symbols are printed by name rather than by id.
+// Text following a `;` is treated as a comment.
+use std::ops::Range;
+
+use ahash::AHashMap;
+use egg::Language;
+
+use crate::{
+    ast_parser::{lower_expr, BinOp, Expr, ExprKind, ParseError, ParseErrorKind, Span},
+    simple_ast::{AstIdx, Context, Predicate, SimpleAst, MAX_WIDTH},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    LParen,
+    RParen,
+    Atom(&'a str),
+}
+
+fn tokenize(input: &str) -> Vec<(Token<'_>, Span)> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if c == b';' {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if c == b'(' || c == b')' {
+            let token = if c == b'(' {
+                Token::LParen
+            } else {
+                Token::RParen
+            };
+            tokens.push((token, Span::new(i, i + 1)));
+            i += 1;
+        } else {
+            let start = i;
+            while i < bytes.len()
+                && !bytes[i].is_ascii_whitespace()
+                && !matches!(bytes[i], b'(' | b')' | b';')
+            {
+                i += 1;
+            }
+            tokens.push((Token::Atom(&input[start..i]), Span::new(start, i)));
+        }
+    }
+
+    return tokens;
+}
+
+fn parse_predicate(s: &str) -> Option<Predicate> {
+    let predicate = match s {
+        "==" => Predicate::Eq,
+        "!=" => Predicate::Ne,
+        ">" => Predicate::Ugt,
+        ">=" => Predicate::Uge,
+        "<" => Predicate::Ult,
+        "<=" => Predicate::Ule,
+        ">s" => Predicate::Sgt,
+        ">=s" => Predicate::Sge,
+        "<s" => Predicate::Slt,
+        "<=s" => Predicate::Sle,
+        _ => return None,
+    };
+
+    Some(predicate)
+}
+
+// Split an atom into it's value and optional width suffix. Both `:64` and `:i64` are accepted.
+fn split_width(atom: &str, span: Span) -> Result<(&str, Option<u8>), ParseError> {
+    let Some((value, suffix)) = atom.rsplit_once(':') else {
+        return Ok((atom, None));
+    };
+
+    let digits = suffix.strip_prefix('i').unwrap_or(suffix);
+    let width = match digits.parse::<u64>() {
+        Ok(w) => w,
+        Err(_) => {
+            return Err(ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    expected: "width",
+                    found: suffix.to_string(),
+                },
+                span,
+            ))
+        }
+    };
+
+    if width == 0 || width > MAX_WIDTH as u64 {
+        return Err(ParseError::new(ParseErrorKind::InvalidWidth(width), span));
+    }
+
+    return Ok((value, Some(width as u8)));
+}
+
+fn parse_integer(s: &str) -> Option<Result<u128, ()>> {
+    let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    if radix == 10 && !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(u128::from_str_radix(digits, radix).map_err(|_| ()))
+}
+
+// A list whose operator has been read but not all of it's operands
+struct Frame<'a> {
+    op: &'a str,
+    op_span: Span,
+    // The opening parenthesis
+    start: Span,
+    predicate: Option<Predicate>,
+    operands: Vec<Expr>,
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    tokens: Vec<(Token<'a>, Span)>,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn unexpected(&self, expected: &'static str) -> ParseError {
+        match self.tokens.get(self.pos) {
+            Some((token, span)) => {
+                let found = match token {
+                    Token::LParen => "(".to_string(),
+                    Token::RParen => ")".to_string(),
+                    Token::Atom(s) => s.to_string(),
+                };
+                ParseError::new(ParseErrorKind::UnexpectedToken { expected, found }, *span)
+            }
+            None => ParseError::new(
+                ParseErrorKind::UnexpectedEndOfInput { expected },
+                Span::new(self.input.len(), self.input.len()),
+            ),
+        }
+    }
+
+    fn read_atom(&self, atom: &str, span: Span) -> Result<Expr, ParseError> {
+        let (value, width) = split_width(atom, span)?;
+
+        // Negative constants are allowed for convenience, e.g. `(* -32 a)`.
+        let (negate, magnitude) = match value.strip_prefix('-') {
+            Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => (true, rest),
+            _ => (false, value),
+        };
+
+        if let Some(c) = parse_integer(magnitude) {
+            let c = c.map_err(|_| ParseError::new(ParseErrorKind::ConstantOutOfRange, span))?;
+            let constant = Expr {
+                kind: ExprKind::Constant { c, width },
+                span,
+            };
+            if !negate {
+                return Ok(constant);
+            }
+
+            return Ok(Expr {
+                kind: ExprKind::Minus(Box::new(constant)),
+                span,
+            });
+        }
+
+        let is_name = value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_name {
+            return Err(ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    expected: "constant or symbol",
+                    found: atom.to_string(),
+                },
+                span,
+            ));
+        }
+
+        return Ok(Expr {
+            kind: ExprKind::Symbol {
+                name: value.to_string(),
+                width,
+            },
+            span,
+        });
+    }
+
+    // Read a width or bit index operand. These are stored as constants in the e-graph, so any width suffix is ignored.
+    fn read_immediate(&mut self) -> Result<u64, ParseError> {
+        if let Some((Token::Atom(atom), span)) = self.tokens.get(self.pos).cloned() {
+            let (value, _) = split_width(atom, span)?;
+            if let Some(Ok(c)) = parse_integer(value) {
+                self.pos += 1;
+                return Ok(u64::try_from(c).unwrap_or(u64::MAX));
+            }
+        }
+
+        return Err(self.unexpected("integer"));
+    }
+
+    // Number of expression operands taken by an operator, or None if the operator is unknown.
+    fn arity(op: &str) -> Option<usize> {
+        let arity = match op {
+            "+" | "*" | "/" | "%" | "/s" | "%s" | "**" | "&" | "|" | "^" | "<<" | ">>" | ">>s"
+            | "<<<" | ">>>" | "++" | "icmp" => 2,
+            "~" | "popcount" | "ctlz" | "cttz" | "zx" | "sx" | "tr" | "extract" => 1,
+            "select" | "carry" => 3,
+            _ => return None,
+        };
+
+        Some(arity)
+    }
+
+    // Iterative so that deeply nested input, e.g. the output of `print_sexpr` for a long chain, cannot overflow the stack.
+    fn read_expr(&mut self) -> Result<Expr, ParseError> {
+        let mut frames: Vec<Frame<'a>> = Vec::new();
+        loop {
+            let Some((token, span)) = self.tokens.get(self.pos).cloned() else {
+                return Err(self.unexpected("expression"));
+            };
+
+            let mut expr = match token {
+                Token::Atom(atom) => {
+                    self.pos += 1;
+                    self.read_atom(atom, span)?
+                }
+                Token::RParen => return Err(self.unexpected("expression")),
+                Token::LParen => {
+                    self.pos += 1;
+                    frames.push(self.read_operator(span)?);
+                    continue;
+                }
+            };
+
+            // Close every list which now has all of it's operands.
+            loop {
+                let Some(frame) = frames.last_mut() else {
+                    return Ok(expr);
+                };
+
+                frame.operands.push(expr);
+                if frame.operands.len() < Self::arity(frame.op).unwrap() {
+                    break;
+                }
+
+                let frame = frames.pop().unwrap();
+                expr = self.finish(frame)?;
+            }
+        }
+    }
+
+    // Read the operator following an opening parenthesis.
+    fn read_operator(&mut self, start: Span) -> Result<Frame<'a>, ParseError> {
+        let Some((Token::Atom(op), op_span)) = self.tokens.get(self.pos).cloned() else {
+            return Err(self.unexpected("operator"));
+        };
+        if Self::arity(op).is_none() {
+            return Err(ParseError::new(
+                ParseErrorKind::UnexpectedToken {
+                    expected: "operator",
+                    found: op.to_string(),
+                },
+                op_span,
+            ));
+        }
+        self.pos += 1;
+
+        let mut predicate = None;
+        if op == "icmp" {
+            predicate = match self.tokens.get(self.pos) {
+                Some((Token::Atom(p), _)) => parse_predicate(p),
+                _ => None,
+            };
+            if predicate.is_none() {
+                return Err(self.unexpected("comparison predicate"));
+            }
+            self.pos += 1;
+        }
+
+        return Ok(Frame {
+            op,
+            op_span,
+            start,
+            predicate,
+            operands: Vec::new(),
+        });
+    }
+
+    // Build a list once all of it's operands have been read, consuming any trailing immediates and the closing parenthesis.
+    fn finish(&mut self, frame: Frame<'a>) -> Result<Expr, ParseError> {
+        let op = frame.op;
+        let mut operands = frame.operands.into_iter().map(Box::new);
+        let mut next = || operands.next().unwrap();
+
+        let kind = match op {
+            "~" => ExprKind::Not(next()),
+            "popcount" => ExprKind::Popcount(next()),
+            "ctlz" => ExprKind::Ctlz(next()),
+            "cttz" => ExprKind::Cttz(next()),
+            "zx" | "sx" | "tr" => {
+                let a = next();
+                let to_span = self.tokens.get(self.pos).map(|t| t.1).unwrap_or(frame.op_span);
+                let to = self.read_immediate()?;
+                if to == 0 || to > MAX_WIDTH as u64 {
+                    return Err(ParseError::new(ParseErrorKind::InvalidWidth(to), to_span));
+                }
+
+                match op {
+                    "zx" => ExprKind::Zext(a, to as u8),
+                    "sx" => ExprKind::Sext(a, to as u8),
+                    _ => ExprKind::Trunc(a, to as u8),
+                }
+            }
+            "extract" => {
+                let a = next();
+                let high = self.read_immediate()?;
+                let low = self.read_immediate()?;
+                ExprKind::Extract(a, high, low)
+            }
+            "icmp" => ExprKind::ICmp(frame.predicate.unwrap(), next(), next()),
+            "select" => ExprKind::Select(next(), next(), next()),
+            "carry" => ExprKind::Carry(next(), next(), next()),
+            _ => {
+                let binop = match op {
+                    "+" => BinOp::Add,
+                    "*" => BinOp::Mul,
+                    "/" => BinOp::Udiv,
+                    "%" => BinOp::Urem,
+                    "/s" => BinOp::Sdiv,
+                    "%s" => BinOp::Srem,
+                    "**" => BinOp::Pow,
+                    "&" => BinOp::And,
+                    "|" => BinOp::Or,
+                    "^" => BinOp::Xor,
+                    "<<" => BinOp::Shl,
+                    ">>" => BinOp::Lshr,
+                    ">>s" => BinOp::Ashr,
+                    "<<<" => BinOp::Rotl,
+                    ">>>" => BinOp::Rotr,
+                    _ => BinOp::Concat,
+                };
+                ExprKind::Binary(binop, next(), next())
+            }
+        };
+
+        let end = match self.tokens.get(self.pos) {
+            Some((Token::RParen, end)) => *end,
+            _ => return Err(self.unexpected("`)`")),
+        };
+        self.pos += 1;
+
+        return Ok(Expr {
+            kind,
+            span: frame.start.join(end),
+        });
+    }
+}
+
+// Parse every top level s-expression in the input, e.g. a corpus file with one expression per line.
+pub fn parse_sexprs(
+    ctx: &mut Context,
+    input: &str,
+    default_width: u8,
+) -> Result<Vec<AstIdx>, ParseError> {
+    let mut reader = Reader {
+        input,
+        tokens: tokenize(input),
+        pos: 0,
+    };
+
+    let mut roots = Vec::new();
+    while reader.pos < reader.tokens.len() {
+        let expr = reader.read_expr()?;
+        roots.push(lower_expr(ctx, &expr, default_width)?);
+    }
+
+    return Ok(roots);
+}
+
+// Parse a single s-expression.
+pub fn parse_sexpr(
+    ctx: &mut Context,
+    input: &str,
+    default_width: u8,
+) -> Result<AstIdx, ParseError> {
+    let mut reader = Reader {
+        input,
+        tokens: tokenize(input),
+        pos: 0,
+    };
+
+    let expr = reader.read_expr()?;
+    if reader.pos < reader.tokens.len() {
+        return Err(reader.unexpected("end of input"));
+    }
+
+    return lower_expr(ctx, &expr, default_width);
+}
+
+// S-expressions cannot express sharing, so the text of a node is repeated at every use and the output grows with the
+// size of the expanded tree rather than the DAG. Each node is only walked once though, later uses copy its text.
+pub fn print_sexpr(ctx: &Context, idx: AstIdx) -> String {
+    let mut output = String::new();
+    print_sexpr_internal(ctx, idx, &mut output);
+    return output;
+}
+
+enum PrintStep {
+    Enter(AstIdx),
+    Space,
+    Close(AstIdx, usize),
+}
+
+// Iterative so that deep DAGs cannot overflow the stack.
+fn print_sexpr_internal(ctx: &Context, root: AstIdx, output: &mut String) {
+    let mut printed: AHashMap<AstIdx, Range<usize>> = AHashMap::new();
+    let mut stack = vec![PrintStep::Enter(root)];
+    while let Some(step) = stack.pop() {
+        match step {
+            PrintStep::Enter(idx) => {
+                if let Some(range) = printed.get(&idx) {
+                    output.extend_from_within(range.clone());
+                    continue;
+                }
+
+                let start = output.len();
+                let ast = ctx.arena.get_node(idx);
+                match ast {
+                    SimpleAst::Constant { c, width } => {
+                        output.push_str(&format!("{}:{}", c, width));
+                        printed.insert(idx, start..output.len());
+                        continue;
+                    }
+                    SimpleAst::Symbol { id, width } => {
+                        output.push_str(&format!("{}:{}", ctx.arena.get_symbol_name(*id), width));
+                        printed.insert(idx, start..output.len());
+                        continue;
+                    }
+                    SimpleAst::ICmp { predicate, .. } => output.push_str(&format!("(icmp {}", predicate)),
+                    _ => output.push_str(&format!("({}", ast)),
+                }
+
+                stack.push(PrintStep::Close(idx, start));
+                for child in ast.children().iter().rev() {
+                    stack.push(PrintStep::Enter(*child));
+                    stack.push(PrintStep::Space);
+                }
+            }
+            PrintStep::Space => output.push(' '),
+            PrintStep::Close(idx, start) => {
+                output.push(')');
+                printed.insert(idx, start..output.len());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::new_ctx;
+
+    use super::*;
+
+    #[test]
+    fn round_trip_all_variants() {
+        let mut ctx = new_ctx();
+        let a = &mut ctx.arena;
+        let x = a.symbol_with_name("x".to_string(), 64);
+        let y = a.symbol_with_name("y".to_string(), 64);
+        let b = a.symbol_with_name("b".to_string(), 8);
+        let c = a.constant(-32i64 as u128, 64);
+
+        let mut cases = Vec::new();
+        let and = a.and(c, x);
+        let mul = a.mul(c, y);
+        let pow = a.pow(x, c);
+        let or = a.or(and, mul);
+        let xor = a.xor(or, pow);
+        let not = a.neg(xor);
+        let shr = a.lshr(not, x);
+        let shl = a.shl(shr, y);
+        let sar = a.ashr(shl, x);
+        let add = a.add(sar, y);
+        cases.extend([and, mul, pow, or, xor, not, shr, shl, sar, add]);
+
+        let udiv = a.udiv(add, x);
+        let urem = a.urem(udiv, y);
+        let sdiv = a.sdiv(urem, c);
+        let srem = a.srem(sdiv, x);
+        let rotl = a.rotl(srem, y);
+        let rotr = a.rotr(rotl, x);
+        let popcount = a.popcount(rotr);
+        let ctlz = a.ctlz(popcount);
+        let cttz = a.cttz(y);
+        cases.extend([udiv, urem, sdiv, srem, rotl, rotr, popcount, ctlz, cttz]);
+
+        let x_tr = a.trunc(x, 8);
+        let b_ext = a.zext(b, 64);
+        let b_sext = a.sext(b, 64);
+        let ext = a.extract(x, 15, 8);
+        let concat = a.concat(ext, b);
+        let carry = a.carry(b, x_tr, ext);
+        cases.extend([x_tr, b_ext, b_sext, ext, concat, carry]);
+
+        for pred in [
+            Predicate::Eq,
+            Predicate::Ne,
+            Predicate::Ugt,
+            Predicate::Uge,
+            Predicate::Ult,
+            Predicate::Ule,
+            Predicate::Sgt,
+            Predicate::Sge,
+            Predicate::Slt,
+            Predicate::Sle,
+        ] {
+            let cmp = a.icmp(pred, x_tr, b);
+            let sel = a.select(cmp, b_ext, add);
+            cases.extend([cmp, sel]);
+        }
+
+        for case in cases {
+            let printed = print_sexpr(&ctx, case);
+            let parsed = parse_sexpr(&mut ctx, &printed, 64)
+                .unwrap_or_else(|e| panic!("{}", e.render(&printed)));
+            assert_eq!(case, parsed, "{}", printed);
+        }
+    }
+
+    #[test]
+    fn print_deep_and_shared_dags() {
+        let mut ctx = new_ctx();
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+
+        // A chain this deep would overflow the stack of a recursive printer or reader.
+        let mut deep = x;
+        for _ in 0..200_000 {
+            deep = ctx.arena.neg(deep);
+        }
+        let printed = print_sexpr(&ctx, deep);
+        assert_eq!(printed.len(), 200_000 * "(~ )".len() + "x:64".len());
+        assert!(printed.starts_with("(~ (~ ") && printed.trim_end_matches(')').ends_with("(~ x:64"));
+        assert_eq!(parse_sexpr(&mut ctx, &printed, 64).unwrap(), deep);
+
+        // Shared nodes are expanded at every use.
+        let mut shared = x;
+        for _ in 0..12 {
+            shared = ctx.arena.lshr(shared, shared);
+        }
+        let printed = print_sexpr(&ctx, shared);
+        assert_eq!(printed.matches("x:64").count(), 1 << 12);
+        assert_eq!(parse_sexpr(&mut ctx, &printed, 64).unwrap(), shared);
+    }
+
+    #[test]
+    fn read_egg_syntax() {
+        let mut ctx = new_ctx();
+        let input = "; A comment\n(+ 1168 (+ (+ (* -32 (& 18 a3)) (* 19 (& 32 a3))) (* 6 (& 64 a3))))\n(zx v0:8 64:8)\n(extract v1:64 15:8 8:8)";
+        let roots = parse_sexprs(&mut ctx, input, 64).unwrap();
+        assert_eq!(roots.len(), 3);
+
+        assert_eq!(ctx.arena.get_data(roots[0]).width, 64);
+        assert_eq!(ctx.arena.get_data(roots[1]).width, 64);
+        assert_eq!(ctx.arena.get_data(roots[2]).width, 8);
+        assert!(matches!(ctx.arena.get_node(roots[1]), SimpleAst::Zext(_)));
+
+        // Every e-node written by the printer should be accepted by egg's `FromOp`.
+        let printed = print_sexpr(&ctx, roots[2]);
+        assert_eq!(printed, "(extract v1:64 15:8 8:8)");
+
+        let infix = crate::ast_parser::parse_ast(
+            &mut ctx,
+            "1168 + -32*(18&a3) + 19*(32&a3) + 6*(64&a3)",
+            64,
+        )
+        .unwrap();
+        let sexpr = parse_sexpr(
+            &mut ctx,
+            "(+ 1168 (+ (* -32 (& 18 a3)) (+ (* 19 (& 32 a3)) (* 6 (& 64 a3)))))",
+            64,
+        )
+        .unwrap();
+        assert_eq!(infix, sexpr);
+    }
+
+    #[test]
+    fn report_errors() {
+        let mut ctx = new_ctx();
+        let err = |ctx: &mut Context, s: &str| parse_sexpr(ctx, s, 64).unwrap_err();
+
+        let e = err(&mut ctx, "(+ a)");
+        assert_eq!(e.span, Span::new(4, 5));
+        assert!(matches!(e.kind, ParseErrorKind::UnexpectedToken { .. }));
+
+        let e = err(&mut ctx, "(- a b)");
+        assert_eq!(e.span, Span::new(1, 2));
+
+        let e = err(&mut ctx, "(icmp =! a b)");
+        assert_eq!(e.span, Span::new(6, 8));
+
+        let e = err(&mut ctx, "(& a:8 b:16)");
+        assert_eq!(e.kind, ParseErrorKind::WidthMismatch { lhs: 8, rhs: 16 });
+
+        let e = err(&mut ctx, "(* a b");
+        assert!(matches!(
+            e.kind,
+            ParseErrorKind::UnexpectedEndOfInput { .. }
+        ));
+
+        let e = err(&mut ctx, "a b");
+        assert_eq!(e.span, Span::new(2, 3));
+    }
+}
@@ -0,0 +1,517 @@
+// Thread-safe node storage that can be shared by many `Context`s.
+//
+// Nodes live in append-only segments that are never moved or freed until the arena is dropped, so reading a node or
+// its `AstData` never takes a lock. Hash consing is split across independently locked shards, which keeps contention
+// low when several threads insert nodes at once. Each node also carries a slot for the result of `recursive_simplify`,
+// so work done by one thread is visible to the others.
+use std::{
+    hash::{BuildHasher, Hash, Hasher},
+    mem::MaybeUninit,
+    ptr,
+    sync::{
+        atomic::{AtomicPtr, AtomicU32, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use ahash::{AHashMap, RandomState};
+
+use crate::{
+    ffi::{self, FfiStatus},
+    simple_ast::{Arena, AstData, AstIdx, Context, SimpleAst},
+};
+
+// The first segment holds `BASE_CAPACITY` nodes, and each following segment is twice the size of the previous one.
+const BASE_SHIFT: u32 = 10;
+const BASE_CAPACITY: usize = 1 << BASE_SHIFT;
+const NUM_SEGMENTS: usize = (33 - BASE_SHIFT) as usize;
+
+const NUM_SHARDS: usize = 64;
+
+// Sentinel for a node that has not been simplified yet.
+const NOT_SIMPLIFIED: u32 = u32::MAX;
+
+struct Slot {
+    node: SimpleAst,
+    data: AstData,
+    simplified: AtomicU32,
+}
+
+#[derive(Default)]
+struct SymbolTable {
+    symbol_ids: Vec<(String, AstIdx)>,
+    name_to_symbol: AHashMap<(String, u8), u32>,
+}
+
+pub struct SharedArena {
+    segments: [AtomicPtr<MaybeUninit<Slot>>; NUM_SEGMENTS],
+    // Number of indices handed out to inserting threads.
+    reserved: AtomicU32,
+    // Number of slots that have been written. Slots are committed in index order, so every index below `len` is
+    // readable.
+    len: AtomicU32,
+    hasher: RandomState,
+    shards: Vec<Mutex<AHashMap<SimpleAst, AstIdx>>>,
+    symbols: RwLock<SymbolTable>,
+}
+
+// Slots are only written before their index is published, either through a shard lock or through `len`.
+unsafe impl Send for SharedArena {}
+unsafe impl Sync for SharedArena {}
+
+impl SharedArena {
+    pub fn new() -> Self {
+        // Slots are never dropped individually, which is only sound while nodes own no heap memory.
+        const { assert!(!std::mem::needs_drop::<Slot>()) };
+
+        SharedArena {
+            segments: std::array::from_fn(|_| AtomicPtr::new(ptr::null_mut())),
+            reserved: AtomicU32::new(0),
+            len: AtomicU32::new(0),
+            hasher: RandomState::new(),
+            shards: (0..NUM_SHARDS).map(|_| Mutex::new(AHashMap::new())).collect(),
+            symbols: RwLock::new(SymbolTable::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire) as usize
+    }
+
+    #[inline(always)]
+    fn locate(idx: usize) -> (usize, usize) {
+        let biased = idx + BASE_CAPACITY;
+        let segment = (usize::BITS - 1 - biased.leading_zeros() - BASE_SHIFT) as usize;
+        (segment, biased - (BASE_CAPACITY << segment))
+    }
+
+    fn segment_len(segment: usize) -> usize {
+        BASE_CAPACITY << segment
+    }
+
+    #[inline(always)]
+    fn slot(&self, idx: AstIdx) -> &Slot {
+        let (segment, offset) = Self::locate(idx.0 as usize);
+        unsafe {
+            let base = self.segments[segment].load(Ordering::Acquire);
+            debug_assert!(!base.is_null());
+            (*base.add(offset)).assume_init_ref()
+        }
+    }
+
+    // Returns the segment with the given index, allocating it if no other thread has done so yet.
+    fn get_or_alloc_segment(&self, segment: usize) -> *mut MaybeUninit<Slot> {
+        let existing = self.segments[segment].load(Ordering::Acquire);
+        if !existing.is_null() {
+            return existing;
+        }
+
+        let storage: Box<[MaybeUninit<Slot>]> = (0..Self::segment_len(segment))
+            .map(|_| MaybeUninit::uninit())
+            .collect();
+        let fresh = Box::into_raw(storage) as *mut MaybeUninit<Slot>;
+        match self.segments[segment].compare_exchange(
+            ptr::null_mut(),
+            fresh,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => fresh,
+            Err(winner) => {
+                unsafe { Self::free_segment(fresh, segment) };
+                winner
+            }
+        }
+    }
+
+    unsafe fn free_segment(base: *mut MaybeUninit<Slot>, segment: usize) {
+        let len = Self::segment_len(segment);
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(base, len)));
+    }
+
+    fn shard_of(&self, node: &SimpleAst) -> usize {
+        let mut hasher = self.hasher.build_hasher();
+        node.hash(&mut hasher);
+        (hasher.finish() as usize) % NUM_SHARDS
+    }
+
+    #[inline(always)]
+    pub fn get_node(&self, idx: AstIdx) -> &SimpleAst {
+        &self.slot(idx).node
+    }
+
+    #[inline(always)]
+    pub fn get_data(&self, idx: AstIdx) -> AstData {
+        self.slot(idx).data
+    }
+
+    pub fn lookup(&self, node: &SimpleAst) -> Option<AstIdx> {
+        let shard = self.shards[self.shard_of(node)].lock().unwrap();
+        shard.get(node).copied()
+    }
+
+    pub fn insert_ast_node(&self, node: SimpleAst, data: AstData) -> AstIdx {
+        let mut shard = self.shards[self.shard_of(&node)].lock().unwrap();
+        if let Some(&idx) = shard.get(&node) {
+            return idx;
+        }
+
+        let raw = self.reserved.fetch_add(1, Ordering::Relaxed);
+        assert!(raw != NOT_SIMPLIFIED, "shared arena is full");
+
+        let (segment, offset) = Self::locate(raw as usize);
+        let base = self.get_or_alloc_segment(segment);
+        unsafe {
+            (*base.add(offset)).write(Slot {
+                node: node.clone(),
+                data,
+                simplified: AtomicU32::new(NOT_SIMPLIFIED),
+            });
+        }
+
+        // Wait for the threads that reserved the preceding indices, so that `len` never covers a slot which is still
+        // being written. Those threads only wait on indices below their own, hence the wait always terminates.
+        // Yield rather than spin, since a preceding thread that has been preempted cannot make progress otherwise.
+        while self.len.load(Ordering::Acquire) != raw {
+            std::thread::yield_now();
+        }
+        self.len.store(raw + 1, Ordering::Release);
+
+        // The slot is fully written before its index becomes reachable through the shard.
+        let idx = AstIdx::from(raw as usize);
+        shard.insert(node, idx);
+        idx
+    }
+
+    pub fn get_simplified(&self, idx: AstIdx) -> Option<AstIdx> {
+        match self.slot(idx).simplified.load(Ordering::Acquire) {
+            NOT_SIMPLIFIED => None,
+            result => Some(AstIdx::from(result as usize)),
+        }
+    }
+
+    // Simplification is deterministic, so racing threads always store the same result.
+    pub fn set_simplified(&self, idx: AstIdx, result: AstIdx) {
+        self.slot(idx).simplified.store(result.0, Ordering::Release);
+    }
+
+    pub fn symbol_with_name(&self, name: String, width: u8, data: AstData) -> AstIdx {
+        {
+            let symbols = self.symbols.read().unwrap();
+            if let Some(&id) = symbols.name_to_symbol.get(&(name.clone(), width)) {
+                return symbols.symbol_ids[id as usize].1;
+            }
+        }
+
+        let mut symbols = self.symbols.write().unwrap();
+        // Another thread may have created the symbol between releasing the read lock and acquiring the write lock.
+        if let Some(&id) = symbols.name_to_symbol.get(&(name.clone(), width)) {
+            return symbols.symbol_ids[id as usize].1;
+        }
+
+        let symbol_id = symbols.symbol_ids.len() as u32;
+        let idx = self.insert_ast_node(
+            SimpleAst::Symbol {
+                id: symbol_id,
+                width,
+            },
+            data,
+        );
+        symbols.name_to_symbol.insert((name.clone(), width), symbol_id);
+        symbols.symbol_ids.push((name, idx));
+        idx
+    }
+
+    pub fn get_symbol_name(&self, symbol_id: u32) -> String {
+        self.symbols.read().unwrap().symbol_ids[symbol_id as usize].0.clone()
+    }
+}
+
+impl Drop for SharedArena {
+    fn drop(&mut self) {
+        for (segment, base) in self.segments.iter_mut().enumerate() {
+            let base = *base.get_mut();
+            if !base.is_null() {
+                unsafe { Self::free_segment(base, segment) };
+            }
+        }
+    }
+}
+
+impl Context {
+    // Create a context whose nodes live in `shared`. Each thread should use its own context.
+    pub fn from_shared(shared: Arc<SharedArena>) -> Self {
+        Context {
+            arena: Arena::from_shared(shared),
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn CreateSharedArena() -> *const SharedArena {
+    ffi::guard(|| Ok(Arc::into_raw(Arc::new(SharedArena::new()))))
+}
+
+// The arena stays alive until it has been freed and every context created from it is gone.
+#[no_mangle]
+pub unsafe extern "C" fn FreeSharedArena(arena: *const SharedArena) -> FfiStatus {
+    ffi::guard_status(|| {
+        drop(Arc::from_raw(ffi::non_null_ref(arena, "arena")?));
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextCreateShared(arena: *const SharedArena) -> *mut Context {
+    ffi::guard(|| {
+        let arena = ffi::non_null_ref(arena, "arena")?;
+        Arc::increment_strong_count(arena);
+        let ctx = Context::from_shared(Arc::from_raw(arena));
+        Ok(Box::into_raw(Box::new(ctx)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::{
+        ast_parser::parse_ast,
+        simple_ast::{
+            minimize_truth_table, recursive_simplify, AstPrinter, ContextAddRoot, ContextClear,
+            ContextCollectGarbage, ContextSetImutData,
+        },
+        snapshot::SnapshotError,
+        truth_table_database::{TruthTable, TruthTableDatabase},
+    };
+
+    use super::*;
+
+    const EXPRS: [&str; 4] = [
+        "(x & y) + (x | y) * 3 - (x ^ ~y)",
+        "((a ^ b) + 2 * (a & b)) * ((c | d) - (c & d))",
+        "(x:i8 <s y:i8 ? x:i8 : y:i8) ++ (z:i16[11:4] sx i16)",
+        "maj(x, y, 5) + popcount(x >> 3) * (x /s y) + ((x tr i1) zx i64)",
+    ];
+
+    #[test]
+    fn segment_layout() {
+        assert_eq!(SharedArena::locate(0), (0, 0));
+        assert_eq!(SharedArena::locate(BASE_CAPACITY - 1), (0, BASE_CAPACITY - 1));
+        assert_eq!(SharedArena::locate(BASE_CAPACITY), (1, 0));
+        assert_eq!(SharedArena::locate(3 * BASE_CAPACITY), (2, 0));
+        assert_eq!(SharedArena::locate(u32::MAX as usize - 1).0, NUM_SEGMENTS - 1);
+    }
+
+    #[test]
+    fn parallel_simplify() {
+        // Simplify every expression in a private context to obtain the expected output.
+        let mut local = Context {
+            arena: Arena::new(),
+        };
+        let expected: Vec<String> = EXPRS
+            .iter()
+            .map(|s| {
+                let idx = parse_ast(&mut local, s, 64).unwrap();
+                let result = recursive_simplify(&mut local, idx);
+                AstPrinter::print(&local, local.arena.get_node(result))
+            })
+            .collect();
+
+        let shared = Arc::new(SharedArena::new());
+        let results: Vec<Vec<(AstIdx, AstIdx)>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|t| {
+                    let shared = shared.clone();
+                    scope.spawn(move || {
+                        let mut ctx = Context::from_shared(shared);
+                        // Vary the order across threads so that nodes are interned concurrently from different roots.
+                        (0..EXPRS.len())
+                            .map(|i| (i + t) % EXPRS.len())
+                            .map(|i| {
+                                let idx = parse_ast(&mut ctx, EXPRS[i], 64).unwrap();
+                                (idx, recursive_simplify(&mut ctx, idx))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let ctx = Context::from_shared(shared.clone());
+        for (t, thread_results) in results.iter().enumerate() {
+            for (j, &(idx, result)) in thread_results.iter().enumerate() {
+                let i = (j + t) % EXPRS.len();
+                // Hash consing must hand out the same index to every thread.
+                assert_eq!((idx, result), results[0][i]);
+                assert_eq!(AstPrinter::print(&ctx, ctx.arena.get_node(result)), expected[i]);
+            }
+        }
+        assert!(shared.len() > 0);
+    }
+
+    #[test]
+    fn read_while_inserting() {
+        const WRITERS: u128 = 4;
+        const NODES: u128 = 20_000;
+
+        let shared = Arc::new(SharedArena::new());
+        let done = std::sync::atomic::AtomicBool::new(false);
+        thread::scope(|scope| {
+            let writers: Vec<_> = (0..WRITERS)
+                .map(|t| {
+                    let shared = shared.clone();
+                    scope.spawn(move || {
+                        let mut ctx = Context::from_shared(shared);
+                        for c in 0..NODES {
+                            ctx.arena.constant(c * WRITERS + t, 64);
+                        }
+                    })
+                })
+                .collect();
+
+            // Every handle that passes validation must refer to a fully written node.
+            scope.spawn(|| {
+                let ctx = Context::from_shared(shared.clone());
+                let mut checked = 0;
+                while !done.load(Ordering::Acquire) {
+                    let len = ctx.arena.len();
+                    for raw in checked..len {
+                        let idx = ffi::node(&ctx, AstIdx::from(raw)).unwrap();
+                        let SimpleAst::Constant { c, width } = *ctx.arena.get_node(idx) else {
+                            panic!("node {} is not a constant", raw);
+                        };
+                        assert_eq!(width, 64);
+                        assert_eq!(ctx.arena.get_data(idx).width, 64);
+                        assert!(c < WRITERS * NODES);
+                    }
+                    checked = len;
+                    thread::yield_now();
+                }
+            });
+
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            done.store(true, Ordering::Release);
+        });
+        assert_eq!(shared.len(), (WRITERS * NODES) as usize);
+    }
+
+    // Minimize every three variable truth table using the variables `vars`.
+    fn minimize_all(
+        ctx: &mut Context,
+        db: &TruthTableDatabase,
+        vars: &[AstIdx],
+        page: *mut u8,
+        offset: usize,
+    ) -> Vec<AstIdx> {
+        (0..256)
+            .map(|i| (i + offset) % 256)
+            .map(|i| {
+                let mut word = i as u64;
+                let table = TruthTable {
+                    num_vars: vars.len() as u32,
+                    arr: &mut word,
+                };
+                minimize_truth_table(ctx, db, &table, vars, page)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_minimize_anf() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/Minimization/TruthTables");
+        let db = TruthTableDatabase::load(std::path::Path::new(dir)).unwrap();
+        let names = ["a", "b", "c"];
+
+        let mut local = Context {
+            arena: Arena::new(),
+        };
+        // Minimize every table in a private context to obtain the expected output.
+        let vars: Vec<_> = names.iter().map(|n| local.arena.symbol_with_name(n.to_string(), 64)).collect();
+        let expected: Vec<String> = minimize_all(&mut local, &db, &vars, std::ptr::null_mut(), 0)
+            .into_iter()
+            .map(|idx| AstPrinter::print(&local, local.arena.get_node(idx)))
+            .collect();
+
+        // Shared contexts must fall back to the interpreter even if they are given a JIT page, so the page should
+        // never be written to.
+        let mut page = vec![0u8; 0x1000];
+        let page_addr = page.as_mut_ptr() as usize;
+        let shared = Arc::new(SharedArena::new());
+        let results: Vec<Vec<AstIdx>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|t| {
+                    let shared = shared.clone();
+                    let db = &db;
+                    scope.spawn(move || {
+                        let mut ctx = Context::from_shared(shared);
+                        let vars: Vec<_> =
+                            names.iter().map(|n| ctx.arena.symbol_with_name(n.to_string(), 64)).collect();
+                        minimize_all(&mut ctx, db, &vars, page_addr as *mut u8, t * 32)
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(page.iter().all(|b| *b == 0));
+
+        let ctx = Context::from_shared(shared);
+        for (t, thread_results) in results.iter().enumerate() {
+            for (j, &result) in thread_results.iter().enumerate() {
+                let i = (j + t * 32) % 256;
+                assert_eq!(result, results[0][i]);
+                assert_eq!(AstPrinter::print(&ctx, ctx.arena.get_node(result)), expected[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn shared_symbols() {
+        let shared = Arc::new(SharedArena::new());
+        let symbols: Vec<Vec<AstIdx>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    let shared = shared.clone();
+                    scope.spawn(move || {
+                        let mut ctx = Context::from_shared(shared);
+                        (0..200)
+                            .map(|i| ctx.arena.symbol_with_name(format!("v{}", i % 50), 8 << (i % 4)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert!(symbols.iter().all(|s| *s == symbols[0]));
+        let ctx = Context::from_shared(shared);
+        for (i, idx) in symbols[0].iter().enumerate() {
+            let SimpleAst::Symbol { id, width } = ctx.arena.get_node(*idx) else {
+                panic!("expected a symbol");
+            };
+            assert_eq!(ctx.arena.get_symbol_name(*id), format!("v{}", i % 50));
+            assert_eq!(*width, 8 << (i % 4));
+        }
+    }
+
+    #[test]
+    fn reject_local_operations() {
+        let shared = Arc::new(SharedArena::new());
+        let mut ctx = Context::from_shared(shared);
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        assert!(ctx.arena.local_mut().is_none());
+
+        // Operations that mutate or free nodes fail instead of racing with other threads.
+        let mut len = 0;
+        assert_eq!(ContextClear(&mut ctx, x), FfiStatus::InvalidArgument);
+        assert_eq!(ContextAddRoot(&mut ctx, x), FfiStatus::InvalidArgument);
+        assert!(ContextCollectGarbage(&mut ctx, &mut len).is_null());
+        assert_eq!(ffi::GetLastStatus(), FfiStatus::InvalidArgument);
+        assert_eq!(ContextSetImutData(&mut ctx, x, 1), FfiStatus::InvalidArgument);
+        assert!(matches!(ctx.write_snapshot(&mut Vec::new(), false), Err(SnapshotError::Shared)));
+        assert_eq!(ctx.arena.get_data(x).imut_data, 0);
+    }
+}
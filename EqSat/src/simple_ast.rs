@@ -4,8 +4,9 @@ use core::num;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     f32::consts::PI,
-    ffi::{CStr, CString},
+    ffi::CStr,
     ops::Add,
+    sync::Arc,
     time::Duration,
     u16, u64, vec,
 };
@@ -14,7 +15,7 @@ use std::{
 
 use ahash::AHashMap;
 use egg::{
-    Analysis, Applier, BackoffScheduler, CostFunction, DidMerge, EClass, EGraph, Extractor, Id, Language, PatternAst, RecExpr, Runner, Subst, Symbol, Var, define_language, rewrite
+    Analysis, Applier, BackoffScheduler, CostFunction, DidMerge, EClass, EGraph, Extractor, Id, Language, PatternAst, RecExpr, Runner, StopReason, Subst, Symbol, Var, define_language, rewrite
 };
 use iced_x86::{
     code_asm::{st, CodeAssembler},
@@ -24,9 +25,9 @@ use libc::{c_char, c_void};
 use std::marker::PhantomData;
 
 use crate::{
-     assembler::{
+     api, ast_parser::parse_ast, constant_range::{self, ConstantRange}, eval::{post_order, Evaluator}, sexpr::{parse_sexpr, print_sexpr}, shared_arena::SharedArena, assembler::{
         self, amd64_assembler::IAmd64Assembler, fast_amd64_assembler::FastAmd64Assembler, *,
-    }, egraph_rules::get_generated_rules, isle_defaults, isle_methods, known_bits::{self, *}, isle_rules::{self, Context as MbaContext}, truth_table_database::{TruthTable, TruthTableDatabase}
+    }, egraph_rules::get_generated_rules, ffi::{self, FfiError, FfiResult, FfiStatus}, isle_defaults, isle_methods, known_bits::{self, *}, isle_rules::{self, Context as MbaContext}, truth_table_database::{TruthTable, TruthTableDatabase}
 };
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -42,13 +43,29 @@ pub struct AstIdx(pub u32);
 pub type AstIdx = egg::Id;
 
 pub struct Arena {
+    storage: Storage,
+}
+
+enum Storage {
+    Local(LocalNodes),
+    // Nodes and symbols live in a thread-safe arena that is shared with other contexts.
+    Shared(Arc<SharedArena>),
+}
+
+// Nodes owned by a single context.
+// Other threads may be reading the nodes of a shared arena, so operations that mutate or free nodes are only
+// available on this type. Use `Arena::local_mut` to get at them.
+pub struct LocalNodes {
     pub elements: Vec<(SimpleAst, AstData)>,
-    ast_to_idx: AHashMap<SimpleAst, AstIdx>,
-    isle_cache: AHashMap<AstIdx, AstIdx>,
+    pub(crate) ast_to_idx: AHashMap<SimpleAst, AstIdx>,
+    pub(crate) isle_cache: AHashMap<AstIdx, AstIdx>,
 
     // Map a name to it's corresponds symbol index.
-    symbol_ids: Vec<(String, AstIdx)>,
-    name_to_symbol: AHashMap<(String, u8), u32>,
+    pub(crate) symbol_ids: Vec<(String, AstIdx)>,
+    pub(crate) name_to_symbol: AHashMap<(String, u8), u32>,
+
+    // Nodes that must survive garbage collection, along with their reference counts.
+    roots: AHashMap<AstIdx, u32>,
 }
 
 pub trait INodeUtil {
@@ -92,18 +109,26 @@ pub trait INodeUtil {
         return a_data.has_poly || b_data.has_poly;
     }
 
-    fn compute_bitwise_data(&self, a: AstIdx, b: AstIdx, known_bits: KnownBits) -> AstData {
+    fn compute_bitwise_data(
+        &self,
+        a: AstIdx,
+        b: AstIdx,
+        known_bits: KnownBits,
+        range: ConstantRange,
+    ) -> AstData {
         let width = self.get_bin_width(a, b);
         let cost = self.get_bin_cost(a, b);
         let has_poly = self.union_contains_poly_part(a, b);
 
         let max = self.compute_bitwise_class(a, b);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: max,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
 
@@ -168,6 +193,8 @@ pub trait INodeUtil {
         max = try_make_semilinear(max, d1.class, d2.class);
 
         let known_bits = KnownBits::add(&d1.known_bits, &d2.known_bits);
+        let range = ConstantRange::add(&d1.range, &d2.range);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
 
         let data = AstData {
             width: width,
@@ -175,6 +202,7 @@ pub trait INodeUtil {
             has_poly: has_poly,
             class: max,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
 
@@ -204,12 +232,15 @@ pub trait INodeUtil {
 
         max = try_make_semilinear(max, d1.class, d2.class);
         let known_bits = KnownBits::mul(&d1.known_bits, &d2.known_bits);
+        let range = ConstantRange::mul(&d1.range, &d2.range);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: max,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -228,6 +259,7 @@ pub trait INodeUtil {
             has_poly: true,
             class: AstClass::Nonlinear,
             known_bits: known_bits,
+            range: ConstantRange::full(width),
             imut_data: 0,
         };
         return data;
@@ -235,19 +267,22 @@ pub trait INodeUtil {
 
     fn and_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
         let kb = KnownBits::and(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
-        let data = self.compute_bitwise_data(a, b, kb);
+        let range = ConstantRange::and(&self.get_data(a).range, &self.get_data(b).range);
+        let data = self.compute_bitwise_data(a, b, kb, range);
         return data;
     }
 
     fn or_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
         let kb = KnownBits::or(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
-        let data = self.compute_bitwise_data(a, b, kb);
+        let range = ConstantRange::or(&self.get_data(a).range, &self.get_data(b).range);
+        let data = self.compute_bitwise_data(a, b, kb, range);
         return data;
     }
 
     fn xor_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
         let kb = KnownBits::xor(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
-        let data = self.compute_bitwise_data(a, b, kb);
+        let range = ConstantRange::full(self.get_width(a));
+        let data = self.compute_bitwise_data(a, b, kb, range);
         return data;
     }
 
@@ -259,12 +294,15 @@ pub trait INodeUtil {
         let c1 = self.get_class(a);
         let max = max_class(c1, c1, AstClass::Bitwise);
         let known_bits = KnownBits::neg(&self.get_data(a).known_bits);
+        let range = ConstantRange::neg(&self.get_data(a).range);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: max,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -278,17 +316,186 @@ pub trait INodeUtil {
 
         let known_bits =
             KnownBits::lshr(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        let range = ConstantRange::lshr(&self.get_data(a).range, &self.get_data(b).range);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
+        let data = AstData {
+            width: width,
+            cost: cost,
+            has_poly: has_poly,
+            class: class,
+            known_bits: known_bits,
+            range: range,
+            imut_data: 0,
+        };
+        return data;
+    }
+
+    fn shl_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let width = self.get_width(a);
+        let cost = self.get_bin_cost(a, b);
+        let has_poly = self.get_data(a).has_poly;
+        let class = AstClass::Nonlinear;
+
+        let known_bits =
+            KnownBits::shl(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        let range = ConstantRange::shl(&self.get_data(a).range, &self.get_data(b).range);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
+        let data = AstData {
+            width: width,
+            cost: cost,
+            has_poly: has_poly,
+            class: class,
+            known_bits: known_bits,
+            range: range,
+            imut_data: 0,
+        };
+        return data;
+    }
+
+    fn ashr_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let width = self.get_width(a);
+        let cost = self.get_bin_cost(a, b);
+        let has_poly = self.get_data(a).has_poly;
+        let class = AstClass::Nonlinear;
+
+        let known_bits =
+            KnownBits::ashr(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        let range = ConstantRange::ashr(&self.get_data(a).range, &self.get_data(b).range);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
+        let data = AstData {
+            width: width,
+            cost: cost,
+            has_poly: has_poly,
+            class: class,
+            known_bits: known_bits,
+            range: range,
+            imut_data: 0,
+        };
+        return data;
+    }
+
+    fn div_transfer(
+        &mut self,
+        a: AstIdx, b: AstIdx,
+        known_bits: KnownBits,
+        range: ConstantRange,
+    ) -> AstData {
+        let width = self.get_bin_width(a, b);
+        let cost = self.get_bin_cost(a, b);
+        let has_poly = self.get_has_poly(a) || self.get_has_poly(b);
+        let class = AstClass::Nonlinear;
+
+        let (known_bits, range) = constant_range::refine(known_bits, range);
+        let data = AstData {
+            width: width,
+            cost: cost,
+            has_poly: has_poly,
+            class: class,
+            known_bits: known_bits,
+            range: range,
+            imut_data: 0,
+        };
+        return data;
+    }
+
+    fn udiv_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let kb = KnownBits::udiv(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        let range = ConstantRange::udiv(&self.get_data(a).range, &self.get_data(b).range);
+        return self.div_transfer(a, b, kb, range);
+    }
+
+    fn urem_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let kb = KnownBits::urem(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        let range = ConstantRange::urem(&self.get_data(a).range, &self.get_data(b).range);
+        return self.div_transfer(a, b, kb, range);
+    }
+
+    fn sdiv_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let kb = KnownBits::sdiv(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        return self.div_transfer(a, b, kb, ConstantRange::full(kb.width as u8));
+    }
+
+    fn srem_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let kb = KnownBits::srem(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        return self.div_transfer(a, b, kb, ConstantRange::full(kb.width as u8));
+    }
+
+    fn rotate_transfer(
+        &mut self,
+        a: AstIdx, b: AstIdx,
+        known_bits: KnownBits,
+        range: ConstantRange,
+    ) -> AstData {
+        let width = self.get_width(a);
+        let cost = self.get_bin_cost(a, b);
+        let has_poly = self.get_data(a).has_poly;
+        let class = AstClass::Nonlinear;
+
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: class,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
     }
 
+    fn rotl_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let kb = KnownBits::rotl(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        return self.rotate_transfer(a, b, kb, ConstantRange::full(kb.width as u8));
+    }
+
+    fn rotr_transfer(&mut self, a: AstIdx, b: AstIdx) -> AstData {
+        let kb = KnownBits::rotr(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        return self.rotate_transfer(a, b, kb, ConstantRange::full(kb.width as u8));
+    }
+
+    fn bit_count_transfer(
+        &mut self,
+        a: AstIdx,
+        known_bits: KnownBits,
+        range: ConstantRange,
+    ) -> AstData {
+        let width = self.get_width(a);
+        let cost = (1 as u32).saturating_add(self.get_data(a).cost);
+        let has_poly = self.get_has_poly(a);
+        let class = AstClass::Nonlinear;
+
+        let (known_bits, range) = constant_range::refine(known_bits, range);
+        let data = AstData {
+            width: width,
+            cost: cost,
+            has_poly: has_poly,
+            class: class,
+            known_bits: known_bits,
+            range: range,
+            imut_data: 0,
+        };
+        return data;
+    }
+
+    fn popcount_transfer(&mut self, a: AstIdx) -> AstData {
+        let kb = KnownBits::popcount(&self.get_data(a).known_bits);
+        let range = ConstantRange::at_most(kb.width as u8, kb.width as u128);
+        return self.bit_count_transfer(a, kb, range);
+    }
+
+    fn ctlz_transfer(&mut self, a: AstIdx) -> AstData {
+        let kb = KnownBits::ctlz(&self.get_data(a).known_bits);
+        let range = ConstantRange::at_most(kb.width as u8, kb.width as u128);
+        return self.bit_count_transfer(a, kb, range);
+    }
+
+    fn cttz_transfer(&mut self, a: AstIdx) -> AstData {
+        let kb = KnownBits::cttz(&self.get_data(a).known_bits);
+        let range = ConstantRange::at_most(kb.width as u8, kb.width as u128);
+        return self.bit_count_transfer(a, kb, range);
+    }
+
     fn zext_transfer(&mut self, a: AstIdx, width: u8) -> AstData {
         let cost = (1 as u32).saturating_add(self.get_data(a).cost);
         let has_poly = self.get_has_poly(a);
@@ -296,12 +503,36 @@ pub trait INodeUtil {
         let mask = get_modulo_mask(self.get_width(a));
         let class = self.compute_bitwise_with_const_class(a);
         let known_bits = KnownBits::zext(&self.get_data(a).known_bits, width as u32);
+        let range = ConstantRange::zext(&self.get_data(a).range, width as u32);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
+        let data = AstData {
+            width: width,
+            cost: cost,
+            has_poly: has_poly,
+            class: class,
+            known_bits: known_bits,
+            range: range,
+            imut_data: 0,
+        };
+        return data;
+    }
+
+    fn sext_transfer(&mut self, a: AstIdx, width: u8) -> AstData {
+        let cost = (1 as u32).saturating_add(self.get_data(a).cost);
+        let has_poly = self.get_has_poly(a);
+
+        // Unlike zero extension, the new high bits depend on the sign bit.
+        let class = AstClass::Nonlinear;
+        let known_bits = KnownBits::sext(&self.get_data(a).known_bits, width as u32);
+        let range = ConstantRange::sext(&self.get_data(a).range, width as u32);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: class,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -314,24 +545,28 @@ pub trait INodeUtil {
         let mask = get_modulo_mask(width);
         let class = self.compute_bitwise_with_const_class(a);
         let known_bits = KnownBits::trunc(&self.get_data(a).known_bits, width as u32);
+        let range = ConstantRange::trunc(&self.get_data(a).range, width as u32);
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: class,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
     }
 
-    fn constant_transfer(&mut self, c: u64, width: u8) -> AstData {
+    fn constant_transfer(&mut self, c: u128, width: u8) -> AstData {
         let data = AstData {
             width: width,
             cost: 1,
             has_poly: false,
             class: AstClass::Bitwise,
             known_bits: KnownBits::constant(c, width),
+            range: ConstantRange::constant(c, width),
             imut_data: 0,
         };
 
@@ -345,6 +580,7 @@ pub trait INodeUtil {
             has_poly: false,
             class: AstClass::Bitwise,
             known_bits: KnownBits::empty(width),
+            range: ConstantRange::full(width),
             imut_data: 0,
         };
         return data;
@@ -361,12 +597,18 @@ pub trait INodeUtil {
             &self.get_data(a).known_bits,
             &self.get_data(b).known_bits,
         );
+        let range = match ConstantRange::icmp(pred, &self.get_data(a).range, &self.get_data(b).range) {
+            Some(known) => ConstantRange::constant(known as u128, width),
+            None => ConstantRange::full(width),
+        };
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: class,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -383,12 +625,19 @@ pub trait INodeUtil {
             &self.get_data(b).known_bits,
             &self.get_data(c).known_bits,
         );
+        let range = ConstantRange::select(
+            &self.get_data(a).range,
+            &self.get_data(b).range,
+            &self.get_data(c).range,
+        );
+        let (known_bits, range) = constant_range::refine(known_bits, range);
         let data = AstData {
             width: width,
             cost: cost,
             has_poly: has_poly,
             class: class,
             known_bits: known_bits,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -398,14 +647,17 @@ pub trait INodeUtil {
         let has_poly = self.get_data(a).has_poly;
         let width = 1 + high - low;
 
-        //  TODO: Knownbits and classification are overapproximated for now.
-        let kb = KnownBits::empty(width);
+        //  TODO: Classification is overapproximated for now.
+        let kb = KnownBits::extract(&self.get_data(a).known_bits, high, low);
+        let range = ConstantRange::extract(&self.get_data(a).range, high, low);
+        let (kb, range) = constant_range::refine(kb, range);
         let data = AstData {
             width: width,
             cost: 1 + self.get_data(a).cost,
             has_poly: has_poly,
             class: AstClass::Nonlinear,
             known_bits: kb,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -415,14 +667,17 @@ pub trait INodeUtil {
         let has_poly = self.union_contains_poly_part(a, b);
         let width = self.get_width(a) + self.get_width(b);
 
-        // TODO: Knownbits and classification are overapproximated for now.
-        let kb = KnownBits::empty(width);
+        // TODO: Classification is overapproximated for now.
+        let kb = KnownBits::concat(&self.get_data(a).known_bits, &self.get_data(b).known_bits);
+        let range = ConstantRange::concat(&self.get_data(a).range, &self.get_data(b).range);
+        let (kb, range) = constant_range::refine(kb, range);
         let data = AstData {
             width: width,
             cost: 1 + self.get_data(a).cost + self.get_data(b).cost,
             has_poly: has_poly,
             class: AstClass::Nonlinear,
             known_bits: kb,
+            range: range,
             imut_data: 0,
         };
         return data;
@@ -435,16 +690,20 @@ pub trait INodeUtil {
         let subclass =
             self.bitwise_class_transfer(self.get_class(a), self.get_class(b), has_constant);
 
-        // Knownbits is unimplemented for now.
         let class = self.bitwise_class_transfer(subclass, self.get_class(c), has_constant);
         let has_poly = self.union_contains_poly_part(a, b) || self.get_data(c).has_poly;
-        let kb = KnownBits::empty(width);
+        let kb = KnownBits::carry(
+            &self.get_data(a).known_bits,
+            &self.get_data(b).known_bits,
+            &self.get_data(c).known_bits,
+        );
         let data = AstData {
             width: width,
             cost: 1 + self.get_data(a).cost + self.get_data(b).cost + self.get_data(c).cost,
             has_poly: has_poly,
             class: class,
             known_bits: kb,
+            range: ConstantRange::from_known_bits(&kb),
             imut_data: 0,
         };
 
@@ -452,9 +711,19 @@ pub trait INodeUtil {
     }
 }
 
+impl INodeUtil for LocalNodes {
+    fn get_data(&self, idx: AstIdx) -> AstData {
+        LocalNodes::get_data(self, idx)
+    }
+
+    fn is_constant(&self, idx: AstIdx) -> bool {
+        matches!(self.get_node(idx), SimpleAst::Constant { .. })
+    }
+}
+
 impl INodeUtil for Arena {
     fn get_data(&self, idx: AstIdx) -> AstData {
-        unsafe { self.elements.get_unchecked(idx.0 as usize).1 }
+        Arena::get_data(self, idx)
     }
 
     fn is_constant(&self, idx: AstIdx) -> bool {
@@ -523,20 +792,46 @@ macro_rules! fold_and_sort {
 
 impl Arena {
     pub fn new() -> Self {
-        let elements = Vec::with_capacity(65536);
-        let ast_to_idx = AHashMap::with_capacity(65536);
-        let isle_cache = AHashMap::with_capacity(65536);
+        Arena::from_local(LocalNodes::new())
+    }
 
-        let symbol_ids = Vec::with_capacity(255);
-        let name_to_symbol = AHashMap::with_capacity(255);
+    pub fn from_local(local: LocalNodes) -> Self {
+        Arena {
+            storage: Storage::Local(local),
+        }
+    }
 
+    // Create a view over a shared arena. Any number of views may be used concurrently from different threads.
+    pub fn from_shared(shared: Arc<SharedArena>) -> Self {
         Arena {
-            elements: elements,
-            ast_to_idx: ast_to_idx,
-            isle_cache: isle_cache,
+            storage: Storage::Shared(shared),
+        }
+    }
 
-            symbol_ids: symbol_ids,
-            name_to_symbol: name_to_symbol,
+    pub fn is_shared(&self) -> bool {
+        matches!(self.storage, Storage::Shared(_))
+    }
+
+    // Returns the nodes of this context, or `None` if they live in a shared arena.
+    pub fn local(&self) -> Option<&LocalNodes> {
+        match &self.storage {
+            Storage::Local(local) => Some(local),
+            Storage::Shared(_) => None,
+        }
+    }
+
+    pub fn local_mut(&mut self) -> Option<&mut LocalNodes> {
+        match &mut self.storage {
+            Storage::Local(local) => Some(local),
+            Storage::Shared(_) => None,
+        }
+    }
+
+    // The number of nodes, which is one past the largest valid index.
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Local(local) => local.elements.len(),
+            Storage::Shared(shared) => shared.len(),
         }
     }
 
@@ -590,6 +885,18 @@ impl Arena {
             SimpleAst::Extract { .. } => 14,
             SimpleAst::Concat { .. } => 15,
             SimpleAst::Carry { .. } => 16,
+            SimpleAst::Shl(_) => 17,
+            SimpleAst::Ashr(_) => 18,
+            SimpleAst::Udiv(_) => 19,
+            SimpleAst::Urem(_) => 20,
+            SimpleAst::Sdiv(_) => 21,
+            SimpleAst::Srem(_) => 22,
+            SimpleAst::Rotl(_) => 23,
+            SimpleAst::Rotr(_) => 24,
+            SimpleAst::Sext(_) => 25,
+            SimpleAst::Popcount(_) => 26,
+            SimpleAst::Ctlz(_) => 27,
+            SimpleAst::Cttz(_) => 28,
         }
     }
 
@@ -629,7 +936,7 @@ impl Arena {
         let op2 = self.get_node(b);
         if let SimpleAst::Constant { c: c1, width } = op1 {
             if let SimpleAst::Constant { c: c2, width } = op2 {
-                let result = self.constant(Pow(*c1, *c2), self.get_width(a));
+                let result = self.constant(pow_const(*c1, *c2), self.get_width(a));
                 return result;
             }
         }
@@ -681,6 +988,271 @@ impl Arena {
         return self.insert_ast_node(SimpleAst::Lshr([a, b]), data);
     }
 
+    pub fn shl(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(shl_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        // Canonicalize shifts by a constant into multiplications, so that the linear
+        // machinery can still see through them.
+        if let SimpleAst::Constant { c, .. } = self.get_node(b) {
+            let width = self.get_width(a);
+            let factor = self.constant(shl_const(1, *c, width), width);
+            return self.mul(factor, a);
+        }
+
+        let data = self.shl_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Shl([a, b]), data);
+    }
+
+    pub fn ashr(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(ashr_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        let data = self.ashr_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Ashr([a, b]), data);
+    }
+
+    pub fn udiv(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(udiv_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        // x / 1 => x
+        if let SimpleAst::Constant { c: 1, .. } = self.get_node(b) {
+            return a;
+        }
+
+        let data = self.udiv_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Udiv([a, b]), data);
+    }
+
+    pub fn urem(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(urem_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        // x % 1 => 0
+        if let SimpleAst::Constant { c: 1, .. } = self.get_node(b) {
+            let width = self.get_width(a);
+            return self.constant(0, width);
+        }
+
+        let data = self.urem_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Urem([a, b]), data);
+    }
+
+    pub fn sdiv(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(sdiv_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        let data = self.sdiv_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Sdiv([a, b]), data);
+    }
+
+    pub fn srem(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(srem_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        let data = self.srem_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Srem([a, b]), data);
+    }
+
+    pub fn rotl(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(rotl_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        // x <<< 0 => x
+        if let SimpleAst::Constant { c, .. } = self.get_node(b) {
+            if *c % self.get_width(a) as u128 == 0 {
+                return a;
+            }
+        }
+
+        let data = self.rotl_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Rotl([a, b]), data);
+    }
+
+    pub fn rotr(&mut self, a: AstIdx, b: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            if let SimpleAst::Constant { c: c2, .. } = self.get_node(b) {
+                let result = self.constant(rotr_const(*c1, *c2, *width), *width);
+                return result;
+            }
+        }
+
+        // Canonicalize rotations by a constant to the left: x >>> c => x <<< (w - c)
+        if let SimpleAst::Constant { c, width: amount_width } = *self.get_node(b) {
+            let width = self.get_width(a) as u128;
+            let amount = self.constant((width - c % width) % width, amount_width);
+            return self.rotl(a, amount);
+        }
+
+        let data = self.rotr_transfer(a, b);
+        return self.insert_ast_node(SimpleAst::Rotr([a, b]), data);
+    }
+
+    // Match the two halves of a rotate by a constant, e.g. `(x << c) | (x >> (w - c))`.
+    // Either half may be and-ed with a mask, as long as the mask only clears bits that the shift already cleared.
+    // Returns the rotated value and the rotate amount.
+    pub fn match_rotate(&self, a: AstIdx, b: AstIdx) -> Option<(AstIdx, u128)> {
+        let strip_mask = |idx: AstIdx| {
+            if let SimpleAst::And([m, inner]) = *self.get_node(idx) {
+                if let SimpleAst::Constant { c, .. } = *self.get_node(m) {
+                    return (inner, c);
+                }
+            }
+
+            return (idx, u128::MAX);
+        };
+
+        for (hi, lo) in [(a, b), (b, a)] {
+            let (hi, hi_mask) = strip_mask(hi);
+            let (lo, lo_mask) = strip_mask(lo);
+
+            // Shifts left by a constant are canonicalized into multiplications.
+            let SimpleAst::Mul([factor, x]) = *self.get_node(hi) else {
+                continue;
+            };
+            let SimpleAst::Lshr([src, by]) = *self.get_node(lo) else {
+                continue;
+            };
+            let SimpleAst::Constant { c: factor, .. } = *self.get_node(factor) else {
+                continue;
+            };
+            let SimpleAst::Constant { c: by, .. } = *self.get_node(by) else {
+                continue;
+            };
+            if src != x || !factor.is_power_of_two() {
+                continue;
+            }
+
+            let width = self.get_width(x) as u128;
+            let amount = factor.trailing_zeros() as u128;
+            if amount == 0 || amount >= width || by != width - amount {
+                continue;
+            }
+
+            let full = get_modulo_mask(width as u8);
+            let low = (1u128 << amount) - 1;
+            if (hi_mask | low) & full != full || lo_mask & low != low {
+                continue;
+            }
+
+            return Some((x, amount));
+        }
+
+        return None;
+    }
+
+    pub fn popcount(&mut self, a: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            let result = self.constant(popcount_const(*c1, *width), *width);
+            return result;
+        }
+
+        let data = self.popcount_transfer(a);
+        return self.insert_ast_node(SimpleAst::Popcount([a]), data);
+    }
+
+    pub fn ctlz(&mut self, a: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            let result = self.constant(ctlz_const(*c1, *width), *width);
+            return result;
+        }
+
+        let data = self.ctlz_transfer(a);
+        return self.insert_ast_node(SimpleAst::Ctlz([a]), data);
+    }
+
+    pub fn cttz(&mut self, a: AstIdx) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width } = self.get_node(a) {
+            let result = self.constant(cttz_const(*c1, *width), *width);
+            return result;
+        }
+
+        let data = self.cttz_transfer(a);
+        return self.insert_ast_node(SimpleAst::Cttz([a]), data);
+    }
+
+    // Match `a + b` where both sides count the set bits of the same value under disjoint masks,
+    // returning the value and the combined mask. A lone bit `(x >> i) & 1` is treated as `popcount(x & (1 << i))`,
+    // so that sums of extracted bits fold into a single popcount.
+    pub fn match_popcount(&self, a: AstIdx, b: AstIdx) -> Option<(AstIdx, u128)> {
+        let (x, lhs_mask) = self.match_bit_count_term(a)?;
+        let (y, rhs_mask) = self.match_bit_count_term(b)?;
+        if x != y || lhs_mask & rhs_mask != 0 || self.get_width(x) != self.get_width(a) {
+            return None;
+        }
+
+        return Some((x, lhs_mask | rhs_mask));
+    }
+
+    fn match_bit_count_term(&self, idx: AstIdx) -> Option<(AstIdx, u128)> {
+        match *self.get_node(idx) {
+            SimpleAst::Popcount([inner]) => {
+                if let SimpleAst::And([m, x]) = *self.get_node(inner) {
+                    if let SimpleAst::Constant { c, .. } = *self.get_node(m) {
+                        return Some((x, c));
+                    }
+                }
+
+                return Some((inner, get_modulo_mask(self.get_width(inner))));
+            }
+            SimpleAst::And([one, x]) => {
+                let SimpleAst::Constant { c: 1, .. } = *self.get_node(one) else {
+                    return None;
+                };
+                if let SimpleAst::Lshr([src, by]) = *self.get_node(x) {
+                    if let SimpleAst::Constant { c: by, .. } = *self.get_node(by) {
+                        if by < self.get_width(src) as u128 {
+                            return Some((src, 1u128 << by));
+                        }
+                    }
+                }
+
+                return Some((x, 1));
+            }
+            SimpleAst::Zext([inner, _]) => {
+                let SimpleAst::Extract([x, high, low]) = *self.get_node(inner) else {
+                    return None;
+                };
+                let (high, low) = (self.get_constant(high), self.get_constant(low));
+                if high != low {
+                    return None;
+                }
+
+                return Some((x, 1u128 << low));
+            }
+            _ => None,
+        }
+    }
+
     pub fn zext(&mut self, a: AstIdx, width: u8) -> AstIdx {
         if let SimpleAst::Constant { c: c1, .. } = self.get_node(a) {
             let result = self.constant(*c1, width);
@@ -688,10 +1260,22 @@ impl Arena {
         }
 
         let data = self.zext_transfer(a, width);
-        let c = self.constant(width as u64, 8);
+        let c = self.constant(width as u128, 8);
         return self.insert_ast_node(SimpleAst::Zext([a, c]), data);
     }
 
+    pub fn sext(&mut self, a: AstIdx, width: u8) -> AstIdx {
+        if let SimpleAst::Constant { c: c1, width: from } = self.get_node(a) {
+            let extended = sign_extend(*c1, *from) as u128;
+            let result = self.constant(extended & get_modulo_mask(width), width);
+            return result;
+        }
+
+        let data = self.sext_transfer(a, width);
+        let c = self.constant(width as u128, 8);
+        return self.insert_ast_node(SimpleAst::Sext([a, c]), data);
+    }
+
     pub fn trunc(&mut self, a: AstIdx, width: u8) -> AstIdx {
         if let SimpleAst::Constant { c: c1, .. } = self.get_node(a) {
             let result = self.constant(*c1, width);
@@ -699,12 +1283,17 @@ impl Arena {
         }
 
         let data = self.trunc_transfer(a, width);
-        let c = self.constant(width as u64, 8);
+        let c = self.constant(width as u128, 8);
         return self.insert_ast_node(SimpleAst::Trunc([a, c]), data);
     }
 
     pub fn icmp(&mut self, pred: Predicate, a: AstIdx, b: AstIdx) -> AstIdx {
+        // Fold comparisons whose result follows from the known bits or ranges of the operands, e.g. (x & 15) <u 17.
         let data = self.icmp_transfer(pred, a, b);
+        if let Some(c) = data.known_bits.as_constant() {
+            return self.constant(c, 1);
+        }
+
         return self.insert_ast_node(
             SimpleAst::ICmp {
                 predicate: pred,
@@ -726,8 +1315,9 @@ impl Arena {
 
     pub fn extract(&mut self, a: AstIdx, b: u8, c: u8) -> AstIdx {
         let data = self.extract_transfer(a, b, c);
-        let b_node = self.constant(b as u64, data.width);
-        let c_node = self.constant(c as u64, data.width);
+        // Bit indices are stored like the widths of casts, since they need not fit in the result width.
+        let b_node = self.constant(b as u128, 8);
+        let c_node = self.constant(c as u128, 8);
         return self.insert_ast_node(SimpleAst::Extract([a, b_node, c_node]), data);
     }
 
@@ -741,60 +1331,45 @@ impl Arena {
         return self.insert_ast_node(SimpleAst::Carry([a, b, c]), data);
     }
 
-    pub fn constant(&mut self, c: u64, width: u8) -> AstIdx {
+    pub fn constant(&mut self, c: u128, width: u8) -> AstIdx {
         let data = self.constant_transfer(c, width);
         // Reduce the constant modulo 2**width
         let constant = get_modulo_mask(width) & c;
 
         return self.insert_ast_node(SimpleAst::Constant { c: constant, width }, data);
-    }
-
-    pub fn symbol(&mut self, id: u32, width: u8) -> AstIdx {
-        let data = self.symbol_transfer(width);
-        return self.insert_ast_node(
-            SimpleAst::Symbol {
-                id: id,
-                width: width,
-            },
-            data,
-        );
-    }
-
-    pub fn symbol_with_name(&mut self, name: String, width: u8) -> AstIdx {
-        if let Some(&id) = self.name_to_symbol.get(&(name.clone(), width)) {
-            return self.symbol_ids[id as usize].1;
-        }
-
-        // Compute an ID(index into a list of symbol names)
-        let symbol_id = self.symbol_ids.len() as u32;
-        self.name_to_symbol.insert((name.clone(), width), symbol_id);
+    }
 
+    pub fn symbol(&mut self, id: u32, width: u8) -> AstIdx {
         let data = self.symbol_transfer(width);
-
-        let symbol_ast_idx = self.insert_ast_node(
+        return self.insert_ast_node(
             SimpleAst::Symbol {
-                id: symbol_id,
+                id: id,
                 width: width,
             },
             data,
         );
-        self.symbol_ids.push((name, symbol_ast_idx));
-        return symbol_ast_idx;
+    }
+
+    pub fn symbol_with_name(&mut self, name: String, width: u8) -> AstIdx {
+        let data = self.symbol_transfer(width);
+        match &mut self.storage {
+            Storage::Local(local) => local.symbol_with_name(name, width, data),
+            Storage::Shared(shared) => shared.symbol_with_name(name, width, data),
+        }
     }
 
     pub fn get_symbol_name(&self, symbol_id: u32) -> String {
-        return self.symbol_ids[symbol_id as usize].0.clone();
+        match &self.storage {
+            Storage::Local(local) => local.get_symbol_name(symbol_id),
+            Storage::Shared(shared) => shared.get_symbol_name(symbol_id),
+        }
     }
 
     pub fn insert_ast_node(&mut self, node: SimpleAst, data: AstData) -> AstIdx {
-        if let Some(&idx) = self.ast_to_idx.get(&node) {
-            return idx;
+        match &mut self.storage {
+            Storage::Local(local) => local.insert_ast_node(node, data),
+            Storage::Shared(shared) => shared.insert_ast_node(node, data),
         }
-
-        let idx = AstIdx::from(self.elements.len() as usize);
-        self.elements.push((node.clone(), data));
-        self.ast_to_idx.insert(node, idx);
-        idx
     }
 
     pub fn insert_node(&mut self, node: SimpleAst) -> AstIdx {
@@ -807,8 +1382,20 @@ impl Arena {
             SimpleAst::Xor([a, b]) => self.xor(a, b),
             SimpleAst::Neg([a]) => self.neg(a),
             SimpleAst::Lshr([a, b]) => self.lshr(a, b),
+            SimpleAst::Shl([a, b]) => self.shl(a, b),
+            SimpleAst::Ashr([a, b]) => self.ashr(a, b),
+            SimpleAst::Udiv([a, b]) => self.udiv(a, b),
+            SimpleAst::Urem([a, b]) => self.urem(a, b),
+            SimpleAst::Sdiv([a, b]) => self.sdiv(a, b),
+            SimpleAst::Srem([a, b]) => self.srem(a, b),
+            SimpleAst::Rotl([a, b]) => self.rotl(a, b),
+            SimpleAst::Rotr([a, b]) => self.rotr(a, b),
             SimpleAst::Zext([a, to]) => self.zext(a, self.get_constant(to) as u8),
+            SimpleAst::Sext([a, to]) => self.sext(a, self.get_constant(to) as u8),
             SimpleAst::Trunc([a, to]) => self.trunc(a, self.get_constant(to) as u8),
+            SimpleAst::Popcount([a]) => self.popcount(a),
+            SimpleAst::Ctlz([a]) => self.ctlz(a),
+            SimpleAst::Cttz([a]) => self.cttz(a),
             SimpleAst::Constant { c, width } => self.constant(c, width),
             SimpleAst::Symbol { id, width } => self.symbol(id, width),
             SimpleAst::ICmp {
@@ -828,7 +1415,10 @@ impl Arena {
 
     #[inline(always)]
     pub fn get_node(&self, idx: AstIdx) -> &SimpleAst {
-        unsafe { &self.elements.get_unchecked(idx.0 as usize).0 }
+        match &self.storage {
+            Storage::Local(local) => local.get_node(idx),
+            Storage::Shared(shared) => shared.get_node(idx),
+        }
     }
 
     pub fn is_constant(&self, idx: AstIdx) -> bool {
@@ -839,7 +1429,7 @@ impl Arena {
         }
     }
 
-    pub fn get_constant(&self, idx: AstIdx) -> u64 {
+    pub fn get_constant(&self, idx: AstIdx) -> u128 {
         let ast = self.get_node(idx);
         match ast {
             SimpleAst::Constant { c, width } => *c,
@@ -847,6 +1437,101 @@ impl Arena {
         }
     }
 
+    pub fn get_data(&self, idx: AstIdx) -> AstData {
+        match &self.storage {
+            Storage::Local(local) => local.get_data(idx),
+            Storage::Shared(shared) => shared.get_data(idx),
+        }
+    }
+
+    // Returns the index of an existing node, if any.
+    pub fn lookup_idx(&self, node: &SimpleAst) -> Option<AstIdx> {
+        match &self.storage {
+            Storage::Local(local) => local.ast_to_idx.get(node).copied(),
+            Storage::Shared(shared) => shared.lookup(node),
+        }
+    }
+
+    // Returns the cached result of `recursive_simplify`, if any.
+    pub fn get_simplified(&self, idx: AstIdx) -> Option<AstIdx> {
+        match &self.storage {
+            Storage::Local(local) => local.isle_cache.get(&idx).copied(),
+            Storage::Shared(shared) => shared.get_simplified(idx),
+        }
+    }
+
+    pub fn set_simplified(&mut self, idx: AstIdx, result: AstIdx) {
+        match &mut self.storage {
+            Storage::Local(local) => {
+                local.isle_cache.insert(idx, result);
+            }
+            Storage::Shared(shared) => shared.set_simplified(idx, result),
+        }
+    }
+}
+
+impl LocalNodes {
+    pub fn new() -> Self {
+        let elements = Vec::with_capacity(65536);
+        let ast_to_idx = AHashMap::with_capacity(65536);
+        let isle_cache = AHashMap::with_capacity(65536);
+
+        let symbol_ids = Vec::with_capacity(255);
+        let name_to_symbol = AHashMap::with_capacity(255);
+
+        LocalNodes {
+            elements: elements,
+            ast_to_idx: ast_to_idx,
+            isle_cache: isle_cache,
+
+            symbol_ids: symbol_ids,
+            name_to_symbol: name_to_symbol,
+
+            roots: AHashMap::new(),
+        }
+    }
+
+    fn symbol_with_name(&mut self, name: String, width: u8, data: AstData) -> AstIdx {
+        if let Some(&id) = self.name_to_symbol.get(&(name.clone(), width)) {
+            return self.symbol_ids[id as usize].1;
+        }
+
+        // Compute an ID(index into a list of symbol names)
+        let symbol_id = self.symbol_ids.len() as u32;
+        self.name_to_symbol.insert((name.clone(), width), symbol_id);
+
+        let symbol_ast_idx = self.insert_ast_node(
+            SimpleAst::Symbol {
+                id: symbol_id,
+                width: width,
+            },
+            data,
+        );
+        self.symbol_ids.push((name, symbol_ast_idx));
+        return symbol_ast_idx;
+    }
+
+    fn get_symbol_name(&self, symbol_id: u32) -> String {
+        return self.symbol_ids[symbol_id as usize].0.clone();
+    }
+
+    fn insert_ast_node(&mut self, node: SimpleAst, data: AstData) -> AstIdx {
+        if let Some(&idx) = self.ast_to_idx.get(&node) {
+            return idx;
+        }
+
+        let idx = AstIdx::from(self.elements.len() as usize);
+        self.elements.push((node.clone(), data));
+        self.ast_to_idx.insert(node, idx);
+        idx
+    }
+
+    #[inline(always)]
+    pub fn get_node(&self, idx: AstIdx) -> &SimpleAst {
+        unsafe { &self.elements.get_unchecked(idx.0 as usize).0 }
+    }
+
+    #[inline(always)]
     pub fn get_data(&self, idx: AstIdx) -> AstData {
         unsafe { self.elements.get_unchecked(idx.0 as usize).1 }
     }
@@ -865,6 +1550,105 @@ impl Arena {
         self.isle_cache.clear();
         self.symbol_ids.clear();
         self.name_to_symbol.clear();
+        self.roots.clear();
+    }
+
+    // Register `idx` as a root, keeping it and every node it references alive across garbage collections.
+    // Roots are reference counted, so each call must be paired with a call to `remove_root`.
+    pub fn add_root(&mut self, idx: AstIdx) {
+        *self.roots.entry(idx).or_insert(0) += 1;
+    }
+
+    pub fn remove_root(&mut self, idx: AstIdx) {
+        if let Entry::Occupied(mut entry) = self.roots.entry(idx) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
+    // Free every node that is not reachable from a root, then compact the surviving nodes to the front of the arena.
+    // Surviving nodes keep their relative order, so operands still precede their users.
+    // Returns a table mapping each old index to its new index, or `None` if the node was freed.
+    pub fn collect_garbage(&mut self) -> Vec<Option<AstIdx>> {
+        // Mark every node reachable from a root.
+        // Symbols also keep alive the node that their id was registered with, so the symbol table never dangles.
+        let mut live = vec![false; self.elements.len()];
+        let mut stack: Vec<AstIdx> = self.roots.keys().copied().collect();
+        while let Some(idx) = stack.pop() {
+            if live[idx.0 as usize] {
+                continue;
+            }
+
+            live[idx.0 as usize] = true;
+            let node = self.get_node(idx);
+            if let SimpleAst::Symbol { id, .. } = node {
+                stack.push(self.symbol_ids[*id as usize].1);
+            }
+            stack.extend_from_slice(node.children());
+        }
+
+        // Renumber the surviving symbols, preserving their relative order.
+        let mut symbol_remap = vec![None; self.symbol_ids.len()];
+        let old_symbols = std::mem::take(&mut self.symbol_ids);
+        for (id, (name, idx)) in old_symbols.into_iter().enumerate() {
+            if live[idx.0 as usize] {
+                symbol_remap[id] = Some(self.symbol_ids.len() as u32);
+                self.symbol_ids.push((name, idx));
+            }
+        }
+
+        self.name_to_symbol.retain(|_, id| match symbol_remap[*id as usize] {
+            Some(new_id) => {
+                *id = new_id;
+                true
+            }
+            None => false,
+        });
+
+        // Move the surviving nodes to the front, rewriting their operands as we go.
+        let mut remap: Vec<Option<AstIdx>> = vec![None; self.elements.len()];
+        let old_elements = std::mem::take(&mut self.elements);
+        self.elements = Vec::with_capacity(live.iter().filter(|l| **l).count());
+        self.ast_to_idx.clear();
+        for (i, (mut node, data)) in old_elements.into_iter().enumerate() {
+            if !live[i] {
+                continue;
+            }
+
+            for child in node.children_mut() {
+                *child = remap[child.0 as usize].unwrap();
+            }
+            if let SimpleAst::Symbol { id, .. } = &mut node {
+                *id = symbol_remap[*id as usize].unwrap();
+            }
+
+            let idx = AstIdx::from(self.elements.len());
+            self.ast_to_idx.insert(node.clone(), idx);
+            self.elements.push((node, data));
+            remap[i] = Some(idx);
+        }
+
+        for (_, idx) in self.symbol_ids.iter_mut() {
+            *idx = remap[idx.0 as usize].unwrap();
+        }
+
+        // Only keep the cached ISLE results where both sides survived.
+        let old_cache = std::mem::take(&mut self.isle_cache);
+        for (from, to) in old_cache {
+            if let (Some(from), Some(to)) = (remap[from.0 as usize], remap[to.0 as usize]) {
+                self.isle_cache.insert(from, to);
+            }
+        }
+
+        self.roots = self
+            .roots
+            .drain()
+            .map(|(idx, count)| (remap[idx.0 as usize].unwrap(), count))
+            .collect();
+
+        return remap;
     }
 }
 
@@ -908,6 +1692,9 @@ pub struct AstData {
     // Known zero or one bits
     pub known_bits: KnownBits,
 
+    // Range of values the node may take. Kept consistent with `known_bits`, see `constant_range::refine`.
+    pub range: ConstantRange,
+
     // Internal mutable data for use in different algorithms.
     // Specifically we use this field to avoid unnecessarily storing data in hashmaps.
     //  e.g "how many users does this node have?" can be stored here temporarily.
@@ -936,8 +1723,20 @@ impl Language for SimpleAst {
                 | (SimpleAst::Xor(_), SimpleAst::Xor(_))
                 | (SimpleAst::Neg(_), SimpleAst::Neg(_))
                 | (SimpleAst::Lshr(_), SimpleAst::Lshr(_))
+                | (SimpleAst::Shl(_), SimpleAst::Shl(_))
+                | (SimpleAst::Ashr(_), SimpleAst::Ashr(_))
+                | (SimpleAst::Udiv(_), SimpleAst::Udiv(_))
+                | (SimpleAst::Urem(_), SimpleAst::Urem(_))
+                | (SimpleAst::Sdiv(_), SimpleAst::Sdiv(_))
+                | (SimpleAst::Srem(_), SimpleAst::Srem(_))
+                | (SimpleAst::Rotl(_), SimpleAst::Rotl(_))
+                | (SimpleAst::Rotr(_), SimpleAst::Rotr(_))
                 | (SimpleAst::Zext(_), SimpleAst::Zext(_))
-                | (SimpleAst::Trunc(_), SimpleAst::Trunc(_)) => true,
+                | (SimpleAst::Sext(_), SimpleAst::Sext(_))
+                | (SimpleAst::Trunc(_), SimpleAst::Trunc(_))
+                | (SimpleAst::Popcount(_), SimpleAst::Popcount(_))
+                | (SimpleAst::Ctlz(_), SimpleAst::Ctlz(_))
+                | (SimpleAst::Cttz(_), SimpleAst::Cttz(_)) => true,
                 (
                     SimpleAst::Constant { c: c1, width: w1 },
                     SimpleAst::Constant { c: c2, width: w2 },
@@ -972,10 +1771,22 @@ impl Language for SimpleAst {
             SimpleAst::Xor(children) => children,
             SimpleAst::Neg(children) => children,
             SimpleAst::Lshr(children) => children,
+            SimpleAst::Shl(children) => children,
+            SimpleAst::Ashr(children) => children,
+            SimpleAst::Udiv(children) => children,
+            SimpleAst::Urem(children) => children,
+            SimpleAst::Sdiv(children) => children,
+            SimpleAst::Srem(children) => children,
+            SimpleAst::Rotl(children) => children,
+            SimpleAst::Rotr(children) => children,
             SimpleAst::Constant { .. } => &[],
             SimpleAst::Symbol { .. } => &[],
             SimpleAst::Zext(children) => children,
+            SimpleAst::Sext(children) => children,
             SimpleAst::Trunc(children) => children,
+            SimpleAst::Popcount(children) => children,
+            SimpleAst::Ctlz(children) => children,
+            SimpleAst::Cttz(children) => children,
             SimpleAst::ICmp {
                 predicate,
                 children,
@@ -997,10 +1808,22 @@ impl Language for SimpleAst {
             SimpleAst::Xor(children) => children,
             SimpleAst::Neg(children) => children,
             SimpleAst::Lshr(children) => children,
+            SimpleAst::Shl(children) => children,
+            SimpleAst::Ashr(children) => children,
+            SimpleAst::Udiv(children) => children,
+            SimpleAst::Urem(children) => children,
+            SimpleAst::Sdiv(children) => children,
+            SimpleAst::Srem(children) => children,
+            SimpleAst::Rotl(children) => children,
+            SimpleAst::Rotr(children) => children,
             SimpleAst::Constant { .. } => &mut [],
             SimpleAst::Symbol { .. } => &mut [],
             SimpleAst::Zext(children) => children,
+            SimpleAst::Sext(children) => children,
             SimpleAst::Trunc(children) => children,
+            SimpleAst::Popcount(children) => children,
+            SimpleAst::Ctlz(children) => children,
+            SimpleAst::Cttz(children) => children,
             SimpleAst::ICmp {
                 predicate,
                 children,
@@ -1024,10 +1847,22 @@ impl ::std::fmt::Display for SimpleAst {
             SimpleAst::Xor(_) => f.write_str("^"),
             SimpleAst::Neg(_) => f.write_str("~"),
             SimpleAst::Lshr(_) => f.write_str(">>"),
+            SimpleAst::Shl(_) => f.write_str("<<"),
+            SimpleAst::Ashr(_) => f.write_str(">>s"),
+            SimpleAst::Udiv(_) => f.write_str("/"),
+            SimpleAst::Urem(_) => f.write_str("%"),
+            SimpleAst::Sdiv(_) => f.write_str("/s"),
+            SimpleAst::Srem(_) => f.write_str("%s"),
+            SimpleAst::Rotl(_) => f.write_str("<<<"),
+            SimpleAst::Rotr(_) => f.write_str(">>>"),
             SimpleAst::Constant { c, width } => f.write_str(format!("{}:{}", c, width).as_str()),
             SimpleAst::Symbol { id, width } => f.write_str(format!("v{}:{}", id, width).as_str()),
             SimpleAst::Zext(_) => f.write_str("zx"),
+            SimpleAst::Sext(_) => f.write_str("sx"),
             SimpleAst::Trunc(_) => f.write_str("tr"),
+            SimpleAst::Popcount(_) => f.write_str("popcount"),
+            SimpleAst::Ctlz(_) => f.write_str("ctlz"),
+            SimpleAst::Cttz(_) => f.write_str("cttz"),
             SimpleAst::ICmp {
                 predicate,
                 children,
@@ -1040,13 +1875,13 @@ impl ::std::fmt::Display for SimpleAst {
     }
 }
 
-fn parse_constant(op: &str) -> Option<(u64, u8)> {
+fn parse_constant(op: &str) -> Option<(u128, u8)> {
     let parts: Vec<&str> = op.split(':').collect();
     if parts.len() != 2 {
         return None;
     }
 
-    let c = parts[0].parse::<u64>().ok()?;
+    let c = parts[0].parse::<u128>().ok()?;
     let width = parts[1].parse::<u8>().ok()?;
     Some((c, width))
 }
@@ -1113,8 +1948,20 @@ impl egg::FromOp for SimpleAst {
             "^" => Ok(SimpleAst::Xor([children[0], children[1]])),
             "~" => Ok(SimpleAst::Neg([children[0]])),
             ">>" => Ok(SimpleAst::Lshr([children[0], children[1]])),
+            "<<" => Ok(SimpleAst::Shl([children[0], children[1]])),
+            ">>s" => Ok(SimpleAst::Ashr([children[0], children[1]])),
+            "/" => Ok(SimpleAst::Udiv([children[0], children[1]])),
+            "%" => Ok(SimpleAst::Urem([children[0], children[1]])),
+            "/s" => Ok(SimpleAst::Sdiv([children[0], children[1]])),
+            "%s" => Ok(SimpleAst::Srem([children[0], children[1]])),
+            "<<<" => Ok(SimpleAst::Rotl([children[0], children[1]])),
+            ">>>" => Ok(SimpleAst::Rotr([children[0], children[1]])),
             "zx" => Ok(SimpleAst::Zext([children[0], children[1]])),
+            "sx" => Ok(SimpleAst::Sext([children[0], children[1]])),
             "tr" => Ok(SimpleAst::Trunc([children[0], children[1]])),
+            "popcount" => Ok(SimpleAst::Popcount([children[0]])),
+            "ctlz" => Ok(SimpleAst::Ctlz([children[0]])),
+            "cttz" => Ok(SimpleAst::Cttz([children[0]])),
             "select" => Ok(SimpleAst::Select {
                 children: [children[0], children[1], children[2]],
             }),
@@ -1148,9 +1995,12 @@ pub type EEGraph = egg::EGraph<SimpleAst, MbaAnalysis>;
 pub type Rewrite = egg::Rewrite<SimpleAst, MbaAnalysis>;
 
 // Since Egg only supports a single analysis class per egraph,
-// we must perform multiple analyses at once. Namely constant folding, classification(e.g., "is this mba linear?"), and known bits analysis.
+// we must perform multiple analyses at once. Namely constant folding, classification(e.g., "is this mba linear?"), known bits and constant range analysis.
 #[derive(Default)]
-pub struct MbaAnalysis;
+pub struct MbaAnalysis {
+    // Number of e-class ids handed out so far. Ids are allocated in order, so every id below it is valid.
+    pub(crate) ids: usize,
+}
 
 pub struct EGraphUtil<'a> {
     pub egraph: &'a egg::EGraph<SimpleAst, MbaAnalysis>,
@@ -1186,10 +2036,22 @@ impl<'a> CostFunction<SimpleAst> for EGraphCostFn<'a> {
             SimpleAst::Xor(_) => 1,
             SimpleAst::Neg(_) => 1,
             SimpleAst::Lshr(_) => 6,
+            SimpleAst::Shl(_) => 6,
+            SimpleAst::Ashr(_) => 6,
+            SimpleAst::Udiv(_) => 6,
+            SimpleAst::Urem(_) => 6,
+            SimpleAst::Sdiv(_) => 6,
+            SimpleAst::Srem(_) => 6,
+            SimpleAst::Rotl(_) => 6,
+            SimpleAst::Rotr(_) => 6,
             SimpleAst::Constant { c, width } => 1,
             SimpleAst::Symbol { id, width } => 1,
             SimpleAst::Zext(_) => 1,
+            SimpleAst::Sext(_) => 2,
             SimpleAst::Trunc(_) => 5,
+            SimpleAst::Popcount(_) => 6,
+            SimpleAst::Ctlz(_) => 6,
+            SimpleAst::Cttz(_) => 6,
             SimpleAst::ICmp {
                 predicate,
                 children,
@@ -1207,6 +2069,7 @@ impl Analysis<SimpleAst> for MbaAnalysis {
     type Data = AstData;
 
     fn make(egraph: &mut egg::EGraph<SimpleAst, Self>, enode: &SimpleAst, id: Id) -> Self::Data {
+        egraph.analysis.ids = usize::from(id) + 1;
         let mut util = EGraphUtil { egraph: &egraph };
 
         let data = match enode {
@@ -1218,14 +2081,29 @@ impl Analysis<SimpleAst> for MbaAnalysis {
             SimpleAst::Xor([a, b]) => util.xor_transfer(*a, *b),
             SimpleAst::Neg([a]) => util.neg_transfer(*a),
             SimpleAst::Lshr([a, b]) => util.lshr_transfer(*a, *b),
+            SimpleAst::Shl([a, b]) => util.shl_transfer(*a, *b),
+            SimpleAst::Ashr([a, b]) => util.ashr_transfer(*a, *b),
+            SimpleAst::Udiv([a, b]) => util.udiv_transfer(*a, *b),
+            SimpleAst::Urem([a, b]) => util.urem_transfer(*a, *b),
+            SimpleAst::Sdiv([a, b]) => util.sdiv_transfer(*a, *b),
+            SimpleAst::Srem([a, b]) => util.srem_transfer(*a, *b),
+            SimpleAst::Rotl([a, b]) => util.rotl_transfer(*a, *b),
+            SimpleAst::Rotr([a, b]) => util.rotr_transfer(*a, *b),
             SimpleAst::Zext([a, to]) => util.zext_transfer(
                 *a,
                 util.get_data(*to).known_bits.as_constant().unwrap() as u8,
             ),
+            SimpleAst::Sext([a, to]) => util.sext_transfer(
+                *a,
+                util.get_data(*to).known_bits.as_constant().unwrap() as u8,
+            ),
             SimpleAst::Trunc([a, to]) => util.trunc_transfer(
                 *a,
                 util.get_data(*to).known_bits.as_constant().unwrap() as u8,
             ),
+            SimpleAst::Popcount([a]) => util.popcount_transfer(*a),
+            SimpleAst::Ctlz([a]) => util.ctlz_transfer(*a),
+            SimpleAst::Cttz([a]) => util.cttz_transfer(*a),
             SimpleAst::Constant { c, width } => util.constant_transfer(*c, *width),
             SimpleAst::Symbol { id: _, width } => util.symbol_transfer(*width),
             SimpleAst::ICmp {
@@ -1251,34 +2129,34 @@ impl Analysis<SimpleAst> for MbaAnalysis {
         let to = a;
         let from = b;
 
-        let kb1 = to.known_bits;
-        let kb2 = from.known_bits;
         if let Some(c) = to.known_bits.as_constant() {
             return DidMerge(false, true /* maybe */);
         }
 
         if let Some(new_cst) = from.known_bits.as_constant() {
             to.known_bits = from.known_bits.clone();
+            to.range = from.range;
             to.cost = 1;
             return DidMerge(true, false);
         }
 
-        // Union until a fixedpoint is reached
-        if kb1 != kb2 {
-            let new = kb1.union(&kb2);
-            to.known_bits = new;
-
-            if new.is_constant() {
-                to.cost = 1;
-                return DidMerge(true, true); // yep
-            }
+        // Both classes hold the same value, so the facts known about either apply to the merged class.
+        // Union the known bits and intersect the ranges, then let `refine` tighten each with the other until neither changes.
+        let (kb, range) = constant_range::refine(
+            to.known_bits.union(&from.known_bits),
+            to.range.intersect(&from.range),
+        );
+        let new_for_to = kb != to.known_bits || range != to.range;
+        let new_for_from = kb != from.known_bits || range != from.range;
+        to.known_bits = kb;
+        to.range = range;
 
-            let new_for_to = new != kb1;
-            let new_for_from = new != kb2;
-            return DidMerge(new_for_to, new_for_from);
+        if kb.is_constant() {
+            to.cost = 1;
+            return DidMerge(true, true);
         }
 
-        return DidMerge(false, false);
+        return DidMerge(new_for_to, new_for_from);
     }
 
     fn modify(egraph: &mut egg::EGraph<SimpleAst, Self>, id: Id) {
@@ -1398,19 +2276,37 @@ pub enum SimpleAst {
     Neg([AstIdx; 1]),
     // Shift operators:
     Lshr([AstIdx; 2]),
+    Shl([AstIdx; 2]),
+    Ashr([AstIdx; 2]),
+    // Division operators:
+    // Division by zero follows SMT-LIB, see `udiv_const` and friends.
+    Udiv([AstIdx; 2]),
+    Urem([AstIdx; 2]),
+    Sdiv([AstIdx; 2]),
+    Srem([AstIdx; 2]),
+    // Rotate operators:
+    // The rotate amount is taken modulo the bit width.
+    Rotl([AstIdx; 2]),
+    Rotr([AstIdx; 2]),
+    // Bit counting operators:
+    // Counting the zeroes of zero yields the bit width, like x86's `lzcnt` and `tzcnt`.
+    Popcount([AstIdx; 1]),
+    Ctlz([AstIdx; 1]),
+    Cttz([AstIdx; 1]),
     // Literals:
     Constant {
-        c: u64,
+        c: u128,
         width: u8,
     },
     Symbol {
         id: u32,
         width: u8,
     },
-    // Zext(src, to), Trunc(src, to)
+    // Zext(src, to), Sext(src, to), Trunc(src, to)
     // The `to` field is a SimpleAst::Constant instance containing the target width.
     // Formerly it was a u8, but egg's ematching engine does not support mixing ast indices and immediates in the same node type..
     Zext([AstIdx; 2]),
+    Sext([AstIdx; 2]),
     Trunc([AstIdx; 2]),
     ICmp {
         predicate: Predicate,
@@ -1497,7 +2393,7 @@ impl isle_rules::Context for Context {
             if let SimpleAst::Constant { c: c2, width } = op2 {
                 let result = self
                     .arena
-                    .constant(Pow(*c1, *c2), self.arena.get_width(arg0));
+                    .constant(pow_const(*c1, *c2), self.arena.get_width(arg0));
 
                 return self.arena.get_node(result).clone();
             }
@@ -1570,18 +2466,79 @@ impl isle_rules::Context for Context {
         return self.arena.get_node(xor).clone();
     }
 
+    fn shl(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let shl = self.arena.shl(arg0, arg1);
+        return self.arena.get_node(shl).clone();
+    }
+
+    fn ashr(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let ashr = self.arena.ashr(arg0, arg1);
+        return self.arena.get_node(ashr).clone();
+    }
+
+    fn udiv(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let udiv = self.arena.udiv(arg0, arg1);
+        return self.arena.get_node(udiv).clone();
+    }
+
+    fn urem(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let urem = self.arena.urem(arg0, arg1);
+        return self.arena.get_node(urem).clone();
+    }
+
+    fn sdiv(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let sdiv = self.arena.sdiv(arg0, arg1);
+        return self.arena.get_node(sdiv).clone();
+    }
+
+    fn srem(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let srem = self.arena.srem(arg0, arg1);
+        return self.arena.get_node(srem).clone();
+    }
+
+    fn rotl(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let rotl = self.arena.rotl(arg0, arg1);
+        return self.arena.get_node(rotl).clone();
+    }
+
+    fn rotr(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
+        let rotr = self.arena.rotr(arg0, arg1);
+        return self.arena.get_node(rotr).clone();
+    }
+
     fn zext(&mut self, arg0: AstIdx, width: AstIdx) -> SimpleAst {
         let zext = self.arena.zext(arg0, self.arena.get_constant(width) as u8);
 
         self.arena.get_node(zext).clone()
     }
 
+    fn sext(&mut self, arg0: AstIdx, width: AstIdx) -> SimpleAst {
+        let sext = self.arena.sext(arg0, self.arena.get_constant(width) as u8);
+
+        self.arena.get_node(sext).clone()
+    }
+
     fn trunc(&mut self, arg0: AstIdx, width: AstIdx) -> SimpleAst {
         let trunc = self.arena.trunc(arg0, self.arena.get_constant(width) as u8);
 
         self.arena.get_node(trunc).clone()
     }
 
+    fn popcount(&mut self, arg0: AstIdx) -> SimpleAst {
+        let popcount = self.arena.popcount(arg0);
+        return self.arena.get_node(popcount).clone();
+    }
+
+    fn ctlz(&mut self, arg0: AstIdx) -> SimpleAst {
+        let ctlz = self.arena.ctlz(arg0);
+        return self.arena.get_node(ctlz).clone();
+    }
+
+    fn cttz(&mut self, arg0: AstIdx) -> SimpleAst {
+        let cttz = self.arena.cttz(arg0);
+        return self.arena.get_node(cttz).clone();
+    }
+
     fn icmp(&mut self, pred: Predicate, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
         let icmp = self.arena.icmp(pred, arg0, arg1);
         return self.arena.get_node(icmp).clone();
@@ -1593,7 +2550,9 @@ impl isle_rules::Context for Context {
     }
 
     fn extract(&mut self, arg0: AstIdx, arg1: AstIdx, arg2: AstIdx) -> SimpleAst {
-        let extract = self.arena.extract(arg0, self.arena.get_width(arg1), self.arena.get_width(arg2));
+        let high = self.arena.get_constant(arg1) as u8;
+        let low = self.arena.get_constant(arg2) as u8;
+        let extract = self.arena.extract(arg0, high, low);
         return self.arena.get_node(extract).clone();
     }
 
@@ -1618,11 +2577,11 @@ impl isle_rules::Context for Context {
     fn lookup_id(&mut self, arg0: &SimpleAst) -> AstIdx {
         // TODO: If this element is not present in the map, we need to compute the data and insert it into the arena.
         // This is due to our recent refactor where we separate the `AstData` from the `SimpleAst` struct definition.
-        return *self.arena.ast_to_idx.get(arg0).unwrap();
+        return self.arena.lookup_idx(arg0).unwrap();
         //return self.arena.insert_ast_node(arg0.clone()); // TODO
     }
 
-    fn constant(&mut self, arg0: u64, width: u8) -> SimpleAst {
+    fn constant(&mut self, arg0: u128, width: u8) -> SimpleAst {
         let id = self.arena.constant(arg0, width);
         return self.arena.get_node(id).clone();
     }
@@ -1633,7 +2592,7 @@ impl isle_rules::Context for Context {
     }
 
     fn fold_add(&mut self, arg0: AstIdx, arg1: AstIdx) -> SimpleAst {
-        let add = |a: u64, b: u64| return a.wrapping_add(b);
+        let add = |a: u128, b: u128| return a.wrapping_add(b);
         return fold_constant_binop(self, arg0, arg1, &add);
     }
 
@@ -1641,12 +2600,79 @@ impl isle_rules::Context for Context {
         return self.arena.get_width(arg0);
     }
 
-    fn is_constant_modulo(&mut self, arg0: u64, arg1: u64, arg2: u8) -> Option<Empty> {
+    fn is_constant_modulo(&mut self, arg0: u128, arg1: u128, arg2: u8) -> Option<Empty> {
         let modulo_mask = get_modulo_mask(arg2);
         let are_equal = (arg0 & modulo_mask) == (arg1 & modulo_mask);
         return if are_equal { Some(Empty()) } else { None };
     }
 
+    fn shl_to_mul_factor(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<u128> {
+        if let SimpleAst::Constant { c, .. } = self.arena.get_node(arg1) {
+            return Some(shl_const(1, *c, self.arena.get_width(arg0)));
+        }
+
+        return None;
+    }
+
+    fn pow2_log2(&mut self, arg0: AstIdx) -> Option<u128> {
+        if let SimpleAst::Constant { c, .. } = self.arena.get_node(arg0) {
+            if c.is_power_of_two() {
+                return Some(c.trailing_zeros() as u128);
+            }
+        }
+
+        return None;
+    }
+
+    fn pow2_low_mask(&mut self, arg0: AstIdx) -> Option<u128> {
+        return self.pow2_log2(arg0).map(|k| (1u128 << k) - 1);
+    }
+
+    fn cast_is_noop(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<Empty> {
+        if self.arena.get_width(arg0) as u128 == self.arena.get_constant(arg1) {
+            return Some(Empty());
+        }
+
+        return None;
+    }
+
+    fn cast_is_narrowing(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<Empty> {
+        if self.arena.get_width(arg0) as u128 > self.arena.get_constant(arg1) {
+            return Some(Empty());
+        }
+
+        return None;
+    }
+
+    fn is_sign_bit_zero(&mut self, arg0: AstIdx) -> Option<Empty> {
+        let width = self.arena.get_width(arg0);
+        let known_bits = self.arena.get_data(arg0).known_bits;
+        if (known_bits.zeroes >> (width - 1)) & 1 != 0 {
+            return Some(Empty());
+        }
+
+        return None;
+    }
+
+    fn rotate_source(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<AstIdx> {
+        return self.arena.match_rotate(arg0, arg1).map(|(x, _)| x);
+    }
+
+    fn rotate_amount(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<u128> {
+        return self.arena.match_rotate(arg0, arg1).map(|(_, amount)| amount);
+    }
+
+    fn popcount_operand(&mut self, arg0: AstIdx, arg1: AstIdx) -> Option<AstIdx> {
+        let (x, mask) = self.arena.match_popcount(arg0, arg1)?;
+        let width = self.arena.get_width(x);
+        if mask == get_modulo_mask(width) {
+            return Some(x);
+        }
+
+        let mask = self.arena.constant(mask, width);
+        return Some(self.arena.and(mask, x));
+    }
+
     is_icmp_predicate!(is_icmp_eq, Predicate::Eq);
     is_icmp_predicate!(is_icmp_ne, Predicate::Ne);
     is_icmp_predicate!(is_icmp_ugt, Predicate::Ugt);
@@ -1674,7 +2700,7 @@ fn fold_constant_binop(
     ctx: &mut Context,
     arg0: AstIdx,
     arg1: AstIdx,
-    f: &dyn Fn(u64, u64) -> u64,
+    f: &dyn Fn(u128, u128) -> u128,
 ) -> SimpleAst {
     let op1 = ctx.arena.get_node(arg0);
     let op2 = ctx.arena.get_node(arg1);
@@ -1716,10 +2742,22 @@ impl AstPrinter {
             SimpleAst::Xor { .. } => "^",
             SimpleAst::Neg(a) => "~",
             SimpleAst::Lshr { .. } => ">>",
+            SimpleAst::Shl { .. } => "<<",
+            SimpleAst::Ashr { .. } => ">>s",
+            SimpleAst::Udiv { .. } => "/",
+            SimpleAst::Urem { .. } => "%",
+            SimpleAst::Sdiv { .. } => "/s",
+            SimpleAst::Srem { .. } => "%s",
+            SimpleAst::Rotl { .. } => "<<<",
+            SimpleAst::Rotr { .. } => ">>>",
             SimpleAst::Constant { c, width } => "",
             SimpleAst::Symbol { id, width } => "",
             SimpleAst::Zext { .. } => "zx",
+            SimpleAst::Sext { .. } => "sx",
             SimpleAst::Trunc { .. } => "tr",
+            SimpleAst::Popcount { .. } => "popcount",
+            SimpleAst::Ctlz { .. } => "ctlz",
+            SimpleAst::Cttz { .. } => "cttz",
             SimpleAst::ICmp {
                 predicate,
                 children,
@@ -1744,12 +2782,23 @@ impl AstPrinter {
             | SimpleAst::Or([a, b])
             | SimpleAst::Xor([a, b])
             | SimpleAst::Lshr([a, b])
+            | SimpleAst::Shl([a, b])
+            | SimpleAst::Udiv([a, b])
+            | SimpleAst::Urem([a, b])
+            | SimpleAst::Rotl([a, b])
+            | SimpleAst::Rotr([a, b])
             | SimpleAst::Concat([a, b]) => {
                 self.print_node(ctx, ctx.arena.get_node(*a));
                 self.output.push_str(&format!("{}", operator));
                 self.print_node(ctx, ctx.arena.get_node(*b));
             }
-            SimpleAst::Zext([a, to_id]) => {
+            // Spaced like the signed comparisons, so the `s` suffix is never glued onto the rhs.
+            SimpleAst::Ashr([a, b]) | SimpleAst::Sdiv([a, b]) | SimpleAst::Srem([a, b]) => {
+                self.print_node(ctx, ctx.arena.get_node(*a));
+                self.output.push_str(&format!(" {} ", operator));
+                self.print_node(ctx, ctx.arena.get_node(*b));
+            }
+            SimpleAst::Zext([a, to_id]) | SimpleAst::Sext([a, to_id]) => {
                 self.print_node(ctx, ctx.arena.get_node(*a));
                 let to = ctx.arena.get_constant(*to_id);
                 self.output.push_str(&format!(" {} i{}", operator, to));
@@ -1763,9 +2812,18 @@ impl AstPrinter {
                 self.output.push('~');
                 self.print_node(ctx, ctx.arena.get_node(*a));
             }
+            SimpleAst::Popcount([a]) | SimpleAst::Ctlz([a]) | SimpleAst::Cttz([a]) => {
+                self.output.push_str(&format!("{}(", operator));
+                self.print_node(ctx, ctx.arena.get_node(*a));
+                self.output.push_str(")");
+            }
             SimpleAst::Constant { c, width } => {
-                self.output
-                    .push_str(&format!("{}:i{}", (*c as i64).to_string(), width))
+                // Constants are printed as signed 64-bit values, or as signed 128-bit values if wider.
+                let value = match *width {
+                    0..=64 => (*c as i64).to_string(),
+                    _ => (*c as i128).to_string(),
+                };
+                self.output.push_str(&format!("{}:i{}", value, width))
             }
             SimpleAst::Symbol { id, width } => self.output.push_str(&format!(
                 "{}:i{}",
@@ -1806,74 +2864,151 @@ impl AstPrinter {
             }
         }
 
-        if parens {
-            self.output.push_str(")")
-        }
+        if parens {
+            self.output.push_str(")")
+        }
+    }
+}
+
+// Widest supported bit-vector. Constants are stored as `u128`, so this is the limit of the IR itself.
+pub const MAX_WIDTH: u8 = 128;
+
+pub fn get_modulo_mask(width: u8) -> u128 {
+    return u128::MAX >> (128 - width);
+}
+
+pub fn rmod(constant: u128, width: u8) -> u128 {
+    return constant & get_modulo_mask(width);
+}
+
+// The result is reduced modulo 2**128, callers must reduce it modulo the bit width.
+pub fn pow_const(mut base: u128, mut exp: u128) -> u128 {
+    let mut res: u128 = 1;
+    while exp != 0 {
+        if (exp & 1) != 0 {
+            res = res.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exp >>= 1;
+    }
+
+    return res;
+}
+
+// Shifting by the bit width or more yields zero, matching SMT-LIB's bvshl.
+pub fn shl_const(a: u128, shift: u128, width: u8) -> u128 {
+    if shift >= width as u128 {
+        return 0;
+    }
+
+    return (a << shift) & get_modulo_mask(width);
+}
+
+pub fn lshr_const(a: u128, shift: u128, width: u8) -> u128 {
+    if shift >= width as u128 {
+        return 0;
+    }
+
+    return (a & get_modulo_mask(width)) >> shift;
+}
+
+// Shifting by the bit width or more fills the result with the sign bit, matching SMT-LIB's bvashr.
+pub fn ashr_const(a: u128, shift: u128, width: u8) -> u128 {
+    let sign_extended = sign_extend(a, width);
+    return ((sign_extended >> shift.min(127)) as u128) & get_modulo_mask(width);
+}
+
+pub fn sign_extend(a: u128, width: u8) -> i128 {
+    let unused = 128 - width as u32;
+    return ((a << unused) as i128) >> unused;
+}
+
+// Division by zero follows SMT-LIB: `x / 0` is all ones, and `x % 0` is `x`.
+pub fn udiv_const(a: u128, b: u128, width: u8) -> u128 {
+    let mask = get_modulo_mask(width);
+    let (a, b) = (a & mask, b & mask);
+    if b == 0 {
+        return mask;
+    }
+
+    return a / b;
+}
+
+pub fn urem_const(a: u128, b: u128, width: u8) -> u128 {
+    let mask = get_modulo_mask(width);
+    let (a, b) = (a & mask, b & mask);
+    if b == 0 {
+        return a;
+    }
+
+    return a % b;
+}
+
+// Signed division by zero yields -1 for non-negative dividends and 1 for negative ones,
+// i.e. the quotient of the unsigned division of the magnitudes. `MIN / -1` wraps to `MIN`.
+pub fn sdiv_const(a: u128, b: u128, width: u8) -> u128 {
+    let (sa, sb) = (sign_extend(a, width), sign_extend(b, width));
+    if sb == 0 {
+        return if sa < 0 { 1 } else { get_modulo_mask(width) };
+    }
+
+    return (sa.wrapping_div(sb) as u128) & get_modulo_mask(width);
+}
+
+// The remainder takes the sign of the dividend. `x % 0` is `x`, and `MIN % -1` is zero.
+pub fn srem_const(a: u128, b: u128, width: u8) -> u128 {
+    let (sa, sb) = (sign_extend(a, width), sign_extend(b, width));
+    if sb == 0 {
+        return a & get_modulo_mask(width);
     }
+
+    return (sa.wrapping_rem(sb) as u128) & get_modulo_mask(width);
 }
 
-pub fn get_modulo_mask(width: u8) -> u64 {
-    return u64::MAX >> (64 - width);
+// Rotate amounts are reduced modulo the bit width, like LLVM's funnel shifts.
+pub fn rotl_const(a: u128, amount: u128, width: u8) -> u128 {
+    let a = a & get_modulo_mask(width);
+    let amount = amount % width as u128;
+    if amount == 0 {
+        return a;
+    }
+
+    return ((a << amount) | (a >> (width as u128 - amount))) & get_modulo_mask(width);
 }
 
-pub fn rmod(constant: u64, width: u8) -> u64 {
-    return constant & get_modulo_mask(width);
+pub fn rotr_const(a: u128, amount: u128, width: u8) -> u128 {
+    let amount = amount % width as u128;
+    return rotl_const(a, width as u128 - amount, width);
 }
 
-fn cmp(pred: Predicate, a: u64, b: u64) -> bool {
-    let sa = a as i64;
-    let sb = b as i64;
-    match pred {
-        Predicate::Eq => a == b,
-        Predicate::Ne => a != b,
-        Predicate::Ugt => a > b,
-        Predicate::Uge => a >= b,
-        Predicate::Ult => a < b,
-        Predicate::Ule => a <= b,
-        Predicate::Sgt => sa > sb,
-        Predicate::Sge => sa >= sb,
-        Predicate::Slt => sa < sb,
-        Predicate::Sle => sa <= sb,
-    }
+pub fn popcount_const(a: u128, width: u8) -> u128 {
+    return (a & get_modulo_mask(width)).count_ones() as u128;
 }
 
-pub fn eval_ast(ctx: &Context, idx: AstIdx, value_mapping: &HashMap<AstIdx, u64>) -> u64 {
-    let ast = ctx.arena.get_node(idx);
-    let e = |i: &AstIdx| eval_ast(ctx, *i, value_mapping);
-    let r = match ast {
-        SimpleAst::Add([a, b]) => e(a).wrapping_add(e(b)),
-        SimpleAst::Mul([a, b]) => e(a).wrapping_mul(e(b)),
-        SimpleAst::Pow([a, b]) => todo!(),
-        SimpleAst::And([a, b]) => e(a) & e(b),
-        SimpleAst::Or([a, b]) => e(a) | e(b),
-        SimpleAst::Xor([a, b]) => e(a) ^ e(b),
-        SimpleAst::Lshr([a, b]) => e(a) >> e(b),
-        SimpleAst::Neg([a]) => !e(a),
-        SimpleAst::Constant { c, width } => *c,
-        SimpleAst::Symbol { id, width } => *value_mapping.get(&idx).unwrap(),
-        SimpleAst::Zext([a, to]) => get_modulo_mask(ctx.arena.get_width(*a)) & e(a),
-        SimpleAst::Trunc([a, to]) => get_modulo_mask(ctx.arena.get_constant(*to) as u8) & e(a),
-        SimpleAst::ICmp {
-            predicate,
-            children,
-        } => cmp(predicate.clone(), e(&children[0]), e(&children[1])) as u64,
-        SimpleAst::Select { children } => {
-            if e(&children[0]) != 0 {
-                e(&children[1])
-            } else {
-                e(&children[2])
-            }
-        }
-        _ => todo!(),
-    };
+// Counting the zeroes of zero yields the bit width.
+pub fn ctlz_const(a: u128, width: u8) -> u128 {
+    let a = a & get_modulo_mask(width);
+    return (a.leading_zeros() - (128 - width as u32)) as u128;
+}
+
+pub fn cttz_const(a: u128, width: u8) -> u128 {
+    let a = a & get_modulo_mask(width);
+    if a == 0 {
+        return width as u128;
+    }
+
+    return a.trailing_zeros() as u128;
+}
 
-    r & get_modulo_mask(ctx.arena.get_width(idx))
+pub fn eval_ast(ctx: &Context, idx: AstIdx, value_mapping: &HashMap<AstIdx, u128>) -> u128 {
+    let (vars, inputs): (Vec<AstIdx>, Vec<u128>) = value_mapping.iter().map(|(k, v)| (*k, *v)).unzip();
+    return Evaluator::new(ctx, idx, &vars).eval(&inputs);
 }
 
 // Recursively apply ISLE over an AST.
 pub fn recursive_simplify(ctx: &mut Context, idx: AstIdx) -> AstIdx {
-    if ctx.arena.isle_cache.get(&idx).is_some() {
-        return *ctx.arena.isle_cache.get(&idx).unwrap();
+    if let Some(result) = ctx.arena.get_simplified(idx) {
+        return result;
     }
     let mut ast = ctx.arena.get_node(idx).clone();
 
@@ -1884,7 +3019,15 @@ pub fn recursive_simplify(ctx: &mut Context, idx: AstIdx) -> AstIdx {
         | SimpleAst::And([a, b])
         | SimpleAst::Or([a, b])
         | SimpleAst::Xor([a, b])
-        | SimpleAst::Lshr([a, b]) => {
+        | SimpleAst::Lshr([a, b])
+        | SimpleAst::Shl([a, b])
+        | SimpleAst::Ashr([a, b])
+        | SimpleAst::Udiv([a, b])
+        | SimpleAst::Urem([a, b])
+        | SimpleAst::Sdiv([a, b])
+        | SimpleAst::Srem([a, b])
+        | SimpleAst::Rotl([a, b])
+        | SimpleAst::Rotr([a, b]) => {
             let op1 = recursive_simplify(ctx, a);
             let op2 = recursive_simplify(ctx, b);
             ast = match ast {
@@ -1895,6 +3038,14 @@ pub fn recursive_simplify(ctx: &mut Context, idx: AstIdx) -> AstIdx {
                 SimpleAst::Or(_) => ctx.or(op1, op2),
                 SimpleAst::Xor(_) => ctx.xor(op1, op2),
                 SimpleAst::Lshr(_) => ctx.lshr(op1, op2),
+                SimpleAst::Shl(_) => ctx.shl(op1, op2),
+                SimpleAst::Ashr(_) => ctx.ashr(op1, op2),
+                SimpleAst::Udiv(_) => ctx.udiv(op1, op2),
+                SimpleAst::Urem(_) => ctx.urem(op1, op2),
+                SimpleAst::Sdiv(_) => ctx.sdiv(op1, op2),
+                SimpleAst::Srem(_) => ctx.srem(op1, op2),
+                SimpleAst::Rotl(_) => ctx.rotl(op1, op2),
+                SimpleAst::Rotr(_) => ctx.rotr(op1, op2),
                 _ => unreachable!(),
             };
         }
@@ -1906,10 +3057,26 @@ pub fn recursive_simplify(ctx: &mut Context, idx: AstIdx) -> AstIdx {
             let op1 = recursive_simplify(ctx, a);
             ast = ctx.zext(op1, to_id);
         }
+        SimpleAst::Sext([a, to_id]) => {
+            let op1 = recursive_simplify(ctx, a);
+            ast = ctx.sext(op1, to_id);
+        }
         SimpleAst::Trunc([a, to_id]) => {
             let op1 = recursive_simplify(ctx, a);
             ast = ctx.trunc(op1, to_id);
         }
+        SimpleAst::Popcount([a]) => {
+            let op1 = recursive_simplify(ctx, a);
+            ast = ctx.popcount(op1);
+        }
+        SimpleAst::Ctlz([a]) => {
+            let op1 = recursive_simplify(ctx, a);
+            ast = ctx.ctlz(op1);
+        }
+        SimpleAst::Cttz([a]) => {
+            let op1 = recursive_simplify(ctx, a);
+            ast = ctx.cttz(op1);
+        }
         SimpleAst::Constant { c, width } => return idx,
         SimpleAst::Symbol { id, width } => return idx,
         SimpleAst::ICmp {
@@ -1957,8 +3124,8 @@ pub fn recursive_simplify(ctx: &mut Context, idx: AstIdx) -> AstIdx {
         ast = result.unwrap();
     }
 
-    let result = ctx.arena.ast_to_idx[&ast];
-    ctx.arena.isle_cache.insert(idx, result);
+    let result = ctx.arena.lookup_idx(&ast).unwrap();
+    ctx.arena.set_simplified(idx, result);
     result
 }
 
@@ -1975,26 +3142,16 @@ pub fn interpret_for_all_truth_values(
     // This is not the most concise way to do this, but it is easier to read.
     let num_vars = var_indices.len() as usize;
     let num_elements = usize::from(2 as u16).pow(num_vars as u32);
-    let mut value_mapping: HashMap<AstIdx, u64> = HashMap::with_capacity(num_vars);
+    let mut evaluator = Evaluator::new(ctx, ast_idx, var_indices);
+    let mut inputs = vec![0u128; num_vars];
     for i in 0..num_elements {
-        // Update the value mapping for this combination of zeroes and ones.
+        // Update the inputs for this combination of zeroes and ones.
         for v in 0..num_vars {
-            // Compute a mask for this variable.
-            let var_mask = 1 << v as u16;
-
-            // Compute the value of this specific variable.
-            let mut var_value: u64 = (i & var_mask) as u64;
-
-            // Shift the value back down to it's corresponding index.
-            var_value = var_value >> v as u16;
-
-            // Lazily use a HashMap to map variables to their input values for this evaluation.
-            // TODO: Use an array instead.
-            value_mapping.insert(var_indices[v], var_value as u64);
+            inputs[v] = ((i >> v) & 1) as u128;
         }
 
         // Evaluate the AST for this combination of input zeroes and ones.
-        let mut res = mask & eval_ast(ctx, ast_idx, &value_mapping);
+        let mut res = mask & evaluator.eval(&inputs) as u64;
 
         // Update the result vector for this combination of zeroes and ones.
         result_vector.push(res);
@@ -2071,11 +3228,21 @@ pub fn order_vars_alphabetically(
 
 #[no_mangle]
 pub extern "C" fn CreateTruthTableDb() -> *mut TruthTableDatabase {
-    let db = TruthTableDatabase::new();
+    ffi::guard(|| {
+        let db = TruthTableDatabase::new();
 
-    let mut pctx = Box::new(db);
+        let mut pctx = Box::new(db);
 
-    return Box::into_raw(pctx);
+        Ok(Box::into_raw(pctx))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn FreeTruthTableDb(db: *mut TruthTableDatabase) -> FfiStatus {
+    ffi::guard_status(|| {
+        drop(Box::from_raw(ffi::non_null(db, "database")?));
+        Ok(())
+    })
 }
 
 #[no_mangle]
@@ -2086,12 +3253,16 @@ pub extern "C" fn GetTruthTableDbEntry(
     vars: *const AstIdx,
     idx: usize,
 ) -> AstIdx {
-    unsafe {
-        let mut context: &mut Context = &mut (*ctx);
-        let mut table: &mut TruthTableDatabase = &mut (*db);
+    ffi::guard(|| {
+        let context = ffi::context(ctx)?;
+        let table = ffi::non_null(db, "truth table database")?;
+        let vars = check_truth_table_vars(context, var_count, vars)?;
+        if idx >= TruthTableDatabase::num_entries(var_count) {
+            return Err(FfiError::invalid(format!("truth table index {} is out of range", idx)));
+        }
 
-        TruthTableDatabase::get_truth_table_entry(table, context, var_count, vars, idx)
-    }
+        Ok(TruthTableDatabase::get_truth_table_entry(table, context, var_count, vars.as_ptr(), idx))
+    })
 }
 
 #[no_mangle]
@@ -2100,11 +3271,34 @@ pub extern "C" fn GetTruthTableDbEntryCost(
     var_count: u32,
     idx: usize,
 ) -> u32 {
-    unsafe {
-        let mut table: &mut TruthTableDatabase = &mut (*db);
+    ffi::guard(|| {
+        let table = ffi::non_null(db, "truth table database")?;
+        if !(2..=4).contains(&var_count) {
+            return Err(FfiError::invalid(format!(
+                "truth table database only supports 2, 3, or 4 variables, got {}",
+                var_count
+            )));
+        }
+        if idx >= TruthTableDatabase::num_entries(var_count) {
+            return Err(FfiError::invalid(format!("truth table index {} is out of range", idx)));
+        }
 
-        TruthTableDatabase::get_boolean_cost(table, var_count, idx)
+        Ok(TruthTableDatabase::get_boolean_cost(table, var_count, idx))
+    })
+}
+
+// Validate the variables passed to the truth table database.
+fn check_truth_table_vars<'a>(ctx: &Context, var_count: u32, vars: *const AstIdx) -> Result<&'a [AstIdx], FfiError> {
+    if !(2..=4).contains(&var_count) {
+        return Err(FfiError::invalid(format!(
+            "truth table database only supports 2, 3, or 4 variables, got {}",
+            var_count
+        )));
     }
+
+    let vars = ffi::slice(vars, var_count as usize, "variables")?;
+    ffi::nodes(ctx, vars)?;
+    return Ok(vars);
 }
 
 #[no_mangle]
@@ -2118,149 +3312,369 @@ pub extern "C" fn CreateContext() -> *mut Context {
     return Box::into_raw(pctx);
 }
 
+// Free a context created by `CreateContext`, `ContextCreateShared` or `ContextLoadSnapshot`. Nodes of a shared context
+// live on in the arena.
 #[no_mangle]
-pub extern "C" fn ContextClear(ctx: *mut Context, a: AstIdx) {
-    unsafe {
-        (*ctx).arena.clear();
-    }
+pub unsafe extern "C" fn FreeContext(ctx: *mut Context) -> FfiStatus {
+    ffi::guard_status(|| {
+        drop(Box::from_raw(ffi::context(ctx)?));
+        Ok(())
+    })
+}
+
+// Returns false if the snapshot could not be written.
+#[no_mangle]
+pub extern "C" fn ContextSaveSnapshot(
+    ctx: *const Context,
+    path: *const c_char,
+    include_isle_cache: bool,
+) -> bool {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let path = ffi::string(path)?;
+        ctx.save_snapshot(path, include_isle_cache)?;
+        Ok(true)
+    })
+}
+
+// Returns null if the snapshot could not be read.
+#[no_mangle]
+pub extern "C" fn ContextLoadSnapshot(path: *const c_char) -> *mut Context {
+    ffi::guard(|| {
+        let path = ffi::string(path)?;
+        let ctx = Context::load_snapshot(path)?;
+        Ok(Box::into_raw(Box::new(ctx)))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextClear(ctx: *mut Context, a: AstIdx) -> FfiStatus {
+    ffi::guard_status(|| {
+        ffi::local(ffi::context(ctx)?)?.clear();
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextAddRoot(ctx: *mut Context, id: AstIdx) -> FfiStatus {
+    ffi::guard_status(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        ffi::local(ctx)?.add_root(id);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextRemoveRoot(ctx: *mut Context, id: AstIdx) -> FfiStatus {
+    ffi::guard_status(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        ffi::local(ctx)?.remove_root(id);
+        Ok(())
+    })
+}
+
+// Returns a table mapping each old index to its new index, where freed nodes are mapped to u32::MAX.
+#[no_mangle]
+pub extern "C" fn ContextCollectGarbage(ctx: *mut Context, out_len: *mut u64) -> *mut AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let out_len = ffi::non_null(out_len, "length")?;
+        let remap = ffi::local(ctx)?.collect_garbage();
+        let freed = ffi::invalid_idx();
+        let result_vector: Vec<AstIdx> = remap.into_iter().map(|idx| idx.unwrap_or(freed)).collect();
+
+        // The caller owns the table, and releases it with `FreeAstIdxArray`.
+        Ok(ffi::into_raw_array(result_vector, out_len))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextAdd(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.add(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.add(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextMul(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.mul(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.mul(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextPow(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.pow(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.pow(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextAnd(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.and(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.and(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextOr(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.or(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.or(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextXor(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.xor(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.xor(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextNeg(ctx: *mut Context, a: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.neg(a);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        Ok(ctx.arena.neg(ffi::node(ctx, a)?))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextLshr(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.lshr(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.lshr(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextShl(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.shl(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextAshr(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.ashr(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextUdiv(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.udiv(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextUrem(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.urem(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextSdiv(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.sdiv(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextSrem(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.srem(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextRotl(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.rotl(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextRotr(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.rotr(a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextPopcount(ctx: *mut Context, a: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        Ok(ctx.arena.popcount(ffi::node(ctx, a)?))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextCtlz(ctx: *mut Context, a: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        Ok(ctx.arena.ctlz(ffi::node(ctx, a)?))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextCttz(ctx: *mut Context, a: AstIdx) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        Ok(ctx.arena.cttz(ffi::node(ctx, a)?))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextZext(ctx: *mut Context, a: AstIdx, width: u8) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.zext(a, width);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let from = ctx.arena.get_width(ffi::node(ctx, a)?);
+        if ffi::width(width)? < from {
+            return Err(FfiError::invalid(format!("cannot extend i{} to i{}", from, width)));
+        }
+        Ok(ctx.arena.zext(a, width))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextSext(ctx: *mut Context, a: AstIdx, width: u8) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let from = ctx.arena.get_width(ffi::node(ctx, a)?);
+        if ffi::width(width)? < from {
+            return Err(FfiError::invalid(format!("cannot extend i{} to i{}", from, width)));
+        }
+        Ok(ctx.arena.sext(a, width))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextTrunc(ctx: *mut Context, a: AstIdx, width: u8) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.trunc(a, width);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let from = ctx.arena.get_width(ffi::node(ctx, a)?);
+        if ffi::width(width)? > from {
+            return Err(FfiError::invalid(format!("cannot truncate i{} to i{}", from, width)));
+        }
+        Ok(ctx.arena.trunc(a, width))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextICmp(ctx: *mut Context, pred: Predicate, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.icmp(pred, a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        Ok(ctx.arena.icmp(pred, a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextSelect(ctx: *mut Context, a: AstIdx, b: AstIdx, c: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.select(a, b, c);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        if ctx.arena.get_width(ffi::node(ctx, a)?) != 1 {
+            return Err(FfiError::invalid("select condition must be an i1"));
+        }
+        ffi::same_width(ctx, b, c)?;
+        Ok(ctx.arena.select(a, b, c))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextExtract(ctx: *mut Context, a: AstIdx, high: u8, low: u8) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.extract(a, high, low);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let width = ctx.arena.get_width(ffi::node(ctx, a)?);
+        if low > high || high >= width {
+            return Err(FfiError::invalid(format!(
+                "cannot extract bits {}:{} from i{}",
+                high, low, width
+            )));
+        }
+        Ok(ctx.arena.extract(a, high, low))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextConcat(ctx: *mut Context, a: AstIdx, b: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.concat(a, b);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let width = ctx.arena.get_width(ffi::node(ctx, a)?) as u32 + ctx.arena.get_width(ffi::node(ctx, b)?) as u32;
+        if width > MAX_WIDTH as u32 {
+            return Err(FfiError::invalid(format!("concatenation would have width {}", width)));
+        }
+        Ok(ctx.arena.concat(a, b))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextCarry(ctx: *mut Context, a: AstIdx, b: AstIdx, c: AstIdx) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.carry(a, b, c);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        ffi::same_width(ctx, a, b)?;
+        ffi::same_width(ctx, b, c)?;
+        Ok(ctx.arena.carry(a, b, c))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextConstant(ctx: *mut Context, c: u64, width: u8) -> AstIdx {
-    unsafe {
-        let id = (*ctx).arena.constant(c, width);
-        return id;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        Ok(ctx.arena.constant(c as u128, ffi::width(width)?))
+    })
 }
 
+// Create a constant of up to 128 bits from its low and high halves.
 #[no_mangle]
-pub extern "C" fn ContextSymbol(ctx: *mut Context, s: *const c_char, width: u8) -> AstIdx {
-    let str = marshal_string(s).to_owned();
+pub extern "C" fn ContextConstantWide(ctx: *mut Context, low: u64, high: u64, width: u8) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        Ok(ctx.arena.constant(((high as u128) << 64) | low as u128, ffi::width(width)?))
+    })
+}
 
-    unsafe {
-        let id = (*ctx).arena.symbol_with_name(str.parse().unwrap(), width);
-        return id;
-    }
+#[no_mangle]
+pub extern "C" fn ContextSymbol(ctx: *mut Context, s: *const c_char, width: u8) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let name = ffi::string(s)?;
+        Ok(ctx.arena.symbol_with_name(name, ffi::width(width)?))
+    })
 }
 
 pub fn marshal_string(s: *const c_char) -> String {
@@ -2275,9 +3689,10 @@ pub fn marshal_string(s: *const c_char) -> String {
 
 #[no_mangle]
 pub extern "C" fn ContextGetOpcode(ctx: *const Context, id: AstIdx) -> u8 {
-    unsafe {
-        return get_opcode(&(*ctx), id);
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        Ok(get_opcode(ctx, ffi::node(ctx, id)?))
+    })
 }
 
 pub fn get_opcode(ctx: &Context, id: AstIdx) -> u8 {
@@ -2303,77 +3718,98 @@ pub fn get_opcode(ctx: &Context, id: AstIdx) -> u8 {
         SimpleAst::Extract(_) => 15,
         SimpleAst::Concat(_) => 16,
         SimpleAst::Carry(_) => 17,
+        SimpleAst::Shl(_) => 18,
+        SimpleAst::Ashr(_) => 19,
+        SimpleAst::Udiv(_) => 20,
+        SimpleAst::Urem(_) => 21,
+        SimpleAst::Sdiv(_) => 22,
+        SimpleAst::Srem(_) => 23,
+        SimpleAst::Rotl(_) => 24,
+        SimpleAst::Rotr(_) => 25,
+        SimpleAst::Sext(_) => 26,
+        SimpleAst::Popcount(_) => 27,
+        SimpleAst::Ctlz(_) => 28,
+        SimpleAst::Cttz(_) => 29,
     };
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetWidth(ctx: *mut Context, id: AstIdx) -> u8 {
-    unsafe {
-        let width = (*ctx).arena.get_width(id);
-
-        return width;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        Ok(ctx.arena.get_width(id))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetCost(ctx: *mut Context, id: AstIdx) -> u32 {
-    unsafe {
-        let width = (*ctx).arena.get_cost(id);
-
-        return width;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        Ok(ctx.arena.get_cost(id))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetHasPoly(ctx: *mut Context, id: AstIdx) -> bool {
-    unsafe {
-        let has = (*ctx).arena.get_has_poly(id);
-
-        return has;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        Ok(ctx.arena.get_has_poly(id))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetClass(ctx: *mut Context, id: AstIdx) -> u8 {
-    unsafe {
-        let class = (*ctx).arena.get_class(id);
-
-        return class as u8;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        Ok(ctx.arena.get_class(id) as u8)
+    })
 }
 
+// Only the low 64 bits are reported for wider nodes.
 #[no_mangle]
-pub extern "C" fn ContextGetKnownBits(ctx: *mut Context, id: AstIdx) -> KnownBits {
-    unsafe {
-        let kb = (*ctx).arena.get_data(id).known_bits;
-
-        return kb;
-    }
+pub extern "C" fn ContextGetKnownBits(ctx: *mut Context, id: AstIdx) -> FfiKnownBits {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        Ok(FfiKnownBits::from(&ctx.arena.get_data(id).known_bits))
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetImutData(ctx: *mut Context, id: AstIdx) -> u64 {
-    unsafe {
-        let kb = (*ctx).arena.get_data(id).imut_data;
-
-        return kb;
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        Ok(ctx.arena.get_data(id).imut_data)
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn ContextSetImutData(ctx: *mut Context, id: AstIdx, imut: u64) {
-    unsafe {
-        let mut data = (*ctx).arena.get_data(id).clone();
+pub extern "C" fn ContextSetImutData(ctx: *mut Context, id: AstIdx, imut: u64) -> FfiStatus {
+    ffi::guard_status(|| {
+        let ctx = ffi::context(ctx)?;
+        let id = ffi::node(ctx, id)?;
+        let mut data = ctx.arena.get_data(id).clone();
         data.imut_data = imut;
-        (*ctx).arena.set_data(id, data);
-    }
+        ffi::local(ctx)?.set_data(id, data);
+        Ok(())
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetOp0(ctx: *const Context, id: AstIdx) -> AstIdx {
-    unsafe {
-        return get_op0(&(*ctx), id);
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let children = ctx.arena.get_node(ffi::node(ctx, id)?).children();
+        match children.get(0) {
+            Some(child) => Ok(*child),
+            None => Err(FfiError::invalid(format!("node has {} operands", children.len()))),
+        }
+    })
 }
 
 pub fn get_op0(ctx: &Context, id: AstIdx) -> AstIdx {
@@ -2383,11 +3819,14 @@ pub fn get_op0(ctx: &Context, id: AstIdx) -> AstIdx {
 
 #[no_mangle]
 pub extern "C" fn ContextGetOp1(ctx: *mut Context, id: AstIdx) -> AstIdx {
-    unsafe {
-        unsafe {
-            return get_op1(&(*ctx), id);
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let children = ctx.arena.get_node(ffi::node(ctx, id)?).children();
+        match children.get(1) {
+            Some(child) => Ok(*child),
+            None => Err(FfiError::invalid(format!("node has {} operands", children.len()))),
         }
-    }
+    })
 }
 
 pub fn get_op1(ctx: &Context, id: AstIdx) -> AstIdx {
@@ -2397,11 +3836,14 @@ pub fn get_op1(ctx: &Context, id: AstIdx) -> AstIdx {
 
 #[no_mangle]
 pub extern "C" fn ContextGetOp2(ctx: *mut Context, id: AstIdx) -> AstIdx {
-    unsafe {
-        unsafe {
-            return get_op2(&(*ctx), id);
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let children = ctx.arena.get_node(ffi::node(ctx, id)?).children();
+        match children.get(2) {
+            Some(child) => Ok(*child),
+            None => Err(FfiError::invalid(format!("node has {} operands", children.len()))),
         }
-    }
+    })
 }
 
 pub fn get_op2(ctx: &Context, id: AstIdx) -> AstIdx {
@@ -2411,63 +3853,88 @@ pub fn get_op2(ctx: &Context, id: AstIdx) -> AstIdx {
 
 #[no_mangle]
 pub extern "C" fn ContextGetPredicate(ctx: *mut Context, id: AstIdx) -> u8 {
-    unsafe {
-        let ast = (*ctx).arena.get_node(id);
-        if let SimpleAst::ICmp {
-            predicate,
-            children,
-        } = ast
-        {
-            return (*predicate) as u8;
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        match ctx.arena.get_node(ffi::node(ctx, id)?) {
+            SimpleAst::ICmp { predicate, .. } => Ok(*predicate as u8),
+            _ => Err(FfiError::invalid("ast is not a comparison!")),
         }
-    }
-
-    panic!("ast is not a constant!");
+    })
 }
 
+// Returns the low 64 bits of the constant.
 #[no_mangle]
 pub extern "C" fn ContextGetConstantValue(ctx: *mut Context, id: AstIdx) -> u64 {
-    unsafe {
-        let ast = (*ctx).arena.get_node(id);
-        if let SimpleAst::Constant { c: c2, width } = ast {
-            return *c2;
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        match ctx.arena.get_node(ffi::node(ctx, id)?) {
+            SimpleAst::Constant { c, .. } => Ok(*c as u64),
+            _ => Err(FfiError::invalid("ast is not a constant!")),
         }
-    }
+    })
+}
 
-    panic!("ast is not a constant!");
+// Returns bits 64 to 127 of the constant.
+#[no_mangle]
+pub extern "C" fn ContextGetConstantValueHigh(ctx: *mut Context, id: AstIdx) -> u64 {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        match ctx.arena.get_node(ffi::node(ctx, id)?) {
+            SimpleAst::Constant { c, .. } => Ok((*c >> 64) as u64),
+            _ => Err(FfiError::invalid("ast is not a constant!")),
+        }
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetSymbolName(ctx: *mut Context, id: AstIdx) -> *mut c_char {
-    unsafe {
-        let ast = (*ctx).arena.get_node(id);
-        if let SimpleAst::Symbol { id, width } = ast {
-            let s = (*ctx).arena.get_symbol_name(*id);
-            return CString::new(s).unwrap().into_raw();
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        match ctx.arena.get_node(ffi::node(ctx, id)?) {
+            SimpleAst::Symbol { id, .. } => {
+                let s = ctx.arena.get_symbol_name(*id);
+                Ok(ffi::into_raw_string(&s))
+            }
+            _ => Err(FfiError::invalid("ast is not a symbol!")),
         }
-    }
-    panic!("ast is not a symbol!");
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn ContextParseAstString(ctx: *mut Context, s: *const c_char) -> AstIdx {
-    let str = marshal_string(s).to_owned();
-
-    // Note that the .NET api should be used for parsing strings into ASTs.
-    unsafe {
-        let mut deref: &mut Context = &mut (*ctx);
-        panic!("TODO: Implement parsing in rust");
-    }
+pub extern "C" fn ContextParseAstString(ctx: *mut Context, s: *const c_char, width: u8) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let str = ffi::string(s)?;
+        Ok(parse_ast(ctx, &str, ffi::width(width)?)?)
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextGetAstString(ctx: *mut Context, id: AstIdx) -> *mut c_char {
-    unsafe {
-        let ast: &SimpleAst = (*ctx).arena.get_node(id);
-        let mut deref: &mut Context = &mut (*ctx);
-        let str = AstPrinter::print(deref, &ast.clone());
-        return CString::new(str).unwrap().into_raw();
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let ast = ctx.arena.get_node(ffi::node(ctx, id)?).clone();
+        let str = AstPrinter::print(ctx, &ast);
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextParseSExpr(ctx: *mut Context, s: *const c_char, width: u8) -> AstIdx {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let str = ffi::string(s)?;
+        Ok(parse_sexpr(ctx, &str, ffi::width(width)?)?)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn ContextGetSExprString(ctx: *mut Context, id: AstIdx) -> *mut c_char {
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx)?;
+        let str = print_sexpr(ctx, ffi::node(ctx, id)?);
+        Ok(ffi::into_raw_string(&str))
+    })
 }
 
 #[no_mangle]
@@ -2476,27 +3943,22 @@ pub extern "C" fn ContextCollectVariables(
     id: AstIdx,
     out_len: *mut u64,
 ) -> *mut AstIdx {
-    let mut out_vars = HashSet::new();
-    unsafe {
+    ffi::guard(|| {
+        let deref = ffi::context(ctx)?;
+        let id = ffi::node(deref, id)?;
+        let out_len = ffi::non_null(out_len, "length")?;
+
         // Collect a unique list of variables.
-        let mut deref: &mut Context = &mut (*ctx);
+        let mut out_vars = HashSet::new();
         collect_var_indices(deref, id, &mut out_vars);
 
         // Order the variables alphabetically.
         let mut ordered_indices = Vec::new();
         order_vars_alphabetically(deref, &out_vars, &mut ordered_indices);
 
-        // Update the length pointer.
-        let len = ordered_indices.len();
-        *out_len = len as u64;
-
-        // Give C# ownership of the boxed slice.
-        let boxed = ordered_indices.into_boxed_slice();
-        let released = Box::into_raw(boxed);
-
-        // https://stackoverflow.com/a/57616981/6855629
-        return released as *mut _;
-    }
+        // Give C# ownership of the array, which it releases with `FreeAstIdxArray`.
+        Ok(ffi::into_raw_array(ordered_indices, out_len))
+    })
 }
 
 #[no_mangle]
@@ -2506,26 +3968,28 @@ pub extern "C" fn ContextGetBooleanForIndex(
     var_count: u32,
     result_vec_idx: u32,
 ) -> AstIdx {
-    let mut ast = None;
-
-    let num_vars = var_count as u16;
+    ffi::guard(|| {
+        let deref = ffi::context(ctx)?;
+        if var_count == 0 || var_count > 32 {
+            return Err(FfiError::invalid(format!("invalid variable count {}", var_count)));
+        }
+        let vars = ffi::slice(vars, var_count as usize, "variables")?;
+        ffi::nodes(deref, vars)?;
 
-    unsafe {
-        let mut deref: &mut Context = &mut (*ctx);
-        for var_idx in 0..num_vars {
+        let mut ast = None;
+        for (var_idx, var) in vars.iter().enumerate() {
             let vmask: u32 = 1 << var_idx;
             let is_set = (result_vec_idx & vmask) != 0;
-            let var = *vars.wrapping_add(var_idx as usize);
-            let term = if is_set { var } else { deref.arena.neg(var) };
+            let term = if is_set { *var } else { deref.arena.neg(*var) };
             if ast.is_none() {
                 ast = Some(term);
             } else {
                 ast = Some(deref.arena.and(ast.unwrap(), term));
             }
         }
-    }
 
-    return ast.unwrap();
+        Ok(ast.unwrap())
+    })
 }
 
 #[no_mangle]
@@ -2534,10 +3998,15 @@ pub extern "C" fn ContextGetConjunctionFromVarMask(
     vars: *const AstIdx,
     var_mask: u64,
 ) -> AstIdx {
-    unsafe {
-        let mut deref: &mut Context = &mut (*ctx);
-        return conjunction_from_var_mask(deref, vars, var_mask);
-    }
+    ffi::guard(|| {
+        let deref = ffi::context(ctx)?;
+        if var_mask == 0 {
+            return Err(FfiError::invalid("variable mask is empty"));
+        }
+        let num_vars = 64 - var_mask.leading_zeros() as usize;
+        ffi::nodes(deref, ffi::slice(vars, num_vars, "variables")?)?;
+        Ok(conjunction_from_var_mask(deref, vars, var_mask))
+    })
 }
 
 fn conjunction_from_var_mask(ctx: &mut Context, vars: *const AstIdx, var_mask: u64) -> AstIdx {
@@ -2569,10 +4038,13 @@ pub extern "C" fn ContextEvaluateForAllZeroesAndOnes(
     mask: u64,
     out_len: *mut u64,
 ) -> *mut u64 {
-    let mut out_vars = HashSet::new();
-    unsafe {
+    ffi::guard(|| {
+        let deref = ffi::context(ctx)?;
+        let id = ffi::node(deref, id)?;
+        let out_len = ffi::non_null(out_len, "length")?;
+
         // Collect a unique list of variables.
-        let mut deref: &mut Context = &mut (*ctx);
+        let mut out_vars = HashSet::new();
         collect_var_indices(deref, id, &mut out_vars);
 
         // Order the variables alphabetically.
@@ -2583,17 +4055,9 @@ pub extern "C" fn ContextEvaluateForAllZeroesAndOnes(
         let mut result_vector: Vec<u64> = Vec::new();
         interpret_for_all_truth_values(deref, id, &ordered_indices, &mut result_vector, mask);
 
-        // Update the length pointer.
-        let len = result_vector.len();
-        *out_len = len as u64;
-
-        // Give C# ownership of the boxed slice.
-        let boxed = result_vector.into_boxed_slice();
-        let released = Box::into_raw(boxed);
-
-        // https://stackoverflow.com/a/57616981/6855629
-        return released as *mut _;
-    }
+        // Give C# ownership of the array, which it releases with `FreeU64Array`.
+        Ok(ffi::into_raw_array(result_vector, out_len))
+    })
 }
 
 const PUSH_RCX: u8 = 0x51;
@@ -2643,6 +4107,34 @@ unsafe fn emit(page: *mut u8, offset: &mut usize, data: &[u8]) {
     }
 }
 
+// `jit_rec` only lowers the arithmetic and bitwise operators, zero extension and truncation.
+// Everything else is rejected before any code is emitted, since a half written page cannot be recovered.
+fn check_jit_supported(ctx: &Context, root: AstIdx) -> FfiResult<()> {
+    for idx in post_order(ctx, root) {
+        match ctx.arena.get_node(idx) {
+            SimpleAst::Constant { .. }
+            | SimpleAst::Symbol { .. }
+            | SimpleAst::Neg(_)
+            | SimpleAst::Add(_)
+            | SimpleAst::Mul(_)
+            | SimpleAst::Pow(_)
+            | SimpleAst::And(_)
+            | SimpleAst::Or(_)
+            | SimpleAst::Xor(_)
+            | SimpleAst::Zext(_)
+            | SimpleAst::Trunc(_) => (),
+            ast => {
+                return Err(FfiError::invalid(format!(
+                    "ContextJit does not support {} nodes",
+                    ast
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
 // Note that we need to incorporate the ast size into the JIT code.
 unsafe fn jit_rec(
     ctx: &Context,
@@ -2665,7 +4157,7 @@ unsafe fn jit_rec(
 
     match ctx.arena.get_node(node) {
         SimpleAst::Constant { c, width } => {
-            jit_constant(*c, page, offset);
+            jit_constant(*c as u64, page, offset);
         }
         SimpleAst::Neg([a]) => {
             jit_rec(ctx, *a, node_to_var, page, offset);
@@ -2746,27 +4238,19 @@ unsafe fn jit_rec(
             emit_u8(page, offset, 0xB8);
             // Fill in the constant
             let to = ctx.arena.get_constant(*to_id);
-            let trunc_mask = get_modulo_mask(to as u8);
+            let trunc_mask = get_modulo_mask(to as u8) as u64;
             emit_u64(page, offset, trunc_mask);
             // and [rsp+8], rax
             emit(page, offset, &[0x48, 0x21, 0x04, 0x24]);
         }
-        SimpleAst::Lshr([a, b]) => todo!(),
-        SimpleAst::ICmp {
-            predicate,
-            children,
-        } => todo!(),
-        SimpleAst::Select { children } => todo!(),
-        SimpleAst::Extract(_) => todo!(),
-        SimpleAst::Concat(_) => todo!(),
-        SimpleAst::Carry(_) => todo!(),
+        ast => unreachable!("{} nodes are rejected by check_jit_supported", ast),
     };
 
     // mov rax, constant
     emit_u8(page, offset, 0x48);
     emit_u8(page, offset, 0xB8);
     // Fill in the constant
-    let c = get_modulo_mask(ctx.arena.get_width(node));
+    let c = get_modulo_mask(ctx.arena.get_width(node)) as u64;
     emit_u64(page, offset, c);
     // and [rsp+8], rax
     emit(page, offset, &[0x48, 0x21, 0x04, 0x24]);
@@ -2798,7 +4282,69 @@ pub extern "C" fn Pow(mut base: u64, mut exp: u64) -> u64 {
         base = base.wrapping_mul(base);
     }
 
-    return res;
+    return res;
+}
+
+#[no_mangle]
+pub extern "C" fn GetUdivPtr() -> u64 {
+    return Udiv as *const () as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn GetUremPtr() -> u64 {
+    return Urem as *const () as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn GetSdivPtr() -> u64 {
+    return Sdiv as *const () as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn GetSremPtr() -> u64 {
+    return Srem as *const () as u64;
+}
+
+// Division thunks called from jitted code. The width is needed to pick the sign bit.
+#[no_mangle]
+pub extern "C" fn Udiv(a: u64, b: u64, width: u64) -> u64 {
+    return udiv_const(a as u128, b as u128, width as u8) as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn Urem(a: u64, b: u64, width: u64) -> u64 {
+    return urem_const(a as u128, b as u128, width as u8) as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn Sdiv(a: u64, b: u64, width: u64) -> u64 {
+    return sdiv_const(a as u128, b as u128, width as u8) as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn Srem(a: u64, b: u64, width: u64) -> u64 {
+    return srem_const(a as u128, b as u128, width as u8) as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn GetRotlPtr() -> u64 {
+    return Rotl as *const () as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn GetRotrPtr() -> u64 {
+    return Rotr as *const () as u64;
+}
+
+// Rotate thunks for widths that the hardware cannot rotate directly.
+#[no_mangle]
+pub extern "C" fn Rotl(a: u64, b: u64, width: u64) -> u64 {
+    return rotl_const(a as u128, b as u128, width as u8) as u64;
+}
+
+#[no_mangle]
+pub extern "C" fn Rotr(a: u64, b: u64, width: u64) -> u64 {
+    return rotr_const(a as u128, b as u128, width as u8) as u64;
 }
 
 #[no_mangle]
@@ -2809,46 +4355,53 @@ pub unsafe extern "C" fn ContextCompileLegacy(
     variables: *const AstIdx,
     var_count: u64,
     page: *mut u8,
-) {
-    let mut ctx: &mut Context = &mut (*ctx_p);
-
-    let mut offset: usize = 0;
-
-    // Push all clobbered registers
-    emit_u8(page, &mut offset, PUSH_RBX);
-    emit_u8(page, &mut offset, PUSH_RSI);
-    emit_u8(page, &mut offset, PUSH_RDI);
+) -> FfiStatus {
+    ffi::guard_status(|| {
+        let mut ctx: &mut Context = ffi::context(ctx_p)?;
+        ffi::node(ctx, node)?;
+        ffi::nodes(ctx, ffi::slice(variables, var_count as usize, "variables")?)?;
+        ffi::non_null(page, "page")?;
+
+        let mut offset: usize = 0;
+
+        // Push all clobbered registers
+        emit_u8(page, &mut offset, PUSH_RBX);
+        emit_u8(page, &mut offset, PUSH_RSI);
+        emit_u8(page, &mut offset, PUSH_RDI);
+
+        // JIT code
+        let mut node_to_var: HashMap<AstIdx, u8> = HashMap::with_capacity(var_count as usize);
+        for i in 0..var_count {
+            node_to_var.insert(*variables.add(i as usize), i as u8);
+        }
 
-    // JIT code
-    let mut node_to_var: HashMap<AstIdx, u8> = HashMap::with_capacity(var_count as usize);
-    for i in 0..var_count {
-        node_to_var.insert(*variables.add(i as usize), i as u8);
-    }
+        jit_rec(ctx, node, &node_to_var, page, &mut offset);
 
-    jit_rec(ctx, node, &node_to_var, page, &mut offset);
+        // Pop the evaluation result
+        emit_u8(page, &mut offset, POP_RAX);
 
-    // Pop the evaluation result
-    emit_u8(page, &mut offset, POP_RAX);
+        // Mask off bits that we don't care about
+        // mov rsi, mask
+        emit(page, &mut offset, &[0x48, 0xBE]);
+        emit_u64(page, &mut offset, mask);
 
-    // Mask off bits that we don't care about
-    // mov rsi, mask
-    emit(page, &mut offset, &[0x48, 0xBE]);
-    emit_u64(page, &mut offset, mask);
+        // and rax, rsi
+        emit(page, &mut offset, &[0x48, 0x21, 0xF0]);
 
-    // and rax, rsi
-    emit(page, &mut offset, &[0x48, 0x21, 0xF0]);
+        // Shift the value back down to bit index zero,
+        // varValue = varValue >> (ushort)v
+        // shr rax, bitIdxRegister
+        emit(page, &mut offset, &[0x48, 0xD3, 0xE8]);
 
-    // Shift the value back down to bit index zero,
-    // varValue = varValue >> (ushort)v
-    // shr rax, bitIdxRegister
-    emit(page, &mut offset, &[0x48, 0xD3, 0xE8]);
+        // Restore the clobbered registers.
+        emit_u8(page, &mut offset, POP_RDI);
+        emit_u8(page, &mut offset, POP_RSI);
+        emit_u8(page, &mut offset, POP_RBX);
 
-    // Restore the clobbered registers.
-    emit_u8(page, &mut offset, POP_RDI);
-    emit_u8(page, &mut offset, POP_RSI);
-    emit_u8(page, &mut offset, POP_RBX);
+        emit_u8(page, &mut offset, RET);
 
-    emit_u8(page, &mut offset, RET);
+        Ok(())
+    })
 }
 
 #[no_mangle]
@@ -2859,18 +4412,31 @@ pub unsafe extern "C" fn ContextCompile(
     variables: *const AstIdx,
     var_count: u64,
     page: *mut u8,
-) {
-    let mut ctx: &mut Context = &mut (*ctx_p);
+) -> FfiStatus {
+    ffi::guard_status(|| {
+        let mut ctx: &mut Context = ffi::context(ctx_p)?;
+        ffi::node(ctx, node)?;
+        ffi::nodes(ctx, ffi::slice(variables, var_count as usize, "variables")?)?;
+        ffi::non_null(page, "page")?;
+        // Every value is kept in a 64-bit register, so wider constants would be silently truncated.
+        if post_order(ctx, node).iter().any(|idx| ctx.arena.get_width(*idx) > 64) {
+            return Err(FfiError::invalid("ContextCompile only supports values of up to 64 bits"));
+        }
+
+        let mut vars: Vec<AstIdx> = Vec::new();
+        // JIT code
+        for i in 0..var_count {
+            vars.push(*variables.add(i as usize));
+        }
 
-    let mut vars: Vec<AstIdx> = Vec::new();
-    // JIT code
-    for i in 0..var_count {
-        vars.push(*variables.add(i as usize));
-    }
+        // The JIT keeps scratch state inside of `AstData`, which cannot be mutated when other threads may be reading it.
+        let nodes = ffi::local(ctx)?;
+        let mut assembler = FastAmd64Assembler::new(page);
+        let mut compiler = Amd64OptimizingJit::<FastAmd64Assembler>::new();
+        compiler.compile(nodes, &mut assembler, node, &vars, page, false);
 
-    let mut assembler = FastAmd64Assembler::new(page);
-    let mut compiler = Amd64OptimizingJit::<FastAmd64Assembler>::new();
-    compiler.compile(ctx, &mut assembler, node, &vars, page, false);
+        Ok(())
+    })
 }
 
 #[no_mangle]
@@ -2883,43 +4449,49 @@ pub unsafe extern "C" fn ContextExecute(
     output: *mut u64,
     one_bit_vars: u32,
     shift: u32,
-) {
-    let multi_bit = multi_bit_u != 0;
-    let num_bit_iterations: u32 = if multi_bit { bit_width } else { 1 };
+) -> FfiStatus {
+    ffi::guard_status(|| {
+        ffi::non_null(page, "page")?;
+        ffi::non_null(output, "output")?;
+        let multi_bit = multi_bit_u != 0;
+        let num_bit_iterations: u32 = if multi_bit { bit_width } else { 1 };
+
+        if (one_bit_vars != 0) {
+            let fptr: unsafe extern "C" fn(u32, u64) -> u64 = std::mem::transmute(page);
+
+            let mut arr_idx: usize = 0;
+            for bit_index in 0..num_bit_iterations {
+                for i in 0..num_combinations {
+                    let result = fptr(bit_index, i);
+                    *output.add(arr_idx) = result;
+                    arr_idx += 1;
+                }
+            }
 
-    if (one_bit_vars != 0) {
-        let fptr: unsafe extern "C" fn(u32, u64) -> u64 = std::mem::transmute(page);
+            return Ok(());
+        }
+
+        let mut var_values = vec![0u64; var_count as usize];
+        let vptr = var_values.as_mut_slice();
 
         let mut arr_idx: usize = 0;
+        let fptr: unsafe extern "C" fn(*mut u64) -> u64 = std::mem::transmute(page);
         for bit_index in 0..num_bit_iterations {
             for i in 0..num_combinations {
-                let result = fptr(bit_index, i);
+                for v_idx in 0..var_count {
+                    vptr[v_idx as usize] = ((i >> v_idx) & 1) << bit_index;
+                }
+            
+                let lshr = if shift != 0 { bit_index } else { 0 };
+
+                let result = (fptr(vptr.as_mut_ptr()) & get_modulo_mask(bit_width as u8) as u64) >> lshr;
                 *output.add(arr_idx) = result;
                 arr_idx += 1;
             }
         }
 
-        return;
-    }
-
-    let mut var_values = vec![0u64; var_count as usize];
-    let vptr = var_values.as_mut_slice();
-
-    let mut arr_idx: usize = 0;
-    let fptr: unsafe extern "C" fn(*mut u64) -> u64 = std::mem::transmute(page);
-    for bit_index in 0..num_bit_iterations {
-        for i in 0..num_combinations {
-            for v_idx in 0..var_count {
-                vptr[v_idx as usize] = ((i >> v_idx) & 1) << bit_index;
-            }
-            
-            let lshr = if shift != 0 { bit_index } else { 0 };
-
-            let result = (fptr(vptr.as_mut_ptr()) & get_modulo_mask(bit_width as u8)) >> lshr;
-            *output.add(arr_idx) = result;
-            arr_idx += 1;
-        }
-    }
+        Ok(())
+    })
 }
 
 #[no_mangle]
@@ -2934,68 +4506,77 @@ pub unsafe extern "C" fn ContextJit(
     num_combinations: u64,
     page: *mut u8,
     output: *mut u64,
-) {
-    let multi_bit = multi_bit_u != 0;
-    let num_bit_iterations: u32 = if multi_bit { bit_width } else { 1 };
-
-    let mut ctx: &mut Context = &mut (*ctx_p);
-
-    let mut offset: usize = 0;
-
-    // Push all clobbered registers
-    emit_u8(page, &mut offset, PUSH_RBX);
-    emit_u8(page, &mut offset, PUSH_RSI);
-    emit_u8(page, &mut offset, PUSH_RDI);
-
-    // JIT code
-    let mut node_to_var: HashMap<AstIdx, u8> = HashMap::with_capacity(var_count as usize);
-    for i in 0..var_count {
-        node_to_var.insert(*variables.add(i as usize), i as u8);
-    }
+) -> FfiStatus {
+    ffi::guard_status(|| {
+        let multi_bit = multi_bit_u != 0;
+        let num_bit_iterations: u32 = if multi_bit { bit_width } else { 1 };
+
+        let mut ctx: &mut Context = ffi::context(ctx_p)?;
+        ffi::node(ctx, node)?;
+        ffi::nodes(ctx, ffi::slice(variables, var_count as usize, "variables")?)?;
+        ffi::non_null(page, "page")?;
+        ffi::non_null(output, "output")?;
+        check_jit_supported(ctx, node)?;
+
+        let mut offset: usize = 0;
+
+        // Push all clobbered registers
+        emit_u8(page, &mut offset, PUSH_RBX);
+        emit_u8(page, &mut offset, PUSH_RSI);
+        emit_u8(page, &mut offset, PUSH_RDI);
+
+        // JIT code
+        let mut node_to_var: HashMap<AstIdx, u8> = HashMap::with_capacity(var_count as usize);
+        for i in 0..var_count {
+            node_to_var.insert(*variables.add(i as usize), i as u8);
+        }
 
-    jit_rec(ctx, node, &node_to_var, page, &mut offset);
+        jit_rec(ctx, node, &node_to_var, page, &mut offset);
 
-    // Pop the evaluation result
-    emit_u8(page, &mut offset, POP_RAX);
+        // Pop the evaluation result
+        emit_u8(page, &mut offset, POP_RAX);
 
-    // Mask off bits that we don't care about
-    // mov rsi, mask
-    emit(page, &mut offset, &[0x48, 0xBE]);
-    emit_u64(page, &mut offset, mask);
+        // Mask off bits that we don't care about
+        // mov rsi, mask
+        emit(page, &mut offset, &[0x48, 0xBE]);
+        emit_u64(page, &mut offset, mask);
 
-    // and rax, rsi
-    emit(page, &mut offset, &[0x48, 0x21, 0xF0]);
+        // and rax, rsi
+        emit(page, &mut offset, &[0x48, 0x21, 0xF0]);
 
-    // Shift the value back down to bit index zero,
-    // varValue = varValue >> (ushort)v
-    // shr rax, bitIdxRegister
-    emit(page, &mut offset, &[0x48, 0xD3, 0xE8]);
+        // Shift the value back down to bit index zero,
+        // varValue = varValue >> (ushort)v
+        // shr rax, bitIdxRegister
+        emit(page, &mut offset, &[0x48, 0xD3, 0xE8]);
 
-    // Restore the clobbered registers.
-    emit_u8(page, &mut offset, POP_RDI);
-    emit_u8(page, &mut offset, POP_RSI);
-    emit_u8(page, &mut offset, POP_RBX);
+        // Restore the clobbered registers.
+        emit_u8(page, &mut offset, POP_RDI);
+        emit_u8(page, &mut offset, POP_RSI);
+        emit_u8(page, &mut offset, POP_RBX);
 
-    emit_u8(page, &mut offset, RET);
+        emit_u8(page, &mut offset, RET);
 
-    let fptr: unsafe extern "C" fn(u32, u64) -> u64 = std::mem::transmute(page);
+        let fptr: unsafe extern "C" fn(u32, u64) -> u64 = std::mem::transmute(page);
 
-    let mut arr_idx: usize = 0;
-    for bit_index in 0..num_bit_iterations {
-        for i in 0..num_combinations {
-            let result = fptr(bit_index, i);
-            *output.add(arr_idx) = result;
-            arr_idx += 1;
+        let mut arr_idx: usize = 0;
+        for bit_index in 0..num_bit_iterations {
+            for i in 0..num_combinations {
+                let result = fptr(bit_index, i);
+                *output.add(arr_idx) = result;
+                arr_idx += 1;
+            }
         }
-    }
+
+        Ok(())
+    })
 }
 
 // Run ISLE until a fixed point is reached, but do not recurse.
 #[no_mangle]
 pub extern "C" fn ContextSingleSimplify(ctx: *mut Context, idx: AstIdx) -> AstIdx {
-    unsafe {
-        let mut deref: &mut Context = &mut (*ctx);
-        let mut ast = deref.arena.get_node(idx).clone();
+    ffi::guard(|| {
+        let deref = ffi::context(ctx)?;
+        let mut ast = deref.arena.get_node(ffi::node(deref, idx)?).clone();
 
         while true {
             let result = try_simplify_with_isle(deref, &ast);
@@ -3006,16 +4587,17 @@ pub extern "C" fn ContextSingleSimplify(ctx: *mut Context, idx: AstIdx) -> AstId
             ast = result.unwrap();
         }
 
-        return deref.arena.ast_to_idx[&ast];
-    }
+        Ok(deref.arena.lookup_idx(&ast).unwrap())
+    })
 }
 
 #[no_mangle]
 pub extern "C" fn ContextRecursiveSimplify(ctx: *mut Context, id: AstIdx) -> AstIdx {
-    unsafe {
-        let mut deref: &mut Context = &mut (*ctx);
-        return recursive_simplify(deref, id);
-    }
+    ffi::guard(|| {
+        let deref = ffi::context(ctx)?;
+        let id = ffi::node(deref, id)?;
+        Ok(recursive_simplify(deref, id))
+    })
 }
 
 const VARIABLE_COMBINATIONS_1: &[u16] = &get_variable_combinations::<1, 1>();
@@ -3117,43 +4699,68 @@ pub unsafe extern "C" fn ContextMinimizeAnf(
     variable_count: u32,
     page: *mut u8,
 ) -> AstIdx {
-    unsafe {
-        let mut ctx_deref: &mut Context = &mut (*ctx);
+    ffi::guard(|| {
+        let ctx_deref = ffi::context(ctx)?;
+        let table_deref = ffi::non_null(db, "truth table database")?;
+        api::check_anf_variable_count(variable_count as usize)?;
+        let vars = ffi::slice(vars, variable_count as usize, "variables")?;
+        ffi::nodes(ctx_deref, vars)?;
+        api::check_anf_variables(ctx_deref, vars)?;
+        ffi::non_null(truth_table, "truth table")?;
         let table = TruthTable {
             num_vars: variable_count,
             arr: truth_table,
         };
 
-        let mut table_deref: &mut TruthTableDatabase = &mut (*db);
+        Ok(minimize_truth_table(ctx_deref, table_deref, &table, vars, page))
+    })
+}
 
-        // If the truth table has a positive constant offset, negate it.
-        // This is necessary because the ANF minimization algorithm does not support positive constants.
-        let negated = table.get_bit(table.get_mut_arr(), 0) != 0;
-        if negated {
-            table.negate();
-        }
+// Find a minimal boolean expression for a truth table over `vars`. The truth table is temporarily modified,
+// but its contents are restored before returning. If `page` is null, the interpreter is used instead of the JIT.
+pub fn minimize_truth_table(
+    ctx: &mut Context,
+    db: &TruthTableDatabase,
+    table: &TruthTable,
+    vars: &[AstIdx],
+    page: *mut u8,
+) -> AstIdx {
+    // Constant truth tables have no terms to minimize.
+    let arr = table.get_mut_arr();
+    let first = table.get_bit(arr, 0);
+    if (1..table.get_num_bits()).all(|i| table.get_bit(arr, i) == first) {
+        let width = ctx.arena.get_width(vars[0]);
+        let c = if first == 0 { 0 } else { get_modulo_mask(width) };
+        return ctx.arena.constant(c, width);
+    }
 
-        // Minimize the boolean.
-        let result = minimize_anf(ctx_deref, table_deref, &table, vars, variable_count, page);
+    // If the truth table has a positive constant offset, negate it.
+    // This is necessary because the ANF minimization algorithm does not support positive constants.
+    let negated = table.get_bit(table.get_mut_arr(), 0) != 0;
+    if negated {
+        table.negate();
+    }
 
-        // We want to preserve the contents of the truth table, so we need to undo the negation.
-        if negated {
-            table.negate();
-        }
+    // Minimize the boolean.
+    let result = minimize_anf(ctx, db, table, vars.as_ptr(), vars.len() as u32, page);
 
-        // If the truth table was negated, we need to negate the whole expression.
-        return if negated {
-            ctx_deref.arena.neg(result)
-        } else {
-            result
-        };
+    // We want to preserve the contents of the truth table, so we need to undo the negation.
+    if negated {
+        table.negate();
     }
+
+    // If the truth table was negated, we need to negate the whole expression.
+    return if negated {
+        ctx.arena.neg(result)
+    } else {
+        result
+    };
 }
 
 // Compute a minimized algebraic normal form.
 pub fn minimize_anf(
     ctx: &mut Context,
-    db: &mut TruthTableDatabase,
+    db: &TruthTableDatabase,
     table: &TruthTable,
     vars: *const AstIdx,
     variable_count: u32,
@@ -3419,7 +5026,7 @@ pub fn get_demanded_vars_mask(
 
 pub fn simplify_rec(
     ctx: &mut Context,
-    db: &mut TruthTableDatabase,
+    db: &TruthTableDatabase,
     idx: AstIdx,
     variables: *const AstIdx,
     variable_count: u32,
@@ -3561,7 +5168,7 @@ pub fn simplify_rec(
 
 pub fn simplify_via_lookup_table(
     ctx: &mut Context,
-    db: &mut TruthTableDatabase,
+    db: &TruthTableDatabase,
     idx: AstIdx,
     variables: *const AstIdx,
     variable_count: u32,
@@ -3586,19 +5193,25 @@ pub fn simplify_via_lookup_table(
 
     // Construct a result vector
     // TODO: Use parallel boolean jit instead of traditional semi-linear JIT
-    unsafe {
-        ContextJit(
-            ctx,
-            idx,
-            1,
-            1,
-            1,
-            var_set.as_ptr(),
-            var_set.len() as u64,
-            num_combinations as u64,
-            page,
-            rv_slice,
-        );
+    if ctx.arena.is_shared() || page.is_null() {
+        // The JIT keeps scratch state inside of `AstData`, which cannot be mutated when other threads may be reading it.
+        rv.clear();
+        interpret_for_all_truth_values(ctx, idx, var_set, rv, 1);
+    } else {
+        unsafe {
+            ContextJit(
+                ctx,
+                idx,
+                1,
+                1,
+                1,
+                var_set.as_ptr(),
+                var_set.len() as u64,
+                num_combinations as u64,
+                page,
+                rv_slice,
+            );
+        }
     }
 
     let mut truth_table: u64 = 0;
@@ -3744,13 +5357,13 @@ struct AuxInfoStorage<T: From<u64> + Into<u64> + Exists> {
 }
 
 impl<T: From<u64> + Into<u64> + Exists> AuxInfoStorage<T> {
-    pub fn contains(ctx: &mut Context, idx: AstIdx) -> bool {
-        let value = Self::get(ctx, idx);
+    pub fn contains(nodes: &mut LocalNodes, idx: AstIdx) -> bool {
+        let value = Self::get(nodes, idx);
         return value.exists();
     }
 
-    pub fn get(ctx: &mut Context, idx: AstIdx) -> T {
-        let value = ctx.arena.get_data(idx).imut_data;
+    pub fn get(nodes: &mut LocalNodes, idx: AstIdx) -> T {
+        let value = nodes.get_data(idx).imut_data;
         return T::from(value);
     }
 
@@ -3768,8 +5381,8 @@ impl<T: From<u64> + Into<u64> + Exists> AuxInfoStorage<T> {
         }
     }
 
-    pub fn set(ctx: &mut Context, idx: AstIdx, value: T) {
-        ctx.arena.get_data_mut(idx).imut_data = value.into();
+    pub fn set(nodes: &mut LocalNodes, idx: AstIdx, value: T) {
+        nodes.get_data_mut(idx).imut_data = value.into();
     }
 
     pub fn set_unsafe(ptr: *mut (SimpleAst, AstData), idx: AstIdx, value: T) {
@@ -3779,8 +5392,8 @@ impl<T: From<u64> + Into<u64> + Exists> AuxInfoStorage<T> {
         }
     }
 
-    pub fn try_get(ctx: &mut Context, idx: AstIdx) -> Option<T> {
-        let value = Self::get(ctx, idx);
+    pub fn try_get(nodes: &mut LocalNodes, idx: AstIdx) -> Option<T> {
+        let value = Self::get(nodes, idx);
         if value.exists() {
             return Some(value);
         }
@@ -3873,7 +5486,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
     #[inline(never)]
     fn compile(
         &mut self,
-        ctx: &mut Context,
+        nodes: &mut LocalNodes,
         assembler: &mut T,
         idx: AstIdx,
         variables: &Vec<AstIdx>,
@@ -3881,23 +5494,23 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         use_iced_backend: bool,
     ) {
         // Collect necessary information about nodes for JITing (dfs order, how many users a node has).
-        Self::collect_info(ctx, idx, &mut self.dfs);
+        Self::collect_info(nodes, idx, &mut self.dfs);
 
         // Store each variables argument index
         for i in 0..variables.len() {
             let var_idx = variables[i];
-            let mut info = AuxInfoStorage::<NodeInfo>::get(ctx, var_idx);
+            let mut info = AuxInfoStorage::<NodeInfo>::get(nodes, var_idx);
             info.var_idx = i as u16;
-            AuxInfoStorage::<NodeInfo>::set(ctx, var_idx, info);
+            AuxInfoStorage::<NodeInfo>::set(nodes, var_idx, info);
         }
 
         // Compile the instructions to x86.
-        self.lower_to_x86(ctx, assembler);
+        self.lower_to_x86(nodes, assembler);
 
         // Clear each node's mutable data.
         for id in self.dfs.iter() {
-            let mut info = AuxInfoStorage::<NodeInfo>::get(ctx, *id);
-            AuxInfoStorage::<NodeInfo>::set(ctx, *id, NodeInfo::from(0));
+            let mut info = AuxInfoStorage::<NodeInfo>::get(nodes, *id);
+            AuxInfoStorage::<NodeInfo>::set(nodes, *id, NodeInfo::from(0));
         }
 
         // If using the fast assembler backend, we've already emitted x86.
@@ -3917,14 +5530,14 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         Self::write_instructions(page_ptr, &instructions);
     }
 
-    fn collect_info(ctx: &mut Context, idx: AstIdx, dfs: &mut Vec<AstIdx>) {
-        let existing = AuxInfoStorage::<NodeInfo>::try_get(ctx, idx);
+    fn collect_info(nodes: &mut LocalNodes, idx: AstIdx, dfs: &mut Vec<AstIdx>) {
+        let existing = AuxInfoStorage::<NodeInfo>::try_get(nodes, idx);
         if existing.is_some() {
             dfs.push(idx);
             return;
         }
 
-        let node = ctx.arena.get_node(idx).clone();
+        let node = nodes.get_node(idx).clone();
         match node {
             SimpleAst::Add([a, b])
             | SimpleAst::Mul([a, b])
@@ -3933,16 +5546,30 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
             | SimpleAst::Or([a, b])
             | SimpleAst::Xor([a, b])
             | SimpleAst::Lshr([a, b])
+            | SimpleAst::Shl([a, b])
+            | SimpleAst::Ashr([a, b])
+            | SimpleAst::Udiv([a, b])
+            | SimpleAst::Urem([a, b])
+            | SimpleAst::Sdiv([a, b])
+            | SimpleAst::Srem([a, b])
+            | SimpleAst::Rotl([a, b])
+            | SimpleAst::Rotr([a, b])
             | SimpleAst::Concat([a, b]) => {
-                Self::collect_info(ctx, a, dfs);
-                Self::collect_info(ctx, b, dfs);
+                Self::collect_info(nodes, a, dfs);
+                Self::collect_info(nodes, b, dfs);
 
-                Self::inc_users(ctx, a);
-                Self::inc_users(ctx, b);
+                Self::inc_users(nodes, a);
+                Self::inc_users(nodes, b);
             }
-            SimpleAst::Neg([a]) | SimpleAst::Zext([a, _]) | SimpleAst::Trunc([a, _]) => {
-                Self::collect_info(ctx, a, dfs);
-                Self::inc_users(ctx, a);
+            SimpleAst::Neg([a])
+            | SimpleAst::Zext([a, _])
+            | SimpleAst::Sext([a, _])
+            | SimpleAst::Trunc([a, _])
+            | SimpleAst::Popcount([a])
+            | SimpleAst::Ctlz([a])
+            | SimpleAst::Cttz([a]) => {
+                Self::collect_info(nodes, a, dfs);
+                Self::inc_users(nodes, a);
             }
             SimpleAst::Constant { .. } | SimpleAst::Symbol { .. } => (),
             SimpleAst::ICmp {
@@ -3956,13 +5583,13 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         }
 
         dfs.push(idx);
-        AuxInfoStorage::<NodeInfo>::set(ctx, idx, NodeInfo::new(0));
+        AuxInfoStorage::<NodeInfo>::set(nodes, idx, NodeInfo::new(0));
     }
 
-    fn inc_users(ctx: &mut Context, idx: AstIdx) {
-        let mut info = AuxInfoStorage::<NodeInfo>::get(ctx, idx);
+    fn inc_users(nodes: &mut LocalNodes, idx: AstIdx) {
+        let mut info = AuxInfoStorage::<NodeInfo>::get(nodes, idx);
         info.num_uses = info.num_uses.add(1);
-        AuxInfoStorage::<NodeInfo>::set(ctx, idx, info);
+        AuxInfoStorage::<NodeInfo>::set(nodes, idx, info);
     }
 
     fn inc_users_unsafe(ptr: *mut (SimpleAst, AstData), idx: AstIdx) {
@@ -3972,7 +5599,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
     }
 
     #[inline(never)]
-    fn lower_to_x86(&mut self, ctx: &mut Context, assembler: &mut T) {
+    fn lower_to_x86(&mut self, nodes: &mut LocalNodes, assembler: &mut T) {
         // rcx reserved for local variables ptr (or all vars in the case of a semi-linear result vector)
         // RSI, RDI reserved for temporary use
 
@@ -3981,14 +5608,14 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
 
         for i in 0..self.dfs.len() {
             let idx = unsafe { *self.dfs.get_unchecked(i) };
-            let node_info = AuxInfoStorage::<NodeInfo>::get(ctx, idx);
+            let node_info = AuxInfoStorage::<NodeInfo>::get(nodes, idx);
             if node_info.num_uses > 1 && node_info.slot_idx != u16::MAX {
                 self.load_slot_value(assembler, node_info.slot_idx as u32);
                 continue;
             }
 
-            let width = ctx.arena.get_width(idx) as u32;
-            let node = ctx.arena.get_node(idx).clone();
+            let width = nodes.get_width(idx) as u32;
+            let node = nodes.get_node(idx).clone();
             match node {
                 SimpleAst::Add([a, b])
                 | SimpleAst::Mul([a, b])
@@ -3996,24 +5623,39 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
                 | SimpleAst::And([a, b])
                 | SimpleAst::Or([a, b])
                 | SimpleAst::Xor([a, b])
-                | SimpleAst::Lshr([a, b]) => {
-                    self.lower_binop(ctx, assembler, idx, node, width, node_info)
+                | SimpleAst::Lshr([a, b])
+                | SimpleAst::Shl([a, b])
+                | SimpleAst::Ashr([a, b])
+                | SimpleAst::Udiv([a, b])
+                | SimpleAst::Urem([a, b])
+                | SimpleAst::Sdiv([a, b])
+                | SimpleAst::Srem([a, b])
+                | SimpleAst::Rotl([a, b])
+                | SimpleAst::Rotr([a, b]) => {
+                    self.lower_binop(nodes, assembler, idx, node, width, node_info)
                 }
-                SimpleAst::Constant { c, width } => self.lower_constant(assembler, c),
+                SimpleAst::Constant { c, width } => self.lower_constant(assembler, c as u64),
                 SimpleAst::Symbol { .. } => {
                     self.lower_variable(assembler, node_info.var_idx.into(), width)
                 }
                 SimpleAst::Neg { .. } | SimpleAst::Zext { .. } => self.lower_unary_op(
-                    ctx,
+                    nodes,
                     assembler,
                     idx,
                     width,
                     node_info,
                     matches!(node, SimpleAst::Neg { .. }),
                 ),
+                SimpleAst::Sext([a, _]) => {
+                    let w = nodes.get_width(a);
+                    self.lower_sext(nodes, assembler, idx, w.into(), width, node_info)
+                }
                 SimpleAst::Trunc([a, to_id]) => {
-                    let w = ctx.get_width(a);
-                    self.lower_zext(ctx, assembler, idx, w.into(), node_info)
+                    let w = nodes.get_width(a);
+                    self.lower_zext(nodes, assembler, idx, w.into(), node_info)
+                }
+                SimpleAst::Popcount(_) | SimpleAst::Ctlz(_) | SimpleAst::Cttz(_) => {
+                    self.lower_bit_count(nodes, assembler, idx, node, width, node_info)
                 }
                 SimpleAst::ICmp {
                     predicate,
@@ -4038,8 +5680,8 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         }
 
         // Reduce the result modulo 2**w
-        let w = ctx.get_width(*self.dfs.last().unwrap());
-        assembler.movabs_reg_imm64(SCRATCH1, get_modulo_mask(w));
+        let w = nodes.get_width(*self.dfs.last().unwrap());
+        assembler.movabs_reg_imm64(SCRATCH1, get_modulo_mask(w) as u64);
         assembler.and_reg_reg(Register::RAX, SCRATCH1);
 
         Self::emit_epilogue(assembler, self.slot_count as u32);
@@ -4059,7 +5701,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
 
     fn lower_binop(
         &mut self,
-        ctx: &mut Context,
+        nodes: &mut LocalNodes,
         assembler: &mut T,
         idx: AstIdx,
         node: SimpleAst,
@@ -4093,42 +5735,73 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
             SimpleAst::And([a, b]) => assembler.and_reg_reg(lhs_dest, rhs_dest),
             SimpleAst::Or([a, b]) => assembler.or_reg_reg(lhs_dest, rhs_dest),
             SimpleAst::Xor([a, b]) => assembler.xor_reg_reg(lhs_dest, rhs_dest),
-            SimpleAst::Lshr([a, b]) => {
-                if width % 8 != 0 {
-                    panic!("Cannot jit lshr with non power of 2 width!");
+            // The hardware reduces shift counts modulo 64, while a shift by the bit width or more
+            // yields zero(or the sign fill for ashr). Counts in [width, 64) already shift every bit
+            // out of the reduced value, so only counts >= 64 need fixing up.
+            SimpleAst::Lshr([a, b]) | SimpleAst::Shl([a, b]) => {
+                assembler.push_reg(Register::RCX);
+                assembler.mov_reg_reg(Register::RCX, rhs_dest);
+                if matches!(node, SimpleAst::Lshr(_)) {
+                    assembler.shr_reg_cl(lhs_dest);
+                } else {
+                    assembler.shl_reg_cl(lhs_dest);
                 }
 
-                // Reduce shift count modulo the bit width of the operation
-                // TODO: (a) Handle non power of two bit widths,
-                //       (b) shift beyond bounds should yield zero
-                assembler.and_reg_imm32(rhs_dest, width - 1);
+                // Clear the result if the count is >= 64.
+                Self::emit_count_in_range_mask(assembler, Register::RCX, rhs_dest);
+                assembler.and_reg_reg(lhs_dest, Register::RCX);
+                assembler.pop_reg(Register::RCX);
+            }
+            SimpleAst::Ashr([a, b]) => {
+                // Sign extend the lhs to 64 bits, so that the sign bit is shifted in from above.
+                if width != 64 {
+                    assembler.shl_reg_imm8(lhs_dest, (64 - width) as u8);
+                    assembler.sar_reg_imm8(lhs_dest, (64 - width) as u8);
+                }
 
+                // Clamp counts >= 64 to 63, which fills the result with the sign bit.
                 assembler.push_reg(Register::RCX);
-                assembler.mov_reg_reg(Register::RCX, rhs_dest);
-                assembler.shr_reg_cl(lhs_dest);
+                Self::emit_count_in_range_mask(assembler, Register::RCX, rhs_dest);
+                assembler.not_reg(Register::RCX);
+                assembler.and_reg_imm32(Register::RCX, 63);
+                assembler.or_reg_reg(Register::RCX, rhs_dest);
+                assembler.sar_reg_cl(lhs_dest);
                 assembler.pop_reg(Register::RCX);
             }
             SimpleAst::Pow([a, b]) => {
-                for r in VOLATILE_REGS.iter() {
-                    assembler.push_reg(*r);
-                }
-
-                assembler.mov_reg_reg(Register::RCX, lhs_dest);
-                assembler.mov_reg_reg(Register::RDX, rhs_dest);
-
                 // TODO: Inline 'pow' function
-                assembler.movabs_reg_imm64(Register::R11, Pow as *const () as u64);
-                assembler.sub_reg_imm32(Register::RSP, 32);
-                assembler.call_reg(Register::R11);
-                assembler.add_reg_imm32(Register::RSP, 32);
-                assembler.mov_reg_reg(SCRATCH1, Register::RAX);
-
-                // Restore volatile registers
-                for &reg in VOLATILE_REGS.iter().rev() {
-                    assembler.pop_reg(reg);
+                let func = Pow as *const () as u64;
+                Self::emit_helper_call(assembler, func, lhs_dest, rhs_dest, width);
+            }
+            // Division is lowered to calls, because the semantics of division by zero
+            // (and of MIN / -1) do not match the hardware's.
+            SimpleAst::Udiv(_) | SimpleAst::Urem(_) | SimpleAst::Sdiv(_) | SimpleAst::Srem(_) => {
+                let func = match node {
+                    SimpleAst::Udiv(_) => Udiv as *const () as u64,
+                    SimpleAst::Urem(_) => Urem as *const () as u64,
+                    SimpleAst::Sdiv(_) => Sdiv as *const () as u64,
+                    _ => Srem as *const () as u64,
+                };
+                Self::emit_helper_call(assembler, func, lhs_dest, rhs_dest, width);
+            }
+            // The hardware reduces the count modulo 64, so only 64 bit rotates can be lowered directly.
+            SimpleAst::Rotl([a, b]) | SimpleAst::Rotr([a, b]) if width == 64 => {
+                assembler.push_reg(Register::RCX);
+                assembler.mov_reg_reg(Register::RCX, rhs_dest);
+                if matches!(node, SimpleAst::Rotl(_)) {
+                    assembler.rol_reg_cl(lhs_dest);
+                } else {
+                    assembler.ror_reg_cl(lhs_dest);
                 }
-
-                assembler.mov_reg_reg(lhs_dest, SCRATCH1);
+                assembler.pop_reg(Register::RCX);
+            }
+            SimpleAst::Rotl([a, b]) => {
+                let func = Rotl as *const () as u64;
+                Self::emit_helper_call(assembler, func, lhs_dest, rhs_dest, width);
+            }
+            SimpleAst::Rotr([a, b]) => {
+                let func = Rotr as *const () as u64;
+                Self::emit_helper_call(assembler, func, lhs_dest, rhs_dest, width);
             }
             _ => unreachable!("Node is not a binary operator"),
         }
@@ -4143,7 +5816,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         let multiple_users = node_info.num_uses > 1;
         if multiple_users {
             assembler.mov_mem64_reg(LOCALS_REGISTER, 8 * (self.slot_count as i32), lhs_dest);
-            self.assign_value_slot(ctx, idx, node_info);
+            self.assign_value_slot(nodes, idx, node_info);
         }
 
         // If the lhs is already in a register, don't move it!
@@ -4169,6 +5842,46 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         }
     }
 
+    // Set `dest` to all ones if `count` is less than 64, or to zero otherwise.
+    fn emit_count_in_range_mask(assembler: &mut T, dest: Register, count: Register) {
+        // (count >> 6) is zero exactly when count < 64, and small enough that subtracting one
+        // only sets the sign bit when it is zero.
+        assembler.mov_reg_reg(dest, count);
+        assembler.shr_reg_imm8(dest, 6);
+        assembler.sub_reg_imm32(dest, 1);
+        assembler.sar_reg_imm8(dest, 63);
+    }
+
+    // Call a `fn(lhs, rhs, width) -> u64` helper, storing the result in `lhs_dest`.
+    fn emit_helper_call(
+        assembler: &mut T,
+        func: u64,
+        lhs_dest: Register,
+        rhs_dest: Register,
+        width: u32,
+    ) {
+        for r in VOLATILE_REGS.iter() {
+            assembler.push_reg(*r);
+        }
+
+        assembler.mov_reg_reg(Register::RCX, lhs_dest);
+        assembler.mov_reg_reg(Register::RDX, rhs_dest);
+        assembler.movabs_reg_imm64(Register::R8, width as u64);
+
+        assembler.movabs_reg_imm64(Register::R11, func);
+        assembler.sub_reg_imm32(Register::RSP, 32);
+        assembler.call_reg(Register::R11);
+        assembler.add_reg_imm32(Register::RSP, 32);
+        assembler.mov_reg_reg(SCRATCH1, Register::RAX);
+
+        // Restore volatile registers
+        for &reg in VOLATILE_REGS.iter().rev() {
+            assembler.pop_reg(reg);
+        }
+
+        assembler.mov_reg_reg(lhs_dest, SCRATCH1);
+    }
+
     fn lower_constant(&mut self, assembler: &mut T, c: u64) {
         if !self.free_registers.is_empty() {
             let dest = self.free_registers.pop().unwrap();
@@ -4198,7 +5911,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
 
     fn lower_unary_op(
         &mut self,
-        ctx: &mut Context,
+        nodes: &mut LocalNodes,
         assembler: &mut T,
         idx: AstIdx,
         width: u32,
@@ -4217,7 +5930,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
             assembler.not_reg(dest_reg);
             Self::reduce_register_modulo(assembler, width, dest_reg, SCRATCH2);
         } else {
-            assembler.movabs_reg_imm64(SCRATCH2, get_modulo_mask(width as u8));
+            assembler.movabs_reg_imm64(SCRATCH2, get_modulo_mask(width as u8) as u64);
             assembler.and_reg_reg(dest_reg, SCRATCH2);
         }
 
@@ -4225,7 +5938,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         let multiple_users = node_info.num_uses > 1;
         if multiple_users {
             assembler.mov_mem64_reg(LOCALS_REGISTER, 8 * (self.slot_count as i32), dest_reg);
-            self.assign_value_slot(ctx, idx, node_info);
+            self.assign_value_slot(nodes, idx, node_info);
         }
 
         if dest_reg != SCRATCH1 {
@@ -4247,7 +5960,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
 
     fn lower_zext(
         &mut self,
-        ctx: &mut Context,
+        nodes: &mut LocalNodes,
         assembler: &mut T,
         idx: AstIdx,
         from_width: u32,
@@ -4273,7 +5986,122 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
             assembler.mov_mem64_reg(LOCALS_REGISTER, 8 * (self.slot_count as i32), SCRATCH1);
         }
 
-        self.assign_value_slot(ctx, idx, node_info);
+        self.assign_value_slot(nodes, idx, node_info);
+    }
+
+    fn lower_sext(
+        &mut self,
+        nodes: &mut LocalNodes,
+        assembler: &mut T,
+        idx: AstIdx,
+        from_width: u32,
+        width: u32,
+        node_info: NodeInfo,
+    ) {
+        let curr = self.stack.pop().unwrap();
+        let mut dest_reg = SCRATCH1;
+        if curr.is_register() {
+            dest_reg = curr.register;
+        } else {
+            assembler.pop_reg(dest_reg);
+        }
+
+        // Move the sign bit into bit 63, then shift it back down arithmetically.
+        // This also discards any garbage above the source width.
+        if from_width != 64 {
+            assembler.shl_reg_imm8(dest_reg, (64 - from_width) as u8);
+            assembler.sar_reg_imm8(dest_reg, (64 - from_width) as u8);
+        }
+        Self::reduce_register_modulo(assembler, width, dest_reg, SCRATCH2);
+
+        // If there are multiple users, store the value in a slot.
+        let multiple_users = node_info.num_uses > 1;
+        if multiple_users {
+            assembler.mov_mem64_reg(LOCALS_REGISTER, 8 * (self.slot_count as i32), dest_reg);
+            self.assign_value_slot(nodes, idx, node_info);
+        }
+
+        if dest_reg != SCRATCH1 {
+            self.stack.push(Location::reg(dest_reg));
+            return;
+        }
+
+        if !self.free_registers.is_empty() {
+            let dest = self.free_registers.pop().unwrap();
+            assembler.mov_reg_reg(dest, dest_reg);
+            self.stack.push(Location::reg(dest));
+            return;
+        }
+
+        // Otherwise this goes on the stack
+        assembler.push_reg(dest_reg);
+        self.stack.push(Location::stack());
+    }
+
+    fn lower_bit_count(
+        &mut self,
+        nodes: &mut LocalNodes,
+        assembler: &mut T,
+        idx: AstIdx,
+        node: SimpleAst,
+        width: u32,
+        node_info: NodeInfo,
+    ) {
+        let curr = self.stack.pop().unwrap();
+        let mut dest_reg = SCRATCH1;
+        if curr.is_register() {
+            dest_reg = curr.register;
+        } else {
+            assembler.pop_reg(dest_reg);
+        }
+
+        // The 64-bit instructions are used for every width, so the input must be reduced first.
+        // The result never exceeds the width, so it needs no reduction.
+        match node {
+            SimpleAst::Popcount(_) => {
+                Self::reduce_register_modulo(assembler, width, dest_reg, SCRATCH2);
+                assembler.popcnt_reg_reg(dest_reg, dest_reg);
+            }
+            SimpleAst::Ctlz(_) => {
+                Self::reduce_register_modulo(assembler, width, dest_reg, SCRATCH2);
+                assembler.lzcnt_reg_reg(dest_reg, dest_reg);
+                if width != 64 {
+                    assembler.sub_reg_imm32(dest_reg, 64 - width);
+                }
+            }
+            SimpleAst::Cttz(_) => {
+                // Setting the bit just above the width makes a zero input count to the width.
+                if width != 64 {
+                    assembler.movabs_reg_imm64(SCRATCH2, 1u64 << width);
+                    assembler.or_reg_reg(dest_reg, SCRATCH2);
+                }
+                assembler.tzcnt_reg_reg(dest_reg, dest_reg);
+            }
+            _ => unreachable!(),
+        }
+
+        // If there are multiple users, store the value in a slot.
+        let multiple_users = node_info.num_uses > 1;
+        if multiple_users {
+            assembler.mov_mem64_reg(LOCALS_REGISTER, 8 * (self.slot_count as i32), dest_reg);
+            self.assign_value_slot(nodes, idx, node_info);
+        }
+
+        if dest_reg != SCRATCH1 {
+            self.stack.push(Location::reg(dest_reg));
+            return;
+        }
+
+        if !self.free_registers.is_empty() {
+            let dest = self.free_registers.pop().unwrap();
+            assembler.mov_reg_reg(dest, dest_reg);
+            self.stack.push(Location::reg(dest));
+            return;
+        }
+
+        // Otherwise this goes on the stack
+        assembler.push_reg(dest_reg);
+        self.stack.push(Location::stack());
     }
 
     fn reduce_register_modulo(
@@ -4287,7 +6115,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
             return;
         }
 
-        let mask = get_modulo_mask(width as u8);
+        let mask = get_modulo_mask(width as u8) as u64;
         assembler.movabs_reg_imm64(free_reg, mask);
         assembler.and_reg_reg(dst_reg, free_reg);
     }
@@ -4297,7 +6125,7 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
             return;
         }
 
-        let mask = get_modulo_mask(width as u8);
+        let mask = get_modulo_mask(width as u8) as u64;
         assembler.movabs_reg_imm64(SCRATCH1, mask);
         if loc.is_register() {
             assembler.and_reg_reg(loc.register, SCRATCH1);
@@ -4306,9 +6134,9 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
         }
     }
 
-    fn assign_value_slot(&mut self, ctx: &mut Context, idx: AstIdx, mut node_info: NodeInfo) {
+    fn assign_value_slot(&mut self, nodes: &mut LocalNodes, idx: AstIdx, mut node_info: NodeInfo) {
         node_info.slot_idx = self.slot_count;
-        AuxInfoStorage::<NodeInfo>::set(ctx, idx, node_info);
+        AuxInfoStorage::<NodeInfo>::set(nodes, idx, node_info);
         self.slot_count = self.slot_count.checked_add(1).unwrap();
     }
 
@@ -4374,11 +6202,11 @@ impl<T: IAmd64Assembler> Amd64OptimizingJit<T> {
     }
 }
 
-pub fn as_constant(data: &AstData) -> Option<u64> {
+pub fn as_constant(data: &AstData) -> Option<u128> {
     return data.known_bits.as_constant();
 }
 
-pub fn eqmod(c1: u64, c2: u64, width: u8) -> bool {
+pub fn eqmod(c1: u128, c2: u128, width: u8) -> bool {
     let mask = get_modulo_mask(width);
     return (c1 & mask) == (c2 & mask);
 }
@@ -4386,10 +6214,20 @@ pub fn eqmod(c1: u64, c2: u64, width: u8) -> bool {
 // Below is an FFI interface for egraphs and Expr instances.
 #[no_mangle]
 pub extern "C" fn CreateEGraph() -> *mut EEGraph {
-    let analysis = MbaAnalysis {};
-    let egraph = EEGraph::new(analysis);
-    let pgraph = Box::new(egraph);
-    return Box::into_raw(pgraph);
+    ffi::guard(|| {
+        let analysis = MbaAnalysis::default();
+        let egraph = EEGraph::new(analysis);
+        let pgraph = Box::new(egraph);
+        Ok(Box::into_raw(pgraph))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn FreeEGraph(egraph_p: *mut EEGraph) -> FfiStatus {
+    ffi::guard_status(|| {
+        drop(Box::from_raw(ffi::non_null(egraph_p, "egraph")?));
+        Ok(())
+    })
 }
 
 #[no_mangle]
@@ -4398,58 +6236,59 @@ pub extern "C" fn EGraphAddFromContext(
     ctx_p: *mut Context,
     idx: AstIdx,
 ) -> AstIdx {
-    let mut ctx: &mut Context = unsafe { &mut (*ctx_p) };
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx_p)?;
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
 
-    let mut cache = AHashMap::new();
-    return add_to_egraph(ctx, egraph, idx, &mut cache);
+        let mut cache = AHashMap::new();
+        Ok(add_to_egraph(ctx, egraph, ffi::node(ctx, idx)?, &mut cache))
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn EGraphRun(egraph_p: *mut EEGraph, ms_limit: u64, iter_limit: u64) {
-    let mut egraph = unsafe { std::mem::take(&mut *egraph_p) };
+pub extern "C" fn EGraphRun(egraph_p: *mut EEGraph, ms_limit: u64, iter_limit: u64) -> FfiStatus {
+    ffi::guard_status(|| {
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
+        run_egraph(egraph, Duration::from_millis(ms_limit), iter_limit as usize);
+        Ok(())
+    })
+}
 
+// Run equality saturation with the generated rules until either limit is reached.
+pub fn run_egraph(egraph: &mut EEGraph, time_limit: Duration, iter_limit: usize) -> Option<StopReason> {
     let mut runner: Runner<SimpleAst, MbaAnalysis> = Runner::default()
-        .with_time_limit(Duration::from_millis(ms_limit))
+        .with_time_limit(time_limit)
         .with_scheduler(
             BackoffScheduler::default()
                 .with_ban_length(5)
                 .with_initial_match_limit(1_000_00),
         )
         .with_node_limit(1000000 * 10)
-        .with_iter_limit(iter_limit as usize)
-        .with_egraph(egraph);
+        .with_iter_limit(iter_limit)
+        .with_egraph(std::mem::take(egraph));
 
-    println!(
-        "Starting egraph run with limit {} ms and {} iters",
-        ms_limit, iter_limit
-    );
     // Run equality saturation
     let rules = get_generated_rules();
     runner = runner.run(&rules);
 
-    dbg!("{}", runner.stop_reason);
-
-    unsafe {
-        std::mem::swap(&mut *egraph_p, &mut runner.egraph);
-    }
+    std::mem::swap(egraph, &mut runner.egraph);
+    return runner.stop_reason.take();
 }
 
 #[no_mangle]
 pub extern "C" fn EGraphGetClasses(egraph_p: *mut EEGraph, out_len: *mut u64) -> *mut Id {
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
+    ffi::guard(|| {
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
+        let out_len = ffi::non_null(out_len, "length")?;
 
-    let mut classes = Vec::new();
-    for c in egraph.classes() {
-        classes.push(c.id);
-    }
-
-    unsafe { *out_len = classes.len() as u64 };
-    let boxed = classes.into_boxed_slice();
-    let released = Box::into_raw(boxed);
+        let mut classes = Vec::new();
+        for c in egraph.classes() {
+            classes.push(c.id);
+        }
 
-    // https://stackoverflow.com/a/57616981/6855629
-    return released as *mut _;
+        // The caller owns the array, and releases it with `FreeAstIdxArray`.
+        Ok(ffi::into_raw_array(classes, out_len))
+    })
 }
 
 #[no_mangle]
@@ -4458,28 +6297,26 @@ pub extern "C" fn EGraphGetClassNodes(
     id: Id,
     storage: *mut Context,
     out_len: *mut u64,
-) -> *const Id {
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
-
-    let mut ctx: &mut Context = unsafe { &mut (*storage) };
-
-    // Fetch all SimpleAst implementations
-    let eclass = egraph[id].clone();
-
-    // Problem: Each enode doesn't have a unique ID?
-    // We would need to throw all of these into an egraph basically..
-    let mut members = Vec::new();
-    for node in eclass.nodes {
-        let id = ctx.arena.insert_ast_node(node.clone(), eclass.data);
-        members.push(id);
-    }
+) -> *mut Id {
+    ffi::guard(|| {
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
+        let ctx = ffi::context(storage)?;
+        let out_len = ffi::non_null(out_len, "length")?;
 
-    unsafe { *out_len = members.len() as u64 };
+        // Fetch all SimpleAst implementations
+        let eclass = egraph[ffi::eclass(egraph, id)?].clone();
 
-    let boxed = members.into_boxed_slice();
-    let released = Box::into_raw(boxed);
+        // Problem: Each enode doesn't have a unique ID?
+        // We would need to throw all of these into an egraph basically..
+        let mut members = Vec::new();
+        for node in eclass.nodes {
+            let id = ctx.arena.insert_ast_node(node.clone(), eclass.data);
+            members.push(id);
+        }
 
-    return released as *const Id;
+        // The caller owns the array, and releases it with `FreeAstIdxArray`.
+        Ok(ffi::into_raw_array(members, out_len))
+    })
 }
 
 #[no_mangle]
@@ -4488,24 +6325,23 @@ pub extern "C" fn EGraphExtractAll(
     ctx_p: *mut Context,
     out_len: *mut u64,
 ) -> *mut Id {
-    let mut ctx: &mut Context = unsafe { &mut (*ctx_p) };
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
-
-    let mut classes = Vec::new();
-    let cost_func = EGraphCostFn { egraph: &egraph };
-    let extractor = Extractor::new(&egraph, cost_func);
-    for c in egraph.classes() {
-        classes.push(c.id);
-        let rec_expr = extractor.find_best(c.id);
-        classes.push(from_rec_expr(ctx, egraph, &rec_expr.1));
-    }
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx_p)?;
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
+        let out_len = ffi::non_null(out_len, "length")?;
 
-    unsafe { *out_len = classes.len() as u64 };
-    let boxed = classes.into_boxed_slice();
-    let released = Box::into_raw(boxed);
+        let mut classes = Vec::new();
+        let cost_func = EGraphCostFn { egraph: &egraph };
+        let extractor = Extractor::new(&egraph, cost_func);
+        for c in egraph.classes() {
+            classes.push(c.id);
+            let rec_expr = extractor.find_best(c.id);
+            classes.push(from_rec_expr(ctx, egraph, &rec_expr.1));
+        }
 
-    // https://stackoverflow.com/a/57616981/6855629
-    return released as *mut _;
+        // The caller owns the array, and releases it with `FreeAstIdxArray`.
+        Ok(ffi::into_raw_array(classes, out_len))
+    })
 }
 
 #[no_mangle]
@@ -4514,28 +6350,38 @@ pub extern "C" fn EGraphExtract(
     ctx_p: *mut Context,
     eclass: AstIdx,
 ) -> AstIdx {
-    let mut ctx: &mut Context = unsafe { &mut (*ctx_p) };
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
+    ffi::guard(|| {
+        let ctx = ffi::context(ctx_p)?;
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
 
-    return extract_from_egraph(ctx, egraph, eclass);
+        Ok(extract_from_egraph(ctx, egraph, ffi::eclass(egraph, eclass)?))
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn EGraphUnion(egraph_p: *mut EEGraph, a: AstIdx, b: AstIdx) {
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
+pub extern "C" fn EGraphUnion(egraph_p: *mut EEGraph, a: AstIdx, b: AstIdx) -> FfiStatus {
+    ffi::guard_status(|| {
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
+        let (a, b) = (ffi::eclass(egraph, a)?, ffi::eclass(egraph, b)?);
 
-    egraph.union(a, b);
+        api::union_classes(egraph, a, b)?;
+        Ok(())
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn EGraphRebuild(egraph_p: *mut EEGraph) {
-    let mut egraph: &mut EEGraph = unsafe { &mut (*egraph_p) };
+pub extern "C" fn EGraphRebuild(egraph_p: *mut EEGraph) -> FfiStatus {
+    ffi::guard_status(|| {
+        let egraph = ffi::non_null(egraph_p, "egraph")?;
 
-    egraph.rebuild();
+        egraph.rebuild();
+        Ok(())
+    })
 }
 
+// The DSL intrinsics operate on 64-bit values, so constants wider than 64 bits are never exposed to rule preconditions.
 pub fn isle_is_const(egraph: &mut Context, node: AstIdx) -> bool {
-    let is_constant = egraph.arena.is_constant(node);
+    let is_constant = egraph.arena.is_constant(node) && egraph.arena.get_width(node) <= 64;
     return is_constant;
 }
 
@@ -4550,12 +6396,12 @@ pub fn isle_is_neg(egraph: &mut Context, node: AstIdx) -> bool {
 pub fn isle_get_const(egraph: &Context, node: AstIdx) -> u64 {
     // We differ from the egraph version of `get_const` because ISLE deals with concrete terms
     let constant = egraph.arena.get_constant(node);
-    return constant;
+    return constant as u64;
 }
 
 pub fn isle_const_eq(egraph: &Context, node: AstIdx, c1: u64) -> bool {
     let constant = egraph.arena.get_constant(node);
-    return constant == rmod(c1, egraph.arena.get_data(node).width);
+    return constant == rmod(c1 as u128, egraph.arena.get_data(node).width);
 }
 
 pub fn isle_get_width(egraph: &Context, node: AstIdx) -> u64 {
@@ -4563,11 +6409,11 @@ pub fn isle_get_width(egraph: &Context, node: AstIdx) -> u64 {
 }
 
 pub fn isle_get_known_zeroes(egraph: &Context, node: AstIdx) -> u64 {
-    return egraph.arena.get_data(node).known_bits.zeroes;
+    return known_zeroes_u64(&egraph.arena.get_data(node).known_bits);
 }
 
 pub fn isle_get_known_ones(egraph: &Context, node: AstIdx) -> u64 {
-    return egraph.arena.get_data(node).known_bits.ones;
+    return known_ones_u64(&egraph.arena.get_data(node).known_bits);
 }
 
 pub fn isle_popcount(egraph: &Context, node: AstIdx) -> u64 {
@@ -4576,15 +6422,15 @@ pub fn isle_popcount(egraph: &Context, node: AstIdx) -> u64 {
 
 
 pub fn is_const(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> bool {
-    return node.data.known_bits.is_constant();
+    return node.data.known_bits.is_constant() && node.data.width <= 64;
 }
 
 pub fn get_const(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> u64 {
-    return node.data.known_bits.as_constant().unwrap();
+    return node.data.known_bits.as_constant().unwrap() as u64;
 }
 
 pub fn const_eq(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>, c1: u64) -> bool {
-    return node.data.known_bits.as_constant().unwrap() == rmod(c1, node.data.width);
+    return node.data.known_bits.as_constant().unwrap() == rmod(c1 as u128, node.data.width);
 }
 
 pub fn get_width(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> u64 {
@@ -4592,13 +6438,157 @@ pub fn get_width(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> u64 {
 }
 
 pub fn get_known_zeroes(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> u64 {
-    return node.data.known_bits.zeroes;
+    return known_zeroes_u64(&node.data.known_bits);
 }
 
 pub fn get_known_ones(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> u64 {
-    return node.data.known_bits.ones;
+    return known_ones_u64(&node.data.known_bits);
+}
+
+// Nothing is reported as known for values wider than 64 bits, since the DSL cannot represent them.
+fn known_zeroes_u64(kb: &KnownBits) -> u64 {
+    if kb.width > 64 {
+        return 0;
+    }
+
+    return kb.zeroes as u64;
+}
+
+fn known_ones_u64(kb: &KnownBits) -> u64 {
+    if kb.width > 64 {
+        return 0;
+    }
+
+    return kb.ones as u64;
 }
 
 pub fn popcount(egraph: &EEGraph, node: &EClass<SimpleAst, AstData>) -> u64 {
     return node.data.known_bits.as_constant().unwrap().count_ones() as u64;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_garbage() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let dead = parse_ast(&mut ctx, "(x + z) * 7", 64).unwrap();
+        let live = parse_ast(&mut ctx, "(x & y) * 3 + (x ^ 5)", 64).unwrap();
+        let printed = AstPrinter::print(&ctx, ctx.arena.get_node(live));
+        let old_len = ctx.arena.local().unwrap().elements.len();
+
+        ctx.arena.local_mut().unwrap().add_root(live);
+        ctx.arena.local_mut().unwrap().add_root(live);
+        let remap = ctx.arena.local_mut().unwrap().collect_garbage();
+        assert_eq!(remap.len(), old_len);
+        assert_eq!(remap[dead.0 as usize], None);
+        assert!(ctx.arena.local().unwrap().elements.len() < old_len);
+
+        // The surviving nodes are unchanged, and remain hash-consed.
+        let live = remap[live.0 as usize].unwrap();
+        assert_eq!(AstPrinter::print(&ctx, ctx.arena.get_node(live)), printed);
+        assert_eq!(parse_ast(&mut ctx, "(x & y) * 3 + (x ^ 5)", 64).unwrap(), live);
+
+        // `z` was only referenced by the dead expression, so it is freed along with its symbol table entry.
+        let len = ctx.arena.local().unwrap().elements.len();
+        let z = ctx.arena.symbol_with_name("z".to_string(), 64);
+        assert_eq!(z.0 as usize, len);
+        assert_eq!(ctx.arena.local().unwrap().symbol_ids.len(), 3);
+
+        // The root was registered twice, so it only dies once both registrations are removed.
+        ctx.arena.local_mut().unwrap().remove_root(live);
+        let remap = ctx.arena.local_mut().unwrap().collect_garbage();
+        let live = remap[live.0 as usize].unwrap();
+        ctx.arena.local_mut().unwrap().remove_root(live);
+        ctx.arena.local_mut().unwrap().collect_garbage();
+        assert!(ctx.arena.local().unwrap().elements.is_empty());
+        assert!(ctx.arena.local().unwrap().symbol_ids.is_empty());
+        assert!(ctx.arena.local().unwrap().name_to_symbol.is_empty());
+    }
+
+    #[test]
+    fn fold_comparisons_using_ranges() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        // Known bits only know that the upper four bits are zero, which cannot decide a comparison against 17.
+        let masked = parse_ast(&mut ctx, "x & 15", 8).unwrap();
+        let range = ctx.arena.get_data(masked).range;
+        assert_eq!((range.umin(), range.umax()), (0, 15));
+        for (expr, expected) in [("(x & 15) < 17", 1), ("(x & 15) == 17", 0), ("((x & 15) + 3) >= 19", 0)] {
+            let idx = parse_ast(&mut ctx, expr, 8).unwrap();
+            assert_eq!(ctx.arena.get_node(idx), &SimpleAst::Constant { c: expected, width: 1 }, "{}", expr);
+        }
+
+        // Ranges also tighten the known bits, here of a value between 85 and 100.
+        let sub = parse_ast(&mut ctx, "100 - (x & 15)", 8).unwrap();
+        let known_bits = ctx.arena.get_data(sub).known_bits;
+        assert_eq!((known_bits.zeroes & 0xC0, known_bits.ones & 0xC0), (0x80, 0x40));
+
+        let unknown = parse_ast(&mut ctx, "(x & 15) < 8", 8).unwrap();
+        assert!(matches!(ctx.arena.get_node(unknown), SimpleAst::ICmp { .. }));
+    }
+
+    #[test]
+    fn compile_rejects_wide_values() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        // The page is never written to, since the DAG is rejected before any code is emitted.
+        let mut page = vec![0u8; 0x1000];
+        let idx = parse_ast(&mut ctx, "(x:i128 + 0x10000000000000001:i128) tr i64", 64).unwrap();
+        let x = ctx.arena.symbol_with_name("x".to_string(), 128);
+        let status = unsafe { ContextCompile(&mut ctx, idx, u64::MAX, &x, 1, page.as_mut_ptr()) };
+        assert_eq!(status, FfiStatus::InvalidArgument);
+        assert!(page.iter().all(|b| *b == 0));
+    }
+
+    // The generated code follows the Windows x64 calling convention, hence the `extern "win64"` call.
+    #[cfg(all(unix, target_arch = "x86_64"))]
+    #[test]
+    fn jit_oversized_shifts() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let len = 0x10000;
+        let prot = libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC;
+        let flags = libc::MAP_PRIVATE | libc::MAP_ANONYMOUS;
+        let page = unsafe { libc::mmap(std::ptr::null_mut(), len, prot, flags, -1, 0) };
+        assert_ne!(page, libc::MAP_FAILED);
+        let page = page as *mut u8;
+
+        for width in [8u8, 16, 32, 64] {
+            let x = ctx.arena.symbol_with_name("x".to_string(), width);
+            let y = ctx.arena.symbol_with_name("y".to_string(), width);
+            let vars = [x, y];
+            let mask = get_modulo_mask(width) as u64;
+            for op in ["<<", ">>", ">>s"] {
+                let input = format!("x:i{} {} y:i{}", width, op, width);
+                let idx = parse_ast(&mut ctx, &input, width).unwrap();
+                let status =
+                    unsafe { ContextCompile(&mut ctx, idx, u64::MAX, vars.as_ptr(), 2, page) };
+                assert_eq!(status, FfiStatus::Ok);
+                let f: extern "win64" fn(*mut u64) -> u64 = unsafe { std::mem::transmute(page) };
+
+                for a in [0x5a5a_a5a5_5a5a_a5a5u64, u64::MAX, 1 << (width - 1)] {
+                    for b in [0, 1, width as u64 - 1, width as u64, 63, 64, 65, 200, 1 << 40, u64::MAX] {
+                        let mut inputs = [a & mask, b & mask];
+                        let mapping = HashMap::from([(x, inputs[0] as u128), (y, inputs[1] as u128)]);
+                        let expected = eval_ast(&ctx, idx, &mapping) as u64;
+                        let actual = f(inputs.as_mut_ptr());
+                        assert_eq!(actual, expected, "{} with x={:#x} y={}", input, a & mask, b & mask);
+                    }
+                }
+            }
+        }
+
+        unsafe { libc::munmap(page as *mut libc::c_void, len) };
+    }
+}
@@ -0,0 +1,748 @@
+// SMT-LIB2 export, for checking the output of the simplifier with any solver that supports `QF_BV`.
+//
+// Nodes that are used more than once are bound with `let`, so the size of a term is linear in the size of the DAG.
+// Operators without a direct counterpart in the logic, such as the bit counts and rotates by a variable amount, are
+// expanded, and comparisons are converted from booleans to 1-bit vectors so they can be used like any other node.
+use std::fmt::Write;
+
+use ahash::{AHashMap, AHashSet};
+use egg::Language;
+use libc::c_char;
+
+use crate::{
+    eval::post_order,
+    ffi,
+    rewrite::num_value_operands,
+    simple_ast::{get_modulo_mask, AstIdx, Context, INodeUtil, Predicate, SimpleAst},
+};
+
+impl Context {
+    // Print the DAG rooted at `root` as a single term. Its variables are left undeclared.
+    pub fn to_smtlib(&self, root: AstIdx) -> String {
+        let mut writer = Writer::new(self, &[root]);
+        let body = writer.term(root);
+        return writer.wrap_lets(body);
+    }
+
+    // Print a complete script which is unsatisfiable if and only if `before` and `after` are equivalent.
+    pub fn smtlib_equivalence_query(&self, before: AstIdx, after: AstIdx) -> String {
+        let width = self.arena.get_width(before);
+        assert_eq!(
+            width,
+            self.arena.get_width(after),
+            "only nodes of the same width can be equivalent"
+        );
+
+        let mut writer = Writer::new(self, &[before, after]);
+        let body = format!("(not (= {} {}))", writer.term(before), writer.term(after));
+        let assertion = writer.wrap_lets(body);
+
+        let mut out = String::new();
+        writeln!(out, "(set-logic QF_BV)").unwrap();
+        let mut symbols: Vec<_> = writer.symbols.iter().collect();
+        symbols.sort();
+        for (name, width) in symbols {
+            writeln!(out, "(declare-fun {} () (_ BitVec {}))", name, width).unwrap();
+        }
+        writeln!(out, "(assert {})", assertion).unwrap();
+        writeln!(out, "(check-sat)").unwrap();
+        return out;
+    }
+}
+
+struct Writer<'a> {
+    ctx: &'a Context,
+    // The identifier of every symbol and `let` bound node.
+    names: AHashMap<AstIdx, String>,
+    // The terms of the remaining nodes, each of which is inlined into its only user.
+    terms: AHashMap<AstIdx, String>,
+    // Declared name and width of each variable.
+    symbols: Vec<(String, u8)>,
+    bindings: Vec<(String, String)>,
+    // Counter for the variables introduced by the expansion of a single node.
+    fresh: usize,
+}
+
+impl<'a> Writer<'a> {
+    fn new(ctx: &'a Context, roots: &[AstIdx]) -> Self {
+        let mut order = Vec::new();
+        let mut seen = AHashSet::new();
+        for root in roots {
+            for idx in post_order(ctx, *root) {
+                if seen.insert(idx) {
+                    order.push(idx);
+                }
+            }
+        }
+
+        // Operands that are referenced several times by the expansion of their parent are counted twice, so that they
+        // are bound rather than duplicated.
+        // The roots are referenced by the caller.
+        let mut uses: AHashMap<AstIdx, usize> = AHashMap::new();
+        for root in roots {
+            *uses.entry(*root).or_default() += 1;
+        }
+        for idx in order.iter() {
+            let ast = ctx.arena.get_node(*idx);
+            let repeated = match ast {
+                SimpleAst::Pow(_) => &ast.children()[1..],
+                SimpleAst::Rotl([_, b]) | SimpleAst::Rotr([_, b]) if !ctx.arena.is_constant(*b) => {
+                    &ast.children()[..1]
+                }
+                SimpleAst::Popcount(_)
+                | SimpleAst::Ctlz(_)
+                | SimpleAst::Cttz(_)
+                | SimpleAst::Carry(_) => ast.children(),
+                _ => &[],
+            };
+            for child in ast.children()[..num_value_operands(ast)].iter() {
+                *uses.entry(*child).or_default() += if repeated.contains(child) { 2 } else { 1 };
+            }
+        }
+
+        let mut writer = Writer {
+            ctx,
+            names: AHashMap::new(),
+            terms: AHashMap::new(),
+            symbols: Vec::new(),
+            bindings: Vec::new(),
+            fresh: 0,
+        };
+
+        // Symbols that share a name but not a width are distinct variables, so their names are qualified.
+        let mut widths_of: AHashMap<String, usize> = AHashMap::new();
+        for idx in order.iter() {
+            if let SimpleAst::Symbol { id, .. } = ctx.arena.get_node(*idx) {
+                *widths_of.entry(ctx.arena.get_symbol_name(*id)).or_default() += 1;
+            }
+        }
+        for idx in order.iter() {
+            if let SimpleAst::Symbol { id, width } = ctx.arena.get_node(*idx) {
+                let name = ctx.arena.get_symbol_name(*id);
+                let name = match widths_of[&name] {
+                    1 => quote(&name),
+                    _ => quote(&format!("{}:i{}", name, width)),
+                };
+                writer.names.insert(*idx, name.clone());
+                writer.symbols.push((name, *width));
+            }
+        }
+
+        // Build the terms bottom-up rather than recursing from the roots, so that long chains of single-use operands
+        // cannot overflow the stack.
+        for idx in order.iter() {
+            let ast = ctx.arena.get_node(*idx);
+            if let SimpleAst::Constant { .. } | SimpleAst::Symbol { .. } = ast {
+                continue;
+            }
+
+            let operands: Vec<String> = ast.children()[..num_value_operands(ast)]
+                .iter()
+                .map(|c| writer.term(*c))
+                .collect();
+            let term = writer.expand(*idx, &operands);
+            if uses.get(idx).copied().unwrap_or(0) > 1 {
+                let name = format!("?t{}", writer.bindings.len());
+                writer.bindings.push((name.clone(), term));
+                writer.names.insert(*idx, name);
+            } else {
+                writer.terms.insert(*idx, term);
+            }
+        }
+
+        return writer;
+    }
+
+    fn wrap_lets(&self, body: String) -> String {
+        let mut out = String::new();
+        for (name, term) in self.bindings.iter() {
+            writeln!(out, "(let (({} {}))", name, term).unwrap();
+        }
+        out.push_str(&body);
+        out.push_str(&")".repeat(self.bindings.len()));
+        return out;
+    }
+
+    // Returns the term of a node that has already been built. Unbound nodes are only used once, except for constants.
+    fn term(&mut self, idx: AstIdx) -> String {
+        if let Some(name) = self.names.get(&idx) {
+            return name.clone();
+        }
+        if let SimpleAst::Constant { c, width } = self.ctx.arena.get_node(idx) {
+            return bv(*c, *width);
+        }
+
+        return self.terms.remove(&idx).unwrap();
+    }
+
+    // Print a node given the terms of its value operands.
+    fn expand(&mut self, idx: AstIdx, operands: &[String]) -> String {
+        let ctx = self.ctx;
+        let ast = ctx.arena.get_node(idx);
+        let width = ctx.arena.get_width(idx);
+        let ow = ast
+            .children()
+            .first()
+            .map(|c| ctx.arena.get_width(*c))
+            .unwrap_or(width);
+        let binop = |op: &str| format!("({} {} {})", op, operands[0], operands[1]);
+        let constant = |i: usize| ctx.arena.get_constant(ast.children()[i]);
+
+        match ast {
+            SimpleAst::Constant { .. } | SimpleAst::Symbol { .. } => unreachable!(),
+            SimpleAst::Add(_) => binop("bvadd"),
+            SimpleAst::Mul(_) => binop("bvmul"),
+            SimpleAst::Pow([_, exponent]) => self.pow(&operands[0], &operands[1], *exponent, width),
+            SimpleAst::And(_) => binop("bvand"),
+            SimpleAst::Or(_) => binop("bvor"),
+            SimpleAst::Xor(_) => binop("bvxor"),
+            SimpleAst::Neg(_) => format!("(bvnot {})", operands[0]),
+            // SMT-LIB shifts, divisions and remainders have the same semantics as `eval_ast`, including for oversized
+            // shift amounts and division by zero.
+            SimpleAst::Shl(_) => binop("bvshl"),
+            SimpleAst::Lshr(_) => binop("bvlshr"),
+            SimpleAst::Ashr(_) => binop("bvashr"),
+            SimpleAst::Udiv(_) => binop("bvudiv"),
+            SimpleAst::Urem(_) => binop("bvurem"),
+            SimpleAst::Sdiv(_) => binop("bvsdiv"),
+            SimpleAst::Srem(_) => binop("bvsrem"),
+            SimpleAst::Rotl([_, b]) | SimpleAst::Rotr([_, b]) => {
+                let left = matches!(ast, SimpleAst::Rotl(_));
+                if ctx.arena.is_constant(*b) {
+                    let op = if left { "rotate_left" } else { "rotate_right" };
+                    return format!(
+                        "((_ {} {}) {})",
+                        op,
+                        constant(1) % width as u128,
+                        operands[0]
+                    );
+                }
+
+                // Shifting by the width yields zero, so a rotate by zero is handled without a special case.
+                let (first, second) = if left {
+                    ("bvshl", "bvlshr")
+                } else {
+                    ("bvlshr", "bvshl")
+                };
+                let n = self.fresh_name("r");
+                format!(
+                    "(let (({} (bvurem {} {}))) (bvor ({} {} {}) ({} {} (bvsub {} {}))))",
+                    n,
+                    operands[1],
+                    bv(width as u128, width),
+                    first,
+                    operands[0],
+                    n,
+                    second,
+                    operands[0],
+                    bv(width as u128, width),
+                    n
+                )
+            }
+            SimpleAst::Popcount(_) => (0..width)
+                .map(|i| zero_extend(width - 1, &bit(&operands[0], i)))
+                .reduce(|acc, b| format!("(bvadd {} {})", acc, b))
+                .unwrap(),
+            // Walk towards the bit that takes precedence, so that it ends up in the outermost `ite`.
+            SimpleAst::Ctlz(_) => (0..width).fold(bv(width as u128, width), |acc, i| {
+                format!(
+                    "(ite (= {} #b1) {} {})",
+                    bit(&operands[0], i),
+                    bv((width - 1 - i) as u128, width),
+                    acc
+                )
+            }),
+            SimpleAst::Cttz(_) => (0..width).rev().fold(bv(width as u128, width), |acc, i| {
+                format!(
+                    "(ite (= {} #b1) {} {})",
+                    bit(&operands[0], i),
+                    bv(i as u128, width),
+                    acc
+                )
+            }),
+            SimpleAst::Zext(_) => zero_extend(width - ow, &operands[0]),
+            SimpleAst::Sext(_) => format!("((_ sign_extend {}) {})", width - ow, operands[0]),
+            SimpleAst::Trunc(_) => format!("((_ extract {} 0) {})", width - 1, operands[0]),
+            SimpleAst::ICmp { predicate, .. } => {
+                format!("(ite {} #b1 #b0)", binop(predicate_name(*predicate)))
+            }
+            SimpleAst::Select { .. } => format!(
+                "(ite (distinct {} {}) {} {})",
+                operands[0],
+                bv(0, ow),
+                operands[1],
+                operands[2]
+            ),
+            SimpleAst::Extract(_) => format!(
+                "((_ extract {} {}) {})",
+                constant(1),
+                constant(2),
+                operands[0]
+            ),
+            SimpleAst::Concat(_) => binop("concat"),
+            // The carry out of a full adder is the majority of its inputs.
+            SimpleAst::Carry(_) => format!(
+                "(bvor (bvor (bvand {} {}) (bvand {} {})) (bvand {} {}))",
+                operands[0], operands[1], operands[0], operands[2], operands[1], operands[2]
+            ),
+        }
+    }
+
+    // Exponentiation by squaring, where the successive squares of the base are bound with `let`. The bits of a
+    // variable exponent select which squares are multiplied together.
+    fn pow(&mut self, base: &str, exponent: &str, exponent_idx: AstIdx, width: u8) -> String {
+        let constant = match self.ctx.arena.is_constant(exponent_idx) {
+            true => Some(self.ctx.arena.get_constant(exponent_idx) & get_modulo_mask(width)),
+            false => None,
+        };
+        let num_bits = match constant {
+            Some(c) => 128 - c.leading_zeros() as u8,
+            None => width,
+        };
+        if num_bits == 0 {
+            return bv(1, width);
+        }
+
+        let prefix = self.fresh_name("p");
+        let square = |i: u8| format!("{}.{}", prefix, i);
+        let mut lets = format!("(let (({} {}))", square(0), base);
+        for i in 1..num_bits {
+            write!(
+                lets,
+                " (let (({} (bvmul {} {})))",
+                square(i),
+                square(i - 1),
+                square(i - 1)
+            )
+            .unwrap();
+        }
+
+        let product = (0..num_bits)
+            .filter_map(|i| match constant {
+                Some(c) => ((c >> i) & 1 != 0).then(|| square(i)),
+                None => Some(format!(
+                    "(ite (= {} #b1) {} {})",
+                    bit(exponent, i),
+                    square(i),
+                    bv(1, width)
+                )),
+            })
+            .reduce(|acc, f| format!("(bvmul {} {})", acc, f))
+            .unwrap();
+        return format!("{} {}{}", lets, product, ")".repeat(num_bits as usize));
+    }
+
+    fn fresh_name(&mut self, prefix: &str) -> String {
+        self.fresh += 1;
+        return format!("?{}{}", prefix, self.fresh);
+    }
+}
+
+fn predicate_name(pred: Predicate) -> &'static str {
+    match pred {
+        Predicate::Eq => "=",
+        Predicate::Ne => "distinct",
+        Predicate::Ugt => "bvugt",
+        Predicate::Uge => "bvuge",
+        Predicate::Ult => "bvult",
+        Predicate::Ule => "bvule",
+        Predicate::Sgt => "bvsgt",
+        Predicate::Sge => "bvsge",
+        Predicate::Slt => "bvslt",
+        Predicate::Sle => "bvsle",
+    }
+}
+
+fn bv(c: u128, width: u8) -> String {
+    format!("(_ bv{} {})", c & get_modulo_mask(width), width)
+}
+
+fn bit(term: &str, i: u8) -> String {
+    format!("((_ extract {} {}) {})", i, i, term)
+}
+
+fn zero_extend(by: u8, term: &str) -> String {
+    match by {
+        0 => term.to_string(),
+        _ => format!("((_ zero_extend {}) {})", by, term),
+    }
+}
+
+// Names that are not simple symbols must be enclosed in bars.
+fn quote(name: &str) -> String {
+    let is_simple = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c));
+    match is_simple {
+        true => name.to_string(),
+        false => format!("|{}|", name.replace('|', "_").replace('\\', "_")),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextGetSmtLibString(ctx: *const Context, id: AstIdx) -> *mut c_char {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let str = ctx.to_smtlib(ffi::node(ctx, id)?);
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextGetSmtLibEquivalenceQuery(
+    ctx: *const Context,
+    before: AstIdx,
+    after: AstIdx,
+) -> *mut c_char {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        ffi::same_width(ctx, before, after)?;
+        let str = ctx.smtlib_equivalence_query(before, after);
+        Ok(ffi::into_raw_string(&str))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        ast_parser::parse_ast,
+        eval::cmp,
+        simple_ast::{
+            ashr_const, eval_ast, lshr_const, rotl_const, rotr_const, sdiv_const, shl_const,
+            srem_const, udiv_const, urem_const, Arena,
+        },
+    };
+
+    use super::*;
+
+    // A minimal interpreter for the subset of SMT-LIB emitted by the writer, with the operators implemented in terms
+    // of the constant folders they are meant to match.
+    #[derive(Debug, Clone)]
+    enum Sx {
+        Atom(String),
+        List(Vec<Sx>),
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    enum Value {
+        Bv(u128, u8),
+        Bool(bool),
+    }
+
+    fn read(input: &str) -> Sx {
+        let mut stack: Vec<Vec<Sx>> = vec![Vec::new()];
+        let mut chars = input.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '(' => stack.push(Vec::new()),
+                ')' => {
+                    let list = stack.pop().unwrap();
+                    stack.last_mut().unwrap().push(Sx::List(list));
+                }
+                c if c.is_whitespace() => {}
+                '|' => {
+                    let mut atom = String::from("|");
+                    for c in chars.by_ref() {
+                        atom.push(c);
+                        if c == '|' {
+                            break;
+                        }
+                    }
+                    stack.last_mut().unwrap().push(Sx::Atom(atom));
+                }
+                c => {
+                    let mut atom = c.to_string();
+                    while let Some(c) = chars
+                        .peek()
+                        .filter(|c| !c.is_whitespace() && !"()".contains(**c))
+                    {
+                        atom.push(*c);
+                        chars.next();
+                    }
+                    stack.last_mut().unwrap().push(Sx::Atom(atom));
+                }
+            }
+        }
+
+        assert_eq!(stack.len(), 1, "unbalanced parentheses");
+        return stack.pop().unwrap().pop().unwrap();
+    }
+
+    fn bits(v: Value) -> (u128, u8) {
+        match v {
+            Value::Bv(c, w) => (c, w),
+            Value::Bool(_) => panic!("expected a bit-vector"),
+        }
+    }
+
+    fn eval(sx: &Sx, env: &mut Vec<(String, Value)>) -> Value {
+        let list = match sx {
+            Sx::Atom(atom) if atom.starts_with("#b") => {
+                return Value::Bv(
+                    u128::from_str_radix(&atom[2..], 2).unwrap(),
+                    atom.len() as u8 - 2,
+                )
+            }
+            Sx::Atom(atom) => return env.iter().rev().find(|(n, _)| n == atom).unwrap().1,
+            Sx::List(list) => list,
+        };
+
+        let atom = |i: usize| match &list[i] {
+            Sx::Atom(a) => a.clone(),
+            Sx::List(_) => panic!("expected an atom"),
+        };
+        let num = |i: usize| atom(i).parse::<u64>().unwrap();
+
+        // Indexed operators, e.g. `((_ extract 7 0) x)`, and constants.
+        if let Sx::List(head) = &list[0] {
+            let head_num = |i: usize| match &head[i] {
+                Sx::Atom(a) => a.parse::<u128>().unwrap(),
+                Sx::List(_) => panic!("expected a number"),
+            };
+            let (a, w) = bits(eval(&list[1], env));
+            let Sx::Atom(op) = &head[1] else { panic!() };
+            return match op.as_str() {
+                "extract" => {
+                    let (high, low) = (head_num(2), head_num(3));
+                    Value::Bv(
+                        (a >> low) & get_modulo_mask((high - low + 1) as u8),
+                        (high - low + 1) as u8,
+                    )
+                }
+                "zero_extend" => Value::Bv(a, w + head_num(2) as u8),
+                "sign_extend" => {
+                    let to = w + head_num(2) as u8;
+                    Value::Bv(
+                        ashr_const(a << (128 - w), 128 - w as u128, 128) & get_modulo_mask(to),
+                        to,
+                    )
+                }
+                "rotate_left" => Value::Bv(rotl_const(a, head_num(2), w), w),
+                "rotate_right" => Value::Bv(rotr_const(a, head_num(2), w), w),
+                _ => panic!("unknown indexed operator {}", op),
+            };
+        }
+
+        let op = atom(0);
+        match op.as_str() {
+            "_" => {
+                let c = atom(1)[2..].parse::<u128>().unwrap();
+                return Value::Bv(c, num(2) as u8);
+            }
+            "let" => {
+                let Sx::List(bindings) = &list[1] else {
+                    panic!()
+                };
+                let len = env.len();
+                let values: Vec<_> = bindings
+                    .iter()
+                    .map(|b| {
+                        let Sx::List(b) = b else { panic!() };
+                        let Sx::Atom(name) = &b[0] else { panic!() };
+                        (name.clone(), eval(&b[1], env))
+                    })
+                    .collect();
+                env.extend(values);
+                let result = eval(&list[2], env);
+                env.truncate(len);
+                return result;
+            }
+            "ite" => {
+                let Value::Bool(c) = eval(&list[1], env) else {
+                    panic!()
+                };
+                return eval(&list[if c { 2 } else { 3 }], env);
+            }
+            "not" => {
+                let Value::Bool(c) = eval(&list[1], env) else {
+                    panic!()
+                };
+                return Value::Bool(!c);
+            }
+            "bvnot" => {
+                let (a, w) = bits(eval(&list[1], env));
+                return Value::Bv(!a & get_modulo_mask(w), w);
+            }
+            _ => {}
+        }
+
+        let a = eval(&list[1], env);
+        let b = eval(&list[2], env);
+        match op.as_str() {
+            "=" => return Value::Bool(a == b),
+            "distinct" => return Value::Bool(a != b),
+            _ => {}
+        }
+
+        let ((a, w), (b, wb)) = (bits(a), bits(b));
+        if op == "concat" {
+            return Value::Bv((a << wb) | b, w + wb);
+        }
+
+        assert_eq!(w, wb);
+        let predicate = match op.as_str() {
+            "bvugt" => Some(Predicate::Ugt),
+            "bvuge" => Some(Predicate::Uge),
+            "bvult" => Some(Predicate::Ult),
+            "bvule" => Some(Predicate::Ule),
+            "bvsgt" => Some(Predicate::Sgt),
+            "bvsge" => Some(Predicate::Sge),
+            "bvslt" => Some(Predicate::Slt),
+            "bvsle" => Some(Predicate::Sle),
+            _ => None,
+        };
+        if let Some(predicate) = predicate {
+            return Value::Bool(cmp(predicate, a, b, w));
+        }
+
+        let result = match op.as_str() {
+            "bvadd" => a.wrapping_add(b),
+            "bvsub" => a.wrapping_sub(b),
+            "bvmul" => a.wrapping_mul(b),
+            "bvand" => a & b,
+            "bvor" => a | b,
+            "bvxor" => a ^ b,
+            "bvshl" => shl_const(a, b, w),
+            "bvlshr" => lshr_const(a, b, w),
+            "bvashr" => ashr_const(a, b, w),
+            "bvudiv" => udiv_const(a, b, w),
+            "bvurem" => urem_const(a, b, w),
+            "bvsdiv" => sdiv_const(a, b, w),
+            "bvsrem" => srem_const(a, b, w),
+            _ => panic!("unknown operator {}", op),
+        };
+        return Value::Bv(result & get_modulo_mask(w), w);
+    }
+
+    const EXPRS: [&str; 15] = [
+        "((x & y) + (x | y) * 3 - (x ^ ~y)) * ((x & y) + (x | y) * 3 - (x ^ ~y))",
+        "(x ** 3) + ((y:i8 ** z:i8) zx i64) + (y ** x)",
+        "((x:i8 << y:i8) ^ (x:i8 >> y:i8)) + (x:i8 >>s y:i8)",
+        "(x:i16 / y:i16) + (x:i16 % y:i16) * 5",
+        "(x:i8 /s y:i8) ++ (x:i8 %s y:i8)",
+        "(x:i8 <<< y:i8) ++ (x:i8 >>> y:i8) ++ (x:i8 <<< 3) ++ (x:i8 >>> 9)",
+        "popcount(x:i16) + ctlz(x:i16) * cttz(y:i16)",
+        "(x:i8 sx i32) + (y:i16 zx i32) + ((x tr i32) & 0xFFFF)",
+        "x:i16[11:4] ++ y:i16[15:15] ++ x:i16[3:0]",
+        "maj(x:i8 + 1, y:i8, z:i8) ++ (x:i8 <s y:i8 ? z:i8 : x:i8)",
+        "(x:i8 <=s y:i8 ? 1:i8 : 0:i8) + (x:i8 > y:i8 ? 2:i8 : 0:i8) + (x:i8 == y:i8 ? 4:i8 : 0:i8)",
+        "(x:i8 != y:i8 ? 1:i8 : 0:i8) + (x:i8 >=s y:i8 ? 2:i8 : 0:i8) + (x:i8 <= y:i8 ? 4:i8 : 0:i8) + (x:i8 >= y:i8 ? 8:i8 : 0:i8) + (x:i8 < y:i8 ? 16:i8 : 0:i8)",
+        "(x:i1 ? y:i8 : z:i8) + (x:i8 == 0 ? 8:i8 : x:i8 >s 0 ? 16:i8 : 32:i8)",
+        "x:i8 + (x:i16 tr i8)",
+        "((x ++ y) >>s (z zx i128)) + (x zx i128) * (y sx i128) + ((x ++ y)[127:64] zx i128)",
+    ];
+
+    const INPUTS: [u128; 8] = [0, 1, 3, 0x7F, 0x80, 0xFF, 0x8000000000000001, u64::MAX as u128];
+
+    #[test]
+    fn terms_match_eval_ast() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        for s in EXPRS {
+            let root = parse_ast(&mut ctx, s, 64).unwrap();
+            let text = ctx.to_smtlib(root);
+            let term = read(&text);
+            let query = ctx.smtlib_equivalence_query(root, root);
+
+            let mut vars = Vec::new();
+            for idx in post_order(&ctx, root) {
+                if let SimpleAst::Symbol { id, width } = ctx.arena.get_node(idx) {
+                    vars.push((idx, ctx.arena.get_symbol_name(*id), *width));
+                }
+            }
+
+            // Symbols that differ only in width are declared separately.
+            for (_, name, width) in vars.iter() {
+                let qualified = vars.iter().filter(|(_, n, _)| n == name).count() > 1;
+                let declared = match qualified {
+                    true => format!(
+                        "(declare-fun |{}:i{}| () (_ BitVec {}))",
+                        name, width, width
+                    ),
+                    false => format!("(declare-fun {} () (_ BitVec {}))", name, width),
+                };
+                assert!(
+                    query.contains(&declared),
+                    "{} is not declared in\n{}",
+                    name,
+                    query
+                );
+            }
+
+            for i in 0..INPUTS.len() {
+                for j in 0..INPUTS.len() {
+                    let picks = [INPUTS[i], INPUTS[j], INPUTS[(i + j) % INPUTS.len()]];
+                    let mut env = Vec::new();
+                    let mut mapping = HashMap::new();
+                    for (k, (idx, name, width)) in vars.iter().enumerate() {
+                        let value = picks[k % 3] & get_modulo_mask(*width);
+                        let qualified = vars.iter().filter(|(_, n, _)| n == name).count() > 1;
+                        let name = match qualified {
+                            true => format!("|{}:i{}|", name, width),
+                            false => name.clone(),
+                        };
+                        env.push((name, Value::Bv(value, *width)));
+                        mapping.insert(*idx, value);
+                    }
+
+                    let expected = eval_ast(&ctx, root, &mapping);
+                    let actual = eval(&term, &mut env);
+                    assert_eq!(
+                        actual,
+                        Value::Bv(expected, ctx.arena.get_width(root)),
+                        "{} with inputs {:?}",
+                        s,
+                        env
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn query_binds_shared_nodes() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let before = parse_ast(&mut ctx, "(x & y) + (x & y) * (x | y)", 64).unwrap();
+        let after = parse_ast(&mut ctx, "(x & y) + (x | y) * 3", 64).unwrap();
+        let query = ctx.smtlib_equivalence_query(before, after);
+
+        let expected = "(set-logic QF_BV)
+(declare-fun x () (_ BitVec 64))
+(declare-fun y () (_ BitVec 64))
+(assert (let ((?t0 (bvand x y)))
+(let ((?t1 (bvor x y)))
+(not (= (bvadd ?t0 (bvmul ?t0 ?t1)) (bvadd ?t0 (bvmul (_ bv3 64) ?t1)))))))
+(check-sat)
+";
+        assert_eq!(query, expected);
+
+        // A node used once is inlined.
+        let root = parse_ast(&mut ctx, "~(x + 1)", 64).unwrap();
+        assert_eq!(ctx.to_smtlib(root), "(bvnot (bvadd (_ bv1 64) x))");
+
+        // The operand of a rotate by a variable amount is referenced twice by its expansion, so it is bound.
+        let root = parse_ast(&mut ctx, "(x:i8 + 1) <<< y:i8", 64).unwrap();
+        assert!(ctx.to_smtlib(root).starts_with("(let ((?t0 (bvadd "));
+
+        // Deep chains of single-use nodes are inlined without recursing.
+        let mut root = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let x = root;
+        for _ in 0..20000 {
+            root = ctx.arena.lshr(root, x);
+        }
+        let text = ctx.to_smtlib(root);
+        assert_eq!(text.matches("bvlshr").count(), 20000);
+        assert!(!text.contains("let"));
+
+        assert_eq!(quote("x.y"), "x.y");
+        assert_eq!(quote("1x"), "|1x|");
+        assert_eq!(quote("a b"), "|a b|");
+    }
+}
@@ -0,0 +1,520 @@
+// Versioned binary snapshots of a `Context`, for checkpointing large DAGs and moving them between processes.
+//
+// All integers are little endian. The layout is:
+//  magic: b"EQSATSNP", version: u32, flags: u32
+//  symbol count: u32, then for each symbol: name length: u32, name: [u8], node: u32
+//  node count: u32, then for each node:
+//      opcode: u8(numbered like `get_opcode`), followed by either
+//          `c: u128, width: u8` for constants,
+//          `id: u32, width: u8` for symbols,
+//          `predicate: u8, a: u32, b: u32` for comparisons,
+//          or the operand indices as u32s for everything else.
+//      width: u8, cost: u32, has_poly: u8, class: u8, known zeroes: u128, known ones: u128,
+//      range lower: u128, range upper: u128, imut_data: u64
+//  if `FLAG_ISLE_CACHE` is set: entry count: u32, then (from: u32, to: u32) pairs
+//
+// Nodes are stored in arena order, so an operand always precedes its users. Roots are not saved.
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use egg::Language;
+
+use crate::{
+    constant_range::ConstantRange,
+    known_bits::KnownBits,
+    simple_ast::{
+        get_modulo_mask, get_opcode, Arena, AstClass, AstData, AstIdx, Context, LocalNodes,
+        Predicate, SimpleAst, MAX_WIDTH,
+    },
+};
+
+const MAGIC: &[u8; 8] = b"EQSATSNP";
+
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+const FLAG_ISLE_CACHE: u32 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u32),
+    Corrupt(&'static str),
+    Shared,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "i/o error: {}", e),
+            SnapshotError::BadMagic => write!(f, "not a snapshot file"),
+            SnapshotError::UnsupportedVersion(v) => write!(
+                f,
+                "unsupported snapshot version {}, expected {}",
+                v, SNAPSHOT_VERSION
+            ),
+            SnapshotError::Corrupt(what) => write!(f, "corrupt snapshot: {}", what),
+            SnapshotError::Shared => write!(f, "contexts with a shared arena cannot be saved"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+impl Context {
+    pub fn write_snapshot<W: Write>(
+        &self,
+        w: &mut W,
+        include_isle_cache: bool,
+    ) -> Result<(), SnapshotError> {
+        let Some(arena) = self.arena.local() else {
+            return Err(SnapshotError::Shared);
+        };
+        w.write_all(MAGIC)?;
+        write_u32(w, SNAPSHOT_VERSION)?;
+        write_u32(w, if include_isle_cache { FLAG_ISLE_CACHE } else { 0 })?;
+
+        write_u32(w, arena.symbol_ids.len() as u32)?;
+        for (name, idx) in arena.symbol_ids.iter() {
+            write_u32(w, name.len() as u32)?;
+            w.write_all(name.as_bytes())?;
+            write_u32(w, idx.0)?;
+        }
+
+        write_u32(w, arena.elements.len() as u32)?;
+        for (i, (node, data)) in arena.elements.iter().enumerate() {
+            w.write_all(&[get_opcode(self, AstIdx::from(i))])?;
+            match node {
+                SimpleAst::Constant { c, width } => {
+                    write_u128(w, *c)?;
+                    w.write_all(&[*width])?;
+                }
+                SimpleAst::Symbol { id, width } => {
+                    write_u32(w, *id)?;
+                    w.write_all(&[*width])?;
+                }
+                _ => {
+                    if let SimpleAst::ICmp { predicate, .. } = node {
+                        w.write_all(&[*predicate as u8])?;
+                    }
+                    for child in node.children() {
+                        write_u32(w, child.0)?;
+                    }
+                }
+            }
+
+            w.write_all(&[data.width])?;
+            write_u32(w, data.cost)?;
+            w.write_all(&[data.has_poly as u8, data.class as u8])?;
+            write_u128(w, data.known_bits.zeroes)?;
+            write_u128(w, data.known_bits.ones)?;
+            write_u128(w, data.range.lower)?;
+            write_u128(w, data.range.upper)?;
+            write_u64(w, data.imut_data)?;
+        }
+
+        if include_isle_cache {
+            write_u32(w, arena.isle_cache.len() as u32)?;
+            for (from, to) in arena.isle_cache.iter() {
+                write_u32(w, from.0)?;
+                write_u32(w, to.0)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn read_snapshot<R: Read>(r: &mut R) -> Result<Context, SnapshotError> {
+        let mut magic = [0u8; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+
+        let version = read_u32(r)?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let flags = read_u32(r)?;
+
+        let mut arena = LocalNodes::new();
+        let num_symbols = read_u32(r)?;
+        let mut symbols = Vec::new();
+        for _ in 0..num_symbols {
+            // The name is read incrementally, so a corrupt length cannot trigger a huge allocation up front.
+            let len = read_u32(r)? as usize;
+            let mut name = Vec::new();
+            r.by_ref().take(len as u64).read_to_end(&mut name)?;
+            if name.len() != len {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+            let name =
+                String::from_utf8(name).map_err(|_| SnapshotError::Corrupt("invalid symbol name"))?;
+            symbols.push((name, read_u32(r)?));
+        }
+
+        let num_nodes = read_u32(r)?;
+        for i in 0..num_nodes {
+            // Operands must precede their users.
+            let operand = |r: &mut R| -> Result<AstIdx, SnapshotError> {
+                let idx = read_u32(r)?;
+                if idx >= i {
+                    return Err(SnapshotError::Corrupt("operand does not precede its user"));
+                }
+                Ok(AstIdx::from(idx as usize))
+            };
+
+            let opcode = read_u8(r)?;
+            let node = match opcode {
+                9 => {
+                    let c = read_u128(r)?;
+                    let width = read_width(r)?;
+                    if c & !get_modulo_mask(width) != 0 {
+                        return Err(SnapshotError::Corrupt("constant does not fit its width"));
+                    }
+                    SimpleAst::Constant { c, width }
+                }
+                10 => {
+                    let id = read_u32(r)?;
+                    if id >= num_symbols {
+                        return Err(SnapshotError::Corrupt("symbol id out of range"));
+                    }
+                    SimpleAst::Symbol {
+                        id,
+                        width: read_width(r)?,
+                    }
+                }
+                13 => {
+                    let predicate = read_predicate(read_u8(r)?)?;
+                    SimpleAst::ICmp {
+                        predicate,
+                        children: [operand(r)?, operand(r)?],
+                    }
+                }
+                7 | 27 | 28 | 29 => {
+                    let a = [operand(r)?];
+                    match opcode {
+                        7 => SimpleAst::Neg(a),
+                        27 => SimpleAst::Popcount(a),
+                        28 => SimpleAst::Ctlz(a),
+                        _ => SimpleAst::Cttz(a),
+                    }
+                }
+                14 | 15 | 17 => {
+                    let children = [operand(r)?, operand(r)?, operand(r)?];
+                    match opcode {
+                        14 => SimpleAst::Select { children },
+                        15 => SimpleAst::Extract(children),
+                        _ => SimpleAst::Carry(children),
+                    }
+                }
+                1..=6 | 8 | 11 | 12 | 16 | 18..=26 => {
+                    let children = [operand(r)?, operand(r)?];
+                    match opcode {
+                        1 => SimpleAst::Add(children),
+                        2 => SimpleAst::Mul(children),
+                        3 => SimpleAst::Pow(children),
+                        4 => SimpleAst::And(children),
+                        5 => SimpleAst::Or(children),
+                        6 => SimpleAst::Xor(children),
+                        8 => SimpleAst::Lshr(children),
+                        11 => SimpleAst::Zext(children),
+                        12 => SimpleAst::Trunc(children),
+                        16 => SimpleAst::Concat(children),
+                        18 => SimpleAst::Shl(children),
+                        19 => SimpleAst::Ashr(children),
+                        20 => SimpleAst::Udiv(children),
+                        21 => SimpleAst::Urem(children),
+                        22 => SimpleAst::Sdiv(children),
+                        23 => SimpleAst::Srem(children),
+                        24 => SimpleAst::Rotl(children),
+                        25 => SimpleAst::Rotr(children),
+                        _ => SimpleAst::Sext(children),
+                    }
+                }
+                _ => return Err(SnapshotError::Corrupt("unknown opcode")),
+            };
+
+            let width = read_width(r)?;
+            if let SimpleAst::Constant { width: w, .. } | SimpleAst::Symbol { width: w, .. } = node {
+                if w != width {
+                    return Err(SnapshotError::Corrupt("node width does not match its data"));
+                }
+            }
+            let cost = read_u32(r)?;
+            let has_poly = read_u8(r)? != 0;
+            let class = read_class(read_u8(r)?)?;
+            let zeroes = read_u128(r)?;
+            let ones = read_u128(r)?;
+            let lower = read_u128(r)?;
+            let upper = read_u128(r)?;
+            let imut_data = read_u64(r)?;
+            let data = AstData {
+                width,
+                cost,
+                has_poly,
+                class,
+                known_bits: KnownBits {
+                    width: width as u32,
+                    zeroes,
+                    ones,
+                },
+                range: ConstantRange {
+                    width: width as u32,
+                    lower,
+                    upper,
+                },
+                imut_data,
+            };
+
+            let idx = AstIdx::from(i as usize);
+            if arena.ast_to_idx.insert(node.clone(), idx).is_some() {
+                return Err(SnapshotError::Corrupt("duplicate node"));
+            }
+            arena.elements.push((node, data));
+        }
+
+        for (id, (name, idx)) in symbols.into_iter().enumerate() {
+            if idx >= num_nodes {
+                return Err(SnapshotError::Corrupt("symbol node out of range"));
+            }
+
+            let idx = AstIdx::from(idx as usize);
+            let SimpleAst::Symbol { width, .. } = *arena.get_node(idx) else {
+                return Err(SnapshotError::Corrupt("symbol table entry is not a symbol"));
+            };
+            arena.name_to_symbol.insert((name.clone(), width), id as u32);
+            arena.symbol_ids.push((name, idx));
+        }
+
+        if flags & FLAG_ISLE_CACHE != 0 {
+            let num_entries = read_u32(r)?;
+            for _ in 0..num_entries {
+                let (from, to) = (read_u32(r)?, read_u32(r)?);
+                if from >= num_nodes || to >= num_nodes {
+                    return Err(SnapshotError::Corrupt("cache entry out of range"));
+                }
+                arena
+                    .isle_cache
+                    .insert(AstIdx::from(from as usize), AstIdx::from(to as usize));
+            }
+        }
+
+        return Ok(Context {
+            arena: Arena::from_local(arena),
+        });
+    }
+
+    pub fn save_snapshot<P: AsRef<Path>>(
+        &self,
+        path: P,
+        include_isle_cache: bool,
+    ) -> Result<(), SnapshotError> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_snapshot(&mut w, include_isle_cache)?;
+        w.flush()?;
+        return Ok(());
+    }
+
+    pub fn load_snapshot<P: AsRef<Path>>(path: P) -> Result<Context, SnapshotError> {
+        let mut r = BufReader::new(File::open(path)?);
+        return Context::read_snapshot(&mut r);
+    }
+}
+
+fn read_predicate(p: u8) -> Result<Predicate, SnapshotError> {
+    let predicate = match p {
+        0 => Predicate::Eq,
+        1 => Predicate::Ne,
+        2 => Predicate::Ugt,
+        3 => Predicate::Uge,
+        4 => Predicate::Ult,
+        5 => Predicate::Ule,
+        6 => Predicate::Sgt,
+        7 => Predicate::Sge,
+        8 => Predicate::Slt,
+        9 => Predicate::Sle,
+        _ => return Err(SnapshotError::Corrupt("unknown predicate")),
+    };
+    return Ok(predicate);
+}
+
+fn read_class(c: u8) -> Result<AstClass, SnapshotError> {
+    let class = match c {
+        0 => AstClass::Bitwise,
+        1 => AstClass::BitwiseWithConstants,
+        2 => AstClass::Linear,
+        3 => AstClass::Semilinear,
+        4 => AstClass::Nonlinear,
+        _ => return Err(SnapshotError::Corrupt("unknown class")),
+    };
+    return Ok(class);
+}
+
+fn read_width<R: Read>(r: &mut R) -> Result<u8, SnapshotError> {
+    let width = read_u8(r)?;
+    if width == 0 || width > MAX_WIDTH {
+        return Err(SnapshotError::Corrupt("invalid width"));
+    }
+    return Ok(width);
+}
+
+fn write_u32<W: Write>(w: &mut W, v: u32) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn write_u64<W: Write>(w: &mut W, v: u64) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn write_u128<W: Write>(w: &mut W, v: u128) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn read_u8<R: Read>(r: &mut R) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_u128<R: Read>(r: &mut R) -> io::Result<u128> {
+    let mut buf = [0u8; 16];
+    r.read_exact(&mut buf)?;
+    Ok(u128::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast_parser::parse_ast,
+        simple_ast::{recursive_simplify, AstPrinter},
+    };
+
+    use super::*;
+
+    #[test]
+    fn round_trip_snapshot() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let exprs = [
+            "(x & y) + (x | y) * 3 - (x ^ ~y)",
+            "(x:i8 <s y:i8 ? x:i8 : y:i8) ++ (z:i16[11:4] sx i16)",
+            "maj(x, y, 5) + popcount(x >> 3) * (x /s y) + ((x tr i1) zx i64)",
+        ];
+        let roots: Vec<AstIdx> = exprs
+            .iter()
+            .map(|s| parse_ast(&mut ctx, s, 64).unwrap())
+            .collect();
+        let simplified = recursive_simplify(&mut ctx, roots[0]);
+        let nodes = ctx.arena.local_mut().unwrap();
+        nodes.set_data(roots[1], AstData {
+            imut_data: 1234,
+            ..nodes.get_data(roots[1])
+        });
+
+        let mut buf = Vec::new();
+        ctx.write_snapshot(&mut buf, true).unwrap();
+        let mut loaded = Context::read_snapshot(&mut buf.as_slice()).unwrap();
+
+        let (nodes, loaded_nodes) = (ctx.arena.local().unwrap(), loaded.arena.local().unwrap());
+        assert_eq!(loaded_nodes.elements, nodes.elements);
+        assert_eq!(loaded_nodes.symbol_ids, nodes.symbol_ids);
+        assert_eq!(loaded_nodes.isle_cache.get(&roots[0]), Some(&simplified));
+        for (s, idx) in exprs.iter().zip(roots.iter()) {
+            assert_eq!(parse_ast(&mut loaded, s, 64).unwrap(), *idx);
+            assert_eq!(
+                AstPrinter::print(&loaded, loaded.arena.get_node(*idx)),
+                AstPrinter::print(&ctx, ctx.arena.get_node(*idx))
+            );
+        }
+
+        // Without the ISLE cache, the nodes are still identical.
+        let mut buf = Vec::new();
+        ctx.write_snapshot(&mut buf, false).unwrap();
+        let loaded = Context::read_snapshot(&mut buf.as_slice()).unwrap();
+        let loaded_nodes = loaded.arena.local().unwrap();
+        assert_eq!(loaded_nodes.elements, ctx.arena.local().unwrap().elements);
+        assert!(loaded_nodes.isle_cache.is_empty());
+    }
+
+    #[test]
+    fn reject_bad_snapshots() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        parse_ast(&mut ctx, "x + y * 3", 64).unwrap();
+
+        let mut buf = Vec::new();
+        ctx.write_snapshot(&mut buf, false).unwrap();
+
+        let read = |buf: &[u8]| Context::read_snapshot(&mut &buf[..]).err().unwrap();
+        assert!(matches!(read(b"not a snapshot"), SnapshotError::BadMagic));
+
+        let mut bad_version = buf.clone();
+        bad_version[8] = 2;
+        assert!(matches!(read(&bad_version), SnapshotError::UnsupportedVersion(2)));
+
+        assert!(matches!(read(&buf[..buf.len() - 1]), SnapshotError::Io(_)));
+    }
+
+    #[test]
+    fn reject_bad_widths() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        ctx.arena.symbol_with_name("x".to_string(), 64);
+        ctx.arena.constant(5, 8);
+
+        let mut buf = Vec::new();
+        ctx.write_snapshot(&mut buf, false).unwrap();
+        let read = |buf: &[u8]| Context::read_snapshot(&mut &buf[..]).err().unwrap();
+        let corrupt = |offset: usize, bytes: &[u8]| {
+            let mut buf = buf.clone();
+            buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+            return read(&buf);
+        };
+
+        // Offsets of the symbol's name length, the symbol's width and data width, and the constant's value.
+        let name_len = 20;
+        let symbol_width = 38;
+        let constant = symbol_width + 2 + 4 + 2 + 16 * 4 + 8 + 1;
+        let constant_width = constant + 16;
+        assert_eq!(buf[symbol_width..symbol_width + 2], [64, 64]);
+        assert_eq!(buf[constant_width..constant_width + 2], [8, 8]);
+
+        for width in [0, MAX_WIDTH + 1] {
+            assert!(matches!(corrupt(symbol_width, &[width]), SnapshotError::Corrupt(_)));
+            assert!(matches!(corrupt(symbol_width + 1, &[width]), SnapshotError::Corrupt(_)));
+            assert!(matches!(corrupt(constant_width, &[width, width]), SnapshotError::Corrupt(_)));
+        }
+        assert!(matches!(corrupt(symbol_width + 1, &[32]), SnapshotError::Corrupt(_)));
+        assert!(matches!(corrupt(constant, &[0, 1]), SnapshotError::Corrupt(_)));
+
+        // A huge name length is reported as truncated input, rather than being allocated up front.
+        assert!(matches!(corrupt(name_len, &u32::MAX.to_le_bytes()), SnapshotError::Io(_)));
+    }
+}
@@ -0,0 +1,220 @@
+// Size and shape metrics for the DAGs stored in an `Arena`, used to measure how much a pass reduced the complexity of
+// an expression.
+use ahash::{AHashMap, AHashSet};
+use egg::Language;
+
+use crate::{
+    eval::post_order,
+    ffi::{self, FfiStatus},
+    simple_ast::{get_opcode, AstClass, AstIdx, Context, INodeUtil, SimpleAst, MAX_WIDTH},
+};
+
+// One more than the largest opcode returned by `get_opcode`.
+pub const NUM_OPCODES: usize = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstStats {
+    // Number of distinct nodes reachable from the root, including the constant operands of extensions and extracts.
+    pub dag_size: u64,
+    // Number of nodes if the DAG were expanded into a tree, saturating at `u64::MAX`.
+    pub tree_size: u64,
+    // Number of nodes on the longest path from the root to a leaf.
+    pub depth: u32,
+    // Number of distinct nodes with each opcode, indexed by `get_opcode`.
+    pub opcode_counts: [u32; NUM_OPCODES],
+    // Name and width of each variable, ordered by name and then by width.
+    pub vars: Vec<(String, u8)>,
+    // Degree of the expression as a polynomial over its variables, treating any non-arithmetic subterm as an atom of
+    // degree one.
+    pub poly_degree: u32,
+    // Number of distinct maximal bitwise subterms, i.e. bitwise nodes that are the root or used by a non-bitwise node.
+    pub num_bitwise_terms: u32,
+    pub class: AstClass,
+}
+
+fn is_bitwise(ast: &SimpleAst) -> bool {
+    matches!(
+        ast,
+        SimpleAst::And(_) | SimpleAst::Or(_) | SimpleAst::Xor(_) | SimpleAst::Neg(_)
+    )
+}
+
+impl Context {
+    pub fn get_stats(&self, root: AstIdx) -> AstStats {
+        let order = post_order(self, root);
+        let slots: AHashMap<AstIdx, usize> = order.iter().enumerate().map(|(i, idx)| (*idx, i)).collect();
+
+        let mut tree_sizes = vec![0u64; order.len()];
+        let mut depths = vec![0u32; order.len()];
+        let mut degrees = vec![0u32; order.len()];
+        let mut opcode_counts = [0; NUM_OPCODES];
+        let mut vars = Vec::new();
+        let mut bitwise_terms = AHashSet::new();
+        if is_bitwise(self.arena.get_node(root)) {
+            bitwise_terms.insert(root);
+        }
+
+        for (i, idx) in order.iter().enumerate() {
+            let ast = self.arena.get_node(*idx);
+            opcode_counts[get_opcode(self, *idx) as usize] += 1;
+
+            let children: Vec<usize> = ast.children().iter().map(|c| slots[c]).collect();
+            tree_sizes[i] = children.iter().fold(1u64, |acc, c| acc.saturating_add(tree_sizes[*c]));
+            depths[i] = 1 + children.iter().map(|c| depths[*c]).max().unwrap_or(0);
+
+            let degree = |j: usize| degrees[children[j]];
+            degrees[i] = match ast {
+                SimpleAst::Constant { .. } => 0,
+                SimpleAst::Symbol { id, width } => {
+                    vars.push((self.arena.get_symbol_name(*id), *width));
+                    1
+                }
+                SimpleAst::Add(_) => degree(0).max(degree(1)),
+                SimpleAst::Mul(_) => degree(0).saturating_add(degree(1)),
+                SimpleAst::Pow([_, exponent]) if self.arena.is_constant(*exponent) => {
+                    let exponent = self.arena.get_constant(*exponent);
+                    degree(0).saturating_mul(u32::try_from(exponent).unwrap_or(u32::MAX))
+                }
+                // Bitwise negation is equivalent to `-a - 1`.
+                SimpleAst::Neg(_) => degree(0),
+                _ => children.iter().any(|c| degrees[*c] != 0) as u32,
+            };
+
+            if !is_bitwise(ast) {
+                for child in ast.children() {
+                    if is_bitwise(self.arena.get_node(*child)) {
+                        bitwise_terms.insert(*child);
+                    }
+                }
+            }
+        }
+
+        vars.sort();
+        let last = order.len() - 1;
+        return AstStats {
+            dag_size: order.len() as u64,
+            tree_size: tree_sizes[last],
+            depth: depths[last],
+            opcode_counts,
+            vars,
+            poly_degree: degrees[last],
+            num_bitwise_terms: bitwise_terms.len() as u32,
+            class: self.arena.get_class(root),
+        };
+    }
+}
+
+// Fixed size version of `AstStats` for FFI.
+#[repr(C)]
+pub struct FfiAstStats {
+    pub dag_size: u64,
+    pub tree_size: u64,
+    pub depth: u32,
+    pub num_vars: u32,
+    pub poly_degree: u32,
+    pub num_bitwise_terms: u32,
+    pub opcode_counts: [u32; NUM_OPCODES],
+    // Number of variables with each width.
+    pub var_width_counts: [u32; MAX_WIDTH as usize + 1],
+    pub class: u8,
+}
+
+impl From<&AstStats> for FfiAstStats {
+    fn from(stats: &AstStats) -> Self {
+        let mut var_width_counts = [0; MAX_WIDTH as usize + 1];
+        for (_, width) in stats.vars.iter() {
+            var_width_counts[*width as usize] += 1;
+        }
+
+        FfiAstStats {
+            dag_size: stats.dag_size,
+            tree_size: stats.tree_size,
+            depth: stats.depth,
+            num_vars: stats.vars.len() as u32,
+            poly_degree: stats.poly_degree,
+            num_bitwise_terms: stats.num_bitwise_terms,
+            opcode_counts: stats.opcode_counts,
+            var_width_counts,
+            class: stats.class as u8,
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextGetStats(ctx: *const Context, id: AstIdx, output: *mut FfiAstStats) -> FfiStatus {
+    ffi::guard_status(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let stats = ctx.get_stats(ffi::node(ctx, id)?);
+        *ffi::non_null(output, "output")? = FfiAstStats::from(&stats);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast_parser::parse_ast, simple_ast::Arena};
+
+    use super::*;
+
+    fn stats(s: &str) -> AstStats {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let idx = parse_ast(&mut ctx, s, 64).unwrap();
+        return ctx.get_stats(idx);
+    }
+
+    #[test]
+    fn count_shared_nodes() {
+        // The conjunction is shared, so the DAG has x, y, x&y, 3, (x&y)*3, and the sum.
+        let s = stats("(x & y) + (x & y) * 3");
+        assert_eq!(s.dag_size, 6);
+        assert_eq!(s.tree_size, 9);
+        assert_eq!(s.depth, 4);
+        assert_eq!(s.opcode_counts[1], 1);
+        assert_eq!(s.opcode_counts[2], 1);
+        assert_eq!(s.opcode_counts[4], 1);
+        assert_eq!(s.opcode_counts[10], 2);
+        assert_eq!(s.opcode_counts.iter().sum::<u32>() as u64, s.dag_size);
+        assert_eq!(s.vars, vec![("x".to_string(), 64), ("y".to_string(), 64)]);
+        assert_eq!(s.poly_degree, 1);
+        assert_eq!(s.num_bitwise_terms, 1);
+        assert_eq!(s.class, AstClass::Linear);
+    }
+
+    #[test]
+    fn measure_degree_and_bitwise_terms() {
+        let s = stats("(x & y) * (x | y) * z + (x ^ 1) ** 3");
+        assert_eq!(s.poly_degree, 3);
+        // The nested negation is part of the maximal bitwise term `~x & y`.
+        assert_eq!(stats("(~x & y) + ~x").num_bitwise_terms, 2);
+        assert_eq!(s.num_bitwise_terms, 3);
+        assert_eq!(s.class, AstClass::Nonlinear);
+
+        // Shifts and other operators are atoms, and constants have degree zero.
+        assert_eq!(stats("(x >> 3) * (y >> 2)").poly_degree, 2);
+        assert_eq!(stats("(x:i8 zx i64) * 7").poly_degree, 1);
+
+        let s = stats("x:i8 ++ y:i16 ++ z:i8");
+        assert_eq!(s.poly_degree, 1);
+        assert_eq!(
+            s.vars,
+            vec![("x".to_string(), 8), ("y".to_string(), 16), ("z".to_string(), 8)]
+        );
+
+        let ffi = FfiAstStats::from(&s);
+        assert_eq!(ffi.num_vars, 3);
+        assert_eq!(ffi.var_width_counts[8], 2);
+        assert_eq!(ffi.var_width_counts[16], 1);
+
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+        let idx = parse_ast(&mut ctx, "x:i128 + (y:i64 zx i128)", 64).unwrap();
+        let mut ffi = std::mem::MaybeUninit::<FfiAstStats>::uninit();
+        assert_eq!(unsafe { ContextGetStats(&ctx, idx, ffi.as_mut_ptr()) }, FfiStatus::Ok);
+        let ffi = unsafe { ffi.assume_init() };
+        assert_eq!(ffi.var_width_counts[128], 1);
+        assert_eq!(ffi.var_width_counts[64], 1);
+    }
+}
@@ -0,0 +1,529 @@
+// Batch interpreter for evaluating an AST on many input vectors at once.
+//
+// The DAG is compiled once into a linear tape of instructions over a small register file,
+// where registers are recycled as soon as their last user has executed. Each register holds a block of `LANES` values,
+// so every instruction is a tight loop over one block, which the compiler vectorizes with AVX2 when it is available.
+// Inputs are passed as columns, where `columns[i][j]` holds the value of the `i`th variable for the `j`th input vector.
+use ahash::AHashMap;
+use egg::Language;
+
+use crate::{
+    eval::{cmp, post_order},
+    ffi::{self, FfiError, FfiResult, FfiStatus},
+    simple_ast::{
+        ashr_const, ctlz_const, cttz_const, get_modulo_mask, lshr_const, popcount_const,
+        rotl_const, rotr_const, sdiv_const, shl_const, sign_extend, srem_const, udiv_const,
+        urem_const, AstIdx, Context, INodeUtil, Pow, Predicate, SimpleAst,
+    },
+};
+
+const LANES: usize = 64;
+
+type Block = [u64; LANES];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Opcode {
+    Add,
+    Mul,
+    Pow,
+    And,
+    Or,
+    Xor,
+    Lshr,
+    Shl,
+    Ashr,
+    Udiv,
+    Urem,
+    Sdiv,
+    Srem,
+    Rotl,
+    Rotr,
+    Neg,
+    Popcount,
+    Ctlz,
+    Cttz,
+    // Broadcast `imm` to every lane.
+    Constant,
+    // Load the input column at index `imm`.
+    Input,
+    // Zero extension and truncation are plain moves, since the result is always reduced modulo its width.
+    Mov,
+    Sext,
+    ICmp(Predicate),
+    Select,
+    // Shift right by `imm`.
+    Extract,
+    // Shift the first operand left by `imm` and or in the second.
+    Concat,
+    Carry,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Inst {
+    opcode: Opcode,
+    width: u8,
+    // Width of the first operand, which determines the semantics of shifts, divisions, comparisons, etc.
+    operand_width: u8,
+    dst: u32,
+    operands: [u32; 3],
+    imm: u64,
+}
+
+pub struct Tape {
+    insts: Vec<Inst>,
+    num_regs: usize,
+    num_vars: usize,
+    use_avx2: bool,
+}
+
+impl Tape {
+    // Fails if the AST contains a symbol that is not present in `vars`, or a value wider than 64 bits.
+    pub fn new(ctx: &Context, root: AstIdx, vars: &[AstIdx]) -> FfiResult<Self> {
+        let var_to_input: AHashMap<AstIdx, u64> = vars
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (*v, i as u64))
+            .collect();
+
+        let order = post_order(ctx, root);
+        let mut position: AHashMap<AstIdx, usize> = AHashMap::with_capacity(order.len());
+        let mut last_use = vec![0; order.len()];
+        for (i, idx) in order.iter().enumerate() {
+            position.insert(*idx, i);
+            for child in ctx.arena.get_node(*idx).children() {
+                last_use[position[child]] = i;
+            }
+        }
+
+        let mut insts = Vec::with_capacity(order.len());
+        let mut regs: Vec<u32> = vec![0; order.len()];
+        let mut free_regs: Vec<u32> = Vec::new();
+        let mut num_regs = 0;
+        for (i, idx) in order.iter().enumerate() {
+            let ast = ctx.arena.get_node(*idx);
+            if ctx.arena.get_width(*idx) > 64 {
+                return Err(FfiError::invalid("the tape only supports values of up to 64 bits"));
+            }
+            let children = ast.children();
+            let operand_width = match children.first() {
+                Some(child) => ctx.arena.get_width(*child),
+                None => ctx.arena.get_width(*idx),
+            };
+
+            let mut imm = 0;
+            let opcode = match ast {
+                SimpleAst::Add(_) => Opcode::Add,
+                SimpleAst::Mul(_) => Opcode::Mul,
+                SimpleAst::Pow(_) => Opcode::Pow,
+                SimpleAst::And(_) => Opcode::And,
+                SimpleAst::Or(_) => Opcode::Or,
+                SimpleAst::Xor(_) => Opcode::Xor,
+                SimpleAst::Lshr(_) => Opcode::Lshr,
+                SimpleAst::Shl(_) => Opcode::Shl,
+                SimpleAst::Ashr(_) => Opcode::Ashr,
+                SimpleAst::Udiv(_) => Opcode::Udiv,
+                SimpleAst::Urem(_) => Opcode::Urem,
+                SimpleAst::Sdiv(_) => Opcode::Sdiv,
+                SimpleAst::Srem(_) => Opcode::Srem,
+                SimpleAst::Rotl(_) => Opcode::Rotl,
+                SimpleAst::Rotr(_) => Opcode::Rotr,
+                SimpleAst::Neg(_) => Opcode::Neg,
+                SimpleAst::Popcount(_) => Opcode::Popcount,
+                SimpleAst::Ctlz(_) => Opcode::Ctlz,
+                SimpleAst::Cttz(_) => Opcode::Cttz,
+                SimpleAst::Constant { c, .. } => {
+                    imm = *c as u64;
+                    Opcode::Constant
+                }
+                SimpleAst::Symbol { id, .. } => {
+                    imm = match var_to_input.get(idx) {
+                        Some(input) => *input,
+                        None => {
+                            return Err(FfiError::invalid(format!(
+                                "no input was provided for symbol {}",
+                                ctx.arena.get_symbol_name(*id)
+                            )))
+                        }
+                    };
+                    Opcode::Input
+                }
+                SimpleAst::Zext(_) | SimpleAst::Trunc(_) => Opcode::Mov,
+                SimpleAst::Sext(_) => Opcode::Sext,
+                SimpleAst::ICmp { predicate, .. } => Opcode::ICmp(*predicate),
+                SimpleAst::Select { .. } => Opcode::Select,
+                SimpleAst::Extract([_, _, low]) => {
+                    imm = ctx.arena.get_constant(*low) as u64;
+                    Opcode::Extract
+                }
+                SimpleAst::Concat([_, low]) => {
+                    imm = ctx.arena.get_width(*low) as u64;
+                    Opcode::Concat
+                }
+                SimpleAst::Carry(_) => Opcode::Carry,
+            };
+
+            // The width operands of casts and the bit indices of extracts are folded into the instruction,
+            // so only the operands that are actually read need to be kept alive.
+            let num_operands = match opcode {
+                Opcode::Mov | Opcode::Sext | Opcode::Extract => 1,
+                _ => children.len(),
+            };
+
+            let mut operands = [0; 3];
+            for (j, child) in children.iter().take(num_operands).enumerate() {
+                operands[j] = regs[position[child]];
+            }
+
+            // Allocate the destination before releasing the operands, so that an instruction never aliases its inputs.
+            let dst = free_regs.pop().unwrap_or_else(|| {
+                num_regs += 1;
+                num_regs as u32 - 1
+            });
+            regs[i] = dst;
+
+            for (j, child) in children.iter().enumerate() {
+                let p = position[child];
+                if last_use[p] == i && !children[..j].contains(child) {
+                    free_regs.push(regs[p]);
+                }
+            }
+
+            insts.push(Inst {
+                opcode,
+                width: ctx.arena.get_width(*idx),
+                operand_width,
+                dst,
+                operands,
+                imm,
+            });
+        }
+
+        return Ok(Tape {
+            insts,
+            num_regs,
+            num_vars: vars.len(),
+            use_avx2: has_avx2(),
+        });
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    // The point count is passed explicitly, since a tape without variables has no columns to take it from.
+    pub fn eval(&self, columns: &[&[u64]], num_points: usize) -> Vec<u64> {
+        let mut output = vec![0; num_points];
+        self.eval_into(columns, &mut output);
+        return output;
+    }
+
+    // Evaluates the tape for every input vector, writing the `j`th result to `output[j]`.
+    pub fn eval_into(&self, columns: &[&[u64]], output: &mut [u64]) {
+        assert_eq!(columns.len(), self.num_vars);
+        assert!(columns.iter().all(|c| c.len() == output.len()));
+
+        let mut regs: Vec<Block> = vec![[0; LANES]; self.num_regs];
+        let mut inputs: Vec<Block> = vec![[0; LANES]; self.num_vars];
+        let root = self.insts.last().unwrap().dst as usize;
+        for start in (0..output.len()).step_by(LANES) {
+            let n = LANES.min(output.len() - start);
+            for (input, column) in inputs.iter_mut().zip(columns) {
+                input[..n].copy_from_slice(&column[start..start + n]);
+            }
+
+            self.run(&mut regs, &inputs);
+            output[start..start + n].copy_from_slice(&regs[root][..n]);
+        }
+    }
+
+    fn run(&self, regs: &mut [Block], inputs: &[Block]) {
+        #[cfg(target_arch = "x86_64")]
+        if self.use_avx2 {
+            unsafe { return self.run_avx2(regs, inputs) };
+        }
+
+        self.run_generic(regs, inputs)
+    }
+
+    // Identical to `run_generic`, but compiled with AVX2 enabled so the lane loops are vectorized with 256-bit registers.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn run_avx2(&self, regs: &mut [Block], inputs: &[Block]) {
+        self.run_generic(regs, inputs)
+    }
+
+    #[inline(always)]
+    fn run_generic(&self, regs: &mut [Block], inputs: &[Block]) {
+        for inst in self.insts.iter() {
+            let w = inst.operand_width;
+            let a = |j: usize| &regs[inst.operands[j] as usize];
+            let mut out: Block = [0; LANES];
+
+            macro_rules! unary {
+                (|$x:ident| $e:expr) => {{
+                    let x = a(0);
+                    for l in 0..LANES {
+                        let $x = x[l];
+                        out[l] = $e;
+                    }
+                }};
+            }
+
+            macro_rules! binary {
+                (|$x:ident, $y:ident| $e:expr) => {{
+                    let (x, y) = (a(0), a(1));
+                    for l in 0..LANES {
+                        let ($x, $y) = (x[l], y[l]);
+                        out[l] = $e;
+                    }
+                }};
+            }
+
+            macro_rules! ternary {
+                (|$x:ident, $y:ident, $z:ident| $e:expr) => {{
+                    let (x, y, z) = (a(0), a(1), a(2));
+                    for l in 0..LANES {
+                        let ($x, $y, $z) = (x[l], y[l], z[l]);
+                        out[l] = $e;
+                    }
+                }};
+            }
+
+            match inst.opcode {
+                Opcode::Add => binary!(|x, y| x.wrapping_add(y)),
+                Opcode::Mul => binary!(|x, y| x.wrapping_mul(y)),
+                Opcode::Pow => binary!(|x, y| Pow(x, y)),
+                Opcode::And => binary!(|x, y| x & y),
+                Opcode::Or => binary!(|x, y| x | y),
+                Opcode::Xor => binary!(|x, y| x ^ y),
+                Opcode::Lshr => binary!(|x, y| lshr_const(x as u128, y as u128, w) as u64),
+                Opcode::Shl => binary!(|x, y| shl_const(x as u128, y as u128, w) as u64),
+                Opcode::Ashr => binary!(|x, y| ashr_const(x as u128, y as u128, w) as u64),
+                Opcode::Udiv => binary!(|x, y| udiv_const(x as u128, y as u128, w) as u64),
+                Opcode::Urem => binary!(|x, y| urem_const(x as u128, y as u128, w) as u64),
+                Opcode::Sdiv => binary!(|x, y| sdiv_const(x as u128, y as u128, w) as u64),
+                Opcode::Srem => binary!(|x, y| srem_const(x as u128, y as u128, w) as u64),
+                Opcode::Rotl => binary!(|x, y| rotl_const(x as u128, y as u128, w) as u64),
+                Opcode::Rotr => binary!(|x, y| rotr_const(x as u128, y as u128, w) as u64),
+                Opcode::Neg => unary!(|x| !x),
+                Opcode::Popcount => unary!(|x| popcount_const(x as u128, w) as u64),
+                Opcode::Ctlz => unary!(|x| ctlz_const(x as u128, w) as u64),
+                Opcode::Cttz => unary!(|x| cttz_const(x as u128, w) as u64),
+                Opcode::Constant => out = [inst.imm; LANES],
+                Opcode::Input => out = inputs[inst.imm as usize],
+                Opcode::Mov => unary!(|x| x),
+                Opcode::Sext => unary!(|x| sign_extend(x as u128, w) as u64),
+                Opcode::ICmp(pred) => binary!(|x, y| cmp(pred, x as u128, y as u128, w) as u64),
+                Opcode::Select => ternary!(|c, x, y| if c != 0 { x } else { y }),
+                Opcode::Extract => unary!(|x| x >> inst.imm),
+                Opcode::Concat => binary!(|x, y| (x << inst.imm) | y),
+                Opcode::Carry => ternary!(|x, y, z| (x & y) | (x & z) | (y & z)),
+            }
+
+            let mask = get_modulo_mask(inst.width) as u64;
+            for l in 0..LANES {
+                out[l] &= mask;
+            }
+
+            regs[inst.dst as usize] = out;
+        }
+    }
+}
+
+fn has_avx2() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    return is_x86_feature_detected!("avx2");
+    #[cfg(not(target_arch = "x86_64"))]
+    return false;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ContextCompileTape(
+    ctx: *const Context,
+    id: AstIdx,
+    variables: *const AstIdx,
+    var_count: u64,
+) -> *mut Tape {
+    ffi::guard(|| {
+        let ctx = ffi::context_ref(ctx)?;
+        let vars = ffi::slice(variables, var_count as usize, "variables")?;
+        ffi::nodes(ctx, vars)?;
+        let tape = Tape::new(ctx, ffi::node(ctx, id)?, vars)?;
+        Ok(Box::into_raw(Box::new(tape)))
+    })
+}
+
+// The inputs are laid out column by column, i.e. `inputs[i * num_points + j]` holds the value of the `i`th variable
+// for the `j`th input vector.
+#[no_mangle]
+pub unsafe extern "C" fn TapeEvaluate(
+    tape: *const Tape,
+    inputs: *const u64,
+    num_points: u64,
+    output: *mut u64,
+) -> FfiStatus {
+    ffi::guard_status(|| {
+        let tape = ffi::non_null_ref(tape, "tape")?;
+        let num_points = num_points as usize;
+        if num_points == 0 {
+            return Ok(());
+        }
+
+        let len = tape
+            .num_vars
+            .checked_mul(num_points)
+            .ok_or_else(|| FfiError::invalid("too many input points"))?;
+        let inputs = ffi::slice(inputs, len, "inputs")?;
+        let columns: Vec<&[u64]> = inputs.chunks(num_points).collect();
+        ffi::non_null(output, "output")?;
+        let output = std::slice::from_raw_parts_mut(output, num_points);
+        tape.eval_into(&columns, output);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn FreeTape(tape: *mut Tape) -> FfiStatus {
+    ffi::guard_status(|| {
+        drop(Box::from_raw(ffi::non_null(tape, "tape")?));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use crate::{ast_parser::parse_ast, eval::Evaluator, simple_ast::Arena};
+
+    use super::*;
+
+    fn check(ctx: &mut Context, s: &str, names: &[&str], width: u8) {
+        let idx = parse_ast(ctx, s, width).unwrap();
+        let vars: Vec<AstIdx> = names
+            .iter()
+            .map(|n| ctx.arena.symbol_with_name(n.to_string(), width))
+            .collect();
+
+        // Use a point count that is not a multiple of the block size, so the final partial block is covered.
+        let mut rng = rand::thread_rng();
+        let num_points = 3 * LANES + 17;
+        let columns: Vec<Vec<u64>> = (0..vars.len())
+            .map(|_| (0..num_points).map(|_| rng.gen::<u64>() >> rng.gen_range(0..64)).collect())
+            .collect();
+        let column_refs: Vec<&[u64]> = columns.iter().map(|c| c.as_slice()).collect();
+
+        let mut tape = Tape::new(ctx, idx, &vars).unwrap();
+        let vectorized = tape.eval(&column_refs, num_points);
+        tape.use_avx2 = false;
+        let scalar = tape.eval(&column_refs, num_points);
+
+        let mut evaluator = Evaluator::new(ctx, idx, &vars);
+        for j in 0..num_points {
+            let inputs: Vec<u128> = columns.iter().map(|c| c[j] as u128).collect();
+            let expected = evaluator.eval(&inputs) as u64;
+            assert_eq!(vectorized[j], expected, "{} on {:?}", s, inputs);
+            assert_eq!(scalar[j], expected, "{} on {:?}", s, inputs);
+        }
+    }
+
+    #[test]
+    fn tape_matches_evaluator() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        check(&mut ctx, "(x & y) + (x | y) * 3 - (x ^ ~y)", &["x", "y"], 64);
+        check(&mut ctx, "(x ** 3) + (x << (y & 7)) + (x >> y) + (x >>s y)", &["x", "y"], 16);
+        check(&mut ctx, "(x / y) + (x % y) + (x /s y) + (x %s y)", &["x", "y"], 8);
+        check(&mut ctx, "(x <<< y) ^ (x >>> y) ^ popcount(x) ^ ctlz(y) ^ cttz(x & y)", &["x", "y"], 32);
+        check(&mut ctx, "(x <s y ? x : y) + (x[5:2] zx i8) + (x[7:4] ++ y[3:0])", &["x", "y"], 8);
+        check(&mut ctx, "maj(x, y, z) + (x tr i8 sx i64) * (z tr i1 zx i64)", &["x", "y", "z"], 64);
+    }
+
+    #[test]
+    fn tape_reuses_registers() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let x = ctx.arena.symbol_with_name("x".to_string(), 64);
+        let one = ctx.arena.constant(1, 64);
+        let mut idx = x;
+        for _ in 0..1000 {
+            idx = ctx.arena.add(idx, one);
+            idx = ctx.arena.mul(idx, idx);
+        }
+
+        let tape = Tape::new(&ctx, idx, &[x]).unwrap();
+        assert!(tape.num_regs <= 3);
+        assert_eq!(tape.eval(&[&[]], 0), Vec::<u64>::new());
+
+        let mut evaluator = Evaluator::new(&ctx, idx, &[x]);
+        assert_eq!(tape.eval(&[&[5]], 1), vec![evaluator.eval(&[5]) as u64]);
+    }
+
+    #[test]
+    fn evaluate_constant_tape() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let idx = parse_ast(&mut ctx, "(3 * 5) ^ 1", 64).unwrap();
+        let tape = Tape::new(&ctx, idx, &[]).unwrap();
+        assert_eq!(tape.eval(&[], 3), vec![14; 3]);
+
+        unsafe {
+            let tape = ContextCompileTape(&ctx, idx, std::ptr::null(), 0);
+            let mut output = [0u64; 2];
+            let status = TapeEvaluate(tape, std::ptr::null(), 2, output.as_mut_ptr());
+            assert_eq!(status, FfiStatus::Ok);
+            assert_eq!(output, [14; 2]);
+            assert_eq!(FreeTape(tape), FfiStatus::Ok);
+        }
+    }
+
+    #[test]
+    fn evaluate_empty_batch() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let idx = parse_ast(&mut ctx, "x + y", 64).unwrap();
+        let vars = [
+            ctx.arena.symbol_with_name("x".to_string(), 64),
+            ctx.arena.symbol_with_name("y".to_string(), 64),
+        ];
+        unsafe {
+            let tape = ContextCompileTape(&ctx, idx, vars.as_ptr(), 2);
+            let mut output = [0u64; 1];
+            let status = TapeEvaluate(tape, [0u64; 0].as_ptr(), 0, output.as_mut_ptr());
+            assert_eq!(status, FfiStatus::Ok);
+
+            // The size of the input buffer must not wrap around.
+            let inputs = [0u64; 2];
+            let num_points = 1 << (usize::BITS - 1);
+            let status = TapeEvaluate(tape, inputs.as_ptr(), num_points, output.as_mut_ptr());
+            assert_eq!(status, FfiStatus::InvalidArgument);
+            assert_eq!(FreeTape(tape), FfiStatus::Ok);
+        }
+    }
+
+    #[test]
+    fn compile_rejects_unsupported_dags() {
+        let mut ctx = Context {
+            arena: Arena::new(),
+        };
+
+        let wide = parse_ast(&mut ctx, "(x:i128 + 1:i128) tr i64", 64).unwrap();
+        let x = ctx.arena.symbol_with_name("x".to_string(), 128);
+        let missing = parse_ast(&mut ctx, "x + y", 64).unwrap();
+        let y = ctx.arena.symbol_with_name("y".to_string(), 64);
+        unsafe {
+            assert!(ContextCompileTape(&ctx, wide, &x, 1).is_null());
+            assert_eq!(ffi::GetLastStatus(), FfiStatus::InvalidArgument);
+            assert!(ContextCompileTape(&ctx, missing, &y, 1).is_null());
+            assert_eq!(ffi::GetLastStatus(), FfiStatus::InvalidArgument);
+        }
+    }
+}
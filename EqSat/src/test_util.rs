@@ -0,0 +1,8 @@
+// Fixtures shared by the unit tests of several modules.
+use crate::simple_ast::{Arena, Context};
+
+pub(crate) fn new_ctx() -> Context {
+    Context {
+        arena: Arena::new(),
+    }
+}
@@ -72,38 +72,41 @@ impl TruthTable {
 // Two, three, and four variable boolean truth table utility.
 impl TruthTableDatabase {
     pub fn new() -> Self {
+        return Self::load(Path::new("Minimization\\TruthTables")).expect("no file found");
+    }
+
+    // Load the truth tables from a directory containing the `{n}variable_truthtable.bc` files.
+    pub fn load(dir: &Path) -> std::io::Result<Self> {
         let mut two_var_tt: Vec<u8> = Vec::new();
-        Self::load_truth_table_from_bin(2, &mut two_var_tt);
+        Self::load_truth_table_from_bin(dir, 2, &mut two_var_tt)?;
 
         let mut three_var_tt: Vec<u8> = Vec::new();
-        Self::load_truth_table_from_bin(3, &mut three_var_tt);
+        Self::load_truth_table_from_bin(dir, 3, &mut three_var_tt)?;
 
         let mut four_var_tt: Vec<u8> = Vec::new();
-        Self::load_truth_table_from_bin(4, &mut four_var_tt);
+        Self::load_truth_table_from_bin(dir, 4, &mut four_var_tt)?;
 
-        return TruthTableDatabase {
+        return Ok(TruthTableDatabase {
             two_var_truth_table: two_var_tt,
             three_var_truth_table: three_var_tt,
             four_var_truth_table: four_var_tt,
-        };
+        });
     }
 
-    fn load_truth_table_from_bin(num_vars: u64, output: &mut Vec<u8>) {
-        let path = format!(
-            "Minimization\\TruthTables\\{}variable_truthtable.bc",
-            num_vars
-        );
-        let bytes = Self::get_file_as_byte_vec(&path, output);
+    fn load_truth_table_from_bin(dir: &Path, num_vars: u64, output: &mut Vec<u8>) -> std::io::Result<()> {
+        let path = dir.join(format!("{}variable_truthtable.bc", num_vars));
+        let mut f = File::open(&path)?;
+        f.read_to_end(output)?;
+        return Ok(());
     }
 
-    fn get_file_as_byte_vec(filename: &String, buffer: &mut Vec<u8>) {
-        let mut f = File::open(&filename).expect("no file found");
-        let metadata = fs::metadata(&filename).expect("unable to read metadata");
-        f.read_to_end(buffer).expect("buffer overflow");
+    // Each table holds one entry for every boolean function of `var_count` variables.
+    pub fn num_entries(var_count: u32) -> usize {
+        return 1 << (1 << var_count);
     }
 
     pub fn get_truth_table_entry(
-        db: &mut TruthTableDatabase,
+        db: &TruthTableDatabase,
         ctx: &mut Context,
         var_count: u32,
         vars: *const AstIdx,
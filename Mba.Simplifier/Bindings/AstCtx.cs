@@ -4,6 +4,7 @@ using System;
 using System.Collections.Generic;
 using System.Linq;
 using System.Runtime.InteropServices;
+using System.Runtime.ExceptionServices;
 using System.Text;
 using System.Threading.Tasks;
 using Mba.Utility;
@@ -35,9 +36,28 @@ namespace Mba.Simplifier.Bindings
         Extract = 15,
         Concat = 16,
         Carry = 17,
+        Shl = 18,
+        Ashr = 19,
+        Udiv = 20,
+        Urem = 21,
+        Sdiv = 22,
+        Srem = 23,
+        Rotl = 24,
+        Rotr = 25,
+        Sext = 26,
+        Popcount = 27,
+        Ctlz = 28,
+        Cttz = 29,
     }
 
-    public class AstCtx
+    public enum CodegenBackend : byte
+    {
+        C = 0,
+        Rust = 1,
+        LlvmIr = 2,
+    }
+
+    public class AstCtx : IDisposable
     {
         private readonly nint handle;
 
@@ -48,40 +68,95 @@ namespace Mba.Simplifier.Bindings
 
         public unsafe AstCtx()
         {
-            handle = (nint)Api.CreateContext();
+            handle = (nint)NativeError.Check(Api.CreateContext());
+        }
+
+        // Create a context over a shared arena. Contexts created from the same arena may be used concurrently from different threads.
+        public unsafe AstCtx(SharedArena arena)
+        {
+            handle = (nint)NativeError.Check(Api.ContextCreateShared(arena));
         }
 
-        public unsafe void Clear() => Api.ContextClear(this);
+        // Load a context from a snapshot written by SaveSnapshot.
+        public static unsafe AstCtx LoadSnapshot(string path)
+        {
+            var ptr = NativeError.Check(Api.ContextLoadSnapshot(new MarshaledString(path)));
+            return new AstCtx((nint)ptr);
+        }
+
+        // Write a versioned binary snapshot of every node, optionally including the ISLE cache.
+        public unsafe void SaveSnapshot(string path, bool includeIsleCache = true)
+        {
+            if (!Api.ContextSaveSnapshot(this, new MarshaledString(path), includeIsleCache))
+                throw NativeError.GetLastError();
+        }
+
+        // Free the native context and all of its nodes. Only the creator of the handle may call this, since wrappers of
+        // borrowed handles, e.g. the context passed to a rewrite callback, share it.
+        public unsafe void Dispose() => NativeError.Check(Api.FreeContext(this));
+
+        public unsafe void Clear() => NativeError.Check(Api.ContextClear(this));
+
+        // Roots are reference counted, and keep the node and everything it references alive across garbage collections.
+        public unsafe void AddRoot(AstIdx id) => NativeError.Check(Api.ContextAddRoot(this, id));
+
+        public unsafe void RemoveRoot(AstIdx id) => NativeError.Check(Api.ContextRemoveRoot(this, id));
+
+        // Free every node that is unreachable from a root, compacting the rest.
+        // Returns a table mapping each old index to its new index, where freed nodes are mapped to uint.MaxValue.
+        public unsafe AstIdx[] CollectGarbage()
+        {
+            ulong len = 0;
+            var ptr = Api.ContextCollectGarbage(this, &len);
+            return NativeBuffers.TakeArray(ptr, len);
+        }
 
         // Constructors
-        public unsafe AstIdx Add(AstIdx a, AstIdx b) => Api.ContextAdd(this, a, b);
+        public unsafe AstIdx Add(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextAdd(this, a, b));
         public unsafe AstIdx Sub(AstIdx a, AstIdx b)
         {
             // a - b
             // a + -b
+            var width = GetWidth(b);
+            if (width > 64)
+                return Add(a, Mul(Constant(UInt128.MaxValue, width), b));
+
             var c = TryGetConstantValue(b);
             if (c != null)
                 return Add(a, Constant(ulong.MaxValue * c.Value, GetWidth(b)));
 
             return Add(a, Mul(Constant(ulong.MaxValue, GetWidth(b)), b)); ;
         }
-        public unsafe AstIdx Mul(AstIdx a, AstIdx b) => Api.ContextMul(this, a, b);
-        public unsafe AstIdx Pow(AstIdx a, AstIdx b) => Api.ContextPow(this, a, b);
-        public unsafe AstIdx And(AstIdx a, AstIdx b) => Api.ContextAnd(this, a, b);
-        public unsafe AstIdx Or(AstIdx a, AstIdx b) => Api.ContextOr(this, a, b);
-        public unsafe AstIdx Xor(AstIdx a, AstIdx b) => Api.ContextXor(this, a, b);
-        public unsafe AstIdx Neg(AstIdx a) => Api.ContextNeg(this, a);
-        public unsafe AstIdx Lshr(AstIdx a, AstIdx b) => Api.ContextLshr(this, a, b);
-        public unsafe AstIdx Zext(AstIdx a, byte width) => Api.ContextZext(this, a, width);
-        public unsafe AstIdx Trunc(AstIdx a, byte width) => Api.ContextTrunc(this, a, width);
-        public unsafe AstIdx ICmp(Predicate pred, AstIdx a, AstIdx b) => Api.ContextICmp(this, pred, a, b);
-        public unsafe AstIdx Select(AstIdx a, AstIdx b, AstIdx c) => Api.ContextSelect(this, a, b, c);
-        public unsafe AstIdx Extract(AstIdx a, byte high, byte low) => Api.ContextExtract(this, a, high, low);
-        public unsafe AstIdx Concat(AstIdx a, AstIdx b) => Api.ContextConcat(this, a, b);
-        public unsafe AstIdx Carry(AstIdx a, AstIdx b, AstIdx c) => Api.ContextCarry(this, a, b, c);
-        public unsafe AstIdx Constant(ulong c, byte width) => Api.ContextConstant(this, c, width);
-        public unsafe AstIdx Constant(ulong c, uint width) => Api.ContextConstant(this, c, (byte)width);
-        public unsafe AstIdx Symbol(string s, byte width) => Api.ContextSymbol(this, new MarshaledString(s), width);
+        public unsafe AstIdx Mul(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextMul(this, a, b));
+        public unsafe AstIdx Pow(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextPow(this, a, b));
+        public unsafe AstIdx And(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextAnd(this, a, b));
+        public unsafe AstIdx Or(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextOr(this, a, b));
+        public unsafe AstIdx Xor(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextXor(this, a, b));
+        public unsafe AstIdx Neg(AstIdx a) => NativeError.Check(Api.ContextNeg(this, a));
+        public unsafe AstIdx Lshr(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextLshr(this, a, b));
+        public unsafe AstIdx Shl(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextShl(this, a, b));
+        public unsafe AstIdx Ashr(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextAshr(this, a, b));
+        public unsafe AstIdx Udiv(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextUdiv(this, a, b));
+        public unsafe AstIdx Urem(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextUrem(this, a, b));
+        public unsafe AstIdx Sdiv(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextSdiv(this, a, b));
+        public unsafe AstIdx Srem(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextSrem(this, a, b));
+        public unsafe AstIdx Rotl(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextRotl(this, a, b));
+        public unsafe AstIdx Rotr(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextRotr(this, a, b));
+        public unsafe AstIdx Popcount(AstIdx a) => NativeError.Check(Api.ContextPopcount(this, a));
+        public unsafe AstIdx Ctlz(AstIdx a) => NativeError.Check(Api.ContextCtlz(this, a));
+        public unsafe AstIdx Cttz(AstIdx a) => NativeError.Check(Api.ContextCttz(this, a));
+        public unsafe AstIdx Zext(AstIdx a, byte width) => NativeError.Check(Api.ContextZext(this, a, width));
+        public unsafe AstIdx Sext(AstIdx a, byte width) => NativeError.Check(Api.ContextSext(this, a, width));
+        public unsafe AstIdx Trunc(AstIdx a, byte width) => NativeError.Check(Api.ContextTrunc(this, a, width));
+        public unsafe AstIdx ICmp(Predicate pred, AstIdx a, AstIdx b) => NativeError.Check(Api.ContextICmp(this, pred, a, b));
+        public unsafe AstIdx Select(AstIdx a, AstIdx b, AstIdx c) => NativeError.Check(Api.ContextSelect(this, a, b, c));
+        public unsafe AstIdx Extract(AstIdx a, byte high, byte low) => NativeError.Check(Api.ContextExtract(this, a, high, low));
+        public unsafe AstIdx Concat(AstIdx a, AstIdx b) => NativeError.Check(Api.ContextConcat(this, a, b));
+        public unsafe AstIdx Carry(AstIdx a, AstIdx b, AstIdx c) => NativeError.Check(Api.ContextCarry(this, a, b, c));
+        public unsafe AstIdx Constant(ulong c, byte width) => NativeError.Check(Api.ContextConstant(this, c, width));
+        public unsafe AstIdx Constant(ulong c, uint width) => NativeError.Check(Api.ContextConstant(this, c, (byte)width));
+        public unsafe AstIdx Constant(UInt128 c, byte width) => NativeError.Check(Api.ContextConstantWide(this, (ulong)c, (ulong)(c >> 64), width));
+        public unsafe AstIdx Symbol(string s, byte width) => NativeError.Check(Api.ContextSymbol(this, new MarshaledString(s), width));
 
         public AstIdx Binop(AstOp opcode, AstIdx a, AstIdx b)
         {
@@ -94,6 +169,26 @@ namespace Mba.Simplifier.Bindings
                 AstOp.Or => Or(a, b),
                 AstOp.Xor => Xor(a, b),
                 AstOp.Lshr => Lshr(a, b),
+                AstOp.Shl => Shl(a, b),
+                AstOp.Ashr => Ashr(a, b),
+                AstOp.Udiv => Udiv(a, b),
+                AstOp.Urem => Urem(a, b),
+                AstOp.Sdiv => Sdiv(a, b),
+                AstOp.Srem => Srem(a, b),
+                AstOp.Rotl => Rotl(a, b),
+                AstOp.Rotr => Rotr(a, b),
+                _ => throw new NotImplementedException(),
+            };
+        }
+
+        public AstIdx Unop(AstOp opcode, AstIdx a)
+        {
+            return opcode switch
+            {
+                AstOp.Neg => Neg(a),
+                AstOp.Popcount => Popcount(a),
+                AstOp.Ctlz => Ctlz(a),
+                AstOp.Cttz => Cttz(a),
                 _ => throw new NotImplementedException(),
             };
         }
@@ -182,8 +277,16 @@ namespace Mba.Simplifier.Bindings
         public unsafe bool GetHasPoly(AstIdx id) => Api.ContextGetHasPoly(this, id);
         public unsafe AstClassification GetClass(AstIdx id) => Api.ContextGetClass(this, id);
         public unsafe KnownBits GetKnownBits(AstIdx id) => Api.ContextGetKnownBits(this, id);
+
+        public unsafe AstStats GetStats(AstIdx id)
+        {
+            AstStats stats;
+            NativeError.Check(Api.ContextGetStats(this, id, &stats));
+            return stats;
+        }
+
         public unsafe ulong GetImutData(AstIdx id) => Api.ContextGetImutData(this, id);
-        public unsafe void SetImutData(AstIdx id, ulong imut) => Api.ContextSetImutData(this, id, imut);
+        public unsafe void SetImutData(AstIdx id, ulong imut) => NativeError.Check(Api.ContextSetImutData(this, id, imut));
         public unsafe AstIdx GetOp(AstIdx id, uint operand)
         {
             return operand switch
@@ -194,11 +297,12 @@ namespace Mba.Simplifier.Bindings
                 _ => throw new InvalidOperationException()
             };
         }
-        public unsafe AstIdx GetOp0(AstIdx id) => Api.ContextGetOp0(this, id);
-        public unsafe AstIdx GetOp1(AstIdx id) => Api.ContextGetOp1(this, id);
-        public unsafe AstIdx GetOp2(AstIdx id) => Api.ContextGetOp2(this, id);
+        public unsafe AstIdx GetOp0(AstIdx id) => NativeError.Check(Api.ContextGetOp0(this, id));
+        public unsafe AstIdx GetOp1(AstIdx id) => NativeError.Check(Api.ContextGetOp1(this, id));
+        public unsafe AstIdx GetOp2(AstIdx id) => NativeError.Check(Api.ContextGetOp2(this, id));
         public unsafe Predicate GetPredicate(AstIdx id) => Api.ContextGetPredicate(this, id);
         public unsafe ulong GetConstantValue(AstIdx id) => Api.ContextGetConstantValue(this, id);
+        public unsafe UInt128 GetWideConstantValue(AstIdx id) => ((UInt128)Api.ContextGetConstantValueHigh(this, id) << 64) | Api.ContextGetConstantValue(this, id);
 
         public unsafe bool IsSymbol(AstIdx id) => GetOpcode(id) == AstOp.Symbol;
         public unsafe bool IsConstant(AstIdx id) => GetOpcode(id) == AstOp.Constant;
@@ -212,11 +316,32 @@ namespace Mba.Simplifier.Bindings
 
             return GetConstantValue(idx);
         }
-        public unsafe string GetSymbolName(AstIdx id) => StringMarshaler.AcquireString(Api.ContextGetSymbolName(this, id));
+        public unsafe string GetSymbolName(AstIdx id) => NativeBuffers.TakeString(Api.ContextGetSymbolName(this, id));
 
         // Utility:
-        public unsafe AstIdx ParseAstString(string astStr) => Api.ContextParseAstString(this, new MarshaledString(astStr));
-        public unsafe string GetAstString(AstIdx id) => StringMarshaler.AcquireString(Api.ContextGetAstString(this, id));
+        public unsafe AstIdx ParseAstString(string astStr, byte width = 64) => NativeError.Check(Api.ContextParseAstString(this, new MarshaledString(astStr), width));
+        public unsafe string GetAstString(AstIdx id) => NativeBuffers.TakeString(Api.ContextGetAstString(this, id));
+        public unsafe AstIdx ParseSExpr(string sexpr, byte width = 64) => NativeError.Check(Api.ContextParseSExpr(this, new MarshaledString(sexpr), width));
+        public unsafe string GetSExprString(AstIdx id) => NativeBuffers.TakeString(Api.ContextGetSExprString(this, id));
+        public unsafe string GetDotString(AstIdx id) => NativeBuffers.TakeString(Api.ContextGetDotString(this, id));
+
+        // Print the node with minimal parentheses, in a syntax accepted by the parser.
+        public unsafe string PrettyPrint(AstIdx id, PrintOptions options) => NativeBuffers.TakeString(Api.ContextPrintWithOptions(this, id, &options));
+
+        public unsafe string GetSmtLibString(AstIdx id) => NativeBuffers.TakeString(Api.ContextGetSmtLibString(this, id));
+
+        // Get a QF_BV script that is unsatisfiable if and only if both nodes are equivalent.
+        public unsafe string GetSmtLibEquivalenceQuery(AstIdx before, AstIdx after) => NativeBuffers.TakeString(Api.ContextGetSmtLibEquivalenceQuery(this, before, after));
+
+        // Translate the DAG into a function named `name`, with one parameter per variable in the given order.
+        public unsafe string EmitCode(AstIdx id, List<AstIdx> variables, CodegenBackend backend, string name)
+        {
+            var span = CollectionsMarshal.AsSpan(variables);
+            fixed (AstIdx* arrPtr = span)
+            {
+                return NativeBuffers.TakeString(Api.ContextEmitCode(this, id, arrPtr, (ulong)variables.Count, backend, new MarshaledString(name)));
+            }
+        }
 
         // Get a unique list of all input variables, sorted in alphabetical order.
         public unsafe List<AstIdx> CollectVariables(AstIdx id)
@@ -231,18 +356,70 @@ namespace Mba.Simplifier.Bindings
         {
             ulong len = 0;
             var ptr = Api.ContextCollectVariables(this, id, &len);
-            var capacity = (int)len;
-            for (int i = 0; i < capacity; i++)
+            vec.AddRange(NativeBuffers.TakeArray(ptr, len));
+            return vec;
+        }
+
+        // Replace every occurrence of the keys in the mapping with their values, preserving sharing.
+        // Replacements are not rewritten themselves, and must have the same width as the node they replace.
+        public unsafe AstIdx Substitute(AstIdx id, Dictionary<AstIdx, AstIdx> mapping)
+        {
+            var from = mapping.Keys.ToArray();
+            var to = mapping.Values.ToArray();
+            fixed (AstIdx* fromPtr = from)
             {
-                // Get a ptr to the elem at this idx.
-                var pItem = &ptr[i];
+                fixed (AstIdx* toPtr = to)
+                {
+                    return NativeError.Check(Api.ContextSubstitute(this, id, fromPtr, toPtr, (ulong)from.Length));
+                }
+            }
+        }
 
-                // Push the variable.
-                var value = *pItem;
-                vec.Add(value);
+        // Rewrite the DAG from the bottom up. The rewriter is invoked once per node with the original node and the node
+        // rebuilt on top of the rewritten operands, and returns the node to replace it with.
+        public unsafe AstIdx Rewrite(AstIdx id, Func<AstIdx, AstIdx, AstIdx> rewriter)
+        {
+            var state = new RewriteState(rewriter);
+            var handle = GCHandle.Alloc(state);
+            try
+            {
+                var result = Api.ContextRewrite(this, id, &RewriteCallback, GCHandle.ToIntPtr(handle));
+                // Exceptions cannot unwind through native frames, so the callback stashes them to be rethrown here.
+                state.Exception?.Throw();
+                return NativeError.Check(result);
+            }
+            finally
+            {
+                handle.Free();
             }
+        }
 
-            return vec;
+        private class RewriteState
+        {
+            public readonly Func<AstIdx, AstIdx, AstIdx> Rewriter;
+
+            public ExceptionDispatchInfo? Exception;
+
+            public RewriteState(Func<AstIdx, AstIdx, AstIdx> rewriter)
+            {
+                Rewriter = rewriter;
+            }
+        }
+
+        [UnmanagedCallersOnly]
+        private static unsafe AstIdx RewriteCallback(OpaqueAstCtx* ctx, AstIdx original, AstIdx rebuilt, nint userData)
+        {
+            var state = (RewriteState)GCHandle.FromIntPtr(userData).Target!;
+            try
+            {
+                return state.Rewriter(original, rebuilt);
+            }
+            catch (Exception e)
+            {
+                // Returning an invalid node aborts the rewrite.
+                state.Exception = ExceptionDispatchInfo.Capture(e);
+                return new AstIdx(uint.MaxValue);
+            }
         }
 
         public unsafe AstIdx GetBooleanForIndex(List<AstIdx> variables, int vecIdx)
@@ -250,7 +427,7 @@ namespace Mba.Simplifier.Bindings
             var span = CollectionsMarshal.AsSpan(variables);
             fixed (AstIdx* arrPtr = &span[0])
             {
-                return Api.ContextGetBooleanForIndex(this, arrPtr, (uint)variables.Count, (uint)vecIdx);
+                return NativeError.Check(Api.ContextGetBooleanForIndex(this, arrPtr, (uint)variables.Count, (uint)vecIdx));
             }
         }
 
@@ -259,7 +436,7 @@ namespace Mba.Simplifier.Bindings
             var span = CollectionsMarshal.AsSpan(variables);
             fixed (AstIdx* arrPtr = &span[0])
             {
-                return Api.ContextGetConjunctionFromVarMask(this, arrPtr, varMask);
+                return NativeError.Check(Api.ContextGetConjunctionFromVarMask(this, arrPtr, varMask));
             }
         }
 
@@ -270,7 +447,7 @@ namespace Mba.Simplifier.Bindings
             {
                 fixed (ulong* tablePtr = &table.Arr[0])
                 {
-                    return Api.ContextMinimizeAnf(this, db, tablePtr, arrPtr, (uint)variables.Count, (ulong*)rwxPagePtr);
+                    return NativeError.Check(Api.ContextMinimizeAnf(this, db, tablePtr, arrPtr, (uint)variables.Count, (ulong*)rwxPagePtr));
                 }
             }
         }
@@ -281,27 +458,14 @@ namespace Mba.Simplifier.Bindings
         {
             ulong len = 0;
             var ptr = Api.ContextEvaluateForAllZeroesAndOnes(this, id, mask, &len);
-
-            var capacity = (int)len;
-            var vec = new ulong[capacity];
-            for (int i = 0; i < capacity; i++)
-            {
-                // Get a ptr to the elem at this idx.
-                var pItem = &ptr[i];
-
-                // Push the variable.
-                var value = *pItem;
-                vec[i] = value;
-            }
-
-            return vec;
+            return NativeBuffers.TakeArray(ptr, len);
         }
 
         public unsafe void JitEvaluate(AstIdx id, ulong mask, bool isMultibit, uint bitWidth, AstIdx[] variables, ulong numCombinations, nint rwxPagePtr, nint outputArrayPtr)
         {
             fixed (AstIdx* arrPtr = &variables[0])
             {
-                Api.ContextJit(this, id, mask, isMultibit ? 1u : 0, bitWidth, arrPtr, (ulong)variables.Length, numCombinations, (ulong*)rwxPagePtr, (ulong*)outputArrayPtr);
+                NativeError.Check(Api.ContextJit(this, id, mask, isMultibit ? 1u : 0, bitWidth, arrPtr, (ulong)variables.Length, numCombinations, (ulong*)rwxPagePtr, (ulong*)outputArrayPtr));
             }
         }
 
@@ -309,7 +473,9 @@ namespace Mba.Simplifier.Bindings
         {
             fixed (AstIdx* arrPtr = &variables[0])
             {
-                return (nint)Api.ContextCompileLegacy(this, id, mask, arrPtr, (ulong)variables.Length, (ulong*)rwxPagePtr);
+                // The code is emitted at the start of the page.
+                NativeError.Check(Api.ContextCompileLegacy(this, id, mask, arrPtr, (ulong)variables.Length, (ulong*)rwxPagePtr));
+                return rwxPagePtr;
             }
         }
 
@@ -317,13 +483,13 @@ namespace Mba.Simplifier.Bindings
         {
             fixed (AstIdx* arrPtr = variables)
             {
-                Api.ContextCompile(this, id, mask, arrPtr, (ulong)variables.Length, (ulong*)rwxPagePtr);
+                NativeError.Check(Api.ContextCompile(this, id, mask, arrPtr, (ulong)variables.Length, (ulong*)rwxPagePtr));
             }
         }
 
         public unsafe void Execute(bool isMultibit, uint bitWidth, AstIdx[] variables, ulong numCombinations, nint rwxPagePtr, nint outputArrayPtr, bool isOneBitVars, bool shift)
         {
-            Api.ContextExecute(isMultibit ? 1u : 0, bitWidth, (ulong)variables.Length, numCombinations, (ulong*)rwxPagePtr, (ulong*)outputArrayPtr, isOneBitVars ? 1u : 0, shift ? 1u : 0);
+            NativeError.Check(Api.ContextExecute(isMultibit ? 1u : 0, bitWidth, (ulong)variables.Length, numCombinations, (ulong*)rwxPagePtr, (ulong*)outputArrayPtr, isOneBitVars ? 1u : 0, shift ? 1u : 0));
         }
 
         public static int GetOpcount(AstOp opc)
@@ -339,9 +505,21 @@ namespace Mba.Simplifier.Bindings
                 AstOp.Xor => 2,
                 AstOp.Neg => 1,
                 AstOp.Lshr => 2,
+                AstOp.Shl => 2,
+                AstOp.Ashr => 2,
+                AstOp.Udiv => 2,
+                AstOp.Urem => 2,
+                AstOp.Sdiv => 2,
+                AstOp.Srem => 2,
+                AstOp.Rotl => 2,
+                AstOp.Rotr => 2,
+                AstOp.Popcount => 1,
+                AstOp.Ctlz => 1,
+                AstOp.Cttz => 1,
                 AstOp.Constant => 0,
                 AstOp.Symbol => 0,
                 AstOp.Zext => 2,
+                AstOp.Sext => 2,
                 AstOp.Trunc => 2,
                 AstOp.ICmp => 2,
                 AstOp.Select => 3,
@@ -349,9 +527,9 @@ namespace Mba.Simplifier.Bindings
         }
 
         // Apply term rewriting, but not recursively.
-        public unsafe AstIdx SingleSimplify(AstIdx id) => Api.ContextSingleSimplify(this, id);
+        public unsafe AstIdx SingleSimplify(AstIdx id) => NativeError.Check(Api.ContextSingleSimplify(this, id));
         // Apply recursive term rewriting via ISLE.
-        public unsafe AstIdx RecursiveSimplify(AstIdx id) => Api.ContextRecursiveSimplify(this, id);
+        public unsafe AstIdx RecursiveSimplify(AstIdx id) => NativeError.Check(Api.ContextRecursiveSimplify(this, id));
 
         public unsafe static implicit operator OpaqueAstCtx*(AstCtx ctx) => (OpaqueAstCtx*)ctx.handle;
 
@@ -363,7 +541,29 @@ namespace Mba.Simplifier.Bindings
             public unsafe static extern OpaqueAstCtx* CreateContext();
 
             [DllImport("eq_sat")]
-            public unsafe static extern void ContextClear(OpaqueAstCtx* ctx);
+            public unsafe static extern FfiStatus FreeContext(OpaqueAstCtx* ctx);
+
+            [DllImport("eq_sat")]
+            [return: MarshalAs(UnmanagedType.U1)]
+            public unsafe static extern bool ContextSaveSnapshot(OpaqueAstCtx* ctx, sbyte* path, [MarshalAs(UnmanagedType.U1)] bool includeIsleCache);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern OpaqueAstCtx* ContextLoadSnapshot(sbyte* path);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern OpaqueAstCtx* ContextCreateShared(OpaqueSharedArena* arena);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus ContextClear(OpaqueAstCtx* ctx);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus ContextAddRoot(OpaqueAstCtx* ctx, AstIdx id);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus ContextRemoveRoot(OpaqueAstCtx* ctx, AstIdx id);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx* ContextCollectGarbage(OpaqueAstCtx* ctx, ulong* outLen);
 
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextAdd(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
@@ -389,9 +589,45 @@ namespace Mba.Simplifier.Bindings
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextLshr(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
 
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextShl(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextAshr(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextUdiv(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextUrem(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextSdiv(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextSrem(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextRotl(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextRotr(OpaqueAstCtx* ctx, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextPopcount(OpaqueAstCtx* ctx, AstIdx a);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextCtlz(OpaqueAstCtx* ctx, AstIdx a);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextCttz(OpaqueAstCtx* ctx, AstIdx a);
+
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextZext(OpaqueAstCtx* ctx, AstIdx a, byte width);
 
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextSext(OpaqueAstCtx* ctx, AstIdx a, byte width);
+
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextTrunc(OpaqueAstCtx* ctx, AstIdx a, byte width);
 
@@ -413,6 +649,9 @@ namespace Mba.Simplifier.Bindings
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextConstant(OpaqueAstCtx* ctx, ulong c, byte width);
 
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextConstantWide(OpaqueAstCtx* ctx, ulong low, ulong high, byte width);
+
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextSymbol(OpaqueAstCtx* ctx, sbyte* name, byte width);
 
@@ -441,13 +680,16 @@ namespace Mba.Simplifier.Bindings
             [SuppressGCTransition]
             public unsafe static extern KnownBits ContextGetKnownBits(OpaqueAstCtx* ctx, AstIdx id);
 
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus ContextGetStats(OpaqueAstCtx* ctx, AstIdx id, AstStats* output);
+
             [DllImport("eq_sat")]
             [SuppressGCTransition]
             public unsafe static extern ulong ContextGetImutData(OpaqueAstCtx* ctx, AstIdx id);
 
             [DllImport("eq_sat")]
             [SuppressGCTransition]
-            public unsafe static extern void ContextSetImutData(OpaqueAstCtx* ctx, AstIdx id, ulong data);
+            public unsafe static extern FfiStatus ContextSetImutData(OpaqueAstCtx* ctx, AstIdx id, ulong data);
 
             [DllImport("eq_sat")]
             [SuppressGCTransition]
@@ -469,15 +711,40 @@ namespace Mba.Simplifier.Bindings
             [SuppressGCTransition]
             public unsafe static extern ulong ContextGetConstantValue(OpaqueAstCtx* ctx, AstIdx id);
 
+            [DllImport("eq_sat")]
+            [SuppressGCTransition]
+            public unsafe static extern ulong ContextGetConstantValueHigh(OpaqueAstCtx* ctx, AstIdx id);
+
             [DllImport("eq_sat")]
             public unsafe static extern sbyte* ContextGetSymbolName(OpaqueAstCtx* ctx, AstIdx id);
 
             [DllImport("eq_sat")]
-            public unsafe static extern AstIdx ContextParseAstString(OpaqueAstCtx* ctx, sbyte* astStr);
+            public unsafe static extern AstIdx ContextParseAstString(OpaqueAstCtx* ctx, sbyte* astStr, byte width);
 
             [DllImport("eq_sat")]
             public unsafe static extern sbyte* ContextGetAstString(OpaqueAstCtx* ctx, AstIdx id);
 
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextParseSExpr(OpaqueAstCtx* ctx, sbyte* sexpr, byte width);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* ContextGetSExprString(OpaqueAstCtx* ctx, AstIdx id);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* ContextGetDotString(OpaqueAstCtx* ctx, AstIdx id);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* ContextPrintWithOptions(OpaqueAstCtx* ctx, AstIdx id, PrintOptions* options);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* ContextGetSmtLibString(OpaqueAstCtx* ctx, AstIdx id);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* ContextGetSmtLibEquivalenceQuery(OpaqueAstCtx* ctx, AstIdx before, AstIdx after);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* ContextEmitCode(OpaqueAstCtx* ctx, AstIdx id, AstIdx* variables, ulong varCount, CodegenBackend backend, sbyte* name);
+
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx* ContextCollectVariables(OpaqueAstCtx* ctx, AstIdx id, ulong* outLen);
 
@@ -494,16 +761,16 @@ namespace Mba.Simplifier.Bindings
             public unsafe static extern AstIdx ContextMinimizeAnf(OpaqueAstCtx* ctx, OpaqueTruthTableDb* db, ulong* truthTable, AstIdx* variableArray, uint numVars, ulong* rwxJitPage);
 
             [DllImport("eq_sat")]
-            public unsafe static extern ulong* ContextJit(OpaqueAstCtx* ctx, AstIdx id, ulong mask, uint isMultiBit, uint bitWidth, AstIdx* variableArray, ulong varCount, ulong numCombinations, ulong* rwxJitPage, ulong* outputArray);
+            public unsafe static extern FfiStatus ContextJit(OpaqueAstCtx* ctx, AstIdx id, ulong mask, uint isMultiBit, uint bitWidth, AstIdx* variableArray, ulong varCount, ulong numCombinations, ulong* rwxJitPage, ulong* outputArray);
 
             [DllImport("eq_sat")]
-            public unsafe static extern ulong* ContextCompileLegacy(OpaqueAstCtx* ctx, AstIdx id, ulong mask, AstIdx* variableArray, ulong varCount, ulong* rwxJitPage);
+            public unsafe static extern FfiStatus ContextCompileLegacy(OpaqueAstCtx* ctx, AstIdx id, ulong mask, AstIdx* variableArray, ulong varCount, ulong* rwxJitPage);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void ContextCompile(OpaqueAstCtx* ctx, AstIdx id, ulong mask, AstIdx* variableArray, ulong varCount, ulong* rwxJitPage);
+            public unsafe static extern FfiStatus ContextCompile(OpaqueAstCtx* ctx, AstIdx id, ulong mask, AstIdx* variableArray, ulong varCount, ulong* rwxJitPage);
 
             [DllImport("eq_sat")]
-            public unsafe static extern ulong* ContextExecute(uint isMultiBit, uint bitWidth, ulong varCount, ulong numCombinations, ulong* rwxJitPage, ulong* outputArray, uint isOneBitVars, uint shift);
+            public unsafe static extern FfiStatus ContextExecute(uint isMultiBit, uint bitWidth, ulong varCount, ulong numCombinations, ulong* rwxJitPage, ulong* outputArray, uint isOneBitVars, uint shift);
 
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextSingleSimplify(OpaqueAstCtx* ctx, AstIdx id);
@@ -511,8 +778,32 @@ namespace Mba.Simplifier.Bindings
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx ContextRecursiveSimplify(OpaqueAstCtx* ctx, AstIdx id);
 
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextSubstitute(OpaqueAstCtx* ctx, AstIdx id, AstIdx* from, AstIdx* to, ulong count);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern AstIdx ContextRewrite(OpaqueAstCtx* ctx, AstIdx id, delegate* unmanaged<OpaqueAstCtx*, AstIdx, AstIdx, nint, AstIdx> callback, nint userData);
+
             [DllImport("eq_sat")]
             public unsafe static extern nint GetPowPtr();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern nint GetUdivPtr();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern nint GetUremPtr();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern nint GetSdivPtr();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern nint GetSremPtr();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern nint GetRotlPtr();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern nint GetRotrPtr();
         }
     }
 }
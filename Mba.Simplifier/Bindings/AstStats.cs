@@ -0,0 +1,53 @@
+﻿using System;
+using System.Collections.Generic;
+using System.Linq;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+using Mba.Common.Ast;
+
+namespace Mba.Simplifier.Bindings
+{
+    // Size and shape metrics of an expression, for measuring how much each pass reduced its complexity.
+    [StructLayout(LayoutKind.Sequential)]
+    public unsafe struct AstStats
+    {
+        // Number of distinct nodes reachable from the root.
+        public readonly ulong DagSize;
+
+        // Number of nodes if the DAG were expanded into a tree, saturating at ulong.MaxValue.
+        public readonly ulong TreeSize;
+
+        public readonly uint Depth;
+
+        public readonly uint NumVars;
+
+        // Degree of the expression as a polynomial, treating non-arithmetic subterms as atoms of degree one.
+        public readonly uint PolyDegree;
+
+        // Number of distinct maximal bitwise subterms.
+        public readonly uint NumBitwiseTerms;
+
+        private fixed uint opcodeCounts[30];
+
+        // Indexed by width, up to the maximum width of 128.
+        private fixed uint varWidthCounts[129];
+
+        private readonly byte classification;
+
+        public uint GetOpcodeCount(AstOp opcode) => opcodeCounts[(int)opcode];
+
+        public uint GetVarCount(uint width) => varWidthCounts[(int)width];
+
+        public AstClassification Classification => (AstClassification)classification;
+
+        public override string ToString()
+        {
+            var sb = new StringBuilder();
+            sb.Append($"dag: {DagSize}, tree: {TreeSize}, depth: {Depth}, vars: {NumVars}, degree: {PolyDegree}, bitwise terms: {NumBitwiseTerms}, class: {Classification}");
+            foreach (var opcode in Enum.GetValues<AstOp>().Where(x => GetOpcodeCount(x) != 0))
+                sb.Append($", {opcode}: {GetOpcodeCount(opcode)}");
+            return sb.ToString();
+        }
+    }
+}
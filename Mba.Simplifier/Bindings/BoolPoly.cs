@@ -26,19 +26,19 @@ namespace Mba.Simplifier.Bindings
         public int NumWords => NumCombinations <= 64 ? 1 : (NumCombinations >> 6);
 
         public static unsafe BoolPoly Create(byte numVars, byte bitWidth)
-            => new BoolPoly(numVars, bitWidth, Api.CreateBoolPoly(numVars, bitWidth));
+            => new BoolPoly(numVars, bitWidth, NativeError.Check(Api.CreateBoolPoly(numVars, bitWidth)));
 
         public static unsafe BoolPoly Create(byte numVars, byte bitWidth, ulong[] vector)
         {
             fixed (ulong* ptr = vector)
             {
-                return new BoolPoly(numVars, bitWidth, Api.CreateBoolPolyFromVec(numVars, bitWidth, ptr));
+                return new BoolPoly(numVars, bitWidth, NativeError.Check(Api.CreateBoolPolyFromVec(numVars, bitWidth, ptr)));
             }
         }
 
         public static unsafe BoolPoly CreateFromVariable(byte numVars, byte bitWidth, uint varIdx)
         {
-            return new BoolPoly(numVars, bitWidth, Api.CreateBoolPolyFromVariable(numVars, bitWidth, varIdx));
+            return new BoolPoly(numVars, bitWidth, NativeError.Check(Api.CreateBoolPolyFromVariable(numVars, bitWidth, varIdx)));
         }
 
         private unsafe BoolPoly(byte numVars, byte bitWidth, OpaqueBoolPoly* ptr)
@@ -54,16 +54,16 @@ namespace Mba.Simplifier.Bindings
         }
 
         public unsafe void And(BoolPoly other)
-            => Api.BoolPolyAnd(numVars, this, other);
+            => NativeError.Check(Api.BoolPolyAnd(numVars, this, other));
 
         public unsafe void Or(BoolPoly other)
-            => Api.BoolPolyOr(numVars, this, other);
+            => NativeError.Check(Api.BoolPolyOr(numVars, this, other));
 
         public unsafe void Xor(BoolPoly other)
-            => Api.BoolPolyXor(numVars, this, other);
+            => NativeError.Check(Api.BoolPolyXor(numVars, this, other));
 
         public unsafe void Not()
-            => Api.BoolPolyNot(numVars, this);
+            => NativeError.Check(Api.BoolPolyNot(numVars, this));
 
         public unsafe void Mul(BoolPoly other)
         {
@@ -75,7 +75,7 @@ namespace Mba.Simplifier.Bindings
         }
 
         public unsafe void DivRem(BoolPoly other, BoolPoly div, BoolPoly rem)
-            => Api.BoolPolyDivRem(numVars, this, other, div, rem);
+            => NativeError.Check(Api.BoolPolyDivRem(numVars, this, other, div, rem));
 
         public unsafe bool IsEqual(BoolPoly other)
             => Api.BoolPolyEquals(numVars, this, other) != 0;
@@ -91,13 +91,13 @@ namespace Mba.Simplifier.Bindings
 
         public unsafe Span<ulong> GetRow(int idx)
         {
-            var ptr = Api.BoolPolyGetRow(numVars, this, (uint)idx);
+            var ptr = NativeError.Check(Api.BoolPolyGetRow(numVars, this, (uint)idx));
             return new Span<ulong>(ptr, NumWords);
         }
 
         public unsafe BooleanTruthTable GetRowTable(int idx)
         {
-            var ptr = Api.BoolPolyGetRow(numVars, this, (uint)idx);
+            var ptr = NativeError.Check(Api.BoolPolyGetRow(numVars, this, (uint)idx));
             var arr = new Span<ulong>(ptr, NumWords).ToArray();
             var table = new BooleanTruthTable(numVars);
             table.Arr = arr;
@@ -110,7 +110,10 @@ namespace Mba.Simplifier.Bindings
             ulong constantOffset = 0;
             ulong coefficient = 0;
             if (Api.BoolPolyTryReduceTo1Bit(numVars, this, &pTable, &constantOffset, &coefficient) == 0)
+            {
+                NativeError.ThrowIfFailed();
                 return null;
+            }
 
             var table = new BoolPoly(numVars, 1, pTable);
             return (table, constantOffset, coefficient);
@@ -120,7 +123,7 @@ namespace Mba.Simplifier.Bindings
         {
             fixed(int* ptr = &arr[0])
             {
-                Api.BoolPolyGetVariableCounts(numVars, this, ptr);
+                NativeError.Check(Api.BoolPolyGetVariableCounts(numVars, this, ptr));
             }
         }
 
@@ -128,27 +131,27 @@ namespace Mba.Simplifier.Bindings
         // with that variable removed from the monomial basis.
         public unsafe BoolPoly FilterByVar(uint varIndex, bool contains)
         {
-            var ptr = Api.BoolPolyFilterByVar(numVars, this, varIndex, contains ? (byte)1 : (byte)0);
+            var ptr = NativeError.Check(Api.BoolPolyFilterByVar(numVars, this, varIndex, contains ? (byte)1 : (byte)0));
             return new BoolPoly((byte)(numVars), bitWidth, ptr);
         }
 
         // Eliminate all monomials containing the selected variable
         public unsafe void EliminateVar(uint varIndex)
-            => Api.BoolPolyEliminateVar(numVars, this, varIndex);
+            => NativeError.Check(Api.BoolPolyEliminateVar(numVars, this, varIndex));
 
         // Multiply all monomials by the selected variable
         public unsafe void MultiplyByVar(uint varIndex)
-            => Api.BoolPolyMultiplyByVar(numVars, this, varIndex);
+            => NativeError.Check(Api.BoolPolyMultiplyByVar(numVars, this, varIndex));
 
         // Return a new polynomial not containing the selected vars
         public unsafe BoolPoly RemoveVars(uint varMask)
-            => new BoolPoly((byte)(numVars - (byte)BitOperations.PopCount(varMask)), bitWidth, Api.BoolPolyRemoveVars(numVars, this, varMask));
+            => new BoolPoly((byte)(numVars - (byte)BitOperations.PopCount(varMask)), bitWidth, NativeError.Check(Api.BoolPolyRemoveVars(numVars, this, varMask)));
 
         public unsafe (uint negMask, ulong constantOffset) Sift()
         {
             uint negMask = 0;
             ulong constantOffset = 0;
-            Api.BoolPolySift(numVars, this, &negMask, &constantOffset);
+            NativeError.Check(Api.BoolPolySift(numVars, this, &negMask, &constantOffset));
             return (negMask, constantOffset);
         }
 
@@ -210,10 +213,10 @@ namespace Mba.Simplifier.Bindings
         // Convert from disjunctive normal form to algebraic normal form
         // Works both ways
         public unsafe void ChangeBasis()
-            => Api.BoolPolyChangeBasis(numVars, this);
+            => NativeError.Check(Api.BoolPolyChangeBasis(numVars, this));
 
         public unsafe BoolPoly Clone()
-            => new BoolPoly(numVars, bitWidth, Api.BoolPolyClone(numVars, this));
+            => new BoolPoly(numVars, bitWidth, NativeError.Check(Api.BoolPolyClone(numVars, this)));
 
         public override int GetHashCode()
             => (int)(uint)GetHash();
@@ -238,22 +241,22 @@ namespace Mba.Simplifier.Bindings
             public unsafe static extern OpaqueBoolPoly* CreateBoolPolyFromVariable(byte numVars, byte bitWidth, uint varIdx);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void FreeBoolPoly(byte numVars, OpaqueBoolPoly* a);
+            public unsafe static extern FfiStatus FreeBoolPoly(byte numVars, OpaqueBoolPoly* a);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyAnd(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b);
+            public unsafe static extern FfiStatus BoolPolyAnd(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyOr(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b);
+            public unsafe static extern FfiStatus BoolPolyOr(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyXor(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b);
+            public unsafe static extern FfiStatus BoolPolyXor(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyNot(byte numVars, OpaqueBoolPoly* a);
+            public unsafe static extern FfiStatus BoolPolyNot(byte numVars, OpaqueBoolPoly* a);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyDivRem(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b, OpaqueBoolPoly* pDiv, OpaqueBoolPoly* pRem);
+            public unsafe static extern FfiStatus BoolPolyDivRem(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b, OpaqueBoolPoly* pDiv, OpaqueBoolPoly* pRem);
 
             [DllImport("eq_sat")]
             public unsafe static extern void BoolPolyWeakDivRem(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly* b, OpaqueBoolPoly** pDiv, OpaqueBoolPoly** pRem);
@@ -277,19 +280,19 @@ namespace Mba.Simplifier.Bindings
             public unsafe static extern byte BoolPolyTryReduceTo1Bit(byte numVars, OpaqueBoolPoly* a, OpaqueBoolPoly** table, ulong* constantOffset, ulong* coefficient);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyChangeBasis(byte numVars, OpaqueBoolPoly* a);
+            public unsafe static extern FfiStatus BoolPolyChangeBasis(byte numVars, OpaqueBoolPoly* a);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolyGetVariableCounts(byte numVars, OpaqueBoolPoly* a, int* vecPtr);
+            public unsafe static extern FfiStatus BoolPolyGetVariableCounts(byte numVars, OpaqueBoolPoly* a, int* vecPtr);
 
             [DllImport("eq_sat")]
             public unsafe static extern OpaqueBoolPoly* BoolPolyFilterByVar(byte numVars, OpaqueBoolPoly* a, uint varIndex, byte contains);
 
             [DllImport("eq_sat")]
-            public unsafe static extern OpaqueBoolPoly* BoolPolyEliminateVar(byte numVars, OpaqueBoolPoly* a, uint varIndex);
+            public unsafe static extern FfiStatus BoolPolyEliminateVar(byte numVars, OpaqueBoolPoly* a, uint varIndex);
 
             [DllImport("eq_sat")]
-            public unsafe static extern OpaqueBoolPoly* BoolPolyMultiplyByVar(byte numVars, OpaqueBoolPoly* a, uint varIndex);
+            public unsafe static extern FfiStatus BoolPolyMultiplyByVar(byte numVars, OpaqueBoolPoly* a, uint varIndex);
 
             [DllImport("eq_sat")]
             public unsafe static extern OpaqueBoolPoly* BoolPolyRemoveVars(byte numVars, OpaqueBoolPoly* a, uint varMask);
@@ -298,7 +301,7 @@ namespace Mba.Simplifier.Bindings
             public unsafe static extern byte BoolPolyEliminateDeadVars(byte numVars, OpaqueBoolPoly* a, uint* varMask, OpaqueBoolPoly** output);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void BoolPolySift(byte numVars, OpaqueBoolPoly* a, uint* pNegatedMask, ulong* negatedConstant);
+            public unsafe static extern FfiStatus BoolPolySift(byte numVars, OpaqueBoolPoly* a, uint* pNegatedMask, ulong* negatedConstant);
 
             [DllImport("eq_sat")]
             public unsafe static extern OpaqueBoolPoly* BoolPolyClone(byte numVars, OpaqueBoolPoly* a);
@@ -1,4 +1,5 @@
-﻿using System;
+﻿using Mba.Interop;
+using System;
 using System.Collections.Generic;
 using System.Linq;
 using System.Runtime.InteropServices;
@@ -9,7 +10,7 @@ namespace Mba.Simplifier.Bindings
 {
     public struct OpaqueEGraph { }
 
-    public class EGraph
+    public class EGraph : IDisposable
     {
         private readonly nint handle;
 
@@ -20,48 +21,33 @@ namespace Mba.Simplifier.Bindings
 
         public unsafe EGraph()
         {
-            handle = (nint)Api.CreateEGraph();
+            handle = (nint)NativeError.Check(Api.CreateEGraph());
         }
 
+        // Wrappers created from a borrowed handle must not be disposed.
+        public unsafe void Dispose() => NativeError.Check(Api.FreeEGraph(this));
+
         public unsafe AstIdx AddFromContext(AstCtx ctx, AstIdx idx)
-            => Api.EGraphAddFromContext(this, ctx, idx);
+            => NativeError.Check(Api.EGraphAddFromContext(this, ctx, idx));
 
         public unsafe void Run(ulong msLimit, ulong iterLimit)
-            => Api.EGraphRun(this, msLimit, iterLimit);
+            => NativeError.Check(Api.EGraphRun(this, msLimit, iterLimit));
 
         public unsafe IReadOnlyList<AstIdx> GetClasses()
         {
             ulong len = 0;
             var ptr = Api.EGraphGetClasses(this, &len);
-            var vec = new List<AstIdx>();
-            for(int i = 0; i < (int)len; i++)
-            {
-                // Get a ptr to the elem at this idx.
-                var pItem = &ptr[i];
-
-                // Push the variable.
-                var value = *pItem;
-                vec.Add(value);
-            }
-
-            return vec;
+            return NativeBuffers.TakeArray(ptr, len);
         }
 
         public unsafe IReadOnlyList<(AstIdx eclass, AstIdx extracted)> ExtractAll(AstCtx ctx)
         {
             ulong len = 0;
             var ptr = Api.EGraphExtractAll(this, ctx, &len);
+            var pairs = NativeBuffers.TakeArray(ptr, len);
             var vec = new List<(AstIdx eclass, AstIdx extracted)>();
-            for (int i = 0; i < (int)len; i += 2)
-            {
-                // Get a ptr to the elem at this idx.
-                var pItem = &ptr[i];
-
-                // Push the variable.
-                var eclass = *pItem;
-                var extracted = pItem[1];
-                vec.Add((eclass, extracted));
-            }
+            for (int i = 0; i < pairs.Length; i += 2)
+                vec.Add((pairs[i], pairs[i + 1]));
 
             return vec;
         }
@@ -70,27 +56,20 @@ namespace Mba.Simplifier.Bindings
         {
             ulong len = 0;
             var ptr = Api.EGraphGetClassNodes(this, idx, ctx, &len);
-            var vec = new List<AstIdx>();
-            for (int i = 0; i < (int)len; i += 1)
-            {
-                // Get a ptr to the elem at this idx.
-                var pItem = &ptr[i];
-
-                // Push the variable.
-                var nodeIdx = *pItem;
-                vec.Add(nodeIdx);
-            }
-
-            return vec;
+            return NativeBuffers.TakeArray(ptr, len);
         }
 
         public unsafe AstIdx Extract(AstCtx ctx, AstIdx eclass)
-            => Api.EGraphExtract(this, ctx, eclass);
+            => NativeError.Check(Api.EGraphExtract(this, ctx, eclass));
 
         public unsafe void Union(AstIdx a, AstIdx b)
-            => Api.EGraphUnion(this, a, b);
+            => NativeError.Check(Api.EGraphUnion(this, a, b));
+
+        public unsafe void Rebuild() => NativeError.Check(Api.EGraphRebuild(this));
 
-        public unsafe void Rebuild() => Api.EGraphRebuild(this);
+        // Get a Graphviz rendering of every e-class, highlighting the term extracted for `root`.
+        public unsafe string GetDotString(AstCtx ctx, AstIdx root)
+            => NativeBuffers.TakeString(Api.EGraphGetDotString(this, ctx, root));
 
         public unsafe static implicit operator OpaqueEGraph*(EGraph egraph) => (OpaqueEGraph*)egraph.handle;
 
@@ -101,11 +80,14 @@ namespace Mba.Simplifier.Bindings
             [DllImport("eq_sat")]
             public unsafe static extern OpaqueEGraph* CreateEGraph();
 
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeEGraph(OpaqueEGraph* egraph);
+
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx EGraphAddFromContext(OpaqueEGraph* egraph, OpaqueAstCtx* ctx, AstIdx idx);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void EGraphRun(OpaqueEGraph* egraph, ulong msLimit, ulong iterLimit);
+            public unsafe static extern FfiStatus EGraphRun(OpaqueEGraph* egraph, ulong msLimit, ulong iterLimit);
 
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx* EGraphGetClasses(OpaqueEGraph* egraph, ulong* outLen);
@@ -120,10 +102,13 @@ namespace Mba.Simplifier.Bindings
             public unsafe static extern AstIdx* EGraphGetClassNodes(OpaqueEGraph* egraph, AstIdx eclass, OpaqueAstCtx* storage, ulong* outLen);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void EGraphUnion(OpaqueEGraph* egraph, AstIdx a, AstIdx b);
+            public unsafe static extern FfiStatus EGraphUnion(OpaqueEGraph* egraph, AstIdx a, AstIdx b);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus EGraphRebuild(OpaqueEGraph* egraph);
 
             [DllImport("eq_sat")]
-            public unsafe static extern void EGraphRebuild(OpaqueEGraph* egraph);
+            public unsafe static extern sbyte* EGraphGetDotString(OpaqueEGraph* egraph, OpaqueAstCtx* ctx, AstIdx root);
         }
     }
 }
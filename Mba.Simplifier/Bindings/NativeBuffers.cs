@@ -0,0 +1,65 @@
+﻿using System;
+using System.Collections.Generic;
+using System.Linq;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+
+namespace Mba.Simplifier.Bindings
+{
+    // Arrays and strings returned by eq_sat are owned by the caller. These helpers copy them into managed memory,
+    // and then release the native allocation.
+    public static class NativeBuffers
+    {
+        public static unsafe string TakeString(sbyte* ptr)
+        {
+            NativeError.Check(ptr);
+            try
+            {
+                return Marshal.PtrToStringUTF8((nint)ptr)!;
+            }
+            finally
+            {
+                NativeError.Check(Api.FreeString(ptr));
+            }
+        }
+
+        public static unsafe AstIdx[] TakeArray(AstIdx* ptr, ulong len)
+        {
+            NativeError.Check(ptr);
+            try
+            {
+                return new ReadOnlySpan<AstIdx>(ptr, (int)len).ToArray();
+            }
+            finally
+            {
+                NativeError.Check(Api.FreeAstIdxArray(ptr, len));
+            }
+        }
+
+        public static unsafe ulong[] TakeArray(ulong* ptr, ulong len)
+        {
+            NativeError.Check(ptr);
+            try
+            {
+                return new ReadOnlySpan<ulong>(ptr, (int)len).ToArray();
+            }
+            finally
+            {
+                NativeError.Check(Api.FreeU64Array(ptr, len));
+            }
+        }
+
+        private static class Api
+        {
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeString(sbyte* s);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeAstIdxArray(AstIdx* ptr, ulong len);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeU64Array(ulong* ptr, ulong len);
+        }
+    }
+}
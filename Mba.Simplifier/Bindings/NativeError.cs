@@ -0,0 +1,78 @@
+﻿using System;
+using System.Collections.Generic;
+using System.Linq;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+
+namespace Mba.Simplifier.Bindings
+{
+    // Mirrors `FfiStatus` in ffi.rs.
+    public enum FfiStatus : uint
+    {
+        Ok = 0,
+        NullPointer = 1,
+        InvalidHandle = 2,
+        InvalidArgument = 3,
+        ParseError = 4,
+        Io = 5,
+        Panic = 6,
+    }
+
+    public class EqSatException : Exception
+    {
+        public readonly FfiStatus Status;
+
+        public EqSatException(FfiStatus status, string message) : base($"{status}: {message}")
+        {
+            Status = status;
+        }
+    }
+
+    // A failed call into eq_sat returns a sentinel (null, an invalid AstIdx, or a non-ok status),
+    // with the reason being available until the next call on the same thread.
+    public static class NativeError
+    {
+        public static void Check(FfiStatus status)
+        {
+            if (status != FfiStatus.Ok)
+                throw GetLastError();
+        }
+
+        public static AstIdx Check(AstIdx idx)
+        {
+            if (idx.Idx == uint.MaxValue)
+                throw GetLastError();
+            return idx;
+        }
+
+        public static unsafe T* Check<T>(T* ptr) where T : unmanaged
+        {
+            if (ptr == null)
+                throw GetLastError();
+            return ptr;
+        }
+
+        // For results without a sentinel, where the status must be queried explicitly.
+        public static void ThrowIfFailed()
+        {
+            if (Api.GetLastStatus() != FfiStatus.Ok)
+                throw GetLastError();
+        }
+
+        public static unsafe EqSatException GetLastError()
+        {
+            var message = Marshal.PtrToStringUTF8((nint)Api.GetLastErrorMessage());
+            return new EqSatException(Api.GetLastStatus(), message ?? "");
+        }
+
+        private static class Api
+        {
+            [DllImport("eq_sat")]
+            public static extern FfiStatus GetLastStatus();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern sbyte* GetLastErrorMessage();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+﻿using System;
+using System.Collections.Generic;
+using System.Linq;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+
+namespace Mba.Simplifier.Bindings
+{
+    public enum ConstantStyle : byte
+    {
+        // Two's complement decimal, e.g. `-1`.
+        Signed = 0,
+        Unsigned = 1,
+        Hex = 2,
+    }
+
+    public enum WidthAnnotations : byte
+    {
+        Never = 0,
+        // Annotate the leaves whose width differs from `DefaultWidth`.
+        NonDefault = 1,
+        Always = 2,
+    }
+
+    // Options for `AstCtx.PrettyPrint`.
+    [StructLayout(LayoutKind.Sequential)]
+    public struct PrintOptions
+    {
+        public ConstantStyle ConstantStyle;
+
+        public WidthAnnotations WidthAnnotations;
+
+        public byte DefaultWidth;
+
+        // Name the nodes that are used more than once rather than printing them at every use.
+        public byte LetBindings;
+
+        public static PrintOptions Default => new PrintOptions()
+        {
+            ConstantStyle = ConstantStyle.Signed,
+            WidthAnnotations = WidthAnnotations.NonDefault,
+            DefaultWidth = 64,
+            LetBindings = 0,
+        };
+    }
+}
@@ -0,0 +1,39 @@
+﻿using System;
+using System.Collections.Generic;
+using System.Linq;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+
+namespace Mba.Simplifier.Bindings
+{
+    public struct OpaqueSharedArena { }
+
+    // Thread-safe node storage that can be shared by many contexts, e.g. to simplify many expressions in parallel.
+    // Each thread should create its own AstCtx over the arena.
+    public class SharedArena
+    {
+        private readonly nint handle;
+
+        public unsafe SharedArena()
+        {
+            handle = (nint)NativeError.Check(Api.CreateSharedArena());
+        }
+
+        unsafe ~SharedArena()
+        {
+            Api.FreeSharedArena(this);
+        }
+
+        public unsafe static implicit operator OpaqueSharedArena*(SharedArena arena) => (OpaqueSharedArena*)arena.handle;
+
+        protected static class Api
+        {
+            [DllImport("eq_sat")]
+            public unsafe static extern OpaqueSharedArena* CreateSharedArena();
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeSharedArena(OpaqueSharedArena* arena);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+﻿using System;
+using System.Collections.Generic;
+using System.Linq;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading.Tasks;
+using static Mba.Simplifier.Bindings.AstCtx;
+
+namespace Mba.Simplifier.Bindings
+{
+    public struct OpaqueTape { }
+
+    // An AST compiled into a linear instruction tape, for evaluating the same expression on many input vectors.
+    public class Tape
+    {
+        private readonly nint handle;
+
+        public readonly int numVars;
+
+        public unsafe Tape(AstCtx ctx, AstIdx id, List<AstIdx> vars)
+        {
+            var span = CollectionsMarshal.AsSpan(vars);
+            fixed (AstIdx* arrPtr = span)
+            {
+                handle = (nint)NativeError.Check(Api.ContextCompileTape(ctx, id, arrPtr, (ulong)vars.Count));
+            }
+
+            numVars = vars.Count;
+        }
+
+        unsafe ~Tape()
+        {
+            Api.FreeTape(this);
+        }
+
+        // The inputs are laid out column by column, i.e. `inputs[i * numPoints + j]` holds the value of the ith variable for the jth input vector.
+        public unsafe ulong[] Evaluate(ulong[] inputs, int numPoints)
+        {
+            if (inputs.Length != numVars * numPoints)
+                throw new ArgumentException($"Expected {numVars * numPoints} inputs, got {inputs.Length}");
+
+            var output = new ulong[numPoints];
+            fixed (ulong* inputPtr = inputs)
+            {
+                fixed (ulong* outputPtr = output)
+                {
+                    NativeError.Check(Api.TapeEvaluate(this, inputPtr, (ulong)numPoints, outputPtr));
+                }
+            }
+
+            return output;
+        }
+
+        public unsafe static implicit operator OpaqueTape*(Tape tape) => (OpaqueTape*)tape.handle;
+
+        protected static class Api
+        {
+            [DllImport("eq_sat")]
+            public unsafe static extern OpaqueTape* ContextCompileTape(OpaqueAstCtx* ctx, AstIdx id, AstIdx* variableArray, ulong varCount);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus TapeEvaluate(OpaqueTape* tape, ulong* inputs, ulong numPoints, ulong* outputArray);
+
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeTape(OpaqueTape* tape);
+        }
+    }
+}
@@ -10,7 +10,7 @@ namespace Mba.Simplifier.Bindings
 {
     public struct OpaqueTruthTableDb { }
 
-    public class TruthTableDb
+    public class TruthTableDb : IDisposable
     {
         private nint handle;
 
@@ -21,15 +21,17 @@ namespace Mba.Simplifier.Bindings
 
         public unsafe TruthTableDb()
         {
-            handle = (nint)Api.CreateTruthTableDb();
+            handle = (nint)NativeError.Check(Api.CreateTruthTableDb());
         }
 
+        public unsafe void Dispose() => NativeError.Check(Api.FreeTruthTableDb(this));
+
         public unsafe AstIdx GetBoolean(AstCtx ctx, uint varCount, List<AstIdx> vars, ulong idx)
         {
             var span = CollectionsMarshal.AsSpan(vars);
             fixed (AstIdx* arrPtr = &span[0])
             {
-                return Api.GetTruthTableDbEntry(this, ctx, varCount, arrPtr, idx);
+                return NativeError.Check(Api.GetTruthTableDbEntry(this, ctx, varCount, arrPtr, idx));
             }
         }
 
@@ -47,6 +49,9 @@ namespace Mba.Simplifier.Bindings
             [DllImport("eq_sat")]
             public unsafe static extern OpaqueTruthTableDb* CreateTruthTableDb();
 
+            [DllImport("eq_sat")]
+            public unsafe static extern FfiStatus FreeTruthTableDb(OpaqueTruthTableDb* db);
+
             [DllImport("eq_sat")]
             public unsafe static extern AstIdx GetTruthTableDbEntry(OpaqueTruthTableDb* db, OpaqueAstCtx* ctx, uint varCount, AstIdx* variableArray, ulong idx);
 
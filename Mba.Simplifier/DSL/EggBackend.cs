@@ -175,7 +175,9 @@ namespace Mba.Simplifier.DSL
                     TranspileRhs(widthField, sb, cache, widthField, outArgs);
 
                 var literalName = $"literal_{constNode.UValue}_id";
-                sb.AppendLine($"let {literalName} = egraph.add(SimpleAst::Constant {{c: {constNode.UValue}, width: bounded_width }});");
+                // Constants are sign extended, so that e.g. -1 is all ones at any width.
+                var value = (UInt128)(Int128)(long)constNode.UValue;
+                sb.AppendLine($"let {literalName} = egraph.add(SimpleAst::Constant {{c: {value}, width: bounded_width }});");
                 outArgs.Add(widthField);
                 cache[ast] = literalName;
                 return;
@@ -146,7 +146,7 @@ namespace Mba.Simplifier.DSL
             if (ast is ConstNode constNode)
             {
                 var width_field = boundedWidths.Any() ? boundedWidths.First() : $"(GetWidth {boundedIndices.First()})";
-                sb.Append($"{(ulong)constNode.Value} {width_field}");
+                sb.Append($"{(long)constNode.Value} {width_field}");
             }
 
             else if (ast is VarNode varNode)
@@ -399,8 +399,8 @@ namespace Mba.Simplifier.DSL
         {
             var variants = new Dictionary<int, string[]>
             {
-                [1] = new[] { "Neg" },
-                [2] = new[] { "Add", "Mul", "Pow", "And", "Or", "Xor", "Lshr", "Zext", "Trunc", "Concat" },
+                [1] = new[] { "Neg", "Popcount", "Ctlz", "Cttz" },
+                [2] = new[] { "Add", "Mul", "Pow", "And", "Or", "Xor", "Lshr", "Shl", "Ashr", "Udiv", "Urem", "Sdiv", "Srem", "Rotl", "Rotr", "Zext", "Sext", "Trunc", "Concat" },
                 [3] = new[] { "Extract", "Carry" },
             };
 
@@ -59,6 +59,9 @@ namespace Mba.Simplifier.Jit
             Diff(nameof(IAmd64Assembler.NotReg), reg1);
             Diff(nameof(IAmd64Assembler.ShlRegCl), reg1);
             Diff(nameof(IAmd64Assembler.ShrRegCl), reg1);
+            Diff(nameof(IAmd64Assembler.SarRegCl), reg1);
+            Diff(nameof(IAmd64Assembler.RolRegCl), reg1);
+            Diff(nameof(IAmd64Assembler.RorRegCl), reg1);
             Diff(nameof(IAmd64Assembler.SeteReg), reg1);
             Diff(nameof(IAmd64Assembler.SetneReg), reg1);
             Diff(nameof(IAmd64Assembler.SetaReg), reg1);
@@ -75,7 +78,9 @@ namespace Mba.Simplifier.Jit
                 Diff(nameof(IAmd64Assembler.AddRegImm32), reg1, (uint)c);
                 Diff(nameof(IAmd64Assembler.SubRegImm32), reg1, (uint)c);
                 Diff(nameof(IAmd64Assembler.AndRegImm32), reg1, (uint)c);
+                Diff(nameof(IAmd64Assembler.ShlRegImm8), reg1, (byte)c);
                 Diff(nameof(IAmd64Assembler.ShrRegImm8), reg1, (byte)c);
+                Diff(nameof(IAmd64Assembler.SarRegImm8), reg1, (byte)c);
                 if (reg1 != rsp)
                     Diff(nameof(IAmd64Assembler.PushMem64), reg1, (int)c);
             }
@@ -96,6 +101,12 @@ namespace Mba.Simplifier.Jit
             Diff(nameof(IAmd64Assembler.XorRegReg), reg2, reg1);
             Diff(nameof(IAmd64Assembler.ImulRegReg), reg1, reg2);
             Diff(nameof(IAmd64Assembler.ImulRegReg), reg2, reg1);
+            Diff(nameof(IAmd64Assembler.PopcntRegReg), reg1, reg2);
+            Diff(nameof(IAmd64Assembler.PopcntRegReg), reg2, reg1);
+            Diff(nameof(IAmd64Assembler.LzcntRegReg), reg1, reg2);
+            Diff(nameof(IAmd64Assembler.LzcntRegReg), reg2, reg1);
+            Diff(nameof(IAmd64Assembler.TzcntRegReg), reg1, reg2);
+            Diff(nameof(IAmd64Assembler.TzcntRegReg), reg2, reg1);
             Diff(nameof(IAmd64Assembler.CmpRegReg), reg1, reg2);
             Diff(nameof(IAmd64Assembler.CmpRegReg), reg2, reg1);
             Diff(nameof(IAmd64Assembler.TestRegReg), reg1, reg2);
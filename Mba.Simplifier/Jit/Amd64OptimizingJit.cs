@@ -257,6 +257,14 @@ namespace Mba.Simplifier.Interpreter
                 case AstOp.Or:
                 case AstOp.Xor:
                 case AstOp.Lshr:
+                case AstOp.Shl:
+                case AstOp.Ashr:
+                case AstOp.Udiv:
+                case AstOp.Urem:
+                case AstOp.Sdiv:
+                case AstOp.Srem:
+                case AstOp.Rotl:
+                case AstOp.Rotr:
                 case AstOp.ICmp:
                     {
                         var op0 = ctx.GetOp0(idx);
@@ -284,7 +292,11 @@ namespace Mba.Simplifier.Interpreter
                     break;
                 case AstOp.Neg:
                 case AstOp.Zext:
+                case AstOp.Sext:
                 case AstOp.Trunc:
+                case AstOp.Popcount:
+                case AstOp.Ctlz:
+                case AstOp.Cttz:
                     var single = ctx.GetOp0(idx);
                     CollectInfo(ctx, single, dfs, seen);
                     seen.Set(single, new NodeInfo(Inc(seen.Get(single).numUses)));
@@ -340,6 +352,14 @@ namespace Mba.Simplifier.Interpreter
                     case AstOp.Xor:
                     case AstOp.Pow:
                     case AstOp.Lshr:
+                    case AstOp.Shl:
+                    case AstOp.Ashr:
+                    case AstOp.Udiv:
+                    case AstOp.Urem:
+                    case AstOp.Sdiv:
+                    case AstOp.Srem:
+                    case AstOp.Rotl:
+                    case AstOp.Rotr:
                     case AstOp.ICmp:
                     case AstOp.Select:
                         LowerBinop(idx, opc, width, nodeInfo);
@@ -354,7 +374,11 @@ namespace Mba.Simplifier.Interpreter
                         break;
 
                     case AstOp.Neg:
+                    case AstOp.Sext:
                     case AstOp.Trunc:
+                    case AstOp.Popcount:
+                    case AstOp.Ctlz:
+                    case AstOp.Cttz:
                         LowerUnaryOp(idx, opc, width, nodeInfo);
                         break;
 
@@ -473,26 +497,68 @@ namespace Mba.Simplifier.Interpreter
                     assembler.PopReg(Register.RCX);
                     break;
 
-                case AstOp.Pow:
-                    foreach (var r in volatileRegs)
-                        assembler.PushReg(r);
+                case AstOp.Shl:
+                    // The hardware reduces the count modulo 64. Counts in [width, 64) already
+                    // shift every bit out of the low `width` bits, so only counts >= 64 are wrong.
+                    // TODO: Shift beyond 64 should yield zero
+                    assembler.PushReg(Register.RCX);
+                    assembler.MovRegReg(Register.RCX, rhsDest);
+                    assembler.ShlRegCl(lhsDest);
+                    assembler.PopReg(Register.RCX);
+                    break;
 
-                    assembler.MovRegReg(rcx, lhsDest);
-                    assembler.MovRegReg(rdx, rhsDest);
+                case AstOp.Ashr:
+                    // Sign extend the lhs to 64 bits, so that the sign bit is shifted in from above.
+                    if (width != 64)
+                    {
+                        assembler.ShlRegImm8(lhsDest, (byte)(64 - width));
+                        assembler.SarRegImm8(lhsDest, (byte)(64 - width));
+                    }
 
+                    // TODO: Shift beyond 64 should yield the sign bit
+                    assembler.PushReg(Register.RCX);
+                    assembler.MovRegReg(Register.RCX, rhsDest);
+                    assembler.SarRegCl(lhsDest);
+                    assembler.PopReg(Register.RCX);
+                    break;
+
+                case AstOp.Pow:
                     // TODO: Inline `pow` function
-                    assembler.MovabsRegImm64(r11, (ulong)AstCtx.Api.GetPowPtr());
-                    assembler.SubRegImm32(rsp, 32);
-                    assembler.CallReg(r11);
-                    assembler.AddRegImm32(rsp, 32);
-                    assembler.MovRegReg(scratch1, rax);
+                    EmitHelperCall((ulong)AstCtx.Api.GetPowPtr(), lhsDest, rhsDest, width);
+                    break;
 
-                    // Restore nonvolatile regs.
-                    for (int regIdx = volatileRegs.Length - 1; regIdx >= 0; regIdx--)
-                        assembler.PopReg(volatileRegs[regIdx]);
+                // Division is lowered to calls, because the semantics of division by zero
+                // (and of MIN / -1) do not match the hardware's.
+                case AstOp.Udiv:
+                    EmitHelperCall((ulong)AstCtx.Api.GetUdivPtr(), lhsDest, rhsDest, width);
+                    break;
+                case AstOp.Urem:
+                    EmitHelperCall((ulong)AstCtx.Api.GetUremPtr(), lhsDest, rhsDest, width);
+                    break;
+                case AstOp.Sdiv:
+                    EmitHelperCall((ulong)AstCtx.Api.GetSdivPtr(), lhsDest, rhsDest, width);
+                    break;
+                case AstOp.Srem:
+                    EmitHelperCall((ulong)AstCtx.Api.GetSremPtr(), lhsDest, rhsDest, width);
+                    break;
 
-                    assembler.MovRegReg(lhsDest, scratch1);
+                // The hardware reduces the count modulo 64, so only 64 bit rotates can be lowered directly.
+                case AstOp.Rotl:
+                case AstOp.Rotr:
+                    if (width != 64)
+                    {
+                        var func = opc == AstOp.Rotl ? AstCtx.Api.GetRotlPtr() : AstCtx.Api.GetRotrPtr();
+                        EmitHelperCall((ulong)func, lhsDest, rhsDest, width);
+                        break;
+                    }
 
+                    assembler.PushReg(Register.RCX);
+                    assembler.MovRegReg(Register.RCX, rhsDest);
+                    if (opc == AstOp.Rotl)
+                        assembler.RolRegCl(lhsDest);
+                    else
+                        assembler.RorRegCl(lhsDest);
+                    assembler.PopReg(Register.RCX);
                     break;
                 case AstOp.ICmp:
                     assembler.CmpRegReg(lhsDest, rhsDest);
@@ -552,6 +618,29 @@ namespace Mba.Simplifier.Interpreter
                 freeRegisters.Push(lhsLoc.Register);
         }
 
+        // Call a `fn(lhs, rhs, width) -> u64` helper, storing the result in `lhsDest`.
+        private void EmitHelperCall(ulong func, Register lhsDest, Register rhsDest, uint width)
+        {
+            foreach (var r in volatileRegs)
+                assembler.PushReg(r);
+
+            assembler.MovRegReg(rcx, lhsDest);
+            assembler.MovRegReg(rdx, rhsDest);
+            assembler.MovabsRegImm64(r8, width);
+
+            assembler.MovabsRegImm64(r11, func);
+            assembler.SubRegImm32(rsp, 32);
+            assembler.CallReg(r11);
+            assembler.AddRegImm32(rsp, 32);
+            assembler.MovRegReg(scratch1, rax);
+
+            // Restore nonvolatile regs.
+            for (int regIdx = volatileRegs.Length - 1; regIdx >= 0; regIdx--)
+                assembler.PopReg(volatileRegs[regIdx]);
+
+            assembler.MovRegReg(lhsDest, scratch1);
+        }
+
         private void LowerConstant(AstIdx idx)
         {
             var c = ctx.GetConstantValue(idx);
@@ -601,6 +690,46 @@ namespace Mba.Simplifier.Interpreter
                 ReduceRegisterModulo(width, destReg, scratch2);
             }
 
+            else if (opc == AstOp.Sext)
+            {
+                // Move the sign bit into bit 63, then shift it back down arithmetically.
+                // This also discards any garbage above the source width.
+                var fromWidth = ctx.GetWidth(ctx.GetOp0(idx));
+                if (fromWidth != 64)
+                {
+                    assembler.ShlRegImm8(destReg, (byte)(64 - fromWidth));
+                    assembler.SarRegImm8(destReg, (byte)(64 - fromWidth));
+                }
+                ReduceRegisterModulo(width, destReg, scratch2);
+            }
+
+            // The 64-bit instructions are used for every width, so the input must be reduced first.
+            // The result never exceeds the width, so it needs no reduction.
+            else if (opc == AstOp.Popcount)
+            {
+                ReduceRegisterModulo(width, destReg, scratch2);
+                assembler.PopcntRegReg(destReg, destReg);
+            }
+
+            else if (opc == AstOp.Ctlz)
+            {
+                ReduceRegisterModulo(width, destReg, scratch2);
+                assembler.LzcntRegReg(destReg, destReg);
+                if (width != 64)
+                    assembler.SubRegImm32(destReg, 64 - width);
+            }
+
+            else if (opc == AstOp.Cttz)
+            {
+                // Setting the bit just above the width makes a zero input count to the width.
+                if (width != 64)
+                {
+                    assembler.MovabsRegImm64(scratch2, 1ul << (int)width);
+                    assembler.OrRegReg(destReg, scratch2);
+                }
+                assembler.TzcntRegReg(destReg, destReg);
+            }
+
             else
             {
                 assembler.MovabsRegImm64(scratch2, (ulong)ModuloReducer.GetMask(width));
@@ -326,29 +326,67 @@ namespace Mba.Simplifier.Jit
         }
 
         public void ShlRegCl(Register reg)
-            => ShiftRegCl(true, reg);
+            => ShiftRegCl(0x04, reg);
 
         public void ShrRegCl(Register reg)
-            => ShiftRegCl(false, reg);
+            => ShiftRegCl(0x05, reg);
 
-        public void ShiftRegCl(bool shl, Register reg)
+        public void SarRegCl(Register reg)
+            => ShiftRegCl(0x07, reg);
+
+        public void RolRegCl(Register reg)
+            => ShiftRegCl(0x00, reg);
+
+        public void RorRegCl(Register reg)
+            => ShiftRegCl(0x01, reg);
+
+        // `ext` is the opcode extension stored in modrm.reg: 0 for rol, 1 for ror, 4 for shl, 5 for shr, 7 for sar.
+        public void ShiftRegCl(int ext, Register reg)
         {
             byte rex = 0x48;
             if (IsExtended(reg)) rex |= 0x01;
 
             byte opcode = 0xD3;
-            var m1 = shl ? 0x04 : 0x05;
-            byte modrm = (byte)(0xC0 | (m1 << 3) | (GetRegisterCode(reg) & 0x07));
+            byte modrm = (byte)(0xC0 | (ext << 3) | (GetRegisterCode(reg) & 0x07));
             EmitBytes(rex, opcode, modrm);
         }
 
+        public void PopcntRegReg(Register reg1, Register reg2)
+            => BitCountRegReg(0xB8, reg1, reg2);
+
+        public void LzcntRegReg(Register reg1, Register reg2)
+            => BitCountRegReg(0xBD, reg1, reg2);
+
+        public void TzcntRegReg(Register reg1, Register reg2)
+            => BitCountRegReg(0xBC, reg1, reg2);
+
+        // `opcode` is the second byte of the F3 0F xx encoding: 0xB8 for popcnt, 0xBC for tzcnt, 0xBD for lzcnt.
+        public void BitCountRegReg(byte opcode, Register reg1, Register reg2)
+        {
+            byte rex = 0x48;
+            if (IsExtended(reg1)) rex |= 0x04;
+            if (IsExtended(reg2)) rex |= 0x01;
+
+            byte modrm = (byte)(0xC0 | ((GetRegisterCode(reg1) & 0x07) << 3) | (GetRegisterCode(reg2) & 0x07));
+            EmitBytes(0xF3, rex, 0x0F, opcode, modrm);
+        }
+
+        public void ShlRegImm8(Register reg, byte imm8)
+            => ShiftRegImm8(0x04, reg, imm8);
+
         public void ShrRegImm8(Register reg, byte imm8)
+            => ShiftRegImm8(0x05, reg, imm8);
+
+        public void SarRegImm8(Register reg, byte imm8)
+            => ShiftRegImm8(0x07, reg, imm8);
+
+        public void ShiftRegImm8(int ext, Register reg, byte imm8)
         {
             byte rex = 0x48;
             if (IsExtended(reg)) rex |= 0x01;
 
             byte opcode = 0xC1;
-            byte modrm = (byte)(0xC0 | (0x05 << 3) | (GetRegisterCode(reg) & 0x07));
+            byte modrm = (byte)(0xC0 | (ext << 3) | (GetRegisterCode(reg) & 0x07));
             EmitBytes(rex, opcode, modrm, imm8);
         }
 
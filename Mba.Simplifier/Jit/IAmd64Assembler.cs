@@ -48,8 +48,24 @@ namespace Mba.Simplifier.Jit
 
         public void ShrRegCl(Register reg1);
 
+        public void SarRegCl(Register reg1);
+
+        public void RolRegCl(Register reg1);
+
+        public void RorRegCl(Register reg1);
+
+        public void PopcntRegReg(Register reg1, Register reg2);
+
+        public void LzcntRegReg(Register reg1, Register reg2);
+
+        public void TzcntRegReg(Register reg1, Register reg2);
+
+        public void ShlRegImm8(Register reg, byte imm8);
+
         public void ShrRegImm8(Register reg, byte imm8);
 
+        public void SarRegImm8(Register reg, byte imm8);
+
         public void CmpRegReg(Register reg1, Register reg2);
 
         public void TestRegReg(Register reg1, Register reg2);
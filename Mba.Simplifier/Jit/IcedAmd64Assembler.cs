@@ -118,11 +118,51 @@ namespace Mba.Simplifier.Jit
             assembler.shr(Reg.Conv(reg1), cl);
         }
 
+        public void SarRegCl(Register reg1)
+        {
+            assembler.sar(Reg.Conv(reg1), cl);
+        }
+
+        public void RolRegCl(Register reg1)
+        {
+            assembler.rol(Reg.Conv(reg1), cl);
+        }
+
+        public void RorRegCl(Register reg1)
+        {
+            assembler.ror(Reg.Conv(reg1), cl);
+        }
+
+        public void PopcntRegReg(Register reg1, Register reg2)
+        {
+            assembler.popcnt(Reg.Conv(reg1), Reg.Conv(reg2));
+        }
+
+        public void LzcntRegReg(Register reg1, Register reg2)
+        {
+            assembler.lzcnt(Reg.Conv(reg1), Reg.Conv(reg2));
+        }
+
+        public void TzcntRegReg(Register reg1, Register reg2)
+        {
+            assembler.tzcnt(Reg.Conv(reg1), Reg.Conv(reg2));
+        }
+
+        public void ShlRegImm8(Register reg, byte imm8)
+        {
+            assembler.shl(Reg.Conv(reg), imm8);
+        }
+
         public void ShrRegImm8(Register reg, byte imm8)
         {
             assembler.shr(Reg.Conv(reg), imm8);
         }
 
+        public void SarRegImm8(Register reg, byte imm8)
+        {
+            assembler.sar(Reg.Conv(reg), imm8);
+        }
+
         public void CmpRegReg(Register reg1, Register reg2)
         {
             assembler.cmp(Reg.Conv(reg1), Reg.Conv(reg2));
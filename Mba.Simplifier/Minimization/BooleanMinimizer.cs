@@ -162,7 +162,7 @@ namespace Mba.Simplifier.Minimization
         private static AstIdx MinimizeAnf(AstCtx ctx, IReadOnlyList<AstIdx> variables, BooleanTruthTable truthTable)
         {
             // Minimize normally if zext/trunc nodes aren't present
-            bool containsExt = variables.Any(x => ctx.GetOpcode(x) == AstOp.Zext || ctx.GetOpcode(x) == AstOp.Trunc);
+            bool containsExt = variables.Any(x => ctx.GetOpcode(x) == AstOp.Zext || ctx.GetOpcode(x) == AstOp.Sext || ctx.GetOpcode(x) == AstOp.Trunc);
             if (!containsExt)
                 return AnfMinimizerV2.SimplifyBoolean(ctx, variables, BoolPoly.Create((byte)truthTable.NumVars, 1, truthTable.Arr));
 
@@ -171,7 +171,7 @@ namespace Mba.Simplifier.Minimization
             var tempVars = new List<AstIdx>();
             foreach (var v in variables)
             {
-                bool isExt = ctx.GetOpcode(v) == AstOp.Zext || ctx.GetOpcode(v) == AstOp.Trunc;
+                bool isExt = ctx.GetOpcode(v) == AstOp.Zext || ctx.GetOpcode(v) == AstOp.Sext || ctx.GetOpcode(v) == AstOp.Trunc;
                 if (!isExt)
                 {
                     tempVars.Add(v);
@@ -428,10 +428,51 @@ namespace Mba.Simplifier.Pipeline
 
                     return GetSubstitution(ctx.Lshr(src, by), substitutionMapping);
 
+                case AstOp.Shl:
+                case AstOp.Ashr:
+                case AstOp.Rotl:
+                case AstOp.Rotr:
+                    var shiftSrc = SimplifyViaRecursiveSiMBA(ctx.GetOp0(id));
+                    var shiftBy = SimplifyViaRecursiveSiMBA(ctx.GetOp1(id));
+                    var shift = ctx.Binop(opcode, shiftSrc, shiftBy);
+                    // If the shift amount folded to a constant, the shift may have been rewritten as a multiplication or constant.
+                    if (ctx.GetOpcode(shift) != opcode)
+                        return visitReplacement(shift, inBitwise, ref isSemiLinear);
+
+                    return GetSubstitution(shift, substitutionMapping);
+
+                case AstOp.Udiv:
+                case AstOp.Urem:
+                case AstOp.Sdiv:
+                case AstOp.Srem:
+                    var dividend = SimplifyViaRecursiveSiMBA(ctx.GetOp0(id));
+                    var divisor = SimplifyViaRecursiveSiMBA(ctx.GetOp1(id));
+                    var quotient = ctx.Binop(opcode, dividend, divisor);
+                    // Division by a constant may fold away entirely.
+                    if (ctx.GetOpcode(quotient) != opcode)
+                        return visitReplacement(quotient, inBitwise, ref isSemiLinear);
+
+                    return GetSubstitution(quotient, substitutionMapping);
+
+                case AstOp.Popcount:
+                case AstOp.Ctlz:
+                case AstOp.Cttz:
+                    var counted = SimplifyViaRecursiveSiMBA(ctx.GetOp0(id));
+                    var count = ctx.Unop(opcode, counted);
+                    // Counting the bits of a constant folds.
+                    if (ctx.GetOpcode(count) != opcode)
+                        return visitReplacement(count, inBitwise, ref isSemiLinear);
+
+                    return GetSubstitution(count, substitutionMapping);
+
                 case AstOp.Zext:
                     isSemiLinear = true;
                     return ctx.Zext(op0(true, ref isSemiLinear), ctx.GetWidth(id));
 
+                case AstOp.Sext:
+                    isSemiLinear = true;
+                    return ctx.Sext(op0(true, ref isSemiLinear), ctx.GetWidth(id));
+
                 case AstOp.Trunc:
                     isSemiLinear = true;
                     var child = ctx.GetOp0(id);
@@ -1278,6 +1319,7 @@ namespace Mba.Simplifier.Pipeline
                 case AstOp.Add:
                 case AstOp.Mul:
                 case AstOp.Pow:
+                case AstOp.Shl:
                     // If we have addition/multiplication, we only care about bits at and below the highest set bit.
                     var demandedWidth = 64 - (uint)BitOperations.LeadingZeroCount(currDemanded);
                     currDemanded = ModuloReducer.GetMask(demandedWidth);
@@ -1324,6 +1366,23 @@ namespace Mba.Simplifier.Pipeline
                     op0(currDemanded, ref totalDemanded);
                     op1(currDemanded, ref totalDemanded);
                     break;
+                // Any bit of the result may depend on any bit of the operands.
+                case AstOp.Ashr:
+                case AstOp.Udiv:
+                case AstOp.Urem:
+                case AstOp.Sdiv:
+                case AstOp.Srem:
+                case AstOp.Rotl:
+                case AstOp.Rotr:
+                    var fullMask = ModuloReducer.GetMask(ctx.GetWidth(idx));
+                    op0(fullMask, ref totalDemanded);
+                    op1(fullMask, ref totalDemanded);
+                    break;
+                case AstOp.Popcount:
+                case AstOp.Ctlz:
+                case AstOp.Cttz:
+                    op0(ModuloReducer.GetMask(ctx.GetWidth(idx)), ref totalDemanded);
+                    break;
                 // TODO: Treat negation as x^-1, then use XOR transfer function
                 case AstOp.Neg:
                     op0(currDemanded, ref totalDemanded);
@@ -1335,6 +1394,17 @@ namespace Mba.Simplifier.Pipeline
                 case AstOp.Zext:
                     op0(currDemanded & ModuloReducer.GetMask(ctx.GetWidth(ctx.GetOp0(idx))), ref totalDemanded);
                     break;
+                case AstOp.Sext:
+                    {
+                        // The extended bits are copies of the sign bit.
+                        var fromWidth = ctx.GetWidth(ctx.GetOp0(idx));
+                        var srcMask = ModuloReducer.GetMask(fromWidth);
+                        var srcDemanded = currDemanded & srcMask;
+                        if ((currDemanded & ~srcMask) != 0)
+                            srcDemanded |= 1ul << (fromWidth - 1);
+                        op0(srcDemanded, ref totalDemanded);
+                        break;
+                    }
                 case AstOp.ICmp:
                     op0(currDemanded, ref totalDemanded);
                     op1(currDemanded, ref totalDemanded);
@@ -2173,7 +2243,19 @@ namespace Mba.Simplifier.Pipeline
                 AstOp.Xor => ctx.Xor(op0(), op1()),
                 AstOp.Neg => ctx.Neg(op0()),
                 AstOp.Lshr => ctx.Lshr(op0(), op1()),
+                AstOp.Shl => ctx.Shl(op0(), op1()),
+                AstOp.Ashr => ctx.Ashr(op0(), op1()),
+                AstOp.Udiv => ctx.Udiv(op0(), op1()),
+                AstOp.Urem => ctx.Urem(op0(), op1()),
+                AstOp.Sdiv => ctx.Sdiv(op0(), op1()),
+                AstOp.Srem => ctx.Srem(op0(), op1()),
+                AstOp.Rotl => ctx.Rotl(op0(), op1()),
+                AstOp.Rotr => ctx.Rotr(op0(), op1()),
+                AstOp.Popcount => ctx.Popcount(op0()),
+                AstOp.Ctlz => ctx.Ctlz(op0()),
+                AstOp.Cttz => ctx.Cttz(op0()),
                 AstOp.Zext => ctx.Zext(op0(), width),
+                AstOp.Sext => ctx.Sext(op0(), width),
                 AstOp.Trunc => ctx.Trunc(op0(), width),
                 AstOp.Constant => id,
                 AstOp.Symbol => id,
@@ -3,6 +3,7 @@ using Mba.Utility;
 using System;
 using System.Collections.Generic;
 using System.Linq;
+using System.Numerics;
 using System.Text;
 using System.Threading.Tasks;
 
@@ -25,14 +26,114 @@ namespace Mba.Simplifier.Utility
                 AstOp.Xor => op0() ^ op1(),
                 AstOp.Neg => ~op0(),
                 AstOp.Zext => op0(),
+                AstOp.Sext => (ulong)SignExtend(op0(), ctx.GetWidth(ctx.GetOp0(idx))),
                 AstOp.Trunc => op0(),
                 AstOp.Lshr => op0() >> (ushort)op1(),
+                AstOp.Shl => Shl(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Ashr => Ashr(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Udiv => Udiv(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Urem => Urem(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Sdiv => Sdiv(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Srem => Srem(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Rotl => Rotl(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Rotr => Rotr(op0(), op1(), ctx.GetWidth(idx)),
+                AstOp.Popcount => (ulong)BitOperations.PopCount(op0()),
+                AstOp.Ctlz => Ctlz(op0(), ctx.GetWidth(idx)),
+                AstOp.Cttz => Cttz(op0(), ctx.GetWidth(idx)),
                 AstOp.Constant => ctx.GetConstantValue(idx),
                 AstOp.Symbol => valueMap[idx],
                 _ => throw new NotImplementedException()
             };
         }
 
+        // Shifting by the bit width or more yields zero.
+        public static ulong Shl(ulong a, ulong shift, byte width)
+        {
+            if (shift >= width)
+                return 0;
+            return a << (int)shift;
+        }
+
+        // Shifting by the bit width or more fills the result with the sign bit.
+        public static ulong Ashr(ulong a, ulong shift, byte width)
+        {
+            return (ulong)(SignExtend(a, width) >> (int)Math.Min(shift, 63));
+        }
+
+        private static long SignExtend(ulong a, byte width)
+        {
+            int unused = 64 - width;
+            return ((long)(a << unused)) >> unused;
+        }
+
+        // Division by zero yields all ones.
+        public static ulong Udiv(ulong a, ulong b, byte width)
+        {
+            var mask = ModuloReducer.GetMask(width);
+            if ((b & mask) == 0)
+                return mask;
+            return (a & mask) / (b & mask);
+        }
+
+        // The remainder of division by zero is the dividend.
+        public static ulong Urem(ulong a, ulong b, byte width)
+        {
+            var mask = ModuloReducer.GetMask(width);
+            if ((b & mask) == 0)
+                return a & mask;
+            return (a & mask) % (b & mask);
+        }
+
+        // Division by zero yields 1 for negative dividends and -1 otherwise. MIN / -1 wraps to MIN.
+        public static ulong Sdiv(ulong a, ulong b, byte width)
+        {
+            long sa = SignExtend(a, width);
+            long sb = SignExtend(b, width);
+            if (sb == 0)
+                return sa < 0 ? 1 : ModuloReducer.GetMask(width);
+            if (sb == -1)
+                return (ulong)(-sa);
+            return (ulong)(sa / sb);
+        }
+
+        // The remainder takes the sign of the dividend. x % 0 is x, and MIN % -1 is zero.
+        public static ulong Srem(ulong a, ulong b, byte width)
+        {
+            long sa = SignExtend(a, width);
+            long sb = SignExtend(b, width);
+            if (sb == 0)
+                return a & ModuloReducer.GetMask(width);
+            if (sb == -1)
+                return 0;
+            return (ulong)(sa % sb);
+        }
+
+        // Rotate amounts are reduced modulo the bit width.
+        public static ulong Rotl(ulong a, ulong amount, byte width)
+        {
+            var mask = ModuloReducer.GetMask(width);
+            a &= mask;
+            amount %= width;
+            if (amount == 0)
+                return a;
+            return ((a << (int)amount) | (a >> (int)(width - amount))) & mask;
+        }
+
+        public static ulong Rotr(ulong a, ulong amount, byte width)
+            => Rotl(a, width - (amount % width), width);
+
+        // Counting the zeroes of zero yields the bit width.
+        public static ulong Ctlz(ulong a, byte width)
+            => (ulong)(BitOperations.LeadingZeroCount(a & ModuloReducer.GetMask(width)) - (64 - width));
+
+        public static ulong Cttz(ulong a, byte width)
+        {
+            a &= ModuloReducer.GetMask(width);
+            if (a == 0)
+                return width;
+            return (ulong)BitOperations.TrailingZeroCount(a);
+        }
+
         public static ulong Pow(ulong bbase, ulong exponent)
         {
             ulong result = 1;
@@ -55,6 +55,10 @@ namespace Mba.Simplifier.Utility
                 AstOp.Neg => ctx.Neg(op0()),
                 // For lshrs we can't truncate any before, because the high bits may now effect the low bits!
                 AstOp.Lshr => ctx.Trunc(node, (byte)newWidth),
+                // Same for sign extensions, where the sign bit may now affect the high bits.
+                AstOp.Sext => ctx.Trunc(node, (byte)newWidth),
+                // Bit counts depend on every bit of their input.
+                AstOp.Popcount or AstOp.Ctlz or AstOp.Cttz => ctx.Trunc(node, (byte)newWidth),
                 AstOp.Constant => ctx.Constant(ctx.GetConstantValue(node) & moduloMask, newWidth),
                 AstOp.Symbol => ResizeValue(ctx, node, (byte)newWidth, moduloMask),
                 _ => throw new InvalidOperationException($"Cannot change width of opcode {opcode}"),
@@ -41,7 +41,9 @@ namespace Mba.Simplifier.Utility
                 sb.AppendLine($"let t{vNum} = {ctx.GetConstantValue(idx)}");
             else if (opc == AstOp.Neg)
                 sb.AppendLine($"let t{vNum} = ctx.arena.neg({op0()})");
-            else if (opc == AstOp.Zext || opc == AstOp.Trunc)
+            else if (opc == AstOp.Popcount || opc == AstOp.Ctlz || opc == AstOp.Cttz)
+                sb.AppendLine($"let t{vNum} = ctx.arena.{GetOperatorName(opc)}({op0()})");
+            else if (opc == AstOp.Zext || opc == AstOp.Sext || opc == AstOp.Trunc)
             {
                 sb.AppendLine($"i{width} t{vNum} = {GetOperatorName(opc)} i{ctx.GetWidth(ctx.GetOp0(idx))} {op0()} to i{width}");
             }
@@ -77,9 +79,21 @@ namespace Mba.Simplifier.Utility
                 AstOp.Xor => 2,
                 AstOp.Neg => 1,
                 AstOp.Lshr => 2,
+                AstOp.Shl => 2,
+                AstOp.Ashr => 2,
+                AstOp.Udiv => 2,
+                AstOp.Urem => 2,
+                AstOp.Sdiv => 2,
+                AstOp.Srem => 2,
+                AstOp.Rotl => 2,
+                AstOp.Rotr => 2,
+                AstOp.Popcount => 1,
+                AstOp.Ctlz => 1,
+                AstOp.Cttz => 1,
                 AstOp.Constant => 0,
                 AstOp.Symbol => 0,
                 AstOp.Zext => 1,
+                AstOp.Sext => 1,
                 AstOp.Trunc => 1,
             };
         }
@@ -96,7 +110,19 @@ namespace Mba.Simplifier.Utility
                 AstOp.Xor => "^",
                 AstOp.Neg => "~",
                 AstOp.Lshr => ">>",
+                AstOp.Shl => "<<",
+                AstOp.Ashr => ">>s",
+                AstOp.Udiv => "/",
+                AstOp.Urem => "%",
+                AstOp.Sdiv => "/s",
+                AstOp.Srem => "%s",
+                AstOp.Rotl => "<<<",
+                AstOp.Rotr => ">>>",
+                AstOp.Popcount => "popcount",
+                AstOp.Ctlz => "ctlz",
+                AstOp.Cttz => "cttz",
                 AstOp.Zext => "zext",
+                AstOp.Sext => "sext",
                 AstOp.Trunc => "trunc",
                 _ => throw new InvalidOperationException(),
             };
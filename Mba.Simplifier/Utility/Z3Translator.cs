@@ -45,10 +45,22 @@ namespace Mba.Simplifier.Utility
                 AstOp.Or => z3Ctx.MkBVOR(op0(), op1()),
                 AstOp.Xor => z3Ctx.MkBVXOR(op0(), op1()),
                 AstOp.Lshr => z3Ctx.MkBVLSHR(op0(), op1()),
+                AstOp.Shl => z3Ctx.MkBVSHL(op0(), op1()),
+                AstOp.Ashr => z3Ctx.MkBVASHR(op0(), op1()),
+                AstOp.Udiv => z3Ctx.MkBVUDiv(op0(), op1()),
+                AstOp.Urem => z3Ctx.MkBVURem(op0(), op1()),
+                AstOp.Sdiv => z3Ctx.MkBVSDiv(op0(), op1()),
+                AstOp.Srem => z3Ctx.MkBVSRem(op0(), op1()),
+                AstOp.Rotl => z3Ctx.MkBVRotateLeft(op0(), z3Ctx.MkBVURem(op1(), z3Ctx.MkBV(ctx.GetWidth(idx), ctx.GetWidth(idx)))),
+                AstOp.Rotr => z3Ctx.MkBVRotateRight(op0(), z3Ctx.MkBVURem(op1(), z3Ctx.MkBV(ctx.GetWidth(idx), ctx.GetWidth(idx)))),
+                AstOp.Popcount => Popcount(op0(), ctx.GetWidth(idx)),
+                AstOp.Ctlz => Ctlz(op0(), ctx.GetWidth(idx)),
+                AstOp.Cttz => Cttz(op0(), ctx.GetWidth(idx)),
                 AstOp.Neg => z3Ctx.MkBVNot(op0()),
                 AstOp.Constant => z3Ctx.MkBV(ctx.GetConstantValue(idx), ctx.GetWidth(idx)),
                 AstOp.Symbol => z3Ctx.MkBVConst(ctx.GetSymbolName(idx), ctx.GetWidth(idx)),
                 AstOp.Zext => z3Ctx.MkZeroExt((uint)ctx.GetWidth(idx) - ctx.GetWidth(ctx.GetOp0(idx)), op0()),
+                AstOp.Sext => z3Ctx.MkSignExt((uint)ctx.GetWidth(idx) - ctx.GetWidth(ctx.GetOp0(idx)), op0()),
                 AstOp.Trunc => z3Ctx.MkExtract((uint)ctx.GetWidth(idx) - 1, 0, op0()),
                 _ => throw new InvalidOperationException($"Cannot translate opcode {opcode} to z3!")
             };
@@ -57,6 +69,42 @@ namespace Mba.Simplifier.Utility
             return z3Ast;
         }
 
+        // Z3 has no bit counting operators, so these are expanded bit by bit.
+        private Expr Popcount(BitVecExpr x, uint width)
+        {
+            BitVecExpr sum = z3Ctx.MkBV(0, width);
+            for (uint i = 0; i < width; i++)
+                sum = z3Ctx.MkBVAdd(sum, z3Ctx.MkZeroExt(width - 1, z3Ctx.MkExtract(i, i, x)));
+            return sum;
+        }
+
+        // Counting the zeroes of zero yields the bit width.
+        private Expr Ctlz(BitVecExpr x, uint width)
+        {
+            // Walk upwards from the lowest bit, so that the highest set bit takes precedence.
+            BitVecExpr count = z3Ctx.MkBV(width, width);
+            for (uint i = 0; i < width; i++)
+            {
+                var isSet = z3Ctx.MkEq(z3Ctx.MkExtract(i, i, x), z3Ctx.MkBV(1, 1));
+                count = (BitVecExpr)z3Ctx.MkITE(isSet, z3Ctx.MkBV(width - 1 - i, width), count);
+            }
+
+            return count;
+        }
+
+        private Expr Cttz(BitVecExpr x, uint width)
+        {
+            // Walk downwards from the highest bit, so that the lowest set bit takes precedence.
+            BitVecExpr count = z3Ctx.MkBV(width, width);
+            for (uint i = width; i-- > 0;)
+            {
+                var isSet = z3Ctx.MkEq(z3Ctx.MkExtract(i, i, x), z3Ctx.MkBV(1, 1));
+                count = (BitVecExpr)z3Ctx.MkITE(isSet, z3Ctx.MkBV(i, width), count);
+            }
+
+            return count;
+        }
+
         private Expr Power(AstIdx idx)
         {
             var lhs = ctx.GetOp0(idx);